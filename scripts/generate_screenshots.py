@@ -17,10 +17,12 @@ from pathlib import Path
 from typing import NamedTuple
 from unittest.mock import patch
 
+from textual.pilot import Pilot
 from textual.widgets import OptionList
 
 from procclean.core import HIGH_MEMORY_THRESHOLD_MB, ProcessInfo
 from procclean.tui.app import ProcessCleanerApp, ViewType
+from procclean.tui.snapshot import render_to_svg
 
 log = logging.getLogger(__name__)
 
@@ -218,12 +220,15 @@ SCREENSHOT_CONFIGS: list[ScreenshotConfig] = [
 # -----------------------------------------------------------------------------
 
 
-def mock_get_process_list(min_memory_mb: float = 0.0) -> list[ProcessInfo]:
+def mock_get_process_list(
+    min_memory_mb: float = 0.0, source: object = None
+) -> list[ProcessInfo]:
     """Return a filtered list of mock processes.
 
     Args:
         min_memory_mb: Minimum RSS memory (in MB) required for a process to be
             included.
+        source: Ignored; accepted for signature compatibility.
 
     Returns:
         A list of mock ProcessInfo objects whose rss_mb >= min_memory_mb.
@@ -258,38 +263,36 @@ async def take_screenshot(
     Returns:
         Path to the generated screenshot.
     """
-    with (
-        patch("procclean.tui.app.get_process_list", mock_get_process_list),
-        patch("procclean.tui.app.get_memory_summary", mock_get_memory_summary),
-    ):
-        app = ProcessCleanerApp()
-
-        async with app.run_test(size=(120, 30)) as pilot:
-            # Map view names to sidebar option indices
-            view_index = {"all": 0, "orphans": 1, "groups": 2, "high-mem": 3}
+    # Map view names to sidebar option indices
+    view_index = {"all": 0, "orphans": 1, "groups": 2, "high-mem": 3}
 
-            # Set view and update sidebar selection
-            if config.view != "all":
-                app.current_view = config.view
-                app.update_table()
+    async def setup(pilot: Pilot) -> None:
+        app = pilot.app
+        assert isinstance(app, ProcessCleanerApp)
 
-            # Update sidebar highlight to match view
-            sidebar = app.query_one("#view-selector", OptionList)
-            sidebar.highlighted = view_index.get(config.view, 0)
+        # Set view and update sidebar selection
+        if config.view != "all":
+            app.current_view = config.view
+            app.update_table()
 
-            # Apply selections
-            if config.selected_pids:
-                app.selected_pids = set(config.selected_pids)
-                app.update_table()
+        # Update sidebar highlight to match view
+        sidebar = app.query_one("#view-selector", OptionList)
+        sidebar.highlighted = view_index.get(config.view, 0)
 
-            # Wait for render
-            await pilot.pause()
+        # Apply selections
+        if config.selected_pids:
+            app.selected_pids = set(config.selected_pids)
+            app.update_table()
 
-            # Save screenshot
-            output_path = output_dir / f"{config.filename}.svg"
-            app.save_screenshot(filename=f"{config.filename}.svg", path=str(output_dir))
+    with (
+        patch("procclean.tui.app.get_process_list", mock_get_process_list),
+        patch("procclean.tui.app.get_memory_summary", mock_get_memory_summary),
+    ):
+        svg = await render_to_svg(ProcessCleanerApp(), setup=setup)
 
-            return output_path
+    output_path = output_dir / f"{config.filename}.svg"
+    output_path.write_text(svg, encoding="utf-8")
+    return output_path
 
 
 def optimize_svgs(output_dir: Path) -> bool:
@@ -1,11 +1,34 @@
 """Process cleanup TUI application."""
 
 from importlib.metadata import version
+from typing import TYPE_CHECKING, Any
 
 __version__ = version("procclean")
 
-# Re-export main entry point and core types
-from procclean.__main__ import main
+# Re-export core types; the core package has no UI dependencies
 from procclean.core import ProcessInfo
 
+if TYPE_CHECKING:
+    from procclean.__main__ import main
+
 __all__ = ["ProcessInfo", "__version__", "main"]
+
+
+def __getattr__(name: str) -> Any:  # noqa: ANN401
+    """Lazily import the entry point so ``procclean.core`` stays UI-free.
+
+    Importing ``main`` pulls in the CLI (rich, tabulate) and TUI (textual);
+    library users that only need ``procclean.core`` never pay for that.
+
+    Returns:
+        The requested attribute.
+
+    Raises:
+        AttributeError: If the attribute does not exist.
+    """
+    if name == "main":
+        from procclean.__main__ import main  # noqa: PLC0415
+
+        return main
+    msg = f"module {__name__!r} has no attribute {name!r}"
+    raise AttributeError(msg)
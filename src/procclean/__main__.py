@@ -1,18 +1,31 @@
 """Entry point for procclean - runs as python -m procclean or via console script."""
 
-from .cli import run_cli
-from .tui import ProcessCleanerApp
+from .extras import missing_extra
+
+try:
+    from .cli import run_cli
+except ModuleNotFoundError as e:  # Installed without the cli extra
+    raise missing_extra(e) from e
 
 
 def main() -> None:
     """Dispatch to CLI or run TUI.
 
+    The TUI (and textual) is only imported when no subcommand is given, so
+    CLI invocations start faster.
+
     Raises:
-        SystemExit: When CLI command returns non-zero exit code.
+        SystemExit: When CLI command returns non-zero exit code, or the TUI
+            is not installed.
     """
     result = run_cli()
     if result == -1:
         # No subcommand - run TUI
+        try:
+            from .tui import ProcessCleanerApp  # noqa: PLC0415
+        except ModuleNotFoundError as e:
+            raise missing_extra(e) from e
+
         ProcessCleanerApp().run()
     else:
         raise SystemExit(result)
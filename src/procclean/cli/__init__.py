@@ -5,11 +5,38 @@ from .commands import (
     _confirm_kill,
     _do_preview,
     _get_kill_targets,
+    cmd_budgets,
+    cmd_clean,
+    cmd_columns,
+    cmd_config_edit,
+    cmd_config_show,
+    cmd_config_validate,
+    cmd_cpu,
+    cmd_daemon,
+    cmd_doctor,
+    cmd_emergency,
+    cmd_export,
+    cmd_fmt,
     cmd_groups,
+    cmd_history,
+    cmd_hook,
+    cmd_jobs,
     cmd_kill,
+    cmd_leftovers,
     cmd_list,
+    cmd_mem_detail,
     cmd_memory,
+    cmd_polkit,
+    cmd_quota,
+    cmd_renice,
+    cmd_restart,
+    cmd_run,
+    cmd_status,
+    cmd_verify,
+    cmd_watch,
+    cmd_wine,
     get_filtered_processes,
+    get_source,
 )
 from .parser import create_parser, run_cli
 
@@ -17,11 +44,38 @@ __all__ = [
     "_confirm_kill",
     "_do_preview",
     "_get_kill_targets",
+    "cmd_budgets",
+    "cmd_clean",
+    "cmd_columns",
+    "cmd_config_edit",
+    "cmd_config_show",
+    "cmd_config_validate",
+    "cmd_cpu",
+    "cmd_daemon",
+    "cmd_doctor",
+    "cmd_emergency",
+    "cmd_export",
+    "cmd_fmt",
     "cmd_groups",
+    "cmd_history",
+    "cmd_hook",
+    "cmd_jobs",
     "cmd_kill",
+    "cmd_leftovers",
     "cmd_list",
+    "cmd_mem_detail",
     "cmd_memory",
+    "cmd_polkit",
+    "cmd_quota",
+    "cmd_renice",
+    "cmd_restart",
+    "cmd_run",
+    "cmd_status",
+    "cmd_verify",
+    "cmd_watch",
+    "cmd_wine",
     "create_parser",
     "get_filtered_processes",
+    "get_source",
     "run_cli",
 ]
@@ -2,33 +2,306 @@
 
 import argparse
 import json
+import os
+import platform
+import shlex
+import shutil
+import signal
+import subprocess
 import sys
+import tempfile
+import time
+from collections.abc import Callable
+from dataclasses import asdict
+from datetime import UTC, datetime
+from importlib.metadata import version
 from pathlib import Path
 
 from rich import print  # pylint: disable=redefined-builtin
 
 from procclean.core import (
+    ALL_FIELDS,
+    ALL_USERS,
+    BUILTIN_SOURCE,
+    DEFAULT_SOURCE,
+    EMERGENCY_COOLDOWN_SECONDS,
+    ESCALATE_TIMEOUT_SECONDS,
+    FILTER_CMD_JOBS,
+    FILTERS,
+    MEMORY_SETTLE_SECONDS,
     PREVIEW_LIMIT,
+    QUOTA_NICE,
+    RENICE_NICE,
+    STATUS_CRITICAL_PERCENT,
+    STATUS_WARNING_PERCENT,
+    VIA_MANAGER,
+    Capability,
+    ErrorCode,
+    FieldMask,
+    FilterSpec,
+    MatchMode,
+    MetricsExporter,
+    NameMatcher,
+    ProccleanError,
+    ProcessCollector,
+    ProcessInfo,
+    ProcessSource,
+    Quotas,
+    ReclaimReport,
+    RestartMethod,
+    RestartPlan,
+    SimulatedSource,
+    SnapshotDaemon,
+    SystemInfo,
+    TimedSource,
+    WineGroup,
+    active_timings,
+    attach_deleted_libs,
+    attach_env,
+    attach_inhibitors,
+    attach_media,
+    attach_packages,
+    attach_pods,
+    attach_ports,
+    attach_repos,
+    attach_runtime_envs,
+    attach_smaps,
+    attach_vms,
+    audit_log_path,
+    cap_processes,
+    capability_report,
+    check_budgets,
+    check_quotas,
+    clean_targets,
+    command_templates,
+    config_path,
+    config_problems,
+    core_usage,
+    daemon_process_list,
+    data_loss_patterns,
+    data_loss_targets,
+    describe_oom,
+    duplicate_victims,
+    effective_settings,
+    emergency_targets,
+    environment_checks,
+    exclude_vms,
+    filter_by_age,
+    filter_by_cmdline,
+    filter_by_command,
+    filter_by_cpu,
     filter_by_cwd,
-    filter_high_memory,
+    filter_by_port,
+    filter_by_repo,
+    filter_by_user,
+    filter_history,
     filter_killable,
-    filter_orphans,
+    find_duplicates,
+    find_leftovers,
+    find_plugins,
     find_similar_processes,
+    find_wine_groups,
+    format_uptime,
     get_memory_summary,
     get_process_list,
+    get_session_jobs,
+    get_system_info,
+    has_package_manager,
+    helper_path,
+    ionice_process,
+    is_kill_signal,
+    kill_escalating,
+    kill_groups,
+    kill_process,
     kill_processes,
+    leftover_holders,
+    load_budgets,
+    load_config,
+    load_defaults,
+    load_emergency_policy,
+    load_kill_hooks,
+    load_manifest,
+    load_presets,
+    load_quotas,
+    measure_reclaim,
+    natural_sort_enabled,
+    no_confirm_match,
+    no_confirm_patterns,
+    oom_status,
+    parse_env_patterns,
+    parse_fault_plan,
+    plan_restarts,
+    policy_xml,
+    priority_note,
+    protected_names,
+    read_history,
+    read_pressure,
+    redact_process,
+    refuse_data_loss_kill,
+    remove_leftover,
+    renice_process,
+    resolve_supervisors,
+    restart_processes,
+    row_cap,
+    run_every,
+    run_tracked,
+    save_config,
+    scoped_source,
+    scoped_source_from_env,
+    set_setting,
+    shutdown_prefix,
+    simulated_source_from_env,
+    socket_path,
     sort_processes,
+    stop_via_managers,
+    top_by_pss,
+    unit_processes,
+    verify_manifest,
 )
-from procclean.formatters import format_output
+from procclean.extras import missing_extra
+from procclean.formatters import (
+    COLUMNS,
+    DEFAULT_COLUMNS,
+    ListDesign,
+    columns_fields,
+    default_columns,
+    format_header,
+    format_json_line,
+    format_jsonl,
+    format_output,
+    get_layouts,
+    highlight_styles,
+    layout_columns,
+    parse_columns,
+    use_color,
+)
+from procclean.i18n import t
 
+from .hooks import render_hook
+from .sinks import output_sink, terminal
 
-def cmd_list(args: argparse.Namespace) -> int:
-    """List processes command.
+CLEAR_SCREEN = "\x1b[H\x1b[2J"  # Cursor home, then clear, as ``clear`` does
+# Table of memory --per-process
+MEMORY_COLUMNS = (
+    "pid",
+    "name",
+    "rss_mb",
+    "pss_mb",
+    "uss_mb",
+    "shared_mb",
+    "swap_mb",
+)
+FMT_PREVIEW_ROWS = 15  # Largest processes previewed by `fmt --interactive`
+# Loaders of config settings that raise for malformed values, checked by doctor
+CONFIG_VALIDATORS: tuple[Callable[[], object], ...] = (
+    load_presets,
+    load_defaults,
+    protected_names,
+    natural_sort_enabled,
+    row_cap,
+    no_confirm_patterns,
+    data_loss_patterns,
+    load_kill_hooks,
+    command_templates,
+    load_quotas,
+    load_budgets,
+    load_emergency_policy,
+    audit_log_path,
+    highlight_styles,
+    get_layouts,
+    default_columns,
+)
+
+
+def get_source(args: argparse.Namespace) -> ProcessSource | None:
+    """Resolve the process backend from ``--scope`` and ``--simulate``/``--faults``.
+
+    Falls back to ``PROCCLEAN_SCOPE``/``PROCCLEAN_SIMULATE`` so the flags also
+    reach the TUI. A simulation wraps the scoped source, never the reverse;
+    with ``--timing`` the reads of the scoped source are timed.
 
     Returns:
-        int: Exit code (0 on success).
+        ProcessSource | None: A scoped and/or simulated source, or None for the
+        unrestricted live system.
+    """
+    scope = getattr(args, "scope", None)
+    base = scoped_source(scope) if scope else scoped_source_from_env()
+    if (timings := active_timings()) is not None:
+        base = TimedSource(base or DEFAULT_SOURCE, timings)
+    faults = getattr(args, "faults", None)
+    if faults is None and not getattr(args, "simulate", False):
+        return simulated_source_from_env(base) or base
+    return SimulatedSource(base or DEFAULT_SOURCE, parse_fault_plan(faults or ""))
+
+
+def _table_width(columns: list[str] | None) -> int | None:
+    """Get the width for a responsive table layout.
+
+    Explicit ``--columns``/``--layout`` and non-TTY output are left untouched
+    so scripts always get every column they asked for.
+
+    Returns:
+        int | None: Terminal width, or None when no layout should be applied.
+    """
+    if columns or not sys.stdout.isatty():
+        return None
+    return shutil.get_terminal_size().columns
+
+
+def _print_report(report: str, color: bool) -> None:
+    """Print a formatted report.
+
+    Colored tables bypass rich, which would count the ANSI escapes as text
+    and wrap the lines.
+    """
+    if color:
+        sys.stdout.write(report + "\n")
+    else:
+        print(report)
+
+
+def _report_header(args: argparse.Namespace) -> SystemInfo | None:
+    """Read the system information for ``--header``.
+
+    Returns:
+        SystemInfo | None: The information, or None without ``--header``.
+    """
+    return get_system_info() if getattr(args, "header", False) else None
+
+
+def _list_fields(args: argparse.Namespace, columns: list[str] | None) -> FieldMask:
+    """Get the optional fields ``list`` prints.
+
+    JSON and CSV dump every field; tables only show their columns.
+
+    Returns:
+        FieldMask: Fields to collect, on top of what filters and sorting need.
+    """
+    if args.format in {"json", "jsonl", "csv"}:
+        return ALL_FIELDS
+    return columns_fields(columns or DEFAULT_COLUMNS)
+
+
+def _collect_list(
+    args: argparse.Namespace,
+    source: ProcessSource | None,
+    fields: FieldMask = ALL_FIELDS,
+    collector: ProcessCollector | None = None,
+) -> list[ProcessInfo]:
+    """Fetch, filter, sort and annotate processes for ``list``.
+
+    Annotations outside ``fields`` are skipped.
+
+    Returns:
+        list[ProcessInfo]: Processes to print.
     """
-    procs = get_filtered_processes(args)
+    procs = get_filtered_processes(
+        args,
+        source,
+        fields,
+        use_daemon=not getattr(args, "no_daemon", False),
+        collector=collector,
+    )
 
     # Apply sorting
     reverse = not args.ascending
@@ -38,89 +311,520 @@ def cmd_list(args: argparse.Namespace) -> int:
     if args.limit:
         procs = procs[: args.limit]
 
+    # Capture allowlisted environment variables (opt-in)
+    if args.include_env is not None:
+        patterns = parse_env_patterns(args.include_env)
+        attach_env(procs, patterns, redact=not args.no_redact, source=source)
+
+    if getattr(args, "attribute", False):
+        _attribute(procs, source)
+    if FieldMask.POD in fields:
+        attach_pods(procs, source)
+    if FieldMask.VM in fields:
+        attach_vms(procs)
+    if FieldMask.MEDIA in fields:
+        attach_media(procs, source)
+    if FieldMask.INHIBITOR in fields:
+        attach_inhibitors(procs)
+    if FieldMask.DELETED_LIBS in fields:
+        attach_deleted_libs(procs, source)
+    if FieldMask.REPO in fields:
+        attach_repos(procs)
+    if FieldMask.RUNTIME_ENV in fields:
+        attach_runtime_envs(procs, source)
+    if FieldMask.PORTS in fields:
+        attach_ports(procs, source)
+    if FieldMask.SMAPS in fields:
+        attach_smaps(procs, source)
+    return procs
+
+
+def cmd_list(args: argparse.Namespace) -> int:
+    """List processes command.
+
+    Returns:
+        int: Exit code (0 on success).
+
+    Raises:
+        ProccleanError: With ``INVALID_ARGUMENT`` for ``--count`` without
+            ``--every`` or ``--watch``, or for both of those.
+    """
+    if getattr(args, "list_filters", False):
+        return _list_filters(args.format == "json")
+    every, watch = getattr(args, "every", None), getattr(args, "watch", None)
+    if every and watch:
+        raise ProccleanError(
+            ErrorCode.INVALID_ARGUMENT, "--every and --watch are mutually exclusive"
+        )
+    if every:
+        return _list_every(args)
+    if watch:
+        return _list_watch(args)
+    if getattr(args, "count", None) is not None:
+        raise ProccleanError(
+            ErrorCode.INVALID_ARGUMENT, "--count requires --every or --watch"
+        )
+    source = get_source(args)
+
     # Parse columns
-    columns = args.columns.split(",") if args.columns else None
+    columns = parse_columns(args.columns) or layout_columns(args.layout)
+    procs = _collect_list(args, source, _list_fields(args, columns))
+    report, color = _list_report(args, procs, columns)
+    _print_report(report, color)
+    return 0
+
+
+def _list_report(
+    args: argparse.Namespace, procs: list[ProcessInfo], columns: list[str] | None
+) -> tuple[str, bool]:
+    """Format ``list`` output, capping tables at the row cap.
+
+    Returns:
+        tuple[str, bool]: The report and whether it is colored.
+    """
+    if args.format in {"table", "md"} and not args.no_cap:
+        total = len(procs)
+        procs, dropped = cap_processes(procs, row_cap())
+        if dropped:
+            print(t("cli-truncated", shown=len(procs), total=total), file=sys.stderr)
+
+    color = args.format == "table" and use_color(getattr(args, "color", "auto"))
+    report = format_output(
+        procs,
+        args.format,
+        columns=columns,
+        width=_table_width(columns),
+        redact=not args.no_redact,
+        color=color,
+        header=_report_header(args),
+    )
+    return report, color
+
+
+def _list_filters(as_json: bool) -> int:
+    """Print the registered filter presets and their parameters.
+
+    Returns:
+        int: Exit code (always 0).
+    """
+    if as_json:
+        # Plain write: rich would wrap long descriptions
+        data = [spec.to_dict() for spec in FILTERS]
+        sys.stdout.write(json.dumps(data, indent=2) + "\n")
+        return 0
+    width = max(len(name) for name in FILTERS.names())
+    for spec in FILTERS:
+        print(f"{spec.name:<{width}}  {spec.description}")
+        for param in spec.params:
+            default = f"{param.default:g}{param.unit}"
+            print(f"{'':<{width}}    {param.option} {param.metavar} ({default})")
+    return 0
+
 
-    print(format_output(procs, args.format, columns=columns))
+def _list_every(args: argparse.Namespace) -> int:
+    """Append a timestamped ``list`` snapshot every ``--every`` seconds.
+
+    JSON snapshots are written as one JSON Lines record each; other formats
+    get a ``# <timestamp>`` line above the report. The output target is
+    reopened for every snapshot, so strftime codes in ``--output-file``
+    rotate the log.
+
+    Returns:
+        int: Exit code (0 when stopped by ``--count`` or Ctrl-C).
+    """
+    source = get_source(args)
+    columns = parse_columns(args.columns) or layout_columns(args.layout)
+    fields = _list_fields(args, columns)
+    redact = not args.no_redact
+
+    def snapshot(_tick: int) -> None:
+        timestamp = datetime.now(tz=UTC).astimezone().isoformat(timespec="seconds")
+        procs = _collect_list(args, source, fields)
+        header = _report_header(args)
+        with output_sink(args.output_file, append=True):
+            if args.format == "json":
+                # One record per line; rich would wrap long lines
+                line = format_json_line(procs, timestamp, redact, header)
+                sys.stdout.write(line + "\n")
+            elif args.format == "jsonl":
+                if lines := format_jsonl(procs, timestamp, redact, header):
+                    sys.stdout.write(lines + "\n")
+            else:
+                report = format_output(
+                    procs, args.format, columns, redact=redact, header=header
+                )
+                print(f"# {timestamp}\n{report}")
+
+    try:
+        run_every(args.every, snapshot, count=args.count)
+    except KeyboardInterrupt:
+        pass
+    return 0
+
+
+def _list_watch(args: argparse.Namespace) -> int:
+    """Redraw ``list`` every ``--watch`` seconds, like ``top``.
+
+    On a terminal, tables and Markdown replace the previous snapshot;
+    elsewhere they are appended below a ``# <timestamp>`` line. JSON streams
+    one JSON Lines record per snapshot and JSON Lines one per process, each
+    stamped with the time of its snapshot. CPU usage after the first
+    snapshot covers the time since the previous one.
+
+    Returns:
+        int: Exit code (0 when stopped by ``--count`` or Ctrl-C).
+    """
+    source = get_source(args)
+    columns = parse_columns(args.columns) or layout_columns(args.layout)
+    fields = _list_fields(args, columns)
+    collector = ProcessCollector(source, cpu_interval=args.cpu_interval)
+    redact = not args.no_redact
+
+    def snapshot(_tick: int) -> None:
+        timestamp = datetime.now(tz=UTC).astimezone().isoformat(timespec="seconds")
+        procs = _collect_list(args, source, fields, collector)
+        if args.format == "json":
+            line = format_json_line(procs, timestamp, redact, _report_header(args))
+            sys.stdout.write(line + "\n")
+        elif args.format == "jsonl":
+            lines = format_jsonl(procs, timestamp, redact, _report_header(args))
+            if lines:
+                sys.stdout.write(lines + "\n")
+        else:
+            report, color = _list_report(args, procs, columns)
+            if sys.stdout.isatty():
+                sys.stdout.write(CLEAR_SCREEN)
+            else:
+                print(f"# {timestamp}")
+            _print_report(report, color)
+        sys.stdout.flush()  # Readers of a pipe get each snapshot as it comes
+
+    try:
+        run_every(args.watch, snapshot, count=args.count)
+    except KeyboardInterrupt:
+        pass
     return 0
 
 
+def _attribute(procs: list[ProcessInfo], source: ProcessSource | None) -> None:
+    """Resolve owning packages, warning about unpackaged binaries on stderr."""
+    if not has_package_manager():
+        print(t("cli-no-package-manager"), file=sys.stderr)
+        return
+    attach_packages(procs, source)
+    for p in procs:
+        if p.is_unpackaged:
+            digest = p.exe_sha256 or "?"
+            print(
+                t("cli-unpackaged", pid=p.pid, name=p.name, digest=digest),
+                file=sys.stderr,
+            )
+
+
 def cmd_groups(args: argparse.Namespace) -> int:
     """Show grouped processes command.
 
     Returns:
         int: Exit code (0 on success).
     """
-    procs = get_process_list(min_memory_mb=args.min_memory)
-    groups = find_similar_processes(procs)
+    source = get_source(args)
+    procs = get_process_list(min_memory_mb=args.min_memory, source=source)
+    if args.kill_duplicates:
+        return _kill_duplicates(args, procs, source)
+    groups = find_similar_processes(procs, args.by)
+    duplicates = {p.pid for dupes in find_duplicates(procs) for p in dupes}
+    header = _report_header(args)
+    if header and args.format != "json":
+        print(format_header(header, args.format))
 
     if not groups:
-        print("No process groups found.")
+        print(t("cli-no-groups"))
         return 0
 
     if args.format == "json":
-        data = {
+        data: dict[str, object] = {
             cmd: [
-                {"pid": p.pid, "name": p.name, "rss_mb": round(p.rss_mb, 2)}
+                {
+                    "pid": p.pid,
+                    "name": p.name,
+                    "rss_mb": round(p.rss_mb, 2),
+                    "duplicate": p.pid in duplicates,
+                }
                 for p in group_procs
             ]
             for cmd, group_procs in groups.items()
         }
+        if header:
+            data = {"header": header.to_dict(), "groups": data}
         print(json.dumps(data, indent=2))
     else:
         for cmd, group_procs in sorted(
             groups.items(), key=lambda x: sum(p.rss_mb for p in x[1]), reverse=True
         ):
             total_mb = sum(p.rss_mb for p in group_procs)
-            print(f"\n{cmd} ({len(group_procs)} processes, {total_mb:.1f} MB total)")
+            header = t(
+                "cli-group-header",
+                cmd=cmd,
+                count=len(group_procs),
+                mb=f"{total_mb:.1f}",
+            )
+            print(f"\n{header}")
             for p in sorted(group_procs, key=lambda x: x.rss_mb, reverse=True):
-                print(f"  PID {p.pid}: {p.rss_mb:.1f} MB")
+                item = t("cli-group-item", pid=p.pid, mb=f"{p.rss_mb:.1f}")
+                if p.pid in duplicates:
+                    item += f" {t('cli-group-duplicate')}"
+                print(f"  {item}")
 
     return 0
 
 
-def get_filtered_processes(args: argparse.Namespace) -> list:
+def _kill_duplicates(
+    args: argparse.Namespace, procs: list[ProcessInfo], source: ProcessSource | None
+) -> int:
+    """Kill all but one instance of each set of duplicate processes.
+
+    Returns:
+        int: Exit code (0 if every kill succeeded).
+    """
+    duplicates = find_duplicates(procs)
+    victims = duplicate_victims(duplicates, args.keep)
+    if not victims:
+        print(t("cli-no-duplicates"))
+        return 0
+    for dupes in duplicates:
+        kept = dupes[0] if args.keep == "oldest" else dupes[-1]
+        count = len(dupes) - 1
+        print(t("cli-duplicate-keep", pid=kept.pid, name=kept.name, count=count))
+    if args.preview:
+        return _do_preview(args, victims)
+    return _confirm_and_kill(args, victims, source)
+
+
+def name_matcher(args: argparse.Namespace) -> NameMatcher | None:
+    """Build the ``--name`` matcher from parsed arguments.
+
+    Returns:
+        NameMatcher | None: The matcher, or None without ``--name``.
+    """
+    pattern = getattr(args, "name", None)
+    if not pattern:
+        return None
+    return NameMatcher(
+        pattern,
+        getattr(args, "name_mode", None) or MatchMode.for_pattern(pattern),
+        ignore_case=getattr(args, "ignore_case", False),
+    )
+
+
+def _filter_fields(args: argparse.Namespace) -> FieldMask:
+    """Get the optional fields the filters and sort key of ``args`` read.
+
+    Returns:
+        FieldMask: Fields that must be collected for filtering to be correct.
+    """
+    fields = FieldMask.NONE
+    sort_keys = (getattr(args, "sort", None) or "").split(",")
+    if getattr(args, "cwd", None) is not None or "cwd" in sort_keys:
+        fields |= FieldMask.CWD
+    cpu_bounds = (getattr(args, "min_cpu", None), getattr(args, "max_cpu", None))
+    if "cpu" in sort_keys or any(bound is not None for bound in cpu_bounds):
+        fields |= FieldMask.CPU
+    if getattr(args, "repo", None) is not None:
+        fields |= FieldMask.CWD | FieldMask.CWD_DELETED
+    if preset := _preset(args):
+        fields |= preset.fields
+    if "{cwd" in (getattr(args, "filter_cmd", None) or ""):
+        fields |= FieldMask.CWD
+    return fields
+
+
+def _preset(args: argparse.Namespace) -> FilterSpec | None:
+    """Get the filter preset chosen by ``--filter`` or a shorthand flag.
+
+    Shorthand flags are named after their preset, e.g. ``--cpu-hogs``.
+
+    Returns:
+        FilterSpec | None: The first registered preset that was chosen.
+    """
+    filt = getattr(args, "filter", None)
+    for spec in FILTERS:
+        if filt == spec.name or getattr(args, spec.name.replace("-", "_"), False):
+            return spec
+    return None
+
+
+def get_filtered_processes(
+    args: argparse.Namespace,
+    source: ProcessSource | None = None,
+    fields: FieldMask = ALL_FIELDS,
+    use_daemon: bool = False,
+    collector: ProcessCollector | None = None,
+) -> list:
     """Get processes with all filters from args applied.
 
+    Args:
+        args: Parsed CLI arguments.
+        source: Process backend; defaults to the live system.
+        fields: Optional fields to collect; whatever the filters read is
+            always added.
+        use_daemon: Start from the snapshot of a running daemon, if any, when
+            reading the unrestricted live system.
+        collector: Scan through this collector, for repeated calls.
+
     Returns:
         list: Filtered list of processes.
     """
-    procs = get_process_list(min_memory_mb=getattr(args, "min_memory", 5.0))
+    min_memory = getattr(args, "min_memory", 5.0)
+    user = getattr(args, "user", None)
+    scan_user = ALL_USERS if user else None
+    procs = None
+    kernel_threads = getattr(args, "kernel_threads", False)
+    if use_daemon and source is None:
+        procs = daemon_process_list(
+            min_memory, user=scan_user, kernel_threads=kernel_threads
+        )
+    if procs is None and collector is not None:
+        procs = collector.collect(
+            filter_user=scan_user,
+            min_memory_mb=min_memory,
+            fields=fields | _filter_fields(args),
+            kernel_threads=kernel_threads,
+        )
+    if procs is None:
+        procs = get_process_list(
+            filter_user=scan_user,
+            min_memory_mb=min_memory,
+            source=source,
+            fields=fields | _filter_fields(args),
+            kernel_threads=kernel_threads,
+            cpu_interval=getattr(args, "cpu_interval", 0.0),
+        )
+
+    # Apply owner filter
+    if user:
+        procs = filter_by_user(procs, user)
 
     # Apply cwd filter
     if getattr(args, "cwd", None) is not None:
         cwd_path = args.cwd or str(Path.cwd())
         procs = filter_by_cwd(procs, cwd_path)
 
+    # Apply git worktree filter
+    if getattr(args, "repo", None) is not None:
+        procs = filter_by_repo(procs, args.repo or str(Path.cwd()))
+
+    # Apply listening port filter
+    if (port := getattr(args, "port", None)) is not None:
+        procs = filter_by_port(procs, port, source)
+
+    # Apply name filter
+    if matcher := name_matcher(args):
+        procs = matcher.filter(procs)
+
+    # Apply command line filter
+    if pattern := getattr(args, "cmdline", None):
+        procs = filter_by_cmdline(
+            procs, pattern, ignore_case=getattr(args, "ignore_case", False)
+        )
+
     # Apply preset filters
-    filt = getattr(args, "filter", None)
-    threshold = getattr(args, "high_memory_threshold", 500.0)
-    if filt == "killable" or getattr(args, "killable", False):
-        procs = filter_killable(procs)
-    elif filt == "orphans" or getattr(args, "orphans", False):
-        procs = filter_orphans(procs)
-    elif filt == "high-memory" or getattr(args, "high_memory", False):
-        procs = filter_high_memory(procs, threshold_mb=threshold)
+    if preset := _preset(args):
+        params = {p.name: getattr(args, p.dest, p.default) for p in preset.params}
+        procs = preset.run(procs, source, **params)
+
+    # Apply CPU bounds on top of any preset
+    min_cpu = getattr(args, "min_cpu", None)
+    max_cpu = getattr(args, "max_cpu", None)
+    if min_cpu is not None or max_cpu is not None:
+        procs = filter_by_cpu(procs, min_cpu=min_cpu, max_cpu=max_cpu)
+
+    # Apply age bounds
+    older_than = getattr(args, "older_than", None)
+    newer_than = getattr(args, "newer_than", None)
+    if older_than is not None or newer_than is not None:
+        procs = filter_by_age(procs, older_than=older_than, newer_than=newer_than)
+
+    # External command last: it is by far the slowest filter
+    if command := getattr(args, "filter_cmd", None):
+        jobs = getattr(args, "filter_jobs", FILTER_CMD_JOBS)
+        procs = filter_by_command(procs, command, jobs=jobs)
 
     return procs
 
 
-def _get_kill_targets(args: argparse.Namespace) -> list:
+def _get_kill_targets(
+    args: argparse.Namespace, source: ProcessSource | None = None
+) -> list:
     """Get target processes for kill command from PIDs or filters.
 
+    Args:
+        args: Parsed CLI arguments.
+        source: Process backend; defaults to the live system.
+
     Returns:
-        list: Target processes to kill.
+        list: Target processes to kill; kernel threads are never included.
     """
+    if (members := _group_targets(args, source)) is not None:
+        return members
     if args.pids:
-        all_procs = get_process_list(min_memory_mb=0)
+        all_procs = get_process_list(
+            min_memory_mb=0, source=source, kernel_threads=True
+        )
         pid_set = set(args.pids)
         procs = [p for p in all_procs if p.pid in pid_set]
         found_pids = {p.pid for p in procs}
         for pid in args.pids:
             if pid not in found_pids:
-                print(f"Warning: PID {pid} not found")
+                print(t("cli-pid-not-found", pid=pid))
+        for proc in procs:
+            if proc.is_kernel_thread:
+                print(t("cli-kernel-thread-skipped", pid=proc.pid, name=proc.name))
+        procs = [p for p in procs if not p.is_kernel_thread]
+        return _skip_vms(args, procs)
+    return _skip_vms(args, get_filtered_processes(args, source))
+
+
+def _group_targets(
+    args: argparse.Namespace, source: ProcessSource | None
+) -> list[ProcessInfo] | None:
+    """Get the members of the ``--pgid`` group or ``--sid`` session.
+
+    The process group of procclean itself is skipped, so a session kill does
+    not stop the kill half-way.
+
+    Returns:
+        list[ProcessInfo] | None: Members by PID, or None without either
+        option.
+    """
+    pgid, sid = getattr(args, "pgid", None), getattr(args, "sid", None)
+    if pgid is None and sid is None:
+        return None
+    procs = get_process_list(
+        "pid", filter_user=ALL_USERS, min_memory_mb=0, source=source
+    )
+    if pgid is not None:
+        members = [p for p in procs if p.pgid == pgid]
+    else:
+        members = [p for p in procs if p.sid == sid]
+    own = (source or DEFAULT_SOURCE).process_group(os.getpid())
+    if own != -1 and any(p.pgid == own for p in members):
+        print(t("cli-group-own", pgid=own))
+        members = [p for p in members if p.pgid != own]
+    return sorted(members, key=lambda p: (p.pgid, p.pid))
+
+
+def _skip_vms(args: argparse.Namespace, procs: list[ProcessInfo]) -> list[ProcessInfo]:
+    """Drop VM processes from kill targets unless ``--include-vms`` is given.
+
+    Returns:
+        list[ProcessInfo]: The targets that may be killed.
+    """
+    if getattr(args, "include_vms", False):
         return procs
-    return get_filtered_processes(args)
+    procs, vms = exclude_vms(procs)
+    for vm in vms:
+        print(t("cli-vm-skipped", pid=vm.pid, name=vm.name, vm=vm.vm))
+    return procs
 
 
 def _do_preview(args: argparse.Namespace, procs: list) -> int:
@@ -133,37 +837,280 @@ def _do_preview(args: argparse.Namespace, procs: list) -> int:
         procs = sort_processes(procs, sort_by=args.sort, reverse=True)
     if hasattr(args, "limit") and args.limit:
         procs = procs[: args.limit]
-    columns = args.columns.split(",") if getattr(args, "columns", None) else None
+    columns = parse_columns(getattr(args, "columns", None)) or layout_columns(
+        getattr(args, "layout", None)
+    )
     fmt = getattr(args, "out_format", "table")
-    print(format_output(procs, fmt, columns=columns))
-    print(f"\n{len(procs)} process(es) would be killed.")
+    width = _table_width(columns)
+    redact = not getattr(args, "no_redact", False)
+    color = fmt == "table" and use_color(getattr(args, "color", "auto"))
+    report = format_output(
+        procs, fmt, columns=columns, width=width, redact=redact, color=color
+    )
+    _print_report(report, color)
+    print(f"\n{t('cli-preview-footer', count=len(procs))}")
     return 0
 
 
-def _confirm_kill(args: argparse.Namespace, procs: list) -> bool:
+def _confirm_kill(
+    args: argparse.Namespace, procs: list, source: ProcessSource | None = None
+) -> bool | str:
     """Prompt for kill confirmation.
 
+    Each previewed process is annotated with the manager that would restart it.
+    If any target has one, ``m`` stops those targets through their manager.
+    A single target matching a ``[kill] no_confirm`` pattern is not prompted.
+
     Args:
         args: Parsed CLI arguments.
         procs: Processes that would be killed.
+        source: Process backend used to resolve supervisors.
 
     Returns:
-        True if the kill action is confirmed (or confirmation is skipped), otherwise
+        True if the kill action is confirmed (or confirmation is skipped),
+        ``VIA_MANAGER`` if the user chose to stop via the manager, otherwise
         False.
     """
     if args.yes or not sys.stdin.isatty():
         return True
-    action = "FORCE KILL" if args.force else "terminate"
-    print(f"About to {action} {len(procs)} process(es):")
+    if len(procs) == 1 and (pattern := no_confirm_match(procs[0])):
+        proc = procs[0]
+        print(t("cli-confirm-skipped", pid=proc.pid, name=proc.name, pattern=pattern))
+        return True
+    supervisors = resolve_supervisors([p.pid for p in procs], source)
+    sig = getattr(args, "signal", None)
+    if sig is not None and not is_kill_signal(sig):
+        action = t("cli-action-signal", signal=sig.name)
+    elif args.force or (sig is not None and sig.name == "SIGKILL"):
+        action = t("cli-action-force")
+    else:
+        action = t("cli-action-terminate")
+    print(t("cli-confirm-header", action=action, count=len(procs)))
     for p in procs[:PREVIEW_LIMIT]:
-        print(f"  {p.pid}: {p.name} ({p.rss_mb:.1f} MB)")
+        item = t("cli-confirm-item", pid=p.pid, name=p.name, mb=f"{p.rss_mb:.1f}")
+        print(f"  {item}")
+        if note := priority_note(p):
+            print(f"      {note}")
+        if sup := supervisors.get(p.pid):
+            print(f"      {sup.describe()}")
     if len(procs) > PREVIEW_LIMIT:
-        print(f"  ... and {len(procs) - PREVIEW_LIMIT} more")
+        print(f"  {t('cli-confirm-more', count=len(procs) - PREVIEW_LIMIT)}")
+    managed = any(s.stop_command for s in supervisors.values())
+    prompt = t("cli-confirm-prompt-manager" if managed else "cli-confirm-prompt")
     try:
-        response = input("Continue? [y/N] ")
-        return response.lower() in {"y", "yes"}
+        response = input(f"{prompt} ").lower()
     except EOFError:
         return True  # Non-interactive
+    if managed and response in {"m", "manager"}:
+        return VIA_MANAGER
+    return response in {"y", "yes"}
+
+
+def _data_loss_risks(
+    args: argparse.Namespace,
+    procs: list[ProcessInfo],
+    sig: signal.Signals | None,
+    escalate: float | None,
+) -> list[ProcessInfo]:
+    """Find targets a kill could corrupt, refusing SIGKILL for them.
+
+    Databases and the other ``[kill] data_loss`` processes need a clean
+    shutdown: SIGTERM lets them flush their files, SIGKILL (``--force``,
+    ``--signal KILL`` or ``--escalate``) does not. Signals that do not end a
+    process and stops through a manager are no risk.
+
+    Returns:
+        list[ProcessInfo]: Targets that need an extra confirmation; none with
+        ``--i-know-what-im-doing``.
+
+    Raises:
+        ProccleanError: With ``PERMISSION_DENIED`` if SIGKILL would reach one
+            of them.
+    """
+    if getattr(args, "i_know_what_im_doing", False) or getattr(
+        args, "via_manager", False
+    ):
+        return []
+    if sig is not None and not is_kill_signal(sig):
+        return []
+    force = getattr(args, "force", False)
+    sigkill = force or escalate is not None or sig == signal.SIGKILL
+    return refuse_data_loss_kill(procs, sigkill=sigkill)
+
+
+def _confirm_data_loss(args: argparse.Namespace, risks: list[ProcessInfo]) -> bool:
+    """Ask once more before killing processes that risk data loss.
+
+    The user has to type the name of the first one. ``-y`` does not skip
+    this, and runs without a terminal are refused.
+
+    Returns:
+        bool: True if the name was typed.
+
+    Raises:
+        ProccleanError: With ``PERMISSION_DENIED`` with ``-y`` or without a
+            terminal to ask on.
+    """
+    if getattr(args, "yes", False) or not sys.stdin.isatty():
+        names = ", ".join(sorted({p.name for p in risks}))
+        msg = (
+            f"Not killing {names} without confirmation at a terminal: risk of "
+            "data loss; pass --i-know-what-im-doing to skip it"
+        )
+        raise ProccleanError(
+            ErrorCode.PERMISSION_DENIED, msg, pids=[p.pid for p in risks]
+        )
+    print(t("cli-data-loss-header", count=len(risks)))
+    for p in risks[:PREVIEW_LIMIT]:
+        item = t("cli-confirm-item", pid=p.pid, name=p.name, mb=f"{p.rss_mb:.1f}")
+        print(f"  {item}")
+    name = risks[0].name
+    try:
+        response = input(f"{t('cli-data-loss-prompt', name=name)} ")
+    except EOFError:
+        return False
+    return response.strip() == name
+
+
+def _check_data_loss(args: argparse.Namespace, procs: list[ProcessInfo]) -> bool:
+    """Refuse SIGKILL for, or confirm, targets of a kill that risk data loss.
+
+    For kills with SIGTERM, or SIGKILL with ``--force``, that already asked
+    for the usual confirmation.
+
+    Returns:
+        bool: True to go ahead with the kill.
+    """
+    risks = _data_loss_risks(args, procs, None, None)
+    if not risks:
+        return True
+    with terminal():  # Prompts bypass --output-file
+        return _confirm_data_loss(args, risks)
+
+
+def _spare_data_loss(
+    args: argparse.Namespace, procs: list[ProcessInfo]
+) -> list[ProcessInfo]:
+    """Pick targets an unattended kill leaves alone.
+
+    Nobody is there to confirm a kill that risks data loss, so those targets
+    are spared unless the command runs with ``--i-know-what-im-doing``.
+
+    Returns:
+        list[ProcessInfo]: Targets not to kill.
+    """
+    if args.i_know_what_im_doing:
+        return []
+    return data_loss_targets(procs)
+
+
+def _print_reclaim(report: ReclaimReport) -> None:
+    """Print memory freed by a kill, measured against the RSS estimate."""
+    if not report.estimated_mb:
+        return
+    estimated = f"{report.estimated_mb:.1f}"
+    if report.ratio is None or report.actual_mb is None:
+        print(t("cli-reclaim-estimated", estimated=estimated))
+        return
+    actual = f"{report.actual_mb:.1f}"
+    percent = f"{report.ratio * 100:.0f}"
+    print(t("cli-reclaim", actual=actual, estimated=estimated, percent=percent))
+
+
+def _measured_kill(
+    procs: list[ProcessInfo],
+    force: bool,
+    source: ProcessSource | None,
+    via_manager: str | None = None,
+    sig: signal.Signals | None = None,
+    escalate: float | None = None,
+    groups: bool = False,
+) -> tuple[list[tuple[int, bool, str]], ReclaimReport]:
+    """Kill (or stop via managers) and measure the memory freed.
+
+    Args:
+        procs: Targets.
+        force: Use SIGKILL instead of SIGTERM.
+        source: Process backend; simulated kills are not measured.
+        via_manager: Manager action (``stop``/``delete``), or None to signal.
+        sig: Signal to send instead; SIGTERM and SIGKILL override ``force``.
+        escalate: Seconds to wait for an exit after SIGTERM before sending
+            SIGKILL, or None to only send the signal.
+        groups: Signal the process groups of the targets as a whole.
+
+    Returns:
+        tuple: Kill results and the memory report.
+    """
+    pids = [p.pid for p in procs]
+    if sig is not None and not is_kill_signal(sig):
+        # Nothing to measure: the targets keep running
+        if groups:
+            return kill_groups(procs, source=source, sig=sig), ReclaimReport(0.0)
+        return kill_processes(pids, source=source, sig=sig), ReclaimReport(0.0)
+    if sig is not None:
+        force = sig.name == "SIGKILL"
+
+    def kill() -> list[tuple[int, bool, str]]:
+        if via_manager:
+            return stop_via_managers(pids, force, source, via_manager)
+        if groups:
+            return kill_groups(procs, force=force, source=source)
+        if escalate is not None:
+            results = kill_escalating(pids, escalate, source)
+            return [(r.pid, r.ok, r.message) for r in results]
+        return kill_processes(pids, force=force, source=source)
+
+    settle = None if isinstance(source, SimulatedSource) else MEMORY_SETTLE_SECONDS
+    return measure_reclaim(kill, {p.pid: p.rss_mb for p in procs}, settle)
+
+
+def _group_option(args: argparse.Namespace) -> str | None:
+    """Get the option selecting a process group or session to kill.
+
+    Returns:
+        str | None: "--pgid" or "--sid", or None if neither is given.
+    """
+    if getattr(args, "pgid", None) is not None:
+        return "--pgid"
+    if getattr(args, "sid", None) is not None:
+        return "--sid"
+    return None
+
+
+def _kill_mode(args: argparse.Namespace) -> tuple[signal.Signals | None, float | None]:
+    """Check the ``kill`` options choosing how processes are signaled.
+
+    Returns:
+        tuple: The ``--signal`` to send, and the ``--escalate`` timeout in
+        seconds; None for either when not given.
+
+    Raises:
+        ProccleanError: With ``INVALID_ARGUMENT`` if ``--signal`` or
+            ``--escalate`` is combined with ``--via-manager``, ``--pgid`` or
+            ``--sid`` with PIDs, ``--escalate`` or ``--via-manager``, or
+            ``--timeout`` is given without ``--escalate``.
+    """
+    group = _group_option(args)
+    conflicts = (
+        ("PIDs", bool(getattr(args, "pids", None))),
+        ("--escalate", getattr(args, "escalate", False)),
+        ("--via-manager", getattr(args, "via_manager", False)),
+    )
+    for other, given in conflicts if group else ():
+        if given:
+            msg = f"{group} cannot be combined with {other}"
+            raise ProccleanError(ErrorCode.INVALID_ARGUMENT, msg, option=group)
+    sig = getattr(args, "signal", None)
+    escalate = getattr(args, "escalate", False)
+    timeout = getattr(args, "timeout", None)
+    if getattr(args, "via_manager", False) and (sig is not None or escalate):
+        option = "--signal" if sig is not None else "--escalate"
+        msg = f"{option} cannot be combined with --via-manager"
+        raise ProccleanError(ErrorCode.INVALID_ARGUMENT, msg, option=option)
+    if timeout is not None and not escalate:
+        msg = "--timeout requires --escalate"
+        raise ProccleanError(ErrorCode.INVALID_ARGUMENT, msg)
+    return sig, (timeout or ESCALATE_TIMEOUT_SECONDS) if escalate else None
 
 
 def cmd_kill(args: argparse.Namespace) -> int:
@@ -172,42 +1119,1545 @@ def cmd_kill(args: argparse.Namespace) -> int:
     Returns:
         int: Exit code (0 on success).
     """
-    procs = _get_kill_targets(args)
+    if getattr(args, "list_filters", False):
+        return _list_filters(args.out_format == "json")
+    sig, escalate = _kill_mode(args)
+    load_kill_hooks()  # Report malformed hook commands before prompting
+    source = get_source(args)
+    procs = _get_kill_targets(args, source)
     if not procs:
-        print("No processes match the filters.")
+        if not getattr(args, "quiet", False):
+            print(t("cli-no-match"))
         return 0
 
     if getattr(args, "preview", False):
         return _do_preview(args, procs)
+    groups = _group_option(args) is not None
+    return _confirm_and_kill(args, procs, source, sig, escalate, groups)
+
+
+def _confirm_and_kill(
+    args: argparse.Namespace,
+    procs: list[ProcessInfo],
+    source: ProcessSource | None,
+    sig: signal.Signals | None = None,
+    escalate: float | None = None,
+    groups: bool = False,
+) -> int:
+    """Confirm a kill, carry it out and report the results.
 
-    if not _confirm_kill(args, procs):
-        print("Aborted.")
+    Args:
+        args: Parsed CLI arguments (``yes``, ``force`` and the kill options).
+        procs: Targets.
+        source: Process backend.
+        sig: Signal to send instead of SIGTERM/SIGKILL.
+        escalate: Seconds to wait before escalating to SIGKILL, or None.
+        groups: Signal the process groups of the targets as a whole.
+
+    Returns:
+        int: Exit code (0 if every kill succeeded).
+    """
+    risks = _data_loss_risks(args, procs, sig, escalate)
+    with terminal():  # Prompts bypass --output-file
+        choice = _confirm_kill(args, procs, source)
+        if choice and choice != VIA_MANAGER and risks:
+            choice = _confirm_data_loss(args, risks)
+    if not choice:
+        print(t("cli-aborted"))
         return 1
 
-    results = kill_processes([p.pid for p in procs], force=args.force)
+    via_manager = None
+    if getattr(args, "via_manager", False) or choice == VIA_MANAGER:
+        via_manager = getattr(args, "manager_action", "stop")
+        sig = escalate = None  # Picked at the prompt; managers stop services
+    results, report = _measured_kill(
+        procs, args.force, source, via_manager, sig, escalate, groups
+    )
     exit_code = 0
     for _, success, msg in results:
-        status = "OK" if success else "FAILED"
+        status = t("cli-result-ok" if success else "cli-result-failed")
         print(f"[{status}] {msg}")
         if not success:
             exit_code = 1
+    _print_reclaim(report)
+    if isinstance(source, SimulatedSource):
+        print(f"\n{t('cli-simulated')}")
     return exit_code
 
 
-def cmd_memory(args: argparse.Namespace) -> int:
-    """Show memory summary command.
+def _restart_targets(
+    targets: list[str], source: ProcessSource | None
+) -> list[ProcessInfo]:
+    """Resolve PIDs and unit names to the processes to restart.
 
     Returns:
-        int: Exit code (0 on success).
+        list[ProcessInfo]: Processes in argument order, each once.
+
+    Raises:
+        ProccleanError: With ``PROCESS_NOT_FOUND`` for an unknown PID or a
+            unit without running processes.
     """
-    mem = get_memory_summary()
+    procs = get_process_list(
+        filter_user=ALL_USERS, min_memory_mb=0, source=source, kernel_threads=True
+    )
+    by_pid = {p.pid: p for p in procs}
+    found: dict[int, ProcessInfo] = {}
+    for target in targets:
+        if target.isdigit():
+            if (proc := by_pid.get(int(target))) is None:
+                msg = f"Process {target} not found"
+                raise ProccleanError(ErrorCode.PROCESS_NOT_FOUND, msg, pid=int(target))
+            matched = [proc]
+        elif not (matched := unit_processes(target, procs, source)):
+            msg = f"No running process belongs to {target}"
+            raise ProccleanError(ErrorCode.PROCESS_NOT_FOUND, msg, unit=target)
+        found.update((p.pid, p) for p in matched)
+    return list(found.values())
+
+
+def _confirm_restart(args: argparse.Namespace, plans: list[RestartPlan]) -> bool:
+    """Show how each process would be restarted and prompt.
+
+    Returns:
+        bool: True if confirmed, or confirmation is skipped.
+    """
+    if args.yes or not sys.stdin.isatty():
+        return True
+    redact = not getattr(args, "no_redact", False)
+    print(t("cli-restart-header", count=len(plans)))
+    for plan in plans[:PREVIEW_LIMIT]:
+        proc = plan.proc
+        item = t(
+            "cli-restart-item", pid=proc.pid, name=proc.name, plan=plan.describe(redact)
+        )
+        print(f"  {item}")
+        if note := priority_note(proc):
+            print(f"      {note}")
+    if len(plans) > PREVIEW_LIMIT:
+        print(f"  {t('cli-confirm-more', count=len(plans) - PREVIEW_LIMIT)}")
+    try:
+        response = input(f"{t('cli-restart-prompt')} ").lower()
+    except EOFError:
+        return True  # Non-interactive
+    return response in {"y", "yes"}
+
+
+def cmd_restart(args: argparse.Namespace) -> int:
+    """Restart processes through their managers, or kill and respawn them.
+
+    Returns:
+        int: Exit code (0 if every process was restarted).
+    """
+    source = get_source(args)
+    plans = plan_restarts(_restart_targets(args.targets, source), source)
+    killed = [
+        p.proc for p in plans if p.method is not RestartMethod.MANAGER and not p.error
+    ]
+    # Killing escalates to SIGKILL, which data-loss processes are spared
+    _data_loss_risks(args, killed, None, args.timeout)
+    with terminal():  # Prompts bypass --output-file
+        confirmed = _confirm_restart(args, plans)
+    if not confirmed:
+        print(t("cli-aborted"))
+        return 1
 
+    results = restart_processes(plans, source, args.timeout)
+    exit_code = 0 if all(r.ok for r in results) else 1
     if args.format == "json":
-        print(json.dumps(mem, indent=2))
-    else:
-        print(f"Total:  {mem['total_gb']:.2f} GB")
-        print(f"Used:   {mem['used_gb']:.2f} GB ({mem['percent']:.1f}%)")
-        print(f"Free:   {mem['free_gb']:.2f} GB")
-        print(f"Swap:   {mem['swap_used_gb']:.2f} / {mem['swap_total_gb']:.2f} GB")
+        print(json.dumps([r.to_dict() for r in results], indent=2))
+        return exit_code
+    for result in results:
+        status = t("cli-result-ok" if result.ok else "cli-result-failed")
+        print(f"[{status}] {result.message}")
+    if isinstance(source, SimulatedSource):
+        print(f"\n{t('cli-simulated')}")
+    return exit_code
+
 
+def cmd_renice(args: argparse.Namespace) -> int:
+    """Change the CPU and I/O priority of processes.
+
+    Returns:
+        int: Exit code (0 if every change was applied).
+    """
+    source = get_source(args)
+    nice = RENICE_NICE if args.nice is None and args.ionice is None else args.nice
+    results = []
+    for pid in args.pids:
+        if nice is not None:
+            results.append((pid, "nice", *renice_process(pid, nice, source)))
+        if args.ionice is not None:
+            ioclass, level = args.ionice
+            ok, msg = ionice_process(pid, ioclass, level, source)
+            results.append((pid, "ionice", ok, msg))
+    exit_code = 0 if all(ok for *_, ok, _ in results) else 1
+    if args.format == "json":
+        keys = ("pid", "action", "ok", "message")
+        print(json.dumps([dict(zip(keys, r, strict=True)) for r in results], indent=2))
+        return exit_code
+    for _, _, ok, msg in results:
+        print(f"[{t('cli-result-ok' if ok else 'cli-result-failed')}] {msg}")
+    if isinstance(source, SimulatedSource):
+        print(f"\n{t('cli-simulated')}")
+    return exit_code
+
+
+def _confirm_leftovers(args: argparse.Namespace, holders: int, files: int) -> bool:
+    """Prompt before killing leftover holders and removing files.
+
+    Returns:
+        bool: True if confirmed (or confirmation is skipped).
+    """
+    if args.yes or not sys.stdin.isatty():
+        return True
+    prompt = t("cli-leftovers-confirm", holders=holders, files=files)
+    try:
+        response = input(f"{prompt} ")
+        return response.lower() in {"y", "yes"}
+    except EOFError:
+        return True  # Non-interactive
+
+
+def cmd_leftovers(args: argparse.Namespace) -> int:
+    """Report stale sockets/lock files and optionally clean them up.
+
+    Returns:
+        int: Exit code (0 on success, 1 if any cleanup step failed).
+    """
+    dirs = [Path(d) for d in args.dirs] if args.dirs else None
+    leftovers = find_leftovers(dirs=dirs)
+
+    if args.format == "json" and not args.fix:
+        print(json.dumps([asdict(item) for item in leftovers], indent=2))
+        return 0
+    if not leftovers:
+        print(t("cli-no-leftovers"))
+        return 0
+
+    for item in leftovers:
+        holder = f"PID {item.pid}" if item.pid is not None else "-"
+        print(f"{item.kind:<6}  {holder:<10}  {item.path}  ({item.reason})")
+
+    if not args.fix:
+        print(f"\n{t('cli-leftovers-footer', count=len(leftovers))}")
+        return 0
+
+    source = get_source(args)
+    procs = get_process_list(min_memory_mb=0, source=source)
+    safe = leftover_holders(leftovers, procs, source)
+    held = {item.pid for item in leftovers if item.pid is not None}
+    for pid in sorted(held - {p.pid for p in safe}):
+        print(t("cli-leftovers-holder-skipped", pid=pid))
+    holders = _skip_vms(args, safe)
+    pids = [p.pid for p in holders]
+    files = [item for item in leftovers if item.removable]
+    if not _confirm_leftovers(args, len(pids), len(files)) or not _check_data_loss(
+        args, holders
+    ):
+        print(t("cli-aborted"))
+        return 1
+
+    results = [
+        (success, msg)
+        for _, success, msg in kill_processes(pids, force=args.force, source=source)
+    ]
+    simulated = isinstance(source, SimulatedSource)
+    if not simulated:
+        results += [remove_leftover(item) for item in files]
+    exit_code = 0
+    for success, msg in results:
+        status = t("cli-result-ok" if success else "cli-result-failed")
+        print(f"[{status}] {msg}")
+        if not success:
+            exit_code = 1
+    if simulated:
+        print(f"\n{t('cli-leftovers-simulated')}")
+    return exit_code
+
+
+def _confirm_clean(args: argparse.Namespace, count: int, mb: float) -> bool:
+    """Prompt once before a consolidated cleanup.
+
+    Returns:
+        bool: True if confirmed (or confirmation is skipped).
+    """
+    if args.yes or not sys.stdin.isatty():
+        return True
+    prompt = t("cli-clean-confirm", count=count, mb=f"{mb:.1f}")
+    try:
+        response = input(f"{prompt} ")
+        return response.lower() in {"y", "yes"}
+    except EOFError:
+        return True  # Non-interactive
+
+
+def cmd_clean(args: argparse.Namespace) -> int:
+    """Clean up every safe category with one preview and confirmation.
+
+    Returns:
+        int: Exit code (0 on success, 1 if aborted or any kill failed).
+    """
+    source = get_source(args)
+    procs = get_process_list(min_memory_mb=args.min_memory, source=source)
+    groups = clean_targets(_skip_vms(args, procs), source)
+    if not groups:
+        print(t("cli-clean-nothing"))
+        return 0
+
+    for category, members in groups.items():
+        mb = f"{sum(p.rss_mb for p in members):.1f}"
+        print(t("cli-clean-category", category=category, count=len(members), mb=mb))
+        for p in members:
+            item = t("cli-confirm-item", pid=p.pid, name=p.name, mb=f"{p.rss_mb:.1f}")
+            print(f"  {item}")
+    targets = [p for members in groups.values() for p in members]
+    total_mb = sum(p.rss_mb for p in targets)
+    print(f"\n{t('cli-clean-total', count=len(targets), mb=f'{total_mb:.1f}')}")
+
+    if not args.auto:
+        print(t("cli-clean-footer"))
+        return 0
+    if not _confirm_clean(args, len(targets), total_mb) or not _check_data_loss(
+        args, targets
+    ):
+        print(t("cli-aborted"))
+        return 1
+
+    results, report = _measured_kill(targets, args.force, source)
+    exit_code = 0
+    for _, success, msg in results:
+        status = t("cli-result-ok" if success else "cli-result-failed")
+        print(f"[{status}] {msg}")
+        if not success:
+            exit_code = 1
+    print()
+    _print_reclaim(report)
+    if isinstance(source, SimulatedSource):
+        print(t("cli-simulated"))
+    return exit_code
+
+
+def _of_limit(value: float, limit: float | None, fmt: str) -> str:
+    """Render ``value`` or ``value/limit`` with ``fmt``.
+
+    Returns:
+        str: The formatted usage.
+    """
+    if limit is None:
+        return fmt.format(value)
+    return f"{fmt.format(value)}/{fmt.format(limit)}"
+
+
+def _print_check_header(args: argparse.Namespace) -> None:
+    """Print a ``# <timestamp>`` line above each repeated check."""
+    if args.every:
+        print(f"# {datetime.now(tz=UTC).astimezone().isoformat(timespec='seconds')}")
+
+
+def _print_check_json(args: argparse.Namespace, data: object) -> None:
+    """Print a check report as JSON, one compact line per ``--every`` check."""
+    if args.every:
+        # One record per line; rich would wrap long lines
+        sys.stdout.write(json.dumps(data) + "\n")
+    else:
+        print(json.dumps(data, indent=2))
+
+
+def _print_offender(
+    proc: ProcessInfo, enforced: bool | None, spared: bool = False
+) -> None:
+    """Print an over-limit offender and, if acted on, whether that worked."""
+    mb = f"{proc.rss_mb:.1f}"
+    print(f"  {t('cli-offender', pid=proc.pid, name=proc.name, mb=mb)}")
+    if spared:
+        print(f"    [{t('cli-result-spared')}]")
+    elif enforced is not None:
+        status = t("cli-result-ok" if enforced else "cli-result-failed")
+        print(f"    [{status}]")
+
+
+def _repeat_check(args: argparse.Namespace, check: Callable[[], int]) -> int:
+    """Run a check once, or every ``--every`` until ``--count`` or Ctrl-C.
+
+    Returns:
+        int: The check's exit code for a single run, otherwise 0.
+    """
+    if not args.every:
+        return check()
+    try:
+        run_every(args.every, lambda _tick: check(), count=args.count)
+    except KeyboardInterrupt:
+        pass
+    return 0
+
+
+def _check_budgets_once(
+    args: argparse.Namespace, budgets: tuple, source: ProcessSource | None
+) -> int:
+    """Report budget usage once, killing offenders if enforcing.
+
+    Returns:
+        int: 1 if any budget is exceeded, otherwise 0.
+    """
+    procs = get_process_list(min_memory_mb=0, source=source)
+    usages = check_budgets(procs, budgets)
+    killed: dict[int, bool] = {}
+    spared: set[int] = set()
+    if args.enforce:
+        offenders = [
+            p
+            for usage in usages
+            if usage.budget.action == "kill"
+            for p in usage.offenders
+        ]
+        spared = {p.pid for p in _spare_data_loss(args, offenders)}
+        pids = [p.pid for p in offenders if p.pid not in spared]
+        killed = {pid: ok for pid, ok, _ in kill_processes(pids, source=source)}
+
+    if args.format == "json":
+        data = [
+            {
+                "path": str(usage.budget.path),
+                "limit_mb": round(usage.budget.limit_mb, 2),
+                "used_mb": round(usage.used_mb, 2),
+                "exceeded": usage.exceeded,
+                "action": usage.budget.action,
+                "offenders": [p.pid for p in usage.offenders],
+                "killed": [p.pid for p in usage.offenders if killed.get(p.pid)],
+                "spared": [p.pid for p in usage.offenders if p.pid in spared],
+            }
+            for usage in usages
+        ]
+        _print_check_json(args, data)
+        return 1 if any(u.exceeded for u in usages) else 0
+
+    _print_check_header(args)
+    for usage in usages:
+        key = "cli-budget-over" if usage.exceeded else "cli-budget-usage"
+        used, limit = f"{usage.used_mb:.1f}", f"{usage.budget.limit_mb:.1f}"
+        path = str(usage.budget.path)
+        print(t(key, path=path, used=used, limit=limit, count=len(usage.procs)))
+        for p in usage.offenders:
+            _print_offender(p, killed.get(p.pid), p.pid in spared)
+    if not args.enforce and any(
+        u.exceeded and u.budget.action == "kill" for u in usages
+    ):
+        print(t("cli-budget-enforce-hint"))
+    return 1 if any(u.exceeded for u in usages) else 0
+
+
+def cmd_budgets(args: argparse.Namespace) -> int:
+    """Check per-directory memory budgets, once or every ``--every``.
+
+    Returns:
+        int: Exit code (1 if a budget is exceeded in a single check).
+    """
+    if args.count is not None and not args.every:
+        raise ProccleanError(ErrorCode.INVALID_ARGUMENT, "--count requires --every")
+    budgets = load_budgets()
+    if not budgets:
+        print(t("cli-no-budgets"))
+        return 0
+    source = get_source(args)
+    return _repeat_check(args, lambda: _check_budgets_once(args, budgets, source))
+
+
+def _check_quotas_once(
+    args: argparse.Namespace, quotas: Quotas, source: ProcessSource | None
+) -> int:
+    """Report quota usage once, renicing or killing offenders if enforcing.
+
+    Returns:
+        int: 1 if any user is over quota, otherwise 0.
+    """
+    procs = get_process_list(filter_user=ALL_USERS, min_memory_mb=0, source=source)
+    usages = check_quotas(procs, quotas)
+    enforced: dict[int, bool] = {}
+    spared: set[int] = set()
+    if args.enforce:
+        offenders = [
+            p
+            for usage in usages
+            if usage.rule.action == "kill"
+            for p in usage.offenders
+        ]
+        spared = {p.pid for p in _spare_data_loss(args, offenders)}
+        for usage in usages:
+            for p in usage.offenders:
+                if p.pid in spared:
+                    continue
+                if usage.rule.action == "kill":
+                    enforced[p.pid], _ = kill_process(p.pid, source=source)
+                elif usage.rule.action == "renice":
+                    enforced[p.pid], _ = renice_process(p.pid, QUOTA_NICE, source)
+
+    if args.format == "json":
+        data = [
+            {
+                "user": usage.user,
+                "processes": len(usage.procs),
+                "process_limit": usage.rule.processes,
+                "memory_mb": round(usage.used_mb, 2),
+                "memory_limit_mb": usage.rule.memory_mb,
+                "exceeded": usage.exceeded,
+                "action": usage.rule.action,
+                "offenders": [p.pid for p in usage.offenders],
+                "enforced": [p.pid for p in usage.offenders if enforced.get(p.pid)],
+                "spared": [p.pid for p in usage.offenders if p.pid in spared],
+            }
+            for usage in usages
+        ]
+        _print_check_json(args, data)
+        return 1 if any(u.exceeded for u in usages) else 0
+
+    _print_check_header(args)
+    for usage in usages:
+        count = _of_limit(len(usage.procs), usage.rule.processes, "{}")
+        memory = _of_limit(usage.used_mb, usage.rule.memory_mb, "{:.1f}")
+        key = "cli-quota-over" if usage.exceeded else "cli-quota-usage"
+        print(t(key, user=usage.user, count=count, memory=memory))
+        for p in usage.offenders:
+            _print_offender(p, enforced.get(p.pid), p.pid in spared)
+    if not args.enforce and any(
+        u.exceeded and u.rule.action != "notify" for u in usages
+    ):
+        print(t("cli-quota-enforce-hint"))
+    return 1 if any(u.exceeded for u in usages) else 0
+
+
+def cmd_quota(args: argparse.Namespace) -> int:
+    """Show per-user usage against quotas, once or every ``--every``.
+
+    Returns:
+        int: Exit code (1 if a user is over quota in a single check).
+    """
+    if args.count is not None and not args.every:
+        raise ProccleanError(ErrorCode.INVALID_ARGUMENT, "--count requires --every")
+    quotas = load_quotas()
+    if not quotas.rules:
+        print(t("cli-no-quotas"))
+        return 0
+    source = get_source(args)
+    return _repeat_check(args, lambda: _check_quotas_once(args, quotas, source))
+
+
+def _log(args: argparse.Namespace, message: str) -> None:
+    """Append a timestamped line to ``--log`` (or stdout)."""
+    timestamp = datetime.now(tz=UTC).astimezone().isoformat(timespec="seconds")
+    with output_sink(args.log, append=True):
+        # Plain write: command lines may contain rich markup brackets
+        sys.stdout.write(f"{timestamp} {message}\n")
+
+
+def cmd_emergency(args: argparse.Namespace) -> int:
+    """Kill pre-approved processes when memory pressure turns critical.
+
+    Returns:
+        int: Exit code (1 if a single check triggered, otherwise 0).
+    """
+    if args.count is not None and not args.every:
+        raise ProccleanError(ErrorCode.INVALID_ARGUMENT, "--count requires --every")
+    policy = load_emergency_policy()
+    if policy is None:
+        print(t("cli-no-emergency"))
+        return 0
+    source = get_source(args)
+    if args.every:
+        thresholds = []
+        if policy.psi_full_avg10 is not None:
+            thresholds.append(f"PSI full avg10 >= {policy.psi_full_avg10:.1f}%")
+        if policy.min_available_mb is not None:
+            thresholds.append(f"available < {policy.min_available_mb:.0f} MB")
+        _log(
+            args,
+            t(
+                "cli-emergency-armed",
+                thresholds=" or ".join(thresholds),
+                count=len(policy.kill),
+            ),
+        )
+    cooldown_until = 0.0
+
+    def check() -> int:
+        nonlocal cooldown_until
+        now = time.monotonic()
+        if now < cooldown_until:
+            return 0
+        reason = policy.triggered(read_pressure())
+        if reason is None:
+            return 0
+        cooldown_until = now + EMERGENCY_COOLDOWN_SECONDS
+        _log(args, t("cli-emergency-trigger", reason=reason))
+        procs = get_process_list(filter_user=ALL_USERS, min_memory_mb=0, source=source)
+        targets = emergency_targets(procs, policy, source)
+        if not targets:
+            _log(args, t("cli-emergency-no-targets"))
+        spared = _spare_data_loss(args, targets)
+        for p in spared:
+            _log(args, t("cli-emergency-spared", pid=p.pid, name=p.name))
+        for p in (p for p in targets if p not in spared):
+            _, result = kill_process(p.pid, force=True, source=source)
+            mb = f"{p.rss_mb:.1f}"
+            message = t("cli-emergency-kill", pid=p.pid, name=p.name, mb=mb)
+            _log(args, f"{message}: {result}")
+        return 1
+
+    return _repeat_check(args, check)
+
+
+def _watch_log(
+    args: argparse.Namespace,
+    event: str,
+    message: str,
+    proc: ProcessInfo | None = None,
+    **fields: object,
+) -> None:
+    """Log one ``watch`` event as text or, with ``--log-format json``, JSON.
+
+    Args:
+        args: Parsed CLI arguments.
+        event: Event name, e.g. ``kill`` or ``would-kill``.
+        message: Text of the event.
+        proc: Process the event is about.
+        **fields: Extra fields of the JSON record.
+    """
+    if args.log_format != "json":
+        _log(args, message)
+        return
+    record: dict[str, object] = {
+        "time": datetime.now(tz=UTC).astimezone().isoformat(timespec="seconds"),
+        "event": event,
+    }
+    if proc is not None:
+        record |= {
+            "pid": proc.pid,
+            "name": proc.name,
+            "rss_mb": round(proc.rss_mb, 2),
+            "cwd": proc.cwd,
+        }
+    record |= fields | {"message": message}
+    with output_sink(args.log, append=True):
+        sys.stdout.write(json.dumps(record) + "\n")
+
+
+def cmd_watch(args: argparse.Namespace) -> int:
+    """Kill processes matching a filter preset every ``--interval``.
+
+    Returns:
+        int: Exit code (0 when stopped by ``--count`` or Ctrl-C).
+
+    Raises:
+        ProccleanError: With ``INVALID_ARGUMENT`` without ``--filter``.
+    """
+    if args.list_filters:
+        return _list_filters(args.log_format == "json")
+    preset = _preset(args)
+    if preset is None:
+        raise ProccleanError(ErrorCode.INVALID_ARGUMENT, "watch requires --filter")
+    source = get_source(args)
+    interval = f"{args.interval:g}s"
+    key = "cli-watch-start-dry" if args.preview else "cli-watch-start"
+    message = t(key, filter=preset.name, interval=interval)
+    _watch_log(args, "start", message, filter=preset.name, dry_run=args.preview)
+    collector = ProcessCollector(source)
+    spared: set[int] = set()  # Logged once, not on every check
+
+    def check(_tick: int) -> None:
+        own = os.getpid()
+        procs = get_filtered_processes(args, source, collector=collector)
+        targets = _skip_vms(args, [p for p in procs if p.pid != own])
+        risks = _spare_data_loss(args, targets)
+        for p in risks:
+            if p.pid not in spared:
+                message = t("cli-watch-spared", pid=p.pid, name=p.name)
+                _watch_log(args, "spared", message, p)
+            spared.add(p.pid)
+        for p in (p for p in targets if p not in risks):
+            mb = f"{p.rss_mb:.1f}"
+            if args.preview:
+                message = t("cli-watch-would-kill", pid=p.pid, name=p.name, mb=mb)
+                _watch_log(args, "would-kill", message, p)
+                continue
+            ok, result = kill_process(p.pid, force=args.force, source=source)
+            message = t("cli-watch-kill", pid=p.pid, name=p.name, mb=mb)
+            _watch_log(args, "kill", f"{message}: {result}", p, ok=ok)
+
+    try:
+        run_every(args.interval, check, count=args.count)
+    except KeyboardInterrupt:
+        pass
+    _watch_log(args, "stop", t("cli-watch-stop"))
+    return 0
+
+
+def cmd_hook(args: argparse.Namespace) -> int:
+    """Print a shell hook that cleans up after leaving a directory.
+
+    Returns:
+        int: Exit code (0 on success).
+    """
+    # Plain write: rich would take "[ -n ... ]" tests for markup
+    sys.stdout.write(render_hook(args.shell, args.action, args.on_exit))
+    return 0
+
+
+def cmd_run(args: argparse.Namespace) -> int:
+    """Run a command, then report or kill the processes it left behind.
+
+    Survivors are reported on stderr so the command's own output stays clean.
+
+    Returns:
+        int: The command's exit status (128 + N if killed by signal N).
+    """
+    source = get_source(args)
+    result = run_tracked(args.run_command, source)
+    if result.survivors:
+        command = shlex.join(args.run_command)
+        count = len(result.survivors)
+        print(t("cli-run-survivors", count=count, command=command), file=sys.stderr)
+        for p in result.survivors:
+            mb = f"{p.rss_mb:.1f}"
+            line = t("cli-run-survivor", pid=p.pid, name=p.name, mb=mb)
+            print(f"  {line}", file=sys.stderr)
+        if args.kill and not _check_data_loss(args, result.survivors):
+            print(t("cli-aborted"), file=sys.stderr)
+        elif args.kill:
+            pids = [p.pid for p in result.survivors]
+            for _, success, msg in kill_processes(pids, args.force, source):
+                status = t("cli-result-ok" if success else "cli-result-failed")
+                print(f"[{status}] {msg}", file=sys.stderr)
+        else:
+            print(t("cli-run-kill-hint"), file=sys.stderr)
+    if result.returncode < 0:
+        return 128 - result.returncode
+    return result.returncode
+
+
+def cmd_verify(args: argparse.Namespace) -> int:
+    """Check the process table against an expected-processes manifest.
+
+    Returns:
+        int: Exit code (0 if the manifest is satisfied, 1 otherwise).
+    """
+    manifest = load_manifest(Path(args.manifest))
+    procs = get_process_list(min_memory_mb=0, source=get_source(args))
+    result = verify_manifest(procs, manifest)
+
+    if args.format == "json":
+        data = {
+            "ok": result.ok,
+            "missing": [
+                {"rule": rule.describe(), "found": found, "min": rule.min_count}
+                for rule, found in result.missing
+            ],
+            "forbidden": [
+                {"rule": rule.describe(), "pids": [p.pid for p in matched]}
+                for rule, matched in result.present
+            ],
+        }
+        print(json.dumps(data, indent=2))
+        return 0 if result.ok else 1
+
+    for rule, found in result.missing:
+        print(
+            t(
+                "cli-verify-missing",
+                rule=rule.describe(),
+                found=found,
+                min=rule.min_count,
+            )
+        )
+    for rule, matched in result.present:
+        pids = ", ".join(str(p.pid) for p in matched)
+        print(t("cli-verify-present", rule=rule.describe(), pids=pids))
+    if result.ok:
+        print(t("cli-verify-ok", count=result.checked))
+        return 0
+    violated = len(result.missing) + len(result.present)
+    print(t("cli-verify-failed", count=violated, total=result.checked))
+    return 1
+
+
+def _print_jobs(jobs: list, fmt: str, redact: bool = True) -> None:
+    """Print jobs like the shell's ``jobs -l``, or as JSON.
+
+    Args:
+        jobs: Jobs in job-number order.
+        fmt: "table" or "json".
+        redact: Mask secrets in command lines.
+    """
+    if redact:
+        jobs = [redact_process(p) for p in jobs]
+    if fmt == "json":
+        data = [
+            {
+                "job": n,
+                "pid": p.pid,
+                "name": p.name,
+                "rss_mb": round(p.rss_mb, 2),
+                "status": p.status,
+                "cmdline": p.cmdline,
+                "argv": p.argv,
+            }
+            for n, p in enumerate(jobs, 1)
+        ]
+        print(json.dumps(data, indent=2))
+        return
+    if not jobs:
+        print(t("cli-no-jobs"))
+    for n, p in enumerate(jobs, 1):
+        mb = f"{p.rss_mb:.1f}"
+        item = t("cli-job-item", pid=p.pid, status=p.status, mb=mb, cmd=p.cmdline)
+        print(f"[{n}] {item}")
+
+
+def cmd_jobs(args: argparse.Namespace) -> int:
+    """List (and optionally kill) jobs of the current shell session.
+
+    Returns:
+        int: Exit code (0 on success, 1 if a kill failed or was aborted).
+    """
+    source = get_source(args)
+    jobs = get_session_jobs(args.session, source)
+    if args.kill is None:
+        _print_jobs(jobs, args.format, redact=not args.no_redact)
+        return 0
+
+    targets = jobs if not args.kill else []
+    for n in args.kill:
+        if 1 <= n <= len(jobs):
+            targets.append(jobs[n - 1])
+        else:
+            print(t("cli-job-not-found", job=n))
+    if not targets:
+        print(t("cli-no-jobs"))
+        return 1 if args.kill else 0
+
+    if not _confirm_kill(args, targets, source) or not _check_data_loss(
+        args, targets
+    ):
+        print(t("cli-aborted"))
+        return 1
+    results = kill_processes([p.pid for p in targets], force=args.force, source=source)
+    exit_code = 0
+    for _, success, msg in results:
+        status = t("cli-result-ok" if success else "cli-result-failed")
+        print(f"[{status}] {msg}")
+        if not success:
+            exit_code = 1
+    return exit_code
+
+
+def _print_checks(checks: tuple[Capability, ...]) -> None:
+    """Print doctor checks as aligned lines, with what to do about misses."""
+    width = max(len(c.key) for c in checks)
+    indent = " " * (width + 12)
+    for cap in checks:
+        status = f"[{t('cli-doctor-ok' if cap.ok else 'cli-doctor-missing')}]"
+        print(f"{status:<10}{cap.key:<{width}}  {cap.detail}")
+        if not cap.ok and cap.columns:
+            print(f"{indent}{t('cli-doctor-columns', columns=', '.join(cap.columns))}")
+        if not cap.ok and cap.fix:
+            print(f"{indent}{t('cli-doctor-fix', fix=cap.fix)}")
+
+
+def cmd_doctor(args: argparse.Namespace) -> int:
+    """Report what procclean can read on this system, and how it is set up.
+
+    Returns:
+        int: Exit code (1 if a restricted environment hides something or the
+        config is invalid).
+    """
+    report = capability_report()
+    environment = environment_checks(CONFIG_VALIDATORS)
+    failed = report.restricted or any(c.restricted and not c.ok for c in environment)
+    exit_code = 1 if failed else 0
+    versions = {"version": version("procclean"), "python": platform.python_version()}
+    if args.format == "json":
+        data = report.to_dict() | versions
+        data["environment"] = [c.to_dict() for c in environment]
+        print(json.dumps(data, indent=2))
+        return exit_code
+    system = f"{platform.system()} {platform.release()}"  # No /proc needed
+    print(t("cli-doctor-version", **versions))
+    print(t("cli-doctor-header", platform=system, user=report.user, uid=report.euid))
+    print()
+    _print_checks(report.capabilities)
+    print(f"\n{t('cli-doctor-environment')}")
+    _print_checks(environment)
+    if not report.missing and all(c.ok for c in environment):
+        print(f"\n{t('cli-doctor-fine')}")
+    return exit_code
+
+
+def cmd_config_show(args: argparse.Namespace) -> int:
+    """Show the effective configuration and where each setting comes from.
+
+    Returns:
+        int: Exit code (0 on success).
+    """
+    path = config_path()
+    settings = effective_settings()
+    if args.format == "json":
+        data = {
+            "path": str(path),
+            "exists": path.is_file(),
+            "settings": [s.to_dict() for s in settings],
+        }
+        print(json.dumps(data, indent=2))
+        return 0
+    key = "cli-config-file" if path.is_file() else "cli-config-no-file"
+    print(t(key, path=str(path)))
+    print()
+    width = max(len(s.key) for s in settings)
+    for setting in settings:
+        source = setting.source
+        if source == BUILTIN_SOURCE:
+            source = t("cli-config-default")
+        value = json.dumps(setting.value)  # TOML spells scalars and arrays alike
+        # Written directly: rich would take [sections] in values for markup
+        sys.stdout.write(f"{setting.key:<{width}} = {value}  # {source}\n")
+    return 0
+
+
+def _print_config_problems(path: Path, problems: list[ProccleanError]) -> None:
+    """List what is wrong with a config file."""
+    print(t("cli-config-problems", path=str(path), count=len(problems)))
+    for problem in problems:
+        sys.stdout.write(f"  - {problem.message}\n")
+
+
+def cmd_config_validate(args: argparse.Namespace) -> int:
+    """Check every setting of the config file.
+
+    Returns:
+        int: Exit code (1 if anything is malformed or unknown).
+    """
+    path = config_path()
+    problems = config_problems(CONFIG_VALIDATORS)
+    if args.format == "json":
+        data = {
+            "path": str(path),
+            "exists": path.is_file(),
+            "valid": not problems,
+            "problems": [e.to_dict() for e in problems],
+        }
+        print(json.dumps(data, indent=2))
+    elif problems:
+        _print_config_problems(path, problems)
+    elif path.is_file():
+        print(t("cli-config-valid", path=str(path)))
+    else:
+        print(t("cli-config-none", path=str(path)))
+    return 1 if problems else 0
+
+
+def _edit_again() -> bool:
+    """Ask whether to reopen a config that failed validation.
+
+    Returns:
+        bool: True unless declined or stdin is not interactive.
+    """
+    if not sys.stdin.isatty():
+        return False
+    try:
+        response = input(f"{t('cli-config-edit-again')} ").lower()
+    except EOFError:
+        return False
+    return response in {"", "y", "yes"}
+
+
+def cmd_config_edit(_args: argparse.Namespace) -> int:
+    """Edit the config file in ``$VISUAL``/``$EDITOR``, validating on save.
+
+    The editor works on a copy next to the file, which only replaces it once
+    it is valid, so a typo never breaks the next run.
+
+    Returns:
+        int: Exit code (0 if saved or unchanged, 1 if discarded).
+
+    Raises:
+        ProccleanError: With ``INVALID_ARGUMENT`` if the editor cannot be run.
+    """
+    path = config_path()
+    editor = shlex.split(os.environ.get("VISUAL") or os.environ.get("EDITOR") or "vi")
+    original = path.read_text(encoding="utf-8") if path.is_file() else ""
+    path.parent.mkdir(parents=True, exist_ok=True)
+    fd, name = tempfile.mkstemp(prefix=".config-", suffix=".toml", dir=path.parent)
+    draft = Path(name)
+    try:
+        with os.fdopen(fd, "w", encoding="utf-8") as f:
+            f.write(original)
+        while True:
+            try:
+                code = subprocess.run([*editor, str(draft)], check=False).returncode
+            except OSError as e:
+                msg = f"Cannot run editor {shlex.join(editor)}: {e.strerror}"
+                raise ProccleanError(
+                    ErrorCode.INVALID_ARGUMENT, msg, editor=editor
+                ) from e
+            if code != 0:
+                print(t("cli-config-editor-failed", code=code, path=str(path)))
+                return 1
+            if draft.read_text(encoding="utf-8") == original:
+                print(t("cli-config-unchanged"))
+                return 0
+            if not (problems := config_problems(CONFIG_VALIDATORS, draft)):
+                if path.exists():
+                    shutil.copymode(path, draft)
+                draft.replace(path)
+                load_config.cache_clear()
+                print(t("cli-config-saved", path=str(path)))
+                return 0
+            _print_config_problems(path, problems)
+            if not _edit_again():
+                print(t("cli-config-discarded", path=str(path)))
+                return 1
+    finally:
+        draft.unlink(missing_ok=True)
+
+
+def cmd_columns(args: argparse.Namespace) -> int:
+    """List the columns ``--columns`` takes.
+
+    Returns:
+        int: Exit code (0 on success).
+    """
+    specs = [spec.to_dict() for spec in COLUMNS.values()]
+    if args.format == "json":
+        print(json.dumps(specs, indent=2))
+        return 0
+    headers = [
+        t(f"cli-columns-{name}")
+        for name in ("key", "header", "type", "slow", "description")
+    ]
+    rows = [
+        [
+            spec["key"] + ("*" if spec["default"] else ""),
+            spec["header"],
+            spec["type"],
+            t("cli-columns-yes") if spec["slow"] else "",
+            spec["description"],
+        ]
+        for spec in specs
+    ]
+    widths = [max(len(row[i]) for row in [headers, *rows]) for i in range(4)]
+    for row in [headers, *rows]:
+        cells = [f"{cell:<{width}}" for cell, width in zip(row, widths, strict=False)]
+        # Written directly: rich would take [orphan] and the like for markup
+        sys.stdout.write("  ".join([*cells, row[4]]).rstrip() + "\n")
+    print(f"\n{t('cli-columns-note')}")
+    return 0
+
+
+def _save_columns(entries: list[str]) -> list[ProccleanError]:
+    """Write ``[defaults] columns`` to the config file, if it stays valid.
+
+    Returns:
+        list[ProccleanError]: What is wrong with the result; empty once saved.
+    """
+    path = config_path()
+    text = path.read_text(encoding="utf-8") if path.is_file() else ""
+    return save_config(
+        set_setting(text, "defaults.columns", ",".join(entries)), CONFIG_VALIDATORS
+    )
+
+
+def cmd_fmt(args: argparse.Namespace) -> int:
+    """Design the columns and format of ``list``, and spell them as options.
+
+    Starts from ``--columns`` or the configured default columns. With
+    ``--interactive``, columns, widths, units and the format are toggled over
+    a live preview of the largest processes first.
+
+    Returns:
+        int: Exit code (1 if cancelled or the config could not be saved).
+
+    Raises:
+        ProccleanError: With ``INVALID_ARGUMENT`` if ``--interactive`` is not
+            run in a terminal.
+        SystemExit: If ``--interactive`` is used without the TUI installed.
+    """
+    design = ListDesign.from_columns(
+        parse_columns(args.columns) or default_columns(), args.format
+    )
+    if args.interactive:
+        if not (sys.stdin.isatty() and sys.stdout.isatty()):
+            msg = "fmt --interactive needs a terminal"
+            raise ProccleanError(ErrorCode.INVALID_ARGUMENT, msg)
+        try:
+            from procclean.tui import ListDesignerApp  # noqa: PLC0415
+        except ModuleNotFoundError as e:
+            raise missing_extra(e) from e
+
+        procs = get_process_list(
+            min_memory_mb=0, source=get_source(args), fields=columns_fields(COLUMNS)
+        )
+        app = ListDesignerApp(design, procs[:FMT_PREVIEW_ROWS], _save_columns)
+        if app.run() is None:
+            print(t("cli-fmt-cancelled"))
+            return 1
+    if args.save:
+        if problems := _save_columns(design.entries):
+            _print_config_problems(config_path(), problems)
+            return 1
+        print(t("cli-config-saved", path=str(config_path())))
+    sys.stdout.write(f"{design.command()}\n")
+    return 0
+
+
+def cmd_history(args: argparse.Namespace) -> int:
+    """Show past kills from the audit log.
+
+    Returns:
+        int: Exit code (0 on success).
+    """
+    records = filter_history(
+        read_history(),
+        since=args.since,
+        pid=args.pid,
+        name=args.name,
+        origin=args.origin,
+        failed=args.failed,
+    )
+    if args.limit > 0:
+        records = records[-args.limit :]
+    if args.format == "json":
+        print(json.dumps([r.to_dict() for r in records], indent=2))
+        return 0
+    if not records:
+        path = audit_log_path()
+        print(t("cli-history-empty", path=path) if path else t("cli-history-off"))
+    for record in records:
+        when = datetime.fromisoformat(record.time).astimezone()
+        item = t(
+            "cli-history-item",
+            time=when.strftime("%Y-%m-%d %H:%M:%S"),
+            status=t("cli-result-ok" if record.ok else "cli-result-failed"),
+            signal=record.signal,
+            pid=record.pid,
+            name=record.name,
+            mb=f"{record.rss_mb:.1f}",
+        )
+        print(item)
+        if record.cmdline:
+            print(f"    {record.cmdline}")
+        if not record.ok:
+            print(f"    {record.result}")
+        origin = t("cli-history-origin", origin=record.origin, filter=record.filter)
+        note = f" {t('cli-history-simulated')}" if record.simulated else ""
+        print(f"    {origin if record.filter else record.origin}{note}")
+    return 0
+
+
+def cmd_memory(args: argparse.Namespace) -> int:
+    """Show memory summary command.
+
+    Returns:
+        int: Exit code (0 on success).
+    """
+    mem = get_memory_summary()
+    oom = oom_status()
+    # VMs often run as libvirt-qemu or root
+    source = get_source(args)
+    procs = get_process_list(filter_user=ALL_USERS, min_memory_mb=0, source=source)
+    vms = exclude_vms(procs)[1]
+    header = _report_header(args)
+    per_process = getattr(args, "per_process", False)
+    top = top_by_pss(procs, args.limit, source) if per_process else []
+
+    if args.format == "json":
+        last = oom.last and {
+            "pid": oom.last.pid,
+            "name": oom.last.name,
+            "time": oom.last.time.isoformat(),
+        }
+        data = {
+            **mem,
+            "oom_kills": oom.count,
+            "last_oom_kill": last,
+            "vms": [
+                {
+                    "pid": p.pid,
+                    "name": p.vm,
+                    "rss_mb": round(p.rss_mb, 2),
+                    "hugetlb_mb": round(p.hugetlb_mb or 0.0, 2),
+                }
+                for p in vms
+            ],
+        }
+        if per_process:
+            data["processes"] = [_memory_counters(p) for p in top]
+        if header:
+            data = {"header": header.to_dict(), **data}
+        print(json.dumps(data, indent=2))
+    else:
+        if header:
+            print(f"{format_header(header, args.format)}\n")
+        print(f"Total:  {mem['total_gb']:.2f} GB")
+        print(f"Used:   {mem['used_gb']:.2f} GB ({mem['percent']:.1f}%)")
+        print(f"Shared: {mem['shared_gb']:.2f} GB")
+        print(f"Free:   {mem['free_gb']:.2f} GB")
+        print(f"Swap:   {mem['swap_used_gb']:.2f} / {mem['swap_total_gb']:.2f} GB")
+        load = " ".join(f"{avg:.2f}" for avg in mem["load_avg"])
+        print(f"Load:   {load} ({mem['cpu_count']} CPUs)")
+        print(f"Uptime: {format_uptime(mem['uptime_seconds'])}")
+        if summary := describe_oom(oom):
+            print(f"OOM:    {summary}")
+        if vms:
+            total_gb = sum(p.memory_mb for p in vms) / 1024
+            huge_gb = sum(p.hugetlb_mb or 0.0 for p in vms) / 1024
+            summary = t(
+                "cli-memory-vms",
+                count=len(vms),
+                gb=f"{total_gb:.2f}",
+                huge_gb=f"{huge_gb:.2f}",
+            )
+            print(f"VMs:    {summary}")
+        if top:
+            print(f"\n{format_output(top, 'table', columns=MEMORY_COLUMNS)}")
+
+    return 0
+
+
+def _memory_counters(proc: ProcessInfo) -> dict[str, object]:
+    """Serialize the memory counters of a process for JSON output.
+
+    Returns:
+        dict[str, object]: PID, name and MB by counter; None where unread.
+    """
+    counters = {
+        "rss_mb": proc.rss_mb,
+        "pss_mb": proc.pss_mb,
+        "uss_mb": proc.uss_mb,
+        "shared_mb": proc.shared_mb,
+        "swap_mb": proc.swap_mb,
+    }
+    return {
+        "pid": proc.pid,
+        "name": proc.name,
+        **{key: None if mb is None else round(mb, 2) for key, mb in counters.items()},
+    }
+
+
+def cmd_mem_detail(args: argparse.Namespace) -> int:
+    """Show the memory breakdown of one process.
+
+    RSS counts pages shared with other processes in full; PSS splits them
+    evenly and USS leaves them out, so USS is what killing it frees.
+
+    Returns:
+        int: Exit code (0 on success).
+
+    Raises:
+        ProccleanError: With ``PROCESS_NOT_FOUND`` for an unknown PID.
+    """
+    source = get_source(args)
+    procs = get_process_list(filter_user=ALL_USERS, min_memory_mb=0, source=source)
+    proc = next((p for p in procs if p.pid == args.pid), None)
+    if proc is None:
+        msg = f"Process {args.pid} not found"
+        raise ProccleanError(ErrorCode.PROCESS_NOT_FOUND, msg, pid=args.pid)
+    attach_smaps([proc], source)
+    data = _memory_counters(proc)
+    if args.format == "json":
+        print(json.dumps(data, indent=2))
+        return 0
+
+    print(f"{proc.name} (PID {proc.pid})")
+    labels = {
+        "rss_mb": "RSS",
+        "pss_mb": "PSS",
+        "uss_mb": "USS",
+        "shared_mb": "Shared",
+        "swap_mb": "Swap",
+    }
+    for key, label in labels.items():
+        mb = data[key]
+        print(f"{label + ':':<8}{'?' if mb is None else f'{mb:.1f} MB'}")
+    if proc.pss_mb is None:
+        print(t("cli-mem-detail-unreadable"))
+    return 0
+
+
+def cmd_cpu(args: argparse.Namespace) -> int:
+    """Show per-core CPU usage and the busiest processes.
+
+    One interval serves both: the per-core counters are started, then the
+    process scan samples, waits and reads its own usage.
+
+    Returns:
+        int: Exit code (0 on success).
+    """
+    source = get_source(args)
+    core_usage()  # Start the per-core interval
+    procs = get_process_list(
+        "cpu",
+        filter_user=ALL_USERS,
+        min_memory_mb=0,
+        source=source,
+        fields=FieldMask.CPU,
+        cpu_interval=args.interval,
+    )
+    usage = core_usage()
+    busiest = [p for p in procs if p.cpu_percent > 0][: args.limit]
+
+    if args.format == "json":
+        data = {
+            **usage.to_dict(),
+            "busiest": [
+                {"pid": p.pid, "name": p.name, "cpu_percent": round(p.cpu_percent, 1)}
+                for p in busiest
+            ],
+        }
+        print(json.dumps(data, indent=2))
+        return 0
+
+    average = f"{usage.average:.1f}"
+    print(f"CPU:    {average}% average over {len(usage.cores)} cores")
+    print(f"Cores:  {usage.strip()}")
+    for number, percent in enumerate(usage.cores):
+        bar = "#" * round(percent / 5)
+        print(f"  cpu{number:<3} {percent:5.1f}% {bar}")
+    cores = ",".join(str(n) for n in usage.pinned)
+    print(t(f"cli-cpu-{usage.state}", cores=cores, average=average))
+    if busiest:
+        print("Busiest:")
+        for p in busiest:
+            print(f"  {p.cpu_percent:5.1f}%  {p.name} (PID {p.pid})")
+    return 0
+
+
+def _status_state(percent: float) -> str:
+    """Classify memory usage for status bar styling.
+
+    Returns:
+        str: One of "critical", "warning" or "normal".
+    """
+    if percent >= STATUS_CRITICAL_PERCENT:
+        return "critical"
+    if percent >= STATUS_WARNING_PERCENT:
+        return "warning"
+    return "normal"
+
+
+def _format_status(fmt: str, percent: float, count: int, reclaim_mb: float) -> str:
+    """Render a compact status line for desktop status bars.
+
+    Args:
+        fmt: One of "waybar", "i3status" or "plain".
+        percent: Memory usage percentage.
+        count: Number of killable processes.
+        reclaim_mb: Memory held by killable processes in MB.
+
+    Returns:
+        str: A single line of text or JSON.
+    """
+    text = f"MEM {percent:.0f}%"
+    if count:
+        text += f" | {count} killable ({reclaim_mb:.0f} MB)"
+    state = _status_state(percent)
+    match fmt:
+        case "waybar":
+            tooltip = f"{count} killable process(es), {reclaim_mb:.1f} MB reclaimable"
+            return json.dumps({
+                "text": text,
+                "tooltip": tooltip,
+                "class": state,
+                "percentage": round(percent),
+            })
+        case "i3status":
+            colors = {"critical": "#ff5555", "warning": "#f1fa8c", "normal": None}
+            block: dict[str, str] = {"full_text": text, "name": "procclean"}
+            if color := colors[state]:
+                block["color"] = color
+            return json.dumps(block)
+        case _:
+            return text
+
+
+def cmd_status(args: argparse.Namespace) -> int:
+    """Show a single-line status for status bars command.
+
+    Returns:
+        int: Exit code (0 on success).
+    """
+    mem = get_memory_summary()
+    source = get_source(args)
+    procs = get_process_list(min_memory_mb=args.min_memory, source=source)
+    killable = filter_killable(procs, source=source)
+    reclaim_mb = sum(p.rss_mb for p in killable)
+    print(_format_status(args.format, mem["percent"], len(killable), reclaim_mb))
+    return 0
+
+
+def _select_wine_group(groups: list[WineGroup], name: str) -> WineGroup:
+    """Pick the prefix named by ``--shutdown``.
+
+    Returns:
+        WineGroup: The group whose prefix, application or Steam app ID matches.
+
+    Raises:
+        ProccleanError: With ``INVALID_ARGUMENT`` if none or several match.
+    """
+    wanted = name.rstrip("/").lower()
+    matches = [
+        g
+        for g in groups
+        if wanted in {g.prefix.lower(), g.app, (g.steam_app_id or "").lower()}
+    ]
+    if len(matches) != 1:
+        reason = "No Wine prefix matches" if not matches else "Several prefixes match"
+        raise ProccleanError(
+            ErrorCode.INVALID_ARGUMENT,
+            f"{reason} {name!r}",
+            prefixes=[g.prefix for g in matches or groups],
+        )
+    return matches[0]
+
+
+def cmd_wine(args: argparse.Namespace) -> int:
+    """Group Wine/Proton processes by prefix, or shut a prefix down.
+
+    Returns:
+        int: Exit code (0 on success, 1 if aborted or the shutdown failed).
+    """
+    source = get_source(args)
+    procs = get_process_list(min_memory_mb=0, source=source)
+    groups = find_wine_groups(procs, source)
+    if args.shutdown is not None:
+        group = _select_wine_group(groups, args.shutdown)
+        prompt = t("cli-wine-confirm", app=group.app, prefix=group.prefix)
+        if not args.yes and sys.stdin.isatty():
+            with terminal():
+                try:
+                    confirmed = input(f"{prompt} ").lower() in {"y", "yes"}
+                except EOFError:
+                    confirmed = True  # Non-interactive
+            if not confirmed:
+                print(t("cli-aborted"))
+                return 1
+        success, msg = shutdown_prefix(group, source)
+        status = t("cli-result-ok" if success else "cli-result-failed")
+        print(f"[{status}] {msg}")
+        if isinstance(source, SimulatedSource):
+            print(f"\n{t('cli-simulated')}")
+        return 0 if success else 1
+
+    if args.format == "json":
+        data = [
+            {
+                "prefix": g.prefix,
+                "app": g.app,
+                "steam_app_id": g.steam_app_id,
+                "rss_mb": round(g.rss_mb, 2),
+                "processes": [
+                    {"pid": p.pid, "name": p.name, "rss_mb": round(p.rss_mb, 2)}
+                    for p in g.procs
+                ],
+            }
+            for g in groups
+        ]
+        print(json.dumps(data, indent=2))
+        return 0
+    if not groups:
+        print(t("cli-no-wine"))
+        return 0
+    for g in groups:
+        header = t(
+            "cli-wine-group",
+            app=g.app,
+            prefix=g.prefix,
+            count=len(g.procs),
+            mb=f"{g.rss_mb:.1f}",
+        )
+        print(f"\n{header}")
+        for p in g.procs:
+            item = t("cli-confirm-item", pid=p.pid, name=p.name, mb=f"{p.rss_mb:.1f}")
+            print(f"  {item}")
+    print(f"\n{t('cli-wine-hint')}")
+    return 0
+
+
+def cmd_plugins(args: argparse.Namespace) -> int:
+    """List the external ``procclean-*`` subcommands on PATH.
+
+    Returns:
+        int: Exit code (0 on success).
+    """
+    plugins = find_plugins()
+    if args.format == "json":
+        data = [{"name": p.name, "path": str(p.path)} for p in plugins]
+        print(json.dumps(data, indent=2))
+        return 0
+    if not plugins:
+        print(t("cli-no-plugins"))
+        return 0
+    width = max(len(p.name) for p in plugins)
+    for p in plugins:
+        print(f"{p.name:<{width}}  {p.path}")
+    return 0
+
+
+def cmd_daemon(args: argparse.Namespace) -> int:
+    """Serve warm process snapshots until interrupted.
+
+    Returns:
+        int: Exit code (0 when stopped with Ctrl-C).
+
+    Raises:
+        ProccleanError: With ``INVALID_ARGUMENT`` without a private place for
+            the socket.
+    """
+    path = Path(args.socket).expanduser() if args.socket else socket_path()
+    if path is None:
+        msg = "No private directory for the socket: set XDG_RUNTIME_DIR or --socket"
+        raise ProccleanError(ErrorCode.INVALID_ARGUMENT, msg)
+    daemon = SnapshotDaemon(path, interval=args.interval)
+    print(t("cli-daemon-listening", interval=f"{args.interval:g}", path=path))
+    try:
+        daemon.serve()
+    except KeyboardInterrupt:
+        pass
+    return 0
+
+
+def cmd_export(args: argparse.Namespace) -> int:
+    """Serve Prometheus metrics until interrupted, or print them once.
+
+    Returns:
+        int: Exit code (0 when stopped with Ctrl-C).
+    """
+    host, port = args.listen
+    exporter = MetricsExporter(host, port, top=args.top, source=get_source(args))
+    if args.once:
+        sys.stdout.write(exporter.collect())
+        return 0
+    address = f"[{host}]:{port}" if ":" in host else f"{host or '*'}:{port}"
+    print(t("cli-export-listening", url=f"http://{address}/metrics"))
+    try:
+        exporter.serve()
+    except KeyboardInterrupt:
+        pass
+    return 0
+
+
+def cmd_polkit(args: argparse.Namespace) -> int:
+    """Print the polkit action that authorizes ``procclean-helper``.
+
+    Returns:
+        int: Exit code (0 on success).
+
+    Raises:
+        ProccleanError: With ``INVALID_ARGUMENT`` if the helper is not found,
+            or ``--helper`` is not an absolute path.
+    """
+    helper = args.helper or helper_path()
+    if helper is None:
+        msg = "procclean-helper is not installed; pass its path with --helper"
+        raise ProccleanError(ErrorCode.INVALID_ARGUMENT, msg)
+    if not Path(helper).is_absolute():
+        msg = f"--helper must be an absolute path: {helper}"
+        raise ProccleanError(ErrorCode.INVALID_ARGUMENT, msg)
+    sys.stdout.write(policy_xml(helper))
     return 0
@@ -0,0 +1,102 @@
+"""Shell hooks that clean up after leaving a project directory.
+
+``procclean hook SHELL`` prints a snippet to ``eval`` (or ``source``) from the
+shell's rc file. Whenever the shell leaves a directory for one outside its
+subtree, the hook runs ``procclean kill --cwd DIR --filter killable`` for the
+directory left behind. ``$HOME`` and ``/`` are never cleaned.
+"""
+
+HOOK_SHELLS = ("bash", "zsh", "fish")
+
+# Extra ``procclean kill`` flags per --action
+HOOK_ACTIONS: dict[str, str] = {
+    "preview": " --preview",
+    "confirm": "",
+    "yes": " --yes",
+}
+
+_BASH = """\
+# procclean: clean up after leaving a project directory
+__procclean_cleanup() {{
+    case "$1" in "$HOME" | / | "") return ;; esac
+    command procclean kill --cwd "$1" --filter killable --quiet{flags}
+}}
+__procclean_prompt() {{
+    if [ -n "$__procclean_dir" ] && [ "$PWD" != "$__procclean_dir" ]; then
+        case "$PWD/" in
+            "$__procclean_dir"/*) ;;
+            *) __procclean_cleanup "$__procclean_dir" ;;
+        esac
+    fi
+    __procclean_dir="$PWD"
+}}
+PROMPT_COMMAND="__procclean_prompt${{PROMPT_COMMAND:+;$PROMPT_COMMAND}}"
+"""
+
+_BASH_EXIT = """\
+trap '__procclean_cleanup "$PWD"' EXIT
+"""
+
+_ZSH = """\
+# procclean: clean up after leaving a project directory
+__procclean_cleanup() {{
+    case "$1" in "$HOME" | / | "") return ;; esac
+    command procclean kill --cwd "$1" --filter killable --quiet{flags}
+}}
+__procclean_chpwd() {{
+    case "$PWD/" in "$OLDPWD"/*) return ;; esac
+    __procclean_cleanup "$OLDPWD"
+}}
+autoload -Uz add-zsh-hook
+add-zsh-hook chpwd __procclean_chpwd
+"""
+
+_ZSH_EXIT = """\
+__procclean_exit() {{ __procclean_cleanup "$PWD"; }}
+add-zsh-hook zshexit __procclean_exit
+"""
+
+_FISH = """\
+# procclean: clean up after leaving a project directory
+function __procclean_cleanup --argument-names dir
+    contains -- "$dir" "$HOME" / ""; and return
+    command procclean kill --cwd "$dir" --filter killable --quiet{flags}
+end
+set -g __procclean_dir $PWD
+function __procclean_pwd --on-variable PWD
+    string match -q -- "$__procclean_dir/*" "$PWD/"
+    or __procclean_cleanup $__procclean_dir
+    set -g __procclean_dir $PWD
+end
+"""
+
+_FISH_EXIT = """\
+function __procclean_exit --on-event fish_exit
+    __procclean_cleanup $PWD
+end
+"""
+
+_TEMPLATES: dict[str, tuple[str, str]] = {
+    "bash": (_BASH, _BASH_EXIT),
+    "zsh": (_ZSH, _ZSH_EXIT),
+    "fish": (_FISH, _FISH_EXIT),
+}
+
+
+def render_hook(shell: str, action: str = "preview", on_exit: bool = False) -> str:
+    """Build the hook snippet for a shell.
+
+    Args:
+        shell: One of ``HOOK_SHELLS``.
+        action: One of ``HOOK_ACTIONS``: only list the leftovers, ask before
+            killing them, or kill them without asking.
+        on_exit: Also clean the current directory when the shell exits.
+
+    Returns:
+        str: Shell code to evaluate at startup.
+    """
+    on_leave, at_exit = _TEMPLATES[shell]
+    snippet = on_leave.format(flags=HOOK_ACTIONS[action])
+    if on_exit:
+        snippet += at_exit.format()
+    return snippet
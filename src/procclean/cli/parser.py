@@ -1,22 +1,613 @@
 """CLI argument parser."""
 
 import argparse
+import json
+import os
+import shlex
+import signal
+import sys
 from importlib.metadata import version
+from itertools import pairwise
+from pathlib import Path
+from typing import Any, NoReturn
 
-from procclean.formatters import get_available_columns
+from procclean.core import (
+    AUDIT_ORIGINS,
+    CPU_SAMPLE_SECONDS,
+    DAEMON_INTERVAL,
+    DEFAULT_ENV_PATTERNS,
+    ESCALATE_TIMEOUT_SECONDS,
+    EXPORT_PORT,
+    EXPORT_TOP,
+    FILTER_CMD_FIELDS,
+    FILTER_CMD_JOBS,
+    FILTERS,
+    GROUP_BY,
+    HARDEN_ENV,
+    HELPER_NAME,
+    KEEP_CHOICES,
+    LIST_WATCH_SECONDS,
+    NICE_RANGE,
+    NO_DAEMON_ENV,
+    NO_REDACT_ENV,
+    POLKIT_ENV,
+    RECORD_ENV,
+    RENICE_NICE,
+    REPLAY_ENV,
+    SCOPE_ENV,
+    SCOPES,
+    SIMULATE_ENV,
+    WATCH_INTERVAL,
+    ErrorCode,
+    MatchMode,
+    Plugin,
+    ProccleanError,
+    SessionRecorder,
+    active_timings,
+    audit_origin,
+    capability_warning,
+    check_hardening,
+    find_plugin,
+    hardening_enabled,
+    load_defaults,
+    load_presets,
+    load_scripts,
+    load_session,
+    parse_fault_plan,
+    parse_filter_command,
+    parse_interval,
+    parse_ionice,
+    parse_listen,
+    parse_port,
+    parse_signal,
+    parse_sort,
+    require_procfs,
+    run_plugin,
+    scope_cgroup,
+    snapshot_cache,
+    start_timing,
+    stop_timing,
+)
+from procclean.formatters import (
+    BUILTIN_LAYOUTS,
+    COLOR_MODES,
+    DESIGN_FORMATS,
+    get_available_columns,
+)
 
-from .commands import cmd_groups, cmd_kill, cmd_list, cmd_memory
+from .commands import (
+    cmd_budgets,
+    cmd_clean,
+    cmd_columns,
+    cmd_config_edit,
+    cmd_config_show,
+    cmd_config_validate,
+    cmd_cpu,
+    cmd_daemon,
+    cmd_doctor,
+    cmd_emergency,
+    cmd_export,
+    cmd_fmt,
+    cmd_groups,
+    cmd_history,
+    cmd_hook,
+    cmd_jobs,
+    cmd_kill,
+    cmd_leftovers,
+    cmd_list,
+    cmd_mem_detail,
+    cmd_memory,
+    cmd_plugins,
+    cmd_polkit,
+    cmd_quota,
+    cmd_renice,
+    cmd_restart,
+    cmd_run,
+    cmd_status,
+    cmd_verify,
+    cmd_watch,
+    cmd_wine,
+)
+from .hooks import HOOK_ACTIONS, HOOK_SHELLS
+from .sinks import infer_format, output_sink
 
 
-def create_parser() -> argparse.ArgumentParser:
+# Options that select JSON output, as separate or attached tokens
+JSON_FORMAT_FLAGS = frozenset({"-f", "--format", "-O", "--out-format"})
+
+
+class CliParser(argparse.ArgumentParser):
+    """Argument parser that raises typed errors when ``exit_on_error`` is off."""
+
+    def error(self, message: str) -> NoReturn:
+        """Report a usage error.
+
+        Raises:
+            ProccleanError: With ``INVALID_ARGUMENT`` if ``exit_on_error`` is off.
+        """
+        if not self.exit_on_error:
+            raise ProccleanError(ErrorCode.INVALID_ARGUMENT, message, prog=self.prog)
+        super().error(message)
+
+
+def _wants_json(argv: list[str]) -> bool:
+    """Check whether the command line requests JSON output.
+
+    Returns:
+        bool: True if a format option is set to ``json``.
+    """
+    pairs = pairwise(argv)
+    if any(flag in JSON_FORMAT_FLAGS and value == "json" for flag, value in pairs):
+        return True
+    return any(
+        arg in {"--format=json", "--out-format=json", "-fjson", "-Ojson"}
+        for arg in argv
+    )
+
+
+def _plugin_format(argv: list[str]) -> str:
+    """Find the output format requested among a plugin's arguments.
+
+    Returns:
+        str: The value of the first format option, ``table`` without one.
+    """
+    for flag, value in pairwise(argv):
+        if flag in JSON_FORMAT_FLAGS:
+            return value
+    for arg in argv:
+        for prefix in ("--format=", "--out-format="):
+            if arg.startswith(prefix):
+                return arg.removeprefix(prefix)
+    return "table"
+
+
+def _external_command(
+    parser: argparse.ArgumentParser, argv: list[str]
+) -> Plugin | None:
+    """Look up the plugin an unknown subcommand names.
+
+    Returns:
+        Plugin | None: The ``procclean-NAME`` executable for ``procclean NAME``,
+        or None for options, built-in commands and unknown names.
+    """
+    if not argv or argv[0].startswith("-"):
+        return None
+    builtin = {
+        name
+        for action in parser._actions  # noqa: SLF001
+        if isinstance(action, argparse._SubParsersAction)  # noqa: SLF001
+        for name in action.choices
+    }
+    return None if argv[0] in builtin else find_plugin(argv[0])
+
+
+def _argument_error(err: argparse.ArgumentError) -> ProccleanError:
+    """Convert an argparse error into a typed error.
+
+    Returns:
+        ProccleanError: ``INVALID_FILTER`` for ``--filter``, otherwise
+        ``INVALID_ARGUMENT``.
+    """
+    name = err.argument_name or ""
+    if "--filter" in name:
+        return ProccleanError(ErrorCode.INVALID_FILTER, str(err), argument=name)
+    return ProccleanError(ErrorCode.INVALID_ARGUMENT, str(err), argument=name or None)
+
+
+def _report_error(err: ProccleanError, as_json: bool) -> int:
+    """Print an error as JSON on stdout or as text on stderr.
+
+    Args:
+        err: The error to report.
+        as_json: Emit ``{"error": {code, message, context}}`` instead of text.
+
+    Returns:
+        int: Exit status for the error.
+    """
+    if as_json:
+        sys.stdout.write(json.dumps({"error": err.to_dict()}, indent=2) + "\n")
+    else:
+        sys.stderr.write(f"procclean: error: {err.message}\n")
+    return err.exit_code
+
+
+def _report_timing(as_json: bool) -> None:
+    """Print the ``--timing`` report on stderr, if timing was on."""
+    if (timings := active_timings()) is None:
+        return
+    stop_timing()
+    if as_json:
+        sys.stderr.write(json.dumps({"timing": timings.as_dict()}) + "\n")
+    else:
+        sys.stderr.write(timings.render() + "\n")
+
+
+def _fault_spec(value: str) -> str:
+    """Validate a ``--faults`` spec for argparse.
+
+    Returns:
+        str: The spec, unchanged.
+
+    Raises:
+        argparse.ArgumentTypeError: If the spec cannot be parsed.
+    """
+    try:
+        parse_fault_plan(value)
+    except ValueError as e:
+        raise argparse.ArgumentTypeError(str(e)) from e
+    return value
+
+
+def _filter_cmd(value: str) -> str:
+    """Validate a ``--filter-cmd`` template for argparse.
+
+    Returns:
+        str: The template, unchanged.
+
+    Raises:
+        argparse.ArgumentTypeError: If the template cannot be parsed.
+    """
+    try:
+        parse_filter_command(value)
+    except ValueError as e:
+        raise argparse.ArgumentTypeError(str(e)) from e
+    return value
+
+
+def _signal(value: str) -> signal.Signals:
+    """Parse a ``--signal`` name or number for argparse.
+
+    Returns:
+        signal.Signals: The parsed signal.
+
+    Raises:
+        argparse.ArgumentTypeError: If the platform has no such signal.
+    """
+    try:
+        return parse_signal(value)
+    except ValueError as e:
+        raise argparse.ArgumentTypeError(str(e)) from e
+
+
+def _nice(value: str) -> int:
+    """Parse a ``--nice`` value for argparse.
+
+    Returns:
+        int: The nice value.
+
+    Raises:
+        argparse.ArgumentTypeError: If the value is not between -20 and 19.
+    """
+    try:
+        nice = int(value)
+    except ValueError:
+        nice = None
+    if nice not in NICE_RANGE:
+        msg = f"invalid nice value '{value}' (expected -20 to 19)"
+        raise argparse.ArgumentTypeError(msg)
+    return nice
+
+
+def _ionice(value: str) -> tuple[int, int | None]:
+    """Parse an ``--ionice`` class and level for argparse.
+
+    Returns:
+        tuple[int, int | None]: Class number and level.
+
+    Raises:
+        argparse.ArgumentTypeError: If the priority cannot be parsed.
+    """
+    try:
+        return parse_ionice(value)
+    except ValueError as e:
+        raise argparse.ArgumentTypeError(str(e)) from e
+
+
+def _port(value: str) -> int:
+    """Parse a ``--port`` number for argparse.
+
+    Returns:
+        int: The port.
+
+    Raises:
+        argparse.ArgumentTypeError: If the value is not a valid port.
+    """
+    try:
+        return parse_port(value)
+    except ValueError as e:
+        raise argparse.ArgumentTypeError(str(e)) from e
+
+
+def _listen(value: str) -> tuple[str, int]:
+    """Parse a ``--listen`` address for argparse.
+
+    Returns:
+        tuple[str, int]: Host and port.
+
+    Raises:
+        argparse.ArgumentTypeError: If the address is not HOST:PORT.
+    """
+    try:
+        return parse_listen(value)
+    except ValueError as e:
+        raise argparse.ArgumentTypeError(str(e)) from e
+
+
+def _interval(value: str) -> float:
+    """Parse an interval or duration option, e.g. ``--every``, for argparse.
+
+    Returns:
+        float: The interval in seconds.
+
+    Raises:
+        argparse.ArgumentTypeError: If the interval cannot be parsed.
+    """
+    try:
+        return parse_interval(value)
+    except ValueError as e:
+        raise argparse.ArgumentTypeError(str(e)) from e
+
+
+def _sort_keys(value: str) -> str:
+    """Validate a ``--sort`` spec for argparse.
+
+    Returns:
+        str: The normalized comma-separated sort keys.
+
+    Raises:
+        argparse.ArgumentTypeError: If a sort key is unknown.
+    """
+    try:
+        return ",".join(parse_sort(value))
+    except ValueError as e:
+        raise argparse.ArgumentTypeError(str(e)) from e
+
+
+def _job_number(value: str) -> int:
+    """Parse a job spec like ``2`` or ``%2`` for argparse.
+
+    Returns:
+        int: The 1-based job number.
+
+    Raises:
+        argparse.ArgumentTypeError: If the spec is not a positive number.
+    """
+    number = value.removeprefix("%")
+    if not number.isdigit() or int(number) < 1:
+        msg = f"invalid job spec '{value}' (expected N or %N)"
+        raise argparse.ArgumentTypeError(msg)
+    return int(number)
+
+
+def _add_name_arguments(parser: argparse.ArgumentParser) -> None:
+    """Add ``--name``, ``--cmdline``, ``--user`` and the matching options."""
+    parser.add_argument(
+        "--name",
+        metavar="PATTERN",
+        help="Only processes whose name contains PATTERN, or matches it if it "
+        "is a glob like 'node*'",
+    )
+    parser.add_argument(
+        "--cmdline",
+        metavar="REGEX",
+        help="Only processes whose command line matches REGEX",
+    )
+    parser.add_argument(
+        "--user",
+        metavar="USER",
+        help="Only processes owned by USER (name or UID) instead of yours",
+    )
+    parser.add_argument(
+        "-i",
+        "--ignore-case",
+        action="store_true",
+        help="Match --name and --cmdline case-insensitively",
+    )
+    mode = parser.add_mutually_exclusive_group()
+    mode.add_argument(
+        "--exact",
+        dest="name_mode",
+        action="store_const",
+        const=MatchMode.EXACT,
+        help="--name must match the whole process name",
+    )
+    mode.add_argument(
+        "--starts-with",
+        dest="name_mode",
+        action="store_const",
+        const=MatchMode.PREFIX,
+        help="--name must match the start of the process name",
+    )
+    mode.add_argument(
+        "--regex",
+        dest="name_mode",
+        action="store_const",
+        const=MatchMode.REGEX,
+        help="--name is a regular expression searched for in the process name",
+    )
+
+
+def _add_filter_arguments(parser: argparse.ArgumentParser, help_text: str) -> None:
+    """Add ``--filter``, ``--list-filters`` and the preset parameters.
+
+    Args:
+        parser: Subcommand parser.
+        help_text: Help of ``--filter``.
+    """
+    parser.add_argument(
+        "-F",
+        "--filter",
+        choices=FILTERS.names(),
+        help=f"{help_text} (see --list-filters)",
+    )
+    parser.add_argument(
+        "--list-filters",
+        action="store_true",
+        help="Describe the filter presets and exit",
+    )
+    for param in FILTERS.params():
+        default = f"{param.default:g}{param.unit}".replace("%", "%%")
+        parser.add_argument(
+            param.option,
+            type=float,
+            default=float(param.default),
+            metavar=param.metavar,
+            help=f"{param.help} (default: {default})",
+        )
+    fields = ", ".join(f"{{{name}}}" for name in FILTER_CMD_FIELDS)
+    parser.add_argument(
+        "--filter-cmd",
+        type=_filter_cmd,
+        metavar="CMD",
+        help="Only processes for which CMD exits 0, e.g. 'mycheck {pid}'; "
+        f"placeholders: {fields}",
+    )
+    parser.add_argument(
+        "--filter-jobs",
+        type=int,
+        default=FILTER_CMD_JOBS,
+        metavar="N",
+        help=f"Run up to N --filter-cmd commands at once (default: {FILTER_CMD_JOBS})",
+    )
+
+
+def _add_data_loss_argument(
+    parser: argparse.ArgumentParser, unattended: bool = False
+) -> None:
+    """Add ``--i-know-what-im-doing`` to a killing subcommand.
+
+    Args:
+        parser: Subcommand parser.
+        unattended: The subcommand kills without asking, so it spares
+            data-loss risks instead of confirming them.
+    """
+    help_text = (
+        "Kill databases and other data-loss risks too (spared otherwise)"
+        if unattended
+        else "Allow SIGKILL for databases and other data-loss risks, and skip "
+        "their extra confirmation"
+    )
+    parser.add_argument("--i-know-what-im-doing", action="store_true", help=help_text)
+
+
+def _add_include_vms_argument(parser: argparse.ArgumentParser) -> None:
+    """Add ``--include-vms`` to a killing subcommand.
+
+    Args:
+        parser: Subcommand parser.
+    """
+    parser.add_argument(
+        "--include-vms",
+        action="store_true",
+        help="Allow killing qemu/kvm and VirtualBox VM processes (skipped otherwise)",
+    )
+
+
+def _add_output_arguments(
+    parser: argparse.ArgumentParser, formats: tuple[str, ...]
+) -> None:
+    """Add ``--output-file`` and ``--append`` to a subcommand.
+
+    Args:
+        parser: Subcommand parser.
+        formats: Output formats the subcommand supports, for inference.
+    """
+    parser.add_argument(
+        "--output-file",
+        metavar="TARGET",
+        help="Write the report to a file, '|COMMAND' or 'syslog:[IDENT]' "
+        "instead of stdout (format inferred from .json/.csv/.md/.txt)",
+    )
+    parser.add_argument(
+        "--append",
+        action="store_true",
+        help="Append to --output-file instead of overwriting it",
+    )
+    parser.set_defaults(output_formats=formats)
+
+
+class _CpuThreshold(argparse.Action):
+    """``--cpu-threshold PCT``: ``--cpu-hogs --cpu-hog-threshold PCT``."""
+
+    def __call__(
+        self,
+        parser: argparse.ArgumentParser,
+        namespace: argparse.Namespace,
+        values: Any,
+        option_string: str | None = None,
+    ) -> None:
+        """Select the cpu-hogs preset with the threshold given."""
+        namespace.cpu_hogs = True
+        namespace.cpu_hog_threshold = values
+
+
+def _add_cpu_arguments(parser: argparse.ArgumentParser) -> None:
+    """Add ``--cpu-threshold`` and ``--cpu-interval`` to a selecting subcommand.
+
+    The subcommand must have the ``--cpu-hogs`` shorthand.
+    """
+    parser.add_argument(
+        "--cpu-threshold",
+        type=float,
+        action=_CpuThreshold,
+        dest="cpu_hog_threshold",
+        metavar="PCT",
+        help="Only CPU hogs above PCT; shorthand for --cpu-hogs "
+        "--cpu-hog-threshold PCT",
+    )
+    parser.add_argument(
+        "--cpu-interval",
+        type=_interval,
+        default=CPU_SAMPLE_SECONDS,
+        metavar="INTERVAL",
+        help="Measure CPU usage over this long, e.g. 2s; only spent when CPU "
+        f"is shown, sorted or filtered on (default: {CPU_SAMPLE_SECONDS:g}s)",
+    )
+
+
+def _add_header_argument(parser: argparse.ArgumentParser) -> None:
+    """Add ``--header`` to a reporting subcommand."""
+    parser.add_argument(
+        "--header",
+        action="store_true",
+        help="Start the report with the hostname, kernel, uptime, load average "
+        "and time, so reports from several machines tell themselves apart",
+    )
+
+
+def _infer_output_format(args: argparse.Namespace) -> None:
+    """Take the format from the ``--output-file`` extension if left at default.
+
+    Raises:
+        ProccleanError: With ``INVALID_ARGUMENT`` for ``--append`` without
+            ``--output-file``.
+    """
+    if not args.output_file:
+        if args.append:
+            raise ProccleanError(
+                ErrorCode.INVALID_ARGUMENT, "--append requires --output-file"
+            )
+        return
+    dest = next(d for d in ("out_format", "format") if hasattr(args, d))
+    if getattr(args, dest) == "table":
+        setattr(args, dest, infer_format(args.output_file, args.output_formats))
+
+
+def create_parser(exit_on_error: bool = True) -> argparse.ArgumentParser:
     """Create CLI argument parser.
 
+    Args:
+        exit_on_error: If False, usage errors raise ``ProccleanError`` instead
+            of printing usage and exiting.
+
     Returns:
-        argparse.ArgumentParser: Configured argument parser for the CLI.
+        argparse.ArgumentParser: Configured argument parser for the CLI, with
+        the ``[defaults]`` of the config file applied.
     """
-    parser = argparse.ArgumentParser(
+    parser = CliParser(
         prog="procclean",
         description="Process cleanup tool with TUI and CLI interfaces.",
+        exit_on_error=exit_on_error,
     )
     parser.add_argument(
         "-v",
@@ -24,6 +615,80 @@ def create_parser() -> argparse.ArgumentParser:
         action="version",
         version=f"%(prog)s {version('procclean')}",
     )
+    parser.add_argument(
+        "--simulate",
+        action="store_true",
+        help="Simulate kills without sending signals",
+    )
+    parser.add_argument(
+        "--faults",
+        type=_fault_spec,
+        metavar="SPEC",
+        help="Inject simulated kill failures, e.g. eperm:3,esrch:5 "
+        "(implies --simulate)",
+    )
+
+    parser.add_argument(
+        "--scope",
+        choices=SCOPES,
+        help="Only list and kill processes in your systemd user slice, your "
+        "login session, or system services",
+    )
+    parser.add_argument(
+        "--no-redact",
+        action="store_true",
+        help="Show secrets in command lines and --include-env values instead "
+        "of masking them",
+    )
+    parser.add_argument(
+        "--timing",
+        action="store_true",
+        help="Report how long each phase took (scan, environ, cwd, reads, "
+        "sort, format) on stderr; as JSON with --format json",
+    )
+    parser.add_argument(
+        "--no-daemon",
+        action="store_true",
+        help="Always scan in-process, even when a procclean daemon is running",
+    )
+    parser.add_argument(
+        "--harden",
+        action="store_true",
+        help="Scan in a child process without capabilities beyond reading /proc, "
+        "confined by seccomp: no network, signals, programs or file writes "
+        f"(also ${HARDEN_ENV})",
+    )
+    parser.add_argument(
+        "--polkit",
+        action="store_true",
+        help=f"Send kills you are not allowed to send through {HELPER_NAME}, "
+        f"after polkit authorization (also ${POLKIT_ENV})",
+    )
+    parser.add_argument(
+        "--max-age",
+        type=_interval,
+        metavar="INTERVAL",
+        help="Reuse a cached scan up to this old, e.g. 5s, and cache fresh "
+        "scans for later invocations",
+    )
+    parser.add_argument(
+        "--color",
+        choices=COLOR_MODES,
+        default="auto",
+        help="Color table cells by value: always, never, or auto for a "
+        "terminal without NO_COLOR (default: auto)",
+    )
+    session_group = parser.add_mutually_exclusive_group()
+    session_group.add_argument(
+        "--record",
+        metavar="FILE",
+        help="Record TUI actions and data snapshots to a JSONL file",
+    )
+    session_group.add_argument(
+        "--replay",
+        metavar="FILE",
+        help="Play back a TUI session recorded with --record (sends no signals)",
+    )
 
     subparsers = parser.add_subparsers(dest="command", help="Commands")
 
@@ -32,16 +697,18 @@ def create_parser() -> argparse.ArgumentParser:
     list_parser.add_argument(
         "-f",
         "--format",
-        choices=["table", "json", "csv", "md"],
+        choices=["table", "json", "jsonl", "csv", "md"],
         default="table",
         help="Output format (default: table)",
     )
     list_parser.add_argument(
         "-s",
         "--sort",
-        choices=["memory", "mem", "cpu", "pid", "name", "cwd"],
+        type=_sort_keys,
         default="memory",
-        help="Sort by field (default: memory)",
+        metavar="KEYS",
+        help="Sort by memory, mem, cpu, pid, name, cwd or age (oldest first); "
+        "comma-separate keys to break ties, e.g. memory,name (default: memory)",
     )
     list_parser.add_argument(
         "-a",
@@ -49,13 +716,7 @@ def create_parser() -> argparse.ArgumentParser:
         action="store_true",
         help="Sort ascending instead of descending",
     )
-    list_parser.add_argument(
-        "-F",
-        "--filter",
-        choices=["killable", "orphans", "high-memory"],
-        help="Filter preset: killable (orphans, not tmux, not system), "
-        "orphans, high-memory",
-    )
+    _add_filter_arguments(list_parser, "Filter preset")
     list_parser.add_argument(
         "-k",
         "--killable",
@@ -75,12 +736,41 @@ def create_parser() -> argparse.ArgumentParser:
         help="Shorthand for --filter high-memory",
     )
     list_parser.add_argument(
-        "--high-memory-threshold",
+        "--cpu-hogs",
+        action="store_true",
+        help="Shorthand for --filter cpu-hogs",
+    )
+    list_parser.add_argument(
+        "--cwd-deleted",
+        action="store_true",
+        help="Shorthand for --filter cwd-deleted",
+    )
+    list_parser.add_argument(
+        "--min-cpu",
         type=float,
-        default=500.0,
-        metavar="MB",
-        help="Threshold for high memory filter (default: 500 MB)",
+        metavar="PCT",
+        help="Only processes using at least PCT CPU (combines with presets)",
+    )
+    list_parser.add_argument(
+        "--max-cpu",
+        type=float,
+        metavar="PCT",
+        help="Only processes using at most PCT CPU, e.g. -m --max-cpu 1 for "
+        "idle memory hogs",
+    )
+    list_parser.add_argument(
+        "--older-than",
+        type=_interval,
+        metavar="DURATION",
+        help="Only processes running for at least DURATION, e.g. 2h",
+    )
+    list_parser.add_argument(
+        "--newer-than",
+        type=_interval,
+        metavar="DURATION",
+        help="Only processes running for at most DURATION, e.g. 10m",
     )
+    _add_cpu_arguments(list_parser)
     list_parser.add_argument(
         "--min-memory",
         type=float,
@@ -88,6 +778,12 @@ def create_parser() -> argparse.ArgumentParser:
         metavar="MB",
         help="Minimum memory to include (default: 5 MB)",
     )
+    list_parser.add_argument(
+        "--kernel-threads",
+        action="store_true",
+        help="Include kernel threads (hidden by default; they use no memory, so "
+        "combine with --min-memory 0)",
+    )
     list_parser.add_argument(
         "-n",
         "--limit",
@@ -96,11 +792,26 @@ def create_parser() -> argparse.ArgumentParser:
         help="Limit output to N processes",
     )
     list_parser.add_argument(
+        "--no-cap",
+        action="store_true",
+        help="Print every row of large tables (default: cap at display.row_cap "
+        "rows, 2000 unless configured)",
+    )
+    list_columns = list_parser.add_mutually_exclusive_group()
+    list_columns.add_argument(
         "-c",
         "--columns",
         type=str,
         metavar="COLS",
-        help=f"Comma-separated columns ({','.join(get_available_columns())})",
+        help="Comma-separated columns, each optionally with a :WIDTH, and memory "
+        f"columns a :kb/:mb/:gb unit ({','.join(get_available_columns())})",
+    )
+    list_columns.add_argument(
+        "-L",
+        "--layout",
+        metavar="NAME",
+        help=f"Named column layout ({','.join(BUILTIN_LAYOUTS)}, or one "
+        "from [layouts] in the config file)",
     )
     list_parser.add_argument(
         "--cwd",
@@ -110,6 +821,62 @@ def create_parser() -> argparse.ArgumentParser:
         metavar="PATH",
         help="Filter by cwd (no value = current dir, or specify path/glob)",
     )
+    list_parser.add_argument(
+        "--repo",
+        nargs="?",
+        const="",
+        default=None,
+        metavar="PATH",
+        help="Filter by the git worktree containing the cwd (no value = the "
+        "current one, or any path inside it)",
+    )
+    list_parser.add_argument(
+        "--port",
+        type=_port,
+        metavar="PORT",
+        help="Filter by a TCP or UDP port listened on, e.g. 3000",
+    )
+    _add_name_arguments(list_parser)
+    list_parser.add_argument(
+        "--include-env",
+        nargs="?",
+        const="",
+        default=None,
+        metavar="PATTERNS",
+        help="Add allowlisted environment variables to JSON output (no value = "
+        f"{','.join(DEFAULT_ENV_PATTERNS)}, or comma-separated name globs)",
+    )
+    list_parser.add_argument(
+        "--attribute",
+        action="store_true",
+        help="Resolve each executable's owning package (dpkg/rpm) and warn "
+        "about unpackaged binaries",
+    )
+    list_parser.add_argument(
+        "--every",
+        type=_interval,
+        metavar="INTERVAL",
+        help="Append a timestamped snapshot every INTERVAL (e.g. 30s, 5m, 1h) "
+        "until interrupted; JSON is written as JSON Lines",
+    )
+    list_parser.add_argument(
+        "--watch",
+        type=_interval,
+        nargs="?",
+        const=LIST_WATCH_SECONDS,
+        metavar="INTERVAL",
+        help="Redraw the list every INTERVAL like top (default: "
+        f"{LIST_WATCH_SECONDS:g}s) until interrupted; JSON and JSON Lines "
+        "stream records instead",
+    )
+    list_parser.add_argument(
+        "--count",
+        type=int,
+        metavar="N",
+        help="With --every or --watch, stop after N snapshots",
+    )
+    _add_output_arguments(list_parser, ("table", "json", "csv", "md"))
+    _add_header_argument(list_parser)
     list_parser.set_defaults(func=cmd_list)
 
     # Groups command
@@ -130,29 +897,105 @@ def create_parser() -> argparse.ArgumentParser:
         metavar="MB",
         help="Minimum memory to include (default: 5 MB)",
     )
-    groups_parser.set_defaults(func=cmd_groups)
-
-    # Kill command
-    kill_parser = subparsers.add_parser("kill", help="Kill process(es)")
-    kill_parser.add_argument(
-        "pids",
-        type=int,
-        nargs="*",
+    groups_parser.add_argument(
+        "--by",
+        choices=GROUP_BY,
+        default="command",
+        help="Group by command line, keeping the script interpreters run, or "
+        "by executable name (default: command)",
+    )
+    groups_parser.add_argument(
+        "--kill-duplicates",
+        action="store_true",
+        help="Kill all but one instance of processes running the same command "
+        "line in the same directory",
+    )
+    groups_parser.add_argument(
+        "--keep",
+        choices=KEEP_CHOICES,
+        default="oldest",
+        help="Duplicate instance --kill-duplicates keeps (default: oldest)",
+    )
+    groups_parser.add_argument(
+        "--force",
+        action="store_true",
+        help="With --kill-duplicates, SIGKILL instead of SIGTERM",
+    )
+    groups_parser.add_argument(
+        "-y",
+        "--yes",
+        action="store_true",
+        help="Skip confirmation prompt",
+    )
+    _add_data_loss_argument(groups_parser)
+    groups_parser.add_argument(
+        "--preview",
+        "--dry-run",
+        action="store_true",
+        dest="preview",
+        help="With --kill-duplicates, show what would be killed without killing",
+    )
+    groups_parser.add_argument(
+        "-O",
+        "--out-format",
+        choices=["table", "json", "csv", "md"],
+        default="table",
+        dest="out_format",
+        help="Output format for preview (default: table)",
+    )
+    _add_output_arguments(groups_parser, ("table", "json"))
+    _add_header_argument(groups_parser)
+    groups_parser.set_defaults(func=cmd_groups)
+
+    # Kill command
+    kill_parser = subparsers.add_parser("kill", help="Kill process(es)")
+    kill_parser.add_argument(
+        "pids",
+        type=int,
+        nargs="*",
         metavar="PID",
         help="Process ID(s) to kill (or use filters)",
     )
-    kill_parser.add_argument(
+    signal_group = kill_parser.add_mutually_exclusive_group()
+    signal_group.add_argument(
         "-f",
         "--force",
         action="store_true",
         help="Force kill (SIGKILL instead of SIGTERM)",
     )
+    signal_group.add_argument(
+        "--signal",
+        type=_signal,
+        metavar="SIG",
+        help="Send this signal instead of SIGTERM, by name or number, e.g. HUP "
+        "to reload, STOP/CONT to pause and resume, or USR1",
+    )
+    signal_group.add_argument(
+        "--escalate",
+        action="store_true",
+        help="Send SIGTERM, wait for the processes to exit and SIGKILL those "
+        "still running after --timeout; fails for processes that survive",
+    )
+    kill_parser.add_argument(
+        "--timeout",
+        type=_interval,
+        metavar="INTERVAL",
+        help=f"With --escalate, wait this long before SIGKILL (e.g. 10s; "
+        f"default: {ESCALATE_TIMEOUT_SECONDS:g}s)",
+    )
     kill_parser.add_argument(
         "-y",
         "--yes",
         action="store_true",
         help="Skip confirmation prompt",
     )
+    _add_data_loss_argument(kill_parser)
+    kill_parser.add_argument(
+        "-q",
+        "--quiet",
+        action="store_true",
+        help="Print nothing when no process matches",
+    )
     kill_parser.add_argument(
         "--cwd",
         nargs="?",
@@ -162,11 +1005,37 @@ def create_parser() -> argparse.ArgumentParser:
         help="Kill processes in cwd (no value = current dir, or specify path/glob)",
     )
     kill_parser.add_argument(
-        "-F",
-        "--filter",
-        choices=["killable", "orphans", "high-memory"],
-        help="Filter preset to select processes",
+        "--repo",
+        nargs="?",
+        const="",
+        default=None,
+        metavar="PATH",
+        help="Kill processes working in a git worktree (no value = the current "
+        "one, or any path inside it)",
     )
+    kill_parser.add_argument(
+        "--port",
+        type=_port,
+        metavar="PORT",
+        help="Kill whatever listens on a TCP or UDP port, e.g. 3000",
+    )
+    group_target = kill_parser.add_mutually_exclusive_group()
+    group_target.add_argument(
+        "--pgid",
+        type=int,
+        metavar="ID",
+        help="Signal the whole process group ID at once (like kill -- -ID), "
+        "e.g. a shell pipeline",
+    )
+    group_target.add_argument(
+        "--sid",
+        type=int,
+        metavar="ID",
+        help="Signal every process group of session ID, e.g. all jobs of a "
+        "terminal",
+    )
+    _add_name_arguments(kill_parser)
+    _add_filter_arguments(kill_parser, "Filter preset to select processes")
     kill_parser.add_argument(
         "-k",
         "--killable",
@@ -185,6 +1054,30 @@ def create_parser() -> argparse.ArgumentParser:
         action="store_true",
         help="Shorthand for --filter high-memory",
     )
+    kill_parser.add_argument(
+        "--cpu-hogs",
+        action="store_true",
+        help="Shorthand for --filter cpu-hogs",
+    )
+    kill_parser.add_argument(
+        "--cwd-deleted",
+        action="store_true",
+        help="Shorthand for --filter cwd-deleted",
+    )
+    _add_include_vms_argument(kill_parser)
+    kill_parser.add_argument(
+        "--via-manager",
+        action="store_true",
+        help="Stop supervised targets through their manager (systemctl, docker, "
+        "supervisorctl, pm2, forever, nodemon) instead of signaling them",
+    )
+    kill_parser.add_argument(
+        "--manager-action",
+        choices=["stop", "delete", "restart"],
+        default="stop",
+        help="Manager command used with --via-manager, e.g. 'pm2 delete' or "
+        "'systemctl restart' (default: stop)",
+    )
     kill_parser.add_argument(
         "--min-memory",
         type=float,
@@ -193,12 +1086,31 @@ def create_parser() -> argparse.ArgumentParser:
         help="Minimum memory for filter (default: 5 MB)",
     )
     kill_parser.add_argument(
-        "--high-memory-threshold",
+        "--min-cpu",
         type=float,
-        default=500.0,
-        metavar="MB",
-        help="Threshold for high memory filter (default: 500 MB)",
+        metavar="PCT",
+        help="Only processes using at least PCT CPU (combines with presets)",
+    )
+    kill_parser.add_argument(
+        "--max-cpu",
+        type=float,
+        metavar="PCT",
+        help="Only processes using at most PCT CPU, e.g. -m --max-cpu 1 for "
+        "idle memory hogs",
     )
+    kill_parser.add_argument(
+        "--older-than",
+        type=_interval,
+        metavar="DURATION",
+        help="Only processes running for at least DURATION, e.g. 2h",
+    )
+    kill_parser.add_argument(
+        "--newer-than",
+        type=_interval,
+        metavar="DURATION",
+        help="Only processes running for at most DURATION, e.g. 10m",
+    )
+    _add_cpu_arguments(kill_parser)
     kill_parser.add_argument(
         "--preview",
         "--dry-run",
@@ -218,9 +1130,10 @@ def create_parser() -> argparse.ArgumentParser:
     kill_parser.add_argument(
         "-s",
         "--sort",
-        choices=["memory", "mem", "cpu", "pid", "name", "cwd"],
+        type=_sort_keys,
         default=None,
-        help="Sort by field for preview",
+        metavar="KEYS",
+        help="Sort the preview by comma-separated keys, e.g. memory,name",
     )
     kill_parser.add_argument(
         "-n",
@@ -229,7 +1142,8 @@ def create_parser() -> argparse.ArgumentParser:
         metavar="N",
         help="Limit preview output to N processes",
     )
-    kill_parser.add_argument(
+    kill_columns = kill_parser.add_mutually_exclusive_group()
+    kill_columns.add_argument(
         "-c",
         "--columns",
         type=str,
@@ -237,8 +1151,93 @@ def create_parser() -> argparse.ArgumentParser:
         help=f"Comma-separated columns for preview "
         f"({','.join(get_available_columns())})",
     )
+    kill_columns.add_argument(
+        "-L",
+        "--layout",
+        metavar="NAME",
+        help="Named column layout for preview",
+    )
+    _add_output_arguments(kill_parser, ("table", "json", "csv", "md"))
     kill_parser.set_defaults(func=cmd_kill)
 
+    # Restart command
+    restart_parser = subparsers.add_parser(
+        "restart",
+        help="Restart processes through their manager, or kill and respawn them",
+    )
+    restart_parser.add_argument(
+        "targets",
+        nargs="+",
+        metavar="PID|UNIT",
+        help="Process IDs, or systemd units, containers or manager app names",
+    )
+    restart_parser.add_argument(
+        "--timeout",
+        type=_interval,
+        default=ESCALATE_TIMEOUT_SECONDS,
+        metavar="INTERVAL",
+        help=f"Wait this long after SIGTERM before SIGKILL (e.g. 10s; "
+        f"default: {ESCALATE_TIMEOUT_SECONDS:g}s)",
+    )
+    restart_parser.add_argument(
+        "-y",
+        "--yes",
+        action="store_true",
+        help="Skip confirmation prompt",
+    )
+    restart_parser.add_argument(
+        "--i-know-what-im-doing",
+        action="store_true",
+        help="Allow respawning databases and other data-loss risks, which may "
+        "take SIGKILL",
+    )
+    restart_parser.add_argument(
+        "-f",
+        "--format",
+        choices=["table", "json"],
+        default="table",
+        help="Output format (default: table)",
+    )
+    _add_output_arguments(restart_parser, ("table", "json"))
+    restart_parser.set_defaults(func=cmd_restart)
+
+    # Renice command
+    renice_parser = subparsers.add_parser(
+        "renice",
+        help="Lower (or raise) the CPU and I/O priority of processes",
+    )
+    renice_parser.add_argument(
+        "pids",
+        type=int,
+        nargs="+",
+        metavar="PID",
+        help="Process ID(s) to renice",
+    )
+    renice_parser.add_argument(
+        "-n",
+        "--nice",
+        type=_nice,
+        metavar="N",
+        help=f"Nice value from -20 (most favorable) to 19 (default: "
+        f"{RENICE_NICE}, unless only --ionice is given)",
+    )
+    renice_parser.add_argument(
+        "--ionice",
+        type=_ionice,
+        metavar="CLASS[:LEVEL]",
+        help="I/O scheduling class: idle, best-effort, realtime or none, with "
+        "a level from 0 (highest) to 7 for best-effort and realtime, e.g. "
+        "best-effort:7",
+    )
+    renice_parser.add_argument(
+        "-f",
+        "--format",
+        choices=["table", "json"],
+        default="table",
+        help="Output format (default: table)",
+    )
+    renice_parser.set_defaults(func=cmd_renice)
+
     # Memory command
     memory_parser = subparsers.add_parser(
         "memory", aliases=["mem"], help="Show memory summary"
@@ -250,23 +1249,748 @@ def create_parser() -> argparse.ArgumentParser:
         default="table",
         help="Output format (default: table)",
     )
+    memory_parser.add_argument(
+        "--per-process",
+        action="store_true",
+        help="Also show the processes using the most memory by PSS, with USS, "
+        "shared and swapped memory",
+    )
+    memory_parser.add_argument(
+        "-n",
+        "--limit",
+        type=int,
+        default=10,
+        metavar="N",
+        help="Processes to show with --per-process (default: 10)",
+    )
+    _add_output_arguments(memory_parser, ("table", "json"))
+    _add_header_argument(memory_parser)
     memory_parser.set_defaults(func=cmd_memory)
 
+    # Memory detail command
+    mem_detail_parser = subparsers.add_parser(
+        "mem-detail", help="Show the RSS, PSS, USS, shared and swap of a process"
+    )
+    mem_detail_parser.add_argument("pid", type=int, help="Process ID")
+    mem_detail_parser.add_argument(
+        "-f",
+        "--format",
+        choices=["table", "json"],
+        default="table",
+        help="Output format (default: table)",
+    )
+    _add_output_arguments(mem_detail_parser, ("table", "json"))
+    mem_detail_parser.set_defaults(func=cmd_mem_detail)
+
+    # CPU command
+    cpu_parser = subparsers.add_parser(
+        "cpu", help="Show per-core CPU usage and the busiest processes"
+    )
+    cpu_parser.add_argument(
+        "-f",
+        "--format",
+        choices=["table", "json"],
+        default="table",
+        help="Output format (default: table)",
+    )
+    cpu_parser.add_argument(
+        "--interval",
+        type=_interval,
+        default=CPU_SAMPLE_SECONDS,
+        metavar="INTERVAL",
+        help=f"Measure over this long, e.g. 2s (default: {CPU_SAMPLE_SECONDS:g}s)",
+    )
+    cpu_parser.add_argument(
+        "-n",
+        "--limit",
+        type=int,
+        default=5,
+        metavar="N",
+        help="Busiest processes to show (default: 5)",
+    )
+    _add_output_arguments(cpu_parser, ("table", "json"))
+    cpu_parser.set_defaults(func=cmd_cpu)
+
+    # Status command
+    status_parser = subparsers.add_parser(
+        "status", help="Single-line status for waybar/polybar/i3status"
+    )
+    status_parser.add_argument(
+        "-f",
+        "--format",
+        choices=["plain", "waybar", "i3status"],
+        default="plain",
+        help="Output format (default: plain)",
+    )
+    status_parser.add_argument(
+        "--min-memory",
+        type=float,
+        default=5.0,
+        metavar="MB",
+        help="Minimum memory to include (default: 5 MB)",
+    )
+    status_parser.set_defaults(func=cmd_status)
+
+    # Jobs command
+    jobs_parser = subparsers.add_parser(
+        "jobs", help="List and kill jobs of the current shell session"
+    )
+    jobs_parser.add_argument(
+        "-f",
+        "--format",
+        choices=["table", "json"],
+        default="table",
+        help="Output format (default: table)",
+    )
+    jobs_parser.add_argument(
+        "-k",
+        "--kill",
+        type=_job_number,
+        nargs="*",
+        metavar="JOB",
+        help="Kill jobs by number (N or %%N); no numbers kills every job",
+    )
+    jobs_parser.add_argument(
+        "--force",
+        action="store_true",
+        help="Force kill (SIGKILL instead of SIGTERM)",
+    )
+    jobs_parser.add_argument(
+        "-y",
+        "--yes",
+        action="store_true",
+        help="Skip confirmation prompt",
+    )
+    _add_data_loss_argument(jobs_parser)
+    jobs_parser.add_argument(
+        "--session",
+        type=int,
+        metavar="SID",
+        help="Session ID to inspect (default: the invoking shell's session)",
+    )
+    jobs_parser.set_defaults(func=cmd_jobs)
+
+    # History command
+    history_parser = subparsers.add_parser(
+        "history", help="Show what procclean killed, from the audit log"
+    )
+    history_parser.add_argument(
+        "-n",
+        "--limit",
+        type=int,
+        default=20,
+        metavar="N",
+        help="Show the last N actions; 0 shows all (default: 20)",
+    )
+    history_parser.add_argument(
+        "--since",
+        type=_interval,
+        metavar="INTERVAL",
+        help="Only actions within this long ago (e.g. 30m, 24h)",
+    )
+    history_parser.add_argument("--pid", type=int, help="Only actions on this PID")
+    history_parser.add_argument(
+        "--name", metavar="NAME", help="Only processes whose name contains NAME"
+    )
+    history_parser.add_argument(
+        "--origin", choices=AUDIT_ORIGINS, help="Only actions from this front end"
+    )
+    history_parser.add_argument(
+        "--failed", action="store_true", help="Only actions that failed"
+    )
+    history_parser.add_argument(
+        "-f",
+        "--format",
+        choices=["table", "json"],
+        default="table",
+        help="Output format (default: table)",
+    )
+    _add_output_arguments(history_parser, ("table", "json"))
+    history_parser.set_defaults(func=cmd_history)
+
+    # Doctor command
+    doctor_parser = subparsers.add_parser(
+        "doctor",
+        help="Report what procclean can read here: /proc mount, hidepid, "
+        "permissions, and which columns are affected",
+    )
+    doctor_parser.add_argument(
+        "-f",
+        "--format",
+        choices=["table", "json"],
+        default="table",
+        help="Output format (default: table)",
+    )
+    # Runs without /proc, to report that it is missing
+    doctor_parser.set_defaults(func=cmd_doctor, needs_procfs=False)
+
+    # Config command
+    config_parser = subparsers.add_parser(
+        "config", help="Show, validate or edit the config file"
+    )
+    config_commands = config_parser.add_subparsers(
+        dest="config_command", required=True, help="Config commands"
+    )
+    config_show = config_commands.add_parser(
+        "show", help="Show the effective settings and where each comes from"
+    )
+    config_validate = config_commands.add_parser(
+        "validate", help="Check every setting; exit 1 if any is malformed or unknown"
+    )
+    for sub in (config_show, config_validate):
+        sub.add_argument(
+            "-f",
+            "--format",
+            choices=["table", "json"],
+            default="table",
+            help="Output format (default: table)",
+        )
+    config_show.set_defaults(func=cmd_config_show, needs_procfs=False)
+    config_validate.set_defaults(func=cmd_config_validate, needs_procfs=False)
+    config_edit = config_commands.add_parser(
+        "edit",
+        help="Edit the config file in $VISUAL or $EDITOR; it is only saved once valid",
+    )
+    config_edit.set_defaults(func=cmd_config_edit, needs_procfs=False)
+
+    # Columns command
+    columns_parser = subparsers.add_parser(
+        "columns",
+        help="List the columns --columns takes, with their types and cost",
+    )
+    columns_parser.add_argument(
+        "-f",
+        "--format",
+        choices=["table", "json"],
+        default="table",
+        help="Output format (default: table)",
+    )
+    columns_parser.set_defaults(func=cmd_columns, needs_procfs=False)
+
+    # Fmt command
+    fmt_parser = subparsers.add_parser(
+        "fmt",
+        help="Design the columns and format of list, and print them as options",
+    )
+    fmt_parser.add_argument(
+        "-i",
+        "--interactive",
+        action="store_true",
+        help="Toggle columns, widths, units and the format over a live preview",
+    )
+    fmt_parser.add_argument(
+        "-c",
+        "--columns",
+        type=str,
+        metavar="COLS",
+        help="Columns to start from (default: [defaults] columns, else the "
+        "default columns)",
+    )
+    fmt_parser.add_argument(
+        "-f",
+        "--format",
+        choices=list(DESIGN_FORMATS),
+        default="table",
+        help="Format to start from (default: table)",
+    )
+    fmt_parser.add_argument(
+        "--save",
+        action="store_true",
+        help="Write the columns to [defaults] columns in the config file",
+    )
+    fmt_parser.set_defaults(func=cmd_fmt, needs_procfs=False)
+
+    # Leftovers command
+    leftovers_parser = subparsers.add_parser(
+        "leftovers",
+        aliases=["stale"],
+        help="Find stale unix sockets and lock files that block restarts",
+    )
+    leftovers_parser.add_argument(
+        "-f",
+        "--format",
+        choices=["table", "json"],
+        default="table",
+        help="Output format for the report (default: table)",
+    )
+    leftovers_parser.add_argument(
+        "--fix",
+        action="store_true",
+        help="Kill processes holding stale sockets and remove leftover files",
+    )
+    leftovers_parser.add_argument(
+        "-y",
+        "--yes",
+        action="store_true",
+        help="Skip confirmation prompt for --fix",
+    )
+    leftovers_parser.add_argument(
+        "--force",
+        action="store_true",
+        help="Use SIGKILL instead of SIGTERM for holders",
+    )
+    _add_data_loss_argument(leftovers_parser)
+    _add_include_vms_argument(leftovers_parser)
+    leftovers_parser.add_argument(
+        "--dir",
+        action="append",
+        dest="dirs",
+        metavar="PATH",
+        help="Directory to scan (repeatable; default: /tmp, /tmp/.X11-unix, "
+        "$XDG_RUNTIME_DIR)",
+    )
+    leftovers_parser.set_defaults(func=cmd_leftovers)
+
+    # Clean command
+    clean_parser = subparsers.add_parser(
+        "clean",
+        help="Kill killable, stale and dev-leftover processes in one go",
+    )
+    clean_parser.add_argument(
+        "--auto",
+        action="store_true",
+        help="Kill the listed processes (after confirmation); without it, "
+        "only show what would be cleaned",
+    )
+    clean_parser.add_argument(
+        "-y",
+        "--yes",
+        action="store_true",
+        help="Skip confirmation prompt",
+    )
+    clean_parser.add_argument(
+        "--force",
+        action="store_true",
+        help="Force kill (SIGKILL instead of SIGTERM)",
+    )
+    _add_data_loss_argument(clean_parser)
+    clean_parser.add_argument(
+        "-m",
+        "--min-memory",
+        type=float,
+        default=5.0,
+        help="Min memory in MB (default: 5)",
+    )
+    _add_include_vms_argument(clean_parser)
+    clean_parser.set_defaults(func=cmd_clean)
+
+    # Budgets command
+    budgets_parser = subparsers.add_parser(
+        "budgets",
+        help="Check per-directory memory budgets from the config file",
+    )
+    budgets_parser.add_argument(
+        "--enforce",
+        action="store_true",
+        help='Kill offenders of exceeded budgets with action = "kill"',
+    )
+    _add_data_loss_argument(budgets_parser, unattended=True)
+    budgets_parser.add_argument(
+        "--every",
+        type=_interval,
+        metavar="INTERVAL",
+        help="Check again every INTERVAL (e.g. 30s, 5m) until interrupted",
+    )
+    budgets_parser.add_argument(
+        "--count",
+        type=int,
+        metavar="N",
+        help="With --every, stop after N checks",
+    )
+    budgets_parser.add_argument(
+        "-f",
+        "--format",
+        choices=["table", "json"],
+        default="table",
+        help="Output format (default: table)",
+    )
+    budgets_parser.set_defaults(func=cmd_budgets)
+
+    # Emergency command
+    emergency_parser = subparsers.add_parser(
+        "emergency",
+        help="Kill pre-approved processes when memory pressure turns critical",
+    )
+    emergency_parser.add_argument(
+        "--every",
+        type=_interval,
+        metavar="INTERVAL",
+        help="Check again every INTERVAL (e.g. 5s) until interrupted",
+    )
+    _add_data_loss_argument(emergency_parser, unattended=True)
+    emergency_parser.add_argument(
+        "--count",
+        type=int,
+        metavar="N",
+        help="With --every, stop after N checks",
+    )
+    emergency_parser.add_argument(
+        "--log",
+        metavar="TARGET",
+        help="Append the log to a file, '|COMMAND' or 'syslog:[IDENT]' "
+        "instead of stdout",
+    )
+    emergency_parser.set_defaults(func=cmd_emergency)
+
+    # Watch command
+    watch_parser = subparsers.add_parser(
+        "watch",
+        help="Keep killing processes that match a filter preset, e.g. as a "
+        "systemd service",
+    )
+    _add_filter_arguments(watch_parser, "Filter preset of processes to kill (required)")
+    watch_parser.add_argument(
+        "--interval",
+        type=_interval,
+        default=WATCH_INTERVAL,
+        metavar="INTERVAL",
+        help=f"Time between checks, e.g. 30s (default: {WATCH_INTERVAL:g}s)",
+    )
+    watch_parser.add_argument(
+        "--count",
+        type=int,
+        metavar="N",
+        help="Stop after N checks (default: run until interrupted)",
+    )
+    watch_parser.add_argument(
+        "--min-memory",
+        type=float,
+        default=5.0,
+        metavar="MB",
+        help="Only kill processes using at least MB (default: 5 MB)",
+    )
+    watch_parser.add_argument(
+        "--cwd",
+        nargs="?",
+        const="",
+        default=None,
+        metavar="PATH",
+        help="Only kill processes working in PATH (no value = current dir, or "
+        "a glob)",
+    )
+    _add_name_arguments(watch_parser)
+    watch_parser.add_argument(
+        "--preview",
+        "--dry-run",
+        "--dry",
+        action="store_true",
+        dest="preview",
+        help="Log what would be killed without killing",
+    )
+    watch_parser.add_argument(
+        "--force",
+        action="store_true",
+        help="Force kill (SIGKILL instead of SIGTERM)",
+    )
+    _add_data_loss_argument(watch_parser, unattended=True)
+    _add_include_vms_argument(watch_parser)
+    watch_parser.add_argument(
+        "--log",
+        metavar="TARGET",
+        help="Append the log to a file, '|COMMAND' or 'syslog:[IDENT]' "
+        "instead of stdout",
+    )
+    watch_parser.add_argument(
+        "--log-format",
+        choices=["text", "json"],
+        default="text",
+        help="Log lines as text or one JSON object per action (default: text)",
+    )
+    watch_parser.set_defaults(func=cmd_watch)
+
+    # Quota command
+    quota_parser = subparsers.add_parser(
+        "quota",
+        help="Show per-user memory and process usage against quotas",
+    )
+    quota_parser.add_argument(
+        "--enforce",
+        action="store_true",
+        help="Renice or kill offenders as each quota's action says",
+    )
+    _add_data_loss_argument(quota_parser, unattended=True)
+    quota_parser.add_argument(
+        "--every",
+        type=_interval,
+        metavar="INTERVAL",
+        help="Check again every INTERVAL (e.g. 30s, 5m) until interrupted",
+    )
+    quota_parser.add_argument(
+        "--count",
+        type=int,
+        metavar="N",
+        help="With --every, stop after N checks",
+    )
+    quota_parser.add_argument(
+        "-f",
+        "--format",
+        choices=["table", "json"],
+        default="table",
+        help="Output format (default: table)",
+    )
+    quota_parser.set_defaults(func=cmd_quota)
+
+    # Hook command
+    hook_parser = subparsers.add_parser(
+        "hook",
+        help="Print a shell hook that cleans up after leaving a directory",
+    )
+    hook_parser.add_argument("shell", choices=HOOK_SHELLS, help="Target shell")
+    hook_parser.add_argument(
+        "--action",
+        choices=list(HOOK_ACTIONS),
+        default="preview",
+        help="List leftovers, ask before killing them, or kill without asking "
+        "(default: preview)",
+    )
+    hook_parser.add_argument(
+        "--on-exit",
+        action="store_true",
+        help="Also clean up the current directory when the shell exits",
+    )
+    hook_parser.set_defaults(func=cmd_hook)
+
+    # Run command
+    run_parser = subparsers.add_parser(
+        "run",
+        help="Run a command and report or kill the processes it leaves behind",
+        usage="%(prog)s [-h] [--kill] [-f] [--i-know-what-im-doing] -- COMMAND "
+        "[ARG ...]",
+    )
+    run_parser.add_argument(
+        "--kill",
+        action="store_true",
+        help="Terminate surviving processes after the command exits",
+    )
+    run_parser.add_argument(
+        "-f",
+        "--force",
+        action="store_true",
+        help="With --kill, use SIGKILL instead of SIGTERM",
+    )
+    _add_data_loss_argument(run_parser)
+    run_parser.add_argument(
+        "run_command",
+        nargs="+",
+        metavar="COMMAND",
+        help="Command to run, after '--'",
+    )
+    run_parser.set_defaults(func=cmd_run)
+
+    # Wine command
+    wine_parser = subparsers.add_parser(
+        "wine",
+        help="Group Wine/Proton processes by application and shut prefixes down",
+    )
+    wine_parser.add_argument(
+        "--shutdown",
+        metavar="NAME",
+        help="Run wineserver -k for the prefix with this path, application "
+        "(e.g. game.exe) or Steam app ID",
+    )
+    wine_parser.add_argument(
+        "-y",
+        "--yes",
+        action="store_true",
+        help="Skip confirmation prompt",
+    )
+    wine_parser.add_argument(
+        "-f",
+        "--format",
+        choices=["table", "json"],
+        default="table",
+        help="Output format (default: table)",
+    )
+    wine_parser.set_defaults(func=cmd_wine)
+
+    # Plugins command
+    plugins_parser = subparsers.add_parser(
+        "plugins",
+        help="List external commands: procclean-NAME executables on PATH, "
+        "run as procclean NAME",
+    )
+    plugins_parser.add_argument(
+        "-f",
+        "--format",
+        choices=["table", "json"],
+        default="table",
+        help="Output format (default: table)",
+    )
+    plugins_parser.set_defaults(func=cmd_plugins)
+
+    # Verify command
+    verify_parser = subparsers.add_parser(
+        "verify",
+        help="Check running processes against an expected-processes manifest",
+    )
+    verify_parser.add_argument(
+        "--manifest",
+        required=True,
+        metavar="FILE",
+        help="TOML file listing required and forbidden processes",
+    )
+    verify_parser.add_argument(
+        "-f",
+        "--format",
+        choices=["table", "json"],
+        default="table",
+        help="Output format (default: table)",
+    )
+    verify_parser.set_defaults(func=cmd_verify)
+
+    # Daemon command
+    daemon_parser = subparsers.add_parser(
+        "daemon",
+        help="Keep scanning in the background so the TUI and list start instantly",
+    )
+    daemon_parser.add_argument(
+        "--socket",
+        metavar="PATH",
+        help="Unix socket to listen on (default: $PROCCLEAN_SOCKET, else "
+        "procclean.sock in $XDG_RUNTIME_DIR)",
+    )
+    daemon_parser.add_argument(
+        "--interval",
+        type=_interval,
+        default=DAEMON_INTERVAL,
+        metavar="INTERVAL",
+        help=f"Time between scans, e.g. 5s (default: {DAEMON_INTERVAL:g}s)",
+    )
+    daemon_parser.set_defaults(func=cmd_daemon)
+
+    # Export command
+    export_parser = subparsers.add_parser(
+        "export",
+        help="Serve process and memory metrics for Prometheus over HTTP",
+    )
+    export_parser.add_argument(
+        "--listen",
+        type=_listen,
+        default=("127.0.0.1", EXPORT_PORT),
+        metavar="HOST:PORT",
+        help=f"Address to serve /metrics on, e.g. 0.0.0.0:{EXPORT_PORT} "
+        f"(default: 127.0.0.1:{EXPORT_PORT})",
+    )
+    export_parser.add_argument(
+        "--top",
+        type=int,
+        default=EXPORT_TOP,
+        metavar="N",
+        help=f"Processes listed by RSS and by CPU usage (default: {EXPORT_TOP})",
+    )
+    export_parser.add_argument(
+        "--once",
+        action="store_true",
+        help="Print the metrics once and exit, e.g. for node_exporter's "
+        "textfile collector",
+    )
+    export_parser.set_defaults(func=cmd_export)
+
+    # Polkit command
+    polkit_parser = subparsers.add_parser(
+        "polkit",
+        help="Print the polkit action that lets --polkit signal other users' "
+        "processes",
+    )
+    polkit_parser.add_argument(
+        "--helper",
+        metavar="PATH",
+        help=f"Absolute path of {HELPER_NAME} (default: found on $PATH)",
+    )
+    polkit_parser.set_defaults(func=cmd_polkit, needs_procfs=False)
+
+    # Configured [defaults] replace the built-in ones where a command has them
+    try:
+        options = load_defaults().options()
+    except ProccleanError:
+        options = {}  # Reported by run_cli already, or by doctor and config
+    for sub in subparsers.choices.values():
+        sub.exit_on_error = exit_on_error
+        dests = {action.dest for action in sub._actions}  # noqa: SLF001
+        sub.set_defaults(**{k: v for k, v in options.items() if k in dests})
+
     return parser
 
 
 def run_cli(args: list[str] | None = None) -> int:
     """Run CLI with given args (or sys.argv if None).
 
+    With ``--format json`` (or ``-O json``), errors are printed to stdout as
+    ``{"error": {"code", "message", "context"}}`` instead of text on stderr.
+    An unknown subcommand ``NAME`` runs the plugin ``procclean-NAME`` from
+    PATH, if there is one (see ``procclean.core.plugins``).
+
     Returns:
         int: Exit status code. Returns ``-1`` when no subcommand is provided to
         signal that the TUI should run.
     """
-    parser = create_parser()
-    parsed = parser.parse_args(args)
+    argv = sys.argv[1:] if args is None else args
+    as_json = _wants_json(argv)
+    try:
+        # Before the parser: script and config presets are --filter choices
+        load_scripts()
+        load_presets()
+        load_defaults()
+    except ProccleanError as e:
+        # Doctor and config report a broken config themselves
+        if argv[:1] not in (["doctor"], ["config"]):
+            return _report_error(e, as_json)
+    parser = create_parser(exit_on_error=not as_json)
+    try:
+        if plugin := _external_command(parser, argv):
+            require_procfs()
+            return run_plugin(plugin, argv[1:], _plugin_format(argv[1:]))
+        try:
+            parsed = parser.parse_args(argv)
+        except argparse.ArgumentError as e:
+            raise _argument_error(e) from e
 
-    if parsed.command is None:
-        # No subcommand - return None to signal TUI should run
-        return -1
+        if parsed.command is not None and (parsed.record or parsed.replay):
+            raise ProccleanError(
+                ErrorCode.INVALID_ARGUMENT,
+                "--record and --replay only apply to the interactive TUI",
+            )
+        if parsed.harden or hardening_enabled():
+            check_hardening()
+            os.environ[HARDEN_ENV] = "1"
+            os.environ[NO_DAEMON_ENV] = "1"  # Scans stay in the sandbox
+        if parsed.polkit:
+            os.environ[POLKIT_ENV] = "1"
+        if parsed.command is None:
+            # No subcommand - return None to signal TUI should run
+            if parsed.simulate or parsed.faults is not None:
+                # The TUI picks the simulation up from the environment
+                os.environ[SIMULATE_ENV] = parsed.faults or ""
+            if parsed.scope:
+                scope_cgroup(parsed.scope)  # Fail here rather than in the TUI
+                os.environ[SCOPE_ENV] = parsed.scope
+            if parsed.no_redact:
+                os.environ[NO_REDACT_ENV] = "1"
+            if parsed.no_daemon:
+                os.environ[NO_DAEMON_ENV] = "1"
+            if parsed.record:
+                SessionRecorder(Path(parsed.record)).close()  # Fail early
+                os.environ[RECORD_ENV] = parsed.record
+            if parsed.replay:
+                load_session(Path(parsed.replay))
+                os.environ[REPLAY_ENV] = parsed.replay
+            return -1
 
-    return parsed.func(parsed)
+        if getattr(parsed, "needs_procfs", True):
+            require_procfs()
+            if warning := capability_warning():
+                print(warning, file=sys.stderr)
+        if parsed.timing:
+            start_timing()
+        with snapshot_cache(parsed.max_age), audit_origin("cli", shlex.join(argv)):
+            if hasattr(parsed, "output_file"):
+                _infer_output_format(parsed)
+                # --every reopens the sink for each snapshot itself
+                if not getattr(parsed, "every", None):
+                    with output_sink(parsed.output_file, parsed.append):
+                        return parsed.func(parsed)
+            return parsed.func(parsed)
+    except ProccleanError as e:
+        return _report_error(e, as_json)
+    finally:
+        _report_timing(as_json)
@@ -0,0 +1,184 @@
+"""Output sinks for ``--output-file``: files, pipes and syslog.
+
+The target is one of:
+
+- ``PATH``: write (or with ``--append``, append) to a file; strftime codes
+  such as ``%Y%m%d`` are expanded with the local time
+- ``|COMMAND``: pipe the report into a command's stdin
+- ``syslog:`` or ``syslog:IDENT``: log each line at ``LOG_INFO``
+
+Everything a command prints goes to the sink; wrap interactive prompts in
+``terminal()`` so they still reach the user.
+"""
+
+import contextlib
+import io
+import shlex
+import subprocess
+import sys
+import syslog
+from collections.abc import Iterator
+from datetime import UTC, datetime
+from pathlib import Path
+from typing import TextIO
+
+import rich
+
+from procclean.core import ErrorCode, ProccleanError
+
+SYSLOG_PREFIX = "syslog:"
+PIPE_PREFIX = "|"
+DEFAULT_SYSLOG_IDENT = "procclean"
+
+# Output format implied by an --output-file extension
+FORMAT_EXTENSIONS: dict[str, str] = {
+    ".json": "json",
+    ".jsonl": "json",
+    ".csv": "csv",
+    ".md": "md",
+    ".txt": "table",
+}
+
+# (terminal, sink) stdout pairs of the active sinks, innermost last
+_active: list[tuple[TextIO, TextIO]] = []
+
+
+def infer_format(target: str | None, choices: tuple[str, ...]) -> str:
+    """Pick the output format implied by an ``--output-file`` target.
+
+    Args:
+        target: The ``--output-file`` value, if any.
+        choices: Formats the command supports.
+
+    Returns:
+        str: The format for the target's extension if the command supports
+        it, otherwise "table".
+    """
+    if target and not target.startswith((SYSLOG_PREFIX, PIPE_PREFIX)):
+        fmt = FORMAT_EXTENSIONS.get(Path(target).suffix.lower())
+        if fmt in choices:
+            return fmt
+    return "table"
+
+
+def _point_rich_at(stream: TextIO | None) -> None:
+    """Rebuild rich's console for a stream (None = follow sys.stdout).
+
+    Rich fixes its color system and width when the console is created, so
+    without this, escape codes meant for the terminal would end up in files.
+    """
+    if stream is None:
+        rich.reconfigure()
+    else:
+        rich.reconfigure(file=stream)
+
+
+@contextlib.contextmanager
+def _redirect(stream: TextIO) -> Iterator[None]:
+    _active.append((sys.stdout, stream))
+    _point_rich_at(stream)
+    try:
+        with contextlib.redirect_stdout(stream):
+            yield
+    finally:
+        _active.pop()
+        _point_rich_at(_active[-1][1] if _active else None)
+
+
+@contextlib.contextmanager
+def _file_sink(path: Path, append: bool) -> Iterator[None]:
+    mode = "w"
+    if append:
+        mode = "a"
+    try:
+        stream = path.open(mode, encoding="utf-8")  # noqa: SIM115
+    except OSError as e:
+        raise ProccleanError(
+            ErrorCode.INVALID_ARGUMENT,
+            f"Cannot write output file {path}: {e.strerror}",
+            path=str(path),
+        ) from e
+    with stream, _redirect(stream):
+        yield
+
+
+@contextlib.contextmanager
+def _pipe_sink(command: str) -> Iterator[None]:
+    argv = shlex.split(command)
+    try:
+        proc = subprocess.Popen(argv, stdin=subprocess.PIPE, text=True)
+    except (OSError, ValueError) as e:
+        raise ProccleanError(
+            ErrorCode.INVALID_ARGUMENT,
+            f"Cannot run output command {command!r}: {e}",
+            command=command,
+        ) from e
+    assert proc.stdin is not None
+    try:
+        with proc.stdin, _redirect(proc.stdin):
+            yield
+    finally:
+        proc.wait()
+
+
+@contextlib.contextmanager
+def _syslog_sink(ident: str) -> Iterator[None]:
+    buffer = io.StringIO()
+    try:
+        with _redirect(buffer):
+            yield
+    finally:
+        syslog.openlog(ident, syslog.LOG_PID, syslog.LOG_USER)
+        for line in buffer.getvalue().splitlines():
+            if line.strip():
+                syslog.syslog(syslog.LOG_INFO, line)
+        syslog.closelog()
+
+
+@contextlib.contextmanager
+def output_sink(target: str | None, append: bool = False) -> Iterator[None]:
+    """Send everything printed inside the block to an ``--output-file`` target.
+
+    Args:
+        target: File path, ``|COMMAND`` or ``syslog:[IDENT]``; None for stdout.
+        append: Append to an existing file instead of truncating it.
+
+    Yields:
+        None: Inside the redirected block.
+
+    Raises:
+        ProccleanError: With ``INVALID_ARGUMENT`` if the file cannot be opened
+            or the command cannot be started.
+    """
+    if target is None:
+        yield
+    elif target.startswith(SYSLOG_PREFIX):
+        ident = target.removeprefix(SYSLOG_PREFIX) or DEFAULT_SYSLOG_IDENT
+        with _syslog_sink(ident):
+            yield
+    elif target.startswith(PIPE_PREFIX):
+        with _pipe_sink(target.removeprefix(PIPE_PREFIX)):
+            yield
+    else:
+        path = datetime.now(tz=UTC).astimezone().strftime(target)
+        with _file_sink(Path(path).expanduser(), append):
+            yield
+
+
+@contextlib.contextmanager
+def terminal() -> Iterator[None]:
+    """Print to the real stdout inside the block, bypassing any active sink.
+
+    Yields:
+        None: Inside the block.
+    """
+    if not _active:
+        yield
+        return
+    terminal_stdout, sink = _active[-1]
+    _point_rich_at(None)
+    try:
+        with contextlib.redirect_stdout(terminal_stdout):
+            yield
+    finally:
+        _point_rich_at(sink)
@@ -1,56 +1,883 @@
 """Core process analysis functionality."""
 
-from .actions import kill_process, kill_processes
+from .actions import (
+    COMMON_SIGNALS,
+    ESCALATE_TIMEOUT_SECONDS,
+    IONICE_CLASSES,
+    IONICE_LEVELS,
+    NICE_RANGE,
+    RENICE_NICE,
+    KillOutcome,
+    KillProgress,
+    KillResult,
+    SignalResult,
+    ionice_process,
+    is_kill_signal,
+    kill_escalating,
+    kill_groups,
+    kill_many,
+    kill_process,
+    kill_processes,
+    parse_ionice,
+    parse_signal,
+    renice_process,
+    signal_choices,
+    signal_group,
+    signal_process,
+)
+from .audit import (
+    AUDIT_ENV,
+    AUDIT_ORIGINS,
+    AuditRecord,
+    audit_log_path,
+    audit_origin,
+    audit_targets,
+    filter_history,
+    log_kills,
+    read_history,
+)
+from .budget import (
+    BUDGET_ACTIONS,
+    BUDGET_VICTIMS,
+    Budget,
+    BudgetUsage,
+    check_budgets,
+    load_budgets,
+    parse_size,
+)
+from .cap import DEFAULT_ROW_CAP, cap_processes, row_cap
+from .capabilities import (
+    LINUX_ONLY_COLUMNS,
+    NO_CAPABILITY_WARNING_ENV,
+    OWNER_ONLY_COLUMNS,
+    Capability,
+    CapabilityReport,
+    capability_report,
+    capability_warning,
+    environment_checks,
+)
+from .cache import (
+    CACHE_ENV,
+    cache_max_age,
+    cache_path,
+    load_snapshot,
+    save_snapshot,
+    snapshot_cache,
+)
+from .clean import CLEAN_CATEGORIES, clean_targets
+from .config import CONFIG_ENV, config_path, load_config
+from .daemon import (
+    DAEMON_INTERVAL,
+    NO_DAEMON_ENV,
+    SNAPSHOT_MAX_AGE,
+    SOCKET_ENV,
+    SnapshotDaemon,
+    daemon_process_list,
+    socket_path,
+)
+from .dataloss import (
+    data_loss_match,
+    data_loss_patterns,
+    data_loss_targets,
+    refuse_data_loss_kill,
+)
+from .defaults import DEFAULTS_KEYS, Defaults, load_defaults
+from .details import ProcessDetails, get_process_details
+from .duplicates import (
+    GROUP_BY,
+    KEEP_CHOICES,
+    command_key,
+    duplicate_victims,
+    find_duplicates,
+    name_key,
+)
 from .constants import (
     CONFIRM_PREVIEW_LIMIT,
+    CPU_HOG_THRESHOLD_PERCENT,
+    CPU_SAMPLE_SECONDS,
     CRITICAL_SERVICES,
     CWD_MAX_WIDTH,
     CWD_TRUNCATE_WIDTH,
+    DATA_LOSS_SERVICES,
+    DETAIL_LIST_LIMIT,
     HIGH_MEMORY_THRESHOLD_MB,
+    LIST_WATCH_SECONDS,
+    MEMORY_SETTLE_SECONDS,
     PREVIEW_LIMIT,
+    REALTIME_POLICIES,
+    SCHED_POLICIES,
+    SECCOMP_MODES,
+    STATUS_CRITICAL_PERCENT,
+    STATUS_WARNING_PERCENT,
     SYSTEM_EXE_PATHS,
+    WATCH_INTERVAL,
+)
+from .cpu import (
+    BUSY_SYSTEM_PERCENT,
+    CORE_LEVELS,
+    PINNED_CORE_PERCENT,
+    CoreUsage,
+    CpuState,
+    core_usage,
+)
+from .emergency import (
+    DEFAULT_MAX_KILLS,
+    EMERGENCY_COOLDOWN_SECONDS,
+    EmergencyPolicy,
+    Pressure,
+    emergency_targets,
+    load_emergency_policy,
+    read_pressure,
+    read_psi_full_avg10,
+)
+from .environ import DEFAULT_ENV_PATTERNS, attach_env, parse_env_patterns, select_env
+from .errors import ErrorCode, ProccleanError
+from .exporter import (
+    EXPORT_PORT,
+    EXPORT_TOP,
+    METRICS_PATH,
+    MetricsExporter,
+    parse_listen,
+    render_metrics,
+)
+from .fields import ALL_FIELDS, SLOW_FIELDS, STATUS_FIELDS, FieldMask
+from .filtercmd import (
+    FILTER_CMD_FIELDS,
+    FILTER_CMD_JOBS,
+    FILTER_CMD_TIMEOUT,
+    filter_by_command,
+    parse_filter_command,
 )
 from .filters import (
+    SORT_KEYS,
+    MatchMode,
+    NameMatcher,
+    ProcessSummary,
+    SortBy,
+    filter_by_age,
+    filter_by_cmdline,
+    filter_by_cpu,
     filter_by_cwd,
+    filter_by_search,
+    filter_by_user,
+    filter_cpu_hogs,
+    filter_cwd_deleted,
+    filter_env_deleted,
     filter_high_memory,
     filter_killable,
+    filter_needs_restart,
     filter_orphans,
     filter_stale,
     is_system_service,
+    natural_key,
+    natural_sort_enabled,
+    parse_sort,
+    priority_note,
+    protected_names,
+    resolve_user,
+    search_spans,
     sort_processes,
+    summarize_processes,
+)
+from .inhibit import (
+    Inhibitor,
+    attach_inhibitors,
+    exclude_inhibiting,
+    find_inhibiting,
+    list_inhibitors,
+)
+from .jobs import current_session, get_session_jobs
+from .killhooks import (
+    HOOK_FAILURE_POLICIES,
+    KILL_HOOK_TIMEOUT,
+    KillHookCommands,
+    load_kill_hooks,
+)
+from .kube import (
+    KUBELET_DIR,
+    POD_LOG_DIR,
+    PodNames,
+    PodRef,
+    attach_pods,
+    in_pod,
+    pod_from_cgroup,
+    pod_names,
+)
+from .leftovers import (
+    Leftover,
+    default_scan_dirs,
+    find_leftovers,
+    find_stale_locks,
+    find_stale_sockets,
+    leftover_holders,
+    remove_leftover,
 )
-from .memory import get_memory_summary
+from .manifest import (
+    Manifest,
+    ProcessRule,
+    VerifyResult,
+    load_manifest,
+    verify_manifest,
+)
+from .media import (
+    MEDIA_KINDS,
+    attach_media,
+    exclude_media_users,
+    find_media_users,
+    parse_pw_dump,
+    pipewire_streams,
+)
+from .memory import ReclaimReport, get_memory_summary, measure_reclaim
 from .models import ProcessInfo
+from .noconfirm import no_confirm_match, no_confirm_patterns
+from .oom import (
+    OomKill,
+    OomStatus,
+    OomTracker,
+    describe_oom,
+    last_oom_kill,
+    oom_status,
+    read_oom_count,
+)
+from .packages import (
+    attach_packages,
+    exe_digest,
+    has_package_manager,
+    package_owner,
+)
+from .plugins import (
+    FORMAT_ENV,
+    PLUGIN_PREFIX,
+    SNAPSHOT_ENV,
+    VERSION_ENV,
+    Plugin,
+    find_plugin,
+    find_plugins,
+    run_plugin,
+)
+from .polkit import (
+    HELPER_NAME,
+    HELPER_SIGNALS,
+    POLKIT_ACTION,
+    POLKIT_AGENT_ONLY,
+    POLKIT_ENV,
+    helper_path,
+    helper_refusal,
+    policy_xml,
+    polkit_enabled,
+    signal_via_helper,
+)
+from .ports import (
+    MAX_PORT,
+    attach_ports,
+    filter_by_port,
+    parse_port,
+    port_numbers,
+    socket_port,
+)
+from .presets import (
+    PRESET_NAME,
+    PRESET_RULES,
+    ConfigPreset,
+    build_preset,
+    load_presets,
+    save_preset,
+)
 from .process import (
+    ALL_USERS,
+    DEFAULT_SOURCE,
+    SMAPS_COUNTERS,
+    PortableSource,
+    ProcessCollector,
+    PsutilSource,
     find_similar_processes,
+    get_cgroup,
+    get_confinement,
     get_cwd,
+    get_deleted_libraries,
+    get_environ,
+    get_listening_ports,
+    get_media_devices,
+    get_open_files,
+    get_privileges,
     get_process_list,
+    get_sched,
+    get_smaps_rollup,
     get_tmux_env,
+    is_cwd_deleted,
     is_exe_deleted,
+    parse_smaps_rollup,
+    require_procfs,
+    select_processes,
+)
+from .quota import (
+    QUOTA_ACTIONS,
+    QUOTA_NICE,
+    QuotaRule,
+    Quotas,
+    QuotaUsage,
+    check_quotas,
+    load_quotas,
+)
+from .redact import (
+    NO_REDACT_ENV,
+    REDACTED,
+    SECRET_PATTERNS,
+    is_secret_name,
+    redact_argv,
+    redact_cmdline,
+    redact_process,
+    redact_text,
+    redact_value,
+)
+from .registry import FILTERS, FilterParam, FilterRegistry, FilterSpec
+from .repo import attach_repos, filter_by_repo, find_repo, read_branch
+from .restart import (
+    Invocation,
+    RestartMethod,
+    RestartPlan,
+    RestartResult,
+    plan_restarts,
+    record_invocation,
+    restart_processes,
+    unit_processes,
+)
+from .run import (
+    RUN_ID_ENV,
+    RUN_POLL_SECONDS,
+    RunResult,
+    find_survivors,
+    run_tracked,
+)
+from .runtime import (
+    RUNTIME_KINDS,
+    RuntimeEnv,
+    attach_runtime_envs,
+    detect_runtime_env,
+)
+from .sandbox import (
+    HARDEN_ENV,
+    SECCOMP_ARCHES,
+    build_filter,
+    check_hardening,
+    hardening_enabled,
+    run_sandboxed,
+)
+from .schedule import INTERVAL_UNITS, parse_interval, run_every
+from .scope import (
+    SCOPE_ENV,
+    SCOPES,
+    ScopedSource,
+    in_cgroup,
+    scope_cgroup,
+    scoped_source,
+    scoped_source_from_env,
+)
+from .scripts import (
+    NO_SCRIPTS_ENV,
+    SCRIPTS,
+    ScriptHooks,
+    load_scripts,
+    scripts_dir,
+)
+from .session import (
+    EVENT_KINDS,
+    RECORD_ENV,
+    REPLAY_ENV,
+    SessionRecorder,
+    load_session,
+    snapshot_processes,
+)
+from .settings import (
+    BUILTIN_SETTINGS,
+    BUILTIN_SOURCE,
+    CONFIG_TABLES,
+    Setting,
+    config_override,
+    config_problems,
+    effective_settings,
+    flatten_config,
+    save_config,
+    set_setting,
+    set_table,
+    unknown_settings,
+)
+from .simulate import (
+    SIMULATE_ENV,
+    FaultPlan,
+    SimulatedSource,
+    parse_fault_plan,
+    simulated_source_from_env,
+)
+from .smaps import attach_smaps, pss_total_mb, top_by_pss
+from .source import (
+    KTHREADD_PID,
+    FakeProcess,
+    FakeProcessSource,
+    KillBackend,
+    ProcessSource,
+)
+from .supervisor import (
+    VIA_MANAGER,
+    Supervisor,
+    command_templates,
+    resolve_supervisors,
+    stop_via_managers,
+    supervisor_from_cgroup,
+)
+from .sysinfo import (
+    SystemInfo,
+    format_uptime,
+    get_system_info,
+    load_average,
+    uptime_seconds,
+)
+from .timing import (
+    PHASES,
+    TimedSource,
+    Timings,
+    active_timings,
+    start_timing,
+    stop_timing,
+    timed,
+)
+from .tree import TreeRow, kill_order, subtree_pids, tree_rows
+from .trend import (
+    CPU_TREND_POINTS,
+    RSS_TREND_MIN_MB,
+    RSS_TREND_PERCENT,
+    TREND_SAMPLES,
+    Trend,
+    TrendTracker,
+)
+from .upgrade import attach_deleted_libs
+from .vm import (
+    VM_PROCESS_NAMES,
+    attach_vms,
+    exclude_vms,
+    get_hugetlb_mb,
+    is_vm,
+    vm_name,
+)
+from .wine import (
+    WINE_HELPERS,
+    WINESERVER,
+    WineGroup,
+    find_wine_groups,
+    is_wine,
+    shutdown_prefix,
+    wine_prefix,
 )
 
 __all__ = [
+    "ALL_FIELDS",
+    "ALL_USERS",
+    "AUDIT_ENV",
+    "AUDIT_ORIGINS",
+    "BUDGET_ACTIONS",
+    "BUDGET_VICTIMS",
+    "BUILTIN_SETTINGS",
+    "BUILTIN_SOURCE",
+    "BUSY_SYSTEM_PERCENT",
+    "CACHE_ENV",
+    "CLEAN_CATEGORIES",
+    "COMMON_SIGNALS",
+    "CONFIG_ENV",
+    "CONFIG_TABLES",
     "CONFIRM_PREVIEW_LIMIT",
+    "CORE_LEVELS",
+    "CPU_HOG_THRESHOLD_PERCENT",
+    "CPU_SAMPLE_SECONDS",
+    "CPU_TREND_POINTS",
     "CRITICAL_SERVICES",
     "CWD_MAX_WIDTH",
     "CWD_TRUNCATE_WIDTH",
+    "DAEMON_INTERVAL",
+    "DATA_LOSS_SERVICES",
+    "DEFAULTS_KEYS",
+    "DEFAULT_ENV_PATTERNS",
+    "DEFAULT_MAX_KILLS",
+    "DEFAULT_ROW_CAP",
+    "DEFAULT_SOURCE",
+    "DETAIL_LIST_LIMIT",
+    "EMERGENCY_COOLDOWN_SECONDS",
+    "ESCALATE_TIMEOUT_SECONDS",
+    "EVENT_KINDS",
+    "EXPORT_PORT",
+    "EXPORT_TOP",
+    "FILTERS",
+    "FILTER_CMD_FIELDS",
+    "FILTER_CMD_JOBS",
+    "FILTER_CMD_TIMEOUT",
+    "FORMAT_ENV",
+    "GROUP_BY",
+    "HARDEN_ENV",
+    "HELPER_NAME",
+    "HELPER_SIGNALS",
     "HIGH_MEMORY_THRESHOLD_MB",
+    "HOOK_FAILURE_POLICIES",
+    "INTERVAL_UNITS",
+    "IONICE_CLASSES",
+    "IONICE_LEVELS",
+    "KEEP_CHOICES",
+    "KILL_HOOK_TIMEOUT",
+    "KTHREADD_PID",
+    "KUBELET_DIR",
+    "LINUX_ONLY_COLUMNS",
+    "LIST_WATCH_SECONDS",
+    "MAX_PORT",
+    "MEDIA_KINDS",
+    "MEMORY_SETTLE_SECONDS",
+    "METRICS_PATH",
+    "NICE_RANGE",
+    "NO_CAPABILITY_WARNING_ENV",
+    "NO_DAEMON_ENV",
+    "NO_REDACT_ENV",
+    "NO_SCRIPTS_ENV",
+    "OWNER_ONLY_COLUMNS",
+    "PHASES",
+    "PINNED_CORE_PERCENT",
+    "PLUGIN_PREFIX",
+    "POD_LOG_DIR",
+    "POLKIT_ACTION",
+    "POLKIT_AGENT_ONLY",
+    "POLKIT_ENV",
+    "PRESET_NAME",
+    "PRESET_RULES",
     "PREVIEW_LIMIT",
+    "QUOTA_ACTIONS",
+    "QUOTA_NICE",
+    "REALTIME_POLICIES",
+    "RECORD_ENV",
+    "REDACTED",
+    "RENICE_NICE",
+    "REPLAY_ENV",
+    "RSS_TREND_MIN_MB",
+    "RSS_TREND_PERCENT",
+    "RUNTIME_KINDS",
+    "RUN_ID_ENV",
+    "RUN_POLL_SECONDS",
+    "SCHED_POLICIES",
+    "SCOPES",
+    "SCOPE_ENV",
+    "SCRIPTS",
+    "SECCOMP_ARCHES",
+    "SECCOMP_MODES",
+    "SECRET_PATTERNS",
+    "SIMULATE_ENV",
+    "SLOW_FIELDS",
+    "SMAPS_COUNTERS",
+    "SNAPSHOT_ENV",
+    "SNAPSHOT_MAX_AGE",
+    "SOCKET_ENV",
+    "SORT_KEYS",
+    "STATUS_CRITICAL_PERCENT",
+    "STATUS_FIELDS",
+    "STATUS_WARNING_PERCENT",
     "SYSTEM_EXE_PATHS",
+    "TREND_SAMPLES",
+    "VERSION_ENV",
+    "VIA_MANAGER",
+    "VM_PROCESS_NAMES",
+    "WATCH_INTERVAL",
+    "WINESERVER",
+    "WINE_HELPERS",
+    "AuditRecord",
+    "Budget",
+    "BudgetUsage",
+    "Capability",
+    "CapabilityReport",
+    "ConfigPreset",
+    "CoreUsage",
+    "CpuState",
+    "Defaults",
+    "EmergencyPolicy",
+    "ErrorCode",
+    "FakeProcess",
+    "FakeProcessSource",
+    "FaultPlan",
+    "FieldMask",
+    "FilterParam",
+    "FilterRegistry",
+    "FilterSpec",
+    "Inhibitor",
+    "Invocation",
+    "KillBackend",
+    "KillHookCommands",
+    "KillOutcome",
+    "KillProgress",
+    "KillResult",
+    "Leftover",
+    "Manifest",
+    "MatchMode",
+    "MetricsExporter",
+    "NameMatcher",
+    "OomKill",
+    "OomStatus",
+    "OomTracker",
+    "Plugin",
+    "PodNames",
+    "PodRef",
+    "PortableSource",
+    "Pressure",
+    "ProccleanError",
+    "ProcessCollector",
+    "ProcessDetails",
     "ProcessInfo",
+    "ProcessRule",
+    "ProcessSource",
+    "ProcessSummary",
+    "PsutilSource",
+    "QuotaRule",
+    "QuotaUsage",
+    "Quotas",
+    "ReclaimReport",
+    "RestartMethod",
+    "RestartPlan",
+    "RestartResult",
+    "RunResult",
+    "RuntimeEnv",
+    "ScopedSource",
+    "ScriptHooks",
+    "SessionRecorder",
+    "Setting",
+    "SignalResult",
+    "SimulatedSource",
+    "SnapshotDaemon",
+    "SortBy",
+    "Supervisor",
+    "SystemInfo",
+    "TimedSource",
+    "Timings",
+    "TreeRow",
+    "Trend",
+    "TrendTracker",
+    "VerifyResult",
+    "WineGroup",
+    "active_timings",
+    "attach_deleted_libs",
+    "attach_env",
+    "attach_inhibitors",
+    "attach_media",
+    "attach_packages",
+    "attach_pods",
+    "attach_ports",
+    "attach_repos",
+    "attach_runtime_envs",
+    "attach_smaps",
+    "attach_vms",
+    "audit_log_path",
+    "audit_origin",
+    "audit_targets",
+    "build_filter",
+    "build_preset",
+    "cache_max_age",
+    "cache_path",
+    "cap_processes",
+    "capability_report",
+    "capability_warning",
+    "check_budgets",
+    "check_hardening",
+    "check_quotas",
+    "clean_targets",
+    "command_key",
+    "command_templates",
+    "config_override",
+    "config_path",
+    "config_problems",
+    "core_usage",
+    "current_session",
+    "daemon_process_list",
+    "data_loss_match",
+    "data_loss_patterns",
+    "data_loss_targets",
+    "default_scan_dirs",
+    "describe_oom",
+    "detect_runtime_env",
+    "duplicate_victims",
+    "effective_settings",
+    "emergency_targets",
+    "environment_checks",
+    "exclude_inhibiting",
+    "exclude_media_users",
+    "exclude_vms",
+    "exe_digest",
+    "filter_by_age",
+    "filter_by_cmdline",
+    "filter_by_command",
+    "filter_by_cpu",
     "filter_by_cwd",
+    "filter_by_port",
+    "filter_by_repo",
+    "filter_by_search",
+    "filter_by_user",
+    "filter_cpu_hogs",
+    "filter_cwd_deleted",
+    "filter_env_deleted",
     "filter_high_memory",
+    "filter_history",
     "filter_killable",
+    "filter_needs_restart",
     "filter_orphans",
     "filter_stale",
+    "find_duplicates",
+    "find_inhibiting",
+    "find_leftovers",
+    "find_media_users",
+    "find_plugin",
+    "find_plugins",
+    "find_repo",
     "find_similar_processes",
+    "find_stale_locks",
+    "find_stale_sockets",
+    "find_survivors",
+    "find_wine_groups",
+    "flatten_config",
+    "format_uptime",
+    "get_cgroup",
+    "get_confinement",
     "get_cwd",
+    "get_deleted_libraries",
+    "get_environ",
+    "get_hugetlb_mb",
+    "get_listening_ports",
+    "get_media_devices",
     "get_memory_summary",
+    "get_open_files",
+    "get_privileges",
+    "get_process_details",
     "get_process_list",
+    "get_sched",
+    "get_session_jobs",
+    "get_smaps_rollup",
+    "get_system_info",
     "get_tmux_env",
+    "hardening_enabled",
+    "has_package_manager",
+    "helper_path",
+    "helper_refusal",
+    "in_cgroup",
+    "in_pod",
+    "ionice_process",
+    "is_cwd_deleted",
     "is_exe_deleted",
+    "is_kill_signal",
+    "is_secret_name",
     "is_system_service",
+    "is_vm",
+    "is_wine",
+    "kill_escalating",
+    "kill_groups",
+    "kill_many",
+    "kill_order",
     "kill_process",
     "kill_processes",
+    "last_oom_kill",
+    "leftover_holders",
+    "list_inhibitors",
+    "load_average",
+    "load_budgets",
+    "load_config",
+    "load_defaults",
+    "load_emergency_policy",
+    "load_kill_hooks",
+    "load_manifest",
+    "load_presets",
+    "load_quotas",
+    "load_scripts",
+    "load_session",
+    "load_snapshot",
+    "log_kills",
+    "measure_reclaim",
+    "name_key",
+    "natural_key",
+    "natural_sort_enabled",
+    "no_confirm_match",
+    "no_confirm_patterns",
+    "oom_status",
+    "package_owner",
+    "parse_env_patterns",
+    "parse_fault_plan",
+    "parse_filter_command",
+    "parse_interval",
+    "parse_ionice",
+    "parse_listen",
+    "parse_port",
+    "parse_pw_dump",
+    "parse_signal",
+    "parse_size",
+    "parse_smaps_rollup",
+    "parse_sort",
+    "pipewire_streams",
+    "plan_restarts",
+    "pod_from_cgroup",
+    "pod_names",
+    "policy_xml",
+    "polkit_enabled",
+    "port_numbers",
+    "priority_note",
+    "protected_names",
+    "pss_total_mb",
+    "read_branch",
+    "read_history",
+    "read_oom_count",
+    "read_pressure",
+    "read_psi_full_avg10",
+    "record_invocation",
+    "redact_argv",
+    "redact_cmdline",
+    "redact_process",
+    "redact_text",
+    "redact_value",
+    "refuse_data_loss_kill",
+    "remove_leftover",
+    "render_metrics",
+    "renice_process",
+    "require_procfs",
+    "resolve_supervisors",
+    "resolve_user",
+    "restart_processes",
+    "row_cap",
+    "run_every",
+    "run_plugin",
+    "run_sandboxed",
+    "run_tracked",
+    "save_config",
+    "save_preset",
+    "save_snapshot",
+    "scope_cgroup",
+    "scoped_source",
+    "scoped_source_from_env",
+    "scripts_dir",
+    "search_spans",
+    "select_env",
+    "select_processes",
+    "set_setting",
+    "set_table",
+    "shutdown_prefix",
+    "signal_choices",
+    "signal_group",
+    "signal_process",
+    "signal_via_helper",
+    "simulated_source_from_env",
+    "snapshot_cache",
+    "snapshot_processes",
+    "socket_path",
+    "socket_port",
     "sort_processes",
+    "start_timing",
+    "stop_timing",
+    "stop_via_managers",
+    "subtree_pids",
+    "summarize_processes",
+    "supervisor_from_cgroup",
+    "timed",
+    "top_by_pss",
+    "tree_rows",
+    "unit_processes",
+    "unknown_settings",
+    "uptime_seconds",
+    "verify_manifest",
+    "vm_name",
+    "wine_prefix",
 ]
@@ -1,46 +1,727 @@
-"""Process kill actions."""
+"""Process kill, renice and ionice actions.
+
+Kills send SIGTERM, or SIGKILL when forced. Any other signal can be sent
+instead, e.g. SIGHUP to make a daemon reload its config or SIGSTOP and
+SIGCONT to pause and resume a process. Kills run the pre- and post-kill hooks
+of user scripts (see ``scripts``), then the configured hook commands (see
+``killhooks``), and are written to the audit log (see ``audit``).
+
+Kills by process group (``kill_groups``) signal every member of a group at
+once, like ``kill -SIG -PGID``, which is how shells stop whole pipelines.
+
+A plain kill only reports whether the signal was delivered. An escalating
+kill (``kill_escalating``) also waits for the processes to exit and falls back
+to SIGKILL for those that ignore SIGTERM.
+
+Renices and ionices lower (or raise) a process's CPU and I/O priority instead,
+for runaway processes worth keeping.
+"""
+
+import signal
+import time
+from collections.abc import Callable
+from dataclasses import dataclass
+from enum import StrEnum, auto
+from typing import Any
 
 import psutil
 
+from procclean.i18n import t
+
+from .audit import audit_log_path, log_kills
+from .errors import ErrorCode, ProccleanError
+from .killhooks import KillHookCommands, load_kill_hooks
+from .models import ProcessInfo
+from .polkit import polkit_enabled, signal_via_helper
+from .process import ALL_USERS, DEFAULT_SOURCE, PsutilSource, get_process_list
+from .scripts import SCRIPTS
+from .source import ProcessSource
+
+# Signals offered by the TUI picker, in order; missing ones are skipped
+COMMON_SIGNALS = ("TERM", "KILL", "HUP", "INT", "QUIT", "USR1", "USR2", "STOP", "CONT")
+
+# I/O scheduling classes by name, as in ioprio_set(2)
+IONICE_CLASSES: dict[str, int] = {"none": 0, "realtime": 1, "best-effort": 2, "idle": 3}
+IONICE_LEVELS = range(8)  # Levels within the realtime and best-effort classes
+NICE_RANGE = range(-20, 20)  # Valid nice values, most to least favorable
+RENICE_NICE = 19  # Nice value `procclean renice` sets by default: the lowest
+
+ESCALATE_TIMEOUT_SECONDS = 5.0  # Wait after SIGTERM before sending SIGKILL
+ESCALATE_POLL_SECONDS = 0.1  # How often waiting kills check for exits
+SIGKILL_WAIT_SECONDS = 1.0  # Wait after SIGKILL before giving up
+
+
+class KillOutcome(StrEnum):
+    """How an escalating kill went for one process."""
+
+    PENDING = auto()  # Signaled, not exited yet (progress reports only)
+    TERMINATED = auto()  # Exited after SIGTERM
+    ESCALATED = auto()  # Ignored SIGTERM, exited after SIGKILL
+    ALIVE = auto()  # Still running after SIGKILL
+    FAILED = auto()  # Not signaled: gone, access denied or vetoed
+
+
+@dataclass(frozen=True, slots=True)
+class KillResult:
+    """Result of an escalating kill of one process."""
+
+    pid: int
+    outcome: KillOutcome
+    message: str
+    seconds: float = 0.0  # From SIGTERM until the process exited
+
+    @property
+    def ok(self) -> bool:
+        """Whether the process is gone because of the kill."""
+        return self.outcome in {KillOutcome.TERMINATED, KillOutcome.ESCALATED}
+
+    def to_dict(self) -> dict[str, Any]:
+        """Describe the result for JSON output and session recordings.
+
+        Returns:
+            dict[str, Any]: PID, outcome, success, message and seconds.
+        """
+        return {
+            "pid": self.pid,
+            "outcome": str(self.outcome),
+            "ok": self.ok,
+            "message": self.message,
+            "seconds": round(self.seconds, 2),
+        }
+
+
+@dataclass(frozen=True, slots=True)
+class SignalResult:
+    """Result of signaling one process with ``kill_many``."""
+
+    pid: int
+    signal: str  # E.g. "SIGTERM"
+    ok: bool  # Whether the signal was delivered, not whether the process exited
+    message: str
 
-def kill_process(pid: int, force: bool = False) -> tuple[bool, str]:
-    """Kill a process by PID.
+    def to_dict(self) -> dict[str, Any]:
+        """Describe the result for JSON output and data frames.
+
+        Returns:
+            dict[str, Any]: PID, signal, success and message.
+        """
+        return {
+            "pid": self.pid,
+            "signal": self.signal,
+            "ok": self.ok,
+            "message": self.message,
+        }
+
+
+# Called while an escalating kill waits, with every result so far and the
+# seconds left before the next step
+KillProgress = Callable[[list[KillResult], float], None]
+
+
+def parse_signal(value: str) -> signal.Signals:
+    """Parse a signal name or number.
+
+    Args:
+        value: Name with or without ``SIG`` in any case, e.g. ``hup`` or
+            ``SIGUSR1``, or a number such as ``9``.
+
+    Returns:
+        signal.Signals: The signal.
+
+    Raises:
+        ValueError: If the platform has no such signal.
+    """
+    name = value.strip().upper()
+    try:
+        if name.isdigit():
+            return signal.Signals(int(name))
+        return signal.Signals[name if name.startswith("SIG") else f"SIG{name}"]
+    except (KeyError, ValueError):
+        choices = ", ".join(s.name.removeprefix("SIG") for s in signal_choices())
+        msg = f"unknown signal '{value}' (e.g. {choices})"
+        raise ValueError(msg) from None
+
+
+def parse_ionice(value: str) -> tuple[int, int | None]:
+    """Parse an I/O priority, as a class with an optional level.
+
+    Args:
+        value: Class name from ``IONICE_CLASSES``, e.g. ``idle``, optionally
+            with a level for realtime and best-effort, e.g. ``best-effort:7``.
+
+    Returns:
+        tuple[int, int | None]: Class number and level; None for the level
+        leaves it to the kernel.
+
+    Raises:
+        ValueError: If the class is unknown or the level does not apply.
+    """
+    name, _, level = value.strip().lower().partition(":")
+    if name not in IONICE_CLASSES:
+        msg = f"unknown I/O class '{name}' (expected {', '.join(IONICE_CLASSES)})"
+        raise ValueError(msg)
+    if not level:
+        return IONICE_CLASSES[name], None
+    leveled = name in {"realtime", "best-effort"}
+    if not (leveled and level.isdigit() and int(level) in IONICE_LEVELS):
+        msg = f"invalid I/O priority '{value}' (realtime and best-effort take 0-7)"
+        raise ValueError(msg)
+    return IONICE_CLASSES[name], int(level)
+
+
+def signal_choices() -> list[signal.Signals]:
+    """List the common signals this platform has.
+
+    Returns:
+        list[signal.Signals]: Signals from ``COMMON_SIGNALS``, in order.
+    """
+    return [
+        signal.Signals[f"SIG{name}"]
+        for name in COMMON_SIGNALS
+        if hasattr(signal, f"SIG{name}")
+    ]
+
+
+def is_kill_signal(sig: signal.Signals | None) -> bool:
+    """Check whether a signal is a plain kill (SIGTERM or SIGKILL).
+
+    Returns:
+        bool: True for None (the default kill), SIGTERM and SIGKILL.
+    """
+    return sig is None or sig.name in {"SIGTERM", "SIGKILL"}
+
+
+def signal_process(
+    pid: int,
+    force: bool = False,
+    source: ProcessSource | None = None,
+    sig: signal.Signals | None = None,
+) -> None:
+    """Kill a process by PID, raising a typed error on failure.
+
+    With ``$PROCCLEAN_POLKIT`` set, a live kill the user may not send goes
+    through the polkit helper instead (see ``polkit``).
 
     Args:
         pid: Process ID to kill.
         force: If True, force kill the process; otherwise, terminate gracefully.
+        source: Process backend; defaults to the live system.
+        sig: Signal to send instead; SIGTERM and SIGKILL override ``force``.
+
+    Raises:
+        ProccleanError: With ``PROCESS_NOT_FOUND``, ``PERMISSION_DENIED`` or
+            ``KILL_FAILED``.
+    """
+    backend = source or DEFAULT_SOURCE
+    try:
+        if sig is None:
+            backend.send_signal(pid, force)
+        elif is_kill_signal(sig):
+            backend.send_signal(pid, sig.name == "SIGKILL")
+        else:
+            backend.send(pid, sig)
+    except psutil.NoSuchProcess as e:
+        msg = t("kill-not-found", pid=pid)
+        raise ProccleanError(ErrorCode.PROCESS_NOT_FOUND, msg, pid=pid) from e
+    except psutil.AccessDenied as e:
+        # Only live kills escalate: a scope or simulation denies on purpose
+        if polkit_enabled() and isinstance(backend, PsutilSource):
+            signal_via_helper(pid, sig or (signal.SIGKILL if force else signal.SIGTERM))
+            return
+        msg = t("kill-access-denied", pid=pid)
+        raise ProccleanError(ErrorCode.PERMISSION_DENIED, msg, pid=pid) from e
+    except OSError as e:
+        msg = t("kill-error", error=e)
+        raise ProccleanError(ErrorCode.KILL_FAILED, msg, pid=pid) from e
+
+
+def signal_group(
+    pgid: int,
+    force: bool = False,
+    source: ProcessSource | None = None,
+    sig: signal.Signals | None = None,
+) -> None:
+    """Signal a whole process group, raising a typed error on failure.
+
+    Args:
+        pgid: Process group ID.
+        force: If True, send SIGKILL; otherwise, SIGTERM.
+        source: Process backend; defaults to the live system.
+        sig: Signal to send instead; overrides ``force``.
+
+    Raises:
+        ProccleanError: With ``PROCESS_NOT_FOUND``, ``PERMISSION_DENIED`` or
+            ``KILL_FAILED``.
+    """
+    if sig is None:
+        sig = signal.SIGKILL if force else signal.SIGTERM
+    try:
+        (source or DEFAULT_SOURCE).signal_group(pgid, sig)
+    except psutil.NoSuchProcess as e:
+        msg = t("kill-group-not-found", pgid=pgid)
+        raise ProccleanError(ErrorCode.PROCESS_NOT_FOUND, msg, pgid=pgid) from e
+    except psutil.AccessDenied as e:
+        msg = t("kill-group-access-denied", pgid=pgid)
+        raise ProccleanError(ErrorCode.PERMISSION_DENIED, msg, pgid=pgid) from e
+    except OSError as e:
+        msg = t("kill-error", error=e)
+        raise ProccleanError(ErrorCode.KILL_FAILED, msg, pgid=pgid) from e
+
+
+def _hook_targets(
+    pids: list[int], source: ProcessSource | None
+) -> dict[int, ProcessInfo]:
+    """Look up the processes the kill hooks and the audit log get.
+
+    Returns:
+        dict[int, ProcessInfo]: Live processes by PID; empty without hooks
+        and audit log.
+
+    Raises:
+        ProccleanError: With ``INVALID_CONFIG`` for malformed hook commands
+            or ``[audit]`` settings.
+    """
+    if not (SCRIPTS.kill_hooks or load_kill_hooks().active or audit_log_path()):
+        return {}
+    wanted = set(pids)
+    procs = get_process_list(
+        filter_user=ALL_USERS, min_memory_mb=0, source=source, kernel_threads=True
+    )
+    return {p.pid: p for p in procs if p.pid in wanted}
+
+
+def _kernel_threads(
+    pids: list[int], targets: dict[int, ProcessInfo], source: ProcessSource | None
+) -> set[int]:
+    """Find the kernel threads among the PIDs, which are never signaled.
+
+    Returns:
+        set[int]: The kernel threads; asked of the source for PIDs the hooks
+        did not look up.
+    """
+    backend = source or DEFAULT_SOURCE
+    found = set()
+    for pid in pids:
+        proc = targets.get(pid)
+        if proc.is_kernel_thread if proc else backend.kernel_thread(pid):
+            found.add(pid)
+    return found
+
+
+def _before_kill(
+    proc: ProcessInfo | None, commands: KillHookCommands
+) -> tuple[str | None, list[str]]:
+    """Run the pre-kill script hooks, then the pre-kill command.
+
+    Returns:
+        tuple[str | None, list[str]]: Why the kill is vetoed, or None to go
+        ahead; and warnings to add to the kill result.
+    """
+    if proc is None:
+        return None, []
+    if veto := SCRIPTS.check_kill(proc):
+        return veto, []
+    veto, warning = commands.check_kill(proc)
+    return veto, [warning] if warning else []
+
+
+def _after_kill(
+    proc: ProcessInfo | None,
+    ok: bool,
+    message: str,
+    commands: KillHookCommands,
+    warnings: list[str],
+) -> str:
+    """Run the post-kill script hooks, then the post-kill command.
+
+    Returns:
+        str: The kill message, with failed hooks and warnings appended.
+    """
+    errors = list(warnings)
+    if proc is not None:
+        errors += SCRIPTS.after_kill(proc, ok, message)
+        if error := commands.after_kill(proc, ok, message):
+            errors.append(error)
+    if not errors:
+        return message
+    return t("kill-hook-failed", message=message, errors="; ".join(errors))
+
+
+def kill_process(
+    pid: int,
+    force: bool = False,
+    source: ProcessSource | None = None,
+    sig: signal.Signals | None = None,
+    proc: ProcessInfo | None = None,
+) -> tuple[bool, str]:
+    """Kill a process by PID, between the pre- and post-kill script hooks.
+
+    Args:
+        pid: Process ID to kill.
+        force: If True, force kill the process; otherwise, terminate gracefully.
+        source: Process backend; defaults to the live system.
+        sig: Signal to send instead (see ``signal_process``).
+        proc: The process for the hooks; looked up if there are hooks.
 
     Returns:
         A tuple of (success, message) indicating whether the operation succeeded and
-        providing a human-readable message.
+        providing a human-readable message; a vetoed kill fails with the reason.
+        Kernel threads are never signaled.
+    """
+    if proc is None:
+        proc = _hook_targets([pid], source).get(pid)
+    targets = {pid: proc} if proc else {}
+    if _kernel_threads([pid], targets, source):
+        ok, msg = False, t("kill-kernel-thread", pid=pid)
+    else:
+        ok, msg = _kill_one(pid, force, source, sig, proc)
+    name = (sig or (signal.SIGKILL if force else signal.SIGTERM)).name
+    log_kills([(pid, name, ok, msg)], {pid: proc} if proc else {}, source)
+    return ok, msg
+
+
+def _kill_one(
+    pid: int,
+    force: bool,
+    source: ProcessSource | None,
+    sig: signal.Signals | None,
+    proc: ProcessInfo | None,
+) -> tuple[bool, str]:
+    """Kill a process between the hooks, as ``kill_process`` does.
+
+    Returns:
+        tuple[bool, str]: Success and message.
     """
+    commands = load_kill_hooks()
+    veto, warnings = _before_kill(proc, commands)
+    if veto:
+        return False, veto
     try:
-        proc = psutil.Process(pid)
-        if force:
-            proc.kill()
+        signal_process(pid, force, source, sig)
+    except ProccleanError as e:
+        ok, msg = False, e.message
+    else:
+        ok = True
+        if sig is not None and not is_kill_signal(sig):
+            msg = t("kill-signaled", pid=pid, signal=sig.name)
+        else:
+            msg = t("kill-terminated", pid=pid)
+    return ok, _after_kill(proc, ok, msg, commands, warnings)
+
+
+def _exited(pid: int, outcome: KillOutcome, seconds: float) -> KillResult:
+    key = "kill-exited" if outcome is KillOutcome.TERMINATED else "kill-escalated"
+    return KillResult(pid, outcome, t(key, pid=pid, seconds=f"{seconds:.1f}"), seconds)
+
+
+def _escalate(
+    waiting: list[int],
+    results: dict[int, KillResult],
+    source: ProcessSource | None,
+    start: float,
+) -> None:
+    """Send SIGKILL to the processes still waiting after SIGTERM.
+
+    PIDs that cannot be signaled leave ``waiting`` with their result set.
+    """
+    for pid in list(waiting):
+        try:
+            signal_process(pid, force=True, source=source)
+        except ProccleanError as e:
+            waiting.remove(pid)
+            if e.code is ErrorCode.PROCESS_NOT_FOUND:  # Exited in the meantime
+                elapsed = time.monotonic() - start
+                results[pid] = _exited(pid, KillOutcome.TERMINATED, elapsed)
+            else:
+                results[pid] = KillResult(pid, KillOutcome.ALIVE, e.message)
+        else:
+            msg = t("kill-waiting", pid=pid, signal="SIGKILL")
+            results[pid] = KillResult(pid, KillOutcome.PENDING, msg)
+
+
+def _after_kills(
+    results: dict[int, KillResult],
+    targets: dict[int, ProcessInfo],
+    commands: KillHookCommands,
+    warnings: dict[int, list[str]],
+) -> None:
+    """Run the post-kill hooks for the processes in ``targets``."""
+    for pid, result in results.items():
+        proc = targets.get(pid)
+        pending = warnings.get(pid, [])
+        if proc is None and not pending:
+            continue
+        msg = _after_kill(proc, result.ok, result.message, commands, pending)
+        results[pid] = KillResult(pid, result.outcome, msg, result.seconds)
+
+
+def kill_escalating(
+    pids: list[int],
+    timeout: float = ESCALATE_TIMEOUT_SECONDS,
+    source: ProcessSource | None = None,
+    on_progress: KillProgress | None = None,
+) -> list[KillResult]:
+    """Send SIGTERM, wait for the processes to exit, then SIGKILL the rest.
+
+    All targets are signaled at once and waited for together, so the whole
+    kill takes at most ``timeout`` plus ``SIGKILL_WAIT_SECONDS``. Script
+    hooks run around it like around ``kill_process``.
+
+    Args:
+        pids: Process IDs to kill; duplicates are killed once.
+        timeout: Seconds to wait after SIGTERM.
+        source: Process backend; defaults to the live system.
+        on_progress: Called after every check while waiting.
+
+    Returns:
+        list[KillResult]: One result per PID, in order.
+    """
+    backend = source or DEFAULT_SOURCE
+    targets = _hook_targets(pids, source)
+    kernel_threads = _kernel_threads(pids, targets, source)
+    logged = dict(targets)
+    commands = load_kill_hooks()
+    results: dict[int, KillResult] = {}
+    warnings: dict[int, list[str]] = {}
+    for pid in dict.fromkeys(pids):
+        if pid in kernel_threads:
+            msg = t("kill-kernel-thread", pid=pid)
+            results[pid] = KillResult(pid, KillOutcome.FAILED, msg)
+            targets.pop(pid, None)  # Never signaled, so no post-kill hooks
+            continue
+        veto, warnings[pid] = _before_kill(targets.get(pid), commands)
+        if veto:
+            results[pid] = KillResult(pid, KillOutcome.FAILED, veto)
+            targets.pop(pid, None)  # Vetoed kills skip the post-kill hooks
+            continue
+        try:
+            signal_process(pid, source=source)
+        except ProccleanError as e:
+            results[pid] = KillResult(pid, KillOutcome.FAILED, e.message)
+        else:
+            msg = t("kill-waiting", pid=pid, signal="SIGTERM")
+            results[pid] = KillResult(pid, KillOutcome.PENDING, msg)
+    waiting = [pid for pid, r in results.items() if r.outcome is KillOutcome.PENDING]
+    start = time.monotonic()
+
+    def wait(deadline: float, outcome: KillOutcome) -> None:
+        while True:
+            for pid in [pid for pid in waiting if not backend.alive(pid)]:
+                waiting.remove(pid)
+                results[pid] = _exited(pid, outcome, time.monotonic() - start)
+            left = max(deadline - time.monotonic(), 0.0)
+            if on_progress is not None:
+                on_progress(list(results.values()), left)
+            if not waiting or left <= 0:
+                return
+            time.sleep(min(ESCALATE_POLL_SECONDS, left))
+
+    if waiting:
+        wait(start + timeout, KillOutcome.TERMINATED)
+    if waiting:
+        _escalate(waiting, results, source, start)
+        wait(time.monotonic() + SIGKILL_WAIT_SECONDS, KillOutcome.ESCALATED)
+    for pid in waiting:
+        msg = t("kill-alive", pid=pid, seconds=f"{time.monotonic() - start:.1f}")
+        results[pid] = KillResult(pid, KillOutcome.ALIVE, msg)
+    _after_kills(results, targets, commands, warnings)
+    sigkill = {KillOutcome.ESCALATED, KillOutcome.ALIVE}
+    log_kills(
+        (
+            (r.pid, "SIGKILL" if r.outcome in sigkill else "SIGTERM", r.ok, r.message)
+            for r in results.values()
+        ),
+        logged,
+        source,
+    )
+    return list(results.values())
+
+
+def _kill_group(
+    pgid: int,
+    members: list[ProcessInfo],
+    sig: signal.Signals,
+    source: ProcessSource | None,
+    targets: dict[int, ProcessInfo],
+    kernel_threads: set[int],
+) -> list[tuple[int, bool, str]]:
+    """Signal one process group between the hooks of its members.
+
+    A veto for any member, or a kernel thread among them, spares the whole
+    group.
+
+    Returns:
+        list[tuple[int, bool, str]]: One (pid, success, message) per member.
+    """
+    commands = load_kill_hooks()
+    warnings: dict[int, list[str]] = {}
+    for proc in members:
+        if proc.pid in kernel_threads:
+            veto = t("kill-kernel-thread", pid=proc.pid)
         else:
-            proc.terminate()
-        return True, f"Process {pid} terminated"
+            veto, warnings[proc.pid] = _before_kill(targets.get(proc.pid), commands)
+        if veto:
+            warnings.pop(proc.pid, None)  # Vetoed kills skip the post-kill hooks
+            reason = t("kill-group-vetoed", pgid=pgid, pid=proc.pid, reason=veto)
+            break
+    else:
+        try:
+            signal_group(pgid, source=source, sig=sig)
+        except ProccleanError as e:
+            reason = e.message
+        else:
+            reason = None
+    ok = reason is None
+    results = []
+    for proc in members:
+        msg = reason or t(
+            "kill-group-signaled", pid=proc.pid, pgid=pgid, signal=sig.name
+        )
+        if proc.pid in warnings:
+            hooked = targets.get(proc.pid)
+            msg = _after_kill(hooked, ok, msg, commands, warnings[proc.pid])
+        results.append((proc.pid, ok, msg))
+    return results
+
+
+def kill_groups(
+    procs: list[ProcessInfo],
+    force: bool = False,
+    source: ProcessSource | None = None,
+    sig: signal.Signals | None = None,
+) -> list[tuple[int, bool, str]]:
+    """Kill the process groups of the given processes, one signal per group.
+
+    Each group is signaled at once with ``signal_group``, so members forked in
+    the meantime are not missed. Script hooks run for every member as they do
+    for ``kill_process``.
+
+    Args:
+        procs: Members of the groups to kill, with ``pgid`` set.
+        force: If True, send SIGKILL; otherwise, SIGTERM.
+        source: Process backend; defaults to the live system.
+        sig: Signal to send instead; overrides ``force``.
+
+    Returns:
+        A list of tuples (pid, success, message) for each process, in order.
+    """
+    if sig is None:
+        sig = signal.SIGKILL if force else signal.SIGTERM
+    pids = [p.pid for p in procs]
+    targets = _hook_targets(pids, source)
+    kernel_threads = _kernel_threads(pids, targets, source)
+    groups: dict[int, list[ProcessInfo]] = {}
+    for proc in procs:
+        groups.setdefault(proc.pgid, []).append(proc)
+    results = {}
+    for pgid, members in groups.items():
+        killed = _kill_group(pgid, members, sig, source, targets, kernel_threads)
+        for pid, ok, msg in killed:
+            results[pid] = (pid, ok, msg)
+    ordered = [results[p.pid] for p in procs]
+    log_kills(((pid, sig.name, ok, msg) for pid, ok, msg in ordered), targets, source)
+    return ordered
+
+
+def renice_process(
+    pid: int, nice: int, source: ProcessSource | None = None
+) -> tuple[bool, str]:
+    """Change a process's nice value.
+
+    Args:
+        pid: Process ID to renice.
+        nice: New nice value (-20 to 19).
+        source: Process backend; defaults to the live system.
+
+    Returns:
+        A tuple of (success, message) like ``kill_process``.
+    """
+    try:
+        (source or DEFAULT_SOURCE).renice(pid, nice)
     except psutil.NoSuchProcess:
-        return False, f"Process {pid} not found"
+        return False, t("kill-not-found", pid=pid)
     except psutil.AccessDenied:
-        return False, f"Access denied for process {pid}"
+        return False, t("kill-access-denied", pid=pid)
     except OSError as e:
-        return False, f"Error: {e}"
+        return False, t("kill-error", error=e)
+    return True, t("kill-reniced", pid=pid, nice=nice)
 
 
-def kill_processes(pids: list[int], force: bool = False) -> list[tuple[int, bool, str]]:
+def ionice_process(
+    pid: int,
+    ioclass: int,
+    level: int | None = None,
+    source: ProcessSource | None = None,
+) -> tuple[bool, str]:
+    """Change a process's I/O scheduling class and level.
+
+    Args:
+        pid: Process ID to ionice.
+        ioclass: Class number from ``IONICE_CLASSES``.
+        level: Level within the realtime and best-effort classes (0-7, lower
+            is more important); None leaves it to the kernel.
+        source: Process backend; defaults to the live system.
+
+    Returns:
+        A tuple of (success, message) like ``kill_process``.
+    """
+    try:
+        (source or DEFAULT_SOURCE).ionice(pid, ioclass, level)
+    except psutil.NoSuchProcess:
+        return False, t("kill-not-found", pid=pid)
+    except psutil.AccessDenied:
+        return False, t("kill-access-denied", pid=pid)
+    except OSError as e:
+        return False, t("kill-error", error=e)
+    name = next(k for k, v in IONICE_CLASSES.items() if v == ioclass)
+    priority = name if level is None else f"{name}:{level}"
+    return True, t("kill-ioniced", pid=pid, priority=priority)
+
+
+def kill_processes(
+    pids: list[int],
+    force: bool = False,
+    source: ProcessSource | None = None,
+    sig: signal.Signals | None = None,
+) -> list[tuple[int, bool, str]]:
     """Kill multiple processes.
 
     Args:
         pids: Process IDs to kill.
         force: If True, force kill the processes; otherwise, terminate gracefully.
+        source: Process backend; defaults to the live system.
+        sig: Signal to send instead (see ``signal_process``).
 
     Returns:
         A list of tuples (pid, success, message) for each PID attempted.
     """
+    targets = _hook_targets(pids, source)
     results: list[tuple[int, bool, str]] = []
     for pid in pids:
-        success, msg = kill_process(pid, force)
+        success, msg = kill_process(pid, force, source, sig, targets.get(pid))
         results.append((pid, success, msg))
     return results
+
+
+def kill_many(
+    pids: list[int],
+    force: bool = False,
+    source: ProcessSource | None = None,
+    sig: signal.Signals | None = None,
+) -> list[SignalResult]:
+    """Kill multiple processes, like ``kill_processes`` with structured results.
+
+    Args:
+        pids: Process IDs to kill.
+        force: If True, force kill the processes; otherwise, terminate gracefully.
+        source: Process backend; defaults to the live system.
+        sig: Signal to send instead (see ``signal_process``).
+
+    Returns:
+        list[SignalResult]: One result per PID attempted, in order.
+    """
+    name = (sig or (signal.SIGKILL if force else signal.SIGTERM)).name
+    return [
+        SignalResult(pid, name, ok, msg)
+        for pid, ok, msg in kill_processes(pids, force, source, sig)
+    ]
@@ -0,0 +1,285 @@
+"""Audit log of the processes procclean signaled.
+
+Every kill, from the CLI, the TUI or the Python API, appends one JSON line
+per process to ``$XDG_STATE_HOME/procclean/actions.jsonl``
+(``~/.local/state`` by default): when, what (PID, name, command line,
+working directory, RSS), which signal or manager command, the result, who
+ran it, and what picked the process, e.g. the CLI command line or the TUI's
+view and search. ``procclean history`` reads it back.
+
+``$PROCCLEAN_AUDIT_LOG`` logs elsewhere, or nowhere when set but empty; the
+``[audit]`` table of the config file does the same::
+
+    [audit]
+    path = "~/procclean-actions.jsonl"
+    enabled = true
+
+Command lines are redacted unless ``PROCCLEAN_NO_REDACT`` is set. A log
+that cannot be written is skipped: kills never fail because of it.
+"""
+
+import contextlib
+import json
+import os
+import pwd
+import time
+from collections.abc import Iterable, Iterator
+from contextlib import contextmanager
+from dataclasses import asdict, dataclass, fields
+from datetime import UTC, datetime
+from pathlib import Path
+from typing import Any, Self
+
+from .config import load_config
+from .errors import ErrorCode, ProccleanError
+from .models import ProcessInfo
+from .process import ALL_USERS, get_process_list
+from .redact import NO_REDACT_ENV, redact_cmdline, redact_text
+from .simulate import SimulatedSource
+from .source import ProcessSource
+
+AUDIT_ENV = "PROCCLEAN_AUDIT_LOG"
+AUDIT_ORIGINS = ("python", "cli", "tui", "polkit")
+
+
+@dataclass(frozen=True, slots=True)
+class AuditRecord:
+    """One signaled (or vetoed) process."""
+
+    time: str  # ISO 8601, UTC
+    pid: int
+    name: str
+    cmdline: str
+    cwd: str
+    rss_mb: float
+    signal: str  # E.g. "SIGTERM", or the manager command for stops via one
+    ok: bool
+    result: str  # The kill message
+    origin: str = "python"  # One of AUDIT_ORIGINS
+    filter: str | None = None  # What picked the process
+    user: str = ""  # Who ran procclean
+    simulated: bool = False
+
+    @property
+    def timestamp(self) -> float:
+        """Seconds since the epoch, for comparisons."""
+        return datetime.fromisoformat(self.time).timestamp()
+
+    def to_dict(self) -> dict[str, Any]:
+        """Describe the record for the log and JSON output.
+
+        Returns:
+            dict[str, Any]: Every field.
+        """
+        return asdict(self)
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> Self:
+        """Rebuild a record read from the log; unknown keys are ignored.
+
+        Returns:
+            AuditRecord: The record.
+
+        Raises:
+            TypeError: If a required field is missing.
+        """
+        known = {f.name for f in fields(cls)}
+        return cls(**{k: v for k, v in data.items() if k in known})
+
+
+def audit_log_path() -> Path | None:
+    """Locate the audit log.
+
+    Returns:
+        Path | None: ``$PROCCLEAN_AUDIT_LOG``, else ``[audit] path``, else
+        ``actions.jsonl`` in the state directory; None when logging is off.
+
+    Raises:
+        ProccleanError: With ``INVALID_CONFIG`` for a malformed ``[audit]``
+            table.
+    """
+    if (explicit := os.environ.get(AUDIT_ENV)) is not None:
+        return Path(explicit).expanduser() if explicit else None
+    table = load_config().get("audit", {})
+    enabled, path = table.get("enabled", True), table.get("path")
+    if not isinstance(enabled, bool) or not isinstance(path, str | None):
+        msg = "Invalid [audit] config: expected enabled = true/false, path = string"
+        raise ProccleanError(ErrorCode.INVALID_CONFIG, msg, table="audit")
+    if not enabled:
+        return None
+    if path:
+        return Path(path).expanduser()
+    base = os.environ.get("XDG_STATE_HOME") or Path.home() / ".local" / "state"
+    return Path(base) / "procclean" / "actions.jsonl"
+
+
+_origins: list[tuple[str, str | None]] = []
+
+
+@contextmanager
+def audit_origin(origin: str, selection: str | None = None) -> Iterator[None]:
+    """Attribute kills inside the block to a front end.
+
+    Args:
+        origin: One of ``AUDIT_ORIGINS``.
+        selection: What picked the processes, e.g. "kill --name node -y".
+
+    Yields:
+        None: Inside the block.
+    """
+    _origins.append((origin, selection))
+    try:
+        yield
+    finally:
+        _origins.pop()
+
+
+def _current_user() -> str:
+    uid = os.geteuid()
+    try:
+        return pwd.getpwuid(uid).pw_name
+    except KeyError:
+        return str(uid)
+
+
+def audit_targets(
+    pids: Iterable[int], source: ProcessSource | None = None
+) -> dict[int, ProcessInfo]:
+    """Look up processes about to be killed, if the audit log is on.
+
+    Returns:
+        dict[int, ProcessInfo]: Live processes by PID; empty when logging is
+        off.
+    """
+    wanted = set(pids)
+    if not wanted or audit_log_path() is None:
+        return {}
+    procs = get_process_list(
+        filter_user=ALL_USERS, min_memory_mb=0, source=source, kernel_threads=True
+    )
+    return {p.pid: p for p in procs if p.pid in wanted}
+
+
+def log_kills(
+    entries: Iterable[tuple[int, str, bool, str]],
+    targets: dict[int, ProcessInfo],
+    source: ProcessSource | None = None,
+) -> None:
+    """Append one record per kill to the audit log.
+
+    Args:
+        entries: (pid, signal, success, message) of each kill.
+        targets: The killed processes by PID; unknown PIDs are logged with
+            their PID alone.
+        source: Process backend the kills went through.
+    """
+    if (path := audit_log_path()) is None:
+        return
+    origin, selection = _origins[-1] if _origins else ("python", None)
+    redact = NO_REDACT_ENV not in os.environ
+    if redact and selection:
+        selection = redact_text(selection)
+    now = datetime.now(UTC).isoformat(timespec="seconds")
+    common = {
+        "time": now,
+        "origin": origin,
+        "filter": selection,
+        "user": _current_user(),
+        "simulated": isinstance(source, SimulatedSource),
+    }
+    lines = []
+    for pid, sig, ok, message in entries:
+        proc = targets.get(pid)
+        cmdline = proc.cmdline if proc else ""
+        record = AuditRecord(
+            pid=pid,
+            name=proc.name if proc else "?",
+            cmdline=redact_cmdline(cmdline) if redact else cmdline,
+            cwd=proc.cwd if proc else "?",
+            rss_mb=round(proc.rss_mb, 2) if proc else 0.0,
+            signal=sig,
+            ok=ok,
+            result=message,
+            **common,
+        )
+        lines.append(json.dumps(record.to_dict()) + "\n")
+    if not lines:
+        return
+    with contextlib.suppress(OSError):
+        path.parent.mkdir(parents=True, exist_ok=True)
+        with path.open("a", encoding="utf-8") as f:
+            f.write("".join(lines))  # At once, so concurrent runs do not mix
+
+
+def read_history(path: Path | None = None) -> list[AuditRecord]:
+    """Read the audit log, oldest first.
+
+    Lines that are not valid records, such as one cut short by a crash, are
+    skipped.
+
+    Args:
+        path: Log to read; defaults to ``audit_log_path()``.
+
+    Returns:
+        list[AuditRecord]: Records in logged order; empty without a log.
+
+    Raises:
+        ProccleanError: With ``INVALID_ARGUMENT`` if the log exists but
+            cannot be read.
+    """
+    if path is None and (path := audit_log_path()) is None:
+        return []
+    try:
+        lines = path.read_text(encoding="utf-8").splitlines()
+    except FileNotFoundError:
+        return []
+    except OSError as e:
+        raise ProccleanError(
+            ErrorCode.INVALID_ARGUMENT,
+            f"Cannot read audit log {path}: {e.strerror}",
+            path=str(path),
+        ) from e
+    records = []
+    for line in lines:
+        try:
+            record = AuditRecord.from_dict(json.loads(line))
+            datetime.fromisoformat(record.time)
+        except (json.JSONDecodeError, TypeError, ValueError, AttributeError):
+            continue
+        records.append(record)
+    return records
+
+
+def filter_history(
+    records: list[AuditRecord],
+    since: float | None = None,
+    pid: int | None = None,
+    name: str | None = None,
+    origin: str | None = None,
+    failed: bool = False,
+) -> list[AuditRecord]:
+    """Narrow audit records down.
+
+    Args:
+        records: Records to filter.
+        since: Keep records at most this many seconds old.
+        pid: Keep records of this PID.
+        name: Keep records whose name contains this, ignoring case.
+        origin: Keep records from this front end.
+        failed: Keep only kills that failed.
+
+    Returns:
+        list[AuditRecord]: Matching records, in order.
+    """
+    cutoff = time.time() - since if since is not None else None
+    needle = name.lower() if name else None
+    return [
+        r
+        for r in records
+        if (cutoff is None or r.timestamp >= cutoff)
+        and (pid is None or r.pid == pid)
+        and (needle is None or needle in r.name.lower())
+        and (origin is None or r.origin == origin)
+        and not (failed and r.ok)
+    ]
+
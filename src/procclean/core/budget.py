@@ -0,0 +1,174 @@
+"""Memory budgets per directory subtree.
+
+Budgets come from ``[[budgets]]`` tables in the config file::
+
+    [[budgets]]
+    path = "~/src/bigproject"
+    limit = "4G"          # or a number of MB
+    action = "kill"       # "warn" (default) or "kill"
+    victim = "newest"     # "largest" (default) or "newest"
+
+Every process whose working directory lies inside ``path`` counts against the
+budget. When the total RSS exceeds ``limit``, offenders are picked largest
+(or newest) first until the remainder fits.
+"""
+
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+
+from .config import load_config
+from .errors import ErrorCode, ProccleanError
+from .models import ProcessInfo
+
+BUDGET_ACTIONS = ("warn", "kill")
+BUDGET_VICTIMS = ("largest", "newest")
+
+# MB per size suffix; a bare number is MB
+SIZE_UNITS: dict[str, float] = {"k": 1 / 1024, "m": 1, "g": 1024, "t": 1024 * 1024}
+
+_SIZE_RE = re.compile(r"(\d+(?:\.\d+)?)\s*([kmgt]?)i?b?")
+
+
+@dataclass(frozen=True)
+class Budget:
+    """Memory limit for one directory subtree."""
+
+    path: Path
+    limit_mb: float
+    action: str = "warn"
+    victim: str = "largest"
+
+    def covers(self, proc: ProcessInfo) -> bool:
+        """Check if a process's working directory is inside the subtree.
+
+        Returns:
+            bool: True if the process counts against this budget.
+        """
+        return bool(proc.cwd) and Path(proc.cwd).is_relative_to(self.path)
+
+
+@dataclass
+class BudgetUsage:
+    """Memory used under one budget."""
+
+    budget: Budget
+    procs: list[ProcessInfo] = field(default_factory=list)
+    # Processes to warn about or kill to get back under the limit
+    offenders: list[ProcessInfo] = field(default_factory=list)
+
+    @property
+    def used_mb(self) -> float:
+        """Total RSS of the covered processes."""
+        return sum(p.rss_mb for p in self.procs)
+
+    @property
+    def exceeded(self) -> bool:
+        """Check if the budget is exceeded."""
+        return self.used_mb > self.budget.limit_mb
+
+
+def parse_size(value: object) -> float:
+    """Parse a memory size such as ``512``, ``"512M"``, ``"4G"`` or ``"1.5GiB"``.
+
+    Args:
+        value: A number of MB or a string with a K/M/G/T suffix.
+
+    Returns:
+        float: The size in MB.
+
+    Raises:
+        ValueError: If the value is malformed or not positive.
+    """
+    if isinstance(value, int | float) and not isinstance(value, bool):
+        mb = float(value)
+    elif isinstance(value, str) and (
+        match := _SIZE_RE.fullmatch(value.strip().lower())
+    ):
+        mb = float(match[1]) * SIZE_UNITS[match[2] or "m"]
+    else:
+        msg = f"invalid size {value!r} (expected e.g. 512M or 4G)"
+        raise ValueError(msg)
+    if mb <= 0:
+        msg = f"size {value!r} must be positive"
+        raise ValueError(msg)
+    return mb
+
+
+def _invalid(index: int, reason: str) -> ProccleanError:
+    return ProccleanError(
+        ErrorCode.INVALID_CONFIG,
+        f"Invalid budgets[{index}]: {reason}",
+        key=f"budgets[{index}]",
+    )
+
+
+def load_budgets() -> tuple[Budget, ...]:
+    """Read the ``[[budgets]]`` tables from the config.
+
+    Returns:
+        tuple[Budget, ...]: Configured budgets; empty when there are none.
+
+    Raises:
+        ProccleanError: With ``INVALID_CONFIG`` for malformed entries.
+    """
+    tables = load_config().get("budgets", [])
+    if not isinstance(tables, list):
+        raise ProccleanError(
+            ErrorCode.INVALID_CONFIG,
+            "Invalid budgets: expected [[budgets]] tables",
+            key="budgets",
+        )
+    budgets = []
+    for index, table in enumerate(tables):
+        if not isinstance(table, dict) or not isinstance(table.get("path"), str):
+            raise _invalid(index, "path is required")
+        try:
+            limit_mb = parse_size(table.get("limit"))
+        except ValueError as e:
+            raise _invalid(index, str(e)) from e
+        action = table.get("action", "warn")
+        if action not in BUDGET_ACTIONS:
+            choices = ", ".join(BUDGET_ACTIONS)
+            raise _invalid(index, f"action must be one of {choices}")
+        victim = table.get("victim", "largest")
+        if victim not in BUDGET_VICTIMS:
+            choices = ", ".join(BUDGET_VICTIMS)
+            raise _invalid(index, f"victim must be one of {choices}")
+        path = Path(table["path"]).expanduser()
+        budgets.append(Budget(path, limit_mb, action, victim))
+    return tuple(budgets)
+
+
+def check_budgets(
+    procs: list[ProcessInfo], budgets: tuple[Budget, ...]
+) -> list[BudgetUsage]:
+    """Measure usage per budget and pick offenders for exceeded ones.
+
+    Privileged processes count towards the total but are never offenders.
+
+    Args:
+        procs: Processes to account.
+        budgets: Budgets to check.
+
+    Returns:
+        list[BudgetUsage]: Usage per budget, in configuration order.
+    """
+    usages = []
+    for budget in budgets:
+        usage = BudgetUsage(budget, [p for p in procs if budget.covers(p)])
+        excess = usage.used_mb - budget.limit_mb
+        if excess > 0:
+            if budget.victim == "newest":
+                candidates = sorted(usage.procs, key=lambda p: -p.create_time)
+            else:
+                candidates = sorted(usage.procs, key=lambda p: -p.rss_mb)
+            for proc in candidates:
+                if excess <= 0:
+                    break
+                if proc.is_privileged:
+                    continue
+                usage.offenders.append(proc)
+                excess -= proc.rss_mb
+        usages.append(usage)
+    return usages
@@ -0,0 +1,148 @@
+"""Snapshot cache shared by back-to-back CLI invocations.
+
+Scripts often chain commands (``list`` to look, then ``kill``), and each one
+pays a full scan. With ``--max-age``, a scan of every process is saved to a
+small file in ``$XDG_RUNTIME_DIR`` (a tmpfs on systemd systems), and later
+invocations reuse it while it is younger than their ``--max-age``. Without
+the option nothing is read or written.
+
+The file holds command lines, so it is only readable by its owner, and a
+file owned by anyone else or readable by others is never loaded: otherwise
+another user could plant a snapshot with made-up PIDs for ``kill`` to act on.
+Without ``$XDG_RUNTIME_DIR`` (a private directory) there is no cache. The
+daemon serves the same encoding over its socket.
+"""
+
+import json
+import os
+import stat
+import tempfile
+import time
+from collections.abc import Iterator
+from contextlib import contextmanager
+from dataclasses import asdict
+from pathlib import Path
+
+from .models import ProcessInfo
+
+CACHE_ENV = "PROCCLEAN_CACHE"
+
+
+def cache_path() -> Path | None:
+    """Locate the snapshot cache.
+
+    Returns:
+        Path | None: ``$PROCCLEAN_CACHE``, else ``procclean-snapshot.json`` in
+        ``$XDG_RUNTIME_DIR``, else None: a shared temp directory is no place
+        for it.
+    """
+    if explicit := os.environ.get(CACHE_ENV):
+        return Path(explicit).expanduser()
+    if runtime := os.environ.get("XDG_RUNTIME_DIR"):
+        return Path(runtime) / "procclean-snapshot.json"
+    return None
+
+
+def encode_snapshot(procs: list[ProcessInfo]) -> bytes:
+    """Serialize a scan with the current time.
+
+    Returns:
+        bytes: One ``{"timestamp", "processes"}`` JSON line.
+    """
+    data = {"timestamp": time.time(), "processes": [asdict(p) for p in procs]}
+    return json.dumps(data).encode() + b"\n"
+
+
+def decode_snapshot(data: object, max_age: float) -> list[ProcessInfo] | None:
+    """Rebuild the processes of a decoded snapshot.
+
+    Args:
+        data: Parsed JSON of an ``encode_snapshot`` line.
+        max_age: Maximum age of the snapshot in seconds.
+
+    Returns:
+        list[ProcessInfo] | None: The processes, or None if the snapshot is
+        too old or malformed.
+    """
+    try:
+        if time.time() - data["timestamp"] > max_age:  # type: ignore[index]
+            return None
+        return [ProcessInfo(**p) for p in data["processes"]]  # type: ignore[index]
+    except (KeyError, TypeError):
+        return None
+
+
+def load_snapshot(max_age: float, path: Path | None = None) -> list[ProcessInfo] | None:
+    """Read the cached scan if it is fresh enough.
+
+    Returns:
+        list[ProcessInfo] | None: The cached processes, or None if there is no
+        usable cache, or the file is not a private one of the current user.
+    """
+    path = path or cache_path()
+    if path is None:
+        return None
+    try:
+        fd = os.open(path, os.O_RDONLY | os.O_NOFOLLOW)  # No planted symlinks
+        with os.fdopen(fd, "rb") as f:
+            st = os.fstat(f.fileno())
+            if (
+                not stat.S_ISREG(st.st_mode)
+                or st.st_uid != os.getuid()
+                or st.st_mode & 0o077
+            ):
+                return None
+            data = json.loads(f.read())
+    except (OSError, ValueError):
+        return None
+    return decode_snapshot(data, max_age)
+
+
+def save_snapshot(procs: list[ProcessInfo], path: Path | None = None) -> None:
+    """Replace the cached scan; failures to write are ignored."""
+    path = path or cache_path()
+    if path is None:
+        return
+    try:
+        fd, tmp = tempfile.mkstemp(dir=path.parent, prefix=f".{path.name}.")
+    except OSError:
+        return
+    try:
+        with os.fdopen(fd, "wb") as f:
+            f.write(encode_snapshot(procs))
+        os.replace(tmp, path)  # Readers never see a partial file
+    except OSError:
+        Path(tmp).unlink(missing_ok=True)
+
+
+_max_age: list[float] = []
+
+
+@contextmanager
+def snapshot_cache(max_age: float | None) -> Iterator[None]:
+    """Let live scans inside the block use the cache.
+
+    Args:
+        max_age: Reuse scans up to this many seconds old; None leaves caching
+            off.
+
+    Yields:
+        None: Inside the block.
+    """
+    if max_age is None:
+        yield
+        return
+    _max_age.append(max_age)
+    try:
+        yield
+    finally:
+        _max_age.pop()
+
+
+def cache_max_age() -> float | None:
+    """Get the ``--max-age`` of the running command.
+
+    Returns:
+        float | None: Maximum snapshot age, or None if caching is off.
+    """
+    return _max_age[-1] if _max_age else None
@@ -0,0 +1,61 @@
+"""Row cap for very large process tables.
+
+On servers with tens of thousands of processes, rendering every row makes the
+TUI sluggish and a table unreadable. Tables are capped to the top rows by the
+active sort key; filters apply before the cap, so it only trims the
+lowest-ranked matches. The cap comes from the ``[display]`` table of the
+config file (``0`` disables it)::
+
+    [display]
+    row_cap = 2000
+"""
+
+from collections.abc import Callable
+
+from .config import load_config
+from .errors import ErrorCode, ProccleanError
+from .models import ProcessInfo
+
+DEFAULT_ROW_CAP = 2000
+
+
+def row_cap() -> int | None:
+    """Read the ``[display] row_cap`` setting.
+
+    Returns:
+        int | None: Maximum number of rows, or None if capping is disabled.
+
+    Raises:
+        ProccleanError: With ``INVALID_CONFIG`` unless the value is a
+            non-negative integer.
+    """
+    value = load_config().get("display", {}).get("row_cap", DEFAULT_ROW_CAP)
+    if isinstance(value, bool) or not isinstance(value, int) or value < 0:
+        msg = "Invalid display.row_cap: expected a non-negative integer"
+        raise ProccleanError(ErrorCode.INVALID_CONFIG, msg, key="display.row_cap")
+    return value or None
+
+
+def cap_processes(
+    procs: list[ProcessInfo],
+    cap: int | None,
+    keep: Callable[[ProcessInfo], bool] | None = None,
+) -> tuple[list[ProcessInfo], int]:
+    """Keep the first ``cap`` processes of a sorted list.
+
+    Args:
+        procs: Processes, already filtered and sorted.
+        cap: Maximum number of rows, or None to keep everything.
+        keep: Processes past the cap matching this are kept too, e.g. the
+            selected rows of the TUI.
+
+    Returns:
+        tuple[list[ProcessInfo], int]: The kept processes in their original
+        order and how many were left out.
+    """
+    if cap is None or len(procs) <= cap:
+        return procs, 0
+    kept = procs[:cap]
+    if keep is not None:
+        kept += [p for p in procs[cap:] if keep(p)]
+    return kept, len(procs) - len(kept)
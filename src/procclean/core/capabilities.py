@@ -0,0 +1,331 @@
+"""What procclean can read on this system, and what it cannot.
+
+Most of what procclean shows comes from /proc. Other platforms have none, a
+/proc mounted with ``hidepid`` or ``subset=pid`` hides processes or
+system-wide files, containers mask parts of it, and without root other
+users' processes keep their details to themselves. Each of these leaves
+columns unknown ("?") or empty. ``capability_report`` checks every source
+once and names the columns it feeds: the CLI and TUI warn at startup when
+the environment is restricted, and ``procclean doctor`` shows the full
+report. ``environment_checks`` adds what doctor alone looks at: cgroup v2,
+the terminal, its encoding and the config file.
+"""
+
+import getpass
+import os
+import sys
+from collections.abc import Callable, Iterable
+from dataclasses import asdict, dataclass
+from pathlib import Path
+from typing import Any, TextIO
+
+from procclean.i18n import t
+
+from .config import config_path
+from .process import PROC_ROOT
+from .settings import config_problems
+
+NO_CAPABILITY_WARNING_ENV = "PROCCLEAN_NO_CAPABILITY_WARNING"
+CAP_SYS_PTRACE = 19  # Bit in CapEff that lifts the per-user /proc limits
+SMAPS_COLUMNS = ("pss_mb", "uss_mb", "shared_mb", "swap_mb")
+# Columns only filled in from /proc; PortableSource leaves their defaults
+LINUX_ONLY_COLUMNS = (
+    "sched_policy",
+    "rt_priority",
+    "privileges",
+    "confinement",
+    "deleted_libs",
+    "media",
+    "pod",
+    "namespace",
+    "container",
+    "ports",
+    *SMAPS_COLUMNS,
+)
+# Columns read from /proc/<pid> files that only the owner (or root) may read
+OWNER_ONLY_COLUMNS = ("cwd", "deleted_libs", "media", "ports", *SMAPS_COLUMNS)
+# hidepid= values by effect, numeric and named (Linux 5.8+)
+HIDEPID_NOACCESS = frozenset({"1", "noaccess"})
+HIDEPID_INVISIBLE = frozenset({"2", "invisible", "4", "ptraceable"})
+
+
+@dataclass(frozen=True, slots=True)
+class Capability:
+    """One thing procclean reads, and whether it can."""
+
+    key: str  # E.g. "hidepid"
+    ok: bool
+    detail: str  # What works, or what is missing and why
+    columns: tuple[str, ...] = ()  # Columns left unknown when it is missing
+    # Missing because of how the system is set up, as opposed to by design
+    # (such as other users' details without root); only these warn at
+    # startup and fail `procclean doctor`
+    restricted: bool = False
+    fix: str = ""  # What would make it available
+
+    def to_dict(self) -> dict[str, Any]:
+        """Describe the capability for JSON output.
+
+        Returns:
+            dict[str, Any]: Every field.
+        """
+        data = asdict(self)
+        data["columns"] = list(self.columns)
+        return data
+
+
+@dataclass(frozen=True, slots=True)
+class CapabilityReport:
+    """Every capability checked, in a fixed order."""
+
+    platform: str  # sys.platform
+    euid: int
+    user: str
+    capabilities: tuple[Capability, ...]
+
+    @property
+    def missing(self) -> tuple[Capability, ...]:
+        """Capabilities that are unavailable."""
+        return tuple(c for c in self.capabilities if not c.ok)
+
+    @property
+    def restricted(self) -> tuple[Capability, ...]:
+        """Unavailable capabilities a restricted environment took away."""
+        return tuple(c for c in self.missing if c.restricted)
+
+    @property
+    def missing_columns(self) -> list[str]:
+        """Columns some capability is missing for, sorted."""
+        return sorted({col for c in self.missing for col in c.columns})
+
+    def to_dict(self) -> dict[str, Any]:
+        """Describe the report for JSON output.
+
+        Returns:
+            dict[str, Any]: Platform, effective UID and user, capabilities and
+            the affected columns.
+        """
+        return {
+            "platform": self.platform,
+            "euid": self.euid,
+            "user": self.user,
+            "capabilities": [c.to_dict() for c in self.capabilities],
+            "missing_columns": self.missing_columns,
+        }
+
+
+def _read(path: Path) -> str:
+    """Read a /proc file.
+
+    Returns:
+        str: Its text; empty if unreadable, or masked with /dev/null.
+    """
+    try:
+        return path.read_text(encoding="utf-8", errors="replace")
+    except OSError:
+        return ""
+
+
+def _proc_mount_options(proc_root: Path) -> dict[str, str]:
+    """Read the mount options of /proc.
+
+    Returns:
+        dict[str, str]: Options by name; flags map to "".
+    """
+    for line in _read(proc_root / "mounts").splitlines():
+        match line.split():
+            case [_, "/proc", "proc", options, *_]:
+                pairs = (option.partition("=") for option in options.split(","))
+                return {name: value for name, _, value in pairs}
+    return {}
+
+
+def _can_ptrace(proc_root: Path, euid: int) -> bool:
+    """Check whether this process may read any user's /proc/<pid> files.
+
+    Returns:
+        bool: True as root or with CAP_SYS_PTRACE.
+    """
+    if euid == 0:
+        return True
+    for line in _read(proc_root / "self" / "status").splitlines():
+        if line.startswith("CapEff:"):
+            try:
+                return bool(int(line.split()[1], 16) >> CAP_SYS_PTRACE & 1)
+            except (IndexError, ValueError):
+                return False
+    return False
+
+
+def _hidepid(options: dict[str, str], privileged: bool) -> Capability:
+    """Check whether ``hidepid`` hides other users' processes.
+
+    Returns:
+        Capability: Missing for ``hidepid`` 1 (details unreadable) or 2/4
+        (processes hidden), unless privileged or in the exempt ``gid``.
+    """
+    mode = options.get("hidepid", "0")
+    gid = options.get("gid", "")
+    exempt = privileged or (gid.isdigit() and int(gid) in os.getgroups())
+    if exempt or mode not in HIDEPID_NOACCESS | HIDEPID_INVISIBLE:
+        return Capability("hidepid", True, t("capability-hidepid-ok"))
+    fix = t("capability-hidepid-fix")
+    if mode in HIDEPID_INVISIBLE:
+        detail = t("capability-hidepid-invisible", mode=mode)
+        return Capability("hidepid", False, detail, restricted=True, fix=fix)
+    detail = t("capability-hidepid-noaccess", mode=mode)
+    return Capability("hidepid", False, detail, OWNER_ONLY_COLUMNS, True, fix)
+
+
+def capability_report(proc_root: Path = PROC_ROOT) -> CapabilityReport:
+    """Check what procclean can read on this system.
+
+    Args:
+        proc_root: procfs mount point.
+
+    Returns:
+        CapabilityReport: The platform check alone off Linux; otherwise
+        procfs, hidepid, subset=pid, other users' details, smaps_rollup,
+        meminfo, pressure and sockets, in that order.
+    """
+    euid = os.geteuid() if hasattr(os, "geteuid") else -1
+    user = getpass.getuser()
+    if sys.platform != "linux":
+        detail = t("capability-platform", platform=sys.platform)
+        fix = t("capability-platform-fix")
+        cap = Capability("platform", False, detail, LINUX_ONLY_COLUMNS, True, fix)
+        return CapabilityReport(sys.platform, euid, user, (cap,))
+    path = str(proc_root)
+    if not (proc_root / "self").is_dir():
+        detail = t("capability-procfs", path=path)
+        fix = t("capability-procfs-fix", path=path)
+        cap = Capability("procfs", False, detail, restricted=True, fix=fix)
+        return CapabilityReport(sys.platform, euid, user, (cap,))
+    options = _proc_mount_options(proc_root)
+    privileged = _can_ptrace(proc_root, euid)
+
+    def check(
+        key: str, ok: bool, columns: tuple[str, ...] = (), restricted: bool = True
+    ) -> Capability:
+        detail = t(f"capability-{key}-ok" if ok else f"capability-{key}")
+        fix = "" if ok else t(f"capability-{key}-fix")
+        return Capability(key, ok, detail, columns, restricted, fix)
+
+    caps = (
+        Capability("procfs", True, t("capability-procfs-ok", path=path)),
+        _hidepid(options, privileged),
+        check("subset", options.get("subset") != "pid", ("ports",)),
+        check("others", privileged, OWNER_ONLY_COLUMNS, restricted=False),
+        check(
+            "smaps",
+            (proc_root / "self" / "smaps_rollup").is_file(),
+            SMAPS_COLUMNS,
+            restricted=False,
+        ),
+        check("meminfo", "MemTotal:" in _read(proc_root / "meminfo")),
+        check(
+            "pressure",
+            bool(_read(proc_root / "pressure" / "memory")),
+            restricted=False,
+        ),
+        check("net", bool(_read(proc_root / "net" / "tcp")), ("ports",)),
+    )
+    return CapabilityReport(sys.platform, euid, user, caps)
+
+
+def _cgroup_v2(proc_root: Path) -> Capability:
+    """Check whether processes live in the unified (v2) cgroup hierarchy.
+
+    Returns:
+        Capability: Missing on cgroup v1 systems, where ``--scope`` and
+        container detection fall back to the systemd hierarchy.
+    """
+    lines = _read(proc_root / "self" / "cgroup").splitlines()
+    if any(line.startswith("0::") for line in lines):
+        return Capability("cgroup", True, t("capability-cgroup-ok"))
+    fix = t("capability-cgroup-fix")
+    return Capability("cgroup", False, t("capability-cgroup"), fix=fix)
+
+
+def _terminal(stream: TextIO) -> tuple[Capability, Capability]:
+    """Check the terminal output goes to, and its encoding.
+
+    Returns:
+        tuple[Capability, Capability]: Whether output is an interactive
+        terminal the TUI can run in, and whether it takes UTF-8.
+    """
+    term = os.environ.get("TERM", "")
+    if stream.isatty() and term not in {"", "dumb"}:
+        terminal = Capability("terminal", True, t("capability-terminal-ok", term=term))
+    else:
+        detail = t("capability-terminal", term=term or "-")
+        fix = t("capability-terminal-fix")
+        terminal = Capability("terminal", False, detail, fix=fix)
+    encoding = getattr(stream, "encoding", None) or "ascii"
+    if encoding.lower().replace("-", "") == "utf8":
+        detail = t("capability-encoding-ok", encoding=encoding)
+        return terminal, Capability("encoding", True, detail)
+    detail = t("capability-encoding", encoding=encoding)
+    fix = t("capability-encoding-fix")
+    return terminal, Capability("encoding", False, detail, fix=fix)
+
+
+def _config(validators: Iterable[Callable[[], object]]) -> Capability:
+    """Check that the config file parses and every setting is valid and known.
+
+    Returns:
+        Capability: Missing, and restricted, with the first problem found.
+    """
+    path = config_path()
+    if problems := config_problems(validators):
+        detail = problems[0].message
+        if len(problems) > 1:
+            detail += f" ({t('capability-config-more', count=len(problems) - 1)})"
+        fix = t("capability-config-fix", path=str(path))
+        return Capability("config", False, detail, restricted=True, fix=fix)
+    key = "capability-config-ok" if path.is_file() else "capability-config-none"
+    return Capability("config", True, t(key, path=str(path)))
+
+
+def environment_checks(
+    validators: Iterable[Callable[[], object]] = (),
+    proc_root: Path = PROC_ROOT,
+    stream: TextIO | None = None,
+) -> tuple[Capability, ...]:
+    """Check the rest of the setup procclean relies on, for doctor.
+
+    Args:
+        validators: Loaders of config settings, each raising ``ProccleanError``
+            for a malformed one; the file itself is always parsed.
+        proc_root: procfs mount point.
+        stream: Output stream to check; defaults to stdout.
+
+    Returns:
+        tuple[Capability, ...]: cgroup v2 (Linux only), terminal, encoding
+        and config, in that order.
+    """
+    cgroup = (_cgroup_v2(proc_root),) if sys.platform == "linux" else ()
+    return (*cgroup, *_terminal(stream or sys.stdout), _config(validators))
+
+
+def capability_warning(report: CapabilityReport | None = None) -> str | None:
+    """Summarize a restricted environment for a startup warning.
+
+    Args:
+        report: Report to summarize; checked now if not given.
+
+    Returns:
+        str | None: One warning naming what is missing and the columns it
+        affects, or None if nothing is restricted or
+        ``$PROCCLEAN_NO_CAPABILITY_WARNING`` is set.
+    """
+    if os.environ.get(NO_CAPABILITY_WARNING_ENV):
+        return None
+    restricted = (report or capability_report()).restricted
+    if not restricted:
+        return None
+    reasons = "; ".join(c.detail for c in restricted)
+    columns = sorted({col for c in restricted for col in c.columns})
+    if columns:
+        reasons += f" ({t('capability-columns', columns=', '.join(columns))})"
+    return t("capability-warning", reasons=reasons)
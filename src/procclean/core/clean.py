@@ -0,0 +1,64 @@
+"""One-shot cleanup across the safe filter presets."""
+
+from .filters import (
+    filter_cwd_deleted,
+    filter_killable,
+    filter_stale,
+    is_system_service,
+)
+from .inhibit import exclude_inhibiting
+from .kube import in_pod
+from .media import exclude_media_users
+from .models import ProcessInfo
+from .source import ProcessSource
+
+# Presets applied by ``clean --auto``, in order
+CLEAN_CATEGORIES: tuple[str, ...] = ("killable", "stale", "dev-leftovers")
+
+
+def clean_targets(
+    procs: list[ProcessInfo], source: ProcessSource | None = None
+) -> dict[str, list[ProcessInfo]]:
+    """Group cleanup candidates by the preset that selected them.
+
+    Presets run in ``CLEAN_CATEGORIES`` order and a process is listed only
+    under the first one that matches. System services, processes running in
+    tmux, Kubernetes pod processes, processes using audio or camera devices
+    and processes holding an inhibitor lock are never selected:
+
+    - ``killable``: orphans (see ``filter_killable``)
+    - ``stale``: processes running a deleted or replaced executable
+    - ``dev-leftovers``: processes whose working directory was removed
+
+    Args:
+        procs: Processes to consider.
+        source: Process backend used for system service detection.
+
+    Returns:
+        dict[str, list[ProcessInfo]]: Targets per category, in preset order;
+        categories without targets are omitted.
+    """
+    safe = exclude_media_users(
+        [
+            p
+            for p in procs
+            if not p.in_tmux
+            and not is_system_service(p, source)
+            and not in_pod(p, source)
+        ],
+        source,
+    )
+    safe = exclude_inhibiting(safe)
+    presets = {
+        "killable": lambda ps: filter_killable(ps, source=source),
+        "stale": filter_stale,
+        "dev-leftovers": filter_cwd_deleted,
+    }
+    seen: set[int] = set()
+    groups: dict[str, list[ProcessInfo]] = {}
+    for category in CLEAN_CATEGORIES:
+        matched = [p for p in presets[category](safe) if p.pid not in seen]
+        if matched:
+            groups[category] = matched
+            seen.update(p.pid for p in matched)
+    return groups
@@ -0,0 +1,52 @@
+"""User configuration file.
+
+Settings are read from ``$PROCCLEAN_CONFIG`` or
+``$XDG_CONFIG_HOME/procclean/config.toml`` (``~/.config`` by default). A
+missing file means defaults everywhere.
+"""
+
+import os
+import tomllib
+from functools import cache
+from pathlib import Path
+from typing import Any
+
+from .errors import ErrorCode, ProccleanError
+
+CONFIG_ENV = "PROCCLEAN_CONFIG"
+
+
+def config_path() -> Path:
+    """Locate the configuration file.
+
+    Returns:
+        Path: The configured path; it may not exist.
+    """
+    if explicit := os.environ.get(CONFIG_ENV):
+        return Path(explicit).expanduser()
+    base = os.environ.get("XDG_CONFIG_HOME") or Path.home() / ".config"
+    return Path(base) / "procclean" / "config.toml"
+
+
+@cache
+def load_config(path: Path | None = None) -> dict[str, Any]:
+    """Load and cache the configuration.
+
+    Args:
+        path: File to read; defaults to ``config_path()``.
+
+    Returns:
+        dict[str, Any]: Parsed TOML tables, or an empty dict if there is no file.
+
+    Raises:
+        ProccleanError: With ``INVALID_CONFIG`` if the file is not valid TOML.
+    """
+    path = path or config_path()
+    try:
+        with path.open("rb") as f:
+            return tomllib.load(f)
+    except FileNotFoundError:
+        return {}
+    except (OSError, tomllib.TOMLDecodeError) as e:
+        msg = f"Cannot read config {path}: {e}"
+        raise ProccleanError(ErrorCode.INVALID_CONFIG, msg, path=str(path)) from e
@@ -3,15 +3,49 @@
 # Display constants
 PREVIEW_LIMIT = 5  # Number of processes to show in previews
 CONFIRM_PREVIEW_LIMIT = 10  # Number of processes to show in confirm dialogs
+DETAIL_LIST_LIMIT = 200  # Entries per list in the TUI inspect screen
 CWD_MAX_WIDTH = 35  # Max width for cwd column display
 CWD_TRUNCATE_WIDTH = 32  # Width to keep when truncating cwd
 
 # Memory thresholds
 HIGH_MEMORY_THRESHOLD_MB = 500  # Default threshold for high memory filter
+STATUS_WARNING_PERCENT = 75  # Status bar turns "warning" at this memory usage
+STATUS_CRITICAL_PERCENT = 90  # Status bar turns "critical" at this memory usage
+MEMORY_SETTLE_SECONDS = 1.0  # Wait after kills before measuring freed memory
+WATCH_INTERVAL = 60.0  # Default seconds between checks of `procclean watch`
+
+# CPU thresholds
+CPU_HOG_THRESHOLD_PERCENT = 50  # Default threshold for the CPU hogs filter
+CPU_SAMPLE_SECONDS = 0.5  # Default interval CPU usage is measured over
+LIST_WATCH_SECONDS = 2.0  # Default refresh of `procclean list --watch`
+
+# Scheduling policies by number, as in sched_setscheduler(2)
+SCHED_POLICIES: dict[int, str] = {
+    0: "other",
+    1: "fifo",
+    2: "rr",
+    3: "batch",
+    5: "idle",
+    6: "deadline",
+}
+# Policies that preempt normal processes; killing these needs extra care
+REALTIME_POLICIES = frozenset({"fifo", "rr", "deadline"})
+
+# Seccomp modes by number, as in the Seccomp field of /proc/<pid>/status
+SECCOMP_MODES: dict[int, str] = {0: "disabled", 1: "strict", 2: "filter"}
+
+# Device nodes that mean a process is recording or playing media, by kind
+MEDIA_DEVICE_PREFIXES: dict[str, str] = {
+    "/dev/snd/pcm": "audio",
+    "/dev/video": "camera",
+}
 
 # System library paths - executables here are system services
 SYSTEM_EXE_PATHS = ("/usr/lib", "/usr/libexec", "/lib")
 
+# Databases that can corrupt their data when killed without a clean shutdown
+DATA_LOSS_SERVICES = ("postgres", "mysqld", "mariadbd", "redis-server", "mongod")
+
 # Critical services in /usr/bin that should never be killed
 # (session managers, audio, shells, display, auth)
 CRITICAL_SERVICES = {
@@ -0,0 +1,89 @@
+"""Per-core CPU usage, to tell a pinned core from a busy system.
+
+A runaway single-threaded process shows 100% in the CPU column whether the
+machine is idle otherwise or every core is saturated. Per-core usage tells
+them apart: one core at 100% and the rest idle points at that process, all
+cores busy at a genuinely loaded system.
+"""
+
+from dataclasses import dataclass
+from enum import StrEnum, auto
+
+import psutil
+
+PINNED_CORE_PERCENT = 90.0  # A core at least this busy counts as pinned
+BUSY_SYSTEM_PERCENT = 75.0  # Average usage at which the whole system is busy
+CORE_LEVELS = " ▁▂▃▄▅▆▇█"  # Strip characters from idle to fully busy
+
+
+class CpuState(StrEnum):
+    """Overall reading of per-core usage."""
+
+    NORMAL = auto()
+    PINNED = auto()  # Some cores pinned while the average stays low
+    BUSY = auto()  # The average itself is high
+
+
+@dataclass(frozen=True, slots=True)
+class CoreUsage:
+    """Utilization of every logical CPU over one interval."""
+
+    cores: tuple[float, ...]  # Percent busy, by CPU number
+
+    @property
+    def average(self) -> float:
+        """Mean usage over all cores, as ``top`` shows it."""
+        return sum(self.cores) / len(self.cores) if self.cores else 0.0
+
+    @property
+    def pinned(self) -> tuple[int, ...]:
+        """Numbers of the cores at or above ``PINNED_CORE_PERCENT``."""
+        return tuple(
+            n for n, usage in enumerate(self.cores) if usage >= PINNED_CORE_PERCENT
+        )
+
+    @property
+    def state(self) -> CpuState:
+        """Classify the usage as normal, pinned cores or a busy system."""
+        if self.average >= BUSY_SYSTEM_PERCENT:
+            return CpuState.BUSY
+        return CpuState.PINNED if self.pinned else CpuState.NORMAL
+
+    def strip(self) -> str:
+        """Render one block character per core, higher for busier cores.
+
+        Returns:
+            str: E.g. "▁▁█▂" for four cores with the third pinned.
+        """
+        top = len(CORE_LEVELS) - 1
+        return "".join(
+            CORE_LEVELS[min(top, max(0, round(usage / 100 * top)))]
+            for usage in self.cores
+        )
+
+    def to_dict(self) -> dict[str, object]:
+        """Serialize for JSON output.
+
+        Returns:
+            dict[str, object]: Usage per core, average, pinned cores and state.
+        """
+        return {
+            "cores": [round(usage, 1) for usage in self.cores],
+            "average": round(self.average, 1),
+            "pinned": list(self.pinned),
+            "state": str(self.state),
+        }
+
+
+def core_usage(interval: float | None = None) -> CoreUsage:
+    """Measure the usage of every logical CPU.
+
+    Args:
+        interval: Seconds to measure over. None reports usage since the
+            previous call, like the TUI does between refreshes; the first
+            call then reads all zeros.
+
+    Returns:
+        CoreUsage: Usage by CPU number.
+    """
+    return CoreUsage(tuple(psutil.cpu_percent(interval=interval, percpu=True)))
@@ -0,0 +1,244 @@
+"""Background daemon serving warm process snapshots.
+
+A full scan reads every ``/proc`` entry plus cwd and environment of each
+process, and psutil only knows CPU usage from the second sample on.
+``procclean daemon`` keeps rescanning in the background and serves the latest
+snapshot over a Unix socket, so the TUI's first frame and one-off ``list``
+runs skip the scan. Without a daemon, or when its snapshot is stale, they scan
+in-process as before.
+
+The protocol is one JSON request line, ``{"op": "snapshot"}`` or
+``{"op": "ping"}``, answered by one JSON line. The socket is only accessible
+to its owner, and the daemon always scans the unrestricted live system.
+Clients only trust a daemon run by their own user (checked with
+``SO_PEERCRED``), and the socket lives in ``$XDG_RUNTIME_DIR``: in a shared
+temp directory anyone could bind it first and serve forged process lists.
+"""
+
+import json
+import os
+import socket
+import socketserver
+import struct
+import sys
+import threading
+from pathlib import Path
+from typing import Any
+
+from .cache import decode_snapshot, encode_snapshot
+from procclean.i18n import t
+
+from .errors import ErrorCode, ProccleanError
+from .models import ProcessInfo
+from .process import ALL_USERS, DEFAULT_SOURCE, get_process_list, select_processes
+from .source import ProcessSource
+
+SOCKET_ENV = "PROCCLEAN_SOCKET"
+NO_DAEMON_ENV = "PROCCLEAN_NO_DAEMON"
+DAEMON_INTERVAL = 2.0  # Seconds between scans
+DAEMON_TIMEOUT = 1.0  # Seconds a client waits for a reply
+SNAPSHOT_MAX_AGE = 10.0  # Older snapshots are ignored by clients
+
+
+def socket_path() -> Path | None:
+    """Locate the daemon socket.
+
+    Returns:
+        Path | None: ``$PROCCLEAN_SOCKET``, else ``procclean.sock`` in
+        ``$XDG_RUNTIME_DIR``, else None: there is no private place for it.
+    """
+    if explicit := os.environ.get(SOCKET_ENV):
+        return Path(explicit).expanduser()
+    if runtime := os.environ.get("XDG_RUNTIME_DIR"):
+        return Path(runtime) / "procclean.sock"
+    return None
+
+
+def peer_uid(sock: socket.socket) -> int | None:
+    """Get the user ID of the process at the other end of a Unix socket.
+
+    Returns:
+        int | None: The peer's UID, or None where ``SO_PEERCRED`` is missing.
+    """
+    option = getattr(socket, "SO_PEERCRED", None)
+    if option is None:
+        return None
+    creds = sock.getsockopt(socket.SOL_SOCKET, option, struct.calcsize("3i"))
+    _pid, uid, _gid = struct.unpack("3i", creds)
+    return uid
+
+
+def _request(
+    path: Path, request: dict[str, Any], timeout: float = DAEMON_TIMEOUT
+) -> dict[str, Any] | None:
+    """Send one request to the daemon.
+
+    Returns:
+        dict[str, Any] | None: The reply, or None if no daemon of the current
+        user answered.
+    """
+    try:
+        with socket.socket(socket.AF_UNIX, socket.SOCK_STREAM) as sock:
+            sock.settimeout(timeout)
+            sock.connect(str(path))
+            if peer_uid(sock) != os.getuid():
+                return None  # Someone else's socket; its snapshot is untrusted
+            sock.sendall(json.dumps(request).encode() + b"\n")
+            with sock.makefile("rb") as reply:
+                data = json.loads(reply.readline())
+    except (OSError, ValueError):
+        return None
+    return data if isinstance(data, dict) else None
+
+
+def daemon_process_list(
+    min_memory_mb: float = 10.0,
+    user: str | None = None,
+    path: Path | None = None,
+    max_age: float = SNAPSHOT_MAX_AGE,
+    kernel_threads: bool = False,
+) -> list[ProcessInfo] | None:
+    """Get the process list from a running daemon.
+
+    Mirrors ``get_process_list`` with its default sorting by memory.
+
+    Args:
+        min_memory_mb: Minimum RSS (in MB) for a process to be included.
+        user: Only include processes owned by this user. Defaults to the
+            current user; ``ALL_USERS`` includes everyone's.
+        path: Socket to connect to; defaults to ``socket_path()``.
+        max_age: Maximum age of the snapshot in seconds.
+        kernel_threads: Include kernel threads.
+
+    Returns:
+        list[ProcessInfo] | None: The processes, or None to scan in-process:
+        no daemon is running, its snapshot is stale, or the reply is invalid.
+    """
+    path = path or socket_path()
+    if os.environ.get(NO_DAEMON_ENV) or path is None:
+        return None
+    reply = _request(path, {"op": "snapshot"})
+    procs = decode_snapshot(reply, max_age)
+    if procs is None:
+        return None
+    try:
+        user = user or DEFAULT_SOURCE.current_user()
+    except OSError:
+        return None
+    procs = select_processes(procs, user, min_memory_mb, kernel_threads)
+    return sorted(procs, key=lambda p: p.rss_mb, reverse=True)
+
+
+class SnapshotDaemon:
+    """Rescans processes periodically and serves the latest snapshot."""
+
+    def __init__(
+        self,
+        path: Path,
+        interval: float = DAEMON_INTERVAL,
+        source: ProcessSource | None = None,
+    ) -> None:
+        """Initialize the daemon.
+
+        Args:
+            path: Socket to listen on.
+            interval: Seconds between scans.
+            source: Process backend; defaults to the live system.
+        """
+        self.path = path
+        self.interval = interval
+        self.source = source
+        self._snapshot = b""
+        self._lock = threading.Lock()
+        self._stop = threading.Event()
+        self._server: socketserver.UnixStreamServer | None = None
+
+    def refresh(self) -> None:
+        """Scan every process and replace the served snapshot."""
+        source = self.source or DEFAULT_SOURCE  # Never from the snapshot cache
+        procs = get_process_list(
+            filter_user=ALL_USERS, min_memory_mb=0, source=source, kernel_threads=True
+        )
+        encoded = encode_snapshot(procs)
+        with self._lock:
+            self._snapshot = encoded
+
+    def handle(self, request: object) -> bytes:
+        """Answer one request.
+
+        Returns:
+            bytes: The JSON reply line.
+        """
+        op = request.get("op") if isinstance(request, dict) else None
+        if op == "snapshot":
+            with self._lock:
+                return self._snapshot
+        if op == "ping":
+            reply: dict[str, Any] = {"pid": os.getpid(), "interval": self.interval}
+        else:
+            reply = {"error": f"unknown op: {op}"}
+        return json.dumps(reply).encode() + b"\n"
+
+    def _bind(self) -> socketserver.UnixStreamServer:
+        """Listen on the socket, replacing a stale one.
+
+        Returns:
+            socketserver.UnixStreamServer: The bound server.
+
+        Raises:
+            ProccleanError: With ``INVALID_ARGUMENT`` if another daemon
+                already listens there.
+        """
+        if self.path.exists():
+            if _request(self.path, {"op": "ping"}) is not None:
+                msg = f"A daemon is already listening on {self.path}"
+                raise ProccleanError(
+                    ErrorCode.INVALID_ARGUMENT, msg, path=str(self.path)
+                )
+            self.path.unlink()
+        daemon = self
+
+        class Handler(socketserver.StreamRequestHandler):
+            """Reads one request line and writes the reply."""
+
+            def handle(self) -> None:
+                try:
+                    request = json.loads(self.rfile.readline())
+                except ValueError:
+                    request = None
+                self.wfile.write(daemon.handle(request))
+
+        self.path.parent.mkdir(parents=True, exist_ok=True)
+        old_umask = os.umask(0o177)  # Owner-only socket
+        try:
+            return socketserver.ThreadingUnixStreamServer(str(self.path), Handler)
+        finally:
+            os.umask(old_umask)
+
+    def _rescan(self) -> None:
+        while not self._stop.wait(self.interval):
+            try:
+                self.refresh()
+            except Exception as e:  # noqa: BLE001 - keep serving the last scan
+                sys.stderr.write(t("daemon-rescan-failed", error=str(e)) + "\n")
+
+    def serve(self) -> None:
+        """Listen, scan once, then serve snapshots until ``shutdown``.
+
+        The socket file is removed on exit.
+        """
+        self._server = self._bind()
+        try:
+            self.refresh()
+            threading.Thread(target=self._rescan, daemon=True).start()
+            self._server.serve_forever()
+        finally:
+            self._stop.set()
+            self._server.server_close()
+            self.path.unlink(missing_ok=True)
+
+    def shutdown(self) -> None:
+        """Stop a running ``serve`` from another thread."""
+        self._stop.set()
+        if self._server is not None:
+            self._server.shutdown()
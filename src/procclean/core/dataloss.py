@@ -0,0 +1,95 @@
+"""Processes that risk data loss when killed, such as local databases.
+
+A database killed with SIGKILL gets no chance to flush and close its files,
+and may need recovery or lose writes on restart. Targets named in
+``DATA_LOSS_SERVICES`` or the ``[kill]`` table of the config file::
+
+    [kill]
+    data_loss = ["etcd", "influxd"]
+
+always get an extra confirmation, and SIGKILL is refused unless the user
+passes ``--i-know-what-im-doing``. Commands that kill unattended, such as
+``budgets --enforce`` or ``watch``, cannot ask and spare them instead.
+
+Names are matched case-insensitively against the process name, as globs.
+"""
+
+import fnmatch
+
+from .config import load_config
+from .constants import DATA_LOSS_SERVICES
+from .errors import ErrorCode, ProccleanError
+from .models import ProcessInfo
+
+
+def data_loss_patterns() -> tuple[str, ...]:
+    """Get the names of processes that risk data loss when killed.
+
+    Returns:
+        tuple[str, ...]: ``DATA_LOSS_SERVICES`` plus the ``[kill] data_loss``
+        names of the config file, lowercased.
+
+    Raises:
+        ProccleanError: With ``INVALID_CONFIG`` unless ``data_loss`` is a list
+            of non-empty names.
+    """
+    value = load_config().get("kill", {}).get("data_loss", [])
+    if not isinstance(value, list) or not all(
+        isinstance(n, str) and n.strip() for n in value
+    ):
+        msg = "Invalid kill.data_loss: expected a list of process names"
+        raise ProccleanError(ErrorCode.INVALID_CONFIG, msg, key="kill.data_loss")
+    return tuple(name.lower() for name in [*DATA_LOSS_SERVICES, *value])
+
+
+def data_loss_match(proc: ProcessInfo) -> str | None:
+    """Find the data-loss pattern a process matches.
+
+    Returns:
+        str | None: The first matching name or glob, or None.
+    """
+    return _match(proc, data_loss_patterns())
+
+
+def _match(proc: ProcessInfo, patterns: tuple[str, ...]) -> str | None:
+    name = proc.name.lower()
+    return next((p for p in patterns if fnmatch.fnmatchcase(name, p)), None)
+
+
+def data_loss_targets(procs: list[ProcessInfo]) -> list[ProcessInfo]:
+    """Pick the processes that risk data loss when killed.
+
+    Returns:
+        list[ProcessInfo]: Matching processes, in their original order.
+    """
+    patterns = data_loss_patterns()  # Read the config once, not per process
+    return [p for p in procs if _match(p, patterns)]
+
+
+def refuse_data_loss_kill(
+    procs: list[ProcessInfo], *, sigkill: bool
+) -> list[ProcessInfo]:
+    """Find targets a kill could corrupt, refusing SIGKILL for them.
+
+    Args:
+        procs: Targets of the kill.
+        sigkill: Whether they get SIGKILL, directly or by escalation.
+
+    Returns:
+        list[ProcessInfo]: Targets that need an extra confirmation.
+
+    Raises:
+        ProccleanError: With ``PERMISSION_DENIED`` if SIGKILL would reach one
+            of them.
+    """
+    risks = data_loss_targets(procs)
+    if risks and sigkill:
+        names = ", ".join(sorted({p.name for p in risks}))
+        msg = (
+            f"Refusing to SIGKILL {names}: risk of data loss; use SIGTERM, "
+            "or --i-know-what-im-doing"
+        )
+        raise ProccleanError(
+            ErrorCode.PERMISSION_DENIED, msg, pids=[p.pid for p in risks]
+        )
+    return risks
@@ -0,0 +1,126 @@
+"""Option defaults from the ``[defaults]`` table of the config file.
+
+::
+
+    [defaults]
+    sort = "cpu,name"
+    min_memory = 20
+    columns = "pid,name,rss_mb,cwd"
+    high_memory_threshold = 1000
+
+``sort`` and ``min_memory`` replace the defaults of ``--sort`` and
+``--min-memory`` and set the initial sort and memory floor of the TUI.
+``columns`` applies whenever neither ``--columns`` nor ``--layout`` is given
+(see ``procclean.formatters.default_columns``). Parameters of the filter
+presets, named after their option (``high_memory_threshold`` for
+``--high-memory-threshold``), move the preset thresholds. Options given on
+the command line still win.
+"""
+
+from dataclasses import dataclass, field
+
+from .config import load_config
+from .errors import ErrorCode, ProccleanError
+from .filters import parse_sort
+from .registry import FILTERS, FilterRegistry, FilterSpec
+
+DEFAULTS_KEYS = ("sort", "min_memory", "columns")
+
+
+@dataclass(frozen=True, slots=True)
+class Defaults:
+    """Configured option defaults; None keeps the built-in one."""
+
+    sort: str | None = None  # Normalized comma-separated sort keys
+    min_memory: float | None = None
+    params: dict[str, float] = field(default_factory=dict)  # By FilterParam.dest
+
+    def options(self) -> dict[str, object]:
+        """Get the defaults by parsed CLI argument name.
+
+        Returns:
+            dict[str, object]: Values for ``ArgumentParser.set_defaults``.
+        """
+        options: dict[str, object] = dict(self.params)
+        if self.sort is not None:
+            options["sort"] = self.sort
+        if self.min_memory is not None:
+            options["min_memory"] = self.min_memory
+        return options
+
+    def param_values(self, spec: FilterSpec) -> dict[str, float]:
+        """Get the configured parameters of a preset.
+
+        Returns:
+            dict[str, float]: Values by parameter name, for ``FilterSpec.run``.
+        """
+        params = self.params
+        return {p.name: params[p.dest] for p in spec.params if p.dest in params}
+
+
+def _number(table: dict[str, object], key: str) -> float | None:
+    """Read a non-negative number from the ``[defaults]`` table.
+
+    Returns:
+        float | None: The value, or None if it is not set.
+
+    Raises:
+        ProccleanError: With ``INVALID_CONFIG`` for anything else.
+    """
+    value = table.get(key)
+    if value is None:
+        return None
+    if isinstance(value, bool) or not isinstance(value, int | float) or value < 0:
+        msg = f"Invalid defaults.{key}: expected a non-negative number"
+        raise ProccleanError(ErrorCode.INVALID_CONFIG, msg, key=f"defaults.{key}")
+    return float(value)
+
+
+def _sort(value: object) -> str:
+    """Validate ``defaults.sort``.
+
+    Returns:
+        str: The normalized comma-separated sort keys.
+
+    Raises:
+        ProccleanError: With ``INVALID_CONFIG`` unless it is a valid sort spec.
+    """
+    error = "expected comma-separated sort keys"
+    if isinstance(value, str):
+        try:
+            return ",".join(parse_sort(value))
+        except ValueError as e:
+            error = str(e)
+    msg = f"Invalid defaults.sort: {error}"
+    raise ProccleanError(ErrorCode.INVALID_CONFIG, msg, key="defaults.sort")
+
+
+def load_defaults(registry: FilterRegistry = FILTERS) -> Defaults:
+    """Read the ``[defaults]`` table.
+
+    Args:
+        registry: Presets whose parameters may be set.
+
+    Returns:
+        Defaults: The configured defaults; empty without a table.
+
+    Raises:
+        ProccleanError: With ``INVALID_CONFIG`` for unknown keys and malformed
+            values.
+    """
+    table = load_config().get("defaults", {})
+    dests = [param.dest for param in registry.params()]
+    if unknown := sorted(table.keys() - {*DEFAULTS_KEYS, *dests}):
+        choices = ", ".join([*DEFAULTS_KEYS, *dests])
+        msg = f"Unknown defaults.{unknown[0]} (choose from {choices})"
+        raise ProccleanError(
+            ErrorCode.INVALID_CONFIG, msg, key=f"defaults.{unknown[0]}"
+        )
+    params: dict[str, float] = {}
+    for dest in dests:
+        if (value := _number(table, dest)) is not None:
+            params[dest] = value
+    sort = table.get("sort")
+    return Defaults(
+        None if sort is None else _sort(sort), _number(table, "min_memory"), params
+    )
@@ -0,0 +1,66 @@
+"""Everything about one process, for the TUI inspect screen.
+
+``ProcessInfo`` holds what the process table shows. The details add what is
+too costly to read for every process: the environment, open file
+descriptors, listening sockets and the full memory breakdown.
+"""
+
+from dataclasses import dataclass
+
+from .models import ProcessInfo
+from .process import ALL_USERS, DEFAULT_SOURCE, get_process_list
+from .redact import redact_argv, redact_value
+from .source import ProcessSource
+
+
+@dataclass(frozen=True, slots=True)
+class ProcessDetails:
+    """Details of one process beyond its ``ProcessInfo``."""
+
+    proc: ProcessInfo
+    argv: list[str]  # Full command line, not truncated like proc.cmdline
+    environ: dict[str, str]  # Sorted by name
+    open_files: dict[int, str]  # Targets by descriptor number
+    ports: tuple[str, ...]  # Listening sockets, e.g. "tcp 0.0.0.0:8080"
+    memory: dict[str, float]  # MB by counter, e.g. rss, uss, pss and swap
+    children: list[ProcessInfo]  # Direct children, by PID
+
+
+def get_process_details(
+    proc: ProcessInfo,
+    source: ProcessSource | None = None,
+    procs: list[ProcessInfo] | None = None,
+    redact: bool = True,
+) -> ProcessDetails:
+    """Collect the details of a process.
+
+    Args:
+        proc: The process.
+        source: Process backend; defaults to the live system.
+        procs: Processes to find the children in; defaults to a scan of all
+            users' processes.
+        redact: Mask secrets in the command line and environment.
+
+    Returns:
+        ProcessDetails: The details; parts that cannot be read are empty.
+    """
+    backend = source or DEFAULT_SOURCE
+    if procs is None:
+        procs = get_process_list(filter_user=ALL_USERS, min_memory_mb=0, source=source)
+    environ = dict(sorted(backend.environ(proc.pid).items()))
+    argv = proc.argv or [proc.cmdline]
+    if redact:
+        environ = {name: redact_value(name, value) for name, value in environ.items()}
+        argv = redact_argv(argv)
+    return ProcessDetails(
+        proc=proc,
+        argv=argv,
+        environ=environ,
+        open_files=backend.open_files(proc.pid),
+        ports=backend.listening_ports(proc.pid),
+        memory=backend.memory_breakdown(proc.pid),
+        children=sorted(
+            (p for p in procs if p.ppid == proc.pid and p.pid != proc.pid),
+            key=lambda p: p.pid,
+        ),
+    )
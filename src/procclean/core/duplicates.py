@@ -0,0 +1,104 @@
+"""Duplicate processes: the same program started more than once.
+
+Processes group by a normalized command: the program without its path and,
+for interpreters, the script they run, so ``node /a/server.js`` and
+``node /b/server.js`` share a group while ``node build.js`` does not. True
+duplicates go further: the same command line in the same working directory,
+typically a dev server or watcher started twice. Cleaning those up keeps one
+instance, the oldest or the newest, and kills the rest.
+"""
+
+import re
+from typing import Literal
+
+from .models import ProcessInfo
+
+type GroupBy = Literal["command", "name"]
+type Keep = Literal["oldest", "newest"]
+
+GROUP_BY: tuple[GroupBy, ...] = ("command", "name")
+KEEP_CHOICES: tuple[Keep, ...] = ("oldest", "newest")
+# Programs whose first argument says what actually runs
+INTERPRETER_RE = re.compile(
+    r"^(python[\d.]*|pypy[\d.]*|node|nodejs|bun|deno|ruby|perl|php[\d.]*|java"
+    r"|bash|sh|zsh|dash)$"
+)
+MODULE_FLAGS = frozenset({"-m", "-jar"})  # Followed by the module or jar to run
+
+
+def _basename(path: str) -> str:
+    return path.rstrip("/").rsplit("/", 1)[-1]
+
+
+def name_key(proc: ProcessInfo) -> str:
+    """Get the program a process runs, without its path.
+
+    Returns:
+        str: E.g. "node" for ``/usr/bin/node server.js``.
+    """
+    cmd = proc.cmdline.split()[0] if proc.cmdline else proc.name
+    return _basename(cmd) if "/" in cmd else cmd
+
+
+def command_key(proc: ProcessInfo) -> str:
+    """Normalize the command of a process for grouping.
+
+    Interpreters keep the script or module they run, without its path;
+    other programs are reduced to their name.
+
+    Returns:
+        str: E.g. "node server.js", "python -m http.server" or "nginx".
+    """
+    program = name_key(proc).lstrip("-")  # Login shells run as "-zsh"
+    if not INTERPRETER_RE.match(program):
+        return program
+    args = iter(proc.argv[1:])
+    for arg in args:
+        if arg in MODULE_FLAGS:
+            if (target := next(args, None)) is not None:
+                return f"{program} {arg} {_basename(target)}"
+            break
+        if not arg.startswith("-"):
+            return f"{program} {_basename(arg)}"
+    return program
+
+
+def find_duplicates(processes: list[ProcessInfo]) -> list[list[ProcessInfo]]:
+    """Find processes running the same command line in the same directory.
+
+    Kernel threads and processes without a command line are left out.
+
+    Args:
+        processes: Processes to search.
+
+    Returns:
+        list[list[ProcessInfo]]: Sets of two or more duplicates, oldest
+        first, in the order their first member was listed.
+    """
+    sets: dict[tuple[tuple[str, ...], str], list[ProcessInfo]] = {}
+    for proc in processes:
+        if proc.is_kernel_thread or not proc.argv:
+            continue
+        sets.setdefault((tuple(proc.argv), proc.cwd), []).append(proc)
+    return [
+        sorted(procs, key=lambda p: (p.create_time, p.pid))
+        for procs in sets.values()
+        if len(procs) > 1
+    ]
+
+
+def duplicate_victims(
+    duplicates: list[list[ProcessInfo]], keep: Keep = "oldest"
+) -> list[ProcessInfo]:
+    """Pick the duplicates to kill, keeping one instance of each set.
+
+    Args:
+        duplicates: Sets from ``find_duplicates``, oldest first.
+        keep: Which instance survives.
+
+    Returns:
+        list[ProcessInfo]: Every other instance, set by set.
+    """
+    if keep == "oldest":
+        return [p for procs in duplicates for p in procs[1:]]
+    return [p for procs in duplicates for p in procs[:-1]]
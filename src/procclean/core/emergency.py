@@ -0,0 +1,200 @@
+"""Pressure-triggered emergency cleanup.
+
+An opt-in ``[emergency]`` table in the config file pre-approves what may be
+killed when memory runs out::
+
+    [emergency]
+    psi_full_avg10 = 25.0     # % of the last 10 s all tasks stalled on memory
+    min_available = "256M"    # or a number of MB
+    kill = ["chromium --type=renderer", "node *vite*"]
+    max_kills = 3             # per trigger (default 3)
+
+When either threshold is crossed, up to ``max_kills`` processes whose full
+command line matches a ``kill`` glob (anywhere in the line) are killed:
+highest user script score first (see ``scripts``), then killable orphans,
+then the largest. Privileged processes and system services are never picked.
+"""
+
+import fnmatch
+from dataclasses import dataclass
+from pathlib import Path
+
+import psutil
+
+from .budget import parse_size
+from .config import load_config
+from .errors import ErrorCode, ProccleanError
+from .filters import filter_killable, is_system_service
+from .models import ProcessInfo
+from .scripts import SCRIPTS
+from .source import ProcessSource
+
+PSI_MEMORY_PATH = Path("/proc/pressure/memory")
+DEFAULT_MAX_KILLS = 3
+# Seconds after a trigger before the next one; PSI averages lag behind kills
+EMERGENCY_COOLDOWN_SECONDS = 30.0
+
+
+@dataclass(frozen=True)
+class Pressure:
+    """Memory pressure at one point in time."""
+
+    psi_full_avg10: float | None  # None if PSI is unavailable
+    available_mb: float
+
+
+@dataclass(frozen=True)
+class EmergencyPolicy:
+    """Thresholds and the pre-approved kill list."""
+
+    kill: tuple[str, ...]
+    psi_full_avg10: float | None = None
+    min_available_mb: float | None = None
+    max_kills: int = DEFAULT_MAX_KILLS
+
+    def triggered(self, pressure: Pressure) -> str | None:
+        """Check if the pressure crosses a threshold.
+
+        Returns:
+            str | None: Why the emergency triggered, or None.
+        """
+        if (
+            self.psi_full_avg10 is not None
+            and pressure.psi_full_avg10 is not None
+            and pressure.psi_full_avg10 >= self.psi_full_avg10
+        ):
+            return (
+                f"PSI full avg10 {pressure.psi_full_avg10:.1f}% >= "
+                f"{self.psi_full_avg10:.1f}%"
+            )
+        if (
+            self.min_available_mb is not None
+            and pressure.available_mb < self.min_available_mb
+        ):
+            return (
+                f"available {pressure.available_mb:.0f} MB < "
+                f"{self.min_available_mb:.0f} MB"
+            )
+        return None
+
+
+def read_psi_full_avg10(path: Path | None = None) -> float | None:
+    """Read the ``full avg10`` memory pressure stall percentage.
+
+    Args:
+        path: PSI file; defaults to ``/proc/pressure/memory``.
+
+    Returns:
+        float | None: The percentage, or None if PSI is unavailable.
+    """
+    try:
+        text = (path or PSI_MEMORY_PATH).read_text(encoding="utf-8")
+    except OSError:
+        return None
+    for line in text.splitlines():
+        kind, *fields = line.split()
+        if kind == "full":
+            values = dict(f.split("=", 1) for f in fields)
+            return float(values["avg10"])
+    return None
+
+
+def read_pressure() -> Pressure:
+    """Measure current memory pressure.
+
+    Returns:
+        Pressure: PSI stall share and available memory.
+    """
+    available_mb = psutil.virtual_memory().available / 1024 / 1024
+    return Pressure(read_psi_full_avg10(), available_mb)
+
+
+def _invalid(field: str, reason: str) -> ProccleanError:
+    key = f"emergency.{field}"
+    return ProccleanError(ErrorCode.INVALID_CONFIG, f"Invalid {key}: {reason}", key=key)
+
+
+def load_emergency_policy() -> EmergencyPolicy | None:
+    """Read the ``[emergency]`` table from the config.
+
+    Returns:
+        EmergencyPolicy | None: The policy, or None if emergency mode is not
+        configured.
+
+    Raises:
+        ProccleanError: With ``INVALID_CONFIG`` for malformed settings.
+    """
+    table = load_config().get("emergency")
+    if table is None:
+        return None
+    if not isinstance(table, dict):
+        raise ProccleanError(
+            ErrorCode.INVALID_CONFIG,
+            "Invalid emergency: expected a table",
+            key="emergency",
+        )
+    kill = table.get("kill")
+    if (
+        not isinstance(kill, list)
+        or not kill
+        or not all(isinstance(p, str) and p.strip() for p in kill)
+    ):
+        raise _invalid("kill", "expected a non-empty list of patterns")
+    psi = table.get("psi_full_avg10")
+    if psi is not None and (
+        not isinstance(psi, int | float) or isinstance(psi, bool) or not 0 < psi <= 100
+    ):
+        raise _invalid("psi_full_avg10", "expected a percentage above 0")
+    min_available_mb = None
+    if "min_available" in table:
+        try:
+            min_available_mb = parse_size(table["min_available"])
+        except ValueError as e:
+            raise _invalid("min_available", str(e)) from e
+    if psi is None and min_available_mb is None:
+        raise _invalid("psi_full_avg10", "set psi_full_avg10 or min_available")
+    max_kills = table.get("max_kills", DEFAULT_MAX_KILLS)
+    if type(max_kills) is not int or max_kills < 1:
+        raise _invalid("max_kills", "expected a positive integer")
+    return EmergencyPolicy(
+        tuple(kill),
+        None if psi is None else float(psi),
+        min_available_mb,
+        max_kills,
+    )
+
+
+def emergency_targets(
+    procs: list[ProcessInfo],
+    policy: EmergencyPolicy,
+    source: ProcessSource | None = None,
+) -> list[ProcessInfo]:
+    """Pick the pre-approved processes to kill, lowest risk first.
+
+    Args:
+        procs: Candidate processes.
+        policy: Emergency policy with the kill list.
+        source: Process backend used for system service detection.
+
+    Returns:
+        list[ProcessInfo]: At most ``max_kills`` processes: higher script
+        scores first, then killable orphans before others, larger before
+        smaller.
+
+    Raises:
+        ProccleanError: With ``SCRIPT_FAILED`` if a script scorer fails.
+    """
+    approved = [
+        p
+        for p in procs
+        if any(
+            fnmatch.fnmatchcase(" ".join(p.argv) or p.cmdline, f"*{pattern}*")
+            for pattern in policy.kill
+        )
+        and not p.is_privileged
+        and not is_system_service(p, source)
+    ]
+    killable = {p.pid for p in filter_killable(approved, source)}
+    scores = {p.pid: SCRIPTS.score(p) for p in approved}
+    approved.sort(key=lambda p: (-scores[p.pid], p.pid not in killable, -p.rss_mb))
+    return approved[: policy.max_kills]
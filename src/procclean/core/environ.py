@@ -0,0 +1,81 @@
+"""Opt-in capture of selected process environment variables."""
+
+import fnmatch
+
+from .models import ProcessInfo
+from .process import DEFAULT_SOURCE
+from .redact import redact_value
+from .source import ProcessSource
+
+# Variables captured by a bare --include-env
+DEFAULT_ENV_PATTERNS: tuple[str, ...] = (
+    "NODE_ENV",
+    "VIRTUAL_ENV",
+    "CONDA_DEFAULT_ENV",
+    "PORT",
+    "HOST",
+    "RAILS_ENV",
+    "DJANGO_SETTINGS_MODULE",
+    "DATABASE_URL",
+)
+
+
+def parse_env_patterns(value: str | None) -> tuple[str, ...]:
+    """Parse a comma-separated ``--include-env`` value.
+
+    Args:
+        value: Raw option value; empty or None selects the defaults.
+
+    Returns:
+        tuple[str, ...]: Glob patterns for variable names.
+    """
+    patterns = tuple(p.strip() for p in (value or "").split(",") if p.strip())
+    return patterns or DEFAULT_ENV_PATTERNS
+
+
+def select_env(
+    environ: dict[str, str], patterns: tuple[str, ...], redact: bool = True
+) -> dict[str, str]:
+    """Pick allowlisted variables from an environment.
+
+    Args:
+        environ: Full process environment.
+        patterns: Glob patterns for names to keep (case-sensitive).
+        redact: Mask values that look like secrets.
+
+    Returns:
+        dict[str, str]: Matching variables, sorted by name.
+    """
+    selected = {
+        name: value
+        for name, value in sorted(environ.items())
+        if any(fnmatch.fnmatchcase(name, p) for p in patterns)
+    }
+    if redact:
+        return {name: redact_value(name, value) for name, value in selected.items()}
+    return selected
+
+
+def attach_env(
+    procs: list[ProcessInfo],
+    patterns: tuple[str, ...] = DEFAULT_ENV_PATTERNS,
+    redact: bool = True,
+    source: ProcessSource | None = None,
+) -> list[ProcessInfo]:
+    """Fill ``env`` on each process with its allowlisted variables.
+
+    Processes whose environment cannot be read get an empty mapping.
+
+    Args:
+        procs: Processes to annotate in place.
+        patterns: Glob patterns for names to keep.
+        redact: Mask values that look like secrets.
+        source: Process backend; defaults to the live system.
+
+    Returns:
+        list[ProcessInfo]: The same processes, for chaining.
+    """
+    source = source or DEFAULT_SOURCE
+    for proc in procs:
+        proc.env = select_env(source.environ(proc.pid), patterns, redact)
+    return procs
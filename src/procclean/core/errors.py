@@ -0,0 +1,67 @@
+"""Typed errors with stable, machine-readable codes."""
+
+from enum import StrEnum
+from typing import Any
+
+
+class ErrorCode(StrEnum):
+    """Stable error codes; scripts may branch on these values."""
+
+    INVALID_ARGUMENT = "invalid_argument"
+    INVALID_FILTER = "invalid_filter"
+    INVALID_COLUMN = "invalid_column"
+    PERMISSION_DENIED = "permission_denied"
+    PROCESS_NOT_FOUND = "process_not_found"
+    KILL_FAILED = "kill_failed"
+    NO_PROCFS = "no_procfs"
+    INVALID_CONFIG = "invalid_config"
+    SCRIPT_FAILED = "script_failed"
+    PLUGIN_FAILED = "plugin_failed"
+    SANDBOX_FAILED = "sandbox_failed"
+
+
+# Exit status for each code; usage errors follow the argparse convention
+EXIT_CODES: dict[ErrorCode, int] = {
+    ErrorCode.INVALID_ARGUMENT: 2,
+    ErrorCode.INVALID_FILTER: 2,
+    ErrorCode.INVALID_COLUMN: 2,
+    ErrorCode.INVALID_CONFIG: 2,
+}
+
+
+class ProccleanError(Exception):
+    """Error carrying a stable code and structured context."""
+
+    def __init__(self, code: ErrorCode, message: str, **context: object) -> None:
+        """Initialize the error.
+
+        Args:
+            code: Stable error code.
+            message: Human-readable description.
+            **context: JSON-serializable details (pid, value, choices, ...).
+        """
+        super().__init__(message)
+        self.code = code
+        self.message = message
+        self.context = context
+
+    @property
+    def exit_code(self) -> int:
+        """Process exit status for this error.
+
+        Returns:
+            int: 2 for usage errors, otherwise 1.
+        """
+        return EXIT_CODES.get(self.code, 1)
+
+    def to_dict(self) -> dict[str, Any]:
+        """Serialize as ``{code, message, context}``.
+
+        Returns:
+            dict[str, Any]: JSON-serializable error object.
+        """
+        return {
+            "code": str(self.code),
+            "message": self.message,
+            "context": self.context,
+        }
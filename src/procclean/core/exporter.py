@@ -0,0 +1,321 @@
+"""Prometheus metrics of processes and memory.
+
+``procclean export`` serves ``/metrics`` over HTTP in the Prometheus text
+format, so orphan buildup and memory hogs can be alerted on across machines
+without a separate exporter. Every scrape scans afresh: the memory summary,
+process counts, orphans and stale executables per user, and the top
+processes by RSS and by CPU usage. CPU usage is measured since the previous
+scrape, so the first one reports the usage over a short sample instead.
+
+Per-process metrics carry the PID, name and user as labels, never the
+command line, which can hold secrets and makes for unbounded label values.
+"""
+
+import ipaddress
+import socket
+import threading
+import time
+from collections import Counter
+from collections.abc import Callable, Iterable
+from http.server import BaseHTTPRequestHandler, ThreadingHTTPServer
+from typing import Any
+
+from .errors import ErrorCode, ProccleanError
+from .fields import FieldMask
+from .filters import filter_orphans, filter_stale
+from .memory import get_memory_summary
+from .models import ProcessInfo
+from .ports import parse_port
+from .process import ALL_USERS, ProcessCollector
+from .source import ProcessSource
+
+EXPORT_PORT = 9321
+EXPORT_TOP = 10  # Processes listed per top-N metric
+METRICS_PATH = "/metrics"
+METRICS_CONTENT_TYPE = "text/plain; version=0.0.4; charset=utf-8"
+# Fields the metrics need; everything else is skipped to keep scrapes cheap
+EXPORT_FIELDS = FieldMask.CPU | FieldMask.EXE_DELETED
+GIB = 1024**3
+
+type Sample = tuple[dict[str, str], float]
+
+
+def parse_listen(value: str) -> tuple[str, int]:
+    """Parse a ``--listen`` address, e.g. "0.0.0.0:9321", ":9321" or "9321".
+
+    IPv6 hosts go in brackets, as in "[::1]:9321".
+
+    Returns:
+        tuple[str, int]: Host ("" for every interface) and port.
+
+    Raises:
+        ValueError: If the port is missing or invalid, or an IPv6 host is
+            malformed or not in brackets.
+    """
+    host, sep, port = value.rpartition(":")
+    if not sep:
+        host, port = "", value
+    if bracketed := host.startswith("[") and host.endswith("]"):
+        host = host[1:-1]
+    if not _is_ipv6(host) if bracketed else ":" in host:
+        msg = f"invalid address: {value!r} (expected HOST:PORT or [IPv6]:PORT)"
+        raise ValueError(msg)
+    return host, parse_port(port)
+
+
+def _is_ipv6(host: str) -> bool:
+    try:
+        ipaddress.IPv6Address(host)
+    except ValueError:
+        return False
+    return True
+
+
+def _escape(value: str) -> str:
+    """Escape a label value for the text format.
+
+    Returns:
+        str: The value with backslashes, quotes and newlines escaped.
+    """
+    return value.replace("\\", "\\\\").replace('"', '\\"').replace("\n", "\\n")
+
+
+def _metric(name: str, help_text: str, samples: Iterable[Sample]) -> list[str]:
+    """Render one gauge with its HELP and TYPE lines.
+
+    Returns:
+        list[str]: Lines of the metric, without trailing newlines.
+    """
+    lines = [f"# HELP {name} {help_text}", f"# TYPE {name} gauge"]
+    for labels, value in samples:
+        pairs = ",".join(f'{k}="{_escape(v)}"' for k, v in labels.items())
+        lines.append(f"{name}{{{pairs}}} {value}" if pairs else f"{name} {value}")
+    return lines
+
+
+def _process_labels(proc: ProcessInfo) -> dict[str, str]:
+    return {"pid": str(proc.pid), "name": proc.name, "user": proc.username}
+
+
+def _per_user(procs: list[ProcessInfo], users: Iterable[str]) -> list[Sample]:
+    """Count processes by user, listing every user seen with 0 if none.
+
+    Returns:
+        list[Sample]: One sample per user, sorted by name.
+    """
+    counts = Counter(p.username for p in procs)
+    return [({"user": user}, counts[user]) for user in sorted(set(users))]
+
+
+def render_metrics(
+    procs: list[ProcessInfo],
+    memory: dict[str, Any],
+    top: int = EXPORT_TOP,
+    duration: float | None = None,
+) -> str:
+    """Render processes and a memory summary in the Prometheus text format.
+
+    Args:
+        procs: Every process scanned.
+        memory: Summary from ``get_memory_summary``.
+        top: Processes listed by RSS and by CPU usage each.
+        duration: Seconds the scan took, exported when given.
+
+    Returns:
+        str: The exposition, ending in a newline.
+    """
+    users = [p.username for p in procs]
+    by_rss = sorted(procs, key=lambda p: p.rss_mb, reverse=True)[:top]
+    by_cpu = sorted(procs, key=lambda p: p.cpu_percent, reverse=True)[:top]
+    lines = [
+        *_metric(
+            "procclean_memory_total_bytes",
+            "Total physical memory.",
+            [({}, round(memory["total_gb"] * GIB))],
+        ),
+        *_metric(
+            "procclean_memory_used_bytes",
+            "Physical memory in use.",
+            [({}, round(memory["used_gb"] * GIB))],
+        ),
+        *_metric(
+            "procclean_memory_available_bytes",
+            "Memory available to new processes without swapping.",
+            [({}, round(memory["free_gb"] * GIB))],
+        ),
+        *_metric(
+            "procclean_memory_shared_bytes",
+            "Memory in tmpfs and shared memory segments.",
+            [({}, round(memory["shared_gb"] * GIB))],
+        ),
+        *_metric(
+            "procclean_memory_used_ratio",
+            "Fraction of physical memory in use.",
+            [({}, round(memory["percent"] / 100, 4))],
+        ),
+        *_metric(
+            "procclean_swap_total_bytes",
+            "Total swap space.",
+            [({}, round(memory["swap_total_gb"] * GIB))],
+        ),
+        *_metric(
+            "procclean_swap_used_bytes",
+            "Swap space in use.",
+            [({}, round(memory["swap_used_gb"] * GIB))],
+        ),
+        *_metric(
+            "procclean_processes",
+            "Processes by user, kernel threads excluded.",
+            _per_user(procs, users),
+        ),
+        *_metric(
+            "procclean_orphan_processes",
+            "Processes whose parent exited (PPID 1), by user.",
+            _per_user(filter_orphans(procs), users),
+        ),
+        *_metric(
+            "procclean_stale_processes",
+            "Processes running a deleted or replaced executable, by user.",
+            _per_user(filter_stale(procs), users),
+        ),
+        *_metric(
+            "procclean_process_resident_memory_bytes",
+            f"Resident memory of the top {top} processes by RSS.",
+            [(_process_labels(p), round(p.rss_mb * 1024**2)) for p in by_rss],
+        ),
+        *_metric(
+            "procclean_process_cpu_percent",
+            f"CPU usage of the top {top} processes, since the previous scrape.",
+            [(_process_labels(p), round(p.cpu_percent, 1)) for p in by_cpu],
+        ),
+    ]
+    if duration is not None:
+        lines += _metric(
+            "procclean_scrape_duration_seconds",
+            "Time taken to collect these metrics.",
+            [({}, round(duration, 3))],
+        )
+    return "\n".join(lines) + "\n"
+
+
+class _Handler(BaseHTTPRequestHandler):
+    """Answers scrapes of the metrics path."""
+
+    server: "_MetricsServer"
+
+    def do_GET(self) -> None:  # noqa: N802 - named by http.server
+        """Serve the metrics, or 404 for any other path."""
+        if self.path.split("?", 1)[0] != METRICS_PATH:
+            self.send_error(404)
+            return
+        body = self.server.exporter.collect().encode()
+        self.send_response(200)
+        self.send_header("Content-Type", METRICS_CONTENT_TYPE)
+        self.send_header("Content-Length", str(len(body)))
+        self.end_headers()
+        self.wfile.write(body)
+
+    def log_message(self, *_args: object) -> None:  # noqa: PLR6301
+        """Keep scrapes out of the terminal."""
+
+
+class _MetricsServer(ThreadingHTTPServer):
+    """HTTP server answering scrapes for one exporter."""
+
+    daemon_threads = True
+
+    def __init__(self, address: tuple[str, int], exporter: "MetricsExporter") -> None:
+        if ":" in address[0]:
+            self.address_family = socket.AF_INET6
+        self.exporter = exporter
+        super().__init__(address, _Handler)
+
+
+class MetricsExporter:
+    """Serves Prometheus metrics over HTTP, scanning on every scrape."""
+
+    def __init__(
+        self,
+        host: str = "",
+        port: int = EXPORT_PORT,
+        top: int = EXPORT_TOP,
+        source: ProcessSource | None = None,
+        memory: Callable[[], dict[str, Any]] | None = None,
+    ) -> None:
+        """Initialize the exporter.
+
+        Args:
+            host: Address to listen on; "" for every interface.
+            port: Port to listen on; 0 picks a free one.
+            top: Processes listed by RSS and by CPU usage each.
+            source: Process backend; defaults to the live system.
+            memory: Reads the memory summary; defaults to
+                ``get_memory_summary``.
+        """
+        self.host = host
+        self.port = port
+        self.top = top
+        self.memory = memory or get_memory_summary
+        self._collector = ProcessCollector(source)
+        self._lock = threading.Lock()  # One scan at a time keeps CPU deltas sane
+        self._server: _MetricsServer | None = None
+        self._ready = threading.Event()
+
+    def collect(self) -> str:
+        """Scan processes and render the metrics.
+
+        Returns:
+            str: The exposition.
+        """
+        with self._lock:
+            start = time.perf_counter()
+            procs = self._collector.collect(
+                filter_user=ALL_USERS, min_memory_mb=0, fields=EXPORT_FIELDS
+            )
+            memory = self.memory()
+            duration = time.perf_counter() - start
+        return render_metrics(procs, memory, self.top, duration)
+
+    @property
+    def address(self) -> tuple[str, int]:
+        """Host and port listened on; waits for ``serve`` to bind first."""
+        self._ready.wait()
+        if self._server is None:
+            return self.host, self.port
+        host, port = self._server.server_address[:2]
+        return str(host), port
+
+    def _bind(self) -> _MetricsServer:
+        """Listen on the address.
+
+        Returns:
+            _MetricsServer: The bound server.
+
+        Raises:
+            ProccleanError: With ``INVALID_ARGUMENT`` if the address is in
+                use or cannot be bound.
+        """
+        try:
+            return _MetricsServer((self.host, self.port), self)
+        except OSError as e:
+            address = f"{self.host or '*'}:{self.port}"
+            msg = f"Cannot listen on {address}: {e.strerror or e}"
+            raise ProccleanError(
+                ErrorCode.INVALID_ARGUMENT, msg, address=address
+            ) from e
+
+    def serve(self) -> None:
+        """Listen and answer scrapes until ``shutdown``."""
+        try:
+            self._server = self._bind()
+        finally:
+            self._ready.set()
+        try:
+            self._server.serve_forever()
+        finally:
+            self._server.server_close()
+
+    def shutdown(self) -> None:
+        """Stop a running ``serve`` from another thread."""
+        if self._server is not None:
+            self._server.shutdown()
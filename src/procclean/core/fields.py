@@ -0,0 +1,63 @@
+"""Optional per-process fields, collected only when something uses them.
+
+Every field here costs a ``/proc`` read (or an external command) per process.
+``list`` builds a mask from the requested columns, filters and sort key, so
+``list -c pid,name,rss_mb`` skips them all. Everything else collects
+``ALL_FIELDS``.
+"""
+
+from enum import Flag, auto
+
+
+class FieldMask(Flag):
+    """Set of optional fields to collect."""
+
+    NONE = 0
+    # Read by get_process_list
+    CWD = auto()
+    CWD_DELETED = auto()
+    EXE_DELETED = auto()
+    PARENT = auto()  # parent_name
+    TMUX = auto()  # in_tmux, read from the environment of orphans
+    SCHED = auto()  # nice, sched_policy, rt_priority
+    PRIVILEGES = auto()
+    CONFINEMENT = auto()  # seccomp, no_new_privs
+    GROUP = auto()  # pgid, sid
+    CPU = auto()  # cpu_percent measured over an interval, not since the last scan
+    # Attached afterwards by list
+    REPO = auto()  # repo, branch
+    RUNTIME_ENV = auto()  # runtime_env, runtime_env_deleted
+    POD = auto()  # pod, namespace, container
+    VM = auto()  # vm, hugetlb_mb
+    MEDIA = auto()
+    INHIBITOR = auto()
+    DELETED_LIBS = auto()
+    PORTS = auto()
+    SMAPS = auto()  # pss_mb, uss_mb, shared_mb, swap_mb
+
+
+ALL_FIELDS = ~FieldMask.NONE
+
+# Fields behind the markers of the status column
+STATUS_FIELDS = (
+    FieldMask.TMUX
+    | FieldMask.CWD_DELETED
+    | FieldMask.SCHED
+    | FieldMask.PRIVILEGES
+    | FieldMask.RUNTIME_ENV
+    | FieldMask.MEDIA
+    | FieldMask.INHIBITOR
+    | FieldMask.DELETED_LIBS
+)
+# Fields that wait on an interval, scan every process's maps or sockets, or
+# run an external command; columns needing them slow ``list`` down noticeably
+SLOW_FIELDS = (
+    FieldMask.CPU
+    | FieldMask.REPO
+    | FieldMask.POD
+    | FieldMask.MEDIA
+    | FieldMask.INHIBITOR
+    | FieldMask.DELETED_LIBS
+    | FieldMask.PORTS
+    | FieldMask.SMAPS
+)
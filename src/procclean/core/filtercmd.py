@@ -0,0 +1,130 @@
+"""Filtering by an external command.
+
+``--filter-cmd 'mycheck {pid}'`` runs a command for every process and keeps
+the processes it exits 0 for, so site-specific rules need no change to
+procclean. The command is split like a shell would split it, but no shell
+runs it; placeholders in its arguments are replaced per process:
+
+- ``{pid}``, ``{ppid}``: process and parent IDs
+- ``{name}``, ``{user}``, ``{cwd}``: process name, owner and working directory
+
+Commands run in parallel, at most ``jobs`` at a time. A command that cannot
+start or outlives its timeout excludes the process.
+"""
+
+import shlex
+import shutil
+import string
+import subprocess
+from concurrent.futures import ThreadPoolExecutor
+
+from .errors import ErrorCode, ProccleanError
+from .models import ProcessInfo
+
+FILTER_CMD_FIELDS = ("pid", "ppid", "name", "user", "cwd")
+FILTER_CMD_JOBS = 8  # Commands running at once by default
+FILTER_CMD_TIMEOUT = 10  # Seconds per command
+
+
+def parse_filter_command(value: str) -> list[str]:
+    """Split a ``--filter-cmd`` template into arguments.
+
+    Args:
+        value: Command line with ``{field}`` placeholders; ``{{`` and ``}}``
+            stand for literal braces.
+
+    Returns:
+        list[str]: The argument templates.
+
+    Raises:
+        ValueError: If the command is empty, cannot be split or uses an
+            unknown placeholder.
+    """
+    try:
+        argv = shlex.split(value)
+    except ValueError as e:
+        msg = f"invalid command '{value}': {e}"
+        raise ValueError(msg) from e
+    if not argv:
+        msg = "expected a command"
+        raise ValueError(msg)
+    choices = ", ".join(f"{{{name}}}" for name in FILTER_CMD_FIELDS)
+    formatter = string.Formatter()
+    for arg in argv:
+        try:
+            fields = [f for _, f, _, _ in formatter.parse(arg) if f is not None]
+        except ValueError as e:
+            msg = f"invalid argument '{arg}': {e}"
+            raise ValueError(msg) from e
+        if unknown := [f for f in fields if f not in FILTER_CMD_FIELDS]:
+            msg = f"unknown placeholder {{{unknown[0]}}} (choose from {choices})"
+            raise ValueError(msg)
+    return argv
+
+
+def _accepts(argv: list[str], proc: ProcessInfo, timeout: float) -> bool:
+    """Run the command for one process.
+
+    Returns:
+        bool: True if it exited 0 within the timeout.
+    """
+    values = {
+        "pid": proc.pid,
+        "ppid": proc.ppid,
+        "name": proc.name,
+        "user": proc.username,
+        "cwd": proc.cwd or "",
+    }
+    try:
+        result = subprocess.run(
+            [arg.format(**values) for arg in argv],
+            stdin=subprocess.DEVNULL,
+            stdout=subprocess.DEVNULL,
+            timeout=timeout,
+            check=False,
+        )
+    except (OSError, subprocess.SubprocessError):
+        return False
+    return result.returncode == 0
+
+
+def filter_by_command(
+    procs: list[ProcessInfo],
+    command: str,
+    jobs: int = FILTER_CMD_JOBS,
+    timeout: float = FILTER_CMD_TIMEOUT,
+) -> list[ProcessInfo]:
+    """Keep the processes an external command accepts.
+
+    The command's output is discarded, except for stderr, so it can explain
+    failures.
+
+    Args:
+        procs: Processes to filter.
+        command: Command template (see ``parse_filter_command``).
+        jobs: Maximum number of commands running at once.
+        timeout: Seconds each command may run.
+
+    Returns:
+        list[ProcessInfo]: Accepted processes, in their original order.
+
+    Raises:
+        ProccleanError: With ``INVALID_FILTER`` for malformed commands and
+            commands that are not installed, ``INVALID_ARGUMENT`` if ``jobs``
+            is not positive.
+    """
+    try:
+        argv = parse_filter_command(command)
+    except ValueError as e:
+        raise ProccleanError(ErrorCode.INVALID_FILTER, str(e), command=command) from e
+    if shutil.which(argv[0]) is None:
+        msg = f"Filter command not found: {argv[0]}"
+        raise ProccleanError(ErrorCode.INVALID_FILTER, msg, command=command)
+    if jobs < 1:
+        msg = f"Invalid filter command concurrency {jobs}: expected at least 1"
+        raise ProccleanError(ErrorCode.INVALID_ARGUMENT, msg, jobs=jobs)
+    if not procs:
+        return []
+    with ThreadPoolExecutor(max_workers=min(jobs, len(procs))) as pool:
+        accepted = list(pool.map(lambda p: _accepts(argv, p, timeout), procs))
+    return [p for p, ok in zip(procs, accepted, strict=True) if ok]
@@ -1,33 +1,78 @@
 """Process filtering and sorting utilities."""
 
 import fnmatch
+import math
+import pwd
+import re
+import time
+from collections.abc import Callable, Sequence
+from dataclasses import asdict, dataclass
+from enum import StrEnum, auto
+from typing import Any
 
 import psutil
 
-from .constants import CRITICAL_SERVICES, SYSTEM_EXE_PATHS
+from procclean.i18n import t
+
+from .config import load_config
+from .constants import CRITICAL_SERVICES, HIGH_MEMORY_THRESHOLD_MB, SYSTEM_EXE_PATHS
+from .errors import ErrorCode, ProccleanError
+from .inhibit import exclude_inhibiting
+from .kube import in_pod
+from .media import exclude_media_users
 from .models import ProcessInfo
+from .process import DEFAULT_SOURCE
+from .source import ProcessSource
+from .timing import timed
+
+
+def protected_names() -> frozenset[str]:
+    """Get the names of processes never to treat as killable.
+
+    Returns:
+        frozenset[str]: ``CRITICAL_SERVICES`` plus the ``[kill] protected``
+        names of the config file, lowercased.
+
+    Raises:
+        ProccleanError: With ``INVALID_CONFIG`` unless ``protected`` is a list
+            of names.
+    """
+    value = load_config().get("kill", {}).get("protected", [])
+    if not isinstance(value, list) or not all(isinstance(n, str) for n in value):
+        msg = "Invalid kill.protected: expected a list of process names"
+        raise ProccleanError(ErrorCode.INVALID_CONFIG, msg, key="kill.protected")
+    return frozenset(name.lower() for name in [*CRITICAL_SERVICES, *value])
 
 
-def is_system_service(proc: ProcessInfo) -> bool:
+def is_system_service(proc: ProcessInfo, source: ProcessSource | None = None) -> bool:
     """Check if process is a system service that shouldn't be killed.
 
-    Uses two heuristics:
+    Kernel threads always count as system services. Otherwise, uses two
+    heuristics:
     1. Exe path in system directories (/usr/lib, /usr/libexec)
-    2. Name matches critical services list (shells, audio, display)
+    2. Name matches critical services list (shells, audio, display) or the
+       configured protected names
+
+    Args:
+        proc: Process to check.
+        source: Process backend used to resolve the executable.
 
     Returns:
         True if the process looks like a system/critical service, otherwise False.
     """
+    if proc.is_kernel_thread:
+        return True
+
     # Check exe path - most system services live in /usr/lib
     try:
-        exe = psutil.Process(proc.pid).exe() or ""
+        exe = (source or DEFAULT_SOURCE).exe(proc.pid)
         if exe.startswith(SYSTEM_EXE_PATHS):
             return True
     except (psutil.NoSuchProcess, psutil.AccessDenied):
         pass
 
     # Check critical services by name
-    return proc.name.lower() in {s.lower() for s in CRITICAL_SERVICES}
+    return proc.name.lower() in protected_names()
 
 
 def filter_orphans(procs: list[ProcessInfo]) -> list[ProcessInfo]:
@@ -42,16 +87,55 @@ def filter_orphans(procs: list[ProcessInfo]) -> list[ProcessInfo]:
     return [p for p in procs if p.is_orphan]
 
 
-def filter_killable(procs: list[ProcessInfo]) -> list[ProcessInfo]:
+def priority_note(proc: ProcessInfo) -> str | None:
+    """Describe a raised scheduling priority for kill confirmations.
+
+    Realtime and negative-nice processes were given priority on purpose
+    (audio servers, compositors, latency-sensitive jobs), so they are flagged
+    before killing.
+
+    Args:
+        proc: Process about to be killed.
+
+    Returns:
+        str | None: E.g. "realtime (fifo, priority 50)", or None for normal
+        priority.
+    """
+    if proc.is_realtime:
+        return t("priority-realtime", policy=proc.sched_policy, prio=proc.rt_priority)
+    if proc.nice < 0:
+        return t("priority-nice", nice=proc.nice)
+    return None
+
+
+def filter_killable(
+    procs: list[ProcessInfo], source: ProcessSource | None = None
+) -> list[ProcessInfo]:
     """Filter to orphaned processes that are safe to kill.
 
+    Args:
+        procs: List of processes to filter.
+        source: Process backend used for system service detection.
+
     Returns:
         Processes that are:
         - Orphaned (parent is init/systemd)
         - Not running in tmux
+        - Not privileged (root, elevated capabilities, setuid)
         - Not a system service (GNOME, pipewire, etc.)
+        - Not in a Kubernetes pod (kubelet would restart it)
+        - Not using audio or camera devices (it may back an active call)
+        - Not holding an inhibitor lock (download, burn, update in progress)
     """
-    return [p for p in procs if p.is_orphan_candidate and not is_system_service(p)]
+    candidates = [
+        p
+        for p in procs
+        if p.is_orphan_candidate
+        and not p.is_privileged
+        and not is_system_service(p, source)
+        and not in_pod(p, source)
+    ]
+    return exclude_inhibiting(exclude_media_users(candidates, source))
 
 
 def filter_high_memory(
@@ -69,6 +153,73 @@ def filter_high_memory(
     return [p for p in procs if p.rss_mb > threshold_mb]
 
 
+def filter_by_age(
+    procs: list[ProcessInfo],
+    older_than: float | None = None,
+    newer_than: float | None = None,
+    now: float | None = None,
+) -> list[ProcessInfo]:
+    """Filter processes by how long they have been running.
+
+    Args:
+        procs: List of processes to filter.
+        older_than: Keep processes running for at least this many seconds.
+        newer_than: Keep processes running for at most this many seconds.
+        now: Time to measure ages at; defaults to the current time.
+
+    Returns:
+        Processes whose age lies within the given bounds.
+    """
+    now = time.time() if now is None else now
+    return [
+        p
+        for p in procs
+        if (older_than is None or now - p.create_time >= older_than)
+        and (newer_than is None or now - p.create_time <= newer_than)
+    ]
+
+
+def filter_by_cpu(
+    procs: list[ProcessInfo],
+    min_cpu: float | None = None,
+    max_cpu: float | None = None,
+) -> list[ProcessInfo]:
+    """Filter processes to a CPU usage range.
+
+    Combined with ``filter_high_memory``, a ``max_cpu`` bound finds processes
+    holding memory while doing nothing.
+
+    Args:
+        procs: List of processes to filter.
+        min_cpu: Keep processes using at least this much CPU (percent).
+        max_cpu: Keep processes using at most this much CPU (percent).
+
+    Returns:
+        Processes whose CPU usage lies within the given bounds.
+    """
+    return [
+        p
+        for p in procs
+        if (min_cpu is None or p.cpu_percent >= min_cpu)
+        and (max_cpu is None or p.cpu_percent <= max_cpu)
+    ]
+
+
+def filter_cpu_hogs(
+    procs: list[ProcessInfo], threshold_percent: float = 50.0
+) -> list[ProcessInfo]:
+    """Filter to processes using more than threshold CPU.
+
+    Args:
+        procs: List of processes to filter.
+        threshold_percent: CPU threshold in percent of one core.
+
+    Returns:
+        Processes whose CPU usage is greater than threshold_percent.
+    """
+    return [p for p in procs if p.cpu_percent > threshold_percent]
+
+
 def filter_stale(procs: list[ProcessInfo]) -> list[ProcessInfo]:
     """Filter to processes with deleted/updated executables.
 
@@ -84,6 +235,303 @@ def filter_stale(procs: list[ProcessInfo]) -> list[ProcessInfo]:
     return [p for p in procs if p.exe_deleted]
 
 
+@dataclass(frozen=True, slots=True)
+class ProcessSummary:
+    """Totals of a process list, for reports and notebooks."""
+
+    count: int
+    rss_mb: float
+    cpu_percent: float
+    orphans: int
+    killable: int
+    stale: int  # Running a deleted or replaced executable
+    cwd_deleted: int
+    high_memory: int  # Above HIGH_MEMORY_THRESHOLD_MB
+
+    def to_dict(self) -> dict[str, Any]:
+        """Describe the summary for JSON output.
+
+        Returns:
+            dict[str, Any]: Every field, with RSS and CPU rounded.
+        """
+        data = asdict(self)
+        data["rss_mb"] = round(self.rss_mb, 2)
+        data["cpu_percent"] = round(self.cpu_percent, 2)
+        return data
+
+
+def summarize_processes(
+    procs: list[ProcessInfo], source: ProcessSource | None = None
+) -> ProcessSummary:
+    """Count and total a process list by cleanup category.
+
+    Args:
+        procs: Processes to summarize.
+        source: Process backend used for system service detection.
+
+    Returns:
+        ProcessSummary: How many processes there are, their RSS and CPU
+        usage, and how many fall in each category.
+    """
+    return ProcessSummary(
+        count=len(procs),
+        rss_mb=sum(p.rss_mb for p in procs),
+        cpu_percent=sum(p.cpu_percent for p in procs),
+        orphans=len(filter_orphans(procs)),
+        killable=len(filter_killable(procs, source)),
+        stale=len(filter_stale(procs)),
+        cwd_deleted=len(filter_cwd_deleted(procs)),
+        high_memory=len(filter_high_memory(procs, HIGH_MEMORY_THRESHOLD_MB)),
+    )
+
+
+def filter_cwd_deleted(procs: list[ProcessInfo]) -> list[ProcessInfo]:
+    """Filter to processes whose working directory was removed.
+
+    Usually leftovers from a deleted project (dev servers, watchers, REPLs)
+    and good cleanup candidates.
+
+    Args:
+        procs: List of processes to filter.
+
+    Returns:
+        Processes whose cwd no longer exists.
+    """
+    return [p for p in procs if p.cwd_deleted]
+
+
+def filter_env_deleted(procs: list[ProcessInfo]) -> list[ProcessInfo]:
+    """Filter to processes whose virtualenv, conda env or nvm version was removed.
+
+    Needs ``runtime_env_deleted`` from ``attach_runtime_envs``.
+
+    Args:
+        procs: List of processes to filter.
+
+    Returns:
+        Processes whose interpreter environment no longer exists.
+    """
+    return [p for p in procs if p.runtime_env_deleted]
+
+
+def filter_needs_restart(procs: list[ProcessInfo]) -> list[ProcessInfo]:
+    """Filter to processes still running code an upgrade replaced.
+
+    Needs ``deleted_libs`` from ``attach_deleted_libs``.
+
+    Args:
+        procs: List of processes to filter.
+
+    Returns:
+        Processes with a deleted executable or deleted shared libraries
+        mapped, like ``needrestart`` reports.
+    """
+    return [p for p in procs if p.needs_restart]
+
+
+class MatchMode(StrEnum):
+    """How a name pattern is compared against process names."""
+
+    CONTAINS = auto()  # Pattern anywhere in the name
+    EXACT = auto()  # Whole name equals the pattern
+    PREFIX = auto()  # Name starts with the pattern
+    GLOB = auto()  # Whole name matches a shell pattern, e.g. "node*"
+    REGEX = auto()  # Regular expression found anywhere in the name
+
+    @classmethod
+    def for_pattern(cls, pattern: str) -> "MatchMode":
+        """Pick the default mode for a pattern.
+
+        Returns:
+            MatchMode: ``GLOB`` if the pattern has ``*``, ``?`` or ``[``,
+            otherwise ``CONTAINS``.
+        """
+        return cls.GLOB if any(c in pattern for c in "*?[") else cls.CONTAINS
+
+
+def _compile(pattern: str, ignore_case: bool, option: str) -> re.Pattern[str]:
+    """Compile a user-supplied regular expression.
+
+    Returns:
+        re.Pattern[str]: The compiled expression.
+
+    Raises:
+        ProccleanError: With ``INVALID_FILTER`` if it does not compile.
+    """
+    try:
+        return re.compile(pattern, re.IGNORECASE if ignore_case else 0)
+    except re.error as e:
+        msg = f"Invalid {option} regular expression '{pattern}': {e}"
+        raise ProccleanError(ErrorCode.INVALID_FILTER, msg, pattern=pattern) from e
+
+
+@dataclass(frozen=True, slots=True)
+class NameMatcher:
+    """Process name matcher shared by CLI filters, kill-by-name and presets."""
+
+    pattern: str
+    mode: MatchMode = MatchMode.CONTAINS
+    ignore_case: bool = False
+
+    def __post_init__(self) -> None:
+        """Reject regular expressions that do not compile.
+
+        Raises:
+            ProccleanError: With ``INVALID_FILTER`` for an invalid ``REGEX``
+                pattern.
+        """
+        if self.mode is MatchMode.REGEX:
+            _compile(self.pattern, self.ignore_case, "--name")
+
+    def matches(self, name: str) -> bool:
+        """Check a process name against the pattern.
+
+        Args:
+            name: Process name to test.
+
+        Returns:
+            True if the name matches according to mode and case sensitivity.
+        """
+        if self.mode is MatchMode.REGEX:
+            regex = _compile(self.pattern, self.ignore_case, "--name")
+            return regex.search(name) is not None
+        pattern = self.pattern
+        if self.ignore_case:
+            name, pattern = name.casefold(), pattern.casefold()
+        match self.mode:
+            case MatchMode.GLOB:
+                return fnmatch.fnmatchcase(name, pattern)
+            case MatchMode.EXACT:
+                return name == pattern
+            case MatchMode.PREFIX:
+                return name.startswith(pattern)
+            case MatchMode.CONTAINS:
+                return pattern in name
+
+    def filter(self, procs: list[ProcessInfo]) -> list[ProcessInfo]:
+        """Filter processes by name.
+
+        Args:
+            procs: List of processes to filter.
+
+        Returns:
+            Processes whose name matches.
+        """
+        return [p for p in procs if self.matches(p.name)]
+
+
+def filter_by_cmdline(
+    procs: list[ProcessInfo], pattern: str, ignore_case: bool = False
+) -> list[ProcessInfo]:
+    """Filter processes by command line.
+
+    Args:
+        procs: List of processes to filter.
+        pattern: Regular expression searched for in the full command line.
+        ignore_case: Match case-insensitively.
+
+    Returns:
+        Processes whose command line matches.
+
+    Raises:
+        ProccleanError: With ``INVALID_FILTER`` if the pattern does not compile.
+    """
+    regex = _compile(pattern, ignore_case, "--cmdline")
+    return [p for p in procs if regex.search(p.cmdline)]
+
+
+def search_spans(
+    query: str, text: str, fuzzy: bool = False
+) -> list[tuple[int, int]] | None:
+    """Find where a search query matches a text, ignoring case.
+
+    Args:
+        query: Text typed into the search.
+        text: Text to search in.
+        fuzzy: Also match the query's characters in order with gaps between
+            them, e.g. "ndsv" in "node-server".
+
+    Returns:
+        list[tuple[int, int]] | None: (start, end) spans of the matched
+        characters, for highlighting; empty for an empty query, None if the
+        text does not match.
+    """
+    if not query:
+        return []
+    haystack, needle = text.lower(), query.lower()
+    if (start := haystack.find(needle)) >= 0:
+        return [(start, start + len(needle))]
+    if not fuzzy:
+        return None
+    spans: list[tuple[int, int]] = []
+    pos = 0
+    for char in needle:
+        pos = haystack.find(char, pos)
+        if pos < 0:
+            return None
+        if spans and spans[-1][1] == pos:
+            spans[-1] = (spans[-1][0], pos + 1)
+        else:
+            spans.append((pos, pos + 1))
+        pos += 1
+    return spans
+
+
+def filter_by_search(procs: list[ProcessInfo], query: str) -> list[ProcessInfo]:
+    """Filter processes by an incremental search, as in the TUI search bar.
+
+    The query matches anywhere in the name, command line or cwd, or fuzzily
+    in the name. Fuzzy matching is left out for command lines and paths,
+    which are long enough to contain almost any query with gaps.
+
+    Args:
+        procs: List of processes to filter.
+        query: Search text, case-insensitive.
+
+    Returns:
+        Processes matching the query, in their original order.
+    """
+    return [
+        p
+        for p in procs
+        if search_spans(query, p.name, fuzzy=True) is not None
+        or search_spans(query, p.cmdline) is not None
+        or search_spans(query, p.cwd) is not None
+    ]
+
+
+def resolve_user(user: str) -> str:
+    """Turn a numeric UID into the user name processes are listed under.
+
+    Args:
+        user: User name or UID.
+
+    Returns:
+        str: The user name; a UID without a passwd entry is kept as is, which
+        is also how such owners are listed.
+    """
+    if not user.isdigit():
+        return user
+    try:
+        return pwd.getpwuid(int(user)).pw_name
+    except (KeyError, OverflowError):
+        return user
+
+
+def filter_by_user(procs: list[ProcessInfo], user: str) -> list[ProcessInfo]:
+    """Filter processes by owner.
+
+    Args:
+        procs: List of processes to filter.
+        user: User name or numeric UID.
+
+    Returns:
+        Processes owned by the user.
+    """
+    name = resolve_user(user)
+    return [p for p in procs if p.username == name]
+
+
 def filter_by_cwd(procs: list[ProcessInfo], cwd_path: str) -> list[ProcessInfo]:
     """Filter processes by current working directory.
 
@@ -109,28 +557,153 @@ def filter_by_cwd(procs: list[ProcessInfo], cwd_path: str) -> list[ProcessInfo]:
     ]
 
 
+_DIGITS = re.compile(r"(\d+)")
+
+
+class SortBy(StrEnum):
+    """Keys processes can be sorted by."""
+
+    MEMORY = auto()
+    CPU = auto()
+    PID = auto()
+    NAME = auto()
+    CWD = auto()
+    AGE = auto()
+
+
+# Accepted spellings of each sort key
+SORT_KEYS: dict[str, SortBy] = {
+    "memory": SortBy.MEMORY,
+    "mem": SortBy.MEMORY,
+    "cpu": SortBy.CPU,
+    "pid": SortBy.PID,
+    "name": SortBy.NAME,
+    "cwd": SortBy.CWD,
+    "age": SortBy.AGE,
+}
+
+_NUMERIC_FIELDS: dict[SortBy, Callable[[ProcessInfo], float]] = {
+    SortBy.MEMORY: lambda p: p.rss_mb,
+    SortBy.CPU: lambda p: p.cpu_percent,
+    SortBy.PID: lambda p: p.pid,
+    SortBy.AGE: lambda p: -p.create_time,  # Older processes started earlier
+}
+
+# Text fields sorted naturally unless ``[display] natural_sort = false``
+_TEXT_FIELDS: dict[SortBy, Callable[[ProcessInfo], str]] = {
+    SortBy.NAME: lambda p: p.name,
+    SortBy.CWD: lambda p: p.cwd or "",
+}
+
+
+def natural_key(text: str) -> tuple[str | int, ...]:
+    """Build a case-insensitive key that orders digit runs by value.
+
+    Sorts ``foo2`` before ``foo10`` and ``/srv/app9`` before ``/srv/app10``.
+
+    Returns:
+        tuple[str | int, ...]: Alternating text and number chunks.
+    """
+    chunks = _DIGITS.split(text.casefold())
+    return tuple(int(c) if i % 2 else c for i, c in enumerate(chunks))
+
+
+def natural_sort_enabled() -> bool:
+    """Read the ``[display] natural_sort`` setting.
+
+    Returns:
+        bool: True unless natural sorting of names and paths is turned off.
+
+    Raises:
+        ProccleanError: With ``INVALID_CONFIG`` unless the value is a boolean.
+    """
+    value = load_config().get("display", {}).get("natural_sort", True)
+    if not isinstance(value, bool):
+        msg = "Invalid display.natural_sort: expected true or false"
+        raise ProccleanError(ErrorCode.INVALID_CONFIG, msg, key="display.natural_sort")
+    return value
+
+
+def parse_sort(value: str) -> list[SortBy]:
+    """Parse a compound sort spec like ``memory,name``.
+
+    Args:
+        value: Comma-separated ``SORT_KEYS``, most significant first.
+
+    Returns:
+        list[SortBy]: The sort keys, without duplicates.
+
+    Raises:
+        ValueError: If a key is unknown or none is given.
+    """
+    names = [k.strip().lower() for k in value.split(",") if k.strip()]
+    if unknown := [k for k in names if k not in SORT_KEYS]:
+        choices = ", ".join(SORT_KEYS)
+        msg = f"unknown sort key(s): {', '.join(unknown)} (choose from {choices})"
+        raise ValueError(msg)
+    if not names:
+        msg = "expected at least one sort key"
+        raise ValueError(msg)
+    return list(dict.fromkeys(SORT_KEYS[k] for k in names))
+
+
+def _sort_func(
+    key: SortBy, natural: bool, descending: bool
+) -> Callable[[ProcessInfo], tuple[object, ...]]:
+    """Get a totally ordered key function for one sort key.
+
+    Returns:
+        Callable[[ProcessInfo], tuple[object, ...]]: Key function. Numbers
+        that are NaN (e.g. CPU usage not sampled yet) sort after every other
+        value in either direction.
+    """
+    if key in _TEXT_FIELDS:
+        text = _TEXT_FIELDS[key]
+        if natural:
+            return lambda p: natural_key(text(p))
+        return lambda p: (text(p).casefold(),)
+    number = _NUMERIC_FIELDS[key]
+
+    def numeric(p: ProcessInfo) -> tuple[bool, float]:
+        value = number(p)
+        nan = math.isnan(value)
+        return nan != descending, 0.0 if nan else value
+
+    return numeric
+
+
 def sort_processes(
     procs: list[ProcessInfo],
-    sort_by: str = "memory",
+    sort_by: str | Sequence[str] = "memory",
     reverse: bool = True,
+    natural: bool | None = None,
 ) -> list[ProcessInfo]:
-    """Sort processes by given key.
+    """Sort processes by one or more keys.
+
+    Later keys only order processes that tie on all earlier ones. Full ties
+    are ordered by ascending PID, so the result never depends on the input
+    order.
 
     Args:
         procs: List of processes to sort
-        sort_by: One of 'memory', 'cpu', 'pid', 'name', 'cwd'
-        reverse: If True, sort descending (default for numeric)
-
-    Returns:
-        A new list of processes sorted by the requested key.
-    """
-    sort_keys = {
-        "memory": lambda p: p.rss_mb,
-        "mem": lambda p: p.rss_mb,
-        "cpu": lambda p: p.cpu_percent,
-        "pid": lambda p: p.pid,
-        "name": lambda p: p.name.lower(),
-        "cwd": lambda p: p.cwd.lower() if p.cwd else "",
-    }
-    key_func = sort_keys.get(sort_by, sort_keys["memory"])
-    return sorted(procs, key=key_func, reverse=reverse)
+        sort_by: One of 'memory', 'cpu', 'pid', 'name', 'cwd', 'age', a list of them
+            or a comma-separated string like 'memory,name'. Unknown keys sort
+            by memory.
+        reverse: If True, sort descending by every key (default for numeric)
+        natural: Order digit runs in names and cwds by value (see
+            ``natural_key``); None reads ``natural_sort_enabled()``.
+
+    Returns:
+        A new list of processes sorted by the requested keys.
+    """
+    if natural is None:
+        natural = natural_sort_enabled()
+    names = sort_by.split(",") if isinstance(sort_by, str) else sort_by
+    keys = [SORT_KEYS.get(name.strip().lower(), SortBy.MEMORY) for name in names]
+    with timed("sort"):
+        result = sorted(procs, key=lambda p: p.pid)
+        # Python's sort is stable in both directions, so sorting by the least
+        # significant key first leaves ties of each pass in the earlier order
+        for key in reversed(keys):
+            result.sort(key=_sort_func(key, natural, reverse), reverse=reverse)
+        return result
@@ -0,0 +1,176 @@
+"""Inhibitor lock awareness.
+
+Programs declare "don't interrupt me" (a download, a disc burn, a system
+update, a video playing) by taking a systemd-logind inhibitor lock or a
+screensaver inhibitor from the desktop session. logind reports the PID of
+each lock; GNOME session inhibitors only name the application, which is
+matched against process names.
+
+Processes holding a blocking inhibitor are never picked by the killable preset
+or ``clean``. Delay locks are left out: services take them routinely to get a
+moment of warning before suspend.
+"""
+
+import json
+import shutil
+import subprocess
+from dataclasses import dataclass
+
+from .models import ProcessInfo
+
+BUSCTL_TIMEOUT = 5  # Seconds for one busctl call
+
+_LOGIND = (
+    "org.freedesktop.login1",
+    "/org/freedesktop/login1",
+    "org.freedesktop.login1.Manager",
+)
+_GNOME_SESSION = (
+    "org.gnome.SessionManager",
+    "/org/gnome/SessionManager",
+    "org.gnome.SessionManager",
+)
+_GNOME_INHIBITOR = "org.gnome.SessionManager.Inhibitor"
+
+
+@dataclass(frozen=True)
+class Inhibitor:
+    """One "don't interrupt me" lock."""
+
+    what: str  # E.g. "sleep:shutdown" for logind, "idle" for the session
+    who: str  # Program or application ID that took the lock
+    why: str
+    pid: int | None = None  # Unknown for session inhibitors
+
+    @property
+    def label(self) -> str:
+        """Short description, e.g. "sleep: Downloading updates"."""
+        return f"{self.what}: {self.why}" if self.why else self.what
+
+    def matches(self, proc: ProcessInfo) -> bool:
+        """Check if the lock belongs to a process.
+
+        Session inhibitors match by name, using the last part of the
+        application ID (``org.mozilla.firefox`` matches ``firefox``).
+
+        Returns:
+            bool: True if the PIDs match, or the names do for a lock without
+            PID.
+        """
+        if self.pid is not None:
+            return proc.pid == self.pid
+        app = self.who.lower().removesuffix(".desktop").rsplit(".", 1)[-1]
+        return bool(app) and proc.name.lower() == app
+
+
+def _busctl(*args: str, user: bool = False) -> list | None:
+    """Call a D-Bus method through busctl.
+
+    Returns:
+        list | None: The decoded return values, or None if busctl is missing
+        or the call failed.
+    """
+    if shutil.which("busctl") is None:
+        return None
+    command = ["busctl", "--user" if user else "--system", "--json=short", "call"]
+    try:
+        result = subprocess.run(
+            [*command, *args],
+            capture_output=True,
+            text=True,
+            timeout=BUSCTL_TIMEOUT,
+            check=False,
+        )
+        data = json.loads(result.stdout)["data"]
+    except (OSError, subprocess.SubprocessError, ValueError, KeyError, TypeError):
+        return None
+    return data if isinstance(data, list) else None
+
+
+def _logind_inhibitors() -> list[Inhibitor]:
+    data = _busctl(*_LOGIND, "ListInhibitors")
+    if not data:
+        return []
+    inhibitors = []
+    for entry in data[0]:
+        try:
+            what, who, why, mode, _uid, pid = entry
+        except (TypeError, ValueError):
+            continue
+        if mode == "block":
+            inhibitors.append(Inhibitor(what, who, why, int(pid)))
+    return inhibitors
+
+
+def _session_inhibitors() -> list[Inhibitor]:
+    data = _busctl(*_GNOME_SESSION, "GetInhibitors", user=True)
+    if not data:
+        return []
+    inhibitors = []
+    for path in data[0]:
+        service = (_GNOME_SESSION[0], path, _GNOME_INHIBITOR)
+        app = _busctl(*service, "GetAppId", user=True)
+        reason = _busctl(*service, "GetReason", user=True)
+        if app and app[0]:
+            inhibitors.append(Inhibitor("idle", app[0], reason[0] if reason else ""))
+    return inhibitors
+
+
+def list_inhibitors() -> list[Inhibitor]:
+    """List the blocking logind locks and desktop session inhibitors.
+
+    Returns:
+        list[Inhibitor]: The active inhibitors; empty where neither logind nor
+        a GNOME session is reachable.
+    """
+    return _logind_inhibitors() + _session_inhibitors()
+
+
+def find_inhibiting(
+    procs: list[ProcessInfo], inhibitors: list[Inhibitor] | None = None
+) -> dict[int, str]:
+    """Find the processes holding an inhibitor.
+
+    Args:
+        procs: Processes to check.
+        inhibitors: Inhibitors to match; listed from D-Bus if not given.
+
+    Returns:
+        dict[int, str]: What each inhibiting process holds, e.g.
+        "sleep: Burning disc"; other processes are left out.
+    """
+    if not procs:
+        return {}
+    if inhibitors is None:
+        inhibitors = list_inhibitors()
+    found: dict[int, str] = {}
+    for proc in procs:
+        labels = [i.label for i in inhibitors if i.matches(proc)]
+        if labels:
+            found[proc.pid] = "; ".join(labels)
+    return found
+
+
+def attach_inhibitors(procs: list[ProcessInfo]) -> list[ProcessInfo]:
+    """Fill ``inhibitor`` on processes holding an inhibitor lock.
+
+    Args:
+        procs: Processes to annotate in place.
+
+    Returns:
+        list[ProcessInfo]: The same processes, for chaining.
+    """
+    found = find_inhibiting(procs)
+    for proc in procs:
+        proc.inhibitor = found.get(proc.pid)
+    return procs
+
+
+def exclude_inhibiting(procs: list[ProcessInfo]) -> list[ProcessInfo]:
+    """Drop processes holding an inhibitor lock from a target set.
+
+    Returns:
+        list[ProcessInfo]: The processes not inhibiting.
+    """
+    found = find_inhibiting(procs)
+    return [p for p in procs if p.pid not in found]
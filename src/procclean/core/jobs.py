@@ -0,0 +1,57 @@
+"""Background jobs of the invoking shell session."""
+
+import os
+
+from .models import ProcessInfo
+from .process import DEFAULT_SOURCE, get_process_list
+from .source import ProcessSource
+
+
+def current_session() -> int:
+    """Return the session ID of the calling process (its shell's session).
+
+    Returns:
+        int: Session ID.
+    """
+    return os.getsid(0)
+
+
+def _own_lineage(source: ProcessSource) -> set[int]:
+    """Collect this process and its ancestors, which are never jobs.
+
+    Returns:
+        set[int]: PIDs of procclean itself and every parent up the chain.
+    """
+    ppids = {info["pid"]: info.get("ppid") or 0 for info in source.iter_processes()}
+    lineage = {os.getpid()}
+    pid = ppids.get(os.getpid(), 0)
+    while pid and pid not in lineage:
+        lineage.add(pid)
+        pid = ppids.get(pid, 0)
+    return lineage
+
+
+def get_session_jobs(
+    session: int | None = None, source: ProcessSource | None = None
+) -> list[ProcessInfo]:
+    """List processes sharing a shell session, like the shell's ``jobs``.
+
+    The session leader (the shell), procclean itself and its ancestors are
+    excluded. Jobs are numbered by start order, oldest first.
+
+    Args:
+        session: Session ID; defaults to the caller's session.
+        source: Process backend; defaults to the live system.
+
+    Returns:
+        list[ProcessInfo]: Jobs sorted by start time.
+    """
+    source = source or DEFAULT_SOURCE
+    sid = current_session() if session is None else session
+    exclude = _own_lineage(source) | {sid}
+    jobs = [
+        p
+        for p in get_process_list(min_memory_mb=0, source=source)
+        if p.pid not in exclude and source.session_id(p.pid) == sid
+    ]
+    return sorted(jobs, key=lambda p: (p.create_time, p.pid))
@@ -0,0 +1,165 @@
+"""Commands run before and after every kill.
+
+Configured in the ``[kill]`` table of the config file::
+
+    [kill]
+    pre_kill = "notify-send procclean 'Killing a process'"
+    post_kill = ["logger", "-t", "procclean"]
+    hook_failure = "abort"
+    hook_timeout = 10
+
+A command is a string, split like a shell would split it but run without
+one, or a list of arguments. It gets a JSON object on stdin: ``hook``
+(``pre_kill`` or ``post_kill``), ``process`` (the fields of the process)
+and, after the kill, ``ok`` and ``message``.
+
+A hook fails when it exits non-zero, cannot start or outlives
+``hook_timeout`` seconds. ``hook_failure`` decides what happens then:
+
+- ``abort`` (default): a failed ``pre_kill`` skips the kill, so the command
+  can veto it; a failed ``post_kill`` is noted in the kill result
+- ``warn``: the kill goes ahead and the failure is noted in the result
+- ``ignore``: failures go unnoticed
+"""
+
+import json
+import shlex
+import subprocess
+from dataclasses import asdict, dataclass
+from typing import Any
+
+from procclean.i18n import t
+
+from .config import load_config
+from .errors import ErrorCode, ProccleanError
+from .models import ProcessInfo
+
+HOOK_FAILURE_POLICIES = ("abort", "warn", "ignore")
+KILL_HOOK_TIMEOUT = 10.0  # Seconds a hook command may run by default
+
+
+@dataclass(frozen=True, slots=True)
+class KillHookCommands:
+    """The configured pre- and post-kill commands."""
+
+    pre_kill: tuple[str, ...] = ()  # Arguments; empty runs nothing
+    post_kill: tuple[str, ...] = ()
+    on_failure: str = "abort"  # One of HOOK_FAILURE_POLICIES
+    timeout: float = KILL_HOOK_TIMEOUT
+
+    @property
+    def active(self) -> bool:
+        """Whether any command runs around kills."""
+        return bool(self.pre_kill or self.post_kill)
+
+    def check_kill(self, proc: ProcessInfo) -> tuple[str | None, str | None]:
+        """Run the pre-kill command.
+
+        Returns:
+            tuple[str | None, str | None]: Why the kill is aborted, and a
+            warning to add to the kill result; None for either if there is
+            none.
+        """
+        error = self._run("pre_kill", self.pre_kill, {"process": asdict(proc)})
+        if error is None or self.on_failure == "ignore":
+            return None, None
+        if self.on_failure == "abort":
+            return t("kill-hook-aborted", pid=proc.pid, error=error), None
+        return None, error
+
+    def after_kill(self, proc: ProcessInfo, ok: bool, message: str) -> str | None:
+        """Run the post-kill command.
+
+        Returns:
+            str | None: The failure to note in the kill result, if any.
+        """
+        payload = {"process": asdict(proc), "ok": ok, "message": message}
+        error = self._run("post_kill", self.post_kill, payload)
+        return None if self.on_failure == "ignore" else error
+
+    def _run(
+        self, hook: str, argv: tuple[str, ...], payload: dict[str, Any]
+    ) -> str | None:
+        """Run one hook command with the payload on stdin.
+
+        Returns:
+            str | None: Why it failed, or None if it succeeded or is unset.
+        """
+        if not argv:
+            return None
+        stdin = json.dumps({"hook": hook, **payload}, default=str)
+        try:
+            result = subprocess.run(
+                argv,
+                input=stdin,
+                capture_output=True,
+                text=True,
+                timeout=self.timeout,
+                check=False,
+            )
+        except subprocess.TimeoutExpired:
+            reason = t("kill-hook-timeout", seconds=f"{self.timeout:g}")
+        except OSError as e:
+            reason = str(e)
+        else:
+            if result.returncode == 0:
+                return None
+            lines = result.stderr.strip().splitlines()
+            reason = lines[-1] if lines else t("kill-hook-exit", code=result.returncode)
+        return t("kill-hook-command-failed", hook=hook, error=reason)
+
+
+def _command(table: dict[str, Any], key: str) -> tuple[str, ...]:
+    """Read one hook command from the ``[kill]`` table.
+
+    Returns:
+        tuple[str, ...]: The arguments; empty when not configured.
+
+    Raises:
+        ProccleanError: With ``INVALID_CONFIG`` for anything but a non-empty
+            string or list of strings.
+    """
+    value = table.get(key)
+    if value is None:
+        return ()
+    if isinstance(value, str):
+        try:
+            value = shlex.split(value)
+        except ValueError as e:
+            msg = f"Invalid kill.{key}: {e}"
+            raise ProccleanError(
+                ErrorCode.INVALID_CONFIG, msg, key=f"kill.{key}"
+            ) from e
+    valid = isinstance(value, list) and all(isinstance(a, str) for a in value)
+    if not valid or not value:
+        msg = f"Invalid kill.{key}: expected a command string or argument list"
+        raise ProccleanError(ErrorCode.INVALID_CONFIG, msg, key=f"kill.{key}")
+    return tuple(value)
+
+
+def load_kill_hooks() -> KillHookCommands:
+    """Read the hook commands and their failure policy from the config.
+
+    Returns:
+        KillHookCommands: The commands; inactive when none is configured.
+
+    Raises:
+        ProccleanError: With ``INVALID_CONFIG`` for malformed settings.
+    """
+    table = load_config().get("kill", {})
+    policy = table.get("hook_failure", "abort")
+    if policy not in HOOK_FAILURE_POLICIES:
+        choices = ", ".join(HOOK_FAILURE_POLICIES)
+        msg = f"Invalid kill.hook_failure: expected one of {choices}"
+        raise ProccleanError(ErrorCode.INVALID_CONFIG, msg, key="kill.hook_failure")
+    timeout = table.get("hook_timeout", KILL_HOOK_TIMEOUT)
+    number = isinstance(timeout, int | float) and not isinstance(timeout, bool)
+    if not number or timeout <= 0:
+        msg = "Invalid kill.hook_timeout: expected a positive number of seconds"
+        raise ProccleanError(ErrorCode.INVALID_CONFIG, msg, key="kill.hook_timeout")
+    return KillHookCommands(
+        _command(table, "pre_kill"),
+        _command(table, "post_kill"),
+        policy,
+        float(timeout),
+    )
@@ -0,0 +1,164 @@
+"""Kubernetes pod awareness on kubelet nodes.
+
+Containerized processes live in a ``kubepods`` cgroup that carries the pod
+UID and the container ID (``.../kubepods-burstable-pod<uid>.slice/
+cri-containerd-<id>.scope`` with the systemd driver, ``/kubepods/burstable/
+pod<uid>/<id>`` with cgroupfs). Names come from ``crictl ps``, or from the
+``<namespace>_<pod>_<uid>`` directories kubelet keeps in ``/var/log/pods``.
+
+Killing a pod process only makes kubelet restart it, so pod processes are
+never picked by the killable preset or ``clean``.
+"""
+
+import json
+import re
+import subprocess
+from dataclasses import dataclass, field
+from pathlib import Path
+
+from .models import ProcessInfo
+from .process import DEFAULT_SOURCE
+from .source import ProcessSource
+
+KUBELET_DIR = Path("/var/lib/kubelet")
+POD_LOG_DIR = Path("/var/log/pods")
+CRICTL_TIMEOUT = 5  # Seconds for one crictl call
+
+# The systemd cgroup driver writes the pod UID with underscores
+_POD_RE = re.compile(
+    r"kubepods\b.*?pod([0-9a-f]{8}(?:[-_][0-9a-f]{4}){3}[-_][0-9a-f]{12})"
+)
+_CONTAINER_ID_RE = re.compile(r"([0-9a-f]{64})(?:\.scope)?$")
+
+
+@dataclass(frozen=True)
+class PodRef:
+    """Pod and container a process's cgroup points at."""
+
+    uid: str
+    container_id: str | None = None
+
+
+@dataclass
+class PodNames:
+    """Human-readable names of a pod and its containers."""
+
+    namespace: str
+    pod: str
+    containers: dict[str, str] = field(default_factory=dict)  # ID to name
+
+
+def pod_from_cgroup(cgroup: str) -> PodRef | None:
+    """Extract the pod UID and container ID from a cgroup path.
+
+    Args:
+        cgroup: cgroup path of the process.
+
+    Returns:
+        PodRef | None: The pod reference, or None outside ``kubepods``.
+    """
+    match = _POD_RE.search(cgroup)
+    if not match:
+        return None
+    container = _CONTAINER_ID_RE.search(cgroup[match.end() :])
+    return PodRef(match[1].replace("_", "-"), container[1] if container else None)
+
+
+def in_pod(proc: ProcessInfo, source: ProcessSource | None = None) -> bool:
+    """Check if a process runs in a Kubernetes pod.
+
+    Returns:
+        bool: True if its cgroup lies under ``kubepods``.
+    """
+    return pod_from_cgroup((source or DEFAULT_SOURCE).cgroup(proc.pid)) is not None
+
+
+def _from_pod_logs() -> dict[str, PodNames]:
+    names: dict[str, PodNames] = {}
+    try:
+        entries = list(POD_LOG_DIR.iterdir())
+    except OSError:
+        return names
+    for entry in entries:
+        parts = entry.name.split("_")
+        if len(parts) == 3:  # noqa: PLR2004
+            namespace, pod, uid = parts
+            names[uid] = PodNames(namespace, pod)
+    return names
+
+
+def _from_crictl() -> dict[str, PodNames]:
+    try:
+        result = subprocess.run(
+            ["crictl", "ps", "-a", "-o", "json"],
+            capture_output=True,
+            text=True,
+            timeout=CRICTL_TIMEOUT,
+            check=False,
+        )
+        containers = json.loads(result.stdout)["containers"]
+    except (OSError, subprocess.SubprocessError, ValueError, KeyError, TypeError):
+        return {}
+    names: dict[str, PodNames] = {}
+    for container in containers:
+        labels = container.get("labels") or {}
+        uid = labels.get("io.kubernetes.pod.uid")
+        if not uid:
+            continue
+        pod = names.setdefault(
+            uid,
+            PodNames(
+                labels.get("io.kubernetes.pod.namespace", "?"),
+                labels.get("io.kubernetes.pod.name", "?"),
+            ),
+        )
+        name = labels.get("io.kubernetes.container.name")
+        if name and container.get("id"):
+            pod.containers[container["id"]] = name
+    return names
+
+
+def pod_names() -> dict[str, PodNames]:
+    """Look up the names of the pods on this node.
+
+    Returns:
+        dict[str, PodNames]: Names keyed by pod UID; crictl wins over the
+        kubelet log directories, which lack container IDs.
+    """
+    return _from_pod_logs() | _from_crictl()
+
+
+def attach_pods(
+    procs: list[ProcessInfo], source: ProcessSource | None = None
+) -> list[ProcessInfo]:
+    """Fill ``pod``, ``namespace`` and ``container`` on kubelet nodes.
+
+    Does nothing unless ``/var/lib/kubelet`` exists. Pods whose names cannot
+    be looked up are shown by UID.
+
+    Args:
+        procs: Processes to annotate in place.
+        source: Process backend used to read cgroups.
+
+    Returns:
+        list[ProcessInfo]: The same processes, for chaining.
+    """
+    if not KUBELET_DIR.exists():
+        return procs
+    source = source or DEFAULT_SOURCE
+    refs = {
+        p.pid: ref for p in procs if (ref := pod_from_cgroup(source.cgroup(p.pid)))
+    }
+    if not refs:
+        return procs
+    names = pod_names()
+    for proc in procs:
+        if (ref := refs.get(proc.pid)) is None:
+            continue
+        found = names.get(ref.uid)
+        proc.pod = found.pod if found else ref.uid
+        proc.namespace = found.namespace if found else None
+        if ref.container_id:
+            container = found.containers.get(ref.container_id) if found else None
+            proc.container = container or ref.container_id[:12]
+    return procs
@@ -0,0 +1,259 @@
+"""Detection of stale unix sockets and lock files.
+
+Leftovers break restarts: a new server cannot bind a socket path that still
+exists, and a lock file naming a dead PID blocks the next instance. Two kinds
+of stale state are reported:
+
+- Sockets still held by a process although their file (or its directory) was
+  removed, and socket files on disk that nothing is listening on.
+- Lock/PID files whose recorded owner is no longer running.
+"""
+
+import os
+import re
+import stat
+from dataclasses import dataclass
+from pathlib import Path
+
+from procclean.i18n import t
+
+from .filters import is_system_service
+from .inhibit import exclude_inhibiting
+from .kube import in_pod
+from .media import exclude_media_users
+from .models import ProcessInfo
+from .process import PROC_ROOT
+from .source import ProcessSource
+
+# Lock file names: foo.lock, foo.pid and X server locks like .X0-lock
+LOCK_NAME_RE = re.compile(r"(\.lock|\.pid|^\.X\d+-lock)$")
+SOCKET_FD_RE = re.compile(r"^socket:\[(\d+)\]$")
+MAX_LOCK_BYTES = 64  # PID files are tiny; skip anything larger
+UNIX_PATH_FIELD = 7  # Index of the path column in net/unix
+
+
+@dataclass(frozen=True, slots=True)
+class Leftover:
+    """A stale socket or lock file."""
+
+    kind: str  # "socket" or "lock"
+    path: str
+    reason: str
+    pid: int | None = None  # Live process still holding it, if any
+    removable: bool = False  # True if the file is on disk and can be deleted
+
+
+def default_scan_dirs() -> list[Path]:
+    """Directories searched for leftover socket and lock files.
+
+    Returns:
+        list[Path]: /tmp, the X11 socket dir and the user's runtime dir.
+    """
+    runtime = os.environ.get("XDG_RUNTIME_DIR") or f"/run/user/{os.getuid()}"
+    return [Path("/tmp"), Path("/tmp/.X11-unix"), Path(runtime)]  # noqa: S108
+
+
+def _unix_socket_paths(proc_root: Path) -> dict[int, str]:
+    """Map socket inodes to bound filesystem paths from ``net/unix``.
+
+    Returns:
+        dict[int, str]: Inode to path; abstract and unnamed sockets are skipped.
+    """
+    try:
+        lines = (proc_root / "net" / "unix").read_text().splitlines()[1:]
+    except OSError:
+        return {}
+    sockets: dict[int, str] = {}
+    for line in lines:
+        fields = line.split(maxsplit=UNIX_PATH_FIELD)
+        if len(fields) > UNIX_PATH_FIELD and not fields[-1].startswith("@"):
+            sockets[int(fields[6])] = fields[-1]
+    return sockets
+
+
+def _socket_holders(proc_root: Path) -> dict[int, int]:
+    """Map socket inodes to the PID of a process holding them.
+
+    Returns:
+        dict[int, int]: Inode to PID, for processes we may inspect.
+    """
+    holders: dict[int, int] = {}
+    for pid_dir in proc_root.iterdir():
+        if not pid_dir.name.isdigit():
+            continue
+        try:
+            fds = list((pid_dir / "fd").iterdir())
+        except OSError:
+            continue
+        for fd in fds:
+            try:
+                match = SOCKET_FD_RE.match(str(fd.readlink()))
+            except OSError:
+                continue
+            if match:
+                holders.setdefault(int(match.group(1)), int(pid_dir.name))
+    return holders
+
+
+def _owned_entries(dirs: list[Path]) -> list[tuple[Path, os.stat_result]]:
+    """List files owned by the current user in the scan directories.
+
+    Returns:
+        list[tuple[Path, os.stat_result]]: Entries with their lstat results.
+    """
+    uid = os.getuid()
+    entries: list[tuple[Path, os.stat_result]] = []
+    for directory in dirs:
+        try:
+            children = list(directory.iterdir())
+        except OSError:
+            continue
+        for path in children:
+            try:
+                st = path.lstat()
+            except OSError:
+                continue
+            if st.st_uid == uid:
+                entries.append((path, st))
+    return entries
+
+
+def find_stale_sockets(
+    proc_root: Path = PROC_ROOT, dirs: list[Path] | None = None
+) -> list[Leftover]:
+    """Find held sockets whose file is gone and socket files nobody listens on.
+
+    Args:
+        proc_root: procfs mount point.
+        dirs: Directories to scan for socket files.
+
+    Returns:
+        list[Leftover]: Stale sockets, sorted by path.
+    """
+    bound = _unix_socket_paths(proc_root)
+    holders = _socket_holders(proc_root)
+    found: dict[tuple[str, int | None], Leftover] = {}
+
+    for inode, path in bound.items():
+        pid = holders.get(inode)
+        if pid is not None and not Path(path).exists():
+            reason = t("leftover-socket-removed")
+            found[path, pid] = Leftover("socket", path, reason, pid=pid)
+
+    live_paths = set(bound.values())
+    for path, st in _owned_entries(dirs or default_scan_dirs()):
+        if stat.S_ISSOCK(st.st_mode) and str(path) not in live_paths:
+            reason = t("leftover-no-listener")
+            found[str(path), None] = Leftover(
+                "socket", str(path), reason, removable=True
+            )
+
+    return sorted(found.values(), key=lambda item: item.path)
+
+
+def read_lock_pid(path: Path) -> int | None:
+    """Read the owner PID recorded in a lock or PID file.
+
+    Returns:
+        int | None: The PID, or None if the file does not contain one.
+    """
+    try:
+        with path.open("rb") as f:
+            content = f.read(MAX_LOCK_BYTES + 1)
+    except OSError:
+        return None
+    if len(content) > MAX_LOCK_BYTES:
+        return None
+    text = content.decode("ascii", errors="ignore").strip()
+    return int(text) if text.isdigit() and int(text) > 0 else None
+
+
+def find_stale_locks(
+    proc_root: Path = PROC_ROOT, dirs: list[Path] | None = None
+) -> list[Leftover]:
+    """Find lock/PID files whose recorded owner is no longer running.
+
+    Args:
+        proc_root: procfs mount point.
+        dirs: Directories to scan for lock files.
+
+    Returns:
+        list[Leftover]: Stale lock files, sorted by path.
+    """
+    stale: list[Leftover] = []
+    for path, st in _owned_entries(dirs or default_scan_dirs()):
+        if not stat.S_ISREG(st.st_mode) or not LOCK_NAME_RE.search(path.name):
+            continue
+        pid = read_lock_pid(path)
+        if pid is not None and not (proc_root / str(pid)).exists():
+            reason = t("leftover-owner-gone", pid=pid)
+            stale.append(Leftover("lock", str(path), reason, removable=True))
+    return sorted(stale, key=lambda item: item.path)
+
+
+def find_leftovers(
+    proc_root: Path = PROC_ROOT, dirs: list[Path] | None = None
+) -> list[Leftover]:
+    """Find all stale sockets and lock files.
+
+    Args:
+        proc_root: procfs mount point.
+        dirs: Directories to scan; defaults to ``default_scan_dirs()``.
+
+    Returns:
+        list[Leftover]: Stale sockets followed by stale lock files.
+    """
+    return find_stale_sockets(proc_root, dirs) + find_stale_locks(proc_root, dirs)
+
+
+def leftover_holders(
+    leftovers: list[Leftover],
+    procs: list[ProcessInfo],
+    source: ProcessSource | None = None,
+) -> list[ProcessInfo]:
+    """Pick the holders of stale sockets that are safe to kill.
+
+    Only processes in ``procs`` are considered, so pass the user's own. As
+    in ``clean_targets``, privileged processes, system services, Kubernetes
+    pod processes, processes using audio or camera devices and processes
+    holding an inhibitor lock are never picked.
+
+    Args:
+        leftovers: Leftovers found by ``find_leftovers``.
+        procs: Candidate processes.
+        source: Process backend used for system service detection.
+
+    Returns:
+        list[ProcessInfo]: Holders to kill, in the order of ``procs``.
+    """
+    pids = {item.pid for item in leftovers if item.pid is not None}
+    holders = [
+        p
+        for p in procs
+        if p.pid in pids
+        and not p.is_privileged
+        and not is_system_service(p, source)
+        and not in_pod(p, source)
+    ]
+    return exclude_inhibiting(exclude_media_users(holders, source))
+
+
+def remove_leftover(leftover: Leftover) -> tuple[bool, str]:
+    """Delete a leftover socket or lock file.
+
+    Args:
+        leftover: The leftover to remove; must be ``removable``.
+
+    Returns:
+        A tuple of (success, message).
+    """
+    if not leftover.removable:
+        return False, t("leftover-not-on-disk", path=leftover.path)
+    try:
+        Path(leftover.path).unlink()
+    except FileNotFoundError:
+        return True, t("leftover-already-gone", path=leftover.path)
+    except OSError as e:
+        error = e.strerror or e
+        return False, t("leftover-remove-error", path=leftover.path, error=error)
+    return True, t("leftover-removed", path=leftover.path)
@@ -0,0 +1,181 @@
+"""Check the process table against an "expected processes" manifest.
+
+A manifest is a TOML file with ``required`` and ``forbidden`` rules::
+
+    required = [
+        "sshd",
+        { name = "postgres", user = "postgres", min = 2 },
+        { cmdline = "*gunicorn*app:app*" },
+    ]
+    forbidden = [{ cmdline = "*xmrig*" }, "nc"]
+
+A bare string is a glob on the process name. Tables may combine ``name``
+(glob on the name), ``cmdline`` (glob on the full command line) and ``user``
+(exact); a process must match all given fields. Required rules need at least
+``min`` (default 1) matching processes; forbidden rules must match none.
+"""
+
+import fnmatch
+import tomllib
+from dataclasses import dataclass, field
+from pathlib import Path
+
+from .errors import ErrorCode, ProccleanError
+from .models import ProcessInfo
+
+_RULE_FIELDS = frozenset({"name", "cmdline", "user", "min"})
+
+
+@dataclass(frozen=True)
+class ProcessRule:
+    """One manifest entry."""
+
+    name: str | None = None
+    cmdline: str | None = None
+    user: str | None = None
+    min_count: int = 1
+
+    def matches(self, proc: ProcessInfo) -> bool:
+        """Check if a process satisfies every field of the rule.
+
+        Returns:
+            bool: True on a match.
+        """
+        full_cmdline = " ".join(proc.argv) or proc.cmdline
+        return (
+            (self.name is None or fnmatch.fnmatchcase(proc.name, self.name))
+            and (
+                self.cmdline is None
+                or fnmatch.fnmatchcase(full_cmdline, self.cmdline)
+            )
+            and (self.user is None or proc.username == self.user)
+        )
+
+    def describe(self) -> str:
+        """Render the rule for reports, e.g. ``name=postgres user=postgres``.
+
+        Returns:
+            str: The non-empty fields as ``key=value`` pairs.
+        """
+        parts = [
+            f"{key}={value}"
+            for key, value in (
+                ("name", self.name),
+                ("cmdline", self.cmdline),
+                ("user", self.user),
+            )
+            if value is not None
+        ]
+        return " ".join(parts)
+
+
+@dataclass(frozen=True)
+class Manifest:
+    """Required and forbidden process rules."""
+
+    required: tuple[ProcessRule, ...] = ()
+    forbidden: tuple[ProcessRule, ...] = ()
+
+
+@dataclass
+class VerifyResult:
+    """Differences between the process table and a manifest."""
+
+    # Required rules with too few matches, with the number found
+    missing: list[tuple[ProcessRule, int]] = field(default_factory=list)
+    # Forbidden rules with the processes that matched them
+    present: list[tuple[ProcessRule, list[ProcessInfo]]] = field(
+        default_factory=list
+    )
+    checked: int = 0  # Number of rules evaluated
+
+    @property
+    def ok(self) -> bool:
+        """Check if the process table conforms to the manifest."""
+        return not self.missing and not self.present
+
+
+def _invalid(path: Path, reason: str) -> ProccleanError:
+    return ProccleanError(
+        ErrorCode.INVALID_ARGUMENT,
+        f"Invalid manifest {path}: {reason}",
+        path=str(path),
+    )
+
+
+def _parse_rule(path: Path, section: str, entry: object) -> ProcessRule:
+    """Build a rule from a manifest entry.
+
+    Returns:
+        ProcessRule: The parsed rule.
+
+    Raises:
+        ProccleanError: If the entry is not a string or a valid table.
+    """
+    if isinstance(entry, str) and entry:
+        return ProcessRule(name=entry)
+    if not isinstance(entry, dict):
+        raise _invalid(path, f"{section} entries must be strings or tables")
+    if unknown := sorted(set(entry) - _RULE_FIELDS):
+        raise _invalid(path, f"unknown {section} field(s): {', '.join(unknown)}")
+    matchers = {k: entry.get(k) for k in ("name", "cmdline", "user")}
+    if not any(matchers.values()):
+        raise _invalid(path, f"{section} entry needs name, cmdline or user")
+    if not all(v is None or isinstance(v, str) for v in matchers.values()):
+        raise _invalid(path, f"{section} name, cmdline and user must be strings")
+    min_count = entry.get("min", 1)
+    if type(min_count) is not int or min_count < 1:
+        raise _invalid(path, f"{section} min must be a positive integer")
+    return ProcessRule(**matchers, min_count=min_count)
+
+
+def load_manifest(path: Path) -> Manifest:
+    """Read and validate a manifest file.
+
+    Args:
+        path: TOML manifest.
+
+    Returns:
+        Manifest: The parsed rules.
+
+    Raises:
+        ProccleanError: With ``INVALID_ARGUMENT`` if the file cannot be read or
+            is malformed.
+    """
+    try:
+        with path.open("rb") as f:
+            data = tomllib.load(f)
+    except OSError as e:
+        raise _invalid(path, e.strerror or str(e)) from e
+    except tomllib.TOMLDecodeError as e:
+        raise _invalid(path, str(e)) from e
+    if unknown := sorted(set(data) - {"required", "forbidden"}):
+        raise _invalid(path, f"unknown key(s): {', '.join(unknown)}")
+    sections: dict[str, tuple[ProcessRule, ...]] = {}
+    for section in ("required", "forbidden"):
+        entries = data.get(section, [])
+        if not isinstance(entries, list):
+            raise _invalid(path, f"{section} must be a list")
+        sections[section] = tuple(_parse_rule(path, section, e) for e in entries)
+    return Manifest(**sections)
+
+
+def verify_manifest(procs: list[ProcessInfo], manifest: Manifest) -> VerifyResult:
+    """Compare processes against a manifest.
+
+    Args:
+        procs: The live process table.
+        manifest: Rules to check.
+
+    Returns:
+        VerifyResult: Missing required and present forbidden processes.
+    """
+    result = VerifyResult(checked=len(manifest.required) + len(manifest.forbidden))
+    for rule in manifest.required:
+        found = sum(1 for p in procs if rule.matches(p))
+        if found < rule.min_count:
+            result.missing.append((rule, found))
+    for rule in manifest.forbidden:
+        if matched := [p for p in procs if rule.matches(p)]:
+            result.present.append((rule, matched))
+    return result
@@ -0,0 +1,146 @@
+"""Audio and camera "in use" detection.
+
+A process is using media when it holds an ALSA PCM or V4L2 device open, or
+when it is a PipeWire client with an audio or video stream (PulseAudio clients
+show up through ``pipewire-pulse``). Killing such a process would cut off a
+call or recording, so media users are never picked by the killable preset or
+``clean``.
+"""
+
+import json
+import shutil
+import subprocess
+
+import psutil
+
+from .models import ProcessInfo
+from .process import DEFAULT_SOURCE
+from .source import ProcessSource
+
+MEDIA_KINDS = ("audio", "camera")
+PW_DUMP_TIMEOUT = 5  # Seconds for one pw-dump call
+
+_PW_NODE = "PipeWire:Interface:Node"
+_PW_CLIENT = "PipeWire:Interface:Client"
+_PW_PID = "application.process.id"
+
+# PipeWire stream media classes, by kind
+_STREAM_KINDS = {
+    "Stream/Output/Audio": "audio",
+    "Stream/Input/Audio": "audio",
+    "Stream/Input/Video": "camera",
+}
+
+
+def _pid(value: object) -> int | None:
+    try:
+        return int(str(value))
+    except ValueError:
+        return None
+
+
+def parse_pw_dump(objects: list[dict]) -> dict[int, set[str]]:
+    """Map PipeWire stream nodes to the processes owning them.
+
+    Args:
+        objects: Objects as printed by ``pw-dump``.
+
+    Returns:
+        dict[int, set[str]]: Media kinds per client PID.
+    """
+    client_pids: dict[int, int] = {}
+    for obj in objects:
+        props = (obj.get("info") or {}).get("props") or {}
+        if obj.get("type") == _PW_CLIENT and (pid := _pid(props.get(_PW_PID))):
+            client_pids[obj.get("id", -1)] = pid
+    streams: dict[int, set[str]] = {}
+    for obj in objects:
+        props = (obj.get("info") or {}).get("props") or {}
+        kind = _STREAM_KINDS.get(props.get("media.class", ""))
+        if obj.get("type") != _PW_NODE or kind is None:
+            continue
+        pid = _pid(props.get(_PW_PID)) or client_pids.get(props.get("client.id"))
+        if pid:
+            streams.setdefault(pid, set()).add(kind)
+    return streams
+
+
+def pipewire_streams() -> dict[int, set[str]]:
+    """Ask PipeWire which processes have audio or video streams.
+
+    Returns:
+        dict[int, set[str]]: Media kinds per PID; empty without ``pw-dump``.
+    """
+    if shutil.which("pw-dump") is None:
+        return {}
+    try:
+        result = subprocess.run(
+            ["pw-dump"],
+            capture_output=True,
+            text=True,
+            timeout=PW_DUMP_TIMEOUT,
+            check=False,
+        )
+        objects = json.loads(result.stdout)
+    except (OSError, subprocess.SubprocessError, ValueError):
+        return {}
+    return parse_pw_dump(objects) if isinstance(objects, list) else {}
+
+
+def find_media_users(
+    procs: list[ProcessInfo], source: ProcessSource | None = None
+) -> dict[int, str]:
+    """Find the processes currently using audio or camera devices.
+
+    Args:
+        procs: Processes to check.
+        source: Process backend used to read open devices.
+
+    Returns:
+        dict[int, str]: Media kinds per PID, e.g. "audio,camera"; processes
+        not using media are left out.
+    """
+    if not procs:
+        return {}
+    source = source or DEFAULT_SOURCE
+    streams = pipewire_streams()
+    users: dict[int, str] = {}
+    for proc in procs:
+        try:
+            kinds = set(source.media_devices(proc.pid))
+        except (psutil.NoSuchProcess, psutil.AccessDenied):
+            kinds = set()
+        kinds |= streams.get(proc.pid, set())
+        if kinds:
+            users[proc.pid] = ",".join(k for k in MEDIA_KINDS if k in kinds)
+    return users
+
+
+def attach_media(
+    procs: list[ProcessInfo], source: ProcessSource | None = None
+) -> list[ProcessInfo]:
+    """Fill ``media`` on processes using audio or camera devices.
+
+    Args:
+        procs: Processes to annotate in place.
+        source: Process backend used to read open devices.
+
+    Returns:
+        list[ProcessInfo]: The same processes, for chaining.
+    """
+    users = find_media_users(procs, source)
+    for proc in procs:
+        proc.media = users.get(proc.pid)
+    return procs
+
+
+def exclude_media_users(
+    procs: list[ProcessInfo], source: ProcessSource | None = None
+) -> list[ProcessInfo]:
+    """Drop processes using audio or camera devices from a target set.
+
+    Returns:
+        list[ProcessInfo]: The processes not using media.
+    """
+    users = find_media_users(procs, source)
+    return [p for p in procs if p.pid not in users]
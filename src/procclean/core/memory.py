@@ -1,22 +1,93 @@
 """Memory summary utilities."""
 
+import time
+from collections.abc import Callable
+from dataclasses import dataclass
+
 import psutil
 
+from .constants import MEMORY_SETTLE_SECONDS
+from .sysinfo import load_average, uptime_seconds
+
+type KillResults = list[tuple[int, bool, str]]
+
 
 def get_memory_summary() -> dict:
     """Get system memory summary.
 
+    Memory pressure means something else on a CPU-saturated machine, so the
+    load averages, CPU count and uptime come along.
+
     Returns:
-        dict: A dictionary containing total, used, and available memory in GB,
-        memory usage percentage, and swap usage/total in GB.
+        dict: A dictionary containing total, used, shared (tmpfs and shared
+        memory segments; 0 where unknown) and available memory in GB,
+        memory usage percentage, swap usage/total in GB, the 1/5/15 minute
+        ``load_avg``, ``cpu_count`` and ``uptime_seconds``.
     """
     mem = psutil.virtual_memory()
     swap = psutil.swap_memory()
     return {
         "total_gb": mem.total / 1024**3,
         "used_gb": mem.used / 1024**3,
+        "shared_gb": getattr(mem, "shared", 0) / 1024**3,
         "free_gb": mem.available / 1024**3,
         "percent": mem.percent,
         "swap_used_gb": swap.used / 1024**3,
         "swap_total_gb": swap.total / 1024**3,
+        "load_avg": load_average(),
+        "cpu_count": psutil.cpu_count() or 1,
+        "uptime_seconds": uptime_seconds(),
     }
+
+
+@dataclass(frozen=True)
+class ReclaimReport:
+    """Memory freed by a kill, estimated from RSS and measured.
+
+    RSS counts shared pages once per process, so the estimate usually
+    overstates what is actually returned to the system.
+    """
+
+    estimated_mb: float  # Summed RSS of the processes that were killed
+    actual_mb: float | None = None  # Drop in used RAM + swap; None if unmeasured
+
+    @property
+    def ratio(self) -> float | None:
+        """Actual as a fraction of estimated, if both are known."""
+        if self.actual_mb is None or not self.estimated_mb:
+            return None
+        return self.actual_mb / self.estimated_mb
+
+
+def _used_mb() -> float:
+    summary = get_memory_summary()
+    return (summary["used_gb"] + summary["swap_used_gb"]) * 1024
+
+
+def measure_reclaim(
+    kill: Callable[[], KillResults],
+    rss: dict[int, float],
+    settle_seconds: float | None = MEMORY_SETTLE_SECONDS,
+) -> tuple[KillResults, ReclaimReport]:
+    """Run a kill and compare the memory it freed with the RSS estimate.
+
+    Used memory is sampled before the kill and again after ``settle_seconds``,
+    giving the kernel time to reap the processes. Other activity on the
+    system shows up in the measurement too, so treat it as approximate.
+
+    Args:
+        kill: Performs the kill and returns ``(pid, success, message)`` tuples.
+        rss: RSS in MB of each targeted PID.
+        settle_seconds: Delay before the second sample; None skips measuring
+            (e.g. for simulated kills).
+
+    Returns:
+        tuple[KillResults, ReclaimReport]: The kill results and the report.
+    """
+    before = _used_mb() if settle_seconds is not None else 0.0
+    results = kill()
+    estimated = sum(rss.get(pid, 0.0) for pid, ok, _ in results if ok)
+    if settle_seconds is None or not estimated:
+        return results, ReclaimReport(estimated)
+    time.sleep(settle_seconds)
+    return results, ReclaimReport(estimated, before - _used_mb())
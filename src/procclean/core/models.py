@@ -1,6 +1,9 @@
 """Process data models."""
 
-from dataclasses import dataclass
+from dataclasses import asdict, dataclass, field
+from typing import Any
+
+from .constants import REALTIME_POLICIES
 
 
 @dataclass
@@ -21,6 +24,103 @@ class ProcessInfo:
     in_tmux: bool
     status: str
     exe_deleted: bool = False  # True if executable was deleted/updated
+    cwd_deleted: bool = False  # True if working directory was removed
+    # Arguments as passed to exec; cmdline is joined and truncated for display
+    argv: list[str] = field(default_factory=list)
+    # Allowlisted environment, only captured with --include-env
+    env: dict[str, str] | None = None
+    nice: int = 0
+    sched_policy: str = "other"  # See SCHED_POLICIES
+    rt_priority: int = 0  # 1-99 for fifo/rr, otherwise 0
+    # Why the process is privileged: "root", "caps" and/or "setuid"
+    privileges: list[str] = field(default_factory=list)
+    seccomp: str = "disabled"  # See SECCOMP_MODES
+    no_new_privs: bool = False
+    pgid: int = -1  # Process group, -1 if unknown
+    sid: int = -1  # Session, -1 if unknown
+    # No command line and spawned by kthreadd; never killed
+    is_kernel_thread: bool = False
+    # Owning distro package, only resolved with --attribute; "" if unpackaged
+    package: str | None = None
+    exe_sha256: str | None = None  # Only computed for unpackaged binaries
+    # Git worktree root containing cwd, only resolved by list and --repo
+    repo: str | None = None
+    branch: str | None = None  # Checked-out branch, or short commit if detached
+    # Interpreter environment, e.g. "nvm:v20.11.0"; see core.runtime
+    runtime_env: str | None = None
+    runtime_env_deleted: bool = False  # True if that environment was removed
+    # Kubernetes pod, only resolved on kubelet nodes; see core.kube
+    pod: str | None = None
+    namespace: str | None = None
+    container: str | None = None
+    # Virtual machine name ("?" if unnamed), only set for VM processes
+    vm: str | None = None
+    hugetlb_mb: float | None = None  # Hugepage memory, only read for VMs
+    # Media in use, e.g. "audio,camera", only resolved by list; see core.media
+    media: str | None = None
+    # Inhibitor locks held, e.g. "sleep: Burning disc"; see core.inhibit
+    inhibitor: str | None = None
+    # Replaced shared libraries still mapped, only read by list and
+    # --filter needs-restart; see core.upgrade
+    deleted_libs: list[str] | None = None
+    # Listening sockets, e.g. "tcp 0.0.0.0:3000", only read by list and
+    # --port; see core.ports
+    ports: list[str] | None = None
+    # Proportional, unique, shared and swapped memory from smaps_rollup, only
+    # read by list and memory --per-process; see core.smaps
+    pss_mb: float | None = None
+    uss_mb: float | None = None
+    shared_mb: float | None = None
+    swap_mb: float | None = None
+
+    def to_dict(self) -> dict[str, Any]:
+        """Describe the process as plain values, one key per field.
+
+        The keys are the same for every process, so a list of these feeds
+        straight into ``pandas.DataFrame``. Fields that were not collected
+        are None.
+
+        Returns:
+            dict[str, Any]: Every field; lists and the environment are copies.
+        """
+        return asdict(self)
+
+    @property
+    def confinement(self) -> str:
+        """Summarize the sandboxing applied to the process.
+
+        Returns:
+            str: E.g. "seccomp=filter,nnp", or "none" if unconfined.
+        """
+        parts = [] if self.seccomp == "disabled" else [f"seccomp={self.seccomp}"]
+        if self.no_new_privs:
+            parts.append("nnp")
+        return ",".join(parts) or "none"
+
+    @property
+    def memory_mb(self) -> float:
+        """RSS plus hugepage memory, which RSS does not count."""
+        return self.rss_mb + (self.hugetlb_mb or 0.0)
+
+    @property
+    def needs_restart(self) -> bool:
+        """Check if an upgrade replaced the executable or a mapped library."""
+        return self.exe_deleted or bool(self.deleted_libs)
+
+    @property
+    def is_privileged(self) -> bool:
+        """Check if process runs as root, with capabilities or setuid."""
+        return bool(self.privileges)
+
+    @property
+    def is_unpackaged(self) -> bool:
+        """Check if attribution found no package owning the executable."""
+        return self.package == ""
+
+    @property
+    def is_realtime(self) -> bool:
+        """Check if process runs under a realtime scheduling policy."""
+        return self.sched_policy in REALTIME_POLICIES
 
     @property
     def is_orphan_candidate(self) -> bool:
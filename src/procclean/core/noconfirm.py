@@ -0,0 +1,51 @@
+"""Processes that may be killed without a confirmation prompt.
+
+Patterns come from the ``[kill]`` table of the config file::
+
+    [kill]
+    no_confirm = ["chromium --type=renderer", "target/debug/*"]
+
+Each pattern is a glob matched anywhere in the full command line. Only
+single-process kills skip the prompt; batches are always confirmed.
+"""
+
+import fnmatch
+
+from .config import load_config
+from .errors import ErrorCode, ProccleanError
+from .models import ProcessInfo
+
+
+def no_confirm_patterns() -> tuple[str, ...]:
+    """Read the ``[kill] no_confirm`` patterns from the config.
+
+    Returns:
+        tuple[str, ...]: Glob patterns; empty when not configured.
+
+    Raises:
+        ProccleanError: With ``INVALID_CONFIG`` unless the value is a list of
+            non-empty strings.
+    """
+    value = load_config().get("kill", {}).get("no_confirm", [])
+    if not isinstance(value, list) or not all(
+        isinstance(p, str) and p.strip() for p in value
+    ):
+        msg = "Invalid kill.no_confirm: expected a list of patterns"
+        raise ProccleanError(ErrorCode.INVALID_CONFIG, msg, key="kill.no_confirm")
+    return tuple(value)
+
+
+def no_confirm_match(proc: ProcessInfo) -> str | None:
+    """Find the pattern that lets a process be killed without confirmation.
+
+    Args:
+        proc: Process about to be killed on its own.
+
+    Returns:
+        str | None: The first matching pattern, or None.
+    """
+    cmdline = " ".join(proc.argv) or proc.cmdline
+    return next(
+        (p for p in no_confirm_patterns() if fnmatch.fnmatchcase(cmdline, f"*{p}*")),
+        None,
+    )
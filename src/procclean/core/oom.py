@@ -0,0 +1,199 @@
+"""Kernel OOM-kill awareness.
+
+``/proc/vmstat`` counts OOM kills since boot (``oom_kill``). It is cheap to
+read, so the kernel log (the journal, or dmesg as a fallback) is only scanned
+for the victim when that counter changes.
+"""
+
+import json
+import re
+import subprocess
+from dataclasses import dataclass
+from datetime import UTC, datetime
+from pathlib import Path
+
+from procclean.i18n import t
+
+VMSTAT_PATH = Path("/proc/vmstat")
+OOM_LOG_TIMEOUT = 5  # Seconds per journalctl/dmesg call
+OOM_LOG_WINDOW = "-24h"  # How far back the journal is searched
+
+# Both the global and the memory cgroup OOM killer log this line
+_KILLED_RE = re.compile(r"Killed process (\d+) \(([^)]*)\)")
+
+
+@dataclass(frozen=True)
+class OomKill:
+    """A process the kernel OOM killer terminated."""
+
+    pid: int
+    name: str
+    time: datetime
+
+
+@dataclass(frozen=True)
+class OomStatus:
+    """OOM kills known at one check."""
+
+    count: int | None = None  # Since boot; None if /proc/vmstat is unreadable
+    last: OomKill | None = None
+    new: int = 0  # Since the previous check of the same tracker
+
+
+def read_oom_count(path: Path | None = None) -> int | None:
+    """Read the number of OOM kills since boot.
+
+    Args:
+        path: vmstat file; defaults to ``/proc/vmstat``.
+
+    Returns:
+        int | None: The ``oom_kill`` counter, or None if unavailable.
+    """
+    try:
+        text = (path or VMSTAT_PATH).read_text(encoding="utf-8")
+    except OSError:
+        return None
+    for line in text.splitlines():
+        key, _, value = line.partition(" ")
+        if key == "oom_kill":
+            return int(value)
+    return None
+
+
+def _run(argv: list[str]) -> str | None:
+    try:
+        result = subprocess.run(
+            argv,
+            capture_output=True,
+            text=True,
+            timeout=OOM_LOG_TIMEOUT,
+            check=False,
+        )
+    except (OSError, subprocess.SubprocessError):
+        return None
+    return result.stdout if result.returncode == 0 else None
+
+
+def _from_journal() -> OomKill | None:
+    """Find the latest OOM kill in the kernel journal.
+
+    Returns:
+        OomKill | None: The newest kill within ``OOM_LOG_WINDOW``.
+    """
+    out = _run([
+        "journalctl",
+        "--dmesg",
+        "--output=json",
+        "--no-pager",
+        f"--since={OOM_LOG_WINDOW}",
+    ])
+    for line in reversed((out or "").splitlines()):
+        try:
+            entry = json.loads(line)
+        except json.JSONDecodeError:
+            continue
+        message = entry.get("MESSAGE")
+        if isinstance(message, str) and (match := _KILLED_RE.search(message)):
+            micros = int(entry["__REALTIME_TIMESTAMP"])
+            when = datetime.fromtimestamp(micros / 1_000_000, tz=UTC)
+            return OomKill(int(match[1]), match[2], when)
+    return None
+
+
+def _from_dmesg() -> OomKill | None:
+    """Find the latest OOM kill in the kernel ring buffer.
+
+    Returns:
+        OomKill | None: The newest kill, if dmesg is readable.
+    """
+    out = _run(["dmesg", "--time-format", "iso"])
+    for line in reversed((out or "").splitlines()):
+        stamp, _, message = line.partition(" ")
+        if match := _KILLED_RE.search(message):
+            try:
+                when = datetime.fromisoformat(stamp)
+            except ValueError:
+                continue
+            return OomKill(int(match[1]), match[2], when)
+    return None
+
+
+def last_oom_kill() -> OomKill | None:
+    """Find the most recent OOM kill in the kernel log.
+
+    Returns:
+        OomKill | None: The newest kill, or None if there is none or the log
+        cannot be read.
+    """
+    return _from_journal() or _from_dmesg()
+
+
+class OomTracker:
+    """Follow OOM kills across periodic checks."""
+
+    def __init__(self) -> None:
+        """Start with nothing seen."""
+        self.checked = False
+        self.count: int | None = None
+        self.last: OomKill | None = None
+
+    def check(self) -> OomStatus:
+        """Read the counter and, if it moved, look up the latest victim.
+
+        Returns:
+            OomStatus: Kills since boot, the latest one and the number of new
+            kills since the previous check.
+        """
+        count = read_oom_count()
+        new = 0
+        if self.checked and count is not None and self.count is not None:
+            new = max(0, count - self.count)
+        if (not self.checked or count != self.count) and count != 0:
+            self.last = last_oom_kill()
+        self.checked, self.count = True, count
+        return OomStatus(count, self.last, new)
+
+
+def _ago(seconds: float) -> str:
+    """Render a duration compactly, e.g. ``40s``, ``5m``, ``3h``, ``2d``.
+
+    Returns:
+        str: The largest whole unit.
+    """
+    for unit, size in (("d", 86400), ("h", 3600), ("m", 60)):
+        if seconds >= size:
+            return f"{int(seconds // size)}{unit}"
+    return f"{max(0, int(seconds))}s"
+
+
+def describe_oom(status: OomStatus, now: datetime | None = None) -> str | None:
+    """Summarize OOM kills for headers and reports.
+
+    Args:
+        status: Result of a check.
+        now: Reference time; defaults to the current time.
+
+    Returns:
+        str | None: E.g. "Kernel OOM-killed node (PID 812) 5m ago", a count
+        since boot if the log is unreadable, or None without any kills.
+    """
+    if status.last:
+        elapsed = ((now or datetime.now(tz=UTC)) - status.last.time).total_seconds()
+        return t(
+            "oom-last",
+            name=status.last.name,
+            pid=status.last.pid,
+            ago=_ago(elapsed),
+        )
+    if status.count:
+        return t("oom-count", count=status.count)
+    return None
+
+
+def oom_status() -> OomStatus:
+    """Check OOM kills once.
+
+    Returns:
+        OomStatus: Kills since boot and the latest one.
+    """
+    return OomTracker().check()
@@ -0,0 +1,134 @@
+"""Opt-in attribution of executables to their owning distro package.
+
+Each executable is looked up with ``dpkg-query -S`` or ``rpm -qf``, whichever
+is installed. Binaries no package owns are hashed so stray local builds can be
+identified. Lookups are cached per path for the lifetime of the process.
+"""
+
+import hashlib
+import shutil
+import subprocess
+from functools import cache
+from pathlib import Path
+
+import psutil
+
+from .models import ProcessInfo
+from .process import DEFAULT_SOURCE
+from .source import ProcessSource
+
+PACKAGE_QUERY_TIMEOUT = 5  # Seconds per package manager query
+
+# Package manager queries, in order of preference
+_QUERIES: tuple[tuple[str, ...], ...] = (
+    ("dpkg-query", "-S"),
+    ("rpm", "-qf", "--queryformat", "%{NAME}\n"),
+)
+
+
+@cache
+def _package_query() -> tuple[str, ...] | None:
+    return next((q for q in _QUERIES if shutil.which(q[0])), None)
+
+
+def has_package_manager() -> bool:
+    """Check if dpkg-query or rpm is available for attribution."""
+    return _package_query() is not None
+
+
+def _query(query: tuple[str, ...], path: str) -> str | None:
+    """Ask the package manager which package owns a path.
+
+    Returns:
+        str | None: The package name, "" if unowned, None if the query failed.
+    """
+    try:
+        result = subprocess.run(
+            [*query, path],
+            capture_output=True,
+            text=True,
+            timeout=PACKAGE_QUERY_TIMEOUT,
+            check=False,
+        )
+    except (OSError, subprocess.SubprocessError):
+        return None
+    lines = result.stdout.strip().splitlines()
+    if result.returncode != 0 or not lines:
+        return ""
+    if query[0] == "dpkg-query":
+        # "pkg[:arch][, pkg2[:arch]]: /path"
+        return lines[0].split(": ", 1)[0].split(",")[0].split(":")[0]
+    return lines[0]
+
+
+@cache
+def package_owner(exe: str) -> str | None:
+    """Find the distro package that installed an executable.
+
+    On merged-/usr systems dpkg may record ``/bin/x`` for ``/usr/bin/x``, so
+    both spellings are tried.
+
+    Args:
+        exe: Absolute executable path.
+
+    Returns:
+        str | None: The package name, "" if no package owns the file, or None
+        if no supported package manager is available.
+    """
+    query = _package_query()
+    if query is None:
+        return None
+    candidates = [exe]
+    if exe.startswith("/usr/"):
+        candidates.append(exe.removeprefix("/usr"))
+    owner = None
+    for path in candidates:
+        owner = _query(query, path)
+        if owner:
+            return owner
+    return owner
+
+
+@cache
+def exe_digest(exe: str) -> str | None:
+    """Hash an executable.
+
+    Returns:
+        str | None: Hex SHA-256 of the file, or None if it cannot be read.
+    """
+    try:
+        with Path(exe).open("rb") as f:
+            return hashlib.file_digest(f, "sha256").hexdigest()
+    except OSError:
+        return None
+
+
+def attach_packages(
+    procs: list[ProcessInfo], source: ProcessSource | None = None
+) -> list[ProcessInfo]:
+    """Fill ``package`` on each process, and ``exe_sha256`` if unpackaged.
+
+    Processes whose executable is unknown, unreadable or deleted are left
+    unattributed.
+
+    Args:
+        procs: Processes to annotate in place.
+        source: Process backend used to resolve executables.
+
+    Returns:
+        list[ProcessInfo]: The same processes, for chaining.
+    """
+    source = source or DEFAULT_SOURCE
+    for proc in procs:
+        if proc.exe_deleted:
+            continue
+        try:
+            exe = source.exe(proc.pid)
+        except (psutil.NoSuchProcess, psutil.AccessDenied):
+            continue
+        if not exe:
+            continue
+        proc.package = package_owner(exe)
+        if proc.is_unpackaged:
+            proc.exe_sha256 = exe_digest(exe)
+    return procs
@@ -0,0 +1,134 @@
+"""External subcommands: ``procclean-*`` executables on ``PATH``.
+
+Like ``git`` and ``cargo``, ``procclean foo ARGS`` runs ``procclean-foo ARGS``
+when ``foo`` is not a built-in command, so new commands need no change to
+procclean. The plugin inherits the terminal and gets its context from the
+environment:
+
+- ``PROCCLEAN_SNAPSHOT``: path of a JSON file with a scan of all processes,
+  in the format of the snapshot cache (``{"timestamp", "processes"}``); it
+  is removed when the plugin exits
+- ``PROCCLEAN_FORMAT``: the output format asked for with ``-f``/``--format``
+  among the plugin's arguments, ``table`` by default
+- ``PROCCLEAN_CONFIG``: the config file procclean reads
+- ``PROCCLEAN_VERSION``: the version of procclean
+
+The exit status of the plugin is the exit status of procclean.
+"""
+
+import os
+import subprocess
+import tempfile
+from dataclasses import dataclass
+from importlib.metadata import version
+from pathlib import Path
+
+from .cache import encode_snapshot
+from .config import CONFIG_ENV, config_path
+from .errors import ErrorCode, ProccleanError
+from .process import ALL_USERS, get_process_list
+from .source import ProcessSource
+
+PLUGIN_PREFIX = "procclean-"
+SNAPSHOT_ENV = "PROCCLEAN_SNAPSHOT"
+FORMAT_ENV = "PROCCLEAN_FORMAT"
+VERSION_ENV = "PROCCLEAN_VERSION"
+
+
+@dataclass(frozen=True, slots=True)
+class Plugin:
+    """An external subcommand."""
+
+    name: str  # Command name, without the prefix
+    path: Path
+
+
+def _is_executable(path: Path) -> bool:
+    return path.is_file() and os.access(path, os.X_OK)
+
+
+def find_plugins(search_path: str | None = None) -> list[Plugin]:
+    """List the plugins on the search path.
+
+    Args:
+        search_path: Directories separated by ``os.pathsep``; defaults to
+            ``PATH``.
+
+    Returns:
+        list[Plugin]: Plugins sorted by name; when a name is found twice,
+        the first directory wins, as it does for the shell.
+    """
+    if search_path is None:
+        search_path = os.environ.get("PATH", os.defpath)
+    found: dict[str, Plugin] = {}
+    for directory in filter(None, search_path.split(os.pathsep)):
+        try:
+            entries = sorted(Path(directory).glob(f"{PLUGIN_PREFIX}*"))
+        except OSError:
+            continue
+        for path in entries:
+            name = path.name.removeprefix(PLUGIN_PREFIX)
+            if name and name not in found and _is_executable(path):
+                found[name] = Plugin(name, path)
+    return sorted(found.values(), key=lambda plugin: plugin.name)
+
+
+def find_plugin(name: str, search_path: str | None = None) -> Plugin | None:
+    """Look up one plugin.
+
+    Args:
+        name: Command name, without the prefix.
+        search_path: Directories separated by ``os.pathsep``; defaults to
+            ``PATH``.
+
+    Returns:
+        Plugin | None: The plugin, or None if no executable has the name.
+    """
+    if not name or os.sep in name:
+        return None
+    return next((p for p in find_plugins(search_path) if p.name == name), None)
+
+
+def run_plugin(
+    plugin: Plugin,
+    args: list[str],
+    fmt: str = "table",
+    source: ProcessSource | None = None,
+) -> int:
+    """Run a plugin with a fresh snapshot of all processes.
+
+    Args:
+        plugin: The plugin to run.
+        args: Its arguments.
+        fmt: Output format passed in ``PROCCLEAN_FORMAT``.
+        source: Process backend for the snapshot; defaults to the live
+            system.
+
+    Returns:
+        int: Exit status of the plugin.
+
+    Raises:
+        ProccleanError: With ``PLUGIN_FAILED`` if the plugin cannot start.
+    """
+    procs = get_process_list(filter_user=ALL_USERS, min_memory_mb=0, source=source)
+    fd, snapshot = tempfile.mkstemp(prefix=PLUGIN_PREFIX, suffix=".json")
+    try:
+        with os.fdopen(fd, "wb") as f:
+            f.write(encode_snapshot(procs))
+        env = os.environ | {
+            SNAPSHOT_ENV: snapshot,
+            FORMAT_ENV: fmt,
+            CONFIG_ENV: str(config_path()),
+            VERSION_ENV: version("procclean"),
+        }
+        try:
+            return subprocess.run(
+                [str(plugin.path), *args], env=env, check=False
+            ).returncode
+        except OSError as e:
+            msg = f"Cannot run plugin {plugin.name} ({plugin.path}): {e}"
+            raise ProccleanError(
+                ErrorCode.PLUGIN_FAILED, msg, plugin=plugin.name
+            ) from e
+    finally:
+        Path(snapshot).unlink(missing_ok=True)
@@ -0,0 +1,213 @@
+"""Privileged kills through a polkit-authorized helper.
+
+With ``--polkit`` (or ``$PROCCLEAN_POLKIT``), a kill the current user is not
+allowed to send is handed to ``procclean-helper`` through ``pkexec``, which
+asks polkit, and so the user, for authorization first. The rest of procclean
+keeps running unprivileged: only the helper, one process per signal, runs as
+root.
+
+The helper trusts nothing but the PID, signal and start time it is sent. It
+looks the process up itself and refuses, whatever the caller asked for:
+
+- signals other than ``HELPER_SIGNALS``;
+- PID 1, kernel threads and system services, including root's
+  ``[kill] protected`` names;
+- a process that started at another time, i.e. a reused PID;
+- SIGKILL for processes that risk data loss.
+
+Kills only escalate for processes signaled through the live system, never
+through a ``--scope`` or a simulation. The polkit action has to be installed
+once, from ``procclean polkit``.
+"""
+
+import json
+import os
+import shutil
+import signal
+import subprocess
+from collections.abc import Callable
+from typing import Any
+
+import psutil
+
+from .dataloss import data_loss_match
+from .errors import ErrorCode, ProccleanError
+from .filters import is_system_service
+from .models import ProcessInfo
+from .source import ProcessSource
+
+POLKIT_ENV = "PROCCLEAN_POLKIT"
+# $PROCCLEAN_POLKIT value that leaves authorization to the session's polkit
+# agent; pkexec's own prompt on the terminal would draw over the TUI
+POLKIT_AGENT_ONLY = "agent"
+POLKIT_ACTION = "com.kjanat.procclean.kill"
+HELPER_NAME = "procclean-helper"
+HELPER_SIGNALS = frozenset({
+    signal.SIGTERM,
+    signal.SIGKILL,
+    signal.SIGINT,
+    signal.SIGHUP,
+    signal.SIGQUIT,
+    signal.SIGSTOP,
+    signal.SIGCONT,
+})
+HELPER_TIMEOUT_SECONDS = 120.0  # Covers typing a password at the prompt
+# pkexec exit statuses: the prompt was dismissed, or authorization refused
+PKEXEC_DISMISSED = 126
+PKEXEC_NOT_AUTHORIZED = 127
+START_TIME_TOLERANCE = 0.01  # Seconds; start times are read in clock ticks
+
+type Runner = Callable[..., subprocess.CompletedProcess[str]]
+
+
+def polkit_enabled() -> bool:
+    """Check whether denied kills go through the polkit helper.
+
+    Returns:
+        bool: True if ``$PROCCLEAN_POLKIT`` is set to anything but "" or "0".
+    """
+    return os.environ.get(POLKIT_ENV, "") not in {"", "0"}
+
+
+def helper_path() -> str | None:
+    """Find the installed helper.
+
+    Returns:
+        str | None: Absolute path of ``procclean-helper``, or None.
+    """
+    return shutil.which(HELPER_NAME)
+
+
+def policy_xml(helper: str) -> str:
+    """Render the polkit action authorizing the helper.
+
+    Active local sessions authenticate as an administrator, and stay
+    authorized for a few minutes; other sessions are refused.
+
+    Args:
+        helper: Absolute path of ``procclean-helper``.
+
+    Returns:
+        str: The contents of ``/usr/share/polkit-1/actions/<action>.policy``.
+    """
+    return f"""\
+<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE policyconfig PUBLIC
+ "-//freedesktop//DTD PolicyKit Policy Configuration 1.0//EN"
+ "http://www.freedesktop.org/standards/PolicyKit/1/policyconfig.dtd">
+<policyconfig>
+  <vendor>procclean</vendor>
+  <vendor_url>https://procclean.kjanat.com</vendor_url>
+  <action id="{POLKIT_ACTION}">
+    <description>Signal processes of other users</description>
+    <message>Authentication is required to signal another user's process</message>
+    <defaults>
+      <allow_any>no</allow_any>
+      <allow_inactive>no</allow_inactive>
+      <allow_active>auth_admin_keep</allow_active>
+    </defaults>
+    <annotate key="org.freedesktop.policykit.exec.path">{helper}</annotate>
+  </action>
+</policyconfig>
+"""
+
+
+def helper_refusal(
+    proc: ProcessInfo | None,
+    sig: signal.Signals,
+    create_time: float,
+    source: ProcessSource | None = None,
+) -> ProccleanError | None:
+    """Apply the helper's policy to one requested signal.
+
+    Args:
+        proc: The live process with the requested PID, or None if gone.
+        sig: Requested signal.
+        create_time: Start time of the process the caller saw.
+        source: Process backend, to resolve executables.
+
+    Returns:
+        ProccleanError | None: Why the signal is refused, or None to send it.
+    """
+    if sig not in HELPER_SIGNALS:
+        allowed = ", ".join(sorted(s.name for s in HELPER_SIGNALS))
+        msg = f"The polkit helper only sends {allowed}, not {sig.name}"
+        return ProccleanError(ErrorCode.PERMISSION_DENIED, msg, signal=sig.name)
+    if proc is None or abs(proc.create_time - create_time) > START_TIME_TOLERANCE:
+        msg = "Process not found (it exited, or its PID was reused)"
+        return ProccleanError(ErrorCode.PROCESS_NOT_FOUND, msg)
+    if proc.pid == 1 or is_system_service(proc, source):
+        msg = f"The polkit helper never signals system services ({proc.name})"
+        return ProccleanError(ErrorCode.PERMISSION_DENIED, msg, pid=proc.pid)
+    if sig == signal.SIGKILL and (pattern := data_loss_match(proc)):
+        msg = f"The polkit helper never force kills {proc.name}: matches {pattern}"
+        return ProccleanError(ErrorCode.PERMISSION_DENIED, msg, pid=proc.pid)
+    return None
+
+
+def _request(pid: int, sig: signal.Signals) -> dict[str, Any]:
+    """Build the helper's request, with the start time the PID has now.
+
+    Returns:
+        dict[str, Any]: PID, signal name and start time.
+
+    Raises:
+        ProccleanError: With ``PROCESS_NOT_FOUND`` if the process is gone.
+    """
+    try:
+        create_time = psutil.Process(pid).create_time()
+    except psutil.NoSuchProcess as e:
+        msg = f"Process {pid} not found"
+        raise ProccleanError(ErrorCode.PROCESS_NOT_FOUND, msg, pid=pid) from e
+    return {"pid": pid, "signal": sig.name, "create_time": create_time}
+
+
+def signal_via_helper(
+    pid: int, sig: signal.Signals, runner: Runner = subprocess.run
+) -> None:
+    """Signal a process through the helper, after polkit authorization.
+
+    Args:
+        pid: Process ID.
+        sig: Signal to send.
+        runner: Runs pkexec; defaults to ``subprocess.run``.
+
+    Raises:
+        ProccleanError: With ``PERMISSION_DENIED`` without pkexec or the
+            helper, or when authorization or the helper's policy refuses;
+            the helper's code when the signal fails; ``KILL_FAILED`` when
+            the helper does not answer.
+    """
+    pkexec, helper = shutil.which("pkexec"), helper_path()
+    if pkexec is None or helper is None:
+        missing = "pkexec" if pkexec is None else HELPER_NAME
+        msg = f"Cannot signal PID {pid} through polkit: {missing} is not installed"
+        raise ProccleanError(ErrorCode.PERMISSION_DENIED, msg, pid=pid)
+    command = [pkexec, helper]
+    if os.environ.get(POLKIT_ENV) == POLKIT_AGENT_ONLY:
+        command.insert(1, "--disable-internal-agent")
+    try:
+        done = runner(
+            command,
+            input=json.dumps(_request(pid, sig)),
+            capture_output=True,
+            text=True,
+            timeout=HELPER_TIMEOUT_SECONDS,
+            check=False,
+        )
+    except (OSError, subprocess.TimeoutExpired) as e:
+        msg = f"Cannot signal PID {pid} through polkit: {e}"
+        raise ProccleanError(ErrorCode.KILL_FAILED, msg, pid=pid) from e
+    if done.returncode in {PKEXEC_DISMISSED, PKEXEC_NOT_AUTHORIZED}:
+        msg = f"Not authorized to signal PID {pid} through polkit"
+        raise ProccleanError(ErrorCode.PERMISSION_DENIED, msg, pid=pid)
+    try:
+        response = json.loads(done.stdout)
+        if response["ok"]:
+            return
+        code, message = ErrorCode(response["code"]), response["message"]
+    except (ValueError, KeyError, TypeError) as e:
+        reason = done.stderr.strip() or f"exit status {done.returncode}"
+        msg = f"Cannot signal PID {pid} through polkit: {reason}"
+        raise ProccleanError(ErrorCode.KILL_FAILED, msg, pid=pid) from e
+    raise ProccleanError(code, message, pid=pid)
@@ -0,0 +1,95 @@
+"""Listening ports, for ``--port`` and the ``ports`` column.
+
+"Whatever is holding port 3000" is the usual reason to go looking for a
+process. The sockets are matched from ``/proc/<pid>/fd`` to the
+``/proc/net`` tables (see ``get_listening_ports``), so only processes whose
+descriptors are readable show their ports: your own, or all with root.
+"""
+
+import psutil
+
+from .models import ProcessInfo
+from .process import DEFAULT_SOURCE
+from .source import ProcessSource
+
+MAX_PORT = 65535
+
+
+def parse_port(value: str) -> int:
+    """Parse a port number, e.g. "3000" or ":3000".
+
+    Returns:
+        int: The port.
+
+    Raises:
+        ValueError: If the value is not a number from 1 to 65535.
+    """
+    try:
+        port = int(value.removeprefix(":"))
+    except ValueError:
+        port = 0
+    if not 1 <= port <= MAX_PORT:
+        msg = f"invalid port: {value!r} (expected 1-{MAX_PORT})"
+        raise ValueError(msg)
+    return port
+
+
+def socket_port(socket: str) -> int | None:
+    """Get the port of a listening socket, e.g. 8080 for "tcp [::1]:8080".
+
+    Returns:
+        int | None: The port, or None if the socket has none.
+    """
+    try:
+        return int(socket.rpartition(":")[2])
+    except ValueError:
+        return None
+
+
+def port_numbers(sockets: list[str] | None) -> str:
+    """Render the distinct ports of some sockets, e.g. "3000,9229".
+
+    Returns:
+        str: Ports in ascending order, or "-" if there are none.
+    """
+    ports = {port for s in sockets or () if (port := socket_port(s)) is not None}
+    return ",".join(str(port) for port in sorted(ports)) or "-"
+
+
+def attach_ports(
+    procs: list[ProcessInfo], source: ProcessSource | None = None
+) -> list[ProcessInfo]:
+    """Fill ``ports`` on processes with listening sockets.
+
+    Args:
+        procs: Processes to annotate in place.
+        source: Process backend used to read sockets.
+
+    Returns:
+        list[ProcessInfo]: The same processes, for chaining.
+    """
+    source = source or DEFAULT_SOURCE
+    for proc in procs:
+        try:
+            sockets = source.listening_ports(proc.pid)
+        except (psutil.NoSuchProcess, psutil.AccessDenied):
+            sockets = ()
+        proc.ports = list(sockets) or None
+    return procs
+
+
+def filter_by_port(
+    procs: list[ProcessInfo], port: int, source: ProcessSource | None = None
+) -> list[ProcessInfo]:
+    """Filter processes by a port they listen on, over TCP or UDP.
+
+    Args:
+        procs: Processes to filter; ``ports`` is attached as needed.
+        port: Port number.
+        source: Process backend used to read sockets.
+
+    Returns:
+        list[ProcessInfo]: Processes with a socket bound to that port.
+    """
+    attach_ports([p for p in procs if p.ports is None], source)
+    return [p for p in procs if any(socket_port(s) == port for s in p.ports or ())]
@@ -0,0 +1,262 @@
+"""Filter presets defined in the config file.
+
+Every table under ``[presets]`` becomes a filter preset, usable as
+``--filter NAME`` and as a TUI view::
+
+    [presets.project-node]
+    description = "Node processes of my projects over 200 MB"
+    name = "node*"
+    cwd = "~/projects"
+    min_memory = 200
+
+A preset keeps the processes that pass all of its rules:
+
+- ``filter``: a built-in or script preset, applied first
+- ``orphan``, ``stale``: true keeps only orphans, or only processes running
+  a deleted executable
+- ``user``: owned by a user, by name or UID
+- ``cwd``: working directory under a path, or matching a glob
+- ``name``: name containing the pattern, or matching it if it is a glob
+- ``cmdline``: regular expression found in the command line
+- ``min_memory``, ``max_memory``: bounds of the resident memory in MB
+
+The TUI filter panel composes these rules and ``save_preset`` writes them
+back as a table.
+"""
+
+import re
+from contextlib import suppress
+
+from collections.abc import Callable, Iterable, Mapping
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Any
+
+from procclean.i18n import t
+
+from .config import config_path, load_config
+from .errors import ErrorCode, ProccleanError
+from .filters import (
+    MatchMode,
+    NameMatcher,
+    filter_by_cmdline,
+    filter_by_cwd,
+    filter_by_user,
+    filter_orphans,
+    filter_stale,
+)
+from .models import ProcessInfo
+from .registry import FILTERS, FilterRegistry, FilterSpec
+from .settings import save_config, set_table
+from .source import ProcessSource
+
+PRESET_RULES = (
+    "filter",
+    "orphan",
+    "stale",
+    "user",
+    "cwd",
+    "name",
+    "cmdline",
+    "min_memory",
+    "max_memory",
+)
+# Names save_preset writes; TOML takes them as bare keys
+PRESET_NAME = re.compile(r"[A-Za-z0-9_-]+")
+
+
+@dataclass(frozen=True, slots=True)
+class ConfigPreset:
+    """The rules of a ``[presets.NAME]`` table; applies them when called."""
+
+    registry: FilterRegistry
+    base: str | None = None  # Preset applied first
+    orphan: bool = False
+    stale: bool = False
+    user: str | None = None
+    cwd: str | None = None
+    name: NameMatcher | None = None
+    cmdline: str | None = None
+    min_memory: float | None = None
+    max_memory: float | None = None
+
+    def __call__(
+        self,
+        procs: list[ProcessInfo],
+        source: ProcessSource | None,
+        _params: Mapping[str, float],
+    ) -> list[ProcessInfo]:
+        """Apply the rules.
+
+        Returns:
+            list[ProcessInfo]: The processes passing all of them.
+        """
+        if self.base is not None:
+            procs = self.registry.get(self.base).run(procs, source)
+        if self.orphan:
+            procs = filter_orphans(procs)
+        if self.stale:
+            procs = filter_stale(procs)
+        if self.user is not None:
+            procs = filter_by_user(procs, self.user)
+        if self.cwd is not None:
+            procs = filter_by_cwd(procs, self.cwd)
+        if self.name is not None:
+            procs = self.name.filter(procs)
+        if self.cmdline is not None:
+            procs = filter_by_cmdline(procs, self.cmdline)
+        low = self.min_memory if self.min_memory is not None else 0.0
+        high = self.max_memory if self.max_memory is not None else float("inf")
+        return [p for p in procs if low <= p.rss_mb <= high]
+
+
+def _invalid(name: str, key: str, problem: str) -> ProccleanError:
+    """Build the error for a malformed preset setting.
+
+    Returns:
+        ProccleanError: With ``INVALID_CONFIG``.
+    """
+    msg = f"Invalid presets.{name}.{key}: {problem}"
+    return ProccleanError(ErrorCode.INVALID_CONFIG, msg, key=f"presets.{name}.{key}")
+
+
+def _text(name: str, table: dict[str, Any], key: str) -> str | None:
+    value = table.get(key)
+    if value is not None and (not isinstance(value, str) or not value):
+        raise _invalid(name, key, "expected a non-empty string")
+    return value
+
+
+def _flag(name: str, table: dict[str, Any], key: str) -> bool:
+    value = table.get(key, False)
+    if not isinstance(value, bool):
+        raise _invalid(name, key, "expected true or false")
+    return value
+
+
+def _megabytes(name: str, table: dict[str, Any], key: str) -> float | None:
+    value = table.get(key)
+    if value is None:
+        return None
+    if isinstance(value, bool) or not isinstance(value, int | float) or value < 0:
+        raise _invalid(name, key, "expected a non-negative number of MB")
+    return float(value)
+
+
+def build_preset(
+    name: str, table: object, registry: FilterRegistry = FILTERS
+) -> FilterSpec:
+    """Build the preset of one ``[presets.NAME]`` table, without registering it.
+
+    Args:
+        name: Name of the preset, and of its TUI view.
+        table: Its rules, as parsed from the config file.
+        registry: Looks up the ``filter`` rule; defaults to ``FILTERS``.
+
+    Returns:
+        FilterSpec: The preset, shown as the TUI view ``NAME``.
+
+    Raises:
+        ProccleanError: With ``INVALID_CONFIG`` for unknown rules, malformed
+            values and unknown base presets.
+    """
+    if not isinstance(table, dict):
+        msg = f"Invalid presets.{name}: expected a table of rules"
+        raise ProccleanError(ErrorCode.INVALID_CONFIG, msg, key=f"presets.{name}")
+    if unknown := sorted(table.keys() - {"description", *PRESET_RULES}):
+        raise _invalid(name, unknown[0], f"choose from {', '.join(PRESET_RULES)}")
+    base = _text(name, table, "filter")
+    if base is not None and (base == name or base not in registry):
+        raise _invalid(name, "filter", f"unknown preset '{base}'")
+    pattern = _text(name, table, "name")
+    matcher = None
+    if pattern is not None:
+        matcher = NameMatcher(pattern, MatchMode.for_pattern(pattern))
+    cmdline = _text(name, table, "cmdline")
+    if cmdline is not None:
+        filter_by_cmdline([], cmdline)  # Reject invalid expressions now
+    cwd = _text(name, table, "cwd")
+    rules = ConfigPreset(
+        registry,
+        base,
+        _flag(name, table, "orphan"),
+        _flag(name, table, "stale"),
+        _text(name, table, "user"),
+        None if cwd is None else str(Path(cwd).expanduser()),
+        matcher,
+        cmdline,
+        _megabytes(name, table, "min_memory"),
+        _megabytes(name, table, "max_memory"),
+    )
+    description = _text(name, table, "description")
+    return FilterSpec(
+        name,
+        description or t("preset-config", path=str(config_path())),
+        rules,
+        view=name,
+    )
+
+
+def load_presets(registry: FilterRegistry = FILTERS) -> list[FilterSpec]:
+    """Register the presets of the config file, replacing earlier ones.
+
+    Call this before building the CLI parser or the TUI, so the presets show
+    up there.
+
+    Args:
+        registry: Receives the presets; defaults to ``FILTERS``.
+
+    Returns:
+        list[FilterSpec]: The registered presets, in file order.
+
+    Raises:
+        ProccleanError: With ``INVALID_CONFIG`` for malformed presets and
+            names that are already taken.
+    """
+    for spec in registry:
+        if isinstance(spec.apply, ConfigPreset):
+            registry.unregister(spec.name)
+    tables = load_config().get("presets", {})
+    if not isinstance(tables, dict):
+        msg = "Invalid presets: expected [presets.NAME] tables"
+        raise ProccleanError(ErrorCode.INVALID_CONFIG, msg, key="presets")
+    specs = [build_preset(name, table, registry) for name, table in tables.items()]
+    for spec in specs:
+        try:
+            registry.register(spec)
+        except ValueError as e:
+            msg = f"Invalid presets.{spec.name}: {e}"
+            raise ProccleanError(
+                ErrorCode.INVALID_CONFIG, msg, key=f"presets.{spec.name}"
+            ) from e
+    return specs
+
+
+def save_preset(
+    name: str,
+    table: dict[str, Any],
+    validators: Iterable[Callable[[], object]] = (load_presets,),
+) -> list[ProccleanError]:
+    """Write a ``[presets.NAME]`` table, replacing one of the same name.
+
+    Presets are registered again afterwards, from the saved file or, if it
+    was not saved, the file as it was.
+
+    Args:
+        name: Name of the preset; letters, digits, "-" and "_".
+        table: Its rules, e.g. ``{"name": "node*", "orphan": True}``.
+        validators: Loaders of settings, as for ``config_problems``.
+
+    Returns:
+        list[ProccleanError]: What is wrong with the name or the resulting
+        file, which was not saved; empty once saved.
+    """
+    if not PRESET_NAME.fullmatch(name):
+        msg = f"Invalid preset name '{name}': use letters, digits, - and _"
+        return [ProccleanError(ErrorCode.INVALID_CONFIG, msg, key=f"presets.{name}")]
+    path = config_path()
+    text = path.read_text(encoding="utf-8") if path.is_file() else ""
+    problems = save_config(set_table(text, f"presets.{name}", table), validators)
+    with suppress(ProccleanError):
+        load_presets()
+    return problems
@@ -1,11 +1,88 @@
 """Process listing and grouping utilities."""
 
+import contextlib
+import errno
+import ipaddress
+import json
 import os
+import pwd
+import re
+import signal
+import socket
+import subprocess
+import sys
+import time
+from collections.abc import Callable, Iterator
 from pathlib import Path
+from typing import Any
 
 import psutil
 
+from .cache import (
+    cache_max_age,
+    decode_snapshot,
+    encode_snapshot,
+    load_snapshot,
+    save_snapshot,
+)
+from .constants import (
+    CPU_SAMPLE_SECONDS,
+    MEDIA_DEVICE_PREFIXES,
+    SCHED_POLICIES,
+    SECCOMP_MODES,
+)
+from .duplicates import GroupBy, command_key, name_key
+from .errors import ErrorCode, ProccleanError
+from .fields import ALL_FIELDS, FieldMask
 from .models import ProcessInfo
+from .sandbox import hardening_enabled, run_sandboxed
+from .source import ProcessSource
+
+PROC_ROOT = Path("/proc")
+STOP_COMMAND_TIMEOUT = 30  # Seconds to wait for a supervisor stop command
+ALL_USERS = "*"  # filter_user value that lists every user's processes
+PF_KTHREAD = 0x00200000  # Per-process flag of kernel threads, in linux/sched.h
+DELETED_SUFFIX = " (deleted)"  # Appended by the kernel to unlinked mappings
+SHARED_LIBRARY_RE = re.compile(r"\.so(\.[\d.]+)?$")
+SOCKET_INODE_RE = re.compile(r"^socket:\[(\d+)\]$")  # fd link of a socket
+TCP_LISTEN = "0A"  # st column of /proc/net/tcp for listening sockets
+SOCKET_KINDS = {socket.SOCK_STREAM: "tcp", socket.SOCK_DGRAM: "udp"}
+ASCENDING_SORTS = frozenset({"pid", "name", "cwd"})  # Other sort_by go descending
+
+# Lines of /proc/<pid>/smaps_rollup summed into each memory counter
+SMAPS_COUNTERS: dict[str, tuple[str, ...]] = {
+    "rss": ("Rss",),
+    "pss": ("Pss",),
+    "uss": ("Private_Clean", "Private_Dirty"),
+    "shared": ("Shared_Clean", "Shared_Dirty"),
+    "swap": ("Swap",),
+}
+
+# Attributes requested from psutil.process_iter for every process
+PROCESS_ATTRS = (
+    "pid",
+    "name",
+    "cmdline",
+    "ppid",
+    "memory_info",
+    "cpu_percent",
+    "username",
+    "create_time",
+    "status",
+)
+
+
+def require_procfs() -> None:
+    """Ensure the /proc filesystem is available on Linux.
+
+    Other platforms are read through ``PortableSource`` and need no procfs.
+
+    Raises:
+        ProccleanError: With ``NO_PROCFS`` if /proc is not mounted.
+    """
+    if sys.platform == "linux" and not PROC_ROOT.is_dir():
+        msg = f"{PROC_ROOT} is not available; procclean needs a Linux procfs"
+        raise ProccleanError(ErrorCode.NO_PROCFS, msg, path=str(PROC_ROOT))
 
 
 def get_tmux_env(pid: int) -> bool:
@@ -27,6 +104,265 @@ def get_tmux_env(pid: int) -> bool:
     return False
 
 
+def get_environ(pid: int) -> dict[str, str]:
+    """Read a process's environment from /proc.
+
+    Args:
+        pid: Process ID.
+
+    Returns:
+        Environment variables, or an empty dict if they cannot be read.
+    """
+    try:
+        raw = Path(f"/proc/{pid}/environ").read_bytes()
+    except (PermissionError, FileNotFoundError, ProcessLookupError):
+        return {}
+    entries = raw.decode("utf-8", errors="replace").split("\0")
+    return dict(e.split("=", 1) for e in entries if "=" in e)
+
+
+def get_sched(pid: int) -> tuple[int, str, int]:
+    """Read scheduling attributes from /proc/<pid>/stat.
+
+    Args:
+        pid: Process ID.
+
+    Returns:
+        Nice value, policy name (see ``SCHED_POLICIES``) and realtime
+        priority, or ``(0, "other", 0)`` if the stat file cannot be read.
+    """
+    try:
+        stat = Path(f"/proc/{pid}/stat").read_text()
+        # comm may contain spaces and parentheses; fields resume after the
+        # last ")", starting with field 3 (state) of proc(5)
+        fields = stat.rsplit(")", 1)[1].split()
+        nice, rt_priority, policy = int(fields[16]), int(fields[37]), int(fields[38])
+    except (OSError, IndexError, ValueError):
+        return 0, "other", 0
+    return nice, SCHED_POLICIES.get(policy, str(policy)), rt_priority
+
+
+def is_kthread_flagged(pid: int) -> bool:
+    """Check the PF_KTHREAD flag of /proc/<pid>/stat.
+
+    Args:
+        pid: Process ID.
+
+    Returns:
+        bool: True for kernel threads; False if the stat file cannot be read.
+    """
+    try:
+        stat = Path(f"/proc/{pid}/stat").read_text()
+        flags = int(stat.rsplit(")", 1)[1].split()[6])  # Field 9 of proc(5)
+    except (OSError, IndexError, ValueError):
+        return False
+    return bool(flags & PF_KTHREAD)
+
+
+def read_status(pid: int) -> dict[str, str]:
+    """Read /proc/<pid>/status as a field to value mapping.
+
+    Returns:
+        dict[str, str]: Raw field values; empty if unreadable.
+    """
+    try:
+        lines = Path(f"/proc/{pid}/status").read_text().splitlines()
+    except OSError:
+        return {}
+    return dict(line.split(":", 1) for line in lines if ":" in line)
+
+
+def get_privileges(pid: int) -> tuple[str, ...]:
+    """Check a process for root, effective capabilities and setuid/setgid.
+
+    Reads the ``Uid``, ``Gid`` and ``CapEff`` lines of /proc/<pid>/status.
+
+    Args:
+        pid: Process ID.
+
+    Returns:
+        Any of "root" (effective UID 0), "caps" (non-empty effective
+        capability set) and "setuid" (effective IDs differ from the real
+        ones), or an empty tuple if unprivileged or unreadable.
+    """
+    status = read_status(pid)
+    try:
+        uid = status["Uid"].split()
+        gid = status["Gid"].split()
+        cap_eff = int(status["CapEff"], 16)
+    except (KeyError, ValueError):
+        return ()
+    reasons = []
+    if uid[1] == "0":
+        reasons.append("root")
+    if cap_eff and uid[1] != "0":
+        reasons.append("caps")
+    if uid[0] != uid[1] or gid[0] != gid[1]:
+        reasons.append("setuid")
+    return tuple(reasons)
+
+
+def get_confinement(pid: int) -> tuple[str, bool]:
+    """Read the seccomp mode and no_new_privs flag of a process.
+
+    Args:
+        pid: Process ID.
+
+    Returns:
+        The seccomp mode ("disabled", "strict" or "filter") and whether
+        no_new_privs is set; ``("disabled", False)`` if unreadable.
+    """
+    status = read_status(pid)
+    try:
+        mode = int(status.get("Seccomp", "0"))
+        no_new_privs = status.get("NoNewPrivs", "0").strip() == "1"
+    except ValueError:
+        return "disabled", False
+    return SECCOMP_MODES.get(mode, str(mode)), no_new_privs
+
+
+def get_media_devices(pid: int) -> tuple[str, ...]:
+    """Find the media devices a process holds open.
+
+    Args:
+        pid: Process ID.
+
+    Returns:
+        The kinds of device open ("audio" for ALSA PCM streams, "camera" for
+        V4L2 devices), sorted; empty if none or the fds are unreadable.
+    """
+    kinds: set[str] = set()
+    try:
+        fds = list(Path(f"/proc/{pid}/fd").iterdir())
+    except (PermissionError, FileNotFoundError, ProcessLookupError):
+        return ()
+    for fd in fds:
+        try:
+            target = str(fd.readlink())
+        except OSError:
+            continue
+        for prefix, kind in MEDIA_DEVICE_PREFIXES.items():
+            if target.startswith(prefix):
+                kinds.add(kind)
+    return tuple(sorted(kinds))
+
+
+def get_open_files(pid: int) -> dict[int, str]:
+    """Read what the file descriptors of a process point to.
+
+    Args:
+        pid: Process ID.
+
+    Returns:
+        Targets by descriptor number, in order, e.g. ``/dev/pts/1`` or
+        ``socket:[12345]``; empty if the fds are unreadable.
+    """
+    try:
+        fds = list(Path(f"/proc/{pid}/fd").iterdir())
+    except (PermissionError, FileNotFoundError, ProcessLookupError):
+        return {}
+    files: dict[int, str] = {}
+    for fd in fds:
+        try:
+            files[int(fd.name)] = str(fd.readlink())
+        except (OSError, ValueError):
+            continue
+    return dict(sorted(files.items()))
+
+
+def _host_port(ip: ipaddress.IPv4Address | ipaddress.IPv6Address, port: int) -> str:
+    host = f"[{ip}]" if isinstance(ip, ipaddress.IPv6Address) else str(ip)
+    return f"{host}:{port}"
+
+
+def _socket_address(hex_address: str) -> str:
+    """Decode an address of ``/proc/net/tcp``, e.g. ``0100007F:1F90``.
+
+    Returns:
+        str: The address, e.g. "127.0.0.1:8080" or "[::1]:8080".
+    """
+    host, port = hex_address.split(":")
+    raw = bytes.fromhex(host)
+    if sys.byteorder == "little":  # Each 32-bit word is in host byte order
+        raw = b"".join(raw[i : i + 4][::-1] for i in range(0, len(raw), 4))
+    return _host_port(ipaddress.ip_address(raw), int(port, 16))
+
+
+def get_listening_ports(pid: int) -> tuple[str, ...]:
+    """Find the sockets a process listens on.
+
+    TCP sockets count while listening, UDP sockets once bound.
+
+    Args:
+        pid: Process ID.
+
+    Returns:
+        Protocol and address of each socket, sorted, e.g. "tcp 0.0.0.0:8080";
+        empty if none or unreadable.
+    """
+    inodes = {
+        match[1]
+        for target in get_open_files(pid).values()
+        if (match := SOCKET_INODE_RE.match(target))
+    }
+    if not inodes:
+        return ()
+    ports: set[str] = set()
+    for table in ("tcp", "tcp6", "udp", "udp6"):
+        try:
+            lines = (PROC_ROOT / str(pid) / "net" / table).read_text().splitlines()
+        except OSError:
+            continue
+        for line in lines[1:]:
+            parts = line.split()
+            if len(parts) < 10 or parts[9] not in inodes:  # noqa: PLR2004
+                continue
+            if table.startswith("tcp") and parts[3] != TCP_LISTEN:
+                continue
+            ports.add(f"{table[:3]} {_socket_address(parts[1])}")
+    return tuple(sorted(ports))
+
+
+def parse_smaps_rollup(text: str) -> dict[str, float]:
+    """Parse the contents of ``/proc/<pid>/smaps_rollup``.
+
+    Returns:
+        dict[str, float]: MB by ``SMAPS_COUNTERS`` counter; empty if the text
+        has no Rss line.
+    """
+    kb: dict[str, int] = {}
+    for line in text.splitlines():
+        key, _, value = line.partition(":")
+        parts = value.split()
+        if len(parts) == 2 and parts[1] == "kB":  # noqa: PLR2004
+            kb[key] = int(parts[0])
+    if "Rss" not in kb:
+        return {}
+    return {
+        counter: sum(kb.get(line, 0) for line in lines) / 1024
+        for counter, lines in SMAPS_COUNTERS.items()
+    }
+
+
+def get_smaps_rollup(pid: int) -> dict[str, float]:
+    """Get the RSS, PSS, USS, shared and swapped memory of a process.
+
+    The kernel sums ``/proc/<pid>/smaps`` into one record, so this costs a
+    page table walk of the process but no per-mapping parsing.
+
+    Args:
+        pid: Process ID.
+
+    Returns:
+        dict[str, float]: MB by counter; empty if unreadable.
+    """
+    try:
+        text = (PROC_ROOT / str(pid) / "smaps_rollup").read_text()
+    except OSError:
+        return {}
+    return parse_smaps_rollup(text)
+
+
 def get_cwd(pid: int) -> str:
     """Get process working directory.
 
@@ -64,40 +400,743 @@ def is_exe_deleted(pid: int) -> bool:
         return False
 
 
-def get_process_list(
-    sort_by: str = "memory",
-    filter_user: str | None = None,
-    min_memory_mb: float = 10.0,
-) -> list[ProcessInfo]:
-    """Get list of processes with detailed info.
+def is_cwd_deleted(pid: int) -> bool:
+    """Check if the process working directory has been removed.
+
+    Typical after ``rm -rf``-ing a project while its dev server keeps running;
+    the cwd symlink then shows a "(deleted)" suffix.
 
     Args:
-        sort_by: Field to sort by ("memory", "cpu", or "name").
-        filter_user: Only include processes owned by this user. Defaults to the
-            current user.
-        min_memory_mb: Minimum RSS (in MB) for a process to be included.
+        pid: Process ID.
 
     Returns:
-        A list of ProcessInfo entries matching the filters, sorted by ``sort_by``.
+        True if the working directory no longer exists, False otherwise.
+    """
+    try:
+        cwd_link = Path(f"/proc/{pid}/cwd").readlink()
+        return str(cwd_link).endswith("(deleted)")
+    except (PermissionError, FileNotFoundError, ProcessLookupError):
+        return False
+
+
+def get_deleted_libraries(pid: int) -> tuple[str, ...]:
+    """Find shared libraries a process still maps after they were replaced.
+
+    A package upgrade replaces ``.so`` files on disk, but running processes
+    keep the old copies mapped; ``/proc/<pid>/maps`` then lists them with a
+    "(deleted)" suffix. Such processes need a restart to pick up the update.
+
+    Args:
+        pid: Process ID.
+
+    Returns:
+        The deleted library paths, sorted; empty if none or unreadable.
+    """
+    try:
+        maps = Path(f"/proc/{pid}/maps").read_text(encoding="utf-8", errors="replace")
+    except (PermissionError, FileNotFoundError, ProcessLookupError):
+        return ()
+    libs: set[str] = set()
+    for line in maps.splitlines():
+        parts = line.split(maxsplit=5)
+        if len(parts) < 6 or not parts[5].endswith(DELETED_SUFFIX):  # noqa: PLR2004
+            continue
+        path = parts[5].removesuffix(DELETED_SUFFIX)
+        if path.startswith("/") and SHARED_LIBRARY_RE.search(path):
+            libs.add(path)
+    return tuple(sorted(libs))
+
+
+def get_cgroup(pid: int) -> str:
+    """Get the cgroup path of a process.
+
+    Prefers the unified (v2) hierarchy and falls back to the systemd v1 one.
+
+    Args:
+        pid: Process ID.
+
+    Returns:
+        The cgroup path (e.g. ``/system.slice/nginx.service``), or "" if it
+        cannot be read.
+    """
+    try:
+        lines = Path(f"/proc/{pid}/cgroup").read_text().splitlines()
+    except (PermissionError, FileNotFoundError, ProcessLookupError):
+        return ""
+    paths: dict[str, str] = {}
+    for line in lines:
+        controllers, _, path = line.partition(":")[2].partition(":")
+        paths[controllers] = path
+    return paths.get("") or paths.get("name=systemd", "")
+
+
+class PsutilSource:
+    """Process source backed by psutil and the live /proc filesystem."""
+
+    def current_user(self) -> str:  # noqa: PLR6301
+        """Return the login name of the current session.
+
+        Returns:
+            str: Login name from ``os.getlogin``.
+        """
+        return os.getlogin()
+
+    def iter_processes(self) -> Iterator[dict[str, Any]]:  # noqa: PLR6301
+        """Yield info mappings, skipping processes that vanish mid-scan.
+
+        Yields:
+            dict[str, Any]: The ``proc.info`` mapping of a live process.
+        """
+        for proc in psutil.process_iter(list(PROCESS_ATTRS)):
+            try:
+                info = proc.info
+            except (psutil.NoSuchProcess, psutil.AccessDenied):
+                continue
+            yield info
+
+    def sample_cpu(self, interval: float) -> None:  # noqa: PLR6301
+        """Take the first CPU sample of every process, then wait ``interval``.
+
+        psutil keeps the sample with the process objects ``process_iter``
+        caches, so the next scan reports usage over the interval instead of
+        0% for every process.
+        """
+        for proc in psutil.process_iter():
+            with contextlib.suppress(psutil.NoSuchProcess, psutil.AccessDenied):
+                proc.cpu_percent()
+        time.sleep(interval)
+
+    def parent_name(self, ppid: int) -> str:  # noqa: PLR6301
+        """Return the name of the parent process.
+
+        Returns:
+            str: Parent process name.
+        """
+        return psutil.Process(ppid).name()
+
+    def cwd(self, pid: int) -> str:  # noqa: PLR6301
+        """Return the working directory from /proc.
+
+        Returns:
+            str: Working directory, or "?" if unknown.
+        """
+        return get_cwd(pid)
+
+    def in_tmux(self, pid: int) -> bool:  # noqa: PLR6301
+        """Check the process environment for TMUX.
+
+        Returns:
+            bool: True if running inside tmux.
+        """
+        return get_tmux_env(pid)
+
+    def environ(self, pid: int) -> dict[str, str]:  # noqa: PLR6301
+        """Read the process environment.
+
+        Returns:
+            dict[str, str]: Environment, or empty if unreadable.
+        """
+        return get_environ(pid)
+
+    def exe(self, pid: int) -> str:  # noqa: PLR6301
+        """Return the executable path.
+
+        Returns:
+            str: Executable path, or an empty string if unknown.
+        """
+        return psutil.Process(pid).exe() or ""
+
+    def exe_deleted(self, pid: int) -> bool:  # noqa: PLR6301
+        """Check whether the executable was deleted or replaced.
+
+        Returns:
+            bool: True if the executable is stale.
+        """
+        return is_exe_deleted(pid)
+
+    def cwd_deleted(self, pid: int) -> bool:  # noqa: PLR6301
+        """Check whether the working directory was removed.
+
+        Returns:
+            bool: True if the cwd no longer exists.
+        """
+        return is_cwd_deleted(pid)
+
+    def deleted_libraries(self, pid: int) -> tuple[str, ...]:  # noqa: PLR6301
+        """Return replaced shared libraries still mapped, from /proc.
+
+        Returns:
+            tuple[str, ...]: Paths of the deleted libraries.
+        """
+        return get_deleted_libraries(pid)
+
+    def cgroup(self, pid: int) -> str:  # noqa: PLR6301
+        """Return the cgroup path from /proc.
+
+        Returns:
+            str: cgroup path, or "" if unknown.
+        """
+        return get_cgroup(pid)
+
+    def session_id(self, pid: int) -> int:  # noqa: PLR6301
+        """Return the session ID.
+
+        Returns:
+            int: Session ID, or -1 if the process is gone or inaccessible.
+        """
+        try:
+            return os.getsid(pid)
+        except OSError:
+            return -1
+
+    def process_group(self, pid: int) -> int:  # noqa: PLR6301
+        """Return the process group ID.
+
+        Returns:
+            int: Process group ID, or -1 if the process is gone.
+        """
+        try:
+            return os.getpgid(pid)
+        except OSError:
+            return -1
+
+    def kernel_thread(self, pid: int) -> bool:  # noqa: PLR6301
+        """Check the PF_KTHREAD flag in /proc/<pid>/stat.
+
+        Returns:
+            bool: True for kernel threads; False if the PID is gone.
+        """
+        return is_kthread_flagged(pid)
+
+    def sched(self, pid: int) -> tuple[int, str, int]:  # noqa: PLR6301
+        """Return scheduling attributes from /proc.
+
+        Returns:
+            tuple[int, str, int]: Nice value, policy and realtime priority.
+        """
+        return get_sched(pid)
+
+    def privileges(self, pid: int) -> tuple[str, ...]:  # noqa: PLR6301
+        """Return privilege reasons from /proc.
+
+        Returns:
+            tuple[str, ...]: Reasons the process is privileged.
+        """
+        return get_privileges(pid)
+
+    def confinement(self, pid: int) -> tuple[str, bool]:  # noqa: PLR6301
+        """Return the seccomp mode and no_new_privs flag from /proc.
+
+        Returns:
+            tuple[str, bool]: Seccomp mode and no_new_privs.
+        """
+        return get_confinement(pid)
+
+    def media_devices(self, pid: int) -> tuple[str, ...]:  # noqa: PLR6301
+        """Return the kinds of media device open, from /proc/<pid>/fd.
+
+        Returns:
+            tuple[str, ...]: Any of "audio" and "camera".
+        """
+        return get_media_devices(pid)
+
+    def open_files(self, pid: int) -> dict[int, str]:  # noqa: PLR6301
+        """Return the targets of the file descriptors, from /proc/<pid>/fd.
+
+        Returns:
+            dict[int, str]: Targets by descriptor number.
+        """
+        return get_open_files(pid)
+
+    def listening_ports(self, pid: int) -> tuple[str, ...]:  # noqa: PLR6301
+        """Return the listening sockets, from /proc/<pid>/net.
+
+        Returns:
+            tuple[str, ...]: E.g. "tcp 0.0.0.0:8080".
+        """
+        return get_listening_ports(pid)
+
+    def memory_breakdown(self, pid: int) -> dict[str, float]:  # noqa: PLR6301
+        """Return the memory counters of ``/proc/<pid>/smaps_rollup``.
+
+        Kernels before 4.14 have no smaps_rollup; psutil's
+        ``memory_full_info`` stands in there, and on other platforms.
+
+        Returns:
+            dict[str, float]: MB by counter, e.g. rss, uss, pss and swap;
+            empty if unreadable.
+        """
+        if rollup := get_smaps_rollup(pid):
+            return rollup
+        return _read_process(
+            pid,
+            lambda p: {
+                name: value / 1024 / 1024
+                for name, value in p.memory_full_info()._asdict().items()
+            },
+            {},
+        )
+
+    def run_stop_command(self, command: list[str]) -> None:  # noqa: PLR6301
+        """Run a supervisor stop command, raising on a non-zero exit."""
+        subprocess.run(
+            command,
+            check=True,
+            capture_output=True,
+            text=True,
+            timeout=STOP_COMMAND_TIMEOUT,
+        )
+
+    def spawn(  # noqa: PLR6301
+        self, argv: list[str], cwd: str, env: dict[str, str], user: str
+    ) -> int:
+        """Start a command in a new session, with output discarded.
+
+        As root, the command runs as ``user`` with that user's groups, so a
+        respawn never gains privileges the original process did not have.
+
+        Returns:
+            int: PID of the new process.
+
+        Raises:
+            OSError: If the command cannot be started or the user is unknown.
+        """
+        ids: dict[str, Any] = {}
+        if os.geteuid() == 0 and user != "root":
+            try:
+                pw = pwd.getpwnam(user)
+            except KeyError as e:
+                msg = f"unknown user {user!r}"
+                raise OSError(msg) from e
+            ids = {
+                "user": pw.pw_uid,
+                "group": pw.pw_gid,
+                "extra_groups": os.getgrouplist(user, pw.pw_gid),
+            }
+        proc = subprocess.Popen(
+            argv,
+            cwd=cwd,
+            env=env,
+            stdin=subprocess.DEVNULL,
+            stdout=subprocess.DEVNULL,
+            stderr=subprocess.DEVNULL,
+            start_new_session=True,
+            **ids,
+        )
+        return proc.pid
+
+    def alive(self, pid: int) -> bool:  # noqa: PLR6301
+        """Check whether a process still runs; zombies count as exited.
+
+        Returns:
+            bool: True if the process exists and is not a zombie.
+        """
+        try:
+            return psutil.Process(pid).status() != psutil.STATUS_ZOMBIE
+        except psutil.NoSuchProcess:
+            return False
+        except psutil.AccessDenied:
+            return True
+
+    def send_signal(self, pid: int, force: bool) -> None:  # noqa: PLR6301
+        """Send SIGTERM, or SIGKILL when ``force`` is set."""
+        proc = psutil.Process(pid)
+        if force:
+            proc.kill()
+        else:
+            proc.terminate()
+
+    def send(self, pid: int, sig: signal.Signals) -> None:  # noqa: PLR6301
+        """Send any signal."""
+        psutil.Process(pid).send_signal(sig)
+
+    def signal_group(self, pgid: int, sig: signal.Signals) -> None:  # noqa: PLR6301
+        """Send a signal to a process group, like ``kill -SIG -PGID``.
+
+        Raises:
+            psutil.NoSuchProcess: If the group has no processes left.
+            psutil.AccessDenied: If any member may not be signaled.
+        """
+        try:
+            os.killpg(pgid, sig)
+        except ProcessLookupError as e:
+            raise psutil.NoSuchProcess(pgid) from e
+        except PermissionError as e:
+            raise psutil.AccessDenied(pgid) from e
+
+    def renice(self, pid: int, nice: int) -> None:  # noqa: PLR6301
+        """Set a process's nice value."""
+        psutil.Process(pid).nice(nice)
+
+    def ionice(self, pid: int, ioclass: int, level: int | None) -> None:  # noqa: PLR6301
+        """Set a process's I/O scheduling class and level."""
+        psutil.Process(pid).ionice(ioclass, level)
+
+
+def _read_process[T](pid: int, read: Callable[[psutil.Process], T], default: T) -> T:
+    """Read a process attribute through psutil.
+
+    Returns:
+        T: The attribute, or ``default`` if the process is gone or
+        inaccessible.
+    """
+    try:
+        return read(psutil.Process(pid))
+    except (psutil.Error, OSError):
+        return default
+
+
+class PortableSource(PsutilSource):
+    """Process source for platforms without /proc, such as macOS and Windows.
+
+    psutil resolves the working directory, environment and executable through
+    the native APIs (libproc on macOS, the Win32 process APIs on Windows), so
+    cwd, tmux and stale detection keep working. Linux-only details (cgroups,
+    scheduling policy, seccomp, mapped libraries, open media devices) keep
+    their defaults.
+    """
+
+    def cwd(self, pid: int) -> str:  # noqa: PLR6301
+        """Return the working directory.
+
+        Returns:
+            str: Working directory, or "?" if unknown.
+        """
+        return _read_process(pid, lambda p: p.cwd(), "") or "?"
+
+    def in_tmux(self, pid: int) -> bool:
+        """Check the process environment for TMUX.
+
+        Returns:
+            bool: True if running inside tmux.
+        """
+        return "TMUX" in self.environ(pid)
+
+    def environ(self, pid: int) -> dict[str, str]:  # noqa: PLR6301
+        """Read the process environment.
+
+        Returns:
+            dict[str, str]: Environment, or empty if unreadable.
+        """
+        return _read_process(pid, lambda p: dict(p.environ()), {})
+
+    def exe_deleted(self, pid: int) -> bool:  # noqa: PLR6301
+        """Check whether the executable is gone from disk.
+
+        Returns:
+            bool: True if the executable path no longer exists.
+        """
+        exe = _read_process(pid, lambda p: p.exe(), "")
+        return bool(exe) and not Path(exe).exists()
+
+    def ionice(self, pid: int, ioclass: int, level: int | None) -> None:  # noqa: PLR6301
+        """Refuse I/O priorities, whose classes only exist on Linux.
+
+        Raises:
+            OSError: Always, with ``ENOTSUP``.
+        """
+        msg = "I/O priorities need Linux"
+        raise OSError(errno.ENOTSUP, msg)
+
+    def cwd_deleted(self, pid: int) -> bool:
+        """Check whether the working directory was removed.
+
+        Returns:
+            bool: True if the cwd no longer exists.
+        """
+        cwd = self.cwd(pid)
+        return cwd != "?" and not Path(cwd).is_dir()
+
+    def deleted_libraries(self, pid: int) -> tuple[str, ...]:  # noqa: PLR6301
+        """Return no libraries; mappings are only inspected on Linux.
+
+        Returns:
+            tuple[str, ...]: Always empty.
+        """
+        return ()
+
+    def cgroup(self, pid: int) -> str:  # noqa: PLR6301
+        """Return no cgroup; cgroups only exist on Linux.
+
+        Returns:
+            str: Always "".
+        """
+        return ""
+
+    def session_id(self, pid: int) -> int:
+        """Return the session ID where the platform has sessions.
+
+        Returns:
+            int: Session ID, or -1 if unknown.
+        """
+        return super().session_id(pid) if hasattr(os, "getsid") else -1
+
+    def process_group(self, pid: int) -> int:
+        """Return the process group ID where the platform has groups.
+
+        Returns:
+            int: Process group ID, or -1 if unknown.
+        """
+        return super().process_group(pid) if hasattr(os, "getpgid") else -1
+
+    def signal_group(self, pgid: int, sig: signal.Signals) -> None:
+        """Send a signal to a process group where the platform has groups.
+
+        Raises:
+            psutil.AccessDenied: Without process groups.
+        """
+        if not hasattr(os, "killpg"):
+            raise psutil.AccessDenied(pgid, msg="no process groups")
+        super().signal_group(pgid, sig)
+
+    def kernel_thread(self, pid: int) -> bool:  # noqa: PLR6301
+        """Report no kernel threads, which only Linux lists as processes.
+
+        Returns:
+            bool: Always False.
+        """
+        return False
+
+    def sched(self, pid: int) -> tuple[int, str, int]:  # noqa: PLR6301
+        """Return the nice value with the default policy.
+
+        Returns:
+            tuple[int, str, int]: Nice value, "other" and priority 0.
+        """
+        return int(_read_process(pid, lambda p: p.nice(), 0)), "other", 0
+
+    def privileges(self, pid: int) -> tuple[str, ...]:  # noqa: PLR6301
+        """Check for root and setuid where the platform has UIDs.
+
+        Returns:
+            tuple[str, ...]: Any of "root" and "setuid".
+        """
+        if not hasattr(psutil.Process, "uids"):  # Windows
+            return ()
+        uids = _read_process(pid, lambda p: p.uids(), None)
+        if uids is None:
+            return ()
+        reasons = []
+        if uids.effective == 0:
+            reasons.append("root")
+        if uids.real != uids.effective:
+            reasons.append("setuid")
+        return tuple(reasons)
+
+    def confinement(self, pid: int) -> tuple[str, bool]:  # noqa: PLR6301
+        """Return no confinement; seccomp only exists on Linux.
+
+        Returns:
+            tuple[str, bool]: Always ``("disabled", False)``.
+        """
+        return "disabled", False
+
+    def media_devices(self, pid: int) -> tuple[str, ...]:  # noqa: PLR6301
+        """Return no devices; open files are only inspected on Linux.
+
+        Returns:
+            tuple[str, ...]: Always empty.
+        """
+        return ()
+
+    def open_files(self, pid: int) -> dict[int, str]:  # noqa: PLR6301
+        """Return the regular files the process has open.
+
+        Returns:
+            dict[int, str]: Paths by descriptor number (-1 where unknown).
+        """
+        return _read_process(
+            pid, lambda p: {f.fd: f.path for f in sorted(p.open_files())}, {}
+        )
+
+    def listening_ports(self, pid: int) -> tuple[str, ...]:  # noqa: PLR6301
+        """Return the listening sockets, from psutil.
+
+        Returns:
+            tuple[str, ...]: E.g. "tcp 0.0.0.0:8080".
+        """
+
+        def read(proc: psutil.Process) -> tuple[str, ...]:
+            ports = set()
+            for conn in proc.net_connections("inet"):
+                kind = SOCKET_KINDS.get(conn.type)
+                listening = kind == "udp" or conn.status == psutil.CONN_LISTEN
+                if kind and conn.laddr and listening:
+                    ip = ipaddress.ip_address(conn.laddr.ip)
+                    ports.add(f"{kind} {_host_port(ip, conn.laddr.port)}")
+            return tuple(sorted(ports))
+
+        return _read_process(pid, read, ())
+
+
+# Default backend used when no source is injected
+DEFAULT_SOURCE: ProcessSource = (
+    PsutilSource() if sys.platform == "linux" else PortableSource()
+)
+
+
+def _parent_name(source: ProcessSource, ppid: int) -> str:
+    try:
+        return source.parent_name(ppid)
+    except (psutil.NoSuchProcess, psutil.AccessDenied):
+        return "?"
+
+
+type Lookups = dict[tuple[int, float], dict[str, Any]]  # By PID and start time
+
+
+def _lookup[T](
+    lookups: Lookups | None, key: tuple[int, float], name: str, read: Callable[[], T]
+) -> T:
+    """Read a per-process value once, reusing it while the process lives.
+
+    Returns:
+        T: The cached value, or a fresh read without ``lookups``.
+    """
+    if lookups is None:
+        return read()
+    entry = lookups.setdefault(key, {})
+    if name not in entry:
+        entry[name] = read()
+    return entry[name]
+
+
+def _build_process_info(
+    info: dict[str, Any],
+    source: ProcessSource,
+    fields: FieldMask = ALL_FIELDS,
+    lookups: Lookups | None = None,
+) -> ProcessInfo:
+    """Build a ``ProcessInfo`` from a raw info mapping.
+
+    Fields left out of ``fields`` keep their defaults ("?" for cwd and
+    parent name) without being read.
+
+    Args:
+        info: psutil-style ``proc.info`` mapping.
+        source: Source used for the extra per-process lookups.
+        fields: Optional fields to read.
+        lookups: Cache of the cwd and environment lookups, kept across scans
+            by ``ProcessCollector``.
+
+    Returns:
+        The populated process record.
     """
+    rss_mb = (info["memory_info"].rss / 1024 / 1024) if info["memory_info"] else 0
+    ppid = info["ppid"] or 0
+
+    # Check if orphaned (reparented to PID 1 system init)
+    # Note:
+    #   ppid != 1 with parent "systemd" means user session service, NOT orphan
+    is_orphan = ppid == 1
+
+    pid = info["pid"]
+    key = (pid, info["create_time"] or 0)
+    argv = list(info["cmdline"] or [])
+    cmdline = " ".join(argv)[:200]
+    kernel_thread = source.kernel_thread(pid)  # The same check as the kill guard
+    if kernel_thread:
+        # Shown bracketed like ps does; their cwd, privileges and so on
+        # describe the kernel, not anything the user started
+        cmdline = f"[{info['name']}]"
+        fields &= FieldMask.PARENT
+    elif not cmdline:
+        cmdline = info["name"]
+
+    nice, policy, rt_priority = 0, "other", 0
+    privileges: list[str] = []
+    seccomp, no_new_privs = "disabled", False
+    pgid = sid = -1
+    if FieldMask.GROUP in fields:
+        pgid, sid = source.process_group(pid), source.session_id(pid)
+    try:
+        if FieldMask.SCHED in fields:
+            nice, policy, rt_priority = source.sched(pid)
+        if FieldMask.PRIVILEGES in fields:
+            privileges = list(source.privileges(pid))
+        if FieldMask.CONFINEMENT in fields:
+            seccomp, no_new_privs = source.confinement(pid)
+    except (psutil.NoSuchProcess, psutil.AccessDenied):
+        nice, policy, rt_priority = 0, "other", 0
+        privileges = []
+        seccomp, no_new_privs = "disabled", False
+    return ProcessInfo(
+        pid=pid,
+        name=info["name"],
+        cmdline=cmdline,
+        cwd=(
+            _lookup(lookups, key, "cwd", lambda: source.cwd(pid))
+            if FieldMask.CWD in fields
+            else "?"
+        ),
+        ppid=ppid,
+        parent_name=_parent_name(source, ppid) if FieldMask.PARENT in fields else "?",
+        rss_mb=rss_mb,
+        cpu_percent=info["cpu_percent"] or 0,
+        username=info["username"],
+        create_time=info["create_time"] or 0,
+        is_orphan=is_orphan,
+        in_tmux=is_orphan
+        and FieldMask.TMUX in fields
+        and _lookup(lookups, key, "tmux", lambda: source.in_tmux(pid)),
+        status=info["status"] or "?",
+        exe_deleted=FieldMask.EXE_DELETED in fields and source.exe_deleted(pid),
+        cwd_deleted=FieldMask.CWD_DELETED in fields and source.cwd_deleted(pid),
+        argv=argv,
+        nice=nice,
+        sched_policy=policy,
+        rt_priority=rt_priority,
+        privileges=privileges,
+        seccomp=seccomp,
+        no_new_privs=no_new_privs,
+        pgid=pgid,
+        sid=sid,
+        is_kernel_thread=kernel_thread,
+    )
+
+
+def _scan(
+    source: ProcessSource,
+    user: str,
+    min_memory_mb: float,
+    fields: FieldMask,
+    cpu_interval: float = 0.0,
+    lookups: Lookups | None = None,
+) -> list[ProcessInfo]:
+    args = (source, user, min_memory_mb, fields, cpu_interval, lookups)
+    if not hardening_enabled():
+        return _scan_here(*args)
+    # Lookups the child makes are lost with it, so nothing is reused
+    snapshot = run_sandboxed(_scan_encoded, *args)
+    return decode_snapshot(json.loads(snapshot), float("inf")) or []
+
+
+def _scan_encoded(
+    source: ProcessSource,
+    user: str,
+    min_memory_mb: float,
+    fields: FieldMask,
+    cpu_interval: float,
+    lookups: Lookups | None,
+) -> bytes:
+    args = (source, user, min_memory_mb, fields, cpu_interval, lookups)
+    return encode_snapshot(_scan_here(*args))
+
+
+def _scan_here(
+    source: ProcessSource,
+    user: str,
+    min_memory_mb: float,
+    fields: FieldMask,
+    cpu_interval: float = 0.0,
+    lookups: Lookups | None = None,
+) -> list[ProcessInfo]:
+    if cpu_interval > 0 and FieldMask.CPU in fields:
+        source.sample_cpu(cpu_interval)
     processes = []
-    current_user = os.getlogin()
-    filter_user = filter_user or current_user
-
-    for proc in psutil.process_iter([
-        "pid",
-        "name",
-        "cmdline",
-        "ppid",
-        "memory_info",
-        "cpu_percent",
-        "username",
-        "create_time",
-        "status",
-    ]):
+    for info in source.iter_processes():
         try:
-            info = proc.info
-            if info["username"] != filter_user:
+            if user not in {ALL_USERS, info["username"]}:
                 continue
 
             rss_mb = (
@@ -106,79 +1145,175 @@ def get_process_list(
             if rss_mb < min_memory_mb:
                 continue
 
-            ppid = info["ppid"] or 0
-            try:
-                parent = psutil.Process(ppid)
-                parent_name = parent.name()
-            except (psutil.NoSuchProcess, psutil.AccessDenied):
-                parent_name = "?"
-
-            # Check if orphaned (reparented to PID 1 system init)
-            # Note:
-            #   ppid != 1 with parent "systemd" means user session service, NOT orphan
-            is_orphan = ppid == 1
-
-            cmdline = " ".join(info["cmdline"] or [])[:200]
-            if not cmdline:
-                cmdline = info["name"]
-
-            pid = info["pid"]
-            processes.append(
-                ProcessInfo(
-                    pid=pid,
-                    name=info["name"],
-                    cmdline=cmdline,
-                    cwd=get_cwd(pid),
-                    ppid=ppid,
-                    parent_name=parent_name,
-                    rss_mb=rss_mb,
-                    cpu_percent=info["cpu_percent"] or 0,
-                    username=info["username"],
-                    create_time=info["create_time"] or 0,
-                    is_orphan=is_orphan,
-                    in_tmux=get_tmux_env(pid) if is_orphan else False,
-                    status=info["status"] or "?",
-                    exe_deleted=is_exe_deleted(pid),
-                )
-            )
+            processes.append(_build_process_info(info, source, fields, lookups))
         except (psutil.NoSuchProcess, psutil.AccessDenied, psutil.ZombieProcess):
             continue
+    return processes
 
-    if sort_by == "memory":
-        processes.sort(key=lambda p: p.rss_mb, reverse=True)
-    elif sort_by == "cpu":
-        processes.sort(key=lambda p: p.cpu_percent, reverse=True)
-    elif sort_by == "name":
-        processes.sort(key=lambda p: p.name.lower())
 
-    return processes
+def select_processes(
+    procs: list[ProcessInfo],
+    user: str,
+    min_memory_mb: float,
+    kernel_threads: bool = False,
+) -> list[ProcessInfo]:
+    """Apply the user and memory filters of ``get_process_list`` to a snapshot.
+
+    Args:
+        procs: Processes of every user, e.g. from a cached scan.
+        user: Only include processes owned by this user; ``ALL_USERS``
+            includes everyone's.
+        min_memory_mb: Minimum RSS (in MB) for a process to be included.
+        kernel_threads: Include kernel threads.
+
+    Returns:
+        list[ProcessInfo]: Matching processes, in their original order.
+    """
+    return [
+        p
+        for p in procs
+        if user in {ALL_USERS, p.username}
+        and p.rss_mb >= min_memory_mb
+        and (kernel_threads or not p.is_kernel_thread)
+    ]
+
+
+def get_process_list(
+    sort_by: str = "memory",
+    filter_user: str | None = None,
+    min_memory_mb: float = 10.0,
+    source: ProcessSource | None = None,
+    fields: FieldMask = ALL_FIELDS,
+    kernel_threads: bool = False,
+    cpu_interval: float = 0.0,
+) -> list[ProcessInfo]:
+    """Get list of processes with detailed info.
+
+    ``cpu_percent`` is the usage since the previous scan of the same process
+    in this interpreter, which is 0% on the first. A ``cpu_interval`` takes
+    a sample first and measures over that interval instead; one-shot
+    commands need it, the TUI only for its first refresh.
+
+    Args:
+        sort_by: Sort keys as for ``sort_processes``, descending unless in
+            ``ASCENDING_SORTS``; ties go by PID.
+        filter_user: Only include processes owned by this user. Defaults to the
+            current user; ``ALL_USERS`` includes everyone's.
+        min_memory_mb: Minimum RSS (in MB) for a process to be included.
+        source: Process backend; defaults to the live system. Live scans go
+            through the snapshot cache while one is active (``--max-age``).
+        fields: Optional fields to read; see ``FieldMask``.
+        kernel_threads: Include kernel threads, which are hidden by default.
+        cpu_interval: Seconds to measure CPU usage over; only spent when
+            ``fields`` includes ``FieldMask.CPU``.
+
+    Returns:
+        A list of ProcessInfo entries matching the filters, sorted by ``sort_by``.
+    """
+    if source is None and (max_age := cache_max_age()) is not None:
+        snapshot = load_snapshot(max_age)
+        if snapshot is None:
+            snapshot = _scan(DEFAULT_SOURCE, ALL_USERS, 0, ALL_FIELDS, cpu_interval)
+            save_snapshot(snapshot)
+        user = filter_user or DEFAULT_SOURCE.current_user()
+        processes = select_processes(snapshot, user, min_memory_mb, kernel_threads)
+    else:
+        source = source or DEFAULT_SOURCE
+        user = filter_user or source.current_user()
+        scanned = _scan(source, user, min_memory_mb, fields, cpu_interval)
+        processes = select_processes(scanned, ALL_USERS, 0, kernel_threads)
+    from .filters import sort_processes  # noqa: PLC0415 - filters imports this
+
+    return sort_processes(processes, sort_by, reverse=sort_by not in ASCENDING_SORTS)
+
+
+class ProcessCollector:
+    """Repeated process scans that reuse what does not change in between.
+
+    ``get_process_list`` starts from scratch on every call. A collector is
+    meant for loops like the TUI refresh and ``watch``: it measures CPU usage
+    over ``cpu_interval`` only on its first scan and since the previous one
+    after that, and reads the cwd and environment of each process once,
+    keyed by PID and start time so a reused PID is read afresh. A process
+    that changes directory keeps showing the directory it was first seen in.
+    """
+
+    def __init__(
+        self,
+        source: ProcessSource | None = None,
+        cpu_interval: float = CPU_SAMPLE_SECONDS,
+    ) -> None:
+        """Initialize a collector that has not scanned yet.
+
+        Args:
+            source: Process backend; defaults to the live system.
+            cpu_interval: Seconds the first scan measures CPU usage over.
+        """
+        self.source = source
+        self.cpu_interval = cpu_interval
+        self._cpu_sampled = False
+        self._lookups: Lookups = {}
+
+    def collect(
+        self,
+        sort_by: str = "memory",
+        filter_user: str | None = None,
+        min_memory_mb: float = 10.0,
+        fields: FieldMask = ALL_FIELDS,
+        kernel_threads: bool = False,
+    ) -> list[ProcessInfo]:
+        """Scan processes, like ``get_process_list`` without its snapshot cache.
+
+        Args:
+            sort_by: Sort keys as for ``sort_processes``, descending
+                unless in ``ASCENDING_SORTS``; ties go by PID.
+            filter_user: Only include processes owned by this user. Defaults
+                to the current user; ``ALL_USERS`` includes everyone's.
+            min_memory_mb: Minimum RSS (in MB) for a process to be included.
+            fields: Optional fields to read; see ``FieldMask``.
+            kernel_threads: Include kernel threads.
+
+        Returns:
+            list[ProcessInfo]: Matching processes, sorted by ``sort_by``.
+        """
+        source = self.source or DEFAULT_SOURCE
+        user = filter_user or source.current_user()
+        # Hardened scans run in a fresh child, with nothing sampled before
+        sampled = self._cpu_sampled and not hardening_enabled()
+        interval = 0.0 if sampled else self.cpu_interval
+        scanned = _scan(source, user, min_memory_mb, fields, interval, self._lookups)
+        self._cpu_sampled |= FieldMask.CPU in fields
+        # Forget processes that exited or no longer match
+        seen = {(p.pid, p.create_time) for p in scanned}
+        self._lookups = {k: v for k, v in self._lookups.items() if k in seen}
+        processes = select_processes(scanned, ALL_USERS, 0, kernel_threads)
+        from .filters import sort_processes  # noqa: PLC0415 - filters imports this
+
+        reverse = sort_by not in ASCENDING_SORTS
+        return sort_processes(processes, sort_by, reverse=reverse)
 
 
 def find_similar_processes(
-    processes: list[ProcessInfo],
+    processes: list[ProcessInfo], by: GroupBy = "command"
 ) -> dict[str, list[ProcessInfo]]:
     """Group processes by similar command patterns.
 
     Args:
         processes: Processes to group.
+        by: "command" groups by the normalized command line, keeping the
+            script interpreters run (see ``command_key``); "name" by the
+            executable alone.
 
     Returns:
         A mapping of group keys (normalized executable/command names) to the list
         of processes in that group. Only groups containing more than one process
         are returned.
     """
+    key = command_key if by == "command" else name_key
     groups: dict[str, list[ProcessInfo]] = {}
 
     for proc in processes:
-        # Extract key identifier from cmdline
-        cmd = proc.cmdline.split()[0] if proc.cmdline else proc.name
-        # Normalize paths
-        if "/" in cmd:
-            cmd = cmd.split("/")[-1]
-
-        if cmd not in groups:
-            groups[cmd] = []
-        groups[cmd].append(proc)
+        groups.setdefault(key(proc), []).append(proc)
 
     # Only return groups with multiple processes
     return {k: v for k, v in groups.items() if len(v) > 1}
@@ -0,0 +1,187 @@
+"""Per-user memory and process-count quotas for shared servers.
+
+Quotas come from the ``[quotas]`` table of the config file::
+
+    [quotas]
+    exempt = ["root", "postgres"]
+
+    [[quotas.rules]]
+    user = "*"            # glob on the user name
+    memory = "8G"         # or a number of MB; optional
+    processes = 200       # optional
+    action = "renice"     # "notify" (default), "renice" or "kill"
+
+Each user is checked against the first rule matching their name; exempt users
+are never checked. When a user is over quota, their newest processes are
+picked as offenders until the remainder fits.
+"""
+
+import fnmatch
+from collections import defaultdict
+from dataclasses import dataclass, field
+
+from .budget import parse_size
+from .config import load_config
+from .errors import ErrorCode, ProccleanError
+from .models import ProcessInfo
+
+QUOTA_ACTIONS = ("notify", "renice", "kill")
+QUOTA_NICE = 19  # Nice value given to offenders of "renice" quotas
+
+
+@dataclass(frozen=True)
+class QuotaRule:
+    """Limits for the users whose name matches ``user``."""
+
+    user: str = "*"
+    memory_mb: float | None = None
+    processes: int | None = None
+    action: str = "notify"
+
+    def matches(self, username: str) -> bool:
+        """Check if the rule applies to a user.
+
+        Returns:
+            bool: True if the user name matches the glob.
+        """
+        return fnmatch.fnmatchcase(username, self.user)
+
+
+@dataclass(frozen=True)
+class Quotas:
+    """Quota rules in priority order and users exempt from all of them."""
+
+    rules: tuple[QuotaRule, ...] = ()
+    exempt: frozenset[str] = frozenset()
+
+    def rule_for(self, username: str) -> QuotaRule | None:
+        """Find the rule that applies to a user.
+
+        Returns:
+            QuotaRule | None: The first matching rule, or None if the user is
+            exempt or no rule matches.
+        """
+        if username in self.exempt:
+            return None
+        return next((r for r in self.rules if r.matches(username)), None)
+
+
+@dataclass
+class QuotaUsage:
+    """One user's usage against their quota rule."""
+
+    user: str
+    rule: QuotaRule
+    procs: list[ProcessInfo] = field(default_factory=list)
+    # Processes to act on to get back under the quota
+    offenders: list[ProcessInfo] = field(default_factory=list)
+
+    @property
+    def used_mb(self) -> float:
+        """Total RSS of the user's processes."""
+        return sum(p.rss_mb for p in self.procs)
+
+    @property
+    def exceeded(self) -> bool:
+        """Check if either limit of the rule is exceeded."""
+        return _over(self.rule, len(self.procs), self.used_mb)
+
+
+def _over(rule: QuotaRule, count: int, used_mb: float) -> bool:
+    return (rule.processes is not None and count > rule.processes) or (
+        rule.memory_mb is not None and used_mb > rule.memory_mb
+    )
+
+
+def _invalid(key: str, reason: str) -> ProccleanError:
+    return ProccleanError(ErrorCode.INVALID_CONFIG, f"Invalid {key}: {reason}", key=key)
+
+
+def _parse_rule(index: int, table: object) -> QuotaRule:
+    """Build a rule from a ``[[quotas.rules]]`` table.
+
+    Returns:
+        QuotaRule: The parsed rule.
+
+    Raises:
+        ProccleanError: With ``INVALID_CONFIG`` for malformed tables.
+    """
+    key = f"quotas.rules[{index}]"
+    if not isinstance(table, dict):
+        raise _invalid(key, "expected a table")
+    user = table.get("user", "*")
+    if not isinstance(user, str) or not user:
+        raise _invalid(key, "user must be a non-empty string")
+    memory_mb = None
+    if "memory" in table:
+        try:
+            memory_mb = parse_size(table["memory"])
+        except ValueError as e:
+            raise _invalid(key, str(e)) from e
+    processes = table.get("processes")
+    if processes is not None and (type(processes) is not int or processes < 1):
+        raise _invalid(key, "processes must be a positive integer")
+    if memory_mb is None and processes is None:
+        raise _invalid(key, "memory or processes is required")
+    action = table.get("action", "notify")
+    if action not in QUOTA_ACTIONS:
+        raise _invalid(key, f"action must be one of {', '.join(QUOTA_ACTIONS)}")
+    return QuotaRule(user, memory_mb, processes, action)
+
+
+def load_quotas() -> Quotas:
+    """Read the ``[quotas]`` table from the config.
+
+    Returns:
+        Quotas: Configured rules; empty when there are none.
+
+    Raises:
+        ProccleanError: With ``INVALID_CONFIG`` for malformed entries.
+    """
+    section = load_config().get("quotas", {})
+    if not isinstance(section, dict):
+        raise _invalid("quotas", "expected a table")
+    rules = section.get("rules", [])
+    if not isinstance(rules, list):
+        raise _invalid("quotas.rules", "expected [[quotas.rules]] tables")
+    exempt = section.get("exempt", [])
+    if not isinstance(exempt, list) or not all(isinstance(u, str) for u in exempt):
+        raise _invalid("quotas.exempt", "expected a list of user names")
+    return Quotas(
+        tuple(_parse_rule(i, table) for i, table in enumerate(rules)),
+        frozenset(exempt),
+    )
+
+
+def check_quotas(procs: list[ProcessInfo], quotas: Quotas) -> list[QuotaUsage]:
+    """Measure usage per user and pick offenders for users over quota.
+
+    Privileged processes count towards the totals but are never offenders.
+
+    Args:
+        procs: Processes to account.
+        quotas: Quota rules and exemptions.
+
+    Returns:
+        list[QuotaUsage]: Usage for every user a rule applies to, by name.
+    """
+    by_user: defaultdict[str, list[ProcessInfo]] = defaultdict(list)
+    for proc in procs:
+        by_user[proc.username].append(proc)
+    usages = []
+    for user in sorted(by_user):
+        rule = quotas.rule_for(user)
+        if rule is None:
+            continue
+        usage = QuotaUsage(user, rule, by_user[user])
+        count, used_mb = len(usage.procs), usage.used_mb
+        for proc in sorted(usage.procs, key=lambda p: -p.create_time):
+            if not _over(rule, count, used_mb):
+                break
+            if proc.is_privileged:
+                continue
+            usage.offenders.append(proc)
+            count -= 1
+            used_mb -= proc.rss_mb
+        usages.append(usage)
+    return usages
@@ -0,0 +1,130 @@
+"""Masking of secrets in command lines and environment values.
+
+Display and export paths redact by default so screenshots and reports are
+safe to share; ``--no-redact`` turns it off.
+"""
+
+import fnmatch
+import re
+from dataclasses import replace
+
+from .models import ProcessInfo
+
+# Environment variable passing --no-redact to the TUI
+NO_REDACT_ENV = "PROCCLEAN_NO_REDACT"
+REDACTED = "<redacted>"
+
+# Names (variables, or flags without dashes) whose values are always masked
+SECRET_PATTERNS: tuple[str, ...] = (
+    "*TOKEN*",
+    "*SECRET*",
+    "*PASSWORD*",
+    "*PASSWD*",
+    "*CREDENTIAL*",
+    "*API_KEY*",
+    "*APIKEY*",
+    "*ACCESS_KEY*",
+    "*PRIVATE_KEY*",
+    "*_KEY",
+    "*COOKIE*",
+)
+
+# user:password@ in URLs such as DATABASE_URL or git remotes
+_URL_PASSWORD_RE = re.compile(r"(?<=://)([^/:@\s]+):[^/@\s]+@")
+# Well-known token formats that are secret wherever they appear
+_TOKEN_RES: tuple[re.Pattern[str], ...] = (
+    re.compile(r"\b(?:AKIA|ASIA)[0-9A-Z]{16}\b"),  # AWS access key ID
+    re.compile(r"\bgh[pousr]_[A-Za-z0-9]{20,}\b"),  # GitHub
+    re.compile(r"\bxox[abprs]-[A-Za-z0-9-]{10,}\b"),  # Slack
+    re.compile(r"\beyJ[\w-]+\.[\w-]+\.[\w-]+"),  # JWT
+)
+_FLAG_RE = re.compile(r"^(-{1,2}[A-Za-z][\w-]*)(?:=(.*))?$")
+_ASSIGN_RE = re.compile(r"^([A-Za-z_]\w*)=(.*)$")
+
+
+def is_secret_name(name: str) -> bool:
+    """Check whether a variable or flag name suggests a secret value.
+
+    Args:
+        name: Name such as ``GITHUB_TOKEN`` or ``api-key``.
+
+    Returns:
+        bool: True if the name matches ``SECRET_PATTERNS``.
+    """
+    normalized = name.upper().replace("-", "_")
+    return any(fnmatch.fnmatchcase(normalized, p) for p in SECRET_PATTERNS)
+
+
+def redact_text(text: str) -> str:
+    """Mask URL passwords and well-known token formats in free text.
+
+    Returns:
+        str: The text with secrets replaced by ``REDACTED``.
+    """
+    text = _URL_PASSWORD_RE.sub(rf"\1:{REDACTED}@", text)
+    for pattern in _TOKEN_RES:
+        text = pattern.sub(REDACTED, text)
+    return text
+
+
+def redact_value(name: str, value: str) -> str:
+    """Hide an environment value if it looks secret.
+
+    Args:
+        name: Variable name, matched against ``SECRET_PATTERNS``.
+        value: Variable value; embedded URL passwords and tokens are masked.
+
+    Returns:
+        str: ``REDACTED``, the value with embedded secrets masked, or the value.
+    """
+    return REDACTED if is_secret_name(name) else redact_text(value)
+
+
+def redact_argv(argv: list[str]) -> list[str]:
+    """Mask secrets in an argument list, keeping argument boundaries.
+
+    Handles ``--password=x``, ``--token x``, ``API_KEY=x`` assignments, URL
+    passwords and well-known token formats.
+
+    Returns:
+        list[str]: Arguments with secret values replaced by ``REDACTED``.
+    """
+    result: list[str] = []
+    hide_next = False
+    for arg in argv:
+        if hide_next and not arg.startswith("-"):
+            result.append(REDACTED)
+            hide_next = False
+            continue
+        hide_next = False
+        if (flag := _FLAG_RE.match(arg)) and is_secret_name(flag[1].lstrip("-")):
+            if flag[2] is None:
+                hide_next = True
+                result.append(arg)
+            else:
+                result.append(f"{flag[1]}={REDACTED}")
+        elif (assign := _ASSIGN_RE.match(arg)) and is_secret_name(assign[1]):
+            result.append(f"{assign[1]}={REDACTED}")
+        else:
+            result.append(redact_text(arg))
+    return result
+
+
+def redact_cmdline(cmdline: str) -> str:
+    """Mask secrets in a space-joined command line.
+
+    Returns:
+        str: The command line with secret values replaced by ``REDACTED``.
+    """
+    return " ".join(redact_argv(cmdline.split(" ")))
+
+
+def redact_process(proc: ProcessInfo) -> ProcessInfo:
+    """Return a copy of a process with secrets masked for display.
+
+    Returns:
+        ProcessInfo: Copy with ``cmdline`` and ``argv`` redacted.
+    """
+    return replace(
+        proc, cmdline=redact_cmdline(proc.cmdline), argv=redact_argv(proc.argv)
+    )
@@ -0,0 +1,315 @@
+"""Registry of the filter presets.
+
+Every preset is registered once, with its description, parameters and the
+fields it reads. The registry drives ``--filter`` choices and its parameter
+options, ``--list-filters``, the views of the TUI and the Python API, so a
+new preset only needs a ``FILTERS.register`` call::
+
+    from procclean.core import FILTERS, FilterSpec
+
+    FILTERS.register(
+        FilterSpec(
+            "zombies",
+            "Zombie processes",
+            lambda procs, _source, _params: [p for p in procs if p.status == "zombie"],
+        )
+    )
+
+Extensions must register before the CLI parser or the TUI is built.
+"""
+
+from collections.abc import Callable, Iterator, Mapping
+from dataclasses import dataclass
+from typing import Any
+
+from .constants import CPU_HOG_THRESHOLD_PERCENT, HIGH_MEMORY_THRESHOLD_MB
+from .errors import ErrorCode, ProccleanError
+from .fields import FieldMask
+from .filters import (
+    filter_cpu_hogs,
+    filter_cwd_deleted,
+    filter_env_deleted,
+    filter_high_memory,
+    filter_killable,
+    filter_needs_restart,
+    filter_orphans,
+)
+from .models import ProcessInfo
+from .runtime import attach_runtime_envs
+from .source import ProcessSource
+from .upgrade import attach_deleted_libs
+
+# Applies a preset: processes, process backend, parameter values
+FilterFunc = Callable[
+    [list[ProcessInfo], ProcessSource | None, Mapping[str, float]],
+    list[ProcessInfo],
+]
+
+
+@dataclass(frozen=True, slots=True)
+class FilterParam:
+    """Numeric parameter of a filter preset, set by a CLI option."""
+
+    name: str
+    default: float
+    option: str  # e.g. "--high-memory-threshold"
+    metavar: str
+    help: str
+    unit: str = ""  # Appended to the default in help texts
+
+    @property
+    def dest(self) -> str:
+        """Attribute of the parsed CLI arguments holding the value."""
+        return self.option.lstrip("-").replace("-", "_")
+
+    def to_dict(self) -> dict[str, Any]:
+        """Describe the parameter for ``--list-filters --format json``.
+
+        Returns:
+            dict[str, Any]: Name, default, option and help text.
+        """
+        return {
+            "name": self.name,
+            "default": self.default,
+            "option": self.option,
+            "help": self.help,
+        }
+
+
+@dataclass(frozen=True, slots=True)
+class FilterSpec:
+    """A named filter preset."""
+
+    name: str
+    description: str
+    apply: FilterFunc
+    fields: FieldMask = FieldMask.NONE  # Optional fields the preset reads
+    params: tuple[FilterParam, ...] = ()
+    view: str | None = None  # TUI view id; None keeps it out of the TUI
+
+    def defaults(self) -> dict[str, float]:
+        """Get the default parameter values.
+
+        Returns:
+            dict[str, float]: Default per parameter name.
+        """
+        return {param.name: param.default for param in self.params}
+
+    def run(
+        self,
+        procs: list[ProcessInfo],
+        source: ProcessSource | None = None,
+        **params: float,
+    ) -> list[ProcessInfo]:
+        """Apply the preset.
+
+        Args:
+            procs: Processes to filter.
+            source: Process backend; defaults to the live system.
+            **params: Parameter values overriding the defaults.
+
+        Returns:
+            list[ProcessInfo]: The matching processes.
+
+        Raises:
+            ProccleanError: With ``INVALID_FILTER`` for unknown parameters.
+        """
+        if unknown := sorted(params.keys() - self.defaults().keys()):
+            msg = f"Unknown parameter(s) of filter {self.name}: {', '.join(unknown)}"
+            raise ProccleanError(ErrorCode.INVALID_FILTER, msg, filter=self.name)
+        return self.apply(procs, source, self.defaults() | params)
+
+    def to_dict(self) -> dict[str, Any]:
+        """Describe the preset for ``--list-filters --format json``.
+
+        Returns:
+            dict[str, Any]: Name, description, TUI view and parameters.
+        """
+        return {
+            "name": self.name,
+            "description": self.description,
+            "view": self.view,
+            "params": [param.to_dict() for param in self.params],
+        }
+
+
+class FilterRegistry:
+    """Filter presets by name, in registration order."""
+
+    def __init__(self) -> None:
+        """Initialize an empty registry."""
+        self._specs: dict[str, FilterSpec] = {}
+
+    def register(self, spec: FilterSpec) -> FilterSpec:
+        """Add a preset.
+
+        Returns:
+            FilterSpec: The registered preset.
+
+        Raises:
+            ValueError: If the name or the TUI view is already taken.
+        """
+        if spec.name in self._specs:
+            msg = f"filter already registered: {spec.name}"
+            raise ValueError(msg)
+        if spec.view is not None and self.by_view(spec.view) is not None:
+            msg = f"view already registered: {spec.view}"
+            raise ValueError(msg)
+        self._specs[spec.name] = spec
+        return spec
+
+    def unregister(self, name: str) -> FilterSpec | None:
+        """Remove a preset.
+
+        Returns:
+            FilterSpec | None: The removed preset, or None if there was none.
+        """
+        return self._specs.pop(name, None)
+
+    def get(self, name: str) -> FilterSpec:
+        """Look up a preset by name.
+
+        Returns:
+            FilterSpec: The preset.
+
+        Raises:
+            ProccleanError: With ``INVALID_FILTER`` for unknown names.
+        """
+        try:
+            return self._specs[name]
+        except KeyError:
+            choices = ", ".join(self._specs)
+            msg = f"Unknown filter '{name}' (choose from {choices})"
+            raise ProccleanError(ErrorCode.INVALID_FILTER, msg, filter=name) from None
+
+    def by_view(self, view: str) -> FilterSpec | None:
+        """Look up the preset behind a TUI view.
+
+        Returns:
+            FilterSpec | None: The preset, or None if no preset has the view.
+        """
+        return next((spec for spec in self if spec.view == view), None)
+
+    def names(self) -> list[str]:
+        """List the preset names.
+
+        Returns:
+            list[str]: Names in registration order.
+        """
+        return list(self._specs)
+
+    def params(self) -> list[FilterParam]:
+        """List the parameters of all presets.
+
+        Returns:
+            list[FilterParam]: Parameters in registration order, each CLI
+            option once.
+        """
+        by_option: dict[str, FilterParam] = {}
+        for spec in self:
+            for param in spec.params:
+                by_option.setdefault(param.option, param)
+        return list(by_option.values())
+
+    def __contains__(self, name: object) -> bool:
+        """Check whether a preset is registered."""
+        return name in self._specs
+
+    def __iter__(self) -> Iterator[FilterSpec]:
+        """Iterate over the presets in registration order."""
+        return iter(list(self._specs.values()))
+
+    def __len__(self) -> int:
+        """Count the registered presets."""
+        return len(self._specs)
+
+
+FILTERS = FilterRegistry()
+
+FILTERS.register(
+    FilterSpec(
+        "orphans",
+        "Processes whose parent is init or systemd",
+        lambda procs, _source, _params: filter_orphans(procs),
+        view="orphans",
+    )
+)
+FILTERS.register(
+    FilterSpec(
+        "killable",
+        "Orphans that are safe to kill: not in tmux, not privileged, not a "
+        "system service",
+        lambda procs, source, _params: filter_killable(procs, source=source),
+        fields=FieldMask.TMUX | FieldMask.PRIVILEGES,
+        view="killable",
+    )
+)
+FILTERS.register(
+    FilterSpec(
+        "high-memory",
+        "Processes using more memory than a threshold",
+        lambda procs, _source, params: filter_high_memory(
+            procs, threshold_mb=params["threshold_mb"]
+        ),
+        params=(
+            FilterParam(
+                "threshold_mb",
+                HIGH_MEMORY_THRESHOLD_MB,
+                "--high-memory-threshold",
+                "MB",
+                "Threshold for high memory filter",
+                " MB",
+            ),
+        ),
+        view="high-mem",
+    )
+)
+FILTERS.register(
+    FilterSpec(
+        "cpu-hogs",
+        "Processes using more CPU than a threshold",
+        lambda procs, _source, params: filter_cpu_hogs(
+            procs, threshold_percent=params["threshold_percent"]
+        ),
+        fields=FieldMask.CPU,
+        params=(
+            FilterParam(
+                "threshold_percent",
+                CPU_HOG_THRESHOLD_PERCENT,
+                "--cpu-hog-threshold",
+                "PCT",
+                "Threshold for CPU hogs filter",
+                "%",
+            ),
+        ),
+        view="cpu-hogs",
+    )
+)
+FILTERS.register(
+    FilterSpec(
+        "cwd-deleted",
+        "Processes whose working directory was removed",
+        lambda procs, _source, _params: filter_cwd_deleted(procs),
+        fields=FieldMask.CWD_DELETED,
+    )
+)
+FILTERS.register(
+    FilterSpec(
+        "env-deleted",
+        "Processes whose virtualenv, conda env or nvm version was removed",
+        lambda procs, source, _params: filter_env_deleted(
+            attach_runtime_envs(procs, source)
+        ),
+    )
+)
+FILTERS.register(
+    FilterSpec(
+        "needs-restart",
+        "Processes running a deleted executable or libraries replaced by an "
+        "upgrade",
+        lambda procs, source, _params: filter_needs_restart(
+            attach_deleted_libs(procs, source)
+        ),
+        fields=FieldMask.EXE_DELETED,
+    )
+)
@@ -0,0 +1,107 @@
+"""Git repository and branch association for working directories.
+
+A process belongs to the git worktree containing its cwd: the nearest
+ancestor with a ``.git`` directory, or a ``.git`` file pointing at a linked
+worktree's git dir. The branch is read straight from ``HEAD``, so no git
+binary is needed.
+"""
+
+from pathlib import Path
+
+from .models import ProcessInfo
+
+_REF_PREFIX = "ref: "
+_BRANCH_PREFIX = "refs/heads/"
+_SHORT_SHA = 7  # Length of a detached HEAD commit as shown for the branch
+
+
+def _git_dir(root: Path) -> Path | None:
+    """Find the git dir of a worktree root candidate.
+
+    Returns:
+        Path | None: ``.git`` itself, the ``gitdir:`` a linked worktree points
+        at, or None if ``root`` is not a worktree root.
+    """
+    dotgit = root / ".git"
+    try:
+        if dotgit.is_dir():
+            return dotgit
+        if dotgit.is_file():
+            text = dotgit.read_text(encoding="utf-8").strip()
+            if text.startswith("gitdir:"):
+                return root / text.removeprefix("gitdir:").strip()
+    except OSError:
+        pass
+    return None
+
+
+def read_branch(git_dir: Path) -> str | None:
+    """Read the checked-out branch from a git dir's ``HEAD``.
+
+    Returns:
+        str | None: The branch name, the short commit for a detached HEAD, or
+        None if ``HEAD`` cannot be read.
+    """
+    try:
+        head = (git_dir / "HEAD").read_text(encoding="utf-8").strip()
+    except OSError:
+        return None
+    if head.startswith(_REF_PREFIX):
+        return head.removeprefix(_REF_PREFIX).removeprefix(_BRANCH_PREFIX)
+    return head[:_SHORT_SHA] or None
+
+
+def find_repo(path: str) -> tuple[str, str | None] | None:
+    """Find the git worktree containing a directory.
+
+    Args:
+        path: Absolute directory path.
+
+    Returns:
+        tuple[str, str | None] | None: Worktree root and branch, or None if
+        the directory is not inside a worktree.
+    """
+    start = Path(path)
+    for root in (start, *start.parents):
+        if git_dir := _git_dir(root):
+            return str(root), read_branch(git_dir)
+    return None
+
+
+def attach_repos(procs: list[ProcessInfo]) -> list[ProcessInfo]:
+    """Fill ``repo`` and ``branch`` from each process's cwd.
+
+    Every distinct cwd is looked up once. Processes whose cwd is unknown or
+    was deleted are left without a repo.
+
+    Args:
+        procs: Processes to annotate in place.
+
+    Returns:
+        list[ProcessInfo]: The same processes, for chaining.
+    """
+    found: dict[str, tuple[str, str | None] | None] = {}
+    for proc in procs:
+        if not proc.cwd or proc.cwd == "?" or proc.cwd_deleted:
+            continue
+        if proc.cwd not in found:
+            found[proc.cwd] = find_repo(proc.cwd)
+        if repo := found[proc.cwd]:
+            proc.repo, proc.branch = repo
+    return procs
+
+
+def filter_by_repo(procs: list[ProcessInfo], path: str) -> list[ProcessInfo]:
+    """Filter processes by the git worktree their cwd belongs to.
+
+    Args:
+        procs: Processes to filter; ``repo`` is attached as needed.
+        path: Any directory inside the worktree, e.g. its root.
+
+    Returns:
+        list[ProcessInfo]: Processes working in that worktree.
+    """
+    resolved = str(Path(path).expanduser().resolve())
+    repo = find_repo(resolved)
+    root = repo[0] if repo else resolved
+    return [p for p in attach_repos(procs) if p.repo == root]
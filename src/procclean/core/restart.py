@@ -0,0 +1,272 @@
+"""Restarting processes: through their manager, or by respawning them.
+
+Supervised processes restart with their manager's restart command
+(``systemctl restart``, ``docker restart``, ``pm2 restart``; see
+``core.supervisor``), and managers without one (kubelet) start the process
+again on their own once it is killed. Anything else is respawned: its command
+line, working directory and environment are recorded, it is killed as
+``kill --escalate`` would, and the same command is started again in a new
+session, detached from procclean, with its output discarded.
+
+Only your own processes are respawned, so a restart as root never starts
+another user's program as root.
+"""
+
+import shlex
+import subprocess
+from dataclasses import dataclass, field
+from enum import StrEnum, auto
+from typing import Any
+
+import psutil
+
+from procclean.i18n import t
+
+from .actions import ESCALATE_TIMEOUT_SECONDS, KillProgress, kill_escalating
+from .errors import ErrorCode, ProccleanError
+from .models import ProcessInfo
+from .process import DEFAULT_SOURCE
+from .redact import redact_argv
+from .source import ProcessSource
+from .supervisor import Supervisor, resolve_supervisors, stop_via_managers
+
+
+class RestartMethod(StrEnum):
+    """How a process is restarted."""
+
+    MANAGER = auto()  # The manager's restart command
+    KILL = auto()  # Killed; the manager starts it again by itself
+    RESPAWN = auto()  # Killed, then started again by procclean
+
+
+@dataclass(frozen=True, slots=True)
+class Invocation:
+    """How a process was started, enough to start it again."""
+
+    argv: tuple[str, ...]
+    cwd: str
+    env: dict[str, str] = field(default_factory=dict, repr=False)
+
+    def command(self, redact: bool = True) -> str:
+        """Render the command line as a shell would take it.
+
+        Returns:
+            str: E.g. "node server.js --port 3000".
+        """
+        return shlex.join(redact_argv(list(self.argv)) if redact else self.argv)
+
+
+@dataclass(frozen=True, slots=True)
+class RestartPlan:
+    """How one process is going to be restarted, decided before asking."""
+
+    proc: ProcessInfo
+    method: RestartMethod
+    supervisor: Supervisor | None = None
+    invocation: Invocation | None = None  # Recorded for RESPAWN
+    error: str | None = None  # Why the process cannot be restarted
+
+    def describe(self, redact: bool = True) -> str:
+        """Render the plan for confirmation prompts.
+
+        Returns:
+            str: E.g. "via `systemctl restart nginx.service`".
+        """
+        if self.error:
+            return self.error
+        match self.method:
+            case RestartMethod.MANAGER if self.supervisor:
+                command = " ".join(self.supervisor.command("restart") or ())
+                return t("restart-plan-manager", command=command)
+            case RestartMethod.KILL if self.supervisor:
+                return t("restart-plan-kill", kind=self.supervisor.kind)
+            case _ if self.invocation:
+                command, cwd = self.invocation.command(redact), self.invocation.cwd
+                return t("restart-plan-respawn", command=command, cwd=cwd)
+        return ""
+
+
+@dataclass(frozen=True, slots=True)
+class RestartResult:
+    """Result of restarting one process."""
+
+    pid: int
+    ok: bool
+    message: str
+    new_pid: int | None = None  # PID of the respawned process
+
+    def to_dict(self) -> dict[str, Any]:
+        """Describe the result for JSON output and session recordings.
+
+        Returns:
+            dict[str, Any]: PID, success, message and respawned PID.
+        """
+        return {
+            "pid": self.pid,
+            "ok": self.ok,
+            "message": self.message,
+            "new_pid": self.new_pid,
+        }
+
+
+def record_invocation(
+    proc: ProcessInfo, source: ProcessSource | None = None
+) -> Invocation:
+    """Record the command line, working directory and environment of a process.
+
+    Args:
+        proc: The process to respawn later.
+        source: Process backend; defaults to the live system.
+
+    Returns:
+        Invocation: What it takes to start the process again.
+
+    Raises:
+        ProccleanError: With ``PERMISSION_DENIED`` for other users' processes,
+            processes that exited and when any of the three cannot be read.
+    """
+    backend = source or DEFAULT_SOURCE
+
+    def refuse(reason: str) -> ProccleanError:
+        msg = t("restart-refused", pid=proc.pid, name=proc.name, reason=reason)
+        return ProccleanError(ErrorCode.PERMISSION_DENIED, msg, pid=proc.pid)
+
+    if proc.username != backend.current_user():
+        raise refuse(t("restart-reason-owner", user=proc.username))
+    if proc.is_kernel_thread or not proc.argv:
+        raise refuse(t("restart-reason-cmdline"))
+    try:
+        cwd = backend.cwd(proc.pid)
+        deleted = backend.cwd_deleted(proc.pid)
+        env = backend.environ(proc.pid)
+    except psutil.NoSuchProcess as e:
+        raise refuse(t("restart-reason-gone")) from e
+    if cwd == "?" or deleted:
+        raise refuse(t("restart-reason-cwd"))
+    if not env:
+        raise refuse(t("restart-reason-env"))
+    return Invocation(tuple(proc.argv), cwd, env)
+
+
+def plan_restarts(
+    procs: list[ProcessInfo], source: ProcessSource | None = None
+) -> list[RestartPlan]:
+    """Decide how to restart each process, recording what respawns need.
+
+    Args:
+        procs: Processes to restart.
+        source: Process backend; defaults to the live system.
+
+    Returns:
+        list[RestartPlan]: One plan per process, in order; plans with an
+        ``error`` are skipped by ``restart_processes``.
+    """
+    supervisors = resolve_supervisors([p.pid for p in procs], source)
+    plans = []
+    for proc in procs:
+        sup = supervisors.get(proc.pid)
+        if sup and sup.command("restart"):
+            plans.append(RestartPlan(proc, RestartMethod.MANAGER, sup))
+        elif sup and sup.respawns:
+            plans.append(RestartPlan(proc, RestartMethod.KILL, sup))
+        else:
+            try:
+                invocation = record_invocation(proc, source)
+            except ProccleanError as e:
+                plan = RestartPlan(proc, RestartMethod.RESPAWN, sup, error=e.message)
+            else:
+                plan = RestartPlan(proc, RestartMethod.RESPAWN, sup, invocation)
+            plans.append(plan)
+    return plans
+
+
+def _respawn(plan: RestartPlan, source: ProcessSource) -> RestartResult:
+    """Start a killed process again from its recorded invocation.
+
+    Returns:
+        RestartResult: The new PID, or why it could not be started.
+    """
+    pid, inv = plan.proc.pid, plan.invocation
+    if inv is None:
+        return RestartResult(pid, False, t("restart-reason-cmdline"))
+    try:
+        new_pid = source.spawn(list(inv.argv), inv.cwd, inv.env, plan.proc.username)
+    except (OSError, subprocess.SubprocessError) as e:
+        error = getattr(e, "strerror", None) or e
+        msg = t("restart-spawn-failed", pid=pid, command=inv.command(), error=error)
+        return RestartResult(pid, False, msg)
+    msg = t("restart-respawned", pid=pid, new_pid=new_pid)
+    return RestartResult(pid, True, msg, new_pid)
+
+
+def restart_processes(
+    plans: list[RestartPlan],
+    source: ProcessSource | None = None,
+    timeout: float = ESCALATE_TIMEOUT_SECONDS,
+    on_progress: KillProgress | None = None,
+) -> list[RestartResult]:
+    """Carry out restart plans.
+
+    Manager restarts run first, each command once. The other processes are
+    killed together (SIGTERM, then SIGKILL after ``timeout``), and those
+    that went down are respawned unless their manager does that itself.
+
+    Args:
+        plans: Plans from ``plan_restarts``.
+        source: Process backend; defaults to the live system.
+        timeout: Seconds to wait after SIGTERM before sending SIGKILL.
+        on_progress: Called while the kill waits, as for ``kill_escalating``.
+
+    Returns:
+        list[RestartResult]: One result per plan, in order.
+    """
+    backend = source or DEFAULT_SOURCE
+    runnable = [p for p in plans if not p.error]
+    managed = [p.proc.pid for p in runnable if p.method is RestartMethod.MANAGER]
+    killed = [p.proc.pid for p in runnable if p.method is not RestartMethod.MANAGER]
+    results: dict[int, RestartResult] = {}
+    if managed:
+        for pid, ok, msg in stop_via_managers(managed, source=source, action="restart"):
+            results[pid] = RestartResult(pid, ok, msg)
+    kills = (
+        {r.pid: r for r in kill_escalating(killed, timeout, source, on_progress)}
+        if killed
+        else {}
+    )
+    for plan in plans:
+        pid = plan.proc.pid
+        if plan.error:
+            results[pid] = RestartResult(pid, False, plan.error)
+        elif (kill := kills.get(pid)) is None:
+            continue
+        elif not kill.ok:
+            results[pid] = RestartResult(pid, False, kill.message)
+        elif plan.method is RestartMethod.KILL and plan.supervisor:
+            msg = t("restart-by-manager", pid=pid, kind=plan.supervisor.kind)
+            results[pid] = RestartResult(pid, True, msg)
+        else:
+            results[pid] = _respawn(plan, backend)
+    return [results[p.proc.pid] for p in plans if p.proc.pid in results]
+
+
+def unit_processes(
+    unit: str, procs: list[ProcessInfo], source: ProcessSource | None = None
+) -> list[ProcessInfo]:
+    """Find the processes a manager knows by a name.
+
+    Args:
+        unit: systemd unit (``.service`` may be left out), container ID,
+            supervisord program or pm2/forever app name.
+        procs: Processes to search.
+        source: Process backend; defaults to the live system.
+
+    Returns:
+        list[ProcessInfo]: Processes whose supervisor has that name.
+    """
+    names = {unit, f"{unit}.service"}
+    supervisors = resolve_supervisors([p.pid for p in procs], source)
+    return [
+        p
+        for p in procs
+        if (sup := supervisors.get(p.pid)) and sup.kind != "shell" and sup.name in names
+    ]
@@ -0,0 +1,132 @@
+"""Run a command and find the processes it leaves behind.
+
+Every process the command spawns inherits ``PROCCLEAN_RUN_ID`` in its
+environment, and the descendant tree is sampled while the command runs. After
+it exits, any process that carries the tag or was seen in the tree is a
+survivor, even if it double-forked away from its parent.
+"""
+
+import os
+import subprocess
+import uuid
+from collections import defaultdict
+from dataclasses import dataclass, field
+
+import psutil
+
+from .errors import ErrorCode, ProccleanError
+from .models import ProcessInfo
+from .process import DEFAULT_SOURCE, get_process_list
+from .source import ProcessSource
+
+RUN_ID_ENV = "PROCCLEAN_RUN_ID"
+RUN_POLL_SECONDS = 0.5  # How often the descendant tree is sampled
+
+
+@dataclass
+class RunResult:
+    """Outcome of a tracked command."""
+
+    returncode: int
+    survivors: list[ProcessInfo] = field(default_factory=list)
+
+
+def _descendants(root: int, source: ProcessSource) -> dict[int, float]:
+    """Map every descendant of ``root`` to its creation time.
+
+    Returns:
+        dict[int, float]: PID to ``create_time``, so reused PIDs can be told
+        apart later.
+    """
+    children: defaultdict[int, list[int]] = defaultdict(list)
+    created: dict[int, float] = {}
+    for info in source.iter_processes():
+        children[info["ppid"]].append(info["pid"])
+        created[info["pid"]] = info["create_time"]
+    found: dict[int, float] = {}
+    stack = [root]
+    while stack:
+        for child in children.get(stack.pop(), []):
+            if child not in found:
+                found[child] = created[child]
+                stack.append(child)
+    return found
+
+
+def _tagged(source: ProcessSource, pid: int, run_id: str) -> bool:
+    try:
+        return source.environ(pid).get(RUN_ID_ENV) == run_id
+    except (psutil.NoSuchProcess, psutil.AccessDenied):
+        return False
+
+
+def find_survivors(
+    run_id: str,
+    seen: dict[int, float],
+    source: ProcessSource | None = None,
+    exclude: frozenset[int] = frozenset(),
+) -> list[ProcessInfo]:
+    """Find processes of a run that are still alive.
+
+    Args:
+        run_id: Value of ``PROCCLEAN_RUN_ID`` given to the command.
+        seen: Descendants sampled during the run, with their creation times.
+        source: Process backend; defaults to the live system.
+        exclude: PIDs never to report, e.g. procclean itself.
+
+    Returns:
+        list[ProcessInfo]: Surviving processes, largest first.
+    """
+    source = source or DEFAULT_SOURCE
+    return [
+        p
+        for p in get_process_list(min_memory_mb=0, source=source)
+        if p.pid not in exclude
+        and (seen.get(p.pid) == p.create_time or _tagged(source, p.pid, run_id))
+    ]
+
+
+def run_tracked(
+    argv: list[str],
+    source: ProcessSource | None = None,
+    poll: float = RUN_POLL_SECONDS,
+    run_id: str | None = None,
+) -> RunResult:
+    """Run a command to completion and collect the processes it left behind.
+
+    Ctrl-C is delivered to the command as usual; procclean keeps waiting for
+    it to exit so survivors are still reported.
+
+    Args:
+        argv: Command and arguments.
+        source: Process backend used to follow descendants.
+        poll: Seconds between samples of the descendant tree.
+        run_id: Tag for the command's environment; random by default.
+
+    Returns:
+        RunResult: The command's exit status and its survivors.
+
+    Raises:
+        ProccleanError: With ``INVALID_ARGUMENT`` if the command cannot start.
+    """
+    source = source or DEFAULT_SOURCE
+    run_id = run_id or uuid.uuid4().hex
+    try:
+        proc = subprocess.Popen(argv, env={**os.environ, RUN_ID_ENV: run_id})
+    except OSError as e:
+        raise ProccleanError(
+            ErrorCode.INVALID_ARGUMENT,
+            f"Cannot run {argv[0]}: {e.strerror}",
+            command=argv[0],
+        ) from e
+    seen: dict[int, float] = {}
+    while True:
+        try:
+            returncode = proc.wait(timeout=poll)
+            break
+        except subprocess.TimeoutExpired:
+            seen.update(_descendants(proc.pid, source))
+        except KeyboardInterrupt:
+            continue
+    exclude = frozenset({proc.pid, os.getpid()})
+    return RunResult(returncode, find_survivors(run_id, seen, source, exclude))
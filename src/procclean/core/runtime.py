@@ -0,0 +1,98 @@
+"""Interpreter context: virtualenv, conda env or nvm Node version.
+
+The interpreter path in ``argv[0]`` is checked first, then the variables an
+activated environment exports (``VIRTUAL_ENV``, ``CONDA_PREFIX``, ``NVM_BIN``;
+direnv layouts set the same ones). A process whose environment directory no
+longer exists is a leftover of a deleted virtualenv.
+"""
+
+import re
+from dataclasses import dataclass
+from pathlib import Path
+
+from .models import ProcessInfo
+from .process import DEFAULT_SOURCE
+from .source import ProcessSource
+
+RUNTIME_KINDS = ("venv", "conda", "nvm")
+
+# Interpreter paths, checked in this order; group 1 is the environment root.
+# An existing root must contain the marker, which rules out e.g. /usr.
+_ARGV_PATTERNS: tuple[tuple[str, re.Pattern[str], str | None], ...] = (
+    ("nvm", re.compile(r"^(/.*/\.nvm/versions/node/[^/]+)/bin/[^/]+$"), None),
+    ("conda", re.compile(r"^(/.*/envs/[^/]+)/bin/[^/]+$"), "conda-meta"),
+    ("venv", re.compile(r"^(/.+)/bin/python[\d.]*$"), "pyvenv.cfg"),
+)
+
+
+@dataclass(frozen=True)
+class RuntimeEnv:
+    """The environment a process's interpreter belongs to."""
+
+    kind: str  # One of RUNTIME_KINDS
+    path: str  # Environment root
+
+    @property
+    def label(self) -> str:
+        """Short description, e.g. "nvm:v20.11.0" or "venv:/srv/app/.venv"."""
+        if self.kind == "venv":
+            return f"venv:{self.path}"
+        return f"{self.kind}:{Path(self.path).name}"
+
+    @property
+    def deleted(self) -> bool:
+        """Check if the environment directory no longer exists."""
+        return not Path(self.path).exists()
+
+
+def _from_argv(argv0: str) -> RuntimeEnv | None:
+    for kind, pattern, marker in _ARGV_PATTERNS:
+        match = pattern.match(argv0)
+        if not match:
+            continue
+        root = Path(match[1])
+        if marker is None or not root.exists() or (root / marker).exists():
+            return RuntimeEnv(kind, match[1])
+    return None
+
+
+def detect_runtime_env(argv: list[str], environ: dict[str, str]) -> RuntimeEnv | None:
+    """Work out the interpreter environment of a process.
+
+    Args:
+        argv: Process arguments; only ``argv[0]`` is used.
+        environ: Process environment.
+
+    Returns:
+        RuntimeEnv | None: The environment, or None if none is recognized.
+    """
+    if argv and (env := _from_argv(argv[0])):
+        return env
+    if path := environ.get("VIRTUAL_ENV"):
+        return RuntimeEnv("venv", path)
+    if path := environ.get("CONDA_PREFIX"):
+        return RuntimeEnv("conda", path)
+    if path := environ.get("NVM_BIN"):
+        return RuntimeEnv("nvm", str(Path(path).parent))
+    return None
+
+
+def attach_runtime_envs(
+    procs: list[ProcessInfo], source: ProcessSource | None = None
+) -> list[ProcessInfo]:
+    """Fill ``runtime_env`` and ``runtime_env_deleted`` on each process.
+
+    Args:
+        procs: Processes to annotate in place.
+        source: Process backend used to read environments.
+
+    Returns:
+        list[ProcessInfo]: The same processes, for chaining.
+    """
+    source = source or DEFAULT_SOURCE
+    for proc in procs:
+        env = detect_runtime_env(proc.argv, source.environ(proc.pid))
+        if env:
+            proc.runtime_env = env.label
+            proc.runtime_env_deleted = env.deleted
+    return procs
@@ -0,0 +1,275 @@
+"""Hardened scans: reading /proc in a sandboxed child process.
+
+Parsing /proc means parsing text other users control (command lines,
+process names, environments), and as root a bug there runs with every
+privilege. With ``--harden`` (or ``$PROCCLEAN_HARDEN``), every scan runs in
+a fresh child interpreter, never a fork of a possibly threaded parent, that
+first drops its capabilities down to the two that reading other users' /proc
+files takes (``CAP_DAC_READ_SEARCH`` and ``CAP_SYS_PTRACE``) and then installs
+a seccomp filter that fails, with EPERM:
+
+- sockets, so no network or local IPC;
+- signals, ptrace and priority changes to other processes;
+- running programs;
+- opening files for writing, and creating, renaming or removing any.
+
+The parent pickles the scan over to the child, which loads it before the
+filter goes in. The child hands back the processes as JSON on its stdout,
+never pickle, so a compromised scanner cannot run code in the parent. Kills, renices and
+restarts stay in the parent, which signals the PIDs it is given without
+parsing anything from /proc itself.
+
+The filter supports x86_64 and aarch64 Linux. Scans in the child start from
+scratch: CPU usage is always measured over the sample interval, and cwd and
+environment reads are not reused between TUI refreshes.
+"""
+
+import ctypes
+import os
+import pickle  # noqa: S403 - only ever loads what the parent sent
+import platform
+import struct
+import subprocess
+import sys
+from collections.abc import Callable
+from pathlib import Path
+
+from .errors import ErrorCode, ProccleanError
+
+HARDEN_ENV = "PROCCLEAN_HARDEN"
+
+# prctl(2) and seccomp(2) constants from <linux/prctl.h> and <linux/seccomp.h>
+PR_SET_NO_NEW_PRIVS = 38
+PR_SET_SECCOMP = 22
+SECCOMP_MODE_FILTER = 2
+SECCOMP_RET_ALLOW = 0x7FFF0000
+SECCOMP_RET_ERRNO = 0x00050000
+# Classic BPF opcodes from <linux/filter.h>
+BPF_LD_W_ABS = 0x20  # BPF_LD | BPF_W | BPF_ABS
+BPF_JEQ_K = 0x15  # BPF_JMP | BPF_JEQ | BPF_K
+BPF_JGE_K = 0x35  # BPF_JMP | BPF_JGE | BPF_K
+BPF_JSET_K = 0x45  # BPF_JMP | BPF_JSET | BPF_K
+BPF_RET_K = 0x06  # BPF_RET | BPF_K
+# Offsets into struct seccomp_data: nr, arch, then the arguments (64-bit
+# each; the low word comes first on both supported architectures)
+DATA_NR = 0
+DATA_ARCH = 4
+DATA_ARGS = 16
+# O_WRONLY | O_RDWR | O_CREAT | O_TRUNC | O_APPEND
+OPEN_WRITE_FLAGS = 0o1 | 0o2 | 0o100 | 0o1000 | 0o2000
+X32_SYSCALL_BIT = 0x40000000  # x32 ABI syscalls on x86_64, denied outright
+# Capabilities the scan keeps, by bit: reading other users' /proc files
+CAP_DAC_READ_SEARCH = 2
+CAP_SYS_PTRACE = 19
+LINUX_CAPABILITY_VERSION_3 = 0x20080522
+
+
+type SeccompArch = tuple[int, tuple[int, ...], tuple[tuple[int, int], ...]]
+
+# AUDIT_ARCH value, denied syscalls and (syscall, flags argument) of opens
+# whose write flags are denied, by machine
+SECCOMP_ARCHES: dict[str, SeccompArch] = {
+    "x86_64": (
+        0xC000003E,
+        (
+            41, 42, 43, 44, 46, 49, 50, 288, 307,  # socket ... sendmmsg
+            62, 200, 234, 129, 297, 424, 101, 311,  # kill ... process_vm_writev
+            141, 144, 251,  # setpriority, sched_setscheduler, ioprio_set
+            59, 322,  # execve, execveat
+            85, 437, 87, 263, 82, 264, 316, 83, 258, 84,  # creat ... rmdir
+            76, 90, 268, 165, 166,  # truncate, chmod, fchmodat, mount, umount2
+        ),
+        ((2, 1), (257, 2)),  # open, openat
+    ),
+    "aarch64": (
+        0xC00000B7,
+        (
+            198, 200, 201, 202, 203, 206, 211, 242, 269,  # socket ... sendmmsg
+            129, 130, 131, 138, 240, 424, 117, 271,  # kill ... process_vm_writev
+            140, 119, 30,  # setpriority, sched_setscheduler, ioprio_set
+            221, 281,  # execve, execveat
+            437, 35, 38, 276, 34,  # openat2 ... mkdirat
+            45, 53, 40, 39,  # truncate, fchmodat, mount, umount2
+        ),
+        ((56, 2),),  # openat
+    ),
+}  # fmt: skip
+
+
+class _SockFprog(ctypes.Structure):
+    _fields_ = (
+        ("len", ctypes.c_ushort),
+        ("filter", ctypes.c_char_p),
+    )
+
+
+class _CapHeader(ctypes.Structure):
+    _fields_ = (("version", ctypes.c_uint32), ("pid", ctypes.c_int))
+
+
+class _CapData(ctypes.Structure):
+    _fields_ = (
+        ("effective", ctypes.c_uint32),
+        ("permitted", ctypes.c_uint32),
+        ("inheritable", ctypes.c_uint32),
+    )
+
+
+def hardening_enabled() -> bool:
+    """Check whether scans run sandboxed.
+
+    Returns:
+        bool: True if ``$PROCCLEAN_HARDEN`` is set to anything but "" or "0".
+    """
+    return os.environ.get(HARDEN_ENV, "") not in {"", "0"}
+
+
+def _insn(code: int, k: int, jt: int = 0, jf: int = 0) -> bytes:
+    return struct.pack("=HBBI", code, jt, jf, k)
+
+
+def build_filter(machine: str) -> bytes:
+    """Assemble the seccomp filter for an architecture.
+
+    Args:
+        machine: One of ``SECCOMP_ARCHES``, as from ``platform.machine()``.
+
+    Returns:
+        bytes: The BPF program, one 8-byte ``sock_filter`` per instruction.
+    """
+    arch, denied, opens = SECCOMP_ARCHES[machine]
+    deny = _insn(BPF_RET_K, SECCOMP_RET_ERRNO | 1)  # EPERM
+    allow = _insn(BPF_RET_K, SECCOMP_RET_ALLOW)
+    program = [
+        _insn(BPF_LD_W_ABS, DATA_ARCH),
+        _insn(BPF_JEQ_K, arch, jt=1),  # Other ABIs, such as i386 on x86_64
+        deny,
+        _insn(BPF_LD_W_ABS, DATA_NR),
+    ]
+    if machine == "x86_64":
+        program += [_insn(BPF_JGE_K, X32_SYSCALL_BIT, jf=1), deny]
+    for nr in denied:
+        program += [_insn(BPF_JEQ_K, nr, jf=1), deny]
+    for nr, arg in opens:
+        program += [
+            _insn(BPF_JEQ_K, nr, jf=4),
+            _insn(BPF_LD_W_ABS, DATA_ARGS + 8 * arg),
+            _insn(BPF_JSET_K, OPEN_WRITE_FLAGS, jf=1),
+            deny,
+            allow,
+        ]
+    program.append(allow)
+    return b"".join(program)
+
+
+def check_hardening() -> None:
+    """Check that scans can be sandboxed here.
+
+    Raises:
+        ProccleanError: With ``SANDBOX_FAILED`` off Linux, on an unsupported
+            architecture, or without seccomp filters in the kernel.
+    """
+    machine = platform.machine()
+    if sys.platform != "linux" or machine not in SECCOMP_ARCHES:
+        msg = f"--harden needs Linux on {' or '.join(SECCOMP_ARCHES)}"
+        raise ProccleanError(ErrorCode.SANDBOX_FAILED, msg, machine=machine)
+    status = Path("/proc/self/status").read_text(encoding="utf-8", errors="replace")
+    if "Seccomp:" not in status:
+        msg = "--harden needs a kernel with seccomp filters"
+        raise ProccleanError(ErrorCode.SANDBOX_FAILED, msg)
+
+
+def _libc_call(name: str, *args: object) -> None:
+    """Call a libc function, raising on a non-zero return.
+
+    Integer arguments are passed as ``unsigned long``, as prctl(2) reads them.
+
+    Raises:
+        OSError: With the errno of the failed call.
+    """
+    libc = ctypes.CDLL(None, use_errno=True)
+    args = tuple(ctypes.c_ulong(a) if isinstance(a, int) else a for a in args)
+    if getattr(libc, name)(*args) != 0:
+        errno = ctypes.get_errno()
+        raise OSError(errno, f"{name}: {os.strerror(errno)}")
+
+
+def drop_capabilities() -> None:
+    """Keep only the capabilities reading other users' /proc files takes.
+
+    A no-op for unprivileged users, who have none to begin with.
+    """
+    header = _CapHeader(LINUX_CAPABILITY_VERSION_3, 0)
+    data = (_CapData * 2)()
+    _libc_call("capget", ctypes.byref(header), data)
+    keep = 1 << CAP_DAC_READ_SEARCH | 1 << CAP_SYS_PTRACE
+    data[0].effective &= keep
+    data[0].permitted &= keep
+    data[0].inheritable = 0
+    data[1].effective = data[1].permitted = data[1].inheritable = 0
+    _libc_call("capset", ctypes.byref(header), data)
+
+
+def install_filter() -> None:
+    """Drop capabilities and confine this process with the seccomp filter.
+
+    Irreversible: only call it in a process about to exit.
+    """
+    drop_capabilities()
+    program = build_filter(platform.machine())
+    fprog = _SockFprog(len(program) // 8, program)
+    _libc_call("prctl", PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0)
+    _libc_call("prctl", PR_SET_SECCOMP, SECCOMP_MODE_FILTER, ctypes.byref(fprog), 0, 0)
+
+
+def run_sandboxed(func: Callable[..., bytes], *args: object) -> bytes:
+    """Run a function in a confined child interpreter.
+
+    Args:
+        func: Work to run under ``install_filter``, returning its result
+            encoded, e.g. as JSON. A module-level function, so the child can
+            import it.
+        *args: Picklable arguments to call it with.
+
+    Returns:
+        bytes: What the function returned.
+
+    Raises:
+        ProccleanError: With ``SANDBOX_FAILED`` if the sandbox cannot be set
+            up, or the function fails or the child dies.
+    """
+    # The child imports the function from the same paths the parent did
+    env = {**os.environ, "PYTHONPATH": os.pathsep.join(p for p in sys.path if p)}
+    child = subprocess.run(
+        [sys.executable, "-m", __name__],
+        input=pickle.dumps((func, args)),
+        capture_output=True,
+        env=env,
+        check=False,
+    )
+    payload = child.stdout
+    if payload[:1] == b"\0":
+        return payload[1:]
+    reason = payload[1:].decode(errors="replace") or (
+        child.stderr.decode(errors="replace").strip()
+        or f"exit status {child.returncode}"
+    )
+    msg = f"Sandboxed scan failed: {reason}"
+    raise ProccleanError(ErrorCode.SANDBOX_FAILED, msg)
+
+
+def _child() -> None:
+    """Run the pickled call from stdin under the filter, result to stdout."""
+    out = sys.stdout.buffer
+    try:
+        func, args = pickle.load(sys.stdin.buffer)  # noqa: S301 - from the parent
+        install_filter()
+        payload = b"\0" + func(*args)
+    except BaseException as e:  # noqa: BLE001 - reported to the parent
+        payload = b"\1" + f"{type(e).__name__}: {e}".encode()
+    out.write(payload)
+    out.flush()
+
+
+if __name__ == "__main__":
+    _child()
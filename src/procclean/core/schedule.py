@@ -0,0 +1,69 @@
+"""Fixed-rate scheduling for repeated snapshots."""
+
+import re
+import time
+from collections.abc import Callable
+
+# Seconds per --every unit suffix; a bare number is seconds
+INTERVAL_UNITS: dict[str, float] = {"s": 1, "m": 60, "h": 3600}
+
+_INTERVAL_RE = re.compile(r"(\d+(?:\.\d+)?)([smh]?)")
+
+
+def parse_interval(value: str) -> float:
+    """Parse an interval like ``30``, ``30s``, ``5m``, ``1.5h``.
+
+    Args:
+        value: Number with an optional ``s``, ``m`` or ``h`` suffix.
+
+    Returns:
+        float: The interval in seconds.
+
+    Raises:
+        ValueError: If the value is malformed or not positive.
+    """
+    match = _INTERVAL_RE.fullmatch(value.strip().lower())
+    if not match:
+        msg = f"invalid interval '{value}' (expected e.g. 30s, 5m, 1h)"
+        raise ValueError(msg)
+    seconds = float(match[1]) * INTERVAL_UNITS[match[2] or "s"]
+    if seconds <= 0:
+        msg = f"interval '{value}' must be positive"
+        raise ValueError(msg)
+    return seconds
+
+
+def run_every(
+    interval: float,
+    tick: Callable[[int], None],
+    count: int | None = None,
+    sleep: Callable[[float], None] = time.sleep,
+    clock: Callable[[], float] = time.monotonic,
+) -> int:
+    """Call ``tick`` at a fixed rate until ``count`` ticks or interruption.
+
+    Ticks are scheduled relative to the first one, so the time a tick takes
+    does not push later ticks back. Slots missed by a tick that overran the
+    interval are skipped rather than run back to back.
+
+    Args:
+        interval: Seconds between ticks.
+        tick: Called with the 0-based tick number.
+        count: Stop after this many ticks; None runs until interrupted.
+        sleep: Sleep function (injectable for tests).
+        clock: Monotonic clock (injectable for tests).
+
+    Returns:
+        int: The number of ticks run.
+    """
+    start = clock()
+    ticks = 0
+    while count is None or ticks < count:
+        tick(ticks)
+        ticks += 1
+        if count is not None and ticks >= count:
+            break
+        elapsed = clock() - start
+        slot = max(ticks, int(elapsed // interval) + 1)
+        sleep(max(0.0, start + slot * interval - clock()))
+    return ticks
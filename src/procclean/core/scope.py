@@ -0,0 +1,361 @@
+"""Cgroup scopes that bound which processes may be listed and killed."""
+
+import os
+import re
+import signal
+from collections.abc import Iterator
+from typing import Any
+
+import psutil
+
+from .errors import ErrorCode, ProccleanError
+from .process import DEFAULT_SOURCE, get_cgroup
+from .source import ProcessSource
+
+# Environment variable passing --scope to the TUI
+SCOPE_ENV = "PROCCLEAN_SCOPE"
+SCOPES = ("user-slice", "session", "system")
+
+_SESSION_RE = re.compile(r"^(.*/session-[^/]+\.scope)(?:/|$)")
+
+
+def scope_cgroup(scope: str) -> str:
+    """Resolve a scope name to the cgroup subtree it covers.
+
+    Args:
+        scope: One of ``SCOPES``.
+
+    Returns:
+        str: cgroup path prefix, e.g. ``/user.slice/user-1000.slice``.
+
+    Raises:
+        ProccleanError: With ``INVALID_ARGUMENT`` for unknown scopes or when
+            no login session can be found.
+    """
+    user_slice = f"/user.slice/user-{os.getuid()}.slice"
+    match scope:
+        case "user-slice":
+            return user_slice
+        case "system":
+            return "/system.slice"
+        case "session":
+            if match := _SESSION_RE.match(get_cgroup(os.getpid())):
+                return match.group(1)
+            if session_id := os.environ.get("XDG_SESSION_ID"):
+                return f"{user_slice}/session-{session_id}.scope"
+            msg = "Not running inside a login session; try --scope user-slice"
+            raise ProccleanError(ErrorCode.INVALID_ARGUMENT, msg, scope=scope)
+        case _:
+            msg = f"Unknown scope '{scope}' (expected {', '.join(SCOPES)})"
+            raise ProccleanError(ErrorCode.INVALID_ARGUMENT, msg, scope=scope)
+
+
+def in_cgroup(cgroup: str, prefix: str) -> bool:
+    """Check whether a cgroup path lies within a subtree.
+
+    Returns:
+        bool: True if ``cgroup`` is ``prefix`` or below it.
+    """
+    return cgroup == prefix or cgroup.startswith(prefix.rstrip("/") + "/")
+
+
+class ScopedSource:
+    """Process source restricted to one cgroup subtree.
+
+    Processes outside the subtree are hidden from scans and cannot be
+    signaled, so every command built on the source stays inside the scope.
+    """
+
+    def __init__(self, source: ProcessSource, cgroup: str, name: str = "") -> None:
+        """Initialize the scope.
+
+        Args:
+            source: Source supplying process data.
+            cgroup: cgroup path prefix of the scope.
+            name: Scope name for display, e.g. "user-slice".
+        """
+        self.source = source
+        self.cgroup_prefix = cgroup
+        self.name = name or cgroup
+
+    def contains(self, pid: int) -> bool:
+        """Check whether a process is inside the scope.
+
+        Returns:
+            bool: True if the process's cgroup is within the scope.
+        """
+        return in_cgroup(self.source.cgroup(pid), self.cgroup_prefix)
+
+    def current_user(self) -> str:
+        """Return the wrapped source's current user.
+
+        Returns:
+            str: Login name.
+        """
+        return self.source.current_user()
+
+    def iter_processes(self) -> Iterator[dict[str, Any]]:
+        """Yield the wrapped source's processes that are inside the scope.
+
+        Yields:
+            dict[str, Any]: A psutil-style info mapping.
+        """
+        for info in self.source.iter_processes():
+            if self.contains(info["pid"]):
+                yield info
+
+    def sample_cpu(self, interval: float) -> None:
+        """Take a first CPU sample through the wrapped source."""
+        self.source.sample_cpu(interval)
+
+    def parent_name(self, ppid: int) -> str:
+        """Return the parent's name from the wrapped source.
+
+        Returns:
+            str: Parent process name.
+        """
+        return self.source.parent_name(ppid)
+
+    def cwd(self, pid: int) -> str:
+        """Return the working directory from the wrapped source.
+
+        Returns:
+            str: Working directory.
+        """
+        return self.source.cwd(pid)
+
+    def in_tmux(self, pid: int) -> bool:
+        """Return the tmux flag from the wrapped source.
+
+        Returns:
+            bool: True if running inside tmux.
+        """
+        return self.source.in_tmux(pid)
+
+    def environ(self, pid: int) -> dict[str, str]:
+        """Return the environment from the wrapped source.
+
+        Returns:
+            dict[str, str]: Environment variables.
+        """
+        return self.source.environ(pid)
+
+    def exe(self, pid: int) -> str:
+        """Return the executable path from the wrapped source.
+
+        Returns:
+            str: Executable path.
+        """
+        return self.source.exe(pid)
+
+    def exe_deleted(self, pid: int) -> bool:
+        """Return the stale-executable flag from the wrapped source.
+
+        Returns:
+            bool: True if the executable is stale.
+        """
+        return self.source.exe_deleted(pid)
+
+    def cwd_deleted(self, pid: int) -> bool:
+        """Return the removed-cwd flag from the wrapped source.
+
+        Returns:
+            bool: True if the cwd no longer exists.
+        """
+        return self.source.cwd_deleted(pid)
+
+    def deleted_libraries(self, pid: int) -> tuple[str, ...]:
+        """Return the replaced libraries from the wrapped source.
+
+        Returns:
+            tuple[str, ...]: Paths of the deleted libraries.
+        """
+        return self.source.deleted_libraries(pid)
+
+    def cgroup(self, pid: int) -> str:
+        """Return the cgroup path from the wrapped source.
+
+        Returns:
+            str: cgroup path.
+        """
+        return self.source.cgroup(pid)
+
+    def session_id(self, pid: int) -> int:
+        """Return the session ID from the wrapped source.
+
+        Returns:
+            int: Session ID.
+        """
+        return self.source.session_id(pid)
+
+    def process_group(self, pid: int) -> int:
+        """Return the process group ID from the wrapped source.
+
+        Returns:
+            int: Process group ID.
+        """
+        return self.source.process_group(pid)
+
+    def kernel_thread(self, pid: int) -> bool:
+        """Check the wrapped source for a kernel thread.
+
+        Returns:
+            bool: True for kernel threads.
+        """
+        return self.source.kernel_thread(pid)
+
+    def sched(self, pid: int) -> tuple[int, str, int]:
+        """Return the scheduling attributes from the wrapped source.
+
+        Returns:
+            tuple[int, str, int]: Nice value, policy and realtime priority.
+        """
+        return self.source.sched(pid)
+
+    def privileges(self, pid: int) -> tuple[str, ...]:
+        """Return the privilege reasons from the wrapped source.
+
+        Returns:
+            tuple[str, ...]: Reasons the process is privileged.
+        """
+        return self.source.privileges(pid)
+
+    def confinement(self, pid: int) -> tuple[str, bool]:
+        """Return the confinement status from the wrapped source.
+
+        Returns:
+            tuple[str, bool]: Seccomp mode and no_new_privs.
+        """
+        return self.source.confinement(pid)
+
+    def media_devices(self, pid: int) -> tuple[str, ...]:
+        """Return the open media devices from the wrapped source.
+
+        Returns:
+            tuple[str, ...]: Media device kinds.
+        """
+        return self.source.media_devices(pid)
+
+    def open_files(self, pid: int) -> dict[int, str]:
+        """Return the open file targets from the wrapped source.
+
+        Returns:
+            dict[int, str]: Targets by descriptor number.
+        """
+        return self.source.open_files(pid)
+
+    def listening_ports(self, pid: int) -> tuple[str, ...]:
+        """Return the listening sockets from the wrapped source.
+
+        Returns:
+            tuple[str, ...]: E.g. "tcp 0.0.0.0:8080".
+        """
+        return self.source.listening_ports(pid)
+
+    def memory_breakdown(self, pid: int) -> dict[str, float]:
+        """Return the memory counters from the wrapped source.
+
+        Returns:
+            dict[str, float]: MB by counter.
+        """
+        return self.source.memory_breakdown(pid)
+
+    def run_stop_command(self, command: list[str]) -> None:
+        """Run a stop command through the wrapped source."""
+        self.source.run_stop_command(command)
+
+    def spawn(
+        self, argv: list[str], cwd: str, env: dict[str, str], user: str
+    ) -> int:
+        """Start a command through the wrapped source.
+
+        Returns:
+            int: PID of the new process.
+        """
+        return self.source.spawn(argv, cwd, env, user)
+
+    def alive(self, pid: int) -> bool:
+        """Check through the wrapped source whether a process still runs.
+
+        Returns:
+            bool: True if it exists and is not a zombie.
+        """
+        return self.source.alive(pid)
+
+    def send_signal(self, pid: int, force: bool) -> None:
+        """Signal a process if it is inside the scope.
+
+        Raises:
+            psutil.AccessDenied: If the process is outside the scope.
+        """
+        if not self.contains(pid):
+            raise psutil.AccessDenied(pid, msg=f"outside scope {self.name}")
+        self.source.send_signal(pid, force)
+
+    def send(self, pid: int, sig: signal.Signals) -> None:
+        """Send a signal to a process if it is inside the scope.
+
+        Raises:
+            psutil.AccessDenied: If the process is outside the scope.
+        """
+        if not self.contains(pid):
+            raise psutil.AccessDenied(pid, msg=f"outside scope {self.name}")
+        self.source.send(pid, sig)
+
+    def signal_group(self, pgid: int, sig: signal.Signals) -> None:
+        """Signal a process group if all of its members are inside the scope.
+
+        Raises:
+            psutil.AccessDenied: If any member is outside the scope.
+        """
+        for info in self.source.iter_processes():
+            pid = info["pid"]
+            if self.source.process_group(pid) == pgid and not self.contains(pid):
+                raise psutil.AccessDenied(pgid, msg=f"outside scope {self.name}")
+        self.source.signal_group(pgid, sig)
+
+    def renice(self, pid: int, nice: int) -> None:
+        """Renice a process if it is inside the scope.
+
+        Raises:
+            psutil.AccessDenied: If the process is outside the scope.
+        """
+        if not self.contains(pid):
+            raise psutil.AccessDenied(pid, msg=f"outside scope {self.name}")
+        self.source.renice(pid, nice)
+
+    def ionice(self, pid: int, ioclass: int, level: int | None) -> None:
+        """Set a process's I/O priority if it is inside the scope.
+
+        Raises:
+            psutil.AccessDenied: If the process is outside the scope.
+        """
+        if not self.contains(pid):
+            raise psutil.AccessDenied(pid, msg=f"outside scope {self.name}")
+        self.source.ionice(pid, ioclass, level)
+
+
+def scoped_source(
+    scope: str | None, source: ProcessSource | None = None
+) -> ProcessSource | None:
+    """Wrap a source in a scope, if one is given.
+
+    Args:
+        scope: Scope name from ``SCOPES``, or None for no restriction.
+        source: Source to wrap; defaults to the live system.
+
+    Returns:
+        ProcessSource | None: The scoped source, or ``source`` unchanged.
+    """
+    if not scope:
+        return source
+    return ScopedSource(source or DEFAULT_SOURCE, scope_cgroup(scope), scope)
+
+
+def scoped_source_from_env() -> ProcessSource | None:
+    """Build a scoped live source if ``PROCCLEAN_SCOPE`` is set.
+
+    Returns:
+        ProcessSource | None: The scoped source, or None.
+    """
+    return scoped_source(os.environ.get(SCOPE_ENV))
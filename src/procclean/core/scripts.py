@@ -0,0 +1,227 @@
+"""User scripts: filters, kill priorities and kill hooks written in Python.
+
+Every ``*.py`` file in the ``scripts`` directory next to the config file
+(``~/.config/procclean/scripts`` by default) runs once at startup, in name
+order, with these decorators in scope:
+
+- ``register_filter(name, description)``: a test on a process becomes a
+  filter preset, usable as ``--filter NAME`` and as a TUI view
+- ``score``: a number added to the kill priority of a process; emergency
+  mode kills higher scores first
+- ``pre_kill``: runs before a process is signaled; returning False vetoes
+  the kill
+- ``post_kill``: runs after the signal with the process, whether it
+  succeeded and the result message
+
+::
+
+    @register_filter("idle-node", "Node processes using no CPU")
+    def idle_node(proc):
+        return proc.name == "node" and proc.cpu_percent < 1
+
+    @pre_kill
+    def spare_prod(proc):
+        return not (proc.cwd or "").startswith("/srv/prod")
+
+Hooks get the ``ProcessInfo`` of the process and must not change it. Scripts
+run with the rights of procclean, like a shell rc file;
+``PROCCLEAN_NO_SCRIPTS`` skips them.
+"""
+
+import os
+from collections.abc import Callable
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any
+
+from procclean.i18n import t
+
+from .config import config_path
+from .errors import ErrorCode, ProccleanError
+from .models import ProcessInfo
+from .registry import FILTERS, FilterRegistry, FilterSpec
+
+NO_SCRIPTS_ENV = "PROCCLEAN_NO_SCRIPTS"
+
+ProcessTest = Callable[[ProcessInfo], bool]
+Scorer = Callable[[ProcessInfo], float]
+PreKillHook = Callable[[ProcessInfo], bool | None]
+PostKillHook = Callable[[ProcessInfo, bool, str], object]
+
+
+def scripts_dir() -> Path:
+    """Locate the user scripts.
+
+    Returns:
+        Path: ``scripts`` next to the config file; it may not exist.
+    """
+    return config_path().parent / "scripts"
+
+
+def _call(hook: str, func: Callable[..., Any], *args: object) -> Any:  # noqa: ANN401
+    """Call a script function, turning its exceptions into typed errors.
+
+    Returns:
+        Any: What the function returned.
+
+    Raises:
+        ProccleanError: With ``SCRIPT_FAILED`` if the function raised.
+    """
+    try:
+        return func(*args)
+    except Exception as e:  # noqa: BLE001 - user code may raise anything
+        msg = t("script-failed", hook=hook, error=e)
+        raise ProccleanError(ErrorCode.SCRIPT_FAILED, msg, hook=hook) from e
+
+
+@dataclass
+class ScriptHooks:
+    """Hooks defined by the loaded scripts, each named ``file:function``."""
+
+    scorers: list[tuple[str, Scorer]] = field(default_factory=list)
+    pre_kill: list[tuple[str, PreKillHook]] = field(default_factory=list)
+    post_kill: list[tuple[str, PostKillHook]] = field(default_factory=list)
+    loaded: list[Path] = field(default_factory=list)
+
+    @property
+    def kill_hooks(self) -> bool:
+        """Whether any hook runs around kills."""
+        return bool(self.pre_kill or self.post_kill)
+
+    def score(self, proc: ProcessInfo) -> float:
+        """Sum the script scores of a process.
+
+        Returns:
+            float: Kill priority adjustment; 0 without scorers.
+
+        Raises:
+            ProccleanError: With ``SCRIPT_FAILED`` if a scorer raises or does
+                not return a number.
+        """
+        total = 0.0
+        for name, scorer in self.scorers:
+            value = _call(name, scorer, proc)
+            try:
+                total += float(value)
+            except (TypeError, ValueError):
+                msg = t("script-failed", hook=name, error=f"not a number: {value!r}")
+                raise ProccleanError(ErrorCode.SCRIPT_FAILED, msg, hook=name) from None
+        return total
+
+    def check_kill(self, proc: ProcessInfo) -> str | None:
+        """Run the pre-kill hooks; a failing hook vetoes the kill too.
+
+        Returns:
+            str | None: Why the kill is vetoed, or None to go ahead.
+        """
+        for name, hook in self.pre_kill:
+            try:
+                allowed = _call(name, hook, proc)
+            except ProccleanError as e:
+                return e.message
+            if allowed is False:
+                return t("kill-vetoed", pid=proc.pid, hook=name)
+        return None
+
+    def after_kill(self, proc: ProcessInfo, ok: bool, message: str) -> list[str]:
+        """Run the post-kill hooks.
+
+        Returns:
+            list[str]: Messages of the hooks that failed.
+        """
+        errors = []
+        for name, hook in self.post_kill:
+            try:
+                _call(name, hook, proc, ok, message)
+            except ProccleanError as e:
+                errors.append(e.message)
+        return errors
+
+
+SCRIPTS = ScriptHooks()
+
+
+def _run_script(path: Path, hooks: ScriptHooks, registry: FilterRegistry) -> None:
+    """Execute one script with the decorators in scope.
+
+    Raises:
+        ProccleanError: With ``INVALID_CONFIG`` if it cannot be read or fails.
+    """
+
+    def name_of(func: Callable[..., object]) -> str:
+        return f"{path.name}:{getattr(func, '__name__', 'hook')}"
+
+    def register_filter(
+        name: str, description: str = ""
+    ) -> Callable[[ProcessTest], ProcessTest]:
+        def register(test: ProcessTest) -> ProcessTest:
+            hook = name_of(test)
+            registry.register(
+                FilterSpec(
+                    name,
+                    description or t("script-filter", file=path.name),
+                    lambda procs, _source, _params: [
+                        p for p in procs if _call(hook, test, p)
+                    ],
+                    view=name,
+                )
+            )
+            return test
+
+        return register
+
+    def score(func: Scorer) -> Scorer:
+        hooks.scorers.append((name_of(func), func))
+        return func
+
+    def pre_kill(func: PreKillHook) -> PreKillHook:
+        hooks.pre_kill.append((name_of(func), func))
+        return func
+
+    def post_kill(func: PostKillHook) -> PostKillHook:
+        hooks.post_kill.append((name_of(func), func))
+        return func
+
+    namespace: dict[str, Any] = {
+        "__name__": f"procclean_script_{path.stem}",
+        "__file__": str(path),
+        "register_filter": register_filter,
+        "score": score,
+        "pre_kill": pre_kill,
+        "post_kill": post_kill,
+    }
+    try:
+        code = compile(path.read_bytes(), str(path), "exec")
+        exec(code, namespace)  # noqa: S102 - running user scripts is the point
+    except Exception as e:  # noqa: BLE001 - user code may raise anything
+        msg = t("script-load-failed", path=str(path), error=e)
+        raise ProccleanError(ErrorCode.INVALID_CONFIG, msg, path=str(path)) from e
+
+
+def load_scripts(
+    directory: Path | None = None,
+    hooks: ScriptHooks = SCRIPTS,
+    registry: FilterRegistry = FILTERS,
+) -> ScriptHooks:
+    """Run the user scripts that have not run yet.
+
+    Call this before building the CLI parser or the TUI, so script filters
+    show up there.
+
+    Args:
+        directory: Where the scripts are; defaults to ``scripts_dir()``.
+        hooks: Collects the hooks; defaults to the global ``SCRIPTS`` that
+            kills and emergency mode consult.
+        registry: Receives the filters; defaults to ``FILTERS``.
+
+    Returns:
+        ScriptHooks: ``hooks``, with the new scripts' hooks added.
+    """
+    if os.environ.get(NO_SCRIPTS_ENV):
+        return hooks
+    directory = directory or scripts_dir()
+    for path in sorted(directory.glob("*.py")):
+        if path not in hooks.loaded:
+            hooks.loaded.append(path)  # Never run twice, even after a failure
+            _run_script(path, hooks, registry)
+    return hooks
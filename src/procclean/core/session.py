@@ -0,0 +1,152 @@
+"""Recording and replay of interactive TUI sessions.
+
+A recording is a JSONL file with one event per line. Each event has a
+``kind`` and ``t``, the seconds since recording started:
+
+- ``snapshot``: the process list and memory summary shown after a refresh
+- ``action``: a key-bound action, with the PID under the cursor
+- ``view``, ``search``, ``select``: state changes made outside key bindings
+- ``kill``: a confirmed kill, with the targeted PIDs and the results
+- ``queue``: a reviewed batch of queued actions, with the results
+- ``restart``: a confirmed restart, with the targeted PIDs and the results
+"""
+
+import json
+import time
+from dataclasses import asdict, fields
+from pathlib import Path
+from typing import Any, TextIO
+
+from .errors import ErrorCode, ProccleanError
+from .models import ProcessInfo
+from .redact import redact_process
+
+# Environment variables passing --record / --replay to the TUI
+RECORD_ENV = "PROCCLEAN_RECORD"
+REPLAY_ENV = "PROCCLEAN_REPLAY"
+
+EVENT_KINDS = frozenset({
+    "snapshot",
+    "action",
+    "view",
+    "search",
+    "select",
+    "kill",
+    "queue",
+    "restart",
+})
+
+_PROCESS_FIELDS = frozenset(f.name for f in fields(ProcessInfo))
+
+
+class SessionRecorder:
+    """Append session events to a JSONL file, flushing after each event."""
+
+    def __init__(self, path: Path, redact: bool = True) -> None:
+        """Open the recording for writing, truncating any existing file.
+
+        Args:
+            path: Destination file.
+            redact: Mask secrets in recorded command lines.
+
+        Raises:
+            ProccleanError: If the file cannot be created.
+        """
+        try:
+            self._file: TextIO = path.open("w", encoding="utf-8")
+        except OSError as e:
+            raise ProccleanError(
+                ErrorCode.INVALID_ARGUMENT,
+                f"Cannot write recording {path}: {e.strerror}",
+                path=str(path),
+            ) from e
+        self.path = path
+        self.redact = redact
+        self._start = time.monotonic()
+
+    def record(self, kind: str, **data: object) -> None:
+        """Write one event.
+
+        Args:
+            kind: One of ``EVENT_KINDS``.
+            **data: JSON-serializable event fields.
+        """
+        event = {"t": round(time.monotonic() - self._start, 3), "kind": kind, **data}
+        self._file.write(json.dumps(event) + "\n")
+        self._file.flush()
+
+    def snapshot(self, procs: list[ProcessInfo], memory: dict[str, float]) -> None:
+        """Record the data shown after a refresh.
+
+        Args:
+            procs: Processes as fetched.
+            memory: Memory summary as fetched.
+        """
+        if self.redact:
+            procs = [redact_process(p) for p in procs]
+        self.record("snapshot", processes=[asdict(p) for p in procs], memory=memory)
+
+    def close(self) -> None:
+        """Close the recording file."""
+        self._file.close()
+
+
+def _invalid(path: Path, line: int, reason: str) -> ProccleanError:
+    return ProccleanError(
+        ErrorCode.INVALID_ARGUMENT,
+        f"Invalid recording {path}, line {line}: {reason}",
+        path=str(path),
+        line=line,
+    )
+
+
+def load_session(path: Path) -> list[dict[str, Any]]:
+    """Read and validate a recording.
+
+    Args:
+        path: File written by ``SessionRecorder``.
+
+    Returns:
+        list[dict[str, Any]]: Events in recorded order.
+
+    Raises:
+        ProccleanError: If the file cannot be read or an event is malformed.
+    """
+    try:
+        lines = path.read_text(encoding="utf-8").splitlines()
+    except OSError as e:
+        raise ProccleanError(
+            ErrorCode.INVALID_ARGUMENT,
+            f"Cannot read recording {path}: {e.strerror}",
+            path=str(path),
+        ) from e
+
+    events: list[dict[str, Any]] = []
+    for number, line in enumerate(lines, start=1):
+        if not line.strip():
+            continue
+        try:
+            event = json.loads(line)
+        except json.JSONDecodeError as e:
+            raise _invalid(path, number, e.msg) from e
+        if not isinstance(event, dict) or event.get("kind") not in EVENT_KINDS:
+            raise _invalid(path, number, "unknown event kind")
+        if not isinstance(event.get("t"), int | float):
+            raise _invalid(path, number, "missing timestamp")
+        events.append(event)
+    return events
+
+
+def snapshot_processes(event: dict[str, Any]) -> list[ProcessInfo]:
+    """Rebuild the process list stored in a snapshot event.
+
+    Fields unknown to this version are dropped, so older builds can replay
+    newer recordings.
+
+    Returns:
+        list[ProcessInfo]: The recorded processes.
+    """
+    return [
+        ProcessInfo(**{k: v for k, v in proc.items() if k in _PROCESS_FIELDS})
+        for proc in event.get("processes", [])
+    ]
@@ -0,0 +1,335 @@
+"""The effective configuration, and what is wrong with it.
+
+Every setting is read where it is used, each module validating its own
+table. This module looks at the file as a whole: ``effective_settings``
+merges the built-in values of the fixed settings with the file and the
+environment, naming where each one comes from, and ``config_problems``
+collects every malformed, unknown or misspelled setting at once, for
+``procclean config validate``, ``config edit`` and ``doctor``.
+``set_setting``, ``set_table`` and ``save_config`` write a setting or table
+back, as ``procclean fmt --interactive`` and the TUI filter panel do, keeping
+the rest of the file as it was.
+"""
+
+import difflib
+import json
+import os
+import re
+import shutil
+import tempfile
+from collections.abc import Callable, Iterable, Iterator
+from contextlib import contextmanager, nullcontext
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Any
+
+from .audit import AUDIT_ENV
+from .cap import DEFAULT_ROW_CAP
+from .config import CONFIG_ENV, config_path, load_config
+from .errors import ErrorCode, ProccleanError
+from .killhooks import KILL_HOOK_TIMEOUT
+
+# Tables of the config file; the ones listed with keys take no others
+CONFIG_TABLES: dict[str, tuple[str, ...] | None] = {
+    "defaults": None,  # Preset parameters too; load_defaults checks the keys
+    "display": ("natural_sort", "row_cap"),
+    "kill": (
+        "protected",
+        "no_confirm",
+        "data_loss",
+        "pre_kill",
+        "post_kill",
+        "hook_failure",
+        "hook_timeout",
+    ),
+    "audit": ("enabled", "path"),
+    "highlight": None,
+    "layouts": None,
+    "presets": None,
+    "supervisors": None,
+    "quotas": None,
+    "budgets": None,
+    "emergency": None,
+}
+# Values of fixed settings that are not in the file
+BUILTIN_SETTINGS: dict[str, Any] = {
+    "defaults.sort": "memory",
+    "defaults.min_memory": 5.0,
+    "display.natural_sort": True,
+    "display.row_cap": DEFAULT_ROW_CAP,
+    "kill.protected": [],
+    "kill.no_confirm": [],
+    "kill.data_loss": [],
+    "kill.hook_failure": "abort",
+    "kill.hook_timeout": KILL_HOOK_TIMEOUT,
+    "audit.enabled": True,
+}
+# Settings an environment variable overrides, when it is set
+ENV_SETTINGS = {"audit.path": AUDIT_ENV}
+BUILTIN_SOURCE = "default"
+
+
+@dataclass(frozen=True, slots=True)
+class Setting:
+    """One effective setting."""
+
+    key: str  # Dotted, e.g. "display.row_cap"
+    value: Any
+    source: str  # BUILTIN_SOURCE, the config file or "$VARIABLE"
+
+    def to_dict(self) -> dict[str, Any]:
+        """Describe the setting for JSON output.
+
+        Returns:
+            dict[str, Any]: Key, value and source.
+        """
+        return {"key": self.key, "value": self.value, "source": self.source}
+
+
+def flatten_config(table: dict[str, Any], prefix: str = "") -> dict[str, Any]:
+    """Flatten nested tables into dotted keys.
+
+    Arrays, including arrays of tables such as ``[[budgets]]``, stay whole.
+
+    Returns:
+        dict[str, Any]: Values by dotted key, e.g. ``"presets.dev.cwd"``.
+    """
+    flat: dict[str, Any] = {}
+    for key, value in table.items():
+        if isinstance(value, dict):
+            flat |= flatten_config(value, f"{prefix}{key}.")
+        else:
+            flat[f"{prefix}{key}"] = value
+    return flat
+
+
+def effective_settings() -> list[Setting]:
+    """Merge built-in values, the config file and the environment.
+
+    Returns:
+        list[Setting]: Every configured or built-in setting, sorted by key.
+
+    Raises:
+        ProccleanError: With ``INVALID_CONFIG`` if the file cannot be parsed.
+    """
+    path = str(config_path())
+    settings = {k: Setting(k, v, BUILTIN_SOURCE) for k, v in BUILTIN_SETTINGS.items()}
+    for key, value in flatten_config(load_config()).items():
+        settings[key] = Setting(key, value, path)
+    for key, var in ENV_SETTINGS.items():
+        if (value := os.environ.get(var)) is not None:
+            settings[key] = Setting(key, value, f"${var}")
+    return sorted(settings.values(), key=lambda s: s.key)
+
+
+def _unknown(kind: str, name: str, choices: Iterable[str]) -> ProccleanError:
+    """Build the error for an unknown table or key, suggesting a close match.
+
+    Returns:
+        ProccleanError: With ``INVALID_CONFIG``.
+    """
+    msg = f"Unknown {kind} {name}"
+    if close := difflib.get_close_matches(name.rpartition(".")[2], choices, n=1):
+        suggestion = f"{name.rpartition('.')[0]}.{close[0]}".lstrip(".")
+        msg += f" (did you mean {suggestion}?)"
+    return ProccleanError(ErrorCode.INVALID_CONFIG, msg, key=name)
+
+
+def unknown_settings(config: dict[str, Any]) -> list[ProccleanError]:
+    """Find tables and fixed-table keys procclean does not know.
+
+    Args:
+        config: Parsed config file.
+
+    Returns:
+        list[ProccleanError]: One ``INVALID_CONFIG`` error per unknown name,
+        suggesting the closest known one.
+    """
+    errors = []
+    for name, table in config.items():
+        if name not in CONFIG_TABLES:
+            errors.append(_unknown("table", name, CONFIG_TABLES))
+        elif (keys := CONFIG_TABLES[name]) is not None and isinstance(table, dict):
+            errors.extend(
+                _unknown("setting", f"{name}.{key}", keys)
+                for key in table
+                if key not in keys
+            )
+    return errors
+
+
+@contextmanager
+def config_override(path: Path) -> Iterator[None]:
+    """Read every setting from another file inside the block.
+
+    Args:
+        path: Config file to use, such as a copy being edited.
+
+    Yields:
+        None: Inside the block.
+    """
+    previous = os.environ.get(CONFIG_ENV)
+    os.environ[CONFIG_ENV] = str(path)
+    load_config.cache_clear()
+    try:
+        yield
+    finally:
+        if previous is None:
+            del os.environ[CONFIG_ENV]
+        else:
+            os.environ[CONFIG_ENV] = previous
+        load_config.cache_clear()
+
+
+def config_problems(
+    validators: Iterable[Callable[[], object]] = (), path: Path | None = None
+) -> list[ProccleanError]:
+    """Collect everything wrong with the config file.
+
+    Args:
+        validators: Loaders of settings, each raising ``ProccleanError`` for
+            a malformed one.
+        path: File to check instead of the configured one.
+
+    Returns:
+        list[ProccleanError]: A parse error alone, else unknown names followed
+        by the error of each failing validator; empty for a valid file.
+    """
+    with config_override(path) if path else nullcontext():
+        try:
+            config = load_config()
+        except ProccleanError as e:
+            return [e]
+        problems = unknown_settings(config)
+        for validate in validators:
+            try:
+                validate()
+            except ProccleanError as e:
+                problems.append(e)
+        return problems
+
+
+def _find_table(lines: list[str], table: str) -> tuple[int, int] | None:
+    """Find a table in the lines of a config file.
+
+    Returns:
+        tuple[int, int] | None: Index of its header and of the next header
+        (or the end); None if the file has no such table.
+    """
+    start = next(
+        (i for i, line in enumerate(lines) if line.strip() == f"[{table}]"), None
+    )
+    if start is None:
+        return None
+    end = next(
+        (i for i in range(start + 1, len(lines)) if lines[i].lstrip().startswith("[")),
+        len(lines),
+    )
+    return start, end
+
+
+def _append_table(text: str, table: str, settings: list[str]) -> str:
+    """Add a table to the end of a config file, after a blank line.
+
+    Returns:
+        str: The updated contents.
+    """
+    head = text.rstrip("\n")
+    added = "\n".join([f"[{table}]", *settings]) + "\n"
+    return f"{head}\n\n{added}" if head else added
+
+
+def set_setting(text: str, key: str, value: str) -> str:
+    """Set a string setting in the text of a config file.
+
+    Comments, order and every other setting stay as they are: an existing
+    line (or multi-line array) for the key is replaced, else the line is
+    added to the end of its table, which is appended if missing.
+
+    Args:
+        text: Contents of the config file.
+        key: Dotted key in a top-level table, e.g. "defaults.columns".
+        value: New value.
+
+    Returns:
+        str: The updated contents.
+    """
+    table, _, name = key.partition(".")
+    setting = f"{name} = {json.dumps(value)}"  # A valid TOML basic string
+    lines = text.splitlines()
+    if (found := _find_table(lines, table)) is None:
+        return _append_table(text, table, [setting])
+    start, end = found
+    assignment = re.compile(rf"\s*{re.escape(name)}\s*=\s*(.*)")
+    for i in range(start + 1, end):
+        if match := assignment.match(lines[i]):
+            last = i
+            if match[1].startswith("[") and "]" not in match[1]:
+                last = next((j for j in range(i + 1, end) if "]" in lines[j]), i)
+            lines[i : last + 1] = [setting]
+            break
+    else:
+        while end > start + 1 and not lines[end - 1].strip():
+            end -= 1
+        lines.insert(end, setting)
+    return "\n".join(lines) + "\n"
+
+
+def set_table(text: str, table: str, values: dict[str, Any]) -> str:
+    """Set every setting of a table in the text of a config file.
+
+    The table, if present, is replaced where it is, else appended; the rest
+    of the file stays as it was.
+
+    Args:
+        text: Contents of the config file.
+        table: Dotted name of the table, e.g. "presets.node".
+        values: Its settings: strings, numbers and booleans.
+
+    Returns:
+        str: The updated contents.
+    """
+    # JSON spells these the way TOML does
+    settings = [f"{key} = {json.dumps(value)}" for key, value in values.items()]
+    lines = text.splitlines()
+    if (found := _find_table(lines, table)) is None:
+        return _append_table(text, table, settings)
+    start, end = found
+    while end > start + 1 and not lines[end - 1].strip():
+        end -= 1  # Keep the blank lines before the next table
+    lines[start:end] = [f"[{table}]", *settings]
+    return "\n".join(lines) + "\n"
+
+
+def save_config(
+    text: str, validators: Iterable[Callable[[], object]] = ()
+) -> list[ProccleanError]:
+    """Replace the config file, if the new contents are valid.
+
+    The contents go to a copy next to the file first, which only replaces it
+    once ``config_problems`` finds nothing wrong with it.
+
+    Args:
+        text: New contents.
+        validators: Loaders of settings, as for ``config_problems``.
+
+    Returns:
+        list[ProccleanError]: What is wrong with the contents, which were not
+        saved; empty once saved.
+    """
+    path = config_path()
+    path.parent.mkdir(parents=True, exist_ok=True)
+    fd, name = tempfile.mkstemp(prefix=".config-", suffix=".toml", dir=path.parent)
+    draft = Path(name)
+    try:
+        with os.fdopen(fd, "w", encoding="utf-8") as f:
+            f.write(text)
+        if problems := config_problems(validators, draft):
+            return problems
+        if path.exists():
+            shutil.copymode(path, draft)
+        draft.replace(path)
+        load_config.cache_clear()
+        return []
+    finally:
+        draft.unlink(missing_ok=True)
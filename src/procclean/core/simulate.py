@@ -0,0 +1,399 @@
+"""Simulated kill backend with deterministic fault injection."""
+
+import os
+import signal
+from collections.abc import Iterator
+from dataclasses import dataclass
+from typing import Any
+
+import psutil
+
+from .process import DEFAULT_SOURCE
+from .source import ProcessSource
+
+# Environment variable enabling simulation (value is a fault spec)
+SIMULATE_ENV = "PROCCLEAN_SIMULATE"
+
+
+@dataclass(frozen=True, slots=True)
+class FaultPlan:
+    """Which simulated kill attempts fail, counted from 1.
+
+    A value of 0 disables that fault. ESRCH is checked before EPERM when both
+    hit the same attempt.
+    """
+
+    eperm_every: int = 0  # Every Nth attempt raises AccessDenied
+    esrch_every: int = 0  # Every Nth attempt raises NoSuchProcess
+
+    def fault_for(self, attempt: int) -> str | None:
+        """Return the fault injected on an attempt.
+
+        Args:
+            attempt: 1-based kill attempt number.
+
+        Returns:
+            "esrch", "eperm", or None if the attempt succeeds.
+        """
+        if self.esrch_every and attempt % self.esrch_every == 0:
+            return "esrch"
+        if self.eperm_every and attempt % self.eperm_every == 0:
+            return "eperm"
+        return None
+
+
+def parse_fault_plan(spec: str) -> FaultPlan:
+    """Parse a fault spec like ``"eperm:3,esrch:5"``.
+
+    An empty spec (or ``"1"``/``"none"``) means every kill succeeds.
+
+    Args:
+        spec: Comma-separated ``fault:N`` pairs.
+
+    Returns:
+        FaultPlan: The parsed plan.
+
+    Raises:
+        ValueError: If a fault name or interval is invalid.
+    """
+    every: dict[str, int] = {}
+    for raw in spec.split(","):
+        part = raw.strip().lower()
+        if part in {"", "1", "none"}:
+            continue
+        name, _, value = part.partition(":")
+        if name not in {"eperm", "esrch"}:
+            msg = f"Unknown fault '{name}' (expected eperm or esrch)"
+            raise ValueError(msg)
+        if not value.isdigit() or int(value) < 1:
+            msg = f"Fault '{name}' needs a positive interval, e.g. {name}:3"
+            raise ValueError(msg)
+        every[name] = int(value)
+    return FaultPlan(
+        eperm_every=every.get("eperm", 0), esrch_every=every.get("esrch", 0)
+    )
+
+
+class SimulatedSource:
+    """Process source that reads from a real source but never sends signals.
+
+    Successfully "killed" PIDs are hidden from later scans so refreshes look
+    like the real thing. Stop commands are recorded in ``commands``, nice
+    changes in ``reniced``, I/O priorities in ``ioniced`` and respawns in
+    ``spawned`` instead of being applied.
+    """
+
+    def __init__(self, source: ProcessSource, plan: FaultPlan | None = None) -> None:
+        """Initialize the simulation.
+
+        Args:
+            source: Source supplying process data.
+            plan: Fault pattern; defaults to no faults.
+        """
+        self.source = source
+        self.plan = plan or FaultPlan()
+        self.attempts = 0
+        self.killed: set[int] = set()
+        self.reniced: dict[int, int] = {}
+        self.ioniced: dict[int, tuple[int, int | None]] = {}
+        self.sent: list[tuple[int, signal.Signals]] = []
+        self.commands: list[list[str]] = []
+        self.spawned: list[list[str]] = []
+
+    def current_user(self) -> str:
+        """Return the wrapped source's current user.
+
+        Returns:
+            str: Login name.
+        """
+        return self.source.current_user()
+
+    def iter_processes(self) -> Iterator[dict[str, Any]]:
+        """Yield the wrapped source's processes minus simulated kills.
+
+        Yields:
+            dict[str, Any]: A psutil-style info mapping.
+        """
+        for info in self.source.iter_processes():
+            if info["pid"] not in self.killed:
+                yield info
+
+    def sample_cpu(self, interval: float) -> None:
+        """Take a first CPU sample through the wrapped source."""
+        self.source.sample_cpu(interval)
+
+    def parent_name(self, ppid: int) -> str:
+        """Return the parent's name from the wrapped source.
+
+        Returns:
+            str: Parent process name.
+        """
+        return self.source.parent_name(ppid)
+
+    def cwd(self, pid: int) -> str:
+        """Return the working directory from the wrapped source.
+
+        Returns:
+            str: Working directory.
+        """
+        return self.source.cwd(pid)
+
+    def in_tmux(self, pid: int) -> bool:
+        """Return the tmux flag from the wrapped source.
+
+        Returns:
+            bool: True if running inside tmux.
+        """
+        return self.source.in_tmux(pid)
+
+    def environ(self, pid: int) -> dict[str, str]:
+        """Return the environment from the wrapped source.
+
+        Returns:
+            dict[str, str]: Environment variables.
+        """
+        return self.source.environ(pid)
+
+    def exe(self, pid: int) -> str:
+        """Return the executable path from the wrapped source.
+
+        Returns:
+            str: Executable path.
+        """
+        return self.source.exe(pid)
+
+    def exe_deleted(self, pid: int) -> bool:
+        """Return the stale-executable flag from the wrapped source.
+
+        Returns:
+            bool: True if the executable is stale.
+        """
+        return self.source.exe_deleted(pid)
+
+    def cwd_deleted(self, pid: int) -> bool:
+        """Return the removed-cwd flag from the wrapped source.
+
+        Returns:
+            bool: True if the cwd no longer exists.
+        """
+        return self.source.cwd_deleted(pid)
+
+    def deleted_libraries(self, pid: int) -> tuple[str, ...]:
+        """Return the replaced libraries from the wrapped source.
+
+        Returns:
+            tuple[str, ...]: Paths of the deleted libraries.
+        """
+        return self.source.deleted_libraries(pid)
+
+    def cgroup(self, pid: int) -> str:
+        """Return the cgroup path from the wrapped source.
+
+        Returns:
+            str: cgroup path.
+        """
+        return self.source.cgroup(pid)
+
+    def session_id(self, pid: int) -> int:
+        """Return the session ID from the wrapped source.
+
+        Returns:
+            int: Session ID.
+        """
+        return self.source.session_id(pid)
+
+    def process_group(self, pid: int) -> int:
+        """Return the process group ID from the wrapped source.
+
+        Returns:
+            int: Process group ID.
+        """
+        return self.source.process_group(pid)
+
+    def kernel_thread(self, pid: int) -> bool:
+        """Check the wrapped source for a kernel thread.
+
+        Returns:
+            bool: True for kernel threads.
+        """
+        return self.source.kernel_thread(pid)
+
+    def sched(self, pid: int) -> tuple[int, str, int]:
+        """Return the scheduling attributes from the wrapped source.
+
+        Returns:
+            tuple[int, str, int]: Nice value, policy and realtime priority.
+        """
+        return self.source.sched(pid)
+
+    def privileges(self, pid: int) -> tuple[str, ...]:
+        """Return the privilege reasons from the wrapped source.
+
+        Returns:
+            tuple[str, ...]: Reasons the process is privileged.
+        """
+        return self.source.privileges(pid)
+
+    def confinement(self, pid: int) -> tuple[str, bool]:
+        """Return the confinement status from the wrapped source.
+
+        Returns:
+            tuple[str, bool]: Seccomp mode and no_new_privs.
+        """
+        return self.source.confinement(pid)
+
+    def media_devices(self, pid: int) -> tuple[str, ...]:
+        """Return the open media devices from the wrapped source.
+
+        Returns:
+            tuple[str, ...]: Media device kinds.
+        """
+        return self.source.media_devices(pid)
+
+    def open_files(self, pid: int) -> dict[int, str]:
+        """Return the open file targets from the wrapped source.
+
+        Returns:
+            dict[int, str]: Targets by descriptor number.
+        """
+        return self.source.open_files(pid)
+
+    def listening_ports(self, pid: int) -> tuple[str, ...]:
+        """Return the listening sockets from the wrapped source.
+
+        Returns:
+            tuple[str, ...]: E.g. "tcp 0.0.0.0:8080".
+        """
+        return self.source.listening_ports(pid)
+
+    def memory_breakdown(self, pid: int) -> dict[str, float]:
+        """Return the memory counters from the wrapped source.
+
+        Returns:
+            dict[str, float]: MB by counter.
+        """
+        return self.source.memory_breakdown(pid)
+
+    def run_stop_command(self, command: list[str]) -> None:
+        """Record a stop command without running it."""
+        self.commands.append(command)
+
+    def spawn(
+        self, argv: list[str], cwd: str, env: dict[str, str], user: str
+    ) -> int:
+        """Record a command without starting it.
+
+        Returns:
+            int: 0, as no process was started.
+        """
+        self.spawned.append(argv)
+        return 0
+
+    def alive(self, pid: int) -> bool:
+        """Check whether a process runs, as far as the simulation goes.
+
+        Returns:
+            bool: False once the simulation killed it.
+        """
+        return pid not in self.killed and self.source.alive(pid)
+
+    def send_signal(self, pid: int, force: bool) -> None:
+        """Pretend to signal a process, failing according to the plan.
+
+        Raises:
+            psutil.NoSuchProcess: On ESRCH attempts or already-killed PIDs.
+            psutil.AccessDenied: On EPERM attempts.
+        """
+        self.attempts += 1
+        if pid in self.killed:
+            raise psutil.NoSuchProcess(pid)
+        match self.plan.fault_for(self.attempts):
+            case "esrch":
+                raise psutil.NoSuchProcess(pid)
+            case "eperm":
+                raise psutil.AccessDenied(pid)
+        self.killed.add(pid)
+
+    def send(self, pid: int, sig: signal.Signals) -> None:
+        """Pretend to send a signal, failing according to the plan.
+
+        The process is not marked as killed: most signals it may survive.
+
+        Raises:
+            psutil.NoSuchProcess: On ESRCH attempts or already-killed PIDs.
+            psutil.AccessDenied: On EPERM attempts.
+        """
+        self.attempts += 1
+        if pid in self.killed:
+            raise psutil.NoSuchProcess(pid)
+        match self.plan.fault_for(self.attempts):
+            case "esrch":
+                raise psutil.NoSuchProcess(pid)
+            case "eperm":
+                raise psutil.AccessDenied(pid)
+        self.sent.append((pid, sig))
+
+    def signal_group(self, pgid: int, sig: signal.Signals) -> None:
+        """Pretend to signal a process group, failing according to the plan.
+
+        SIGTERM and SIGKILL mark the live members as killed.
+
+        Raises:
+            psutil.NoSuchProcess: On ESRCH attempts or groups without live
+                members.
+            psutil.AccessDenied: On EPERM attempts.
+        """
+        self.attempts += 1
+        members = {
+            info["pid"]
+            for info in self.iter_processes()
+            if self.source.process_group(info["pid"]) == pgid
+        }
+        if not members:
+            raise psutil.NoSuchProcess(pgid)
+        match self.plan.fault_for(self.attempts):
+            case "esrch":
+                raise psutil.NoSuchProcess(pgid)
+            case "eperm":
+                raise psutil.AccessDenied(pgid)
+        if sig in {signal.SIGTERM, signal.SIGKILL}:
+            self.killed |= members
+        else:
+            self.sent += [(pid, sig) for pid in sorted(members)]
+
+    def renice(self, pid: int, nice: int) -> None:
+        """Pretend to renice a process.
+
+        Raises:
+            psutil.NoSuchProcess: If the PID was killed in the simulation.
+        """
+        if pid in self.killed:
+            raise psutil.NoSuchProcess(pid)
+        self.reniced[pid] = nice
+
+    def ionice(self, pid: int, ioclass: int, level: int | None) -> None:
+        """Pretend to change a process's I/O priority.
+
+        Raises:
+            psutil.NoSuchProcess: If the PID was killed in the simulation.
+        """
+        if pid in self.killed:
+            raise psutil.NoSuchProcess(pid)
+        self.ioniced[pid] = (ioclass, level)
+
+
+def simulated_source_from_env(
+    source: ProcessSource | None = None,
+) -> SimulatedSource | None:
+    """Build a simulated source if ``PROCCLEAN_SIMULATE`` is set.
+
+    Args:
+        source: Source to simulate over; defaults to the live system.
+
+    Returns:
+        SimulatedSource | None: Simulation over ``source``, or None.
+    """
+    spec = os.environ.get(SIMULATE_ENV)
+    if spec is None:
+        return None
+    return SimulatedSource(source or DEFAULT_SOURCE, parse_fault_plan(spec))
@@ -0,0 +1,86 @@
+"""Proportional and unique memory, for the ``pss_mb`` and ``uss_mb`` columns.
+
+RSS counts every resident page a process maps, including pages it shares
+with others. Forked workers (gunicorn, php-fpm, Chromium renderers) share
+most of their memory with the parent, so their RSS adds up to far more than
+the machine has. The kernel's ``/proc/<pid>/smaps_rollup`` splits it:
+
+- USS: private pages, freed when the process exits.
+- Shared: pages mapped by other processes too.
+- PSS: private pages plus an even share of each shared page; PSS sums to the
+  memory actually in use.
+- Swap: pages swapped out, which RSS leaves out.
+
+Reading it walks the page tables of the process, so ``list`` only does so
+for the columns above (see ``FieldMask.SMAPS``). Like the ports, only
+processes whose smaps are readable get values: your own, or all with root.
+"""
+
+from .models import ProcessInfo
+from .process import DEFAULT_SOURCE
+from .source import ProcessSource
+
+
+def attach_smaps(
+    procs: list[ProcessInfo], source: ProcessSource | None = None
+) -> list[ProcessInfo]:
+    """Fill ``pss_mb``, ``uss_mb``, ``shared_mb`` and ``swap_mb``.
+
+    Counters the source cannot read stay None.
+
+    Args:
+        procs: Processes to annotate in place.
+        source: Process backend used to read the memory counters.
+
+    Returns:
+        list[ProcessInfo]: The same processes, for chaining.
+    """
+    source = source or DEFAULT_SOURCE
+    for proc in procs:
+        counters = source.memory_breakdown(proc.pid)
+        proc.pss_mb = counters.get("pss")
+        proc.uss_mb = counters.get("uss")
+        proc.shared_mb = counters.get("shared")
+        proc.swap_mb = counters.get("swap")
+    return procs
+
+
+def _pss(proc: ProcessInfo) -> float:
+    return proc.rss_mb if proc.pss_mb is None else proc.pss_mb
+
+
+def pss_total_mb(procs: list[ProcessInfo]) -> float:
+    """Sum the PSS of processes, falling back to RSS where it is unread.
+
+    Returns:
+        float: Memory in MB the processes account for, without counting
+        shared pages more than once.
+    """
+    return sum(_pss(p) for p in procs)
+
+
+def top_by_pss(
+    procs: list[ProcessInfo], limit: int, source: ProcessSource | None = None
+) -> list[ProcessInfo]:
+    """Find the processes with the most PSS, reading as few smaps as needed.
+
+    PSS never exceeds RSS, so processes are read in order of RSS until the
+    next one has less RSS than the smallest PSS kept.
+
+    Args:
+        procs: Candidate processes; the ones read get their counters filled.
+        limit: Processes to return.
+        source: Process backend used to read the memory counters.
+
+    Returns:
+        list[ProcessInfo]: Up to ``limit`` processes, by PSS descending.
+    """
+    top: list[ProcessInfo] = []
+    if limit < 1:
+        return top
+    for proc in sorted(procs, key=lambda p: p.rss_mb, reverse=True):
+        if len(top) == limit and proc.rss_mb <= _pss(top[-1]):
+            break
+        attach_smaps([proc], source)
+        top = sorted([*top, proc], key=_pss, reverse=True)[:limit]
+    return top
@@ -0,0 +1,530 @@
+"""Process data sources: the live system or in-memory fixtures."""
+
+import signal
+from collections.abc import Iterator
+from dataclasses import dataclass, field
+from types import SimpleNamespace
+from typing import Any, Protocol
+
+import psutil
+
+KTHREADD_PID = 2  # Parent of every kernel thread
+
+
+def is_kernel_thread(pid: int, ppid: int, argv: list[str]) -> bool:
+    """Tell kernel threads from user processes by their parent and argv.
+
+    Returns:
+        bool: True for kthreadd, started by the kernel (PPID 0), and the
+        threads it starts, none of which have a command line.
+    """
+    return not argv and (ppid == KTHREADD_PID or (pid == KTHREADD_PID and ppid == 0))
+
+
+class KillBackend(Protocol):
+    """Backend that delivers kill signals and runs manager stop commands.
+
+    ``send_signal``, ``send``, ``signal_group``, ``renice`` and ``ionice`` raise
+    ``psutil.NoSuchProcess`` or ``psutil.AccessDenied`` on failure;
+    ``run_stop_command`` and ``spawn`` raise ``OSError`` or
+    ``subprocess.SubprocessError``.
+    """
+
+    def alive(self, pid: int) -> bool:
+        """Check whether a process still runs; zombies count as exited."""
+        ...
+
+    def send_signal(self, pid: int, force: bool) -> None:
+        """Terminate (or force kill) a process."""
+        ...
+
+    def send(self, pid: int, sig: signal.Signals) -> None:
+        """Send any other signal, e.g. SIGHUP to reload or SIGSTOP to pause."""
+        ...
+
+    def signal_group(self, pgid: int, sig: signal.Signals) -> None:
+        """Send a signal to every process of a process group at once."""
+        ...
+
+    def renice(self, pid: int, nice: int) -> None:
+        """Set a process's nice value."""
+        ...
+
+    def ionice(self, pid: int, ioclass: int, level: int | None) -> None:
+        """Set a process's I/O scheduling class and level."""
+        ...
+
+    def run_stop_command(self, command: list[str]) -> None:
+        """Run a supervisor's stop command, e.g. ``systemctl stop x.service``."""
+        ...
+
+    def spawn(
+        self, argv: list[str], cwd: str, env: dict[str, str], user: str
+    ) -> int:
+        """Start a command detached from procclean, as ``user``; return its PID."""
+        ...
+
+
+class ProcessSource(KillBackend, Protocol):
+    """Backend that supplies raw process data to the core functions.
+
+    Methods that act on a single PID raise ``psutil.NoSuchProcess`` or
+    ``psutil.AccessDenied`` on failure, mirroring psutil itself.
+    """
+
+    def current_user(self) -> str:
+        """Return the login name used as the default user filter."""
+        ...
+
+    def iter_processes(self) -> Iterator[dict[str, Any]]:
+        """Yield psutil-style ``proc.info`` mappings for every process."""
+        ...
+
+    def sample_cpu(self, interval: float) -> None:
+        """Take a first CPU sample and wait; the next scan reports usage since."""
+        ...
+
+    def parent_name(self, ppid: int) -> str:
+        """Return the name of the parent process."""
+        ...
+
+    def cwd(self, pid: int) -> str:
+        """Return the working directory, or "?" if unknown."""
+        ...
+
+    def in_tmux(self, pid: int) -> bool:
+        """Return True if the process environment contains TMUX."""
+        ...
+
+    def environ(self, pid: int) -> dict[str, str]:
+        """Return the environment, or an empty dict if it cannot be read."""
+        ...
+
+    def exe(self, pid: int) -> str:
+        """Return the executable path."""
+        ...
+
+    def exe_deleted(self, pid: int) -> bool:
+        """Return True if the executable was deleted or replaced."""
+        ...
+
+    def cwd_deleted(self, pid: int) -> bool:
+        """Return True if the working directory was removed."""
+        ...
+
+    def deleted_libraries(self, pid: int) -> tuple[str, ...]:
+        """Return replaced shared libraries the process still maps."""
+        ...
+
+    def cgroup(self, pid: int) -> str:
+        """Return the cgroup path, or an empty string if unknown."""
+        ...
+
+    def session_id(self, pid: int) -> int:
+        """Return the session ID, or -1 if unknown."""
+        ...
+
+    def process_group(self, pid: int) -> int:
+        """Return the process group ID, or -1 if unknown."""
+        ...
+
+    def sched(self, pid: int) -> tuple[int, str, int]:
+        """Return the nice value, scheduling policy and realtime priority."""
+        ...
+
+    def kernel_thread(self, pid: int) -> bool:
+        """Check whether a process is a kernel thread, which is never signaled."""
+        ...
+
+    def privileges(self, pid: int) -> tuple[str, ...]:
+        """Return why the process is privileged ("root", "caps", "setuid")."""
+        ...
+
+    def confinement(self, pid: int) -> tuple[str, bool]:
+        """Return the seccomp mode and whether no_new_privs is set."""
+        ...
+
+    def media_devices(self, pid: int) -> tuple[str, ...]:
+        """Return the kinds of media device open ("audio", "camera")."""
+        ...
+
+    def open_files(self, pid: int) -> dict[int, str]:
+        """Return what the file descriptors point to, by descriptor number."""
+        ...
+
+    def listening_ports(self, pid: int) -> tuple[str, ...]:
+        """Return the listening sockets, e.g. "tcp 0.0.0.0:8080"."""
+        ...
+
+    def memory_breakdown(self, pid: int) -> dict[str, float]:
+        """Return memory counters in MB, e.g. rss, uss, pss and swap."""
+        ...
+
+
+@dataclass
+class FakeProcess:
+    """In-memory process record for a ``FakeProcessSource``."""
+
+    pid: int
+    name: str
+    cmdline: list[str] = field(default_factory=list)
+    ppid: int = 1
+    rss_mb: float = 100.0
+    cpu_percent: float = 0.0
+    username: str = "user"
+    create_time: float = 0.0
+    status: str = "running"
+    cwd: str = "/"
+    exe: str = ""
+    exe_deleted: bool = False
+    cwd_deleted: bool = False
+    deleted_libs: tuple[str, ...] = ()  # Replaced libraries still mapped
+    cgroup: str = ""
+    sid: int = -1  # Session ID
+    pgid: int = -1  # Process group ID
+    nice: int = 0
+    policy: str = "other"  # Scheduling policy, as in SCHED_POLICIES
+    rt_priority: int = 0
+    privileges: tuple[str, ...] = ()  # Any of "root", "caps", "setuid"
+    seccomp: str = "disabled"
+    no_new_privs: bool = False
+    media: tuple[str, ...] = ()  # Media devices open: "audio", "camera"
+    fds: dict[int, str] = field(default_factory=dict)  # Open file targets
+    ports: tuple[str, ...] = ()  # Listening sockets, e.g. "tcp 0.0.0.0:80"
+    # Memory counters in MB besides RSS, e.g. {"pss": 40.0, "uss": 10.0}
+    memory: dict[str, float] = field(default_factory=dict)
+    in_tmux: bool = False
+    env: dict[str, str] = field(default_factory=dict)
+    protected: bool = False  # Signals raise AccessDenied
+    ignores_term: bool = False  # SIGTERM is recorded, but it keeps running
+    unkillable: bool = False  # Even SIGKILL is recorded without effect
+
+    def info(self) -> dict[str, Any]:
+        """Build a psutil-style ``proc.info`` mapping.
+
+        Returns:
+            dict[str, Any]: Process attributes keyed like ``psutil.process_iter``.
+        """
+        return {
+            "pid": self.pid,
+            "name": self.name,
+            "cmdline": self.cmdline,
+            "ppid": self.ppid,
+            "memory_info": SimpleNamespace(rss=int(self.rss_mb * 1024 * 1024)),
+            "cpu_percent": self.cpu_percent,
+            "username": self.username,
+            "create_time": self.create_time,
+            "status": self.status,
+        }
+
+
+class FakeProcessSource:
+    """Deterministic in-memory process table for tests and demos.
+
+    Killed processes are removed from the table and recorded in ``signals``;
+    reniced ones are updated and recorded in ``renices``, ionices in
+    ``ionices``; stop commands are
+    recorded in ``commands`` and never executed; spawned commands are
+    recorded in ``spawned`` and added to the table.
+    """
+
+    def __init__(self, processes: list[FakeProcess], user: str = "user") -> None:
+        """Initialize the fake process table.
+
+        Args:
+            processes: Processes that make up the table.
+            user: Login name returned by ``current_user``.
+        """
+        self.processes = {p.pid: p for p in processes}
+        self.user = user
+        self.signals: list[tuple[int, bool]] = []
+        self.sent: list[tuple[int, signal.Signals]] = []  # Signals from ``send``
+        # Signals from ``signal_group``, by process group
+        self.group_signals: list[tuple[int, signal.Signals]] = []
+        self.renices: list[tuple[int, int]] = []
+        self.ionices: list[tuple[int, int, int | None]] = []
+        self.commands: list[list[str]] = []
+        self.spawned: list[tuple[list[str], str, dict[str, str], str]] = []
+
+    def _get(self, pid: int) -> FakeProcess:
+        if pid not in self.processes:
+            raise psutil.NoSuchProcess(pid)
+        return self.processes[pid]
+
+    def current_user(self) -> str:
+        """Return the configured login name.
+
+        Returns:
+            str: The fake current user.
+        """
+        return self.user
+
+    def iter_processes(self) -> Iterator[dict[str, Any]]:
+        """Yield info mappings for all fake processes.
+
+        Yields:
+            dict[str, Any]: A psutil-style info mapping.
+        """
+        for proc in list(self.processes.values()):
+            yield proc.info()
+
+    def sample_cpu(self, interval: float) -> None:
+        """Do nothing: fake processes report a fixed ``cpu_percent``."""
+
+    def parent_name(self, ppid: int) -> str:
+        """Return the parent's name.
+
+        Returns:
+            str: Parent process name.
+        """
+        return self._get(ppid).name
+
+    def cwd(self, pid: int) -> str:
+        """Return the fake working directory.
+
+        Returns:
+            str: Working directory, or "?" for unknown PIDs.
+        """
+        return self.processes[pid].cwd if pid in self.processes else "?"
+
+    def in_tmux(self, pid: int) -> bool:
+        """Return the fake tmux flag.
+
+        Returns:
+            bool: True if the process is marked as running in tmux.
+        """
+        return pid in self.processes and self.processes[pid].in_tmux
+
+    def environ(self, pid: int) -> dict[str, str]:
+        """Return the fake environment.
+
+        Returns:
+            dict[str, str]: Environment, or empty for unknown PIDs.
+        """
+        return dict(self.processes[pid].env) if pid in self.processes else {}
+
+    def exe(self, pid: int) -> str:
+        """Return the fake executable path.
+
+        Returns:
+            str: Executable path.
+        """
+        return self._get(pid).exe
+
+    def exe_deleted(self, pid: int) -> bool:
+        """Return the fake stale-executable flag.
+
+        Returns:
+            bool: True if the executable is marked deleted.
+        """
+        return pid in self.processes and self.processes[pid].exe_deleted
+
+    def cwd_deleted(self, pid: int) -> bool:
+        """Return the fake removed-cwd flag.
+
+        Returns:
+            bool: True if the working directory is marked deleted.
+        """
+        return pid in self.processes and self.processes[pid].cwd_deleted
+
+    def deleted_libraries(self, pid: int) -> tuple[str, ...]:
+        """Return the fake replaced libraries.
+
+        Returns:
+            tuple[str, ...]: Library paths, or empty for unknown PIDs.
+        """
+        return self.processes[pid].deleted_libs if pid in self.processes else ()
+
+    def cgroup(self, pid: int) -> str:
+        """Return the fake cgroup path.
+
+        Returns:
+            str: cgroup path, or an empty string for unknown PIDs.
+        """
+        return self.processes[pid].cgroup if pid in self.processes else ""
+
+    def session_id(self, pid: int) -> int:
+        """Return the fake session ID.
+
+        Returns:
+            int: Session ID, or -1 for unknown PIDs.
+        """
+        return self.processes[pid].sid if pid in self.processes else -1
+
+    def process_group(self, pid: int) -> int:
+        """Return the fake process group ID.
+
+        Returns:
+            int: Process group ID, or -1 for unknown PIDs.
+        """
+        return self.processes[pid].pgid if pid in self.processes else -1
+
+    def kernel_thread(self, pid: int) -> bool:
+        """Check the fake process's parent and command line.
+
+        Returns:
+            bool: True for kernel threads; False for unknown PIDs.
+        """
+        proc = self.processes.get(pid)
+        return proc is not None and is_kernel_thread(pid, proc.ppid, proc.cmdline)
+
+    def sched(self, pid: int) -> tuple[int, str, int]:
+        """Return the fake scheduling attributes.
+
+        Returns:
+            tuple[int, str, int]: Nice value, policy and realtime priority.
+        """
+        proc = self._get(pid)
+        return proc.nice, proc.policy, proc.rt_priority
+
+    def privileges(self, pid: int) -> tuple[str, ...]:
+        """Return the fake privilege reasons.
+
+        Returns:
+            tuple[str, ...]: Reasons the process is privileged.
+        """
+        return self._get(pid).privileges
+
+    def confinement(self, pid: int) -> tuple[str, bool]:
+        """Return the fake seccomp mode and no_new_privs flag.
+
+        Returns:
+            tuple[str, bool]: Seccomp mode and no_new_privs.
+        """
+        proc = self._get(pid)
+        return proc.seccomp, proc.no_new_privs
+
+    def media_devices(self, pid: int) -> tuple[str, ...]:
+        """Return the fake open media devices.
+
+        Returns:
+            tuple[str, ...]: Media device kinds, or empty for unknown PIDs.
+        """
+        return self.processes[pid].media if pid in self.processes else ()
+
+    def open_files(self, pid: int) -> dict[int, str]:
+        """Return the fake open file targets.
+
+        Returns:
+            dict[int, str]: Targets by descriptor, or empty for unknown PIDs.
+        """
+        return dict(self.processes[pid].fds) if pid in self.processes else {}
+
+    def listening_ports(self, pid: int) -> tuple[str, ...]:
+        """Return the fake listening sockets.
+
+        Returns:
+            tuple[str, ...]: Sockets, or empty for unknown PIDs.
+        """
+        return self.processes[pid].ports if pid in self.processes else ()
+
+    def memory_breakdown(self, pid: int) -> dict[str, float]:
+        """Return the fake RSS and other memory counters.
+
+        Returns:
+            dict[str, float]: ``{"rss": MB, **memory}``, or empty for unknown
+            PIDs.
+        """
+        if pid not in self.processes:
+            return {}
+        proc = self.processes[pid]
+        return {"rss": proc.rss_mb, **proc.memory}
+
+    def run_stop_command(self, command: list[str]) -> None:
+        """Record a stop command without running it."""
+        self.commands.append(command)
+
+    def spawn(
+        self, argv: list[str], cwd: str, env: dict[str, str], user: str
+    ) -> int:
+        """Record a command and add it to the table under the next free PID.
+
+        Returns:
+            int: PID of the new process.
+        """
+        self.spawned.append((argv, cwd, env, user))
+        pid = max(self.processes, default=1) + 1
+        name = argv[0].rsplit("/", 1)[-1] if argv else "?"
+        self.processes[pid] = FakeProcess(
+            pid, name, list(argv), username=user, cwd=cwd, env=dict(env)
+        )
+        return pid
+
+    def alive(self, pid: int) -> bool:
+        """Check whether a process is still in the table.
+
+        Returns:
+            bool: True until the process is killed.
+        """
+        return pid in self.processes
+
+    def send_signal(self, pid: int, force: bool) -> None:
+        """Record a signal and remove the process from the table.
+
+        Processes marked ``ignores_term`` stay on SIGTERM and ``unkillable``
+        ones on any signal.
+
+        Raises:
+            psutil.AccessDenied: If the process is marked as protected.
+        """
+        proc = self._get(pid)
+        if proc.protected:
+            raise psutil.AccessDenied(pid)
+        self.signals.append((pid, force))
+        if not (proc.unkillable or (proc.ignores_term and not force)):
+            del self.processes[pid]
+
+    def send(self, pid: int, sig: signal.Signals) -> None:
+        """Record a signal; the process stays in the table.
+
+        Raises:
+            psutil.AccessDenied: If the process is marked as protected.
+        """
+        proc = self._get(pid)
+        if proc.protected:
+            raise psutil.AccessDenied(pid)
+        self.sent.append((pid, sig))
+
+    def signal_group(self, pgid: int, sig: signal.Signals) -> None:
+        """Record a group signal; SIGTERM and SIGKILL remove the members.
+
+        Members marked ``ignores_term`` stay on SIGTERM and ``unkillable``
+        ones on any signal.
+
+        Raises:
+            psutil.NoSuchProcess: If no process is in the group.
+            psutil.AccessDenied: If any member is marked as protected.
+        """
+        members = [p for p in self.processes.values() if p.pgid == pgid]
+        if not members:
+            raise psutil.NoSuchProcess(pgid)
+        if any(p.protected for p in members):
+            raise psutil.AccessDenied(pgid)
+        self.group_signals.append((pgid, sig))
+        for proc in members:
+            if sig is signal.SIGKILL and not proc.unkillable:
+                del self.processes[proc.pid]
+            elif sig is signal.SIGTERM and not (proc.unkillable or proc.ignores_term):
+                del self.processes[proc.pid]
+
+    def renice(self, pid: int, nice: int) -> None:
+        """Record a nice value change and apply it to the table.
+
+        Raises:
+            psutil.AccessDenied: If the process is marked as protected.
+        """
+        proc = self._get(pid)
+        if proc.protected:
+            raise psutil.AccessDenied(pid)
+        self.renices.append((pid, nice))
+        proc.nice = nice
+
+    def ionice(self, pid: int, ioclass: int, level: int | None) -> None:
+        """Record an I/O priority change.
+
+        Raises:
+            psutil.AccessDenied: If the process is marked as protected.
+        """
+        if self._get(pid).protected:
+            raise psutil.AccessDenied(pid)
+        self.ionices.append((pid, ioclass, level))
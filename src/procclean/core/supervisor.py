@@ -0,0 +1,382 @@
+"""Supervisor resolution: who will restart a process after it is killed.
+
+A process is attributed to the innermost manager found, in this order:
+kubelet (kubepods cgroup), container runtime (docker/podman cgroup), a
+process manager ancestor
+(supervisord, pm2, forever, nodemon), a systemd service cgroup, then an
+interactive shell.
+
+Stop/delete commands are templates that can be overridden per manager in the
+config file::
+
+    [supervisors.pm2]
+    stop = "pm2 stop {name} --silent"
+    delete = "pm2 delete {name}"
+
+Placeholders: ``{name}`` (unit, container, program or app name), ``{user}``
+(``--user`` for systemd user units) and ``{manager_pid}``.
+"""
+
+import re
+import shlex
+import subprocess
+from collections import defaultdict
+from collections.abc import Iterable
+from dataclasses import dataclass
+from itertools import chain
+from pathlib import Path
+from typing import Any
+
+from procclean.i18n import t
+
+from .actions import kill_processes
+from .audit import audit_targets, log_kills
+from .config import load_config
+from .errors import ErrorCode, ProccleanError
+from .kube import pod_from_cgroup
+from .process import DEFAULT_SOURCE
+from .source import ProcessSource
+
+SHELL_NAMES = frozenset({"bash", "dash", "fish", "ksh", "nu", "sh", "tcsh", "zsh"})
+MAX_ANCESTORS = 32  # Guards against ppid cycles in broken process tables
+
+# Confirmation result meaning "stop supervised targets through their manager"
+VIA_MANAGER = "manager"
+
+# cgroup name prefix of each container runtime
+CONTAINER_RUNTIMES = {"docker": "docker", "libpod": "podman"}
+
+# Manager actions a template can be configured for
+ACTIONS = ("stop", "delete", "restart")
+
+DEFAULT_COMMANDS: dict[str, dict[str, str]] = {
+    "systemd": {
+        "stop": "systemctl {user} stop {name}",
+        "restart": "systemctl {user} restart {name}",
+    },
+    "docker": {
+        "stop": "docker stop {name}",
+        "delete": "docker rm -f {name}",
+        "restart": "docker restart {name}",
+    },
+    "podman": {
+        "stop": "podman stop {name}",
+        "delete": "podman rm -f {name}",
+        "restart": "podman restart {name}",
+    },
+    "supervisord": {
+        "stop": "supervisorctl stop {name}",
+        "restart": "supervisorctl restart {name}",
+    },
+    "pm2": {
+        "stop": "pm2 stop {name}",
+        "delete": "pm2 delete {name}",
+        "restart": "pm2 restart {name}",
+    },
+    "forever": {"stop": "forever stop {name}", "restart": "forever restart {name}"},
+    # nodemon has no control CLI; stopping the watcher ends the restart loop
+    "nodemon": {"stop": "kill {manager_pid}"},
+}
+
+_CONTAINER_RE = re.compile(r"(docker|libpod)[-/]([0-9a-f]{12,64})")
+_USER_MANAGER_RE = re.compile(r"^user@\d+\.service$")
+
+
+@dataclass(frozen=True, slots=True)
+class Supervisor:
+    """The manager responsible for a process."""
+
+    kind: str  # "systemd", "docker", "kubelet", "pm2", "nodemon", "shell", ...
+    name: str  # Unit, container ID, pod UID, program, app/script or shell name
+    user: bool = False  # systemd: unit belongs to the user manager
+    manager_pid: int = 0  # PID of the managing process, if known
+
+    @property
+    def respawns(self) -> bool:
+        """Whether the manager is likely to restart a killed process.
+
+        Returns:
+            bool: False only for interactive shells.
+        """
+        return self.kind != "shell"
+
+    @property
+    def stop_command(self) -> list[str] | None:
+        """Command that stops the process through its manager.
+
+        Returns:
+            list[str] | None: argv to run, or None if there is no manager to ask.
+        """
+        return self.command("stop")
+
+    def command(self, action: str = "stop") -> list[str] | None:
+        """Expand the manager's command template for an action.
+
+        Args:
+            action: One of ``ACTIONS``.
+
+        Returns:
+            list[str] | None: argv to run, or None if the manager has no such
+            command.
+        """
+        template = command_templates().get(self.kind, {}).get(action)
+        if not template:
+            return None
+        return shlex.split(
+            template.format(
+                name=shlex.quote(self.name),
+                user="--user" if self.user else "",
+                manager_pid=self.manager_pid,
+            )
+        )
+
+    def describe(self) -> str:
+        """Render a short note for confirm dialogs.
+
+        Returns:
+            str: E.g. "will likely respawn via systemd (nginx.service)".
+        """
+        if not self.respawns:
+            return t("supervisor-shell", shell=self.name)
+        return t("supervisor-respawn", kind=self.kind, name=self.name)
+
+
+def command_templates() -> dict[str, dict[str, str]]:
+    """Get command templates, applying ``[supervisors.<kind>]`` overrides.
+
+    Returns:
+        dict[str, dict[str, str]]: Templates keyed by manager kind and action.
+
+    Raises:
+        ProccleanError: With ``INVALID_CONFIG`` for unknown actions or
+            placeholders.
+    """
+    templates = {kind: dict(cmds) for kind, cmds in DEFAULT_COMMANDS.items()}
+    for kind, table in load_config().get("supervisors", {}).items():
+        for action, template in table.items():
+            if action not in ACTIONS or not isinstance(template, str):
+                msg = "expected stop/delete/restart strings"
+                raise _template_error(kind, action, msg)
+            try:
+                template.format(name="", user="", manager_pid=0)
+            except (KeyError, IndexError, ValueError) as e:
+                raise _template_error(kind, action, f"bad placeholder {e}") from e
+            templates.setdefault(kind, {})[action] = template
+    return templates
+
+
+def _template_error(kind: str, action: str, reason: str) -> ProccleanError:
+    """Build the error for an invalid ``[supervisors]`` entry.
+
+    Returns:
+        ProccleanError: With ``INVALID_CONFIG``.
+    """
+    msg = f"Invalid template supervisors.{kind}.{action}: {reason}"
+    return ProccleanError(ErrorCode.INVALID_CONFIG, msg, kind=kind, action=action)
+
+
+def supervisor_from_cgroup(cgroup: str) -> Supervisor | None:
+    """Detect a pod, container or systemd service from a cgroup path.
+
+    Args:
+        cgroup: cgroup path, e.g. ``/system.slice/nginx.service``.
+
+    Returns:
+        Supervisor | None: The pod, container or service, or None.
+    """
+    if pod := pod_from_cgroup(cgroup):
+        return Supervisor("kubelet", pod.uid)
+    if match := _CONTAINER_RE.search(cgroup):
+        return Supervisor(CONTAINER_RUNTIMES[match.group(1)], match.group(2)[:12])
+    services = [
+        part
+        for part in cgroup.split("/")
+        if part.endswith(".service") and not _USER_MANAGER_RE.match(part)
+    ]
+    if services:
+        return Supervisor("systemd", services[-1], user="/user@" in cgroup)
+    return None
+
+
+def _manager_from_ancestor(name: str, cmdline: list[str]) -> str | None:
+    """Classify an ancestor process as a process manager.
+
+    Returns:
+        str | None: "supervisord", "pm2", "forever", "nodemon", or None.
+    """
+    names = {name.lower(), *(Path(arg).name.lower() for arg in cmdline[:2])}
+    if "supervisord" in names:
+        return "supervisord"
+    if any(n.startswith("pm2") for n in names):
+        return "pm2"
+    if "nodemon" in names:
+        return "nodemon"
+    if "forever" in names or any("/forever/" in arg for arg in cmdline[:2]):
+        return "forever"
+    return None
+
+
+def _script(info: dict[str, Any]) -> str:
+    """Get the script a runtime was started with, e.g. ``server.js``.
+
+    Returns:
+        str: The first non-option argument, or the process name.
+    """
+    args = (info.get("cmdline") or [])[1:]
+    return next((a for a in args if not a.startswith("-")), info["name"])
+
+
+def _managed(kind: str, info: dict[str, Any], manager: dict[str, Any]) -> Supervisor:
+    """Build the supervisor entry for a process run by a manager ancestor.
+
+    pm2 names apps after the script stem by default; forever and nodemon are
+    addressed by script, supervisord by program (assumed to be the name).
+
+    Returns:
+        Supervisor: The manager with the name it knows the process by.
+    """
+    match kind:
+        case "pm2":
+            name = Path(_script(info)).stem
+        case "forever" | "nodemon":
+            name = _script(info)
+        case _:
+            name = info["name"]
+    return Supervisor(kind, name, manager_pid=manager["pid"])
+
+
+def _ancestors(pid: int, table: dict[int, dict[str, Any]]) -> list[dict[str, Any]]:
+    """Walk the parent chain, nearest parent first.
+
+    Returns:
+        list[dict[str, Any]]: Info mappings of the ancestors found in ``table``.
+    """
+    chain: list[dict[str, Any]] = []
+    current = table.get(pid)
+    while current and len(chain) < MAX_ANCESTORS:
+        parent = table.get(current.get("ppid") or 0)
+        if parent is None or parent["pid"] == current["pid"]:
+            break
+        chain.append(parent)
+        current = parent
+    return chain
+
+
+def _resolve(
+    pid: int, table: dict[int, dict[str, Any]], cgroup: str
+) -> Supervisor | None:
+    """Resolve the supervisor of one process from its cgroup and ancestors.
+
+    Returns:
+        Supervisor | None: The innermost manager, or None.
+    """
+    from_cgroup = supervisor_from_cgroup(cgroup)
+    if from_cgroup and from_cgroup.kind != "systemd":
+        return from_cgroup
+    chain = _ancestors(pid, table)
+    for ancestor in chain:
+        if kind := _manager_from_ancestor(ancestor["name"], ancestor["cmdline"] or []):
+            return _managed(kind, table[pid], ancestor)
+    if from_cgroup:
+        return from_cgroup
+    if chain and chain[0]["name"] in SHELL_NAMES:
+        return Supervisor("shell", chain[0]["name"])
+    return None
+
+
+def resolve_supervisors(
+    pids: Iterable[int], source: ProcessSource | None = None
+) -> dict[int, Supervisor]:
+    """Determine which manager would restart each process.
+
+    Args:
+        pids: Process IDs to analyze.
+        source: Process backend; defaults to the live system.
+
+    Returns:
+        dict[int, Supervisor]: Supervisors keyed by PID; unmanaged PIDs are
+        omitted.
+    """
+    source = source or DEFAULT_SOURCE
+    table = {info["pid"]: info for info in source.iter_processes()}
+    result: dict[int, Supervisor] = {}
+    for pid in pids:
+        if pid in table and (sup := _resolve(pid, table, source.cgroup(pid))):
+            result[pid] = sup
+    return result
+
+
+def stop_via_managers(
+    pids: list[int],
+    force: bool = False,
+    source: ProcessSource | None = None,
+    action: str = "stop",
+) -> list[tuple[int, bool, str]]:
+    """Stop processes through their managers, killing unmanaged ones.
+
+    Each manager command runs once even if it covers several PIDs.
+
+    Args:
+        pids: Process IDs to stop.
+        force: Force kill processes that have no manager command.
+        source: Process backend; defaults to the live system.
+        action: Manager action, "stop", "delete" or "restart"; managers
+            without a delete command fall back to "stop". Processes that
+            cannot be restarted through a manager are left running.
+
+    Returns:
+        A list of tuples (pid, success, message) for each PID.
+    """
+    source = source or DEFAULT_SOURCE
+    supervisors = resolve_supervisors(pids, source)
+    restart = action == "restart"
+    by_command: dict[tuple[str, ...], list[int]] = defaultdict(list)
+    unmanaged: list[int] = []
+    for pid in pids:
+        sup = supervisors.get(pid)
+        command = sup and sup.command(action)
+        if sup and not command and not restart:
+            command = sup.stop_command
+        if command:
+            by_command[tuple(command)].append(pid)
+        else:
+            unmanaged.append(pid)
+
+    results: list[tuple[int, bool, str]] = []
+    targets = audit_targets(chain.from_iterable(by_command.values()), source)
+    for command, managed in by_command.items():
+        success, msg = run_stop_command(list(command), source)
+        if success and restart:
+            msg = t("supervisor-restarted", command=" ".join(command))
+        results.extend((pid, success, msg) for pid in managed)
+        joined = shlex.join(command)
+        log_kills(((pid, joined, success, msg) for pid in managed), targets, source)
+    if restart:
+        results.extend(
+            (pid, False, t("supervisor-no-restart", pid=pid)) for pid in unmanaged
+        )
+    else:
+        results.extend(kill_processes(unmanaged, force=force, source=source))
+    return results
+
+
+def run_stop_command(command: list[str], source: ProcessSource) -> tuple[bool, str]:
+    """Run a manager's stop command through the source.
+
+    Args:
+        command: argv from ``Supervisor.stop_command``.
+        source: Process backend that executes the command.
+
+    Returns:
+        A tuple of (success, message).
+    """
+    shown = " ".join(command)
+    try:
+        source.run_stop_command(command)
+    except subprocess.CalledProcessError as e:
+        error = (e.stderr or "").strip() or f"exit status {e.returncode}"
+        return False, t("supervisor-stop-failed", command=shown, error=error)
+    except (OSError, subprocess.SubprocessError) as e:
+        error = getattr(e, "strerror", None) or e
+        return False, t("supervisor-stop-failed", command=shown, error=error)
+    return True, t("supervisor-stopped", command=shown)
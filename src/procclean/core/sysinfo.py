@@ -0,0 +1,96 @@
+"""Facts about the machine, for self-describing reports (``--header``).
+
+Reports exported from several machines are otherwise indistinguishable;
+the header names the host and kernel and shows how long and how busy the
+system has been when the report was taken. The memory summary shows the
+same load and uptime.
+"""
+
+import os
+import platform
+import time
+from dataclasses import asdict, dataclass
+from datetime import UTC, datetime
+
+import psutil
+
+
+def load_average() -> tuple[float, float, float]:
+    """Read the 1, 5 and 15 minute load averages.
+
+    Returns:
+        tuple[float, float, float]: The averages; zero where the platform
+        has none.
+    """
+    try:
+        return os.getloadavg()
+    except (AttributeError, OSError):
+        return (0.0, 0.0, 0.0)
+
+
+def uptime_seconds() -> float:
+    """Get the time since boot.
+
+    Returns:
+        float: Seconds the machine has been up.
+    """
+    return max(0.0, time.time() - psutil.boot_time())
+
+
+def format_uptime(seconds: float) -> str:
+    """Render an uptime in days, hours and minutes, e.g. "3d 4h 12m".
+
+    Returns:
+        str: The uptime; days are left out for the first day.
+    """
+    minutes = int(seconds // 60)
+    days, minutes = divmod(minutes, 1440)
+    hours, minutes = divmod(minutes, 60)
+    return f"{days}d {hours}h {minutes}m" if days else f"{hours}h {minutes}m"
+
+
+@dataclass(frozen=True, slots=True)
+class SystemInfo:
+    """Host, kernel, uptime and load of the machine a report comes from."""
+
+    hostname: str
+    kernel: str  # System and release, e.g. "Linux 6.8.0-45-generic"
+    uptime_seconds: float
+    load_avg: tuple[float, float, float]  # 1, 5 and 15 minute load averages
+    timestamp: str  # Local ISO 8601 time the information was read
+
+    @property
+    def uptime(self) -> str:
+        """Render the uptime in days, hours and minutes, e.g. "3d 4h 12m"."""
+        return format_uptime(self.uptime_seconds)
+
+    def to_dict(self) -> dict[str, object]:
+        """Serialize for JSON output.
+
+        Returns:
+            dict[str, object]: The fields, with the load averages rounded.
+        """
+        data = asdict(self)
+        data["uptime_seconds"] = round(self.uptime_seconds)
+        data["load_avg"] = [round(load, 2) for load in self.load_avg]
+        return data
+
+
+def get_system_info(now: datetime | None = None) -> SystemInfo:
+    """Read the host name, kernel, uptime and load averages.
+
+    Args:
+        now: Time of the report; defaults to the current time.
+
+    Returns:
+        SystemInfo: The information; load averages are zero where the
+        platform has none.
+    """
+    now = now or datetime.now(tz=UTC)
+    return SystemInfo(
+        hostname=platform.node(),
+        kernel=f"{platform.system()} {platform.release()}",
+        uptime_seconds=uptime_seconds(),
+        load_avg=load_average(),
+        timestamp=now.astimezone().isoformat(timespec="seconds"),
+    )
@@ -0,0 +1,366 @@
+"""Phase timing for ``--timing``.
+
+On systems with thousands of processes a listing can take seconds. Timing
+wraps the process source so every per-process read is attributed to a phase
+(the scan itself, environment reads, cwd resolution, other ``/proc`` reads),
+and sorting and formatting time themselves while timing is active.
+"""
+
+import signal
+import time
+from collections.abc import Callable, Iterator
+from contextlib import contextmanager
+from dataclasses import dataclass, field
+from typing import Any
+
+from .source import ProcessSource
+
+# Reported phases, in pipeline order
+PHASES = ("scan", "environ", "cwd", "reads", "sort", "format")
+
+
+@dataclass
+class Timings:
+    """Accumulated time and call counts per phase."""
+
+    seconds: dict[str, float] = field(default_factory=dict)
+    calls: dict[str, int] = field(default_factory=dict)
+    started: float = field(default_factory=time.perf_counter)
+
+    def add(self, phase: str, elapsed: float) -> None:
+        """Account one call of a phase."""
+        self.seconds[phase] = self.seconds.get(phase, 0.0) + elapsed
+        self.calls[phase] = self.calls.get(phase, 0) + 1
+
+    @contextmanager
+    def phase(self, name: str) -> Iterator[None]:
+        """Time the block as one call of a phase.
+
+        Yields:
+            None: Inside the timed block.
+        """
+        start = time.perf_counter()
+        try:
+            yield
+        finally:
+            self.add(name, time.perf_counter() - start)
+
+    def as_dict(self) -> dict[str, Any]:
+        """Summarize the timings for JSON output.
+
+        Returns:
+            dict[str, Any]: Seconds and calls per phase in ``PHASES`` order,
+            the total wall time and the time not covered by any phase.
+        """
+        total = time.perf_counter() - self.started
+        phases = {
+            name: {"seconds": round(self.seconds[name], 6), "calls": self.calls[name]}
+            for name in PHASES
+            if name in self.seconds
+        }
+        other = max(total - sum(self.seconds.values()), 0.0)
+        return {
+            "phases": phases,
+            "other_seconds": round(other, 6),
+            "total_seconds": round(total, 6),
+        }
+
+    def render(self) -> str:
+        """Render the timings as a small table.
+
+        Returns:
+            str: One line per phase, then other and total, in milliseconds.
+        """
+        data = self.as_dict()
+        lines = ["timing:"]
+        for name, phase in data["phases"].items():
+            ms = phase["seconds"] * 1000
+            lines.append(f"  {name:<8}{ms:>10.1f} ms  ({phase['calls']} calls)")
+        lines.append(f"  {'other':<8}{data['other_seconds'] * 1000:>10.1f} ms")
+        lines.append(f"  {'total':<8}{data['total_seconds'] * 1000:>10.1f} ms")
+        return "\n".join(lines)
+
+
+_active: list[Timings] = []
+
+
+def start_timing() -> Timings:
+    """Start collecting timings for the running command.
+
+    Returns:
+        Timings: The collector, filled until ``stop_timing``.
+    """
+    timings = Timings()
+    _active.append(timings)
+    return timings
+
+
+def stop_timing() -> None:
+    """Stop collecting timings."""
+    if _active:
+        _active.pop()
+
+
+def active_timings() -> Timings | None:
+    """Get the collector of the running command.
+
+    Returns:
+        Timings | None: The collector, or None if ``--timing`` is off.
+    """
+    return _active[-1] if _active else None
+
+
+@contextmanager
+def timed(phase: str) -> Iterator[None]:
+    """Time a block as a phase if timing is active.
+
+    Yields:
+        None: Inside the block.
+    """
+    if (timings := active_timings()) is None:
+        yield
+        return
+    with timings.phase(phase):
+        yield
+
+
+class TimedSource:
+    """Process source that attributes the time of every read to a phase.
+
+    Kill backend calls are passed through untimed.
+    """
+
+    def __init__(self, source: ProcessSource, timings: Timings) -> None:
+        """Initialize the timed source.
+
+        Args:
+            source: Source supplying process data.
+            timings: Collector the read times are added to.
+        """
+        self.source = source
+        self.timings = timings
+
+    def _timed[T](self, phase: str, read: Callable[[], T]) -> T:
+        with self.timings.phase(phase):
+            return read()
+
+    def current_user(self) -> str:
+        """Return the wrapped source's current user.
+
+        Returns:
+            str: Login name.
+        """
+        return self.source.current_user()
+
+    def iter_processes(self) -> Iterator[dict[str, Any]]:
+        """Yield the wrapped source's processes, timing each step of the scan.
+
+        Yields:
+            dict[str, Any]: A psutil-style info mapping.
+        """
+        processes = iter(self.source.iter_processes())
+        while True:
+            start = time.perf_counter()
+            info = next(processes, None)
+            self.timings.add("scan", time.perf_counter() - start)
+            if info is None:
+                return
+            yield info
+
+    def sample_cpu(self, interval: float) -> None:
+        """Take a first CPU sample through the wrapped source, timed as scan."""
+        self._timed("scan", lambda: self.source.sample_cpu(interval))
+
+    def parent_name(self, ppid: int) -> str:
+        """Return the parent's name, timed as a read.
+
+        Returns:
+            str: Parent process name.
+        """
+        return self._timed("reads", lambda: self.source.parent_name(ppid))
+
+    def cwd(self, pid: int) -> str:
+        """Return the working directory, timed as cwd resolution.
+
+        Returns:
+            str: Working directory.
+        """
+        return self._timed("cwd", lambda: self.source.cwd(pid))
+
+    def in_tmux(self, pid: int) -> bool:
+        """Return the tmux flag, timed as an environment read.
+
+        Returns:
+            bool: True if the process runs in tmux.
+        """
+        return self._timed("environ", lambda: self.source.in_tmux(pid))
+
+    def environ(self, pid: int) -> dict[str, str]:
+        """Return the environment, timed as an environment read.
+
+        Returns:
+            dict[str, str]: Process environment.
+        """
+        return self._timed("environ", lambda: self.source.environ(pid))
+
+    def exe(self, pid: int) -> str:
+        """Return the executable path, timed as a read.
+
+        Returns:
+            str: Executable path.
+        """
+        return self._timed("reads", lambda: self.source.exe(pid))
+
+    def exe_deleted(self, pid: int) -> bool:
+        """Return the stale-executable flag, timed as a read.
+
+        Returns:
+            bool: True if the executable was deleted or replaced.
+        """
+        return self._timed("reads", lambda: self.source.exe_deleted(pid))
+
+    def cwd_deleted(self, pid: int) -> bool:
+        """Return the removed-cwd flag, timed as cwd resolution.
+
+        Returns:
+            bool: True if the cwd no longer exists.
+        """
+        return self._timed("cwd", lambda: self.source.cwd_deleted(pid))
+
+    def deleted_libraries(self, pid: int) -> tuple[str, ...]:
+        """Return the replaced libraries, timed as a read.
+
+        Returns:
+            tuple[str, ...]: Paths of the deleted libraries.
+        """
+        return self._timed("reads", lambda: self.source.deleted_libraries(pid))
+
+    def cgroup(self, pid: int) -> str:
+        """Return the cgroup path, timed as a read.
+
+        Returns:
+            str: cgroup path.
+        """
+        return self._timed("reads", lambda: self.source.cgroup(pid))
+
+    def session_id(self, pid: int) -> int:
+        """Return the session ID, timed as a read.
+
+        Returns:
+            int: Session ID.
+        """
+        return self._timed("reads", lambda: self.source.session_id(pid))
+
+    def process_group(self, pid: int) -> int:
+        """Return the process group ID, timed as a read.
+
+        Returns:
+            int: Process group ID.
+        """
+        return self._timed("reads", lambda: self.source.process_group(pid))
+
+    def kernel_thread(self, pid: int) -> bool:
+        """Check for a kernel thread, timed as a read.
+
+        Returns:
+            bool: True for kernel threads.
+        """
+        return self._timed("reads", lambda: self.source.kernel_thread(pid))
+
+    def sched(self, pid: int) -> tuple[int, str, int]:
+        """Return the scheduling attributes, timed as a read.
+
+        Returns:
+            tuple[int, str, int]: Nice value, policy and realtime priority.
+        """
+        return self._timed("reads", lambda: self.source.sched(pid))
+
+    def privileges(self, pid: int) -> tuple[str, ...]:
+        """Return the privilege reasons, timed as a read.
+
+        Returns:
+            tuple[str, ...]: Reasons the process is privileged.
+        """
+        return self._timed("reads", lambda: self.source.privileges(pid))
+
+    def confinement(self, pid: int) -> tuple[str, bool]:
+        """Return the confinement status, timed as a read.
+
+        Returns:
+            tuple[str, bool]: Seccomp mode and no_new_privs.
+        """
+        return self._timed("reads", lambda: self.source.confinement(pid))
+
+    def media_devices(self, pid: int) -> tuple[str, ...]:
+        """Return the open media devices, timed as a read.
+
+        Returns:
+            tuple[str, ...]: Media device kinds.
+        """
+        return self._timed("reads", lambda: self.source.media_devices(pid))
+
+    def open_files(self, pid: int) -> dict[int, str]:
+        """Return the open file targets, timed as a read.
+
+        Returns:
+            dict[int, str]: Targets by descriptor number.
+        """
+        return self._timed("reads", lambda: self.source.open_files(pid))
+
+    def listening_ports(self, pid: int) -> tuple[str, ...]:
+        """Return the listening sockets, timed as a read.
+
+        Returns:
+            tuple[str, ...]: E.g. "tcp 0.0.0.0:8080".
+        """
+        return self._timed("reads", lambda: self.source.listening_ports(pid))
+
+    def memory_breakdown(self, pid: int) -> dict[str, float]:
+        """Return the memory counters, timed as a read.
+
+        Returns:
+            dict[str, float]: MB by counter.
+        """
+        return self._timed("reads", lambda: self.source.memory_breakdown(pid))
+
+    def run_stop_command(self, command: list[str]) -> None:
+        """Run a stop command through the wrapped source."""
+        self.source.run_stop_command(command)
+
+    def spawn(
+        self, argv: list[str], cwd: str, env: dict[str, str], user: str
+    ) -> int:
+        """Start a command through the wrapped source.
+
+        Returns:
+            int: PID of the new process.
+        """
+        return self.source.spawn(argv, cwd, env, user)
+
+    def alive(self, pid: int) -> bool:
+        """Check through the wrapped source whether a process still runs.
+
+        Returns:
+            bool: True if it exists and is not a zombie.
+        """
+        return self.source.alive(pid)
+
+    def send_signal(self, pid: int, force: bool) -> None:
+        """Signal a process through the wrapped source."""
+        self.source.send_signal(pid, force)
+
+    def send(self, pid: int, sig: signal.Signals) -> None:
+        """Send a signal through the wrapped source."""
+        self.source.send(pid, sig)
+
+    def signal_group(self, pgid: int, sig: signal.Signals) -> None:
+        """Signal a process group through the wrapped source."""
+        self.source.signal_group(pgid, sig)
+
+    def renice(self, pid: int, nice: int) -> None:
+        """Renice a process through the wrapped source."""
+        self.source.renice(pid, nice)
+
+    def ionice(self, pid: int, ioclass: int, level: int | None) -> None:
+        """Set a process's I/O priority through the wrapped source."""
+        self.source.ionice(pid, ioclass, level)
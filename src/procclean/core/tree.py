@@ -0,0 +1,120 @@
+"""Parent/child hierarchy of processes.
+
+The tree is built from the processes at hand: a process whose parent is not
+among them is a root, so a filtered list still forms a forest. Siblings keep
+the order of the input list, which lets callers sort first.
+"""
+
+from collections import defaultdict
+from dataclasses import dataclass
+
+from .models import ProcessInfo
+
+
+@dataclass(frozen=True, slots=True)
+class TreeRow:
+    """A process at its place in the tree."""
+
+    proc: ProcessInfo
+    depth: int  # 0 for roots
+    children: int  # Direct children among the listed processes
+    collapsed: bool = False  # Children are hidden
+
+
+def _children(procs: list[ProcessInfo]) -> dict[int, list[ProcessInfo]]:
+    """Group the processes by parent.
+
+    Returns:
+        dict[int, list[ProcessInfo]]: Children per listed parent PID, in
+        input order.
+    """
+    pids = {p.pid for p in procs}
+    children: defaultdict[int, list[ProcessInfo]] = defaultdict(list)
+    for proc in procs:
+        if proc.ppid in pids and proc.ppid != proc.pid:
+            children[proc.ppid].append(proc)
+    return children
+
+
+def _roots(procs: list[ProcessInfo]) -> list[ProcessInfo]:
+    pids = {p.pid for p in procs}
+    return [p for p in procs if p.ppid not in pids or p.ppid == p.pid]
+
+
+def tree_rows(
+    procs: list[ProcessInfo], collapsed: frozenset[int] | set[int] = frozenset()
+) -> list[TreeRow]:
+    """Flatten the process tree into display rows, parents before children.
+
+    Args:
+        procs: Processes to arrange, in sibling order.
+        collapsed: PIDs whose descendants are left out.
+
+    Returns:
+        list[TreeRow]: One row per shown process, depth first.
+    """
+    children = _children(procs)
+    rows: list[TreeRow] = []
+    seen: set[int] = set()
+    stack = [(proc, 0) for proc in reversed(_roots(procs))]
+    while stack:
+        proc, depth = stack.pop()
+        if proc.pid in seen:
+            continue
+        seen.add(proc.pid)
+        kids = children.get(proc.pid, [])
+        folded = proc.pid in collapsed and bool(kids)
+        rows.append(TreeRow(proc, depth, len(kids), folded))
+        if not folded:
+            stack.extend((kid, depth + 1) for kid in reversed(kids))
+    return rows
+
+
+def subtree_pids(procs: list[ProcessInfo], pid: int) -> list[int]:
+    """Collect a process and all of its descendants.
+
+    Args:
+        procs: Processes the tree is built from.
+        pid: Root of the subtree.
+
+    Returns:
+        list[int]: ``pid`` first, then its descendants depth first.
+    """
+    children = _children(procs)
+    found: list[int] = []
+    stack = [pid]
+    while stack:
+        current = stack.pop()
+        if current in found:
+            continue
+        found.append(current)
+        stack.extend(kid.pid for kid in reversed(children.get(current, [])))
+    return found
+
+
+def kill_order(procs: list[ProcessInfo], pids: list[int]) -> list[int]:
+    """Order PIDs so that children are signaled before their parents.
+
+    A parent killed first can leave its children to be reparented, or
+    respawn them; killing the leaves first takes a subtree down cleanly.
+
+    Args:
+        procs: Processes the tree is built from.
+        pids: PIDs to kill.
+
+    Returns:
+        list[int]: ``pids``, deepest first; PIDs of equal depth keep their
+        order.
+    """
+    parent = {p.pid: p.ppid for p in procs}
+
+    def depth(pid: int) -> int:
+        level, seen = 0, {pid}
+        while (pid := parent.get(pid, pid)) not in seen:
+            seen.add(pid)
+            if pid not in parent:
+                break
+            level += 1
+        return level
+
+    return sorted(pids, key=depth, reverse=True)
@@ -0,0 +1,109 @@
+"""Memory and CPU trends of processes across refreshes.
+
+A single RSS or CPU figure says nothing about where it is heading. The TUI
+keeps the last few samples of every process and marks values that rose (↑),
+fell (↓) or held steady (→) over them, so a leak shows up before it is big.
+"""
+
+from collections import deque
+from enum import StrEnum
+
+from .models import ProcessInfo
+
+TREND_SAMPLES = 5  # Samples per process the trend is taken over
+RSS_TREND_PERCENT = 5.0  # Relative RSS change that counts as a trend
+RSS_TREND_MIN_MB = 1.0  # Smaller RSS changes never count
+CPU_TREND_POINTS = 5.0  # CPU change in percentage points that counts
+
+
+class Trend(StrEnum):
+    """Direction of a value over the kept samples."""
+
+    UP = "↑"
+    DOWN = "↓"
+    FLAT = "→"
+
+
+def _trend(first: float, last: float, threshold: float) -> Trend:
+    """Classify the change from ``first`` to ``last``.
+
+    Returns:
+        Trend: UP or DOWN if it is at least ``threshold``, else FLAT.
+    """
+    if last - first >= threshold:
+        return Trend.UP
+    if first - last >= threshold:
+        return Trend.DOWN
+    return Trend.FLAT
+
+
+class TrendTracker:
+    """Recent RSS and CPU samples of every process, by refresh.
+
+    Processes are told apart by PID and start time, so a reused PID starts a
+    new history.
+    """
+
+    def __init__(self, samples: int = TREND_SAMPLES) -> None:
+        """Initialize an empty tracker.
+
+        Args:
+            samples: Samples kept per process.
+        """
+        self.samples = samples
+        self._history: dict[tuple[int, float], deque[tuple[float, float]]] = {}
+
+    def record(self, procs: list[ProcessInfo]) -> None:
+        """Add a sample for each process; forget processes no longer listed.
+
+        Args:
+            procs: Processes of one refresh.
+        """
+        history: dict[tuple[int, float], deque[tuple[float, float]]] = {}
+        for proc in procs:
+            key = (proc.pid, proc.create_time)
+            samples = self._history.get(key) or deque(maxlen=self.samples)
+            samples.append((proc.rss_mb, proc.cpu_percent))
+            history[key] = samples
+        self._history = history
+
+    def _ends(
+        self, proc: ProcessInfo
+    ) -> tuple[tuple[float, float], tuple[float, float]] | None:
+        """Get the oldest and newest sample of a process.
+
+        Returns:
+            tuple | None: Both samples, or None with fewer than two.
+        """
+        samples = self._history.get((proc.pid, proc.create_time))
+        if not samples or len(samples) < 2:  # noqa: PLR2004
+            return None
+        return samples[0], samples[-1]
+
+    def rss(self, proc: ProcessInfo) -> Trend | None:
+        """Get the RSS trend of a process.
+
+        A change counts if it is at least ``RSS_TREND_MIN_MB`` and
+        ``RSS_TREND_PERCENT`` of the oldest sample.
+
+        Returns:
+            Trend | None: The trend, or None before the second sample.
+        """
+        if (ends := self._ends(proc)) is None:
+            return None
+        first, last = ends[0][0], ends[1][0]
+        threshold = max(RSS_TREND_MIN_MB, first * RSS_TREND_PERCENT / 100)
+        return _trend(first, last, threshold)
+
+    def cpu(self, proc: ProcessInfo) -> Trend | None:
+        """Get the CPU trend of a process.
+
+        A change counts if it is at least ``CPU_TREND_POINTS`` percentage
+        points.
+
+        Returns:
+            Trend | None: The trend, or None before the second sample.
+        """
+        if (ends := self._ends(proc)) is None:
+            return None
+        return _trend(ends[0][1], ends[1][1], CPU_TREND_POINTS)
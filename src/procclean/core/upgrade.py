@@ -0,0 +1,36 @@
+"""Upgrade-pending processes, as ``needrestart`` finds them.
+
+After a package upgrade, running processes keep executing the old binary and
+libraries they mapped at startup. Those show up as "(deleted)" in
+``/proc/<pid>/exe`` and ``/proc/<pid>/maps``; the process needs a restart to
+pick up the fixes. Services are best restarted through systemd (``kill
+--via-manager --manager-action restart``).
+"""
+
+import psutil
+
+from .models import ProcessInfo
+from .process import DEFAULT_SOURCE
+from .source import ProcessSource
+
+
+def attach_deleted_libs(
+    procs: list[ProcessInfo], source: ProcessSource | None = None
+) -> list[ProcessInfo]:
+    """Fill ``deleted_libs`` on processes mapping replaced libraries.
+
+    Args:
+        procs: Processes to annotate in place.
+        source: Process backend used to read memory maps.
+
+    Returns:
+        list[ProcessInfo]: The same processes, for chaining.
+    """
+    source = source or DEFAULT_SOURCE
+    for proc in procs:
+        try:
+            libs = source.deleted_libraries(proc.pid)
+        except (psutil.NoSuchProcess, psutil.AccessDenied):
+            libs = ()
+        proc.deleted_libs = list(libs) or None
+    return procs
@@ -0,0 +1,102 @@
+"""QEMU/KVM and VirtualBox virtual machine processes.
+
+A VM process holds the guest's whole RAM, often backed by hugepages that RSS
+does not count, so its memory is RSS plus ``HugetlbPages`` from
+``/proc/<pid>/status``. Killing one pulls the power on a running guest, so VMs
+are only kill targets with ``--include-vms``.
+"""
+
+from pathlib import Path
+
+from .models import ProcessInfo
+from .process import read_status
+
+# Executable names of VM processes; qemu-system-* is matched by prefix
+VM_PROCESS_NAMES = frozenset({"qemu-kvm", "kvm", "VBoxHeadless", "VirtualBoxVM"})
+_QEMU_PREFIX = "qemu-system-"
+
+# Options naming the VM, in order of preference
+_QEMU_NAME_OPTIONS = ("-name",)
+_VBOX_NAME_OPTIONS = ("--comment", "--startvm", "-startvm", "-s")
+
+
+def is_vm(proc: ProcessInfo) -> bool:
+    """Check if a process runs a virtual machine.
+
+    Returns:
+        bool: True for qemu-system-*, qemu-kvm, kvm and VirtualBox VM processes.
+    """
+    names = {proc.name}
+    if proc.argv:
+        names.add(Path(proc.argv[0]).name)
+    return any(n in VM_PROCESS_NAMES or n.startswith(_QEMU_PREFIX) for n in names)
+
+
+def _option(argv: list[str], options: tuple[str, ...]) -> str | None:
+    for option in options:
+        for i, arg in enumerate(argv[:-1]):
+            if arg == option:
+                return argv[i + 1]
+        for arg in argv:
+            if arg.startswith(f"{option}="):
+                return arg.split("=", 1)[1]
+    return None
+
+
+def vm_name(argv: list[str]) -> str | None:
+    """Parse the VM name from a qemu or VirtualBox command line.
+
+    Handles ``-name win10``, ``-name guest=win10,debug-threads=on`` (libvirt)
+    and VirtualBox's ``--comment``/``--startvm``.
+
+    Returns:
+        str | None: The name, or None if the command line does not give one.
+    """
+    if value := _option(argv, _QEMU_NAME_OPTIONS):
+        for part in value.split(","):
+            if part.startswith("guest="):
+                return part.removeprefix("guest=")
+        return value.split(",", 1)[0] or None
+    return _option(argv, _VBOX_NAME_OPTIONS)
+
+
+def get_hugetlb_mb(pid: int) -> float:
+    """Read the hugepage memory mapped by a process.
+
+    Returns:
+        float: ``HugetlbPages`` in MB; 0 if unreadable.
+    """
+    value = read_status(pid).get("HugetlbPages", "0 kB").split()
+    try:
+        return int(value[0]) / 1024
+    except (IndexError, ValueError):
+        return 0.0
+
+
+def attach_vms(procs: list[ProcessInfo]) -> list[ProcessInfo]:
+    """Fill ``vm`` and ``hugetlb_mb`` on VM processes.
+
+    Args:
+        procs: Processes to annotate in place.
+
+    Returns:
+        list[ProcessInfo]: The same processes, for chaining.
+    """
+    for proc in procs:
+        if is_vm(proc):
+            proc.vm = vm_name(proc.argv) or "?"
+            proc.hugetlb_mb = get_hugetlb_mb(proc.pid)
+    return procs
+
+
+def exclude_vms(
+    procs: list[ProcessInfo],
+) -> tuple[list[ProcessInfo], list[ProcessInfo]]:
+    """Split off VM processes from a kill target set.
+
+    Returns:
+        tuple[list[ProcessInfo], list[ProcessInfo]]: The remaining targets and
+        the VMs taken out, with ``vm`` filled in.
+    """
+    vms = attach_vms([p for p in procs if is_vm(p)])
+    return [p for p in procs if not is_vm(p)], vms
@@ -0,0 +1,148 @@
+"""Wine and Proton process grouping.
+
+Every Windows program runs as its own ``.exe`` process next to a handful of
+Wine helpers (``services.exe``, ``winedevice.exe``, ...) and one
+``wineserver`` per prefix. Processes are grouped by prefix (``WINEPREFIX``,
+Proton's ``STEAM_COMPAT_DATA_PATH/pfx``, or ``~/.wine``) and named after the
+application they belong to. A prefix is shut down cleanly with
+``wineserver -k``, which ends all its processes together.
+"""
+
+from dataclasses import dataclass, field
+from pathlib import PureWindowsPath
+
+import psutil
+
+from .models import ProcessInfo
+from .process import DEFAULT_SOURCE
+from .source import ProcessSource
+from .supervisor import run_stop_command
+
+WINESERVER = "wineserver"
+
+# Processes Wine starts for itself; never taken as the application
+WINE_HELPERS = frozenset({
+    "conhost.exe",
+    "explorer.exe",
+    "plugplay.exe",
+    "rpcss.exe",
+    "rundll32.exe",
+    "services.exe",
+    "start.exe",
+    "steam.exe",  # Proton's Steam client shim
+    "svchost.exe",
+    "tabtip.exe",
+    "wineboot.exe",
+    "winedevice.exe",
+})
+_PRELOADERS = frozenset({"wine-preloader", "wine64-preloader", "wine", "wine64"})
+
+
+@dataclass
+class WineGroup:
+    """The processes of one Wine prefix."""
+
+    prefix: str
+    app: str  # Application .exe, or "-" if only Wine itself is running
+    steam_app_id: str | None = None  # Set for Proton games
+    procs: list[ProcessInfo] = field(default_factory=list)
+    wineserver_exe: str | None = None  # Binary of the running wineserver
+
+    @property
+    def rss_mb(self) -> float:
+        """Total RSS of the group."""
+        return sum(p.rss_mb for p in self.procs)
+
+    @property
+    def shutdown_command(self) -> list[str]:
+        """Command that ends every process of the prefix.
+
+        Returns:
+            list[str]: ``wineserver -k`` for this prefix, using the running
+            server's binary so Proton's own Wine build is used.
+        """
+        server = self.wineserver_exe or WINESERVER
+        return ["env", f"WINEPREFIX={self.prefix}", server, "-k"]
+
+
+def _exe_name(proc: ProcessInfo) -> str:
+    """Get the Windows executable name, e.g. ``game.exe``.
+
+    Returns:
+        str: Lower-cased basename of ``argv[0]`` (a Windows path under Wine),
+        or the process name.
+    """
+    argv0 = proc.argv[0] if proc.argv else proc.name
+    return PureWindowsPath(argv0).name.lower()
+
+
+def is_wine(proc: ProcessInfo) -> bool:
+    """Check if a process belongs to Wine.
+
+    Returns:
+        bool: True for wineserver, the Wine loaders and ``.exe`` processes.
+    """
+    return (
+        proc.name in {WINESERVER, *_PRELOADERS}
+        or proc.name.lower().endswith(".exe")
+        or _exe_name(proc).endswith(".exe")
+    )
+
+
+def wine_prefix(environ: dict[str, str]) -> str:
+    """Work out the Wine prefix from a process environment.
+
+    Returns:
+        str: ``WINEPREFIX``, Proton's prefix, ``$HOME/.wine``, or "?".
+    """
+    if prefix := environ.get("WINEPREFIX"):
+        return prefix.rstrip("/")
+    if compat := environ.get("STEAM_COMPAT_DATA_PATH"):
+        return f"{compat.rstrip('/')}/pfx"
+    if home := environ.get("HOME"):
+        return f"{home.rstrip('/')}/.wine"
+    return "?"
+
+
+def find_wine_groups(
+    procs: list[ProcessInfo], source: ProcessSource | None = None
+) -> list[WineGroup]:
+    """Group Wine processes by prefix and name their application.
+
+    The application is the oldest ``.exe`` that is not a Wine helper.
+
+    Args:
+        procs: Processes to consider.
+        source: Process backend used to read environments and executables.
+
+    Returns:
+        list[WineGroup]: One group per prefix, largest first.
+    """
+    source = source or DEFAULT_SOURCE
+    groups: dict[str, WineGroup] = {}
+    for proc in sorted(filter(is_wine, procs), key=lambda p: p.create_time):
+        environ = source.environ(proc.pid)
+        prefix = wine_prefix(environ)
+        group = groups.setdefault(prefix, WineGroup(prefix, "-"))
+        group.procs.append(proc)
+        group.steam_app_id = group.steam_app_id or environ.get("SteamAppId")
+        exe = _exe_name(proc)
+        if proc.name == WINESERVER:
+            try:
+                group.wineserver_exe = source.exe(proc.pid) or None
+            except (psutil.NoSuchProcess, psutil.AccessDenied):
+                group.wineserver_exe = None
+        elif group.app == "-" and exe.endswith(".exe") and exe not in WINE_HELPERS:
+            group.app = exe
+    return sorted(groups.values(), key=lambda g: -g.rss_mb)
+
+
+def shutdown_prefix(
+    group: WineGroup, source: ProcessSource | None = None
+) -> tuple[bool, str]:
+    """Shut down a whole Wine prefix with ``wineserver -k``.
+
+    Returns:
+        tuple[bool, str]: Success and a message naming the command.
+    """
+    return run_stop_command(group.shutdown_command, source or DEFAULT_SOURCE)
@@ -0,0 +1,36 @@
+"""Optional dependencies and the extras that install them.
+
+A plain ``pip install procclean`` only brings psutil, enough for library use
+of ``procclean.core``. The command line and the TUI need an extra::
+
+    pip install 'procclean[cli]'   # procclean <command>
+    pip install 'procclean[tui]'   # the TUI, plus the command line
+"""
+
+# Top-level modules of the optional dependencies, per extra
+EXTRA_MODULES: dict[str, tuple[str, ...]] = {
+    "cli": ("rich", "tabulate"),
+    "tui": ("textual",),
+    "graphics": ("textual_image", "PIL"),
+}
+
+
+def missing_extra(error: ModuleNotFoundError) -> SystemExit:
+    """Turn a failed import of an optional dependency into an install hint.
+
+    Args:
+        error: The failed import.
+
+    Returns:
+        SystemExit: Exit with a message naming the extra to install.
+
+    Raises:
+        ModuleNotFoundError: ``error`` itself if no extra provides the module.
+    """
+    module = (error.name or "").split(".")[0]
+    extra = next((x for x, mods in EXTRA_MODULES.items() if module in mods), None)
+    if extra is None:
+        raise error
+    return SystemExit(
+        f"procclean: {module} is not installed; run: pip install 'procclean[{extra}]'"
+    )
@@ -1,16 +1,53 @@
 """Output formatters for process data."""
 
+from .colors import (
+    COLOR_MODES,
+    COLOR_RULES,
+    HIGHLIGHT_STYLES,
+    ColorRule,
+    ansi,
+    cell_styles,
+    highlight_rules,
+    highlight_styles,
+    use_color,
+)
 from .columns import (
+    BREAKPOINTS,
+    BUILTIN_LAYOUTS,
     COLUMNS,
     DEFAULT_COLUMNS,
+    MEMORY_UNITS,
+    MIN_COLUMN_WIDTH,
+    Breakpoint,
     ClipSide,
     ColumnSpec,
     clip,
+    column_key,
+    column_spec,
+    columns_fields,
+    default_columns,
+    fit_columns,
+    format_age,
     get_available_columns,
+    get_breakpoint,
+    get_layouts,
+    layout_columns,
+    lib_names,
+    parse_columns,
+)
+from .designer import (
+    DESIGN_FORMATS,
+    UNIT_STEPS,
+    WIDTH_STEPS,
+    ColumnChoice,
+    ListDesign,
 )
 from .output import (
     format_csv,
+    format_header,
     format_json,
+    format_json_line,
+    format_jsonl,
     format_markdown,
     format_output,
     format_table,
@@ -18,16 +55,49 @@ from .output import (
 )
 
 __all__ = [
+    "BREAKPOINTS",
+    "BUILTIN_LAYOUTS",
+    "COLOR_MODES",
+    "COLOR_RULES",
     "COLUMNS",
     "DEFAULT_COLUMNS",
+    "DESIGN_FORMATS",
+    "HIGHLIGHT_STYLES",
+    "MEMORY_UNITS",
+    "MIN_COLUMN_WIDTH",
+    "UNIT_STEPS",
+    "WIDTH_STEPS",
+    "Breakpoint",
     "ClipSide",
+    "ColorRule",
+    "ColumnChoice",
     "ColumnSpec",
+    "ListDesign",
+    "ansi",
+    "cell_styles",
     "clip",
+    "column_key",
+    "column_spec",
+    "columns_fields",
+    "default_columns",
+    "fit_columns",
+    "format_age",
     "format_csv",
+    "format_header",
     "format_json",
+    "format_json_line",
+    "format_jsonl",
     "format_markdown",
     "format_output",
     "format_table",
     "get_available_columns",
+    "get_breakpoint",
+    "get_layouts",
     "get_rows",
+    "highlight_rules",
+    "highlight_styles",
+    "layout_columns",
+    "lib_names",
+    "parse_columns",
+    "use_color",
 ]
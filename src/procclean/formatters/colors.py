@@ -0,0 +1,186 @@
+"""Value-based coloring shared by the CLI table and the TUI.
+
+Rules decide the style of cells from the values of a process: memory hogs get
+a red RSS cell, orphans a yellow row and system services a dim one. Styles
+are rich/Textual style names, so the TUI uses them as is and the CLI table
+turns them into ANSI escapes.
+
+The TUI highlights rows by risk instead, with styles from the ``[highlight]``
+table of the config file; an empty style turns a highlight off::
+
+    [highlight]
+    orphan = "bold yellow"
+    stale = ""
+"""
+
+import os
+import sys
+from collections.abc import Callable, Sequence
+from dataclasses import dataclass
+
+from rich.errors import StyleSyntaxError
+from rich.style import Style
+
+from procclean.core import (
+    HIGH_MEMORY_THRESHOLD_MB,
+    ErrorCode,
+    ProccleanError,
+    ProcessInfo,
+    is_system_service,
+    load_config,
+)
+
+COLOR_MODES = ("auto", "always", "never")
+
+# SGR codes of the style names rules may use
+_SGR = {
+    "bold": "1",
+    "dim": "2",
+    "italic": "3",
+    "underline": "4",
+    "red": "31",
+    "green": "32",
+    "yellow": "33",
+    "blue": "34",
+    "magenta": "35",
+    "cyan": "36",
+}
+
+
+@dataclass(frozen=True, slots=True)
+class ColorRule:
+    """Style for the cells of processes matching a test."""
+
+    name: str
+    style: str
+    test: Callable[[ProcessInfo], bool]
+    columns: frozenset[str] = frozenset()  # Empty: the whole row
+
+
+_HIGH_MEMORY = ColorRule(
+    "high-memory",
+    "red",
+    lambda p: p.rss_mb > HIGH_MEMORY_THRESHOLD_MB,
+    frozenset({"rss_mb"}),
+)
+
+# Applied in order; later rules override the colors of earlier ones
+COLOR_RULES = (
+    ColorRule("system", "dim", is_system_service),
+    ColorRule("orphan", "yellow", lambda p: p.is_orphan),
+    _HIGH_MEMORY,
+)
+
+# Row highlights of the TUI by increasing precedence
+HIGHLIGHT_STYLES = {
+    "protected": "dim",
+    "orphan": "yellow",
+    "stale": "magenta",
+    "selected-other-user": "bold red",
+}
+
+
+def cell_styles(
+    proc: ProcessInfo,
+    columns: Sequence[str],
+    rules: Sequence[ColorRule] = COLOR_RULES,
+) -> dict[str, str]:
+    """Resolve the style of every cell of a process row.
+
+    Args:
+        proc: Process the row shows.
+        columns: Column keys of the row.
+        rules: Rules to apply, in order.
+
+    Returns:
+        dict[str, str]: Style per column key; empty for unstyled cells.
+    """
+    styles = dict.fromkeys(columns, "")
+    for rule in rules:
+        if not rule.test(proc):
+            continue
+        for key in columns:
+            if not rule.columns or key in rule.columns:
+                styles[key] = f"{styles[key]} {rule.style}".lstrip()
+    return styles
+
+
+def highlight_styles() -> dict[str, str]:
+    """Read the ``[highlight]`` config table over the default styles.
+
+    Returns:
+        dict[str, str]: Style per highlight name; empty for disabled ones.
+
+    Raises:
+        ProccleanError: With ``INVALID_CONFIG`` for unknown highlights and
+            values that are not valid styles.
+    """
+    styles = dict(HIGHLIGHT_STYLES)
+    for name, style in load_config().get("highlight", {}).items():
+        key = f"highlight.{name}"
+        if name not in HIGHLIGHT_STYLES:
+            choices = ", ".join(HIGHLIGHT_STYLES)
+            msg = f"Unknown highlight '{name}' (choose from {choices})"
+            raise ProccleanError(ErrorCode.INVALID_CONFIG, msg, key=key)
+        if not isinstance(style, str):
+            msg = f"Invalid {key}: expected a style string"
+            raise ProccleanError(ErrorCode.INVALID_CONFIG, msg, key=key)
+        try:
+            if style:
+                Style.parse(style)
+        except StyleSyntaxError as e:
+            msg = f"Invalid {key}: {e}"
+            raise ProccleanError(ErrorCode.INVALID_CONFIG, msg, key=key) from e
+        styles[name] = style
+    return styles
+
+
+def highlight_rules(
+    user: str | None, is_selected: Callable[[int], bool]
+) -> list[ColorRule]:
+    """Build the rules of the TUI table.
+
+    Args:
+        user: Current user; selected processes of everyone else are
+            highlighted. None disables that highlight.
+        is_selected: Whether a PID is selected.
+
+    Returns:
+        list[ColorRule]: The enabled highlights, then the memory cell color.
+    """
+    tests: dict[str, Callable[[ProcessInfo], bool]] = {
+        "protected": is_system_service,
+        "orphan": lambda p: p.is_orphan,
+        "stale": lambda p: p.exe_deleted,
+        "selected-other-user": lambda p: (
+            user is not None and p.username != user and is_selected(p.pid)
+        ),
+    }
+    styles = highlight_styles()
+    rules = [ColorRule(name, styles[name], tests[name]) for name in tests]
+    return [*(rule for rule in rules if rule.style), _HIGH_MEMORY]
+
+
+def ansi(text: str, style: str) -> str:
+    """Wrap text in the ANSI escapes of a style.
+
+    Returns:
+        str: The styled text; unchanged if the style has no known attribute.
+    """
+    codes = [_SGR[word] for word in style.split() if word in _SGR]
+    if not codes:
+        return text
+    return f"\x1b[{';'.join(codes)}m{text}\x1b[0m"
+
+
+def use_color(mode: str) -> bool:
+    """Decide whether to color output for a ``--color`` mode.
+
+    ``auto`` colors only a terminal, and honors ``NO_COLOR``.
+
+    Returns:
+        bool: True to color output written to stdout.
+    """
+    if mode != "auto":
+        return mode == "always"
+    return sys.stdout.isatty() and not os.environ.get("NO_COLOR")
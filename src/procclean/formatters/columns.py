@@ -1,11 +1,22 @@
 """Column specifications for process tables."""
 
-from collections.abc import Callable
+import time
+from collections.abc import Callable, Sequence
 from dataclasses import dataclass, replace
 from enum import StrEnum, auto
-from typing import Self
+from pathlib import Path
+from typing import Any, Self
 
-from procclean.core import ProcessInfo
+from procclean.core import (
+    SLOW_FIELDS,
+    STATUS_FIELDS,
+    ErrorCode,
+    FieldMask,
+    ProccleanError,
+    ProcessInfo,
+    load_config,
+    port_numbers,
+)
 
 
 class ClipSide(StrEnum):
@@ -46,6 +57,30 @@ class ColumnSpec[T]:
     fmt: Callable[[T], str] = str
     max_width: int | None = None
     clip_side: ClipSide = ClipSide.RIGHT
+    fields: FieldMask = FieldMask.NONE  # Optional fields the value depends on
+    description: str = ""  # For `procclean columns`; defaults to the header
+    value_type: str = "str"  # int, float, str, list or duration, as in JSON
+
+    @property
+    def slow(self) -> bool:
+        """Whether the column needs fields that are slow to collect."""
+        return bool(self.fields & SLOW_FIELDS)
+
+    def to_dict(self) -> dict[str, Any]:
+        """Describe the column for `procclean columns`.
+
+        Returns:
+            dict[str, Any]: Key, header, type, description, cost and whether
+            ``list`` shows it by default.
+        """
+        return {
+            "key": self.key,
+            "header": self.header,
+            "type": self.value_type,
+            "description": self.description or self.header,
+            "slow": self.slow,
+            "default": self.key in DEFAULT_COLUMNS,
+        }
 
     def extract(self, proc: ProcessInfo) -> str:
         """Extract and format value from a process.
@@ -74,38 +109,375 @@ class ColumnSpec[T]:
         """
         return replace(self, max_width=width, clip_side=side)
 
+    def with_unit(self, unit: str) -> Self:
+        """Return a copy of a memory column showing another unit.
+
+        Args:
+            unit: One of ``MEMORY_UNITS``.
+
+        Returns:
+            A new ``ColumnSpec`` converting from MB, with the unit in its
+            header.
+        """
+        factor, digits = MEMORY_UNITS[unit]
+        get = self.get
+
+        def convert(proc: ProcessInfo) -> float | None:
+            value = get(proc)
+            return None if value is None else value * factor
+
+        def fmt(value: float | None) -> str:
+            return "-" if value is None else f"{value:.{digits}f}"
+
+        header = self.header.replace("(MB)", f"({unit.upper()})")
+        return replace(self, header=header, get=convert, fmt=fmt)
+
 
 def _fmt_float1(v: float) -> str:
     return f"{v:.1f}"
 
 
+def _fmt_mb(v: float | None) -> str:
+    return "-" if v is None else f"{v:.1f}"
+
+
+def format_age(seconds: float) -> str:
+    """Render how long a process has been running, e.g. "4h 12m".
+
+    Returns:
+        str: Seconds under a minute, minutes under an hour, then hours and
+        minutes, then days and hours.
+    """
+    seconds = max(int(seconds), 0)
+    if seconds < 60:
+        return f"{seconds}s"
+    minutes = seconds // 60
+    if minutes < 60:
+        return f"{minutes}m"
+    hours, minutes = divmod(minutes, 60)
+    if hours < 24:
+        return f"{hours}h {minutes}m"
+    days, hours = divmod(hours, 24)
+    return f"{days}d {hours}h"
+
+
+def _fmt_age(create_time: float) -> str:
+    return format_age(time.time() - create_time) if create_time > 0 else "-"
+
+
+def _fmt_package(p: ProcessInfo) -> str:
+    if p.package is None:
+        return "-"
+    return p.package or "unpackaged"
+
+
+def lib_names(libs: list[str] | None) -> str:
+    """Summarize library paths by file name, e.g. "libssl.so.3,libc.so.6".
+
+    Returns:
+        str: Comma-separated names, or "-" if there are none.
+    """
+    return ",".join(Path(lib).name for lib in libs) if libs else "-"
+
+
 def _fmt_status(p: ProcessInfo) -> str:
     parts = [p.status]
+    if p.is_kernel_thread:
+        parts.append("[kthread]")
     if p.is_orphan:
         parts.append("[orphan]")
     if p.in_tmux:
         parts.append("[tmux]")
+    if p.cwd_deleted:
+        parts.append("[cwd-deleted]")
+    if p.runtime_env_deleted:
+        parts.append("[env-deleted]")
+    if p.media:
+        parts.append("[media]")
+    if p.inhibitor:
+        parts.append("[inhibit]")
+    if p.deleted_libs:
+        parts.append("[needs-restart]")
+    if p.is_realtime:
+        parts.append("[realtime]")
+    if p.is_privileged:
+        parts.append("[priv]")
+    if p.is_unpackaged:
+        parts.append("[unpackaged]")
     return " ".join(parts)
 
 
+# Repos are looked up from the cwd, skipped where it was removed
+_REPO_FIELDS = FieldMask.REPO | FieldMask.CWD | FieldMask.CWD_DELETED
+
 # Column definitions
 COLUMNS: dict[str, ColumnSpec] = {
-    "pid": ColumnSpec("pid", "PID", lambda p: p.pid),
-    "name": ColumnSpec("name", "Name", lambda p: p.name, max_width=25),
-    "rss_mb": ColumnSpec("rss_mb", "RAM (MB)", lambda p: p.rss_mb, _fmt_float1),
+    "pid": ColumnSpec(
+        "pid", "PID", lambda p: p.pid, description="Process ID", value_type="int"
+    ),
+    "name": ColumnSpec(
+        "name", "Name", lambda p: p.name, max_width=25, description="Process name"
+    ),
+    "rss_mb": ColumnSpec(
+        "rss_mb",
+        "RAM (MB)",
+        lambda p: p.rss_mb,
+        _fmt_float1,
+        description="Resident memory, counting shared pages in full",
+        value_type="float",
+    ),
     "cpu_percent": ColumnSpec(
-        "cpu_percent", "CPU%", lambda p: p.cpu_percent, _fmt_float1
+        "cpu_percent",
+        "CPU%",
+        lambda p: p.cpu_percent,
+        _fmt_float1,
+        fields=FieldMask.CPU,
+        description="CPU usage over --cpu-interval",
+        value_type="float",
     ),
     "cwd": ColumnSpec(
-        "cwd", "CWD", lambda p: p.cwd, max_width=40, clip_side=ClipSide.LEFT
+        "cwd",
+        "CWD",
+        lambda p: p.cwd,
+        max_width=40,
+        clip_side=ClipSide.LEFT,
+        fields=FieldMask.CWD,
+        description="Working directory",
+    ),
+    "ppid": ColumnSpec(
+        "ppid", "PPID", lambda p: p.ppid, description="Parent PID", value_type="int"
+    ),
+    "age": ColumnSpec(
+        "age",
+        "Age",
+        lambda p: p.create_time,
+        _fmt_age,
+        description="Time since the process started",
+        value_type="duration",
+    ),
+    "pgid": ColumnSpec(
+        "pgid",
+        "PGID",
+        lambda p: p.pgid,
+        fields=FieldMask.GROUP,
+        description="Process group ID",
+        value_type="int",
+    ),
+    "sid": ColumnSpec(
+        "sid",
+        "SID",
+        lambda p: p.sid,
+        fields=FieldMask.GROUP,
+        description="Session ID",
+        value_type="int",
     ),
-    "ppid": ColumnSpec("ppid", "PPID", lambda p: p.ppid),
     "parent_name": ColumnSpec(
-        "parent_name", "Parent", lambda p: p.parent_name, max_width=15
+        "parent_name",
+        "Parent",
+        lambda p: p.parent_name,
+        max_width=15,
+        fields=FieldMask.PARENT,
+        description="Name of the parent process",
+    ),
+    "status": ColumnSpec(
+        "status",
+        "Status",
+        lambda p: p,
+        _fmt_status,
+        fields=STATUS_FIELDS,
+        description="State plus markers such as [orphan] and [needs-restart]",
+    ),
+    "cmdline": ColumnSpec(
+        "cmdline",
+        "Command",
+        lambda p: p.cmdline,
+        max_width=60,
+        description="Full command line",
+    ),
+    "username": ColumnSpec(
+        "username", "User", lambda p: p.username, description="Owner"
+    ),
+    "nice": ColumnSpec(
+        "nice",
+        "NI",
+        lambda p: p.nice,
+        fields=FieldMask.SCHED,
+        description="Nice value",
+        value_type="int",
+    ),
+    "sched_policy": ColumnSpec(
+        "sched_policy",
+        "Policy",
+        lambda p: p.sched_policy,
+        fields=FieldMask.SCHED,
+        description="Scheduling policy: other, batch, idle, fifo, rr or deadline",
+    ),
+    "rt_priority": ColumnSpec(
+        "rt_priority",
+        "RT",
+        lambda p: p.rt_priority,
+        fields=FieldMask.SCHED,
+        description="Realtime priority (1-99), 0 for normal processes",
+        value_type="int",
+    ),
+    "privileges": ColumnSpec(
+        "privileges",
+        "Priv",
+        lambda p: ",".join(p.privileges) or "-",
+        fields=FieldMask.PRIVILEGES,
+        description="Why the process is privileged: root, caps and/or setuid",
+        value_type="list",
+    ),
+    "confinement": ColumnSpec(
+        "confinement",
+        "Sandbox",
+        lambda p: p.confinement,
+        fields=FieldMask.CONFINEMENT,
+        description="Seccomp mode and no_new_privs",
+    ),
+    "package": ColumnSpec(
+        "package",
+        "Package",
+        lambda p: p,
+        _fmt_package,
+        max_width=25,
+        description="Distro package owning the executable (with --attribute)",
+    ),
+    "repo": ColumnSpec(
+        "repo",
+        "Repo",
+        lambda p: p.repo or "-",
+        max_width=40,
+        clip_side=ClipSide.LEFT,
+        fields=_REPO_FIELDS,
+        description="Git checkout the working directory lies in",
+    ),
+    "branch": ColumnSpec(
+        "branch",
+        "Branch",
+        lambda p: p.branch or "-",
+        max_width=25,
+        fields=_REPO_FIELDS,
+        description="Checked-out branch of that repo, or commit if detached",
+    ),
+    "pod": ColumnSpec(
+        "pod",
+        "Pod",
+        lambda p: p.pod or "-",
+        max_width=30,
+        fields=FieldMask.POD,
+        description="Kubernetes pod",
+    ),
+    "namespace": ColumnSpec(
+        "namespace",
+        "Namespace",
+        lambda p: p.namespace or "-",
+        fields=FieldMask.POD,
+        description="Kubernetes namespace of the pod",
+    ),
+    "container": ColumnSpec(
+        "container",
+        "Container",
+        lambda p: p.container or "-",
+        max_width=25,
+        fields=FieldMask.POD,
+        description="Container in the pod",
+    ),
+    "vm": ColumnSpec(
+        "vm",
+        "VM",
+        lambda p: p.vm or "-",
+        max_width=25,
+        fields=FieldMask.VM,
+        description="Name of the QEMU or VirtualBox guest the process runs",
+    ),
+    "hugetlb_mb": ColumnSpec(
+        "hugetlb_mb",
+        "Huge (MB)",
+        lambda p: p.hugetlb_mb or 0.0,
+        _fmt_float1,
+        fields=FieldMask.VM,
+        description="Hugepage memory of a VM, which RSS leaves out",
+        value_type="float",
+    ),
+    "media": ColumnSpec(
+        "media",
+        "Media",
+        lambda p: p.media or "-",
+        fields=FieldMask.MEDIA,
+        description="Open camera, microphone or audio streams",
+    ),
+    "deleted_libs": ColumnSpec(
+        "deleted_libs",
+        "Deleted libs",
+        lambda p: lib_names(p.deleted_libs),
+        max_width=40,
+        fields=FieldMask.DELETED_LIBS,
+        description="Mapped libraries an upgrade replaced",
+        value_type="list",
+    ),
+    "inhibitor": ColumnSpec(
+        "inhibitor",
+        "Inhibitor",
+        lambda p: p.inhibitor or "-",
+        max_width=40,
+        fields=FieldMask.INHIBITOR,
+        description="Sleep or shutdown inhibitor locks held",
+    ),
+    "runtime_env": ColumnSpec(
+        "runtime_env",
+        "Runtime",
+        lambda p: p.runtime_env or "-",
+        max_width=30,
+        clip_side=ClipSide.LEFT,
+        fields=FieldMask.RUNTIME_ENV,
+        description="Interpreter environment, e.g. a virtualenv or nvm version",
+    ),
+    "ports": ColumnSpec(
+        "ports",
+        "Ports",
+        lambda p: port_numbers(p.ports),
+        max_width=30,
+        fields=FieldMask.PORTS,
+        description="Ports the process listens on",
+        value_type="list",
+    ),
+    "pss_mb": ColumnSpec(
+        "pss_mb",
+        "PSS (MB)",
+        lambda p: p.pss_mb,
+        _fmt_mb,
+        fields=FieldMask.SMAPS,
+        description="Memory with shared pages split among their users",
+        value_type="float",
+    ),
+    "uss_mb": ColumnSpec(
+        "uss_mb",
+        "USS (MB)",
+        lambda p: p.uss_mb,
+        _fmt_mb,
+        fields=FieldMask.SMAPS,
+        description="Memory only this process uses, freed by killing it",
+        value_type="float",
+    ),
+    "shared_mb": ColumnSpec(
+        "shared_mb",
+        "Shared (MB)",
+        lambda p: p.shared_mb,
+        _fmt_mb,
+        fields=FieldMask.SMAPS,
+        description="Resident memory shared with other processes",
+        value_type="float",
+    ),
+    "swap_mb": ColumnSpec(
+        "swap_mb",
+        "Swap (MB)",
+        lambda p: p.swap_mb,
+        _fmt_mb,
+        fields=FieldMask.SMAPS,
+        description="Memory swapped out",
+        value_type="float",
     ),
-    "status": ColumnSpec("status", "Status", lambda p: p, _fmt_status),
-    "cmdline": ColumnSpec("cmdline", "Command", lambda p: p.cmdline, max_width=60),
-    "username": ColumnSpec("username", "User", lambda p: p.username),
 }
 
 DEFAULT_COLUMNS: tuple[str, ...] = (
@@ -119,6 +491,16 @@ DEFAULT_COLUMNS: tuple[str, ...] = (
 )
 
 
+# Units memory columns (keys ending in "_mb") take, as factor from MB and
+# decimals shown
+MEMORY_UNITS: dict[str, tuple[float, int]] = {
+    "kb": (1024, 0),
+    "mb": (1, 1),
+    "gb": (1 / 1024, 2),
+}
+MIN_COLUMN_WIDTH = 4  # One character and the ellipsis
+
+
 def get_available_columns() -> list[str]:
     """Return list of available column keys.
 
@@ -126,3 +508,241 @@ def get_available_columns() -> list[str]:
         A list of keys for all available columns.
     """
     return list(COLUMNS)
+
+
+def columns_fields(columns: Sequence[str]) -> FieldMask:
+    """Collect the optional fields a set of columns displays.
+
+    Args:
+        columns: Column keys; unknown keys are ignored.
+
+    Returns:
+        FieldMask: Union of the columns' fields.
+    """
+    mask = FieldMask.NONE
+    for key in map(column_key, columns):
+        if key in COLUMNS:
+            mask |= COLUMNS[key].fields
+    return mask
+
+
+def column_key(entry: str) -> str:
+    """Strip the width and unit off a ``--columns`` entry.
+
+    Returns:
+        str: The column key, e.g. "cwd" for "cwd:40".
+    """
+    return entry.partition(":")[0]
+
+
+def column_spec(entry: str) -> ColumnSpec:
+    """Resolve a ``--columns`` entry to its spec.
+
+    Entries are a key, optionally followed by a width and, for memory
+    columns, a unit: "cwd:40", "rss_mb:gb" or "rss_mb:gb:8".
+
+    Args:
+        entry: The entry.
+
+    Returns:
+        ColumnSpec: The column, clipped to the width and in the unit given.
+
+    Raises:
+        ProccleanError: With ``INVALID_COLUMN`` if the key is unknown, or the
+            width or unit is invalid.
+    """
+    key, *options = entry.split(":")
+    if key not in COLUMNS:
+        msg = f"Unknown column(s): {key}"
+        raise ProccleanError(
+            ErrorCode.INVALID_COLUMN,
+            msg,
+            columns=[key],
+            choices=get_available_columns(),
+        )
+    spec = COLUMNS[key]
+    for option in options:
+        if option.isdigit() and int(option) >= MIN_COLUMN_WIDTH:
+            spec = spec.with_width(int(option), spec.clip_side)
+        elif option in MEMORY_UNITS and key.endswith("_mb"):
+            spec = spec.with_unit(option)
+        else:
+            expected = f"a width of at least {MIN_COLUMN_WIDTH}"
+            if key.endswith("_mb"):
+                expected += f" or a unit ({'/'.join(MEMORY_UNITS)})"
+            msg = f"Invalid column '{entry}': expected {expected}"
+            raise ProccleanError(ErrorCode.INVALID_COLUMN, msg, columns=[entry])
+    return spec
+
+
+def parse_columns(value: str | None) -> list[str] | None:
+    """Parse a comma-separated ``--columns`` value.
+
+    Args:
+        value: Raw option value, or None when the option was not given.
+
+    Returns:
+        The column entries, keys with any width or unit (see
+        ``column_spec``), or None to use the defaults.
+
+    Raises:
+        ProccleanError: With ``INVALID_COLUMN`` if any key is unknown, or a
+            width or unit is invalid.
+    """
+    if not value:
+        return None
+    columns = [c.strip() for c in value.split(",") if c.strip()]
+    if unknown := [c for c in map(column_key, columns) if c not in COLUMNS]:
+        msg = f"Unknown column(s): {', '.join(unknown)}"
+        raise ProccleanError(
+            ErrorCode.INVALID_COLUMN,
+            msg,
+            columns=unknown,
+            choices=get_available_columns(),
+        )
+    for entry in columns:
+        column_spec(entry)
+    return columns
+
+
+# Built-in named layouts; ``[layouts]`` in the config adds or overrides these
+BUILTIN_LAYOUTS: dict[str, tuple[str, ...]] = {
+    "minimal": ("pid", "name", "rss_mb"),
+    "debug": ("pid", "ppid", "name", "cmdline", "status"),
+}
+
+
+def get_layouts() -> dict[str, list[str]]:
+    """Return the named column layouts.
+
+    Config entries map a name to a comma-separated string or a list of keys,
+    e.g. ``wide = "pid,name,rss_mb,cwd,cmdline"``.
+
+    Returns:
+        dict[str, list[str]]: Column keys keyed by layout name.
+
+    Raises:
+        ProccleanError: With ``INVALID_CONFIG`` for malformed layouts or
+            unknown columns.
+    """
+    layouts = {name: list(cols) for name, cols in BUILTIN_LAYOUTS.items()}
+    for name, value in load_config().get("layouts", {}).items():
+        if isinstance(value, list):
+            value = ",".join(map(str, value))
+        try:
+            columns = parse_columns(value) if isinstance(value, str) else None
+        except ProccleanError as e:
+            msg = f"Invalid layout '{name}': {e.message}"
+            raise ProccleanError(ErrorCode.INVALID_CONFIG, msg, layout=name) from e
+        if not columns:
+            msg = f"Invalid layout '{name}': expected a list of columns"
+            raise ProccleanError(ErrorCode.INVALID_CONFIG, msg, layout=name)
+        layouts[name] = columns
+    return layouts
+
+
+def default_columns() -> list[str] | None:
+    """Read the ``[defaults] columns`` setting.
+
+    Returns:
+        list[str] | None: The configured column keys, or None for the
+        responsive default columns.
+
+    Raises:
+        ProccleanError: With ``INVALID_CONFIG`` for malformed values or unknown
+            columns.
+    """
+    value = load_config().get("defaults", {}).get("columns")
+    if value is None:
+        return None
+    if isinstance(value, list):
+        value = ",".join(map(str, value))
+    try:
+        columns = parse_columns(value) if isinstance(value, str) else None
+    except ProccleanError as e:
+        msg = f"Invalid defaults.columns: {e.message}"
+        raise ProccleanError(
+            ErrorCode.INVALID_CONFIG, msg, key="defaults.columns"
+        ) from e
+    if not columns:
+        msg = "Invalid defaults.columns: expected a list of columns"
+        raise ProccleanError(ErrorCode.INVALID_CONFIG, msg, key="defaults.columns")
+    return columns
+
+
+def layout_columns(name: str | None) -> list[str] | None:
+    """Resolve a ``--layout`` name to its column keys.
+
+    Args:
+        name: Layout name, or None when the option was not given.
+
+    Returns:
+        The column keys; without a name, the ``[defaults] columns`` of the
+        config file, or None to use the responsive defaults.
+
+    Raises:
+        ProccleanError: With ``INVALID_ARGUMENT`` if no such layout exists.
+    """
+    if name is None:
+        return default_columns()
+    layouts = get_layouts()
+    if name not in layouts:
+        msg = f"Unknown layout '{name}'"
+        raise ProccleanError(
+            ErrorCode.INVALID_ARGUMENT, msg, layout=name, choices=list(layouts)
+        )
+    return layouts[name]
+
+
+@dataclass(frozen=True, slots=True)
+class Breakpoint:
+    """Layout applied when the available width is below ``max_width``."""
+
+    max_width: int
+    drop: frozenset[str]
+    cwd_width: int
+
+
+# Ordered narrowest first: low-priority columns go first, then cwd shrinks
+BREAKPOINTS: tuple[Breakpoint, ...] = (
+    Breakpoint(80, frozenset({"parent_name", "ppid", "status"}), 20),
+    Breakpoint(100, frozenset({"parent_name", "ppid"}), 25),
+    Breakpoint(120, frozenset({"parent_name"}), 30),
+)
+
+
+def get_breakpoint(width: int | None) -> Breakpoint | None:
+    """Find the layout breakpoint for an available width.
+
+    Args:
+        width: Available width in characters, or None for unconstrained output.
+
+    Returns:
+        The narrowest matching breakpoint, or None if the width fits everything.
+    """
+    if width is None:
+        return None
+    return next((bp for bp in BREAKPOINTS if width < bp.max_width), None)
+
+
+def fit_columns(columns: Sequence[str], width: int | None) -> list[ColumnSpec]:
+    """Resolve column keys to specs adapted to the available width.
+
+    Args:
+        columns: Ordered column entries, as from ``parse_columns``; unknown
+            keys are ignored.
+        width: Available width in characters, or None to keep all columns.
+
+    Returns:
+        Column specs with low-priority columns dropped and cwd narrowed
+        according to the matching breakpoint.
+    """
+    specs = [column_spec(c) for c in columns if column_key(c) in COLUMNS]
+    bp = get_breakpoint(width)
+    if bp is None:
+        return specs
+    return [
+        s.with_width(bp.cwd_width, s.clip_side) if s.key == "cwd" else s
+        for s in specs
+        if s.key not in bp.drop
+    ]
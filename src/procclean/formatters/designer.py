@@ -0,0 +1,181 @@
+"""Interactive design of a process list, for ``procclean fmt --interactive``.
+
+``ListDesign`` holds what the designer toggles: which columns are shown and
+in what order, their widths and memory units, and the output format. It
+previews sample processes the way ``procclean list`` would print them, and
+spells the design as the ``--columns``/``--format`` options that reproduce
+it.
+"""
+
+import shlex
+from collections.abc import Sequence
+from dataclasses import dataclass
+from typing import Self
+
+from procclean.core import ProcessInfo
+
+from .columns import COLUMNS, DEFAULT_COLUMNS, MEMORY_UNITS, column_spec
+from .output import format_output
+
+# Formats of `procclean list`; JSON, JSON Lines and CSV show every field
+DESIGN_FORMATS = ("table", "json", "jsonl", "csv", "md")
+# Widths cycled through; None leaves a column as wide as its values
+WIDTH_STEPS: tuple[int | None, ...] = (None, 8, 12, 20, 30, 40, 60)
+# Units cycled through on memory columns; None keeps MB
+UNIT_STEPS: tuple[str | None, ...] = (None, *(u for u in MEMORY_UNITS if u != "mb"))
+
+
+def _next[T](steps: Sequence[T], current: T) -> T:
+    """Pick the step after the current one, wrapping around.
+
+    Returns:
+        The next step; the first if the current one is not a step.
+    """
+    index = steps.index(current) + 1 if current in steps else 0
+    return steps[index % len(steps)]
+
+
+@dataclass(slots=True)
+class ColumnChoice:
+    """One column of a design."""
+
+    key: str
+    shown: bool = False
+    width: int | None = None
+    unit: str | None = None  # One of MEMORY_UNITS; None keeps MB
+
+    @classmethod
+    def parse(cls, entry: str) -> Self:
+        """Read a ``--columns`` entry such as "rss_mb:gb:8" as a shown column.
+
+        Returns:
+            ColumnChoice: The column, with its width and unit.
+
+        Raises:
+            ProccleanError: With ``INVALID_COLUMN`` for an invalid entry.
+        """
+        column_spec(entry)
+        key, *options = entry.split(":")
+        choice = cls(key, shown=True)
+        for option in options:
+            if option.isdigit():
+                choice.width = int(option)
+            elif option != "mb":
+                choice.unit = option
+        return choice
+
+    @property
+    def takes_unit(self) -> bool:
+        """Whether the column shows memory, which comes in ``MEMORY_UNITS``."""
+        return self.key.endswith("_mb")
+
+    @property
+    def entry(self) -> str:
+        """The column as a ``--columns`` entry, e.g. "cwd:40"."""
+        options = [self.unit, str(self.width) if self.width else None]
+        return ":".join([self.key, *filter(None, options)])
+
+
+@dataclass(slots=True)
+class ListDesign:
+    """Columns, in display order, and format of a process list."""
+
+    columns: list[ColumnChoice]
+    fmt: str = "table"
+
+    @classmethod
+    def from_columns(
+        cls, entries: Sequence[str] | None = None, fmt: str = "table"
+    ) -> Self:
+        """Start from a list of columns, shown first, then every other column.
+
+        Args:
+            entries: ``--columns`` entries; None for the default columns.
+            fmt: Output format, one of ``DESIGN_FORMATS``.
+
+        Returns:
+            ListDesign: The design.
+
+        Raises:
+            ProccleanError: With ``INVALID_COLUMN`` for an invalid entry.
+        """
+        shown = [ColumnChoice.parse(e) for e in entries or DEFAULT_COLUMNS]
+        keys = {c.key for c in shown}
+        hidden = [ColumnChoice(key) for key in COLUMNS if key not in keys]
+        return cls([*shown, *hidden], fmt)
+
+    @property
+    def entries(self) -> list[str]:
+        """``--columns`` entries of the shown columns, in order."""
+        return [c.entry for c in self.columns if c.shown]
+
+    def toggle(self, index: int) -> None:
+        """Show or hide a column."""
+        self.columns[index].shown = not self.columns[index].shown
+
+    def move(self, index: int, offset: int) -> int:
+        """Move a column up or down the order.
+
+        Args:
+            index: Position of the column.
+            offset: Positions to move it by; negative moves it up.
+
+        Returns:
+            int: Its new position, clamped to the list.
+        """
+        target = max(0, min(len(self.columns) - 1, index + offset))
+        self.columns.insert(target, self.columns.pop(index))
+        return target
+
+    def cycle_width(self, index: int) -> None:
+        """Switch a column to the next of ``WIDTH_STEPS``."""
+        choice = self.columns[index]
+        choice.width = _next(WIDTH_STEPS, choice.width)
+
+    def cycle_unit(self, index: int) -> bool:
+        """Switch a memory column to the next of ``UNIT_STEPS``.
+
+        Returns:
+            bool: False, leaving it alone, if the column is not about memory.
+        """
+        choice = self.columns[index]
+        if not choice.takes_unit:
+            return False
+        choice.unit = _next(UNIT_STEPS, choice.unit)
+        return True
+
+    def cycle_format(self) -> None:
+        """Switch to the next of ``DESIGN_FORMATS``."""
+        self.fmt = _next(DESIGN_FORMATS, self.fmt)
+
+    def arguments(self) -> list[str]:
+        """Spell the design as ``procclean list`` options.
+
+        Returns:
+            list[str]: ``--columns`` unless no column is shown (the defaults),
+            and ``--format`` unless it is a table.
+        """
+        entries = self.entries
+        arguments = ["--columns", ",".join(entries)] if entries else []
+        if self.fmt != "table":
+            arguments += ["--format", self.fmt]
+        return arguments
+
+    def command(self) -> str:
+        """Spell the design as a shell command.
+
+        Returns:
+            str: E.g. "procclean list --columns pid,name,cwd:40 --format md".
+        """
+        return shlex.join(["procclean", "list", *self.arguments()])
+
+    def render(self, procs: list[ProcessInfo]) -> str:
+        """Format processes as the design would.
+
+        Args:
+            procs: Sample processes.
+
+        Returns:
+            str: The output of ``procclean list`` with these options.
+        """
+        return format_output(procs, self.fmt, self.entries or None)
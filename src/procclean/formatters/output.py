@@ -4,52 +4,92 @@ import csv
 import io
 import json
 from collections.abc import Sequence
-from dataclasses import asdict, fields
+from dataclasses import fields
 
 from tabulate import tabulate
 
-from procclean.core import ProcessInfo
-
-from .columns import COLUMNS, DEFAULT_COLUMNS
+from procclean.core import ProcessInfo, SystemInfo, redact_process, timed
+
+from .colors import ansi, cell_styles
+from .columns import DEFAULT_COLUMNS, fit_columns
+
+
+# Serialized only when set
+_OPTIONAL_FIELDS = (
+    "env",
+    "package",
+    "exe_sha256",
+    "repo",
+    "branch",
+    "runtime_env",
+    "pod",
+    "namespace",
+    "container",
+    "vm",
+    "hugetlb_mb",
+    "media",
+    "inhibitor",
+    "deleted_libs",
+    "ports",
+    "pss_mb",
+    "uss_mb",
+    "shared_mb",
+    "swap_mb",
+)
 
 
 def get_rows(
     procs: list[ProcessInfo],
     columns: Sequence[str] | None = None,
+    width: int | None = None,
+    color: bool = False,
 ) -> tuple[list[str], list[list[str]]]:
     """Extract headers and formatted rows from processes.
 
     Args:
         procs: Processes to extract rows from.
         columns: Optional ordered list of column keys to include.
+        width: Optional available width; narrow widths drop low-priority
+            columns and shrink cwd.
+        color: Wrap cells in the ANSI escapes of the ``COLOR_RULES`` matching
+            their process.
 
     Returns:
         A tuple of (headers, rows), where headers is a list of column headers and
         rows is a list of formatted string rows.
     """
-    cols = columns or DEFAULT_COLUMNS
-    specs = [COLUMNS[c] for c in cols if c in COLUMNS]
+    specs = fit_columns(columns or DEFAULT_COLUMNS, width)
     headers = [s.header for s in specs]
-    rows = [[s.extract(p) for s in specs] for p in procs]
+    if not color:
+        return headers, [[s.extract(p) for s in specs] for p in procs]
+    keys = [s.key for s in specs]
+    rows = []
+    for p in procs:
+        styles = cell_styles(p, keys)
+        rows.append([ansi(s.extract(p), styles[s.key]) for s in specs])
     return headers, rows
 
 
 def format_table(
     procs: list[ProcessInfo],
     columns: Sequence[str] | None = None,
+    width: int | None = None,
+    color: bool = False,
 ) -> str:
     """Format processes as ASCII table.
 
     Args:
         procs: Processes to format.
         columns: Optional ordered list of column keys to include.
+        width: Optional terminal width used to pick a responsive layout.
+        color: Color cells by value (see ``COLOR_RULES``).
 
     Returns:
         A formatted ASCII table string, or a message if no processes are found.
     """
     if not procs:
         return "No processes found."
-    headers, rows = get_rows(procs, columns)
+    headers, rows = get_rows(procs, columns, width, color)
     return tabulate(rows, headers=headers, tablefmt="simple_outline")
 
 
@@ -75,7 +115,10 @@ def format_markdown(
 def _serialize_process(p: ProcessInfo) -> dict:
     """Convert a process to a JSON-serializable dictionary.
 
-    Float values are rounded to 2 decimal places for stable output.
+    Float values are rounded to 2 decimal places for stable output, and
+    Optional annotations (``env``, ``package``, ``exe_sha256``, ``repo``,
+    ``branch``, ``runtime_env``, the pod and the VM fields) are only present
+    when captured.
 
     Args:
         p: Process to serialize.
@@ -83,22 +126,118 @@ def _serialize_process(p: ProcessInfo) -> dict:
     Returns:
         A JSON-serializable dict representation of the process.
     """
-    data = asdict(p)
+    data = p.to_dict()
     data["rss_mb"] = round(data["rss_mb"], 2)
     data["cpu_percent"] = round(data["cpu_percent"], 2)
+    for key in _OPTIONAL_FIELDS:
+        if data[key] is None:
+            del data[key]
     return data
 
 
-def format_json(procs: list[ProcessInfo]) -> str:
+def format_json(procs: list[ProcessInfo], header: SystemInfo | None = None) -> str:
     """Format processes as JSON.
 
     Args:
         procs: Processes to format.
+        header: System information to include (``--header``).
+
+    Returns:
+        A pretty-printed JSON string representing the processes; with a
+        header, a ``{"header", "processes"}`` object instead of a list.
+    """
+    processes = [_serialize_process(p) for p in procs]
+    if header is None:
+        return json.dumps(processes, indent=2)
+    return json.dumps({"header": header.to_dict(), "processes": processes}, indent=2)
+
+
+def format_header(info: SystemInfo, fmt: str) -> str:
+    """Format the system information printed above a report (``--header``).
+
+    JSON reports embed ``info.to_dict()`` instead.
+
+    Args:
+        info: Machine the report comes from.
+        fmt: Format of the report below it.
 
     Returns:
-        A pretty-printed JSON string representing the processes.
+        Aligned "Host: ..." lines for tables, a bullet list for Markdown, and
+        lines starting with "# " for CSV, which CSV readers can skip as
+        comments.
     """
-    return json.dumps([_serialize_process(p) for p in procs], indent=2)
+    rows = [
+        ("Host", info.hostname),
+        ("Kernel", info.kernel),
+        ("Uptime", info.uptime),
+        ("Load", " ".join(f"{load:.2f}" for load in info.load_avg)),
+        ("Time", info.timestamp),
+    ]
+    match fmt:
+        case "csv":
+            return "\n".join(f"# {key}: {value}" for key, value in rows)
+        case "md" | "markdown":
+            return "\n".join(f"- **{key}:** {value}" for key, value in rows)
+        case _:
+            return "\n".join(f"{key + ':':<8}{value}" for key, value in rows)
+
+
+def format_json_line(
+    procs: list[ProcessInfo],
+    timestamp: str,
+    redact: bool = True,
+    header: SystemInfo | None = None,
+) -> str:
+    """Format a timestamped snapshot as a single JSON Lines record.
+
+    Args:
+        procs: Processes in the snapshot.
+        timestamp: ISO 8601 time the snapshot was taken.
+        redact: Mask secrets in command lines (see ``redact_process``).
+        header: System information to include (``--header``).
+
+    Returns:
+        A compact ``{"timestamp", "processes"}`` object without newlines,
+        plus ``"header"`` if given.
+    """
+    if redact:
+        procs = [redact_process(p) for p in procs]
+    record: dict[str, object] = {"timestamp": timestamp}
+    if header is not None:
+        record["header"] = header.to_dict()
+    record["processes"] = [_serialize_process(p) for p in procs]
+    return json.dumps(record)
+
+
+def format_jsonl(
+    procs: list[ProcessInfo],
+    timestamp: str | None = None,
+    redact: bool = True,
+    header: SystemInfo | None = None,
+) -> str:
+    """Format processes as JSON Lines, one compact record per process.
+
+    Unlike ``format_json_line``, which packs a whole snapshot into one line,
+    every process is its own record, for ``jq -c`` filters and log shippers.
+
+    Args:
+        procs: Processes to format.
+        timestamp: ISO 8601 time of the snapshot, added to every record.
+        redact: Mask secrets in command lines (see ``redact_process``).
+        header: System information, written as a ``{"header"}`` record first.
+
+    Returns:
+        The records separated by newlines, without a trailing one; empty
+        without processes or header.
+    """
+    if redact:
+        procs = [redact_process(p) for p in procs]
+    lines = []
+    if header is not None:
+        lines.append(json.dumps({"header": header.to_dict()}))
+    stamp = {} if timestamp is None else {"timestamp": timestamp}
+    lines.extend(json.dumps(stamp | _serialize_process(p)) for p in procs)
+    return "\n".join(lines)
 
 
 def format_csv(procs: list[ProcessInfo]) -> str:
@@ -126,6 +265,8 @@ def format_csv(procs: list[ProcessInfo]) -> str:
             val = getattr(p, name)
             if isinstance(val, float):
                 val = f"{val:.2f}"
+            elif isinstance(val, list | dict):
+                val = json.dumps(val)  # argv, env
             row.append(val)
         writer.writerow(row)
 
@@ -136,23 +277,41 @@ def format_output(
     procs: list[ProcessInfo],
     fmt: str,
     columns: Sequence[str] | None = None,
+    width: int | None = None,
+    redact: bool = True,
+    color: bool = False,
+    header: SystemInfo | None = None,
 ) -> str:
     """Format processes in the requested format.
 
     Args:
         procs: Processes to format.
-        fmt: Output format key (e.g., "json", "csv", "md"/"markdown").
+        fmt: Output format key (e.g., "json", "jsonl", "csv", "md"/"markdown").
         columns: Optional ordered list of column keys to include (table/markdown).
+        width: Optional terminal width for the responsive table layout.
+        redact: Mask secrets in command lines (see ``redact_process``).
+        color: Color the cells of a table by value; other formats stay plain.
+        header: System information to put above the report (``--header``).
 
     Returns:
         The formatted output string.
     """
-    match fmt:
-        case "json":
-            return format_json(procs)
-        case "csv":
-            return format_csv(procs)
-        case "md" | "markdown":
-            return format_markdown(procs, columns)
-        case _:
-            return format_table(procs, columns)
+    with timed("format"):
+        if redact:
+            procs = [redact_process(p) for p in procs]
+        match fmt:
+            case "json":
+                return format_json(procs, header)
+            case "jsonl":
+                return format_jsonl(procs, redact=False, header=header)
+            case "csv":
+                report = format_csv(procs)
+            case "md" | "markdown":
+                report = format_markdown(procs, columns)
+            case _:
+                report = format_table(procs, columns, width, color)
+        if header is None:
+            return report
+        # CSV readers skip the comment lines, but not a blank line
+        separator = "\n" if fmt == "csv" else "\n\n"
+        return f"{format_header(header, fmt)}{separator}{report}"
@@ -0,0 +1,95 @@
+"""Privileged helper of ``procclean --polkit``, run as root through pkexec.
+
+Reads one JSON request from stdin, ``{"pid": ..., "signal": "SIGTERM",
+"create_time": ...}``, applies the policy of ``core.polkit`` to the live
+process, signals it and writes ``{"ok": ..., "code": ..., "message": ...}``
+to stdout. Signals sent are written to root's audit log with the origin
+"polkit" and the UID that asked for them.
+"""
+
+import json
+import os
+import signal
+import sys
+from typing import Any
+
+from procclean.core import (
+    ALL_USERS,
+    ErrorCode,
+    ProccleanError,
+    ProcessSource,
+    audit_origin,
+    get_process_list,
+    helper_refusal,
+    is_kill_signal,
+    log_kills,
+    signal_process,
+)
+from procclean.i18n import t
+
+
+def _failure(error: ProccleanError) -> dict[str, Any]:
+    return {"ok": False, "code": error.code.value, "message": error.message}
+
+
+def handle_request(
+    request: dict[str, Any], caller: str, source: ProcessSource | None = None
+) -> dict[str, Any]:
+    """Check and carry out one request.
+
+    Args:
+        request: PID, signal name and start time, as sent by the client.
+        caller: UID that ran pkexec, for the audit log.
+        source: Process backend; defaults to the live system.
+
+    Returns:
+        dict[str, Any]: Whether the signal was sent, with an ``ErrorCode``
+        value and a message.
+    """
+    try:
+        pid = int(request["pid"])
+        sig = signal.Signals[request["signal"]]
+        create_time = float(request["create_time"])
+    except (KeyError, TypeError, ValueError):
+        msg = "Malformed request: expected pid, signal and create_time"
+        return _failure(ProccleanError(ErrorCode.INVALID_ARGUMENT, msg))
+    procs = get_process_list(
+        filter_user=ALL_USERS, min_memory_mb=0, source=source, kernel_threads=True
+    )
+    proc = next((p for p in procs if p.pid == pid), None)
+    if refusal := helper_refusal(proc, sig, create_time, source):
+        return _failure(refusal)
+    try:
+        signal_process(pid, source=source, sig=sig)
+    except ProccleanError as e:
+        ok, code, msg = False, e.code.value, e.message
+    else:
+        key = "kill-terminated" if is_kill_signal(sig) else "kill-signaled"
+        ok, code, msg = True, "", t(key, pid=pid, signal=sig.name)
+    with audit_origin("polkit", f"uid={caller}"):
+        log_kills([(pid, sig.name, ok, msg)], {pid: proc} if proc else {}, source)
+    return {"ok": ok, "code": code, "message": msg}
+
+
+def main() -> None:
+    """Serve one request from stdin.
+
+    Raises:
+        SystemExit: With status 1 when not run as root.
+    """
+    if os.geteuid() != 0:
+        sys.stderr.write("procclean-helper: run it through pkexec\n")
+        raise SystemExit(1)
+    caller = os.environ.get("PKEXEC_UID", "?")
+    try:
+        request = json.loads(sys.stdin.read())
+    except ValueError:
+        request = {}
+    if not isinstance(request, dict):
+        request = {}
+    json.dump(handle_request(request, caller), sys.stdout)
+    sys.stdout.write("\n")
+
+
+if __name__ == "__main__":
+    main()
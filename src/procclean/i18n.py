@@ -0,0 +1,161 @@
+"""Localization of user-facing strings.
+
+Catalogs live in ``locales/<locale>.ftl`` and use the simple-message subset
+of Project Fluent: ``message-id = text`` with ``{ $variable }`` placeables,
+indented continuation lines and ``#`` comments. Missing messages fall back to
+the English catalog, then to the message id itself.
+"""
+
+import os
+import re
+from collections.abc import Mapping
+from functools import cache
+from pathlib import Path
+
+LOCALES_DIR = Path(__file__).parent / "locales"
+DEFAULT_LOCALE = "en"
+
+# Checked in order; the first non-empty value wins
+LOCALE_ENV_VARS = ("PROCCLEAN_LANG", "LC_ALL", "LC_MESSAGES", "LANG")
+
+_MESSAGE_RE = re.compile(r"^([a-zA-Z][\w-]*)\s*=\s?(.*)$")
+_PLACEABLE_RE = re.compile(r"\{\s*(?:\$([a-zA-Z][\w-]*)|\"([^\"]*)\")\s*\}")
+
+
+def parse_ftl(text: str) -> dict[str, str]:
+    """Parse simple Fluent messages.
+
+    Args:
+        text: Contents of an ``.ftl`` file.
+
+    Returns:
+        dict[str, str]: Message patterns keyed by message id.
+    """
+    messages: dict[str, str] = {}
+    current: str | None = None
+    for line in text.splitlines():
+        if line.startswith("#") or not line.strip():
+            current = None
+            continue
+        if line[0].isspace():
+            # Continuation of a multiline message; attributes are not supported
+            if current is not None and not line.lstrip().startswith("."):
+                sep = "\n" if messages[current] else ""
+                messages[current] += sep + line.strip()
+            continue
+        if match := _MESSAGE_RE.match(line):
+            current = match.group(1)
+            messages[current] = match.group(2).strip()
+    return messages
+
+
+def available_locales() -> list[str]:
+    """List locales that ship a catalog.
+
+    Returns:
+        list[str]: Locale names, e.g. ``["en"]``.
+    """
+    return sorted(p.stem for p in LOCALES_DIR.glob("*.ftl"))
+
+
+def detect_locale(environ: Mapping[str, str] | None = None) -> str:
+    """Pick the best available locale from the environment.
+
+    ``de_DE.UTF-8`` tries ``de-DE`` then ``de``; ``C``/``POSIX`` and unknown
+    languages use English.
+
+    Args:
+        environ: Environment to read; defaults to ``os.environ``.
+
+    Returns:
+        str: An available locale name.
+    """
+    env = os.environ if environ is None else environ
+    value = next((env[v] for v in LOCALE_ENV_VARS if env.get(v)), "")
+    tag = value.split(".")[0].split("@")[0].replace("_", "-")
+    available = set(available_locales())
+    for candidate in (tag, tag.split("-")[0]):
+        if candidate in available:
+            return candidate
+    return DEFAULT_LOCALE
+
+
+class Catalog:
+    """Messages for one locale, with fallback to another catalog."""
+
+    def __init__(
+        self, locale: str, messages: dict[str, str], fallback: "Catalog | None" = None
+    ) -> None:
+        """Initialize the catalog.
+
+        Args:
+            locale: Locale name.
+            messages: Message patterns keyed by id.
+            fallback: Catalog consulted for missing ids.
+        """
+        self.locale = locale
+        self.messages = messages
+        self.fallback = fallback
+
+    def pattern(self, key: str) -> str | None:
+        """Look up a raw message pattern.
+
+        Returns:
+            str | None: The pattern, or None if no catalog defines ``key``.
+        """
+        if key in self.messages:
+            return self.messages[key]
+        return self.fallback.pattern(key) if self.fallback else None
+
+    def format(self, key: str, **args: object) -> str:
+        """Format a message, substituting ``{ $name }`` placeables.
+
+        Args:
+            key: Message id.
+            **args: Values for the message variables.
+
+        Returns:
+            str: The formatted message, or ``key`` if it is not defined.
+        """
+        pattern = self.pattern(key)
+        if pattern is None:
+            return key
+
+        def substitute(match: re.Match[str]) -> str:
+            name, literal = match.groups()
+            if literal is not None:
+                return literal
+            return str(args[name]) if name in args else f"{{${name}}}"
+
+        return _PLACEABLE_RE.sub(substitute, pattern)
+
+
+@cache
+def get_catalog(locale: str | None = None) -> Catalog:
+    """Load the catalog for a locale (detected from the environment if None).
+
+    Returns:
+        Catalog: The catalog, falling back to English for missing messages.
+    """
+    locale = locale or detect_locale()
+    path = LOCALES_DIR / f"{DEFAULT_LOCALE}.ftl"
+    english = Catalog(DEFAULT_LOCALE, parse_ftl(path.read_text(encoding="utf-8")))
+    if locale == DEFAULT_LOCALE:
+        return english
+    path = LOCALES_DIR / f"{locale}.ftl"
+    if not path.is_file():
+        return english
+    return Catalog(locale, parse_ftl(path.read_text(encoding="utf-8")), english)
+
+
+def t(key: str, **args: object) -> str:
+    """Translate a message for the current locale.
+
+    Args:
+        key: Message id from the catalog.
+        **args: Values for the message variables.
+
+    Returns:
+        str: The localized message.
+    """
+    return get_catalog().format(key, **args)
@@ -0,0 +1,501 @@
+# English messages for procclean.
+# Copy this file to <locale>.ftl (e.g. de.ftl, pt-BR.ftl) to add a translation;
+# missing messages fall back to English.
+
+## Kill actions
+
+kill-terminated = Process { $pid } terminated
+kill-signaled = Sent { $signal } to process { $pid }
+kill-not-found = Process { $pid } not found
+kill-access-denied = Access denied for process { $pid }
+kill-error = Error: { $error }
+kill-group-signaled = Sent { $signal } to process { $pid } with process group { $pgid }
+kill-group-not-found = Process group { $pgid } not found
+kill-group-access-denied = Access denied for process group { $pgid }
+kill-group-vetoed = Process group { $pgid } spared: { $reason } (process { $pid })
+kill-reniced = Process { $pid } reniced to { $nice }
+kill-ioniced = Process { $pid } I/O priority set to { $priority }
+kill-kernel-thread = Process { $pid } is a kernel thread; not killed
+kill-vetoed = Kill of process { $pid } vetoed by { $hook }
+kill-hook-failed = { $message } ({ $errors })
+kill-hook-aborted = Kill of process { $pid } aborted: { $error }
+kill-hook-command-failed = { $hook } command failed: { $error }
+kill-hook-exit = exit status { $code }
+kill-hook-timeout = timed out after { $seconds }s
+kill-waiting = Sent { $signal } to process { $pid }, waiting for it to exit
+kill-exited = Process { $pid } exited after { $seconds }s
+kill-escalated = Process { $pid } ignored SIGTERM, killed with SIGKILL after { $seconds }s
+kill-alive = Process { $pid } still running { $seconds }s after SIGKILL
+kill-outcome-pending = waiting
+kill-outcome-terminated = exited
+kill-outcome-escalated = killed
+kill-outcome-alive = still running
+kill-outcome-failed = failed
+
+## User scripts
+
+script-failed = Script { $hook } failed: { $error }
+script-load-failed = Cannot run script { $path }: { $error }
+script-filter = Defined in { $file }
+preset-config = Defined in { $path }
+
+## OOM kills
+
+oom-last = Kernel OOM-killed { $name } (PID { $pid }) { $ago } ago
+oom-count = Kernel OOM-killed { $count } process(es) since boot
+
+## CLI
+
+cli-no-groups = No process groups found.
+cli-group-header = { $cmd } ({ $count } processes, { $mb } MB total)
+cli-group-item = PID { $pid }: { $mb } MB
+cli-group-duplicate = (duplicate)
+cli-no-duplicates = No duplicate processes found.
+cli-duplicate-keep = Keeping { $name } (PID { $pid }), killing { $count } duplicate(s)
+cli-pid-not-found = Warning: PID { $pid } not found
+cli-mem-detail-unreadable = Only RSS is readable; PSS, USS and swap need access to /proc/<pid>/smaps_rollup (your own processes, or root).
+cli-group-own = Skipping process group { $pgid }: procclean runs in it
+cli-kernel-thread-skipped = Skipping kernel thread { $name } (PID { $pid }); kernel threads are never killed
+cli-vm-skipped = Skipping VM { $vm } ({ $name }, PID { $pid }); pass --include-vms to kill it
+cli-memory-vms = { $count } VM(s) using { $gb } GB, { $huge_gb } GB of it hugepages
+cli-cpu-normal = No core is pinned
+cli-cpu-pinned = Core(s) { $cores } pinned while the average is { $average }%: likely a runaway single-threaded process
+cli-cpu-busy = All cores average { $average }%: the system is busy, not one process
+cli-no-match = No processes match the filters.
+cli-truncated = Showing { $shown } of { $total } processes; pass --no-cap to list all
+cli-preview-footer = { $count } process(es) would be killed.
+cli-confirm-header = About to { $action } { $count } process(es):
+cli-confirm-item = { $pid }: { $name } ({ $mb } MB)
+cli-confirm-more = ... and { $count } more
+cli-confirm-prompt = Continue? [y/N]
+cli-data-loss-header = { $count } target(s) may lose data if killed mid-write:
+cli-data-loss-prompt = Type "{ $name }" to kill anyway:
+cli-confirm-skipped = { $pid }: { $name } matches no_confirm pattern "{ $pattern }", not asking
+cli-action-terminate = terminate
+cli-action-force = FORCE KILL
+cli-action-signal = send { $signal } to
+cli-aborted = Aborted.
+cli-result-ok = OK
+cli-result-failed = FAILED
+cli-result-spared = SPARED: risk of data loss
+cli-simulated = Simulation: no signals were sent.
+cli-reclaim = Memory freed: { $actual } MB measured vs { $estimated } MB estimated from RSS ({ $percent }%)
+cli-reclaim-estimated = Memory freed: about { $estimated } MB estimated from RSS (not measured)
+
+## Shell jobs
+
+cli-no-jobs = No jobs in this shell session.
+cli-job-item = { $pid }  { $status }  { $mb } MB  { $cmd }
+cli-job-not-found = Warning: job %{ $job } not found
+
+## History
+
+cli-history-empty = No actions recorded in { $path }.
+cli-history-off = The audit log is turned off.
+cli-history-item = { $time }  [{ $status }]  { $signal }  { $pid } { $name } ({ $mb } MB)
+cli-history-origin = from { $origin }: { $filter }
+cli-history-simulated = (simulated)
+
+## Capabilities
+
+capability-platform = { $platform } has no /proc, so Linux-only details are unavailable
+capability-platform-fix = Run procclean on Linux for every column
+capability-procfs-ok = { $path } is mounted
+capability-procfs = { $path } is not mounted, so no process can be listed
+capability-procfs-fix = Mount it: mount -t proc proc { $path }
+capability-hidepid-ok = Every user's processes are visible
+capability-hidepid-invisible = /proc is mounted with hidepid={ $mode }, so other users' processes are hidden
+capability-hidepid-noaccess = /proc is mounted with hidepid={ $mode }, so other users' process details are unreadable
+capability-hidepid-fix = Run as root, or as a member of the group given by the gid= mount option
+capability-subset-ok = System-wide /proc files are visible
+capability-subset = /proc is mounted with subset=pid, so memory, load and socket files are hidden
+capability-subset-fix = Mount /proc without subset=pid, e.g. run outside the sandbox
+capability-others-ok = Other users' process details are readable (root or CAP_SYS_PTRACE)
+capability-others = Other users' working directories, mappings and open files are unreadable without root
+capability-others-fix = Run with sudo to include other users' details
+capability-smaps-ok = Per-process memory breakdown (smaps_rollup) is available
+capability-smaps = /proc/<pid>/smaps_rollup is missing (kernel older than 4.14), so PSS, USS, shared and swap are unknown
+capability-smaps-fix = Upgrade to Linux 4.14 or later
+capability-meminfo-ok = /proc/meminfo is readable
+capability-meminfo = /proc/meminfo is unreadable or masked, so the memory summary is unavailable
+capability-meminfo-fix = Unmask /proc/meminfo in the container runtime, or run on the host
+capability-pressure-ok = Memory pressure (PSI) is available
+capability-pressure = /proc/pressure/memory is unavailable, so pressure-based quota and emergency triggers never fire
+capability-pressure-fix = Use Linux 4.20 or later, booted with psi=1 if the kernel disables it by default
+capability-net-ok = Socket tables (/proc/net) are readable
+capability-net = /proc/net is unreadable or masked, so listening ports are unknown
+capability-net-fix = Unmask /proc/net in the container runtime, or run on the host
+capability-cgroup-ok = Processes live in the unified cgroup v2 hierarchy
+capability-cgroup = cgroup v2 is not in use, so --scope and container detection rely on the systemd v1 hierarchy
+capability-cgroup-fix = Boot with systemd.unified_cgroup_hierarchy=1
+capability-terminal-ok = Output is an interactive terminal (TERM={ $term })
+capability-terminal = Output is not an interactive terminal (TERM={ $term }), so the TUI cannot start and colors are off
+capability-terminal-fix = Run procclean from a terminal emulator with TERM set, e.g. xterm-256color
+capability-encoding-ok = Output encoding is { $encoding }
+capability-encoding = Output encoding is { $encoding }, so tree lines and chart glyphs may not render
+capability-encoding-fix = Use a UTF-8 locale, e.g. LANG=C.UTF-8
+capability-config-ok = { $path } is valid
+capability-config-none = No config file at { $path }; defaults apply
+capability-config-more = and { $count } more
+capability-config-fix = Run `procclean config edit`, or move { $path } aside to use the defaults
+capability-columns = columns affected: { $columns }
+capability-warning = Warning: { $reasons }. Run `procclean doctor` for details.
+cli-doctor-header = { $platform }, running as { $user } (UID { $uid })
+cli-doctor-ok = OK
+cli-doctor-missing = MISSING
+cli-doctor-columns = Columns affected: { $columns }
+cli-doctor-fix = Fix: { $fix }
+cli-doctor-environment = Environment:
+cli-doctor-version = procclean { $version }, Python { $python }
+
+## Config
+
+cli-config-file = Config file: { $path }
+cli-config-no-file = No config file at { $path }; showing built-in defaults
+cli-config-default = default
+cli-config-valid = { $path } is valid.
+cli-config-none = No config file at { $path }; defaults apply.
+cli-config-problems = { $path } has { $count } problem(s):
+cli-config-edit-again = Edit again? [Y/n]
+cli-config-editor-failed = The editor exited with status { $code }; { $path } is unchanged.
+cli-config-unchanged = No changes.
+cli-config-saved = Saved { $path }.
+cli-config-discarded = Changes discarded; { $path } is unchanged.
+cli-doctor-fine = Everything procclean reads is available.
+
+## Columns
+
+cli-columns-key = Key
+cli-columns-header = Header
+cli-columns-type = Type
+cli-columns-slow = Slow
+cli-columns-description = Description
+cli-columns-yes = yes
+cli-columns-note = * shown by default. Append :WIDTH to clip a column (cwd:40), and :kb or :gb to show a memory column in another unit (rss_mb:gb). Slow columns wait on a CPU interval, read every process's memory maps or sockets, or run external tools; leave them out of --columns to keep `list` fast.
+
+## Fmt
+
+cli-fmt-cancelled = Cancelled; nothing printed or saved.
+
+## Scheduling priority
+
+priority-realtime = realtime ({ $policy }, priority { $prio }); kill with care
+priority-nice = raised priority (nice { $nice }); kill with care
+
+## Package attribution
+
+cli-unpackaged = Warning: PID { $pid } ({ $name }) runs an unpackaged binary (sha256 { $digest })
+cli-no-package-manager = Warning: --attribute needs dpkg-query or rpm; packages were not resolved
+
+## Supervisors
+
+supervisor-respawn = will likely respawn via { $kind } ({ $name })
+supervisor-shell = started from { $shell }; will not respawn
+supervisor-stopped = Stopped via `{ $command }`
+supervisor-stop-failed = `{ $command }` failed: { $error }
+supervisor-restarted = Restarted via `{ $command }`
+supervisor-no-restart = Process { $pid } has no manager that can restart it; left running
+cli-confirm-prompt-manager = Continue? [y/N, m = stop via manager]
+
+## Restart
+
+restart-plan-manager = via `{ $command }`
+restart-plan-kill = kill; { $kind } starts it again
+restart-plan-respawn = kill, then run `{ $command }` in { $cwd }
+restart-refused = Cannot respawn PID { $pid } ({ $name }): { $reason }
+restart-reason-owner = owned by { $user }, not you
+restart-reason-cmdline = its command line is unreadable
+restart-reason-cwd = its working directory is unreadable or deleted
+restart-reason-env = its environment is unreadable
+restart-reason-gone = it is no longer running
+restart-respawned = Restarted PID { $pid } as PID { $new_pid }
+restart-by-manager = Killed PID { $pid }; { $kind } will start it again
+restart-spawn-failed = Killed PID { $pid }, but `{ $command }` failed to start: { $error }
+cli-restart-header = Restarting { $count } process(es):
+cli-restart-item = { $pid } { $name }: { $plan }
+cli-restart-prompt = Restart? [y/N]
+
+## Leftover sockets and lock files
+
+daemon-rescan-failed = Rescan failed, still serving the previous snapshot: { $error }
+leftover-socket-removed = socket file was removed
+leftover-no-listener = no process is listening
+leftover-owner-gone = owner PID { $pid } is not running
+leftover-not-on-disk = { $path }: not on disk
+leftover-already-gone = { $path }: already gone
+leftover-remove-error = { $path }: { $error }
+leftover-removed = Removed { $path }
+cli-no-leftovers = No stale sockets or lock files found.
+cli-leftovers-footer = { $count } leftover(s); run with --fix to clean up.
+cli-leftovers-confirm = Kill { $holders } holder(s) and remove { $files } file(s)? [y/N]
+cli-leftovers-holder-skipped = Not killing PID { $pid }: owned by another user, or a protected process
+cli-leftovers-simulated = Simulation: no signals were sent and no files were removed.
+
+## Clean
+
+cli-clean-nothing = Nothing to clean up.
+cli-clean-category = { $category } ({ $count } processes, { $mb } MB)
+cli-clean-total = Total: { $count } process(es), { $mb } MB
+cli-clean-footer = Run with --auto to clean up.
+cli-clean-confirm = Kill { $count } process(es) to free about { $mb } MB? [y/N]
+
+## Run
+
+cli-run-survivors = { $count } process(es) outlived { $command }:
+cli-run-survivor = { $pid } { $name } ({ $mb } MB)
+cli-run-kill-hint = Use --kill to terminate them.
+
+## Wine
+
+cli-no-wine = No Wine or Proton processes running.
+cli-wine-group = { $app } in { $prefix } ({ $count } processes, { $mb } MB)
+cli-wine-hint = Use --shutdown NAME to end a whole prefix with wineserver -k.
+cli-wine-confirm = Shut down { $app } and everything else in { $prefix }? [y/N]
+
+## Plugins
+
+cli-no-plugins = No plugins found; name an executable procclean-NAME and put it on PATH to add the command NAME.
+
+## Daemon
+
+cli-daemon-listening = Scanning every { $interval }s, serving snapshots on { $path } (Ctrl-C stops)
+cli-export-listening = Serving metrics on { $url } (Ctrl-C stops)
+
+## Verify
+
+cli-verify-missing = - missing: { $rule } (found { $found }, need { $min })
+cli-verify-present = + forbidden: { $rule } (PID { $pids })
+cli-verify-ok = OK: all { $count } manifest rule(s) satisfied
+cli-verify-failed = FAILED: { $count } of { $total } manifest rule(s) violated
+
+## Memory budgets
+
+cli-no-budgets = No memory budgets configured; add [[budgets]] tables to the config file.
+cli-budget-usage = { $path }: { $used } of { $limit } MB ({ $count } processes)
+cli-budget-over = { $path }: { $used } of { $limit } MB ({ $count } processes), over budget
+cli-offender = offender { $pid }: { $name } ({ $mb } MB)
+cli-budget-enforce-hint = Run with --enforce to kill offenders of budgets with action = "kill".
+
+## Emergency cleanup
+
+cli-no-emergency = Emergency mode is not configured; add an [emergency] table to the config file.
+cli-emergency-armed = Emergency mode armed: { $thresholds }; { $count } approved pattern(s)
+cli-emergency-trigger = EMERGENCY: { $reason }
+cli-emergency-no-targets = No pre-approved process to kill
+cli-emergency-kill = Kill { $pid } ({ $name }, { $mb } MB)
+cli-emergency-spared = Spare { $pid } ({ $name }): risk of data loss; pass --i-know-what-im-doing to kill it
+
+## Watch mode
+
+cli-watch-start = Watching every { $interval } for { $filter } processes
+cli-watch-start-dry = Watching every { $interval } for { $filter } processes (dry run, nothing is killed)
+cli-watch-kill = Kill { $pid } ({ $name }, { $mb } MB)
+cli-watch-would-kill = Would kill { $pid } ({ $name }, { $mb } MB)
+cli-watch-spared = Spare { $pid } ({ $name }): risk of data loss; pass --i-know-what-im-doing to kill it
+cli-watch-stop = Stopped watching
+
+## Per-user quotas
+
+cli-no-quotas = No quotas configured; add [[quotas.rules]] tables to the config file.
+cli-quota-usage = { $user }: { $count } processes, { $memory } MB
+cli-quota-over = { $user }: { $count } processes, { $memory } MB, over quota
+cli-quota-enforce-hint = Run with --enforce to renice or kill offenders of quotas with action = "renice" or "kill".
+
+## TUI
+
+tui-title = ProcClean
+tui-subtitle = Process Cleanup Tool
+tui-oom-new = The kernel OOM killer terminated { $count } process(es)
+tui-simulated = [simulated]
+tui-scope = [scope: { $scope }]
+tui-recording = [recording: { $path }]
+tui-replay = [replay]
+tui-views = Views
+tui-view-all = All Processes
+tui-view-orphans = Orphaned
+tui-view-killable = Killable
+tui-view-groups = Process Groups
+tui-view-duplicates = Duplicates
+tui-view-high-mem = High Memory (>{ $threshold_mb }MB)
+tui-view-cpu-hogs = CPU Hogs (>{ $threshold_percent }%)
+tui-mem-total = Total: { $gb }G
+tui-mem-used = Used: { $gb }G ({ $percent }%)
+tui-mem-free = Free: { $gb }G
+tui-mem-swap = Swap: { $used }G/{ $total }G
+tui-load = Load: { $load } ({ $cpus } CPUs)
+tui-uptime = Up: { $uptime }
+tui-cores = CPU { $strip } { $average }%
+tui-cores-on = Per-core CPU shown
+tui-cores-off = Per-core CPU hidden
+tui-selected = Selected: { $count } processes ({ $mb } MB)
+tui-truncated = [truncated: { $shown } of { $total } rows]
+tui-row-position = Row { $row } of { $total }
+tui-refreshed = Refreshed
+tui-sort = Sort: { $key } ({ $order })
+tui-layout = Layout: { $name }
+tui-sort-asc = asc
+tui-sort-desc = desc
+tui-filter-cwd = Filter: cwd={ $cwd }
+tui-filter-cwd-unknown = Cannot filter: unknown cwd
+tui-filter-cwd-cleared = CWD filter cleared
+tui-search = Search: "{ $query }"
+tui-search-cleared = Search cleared
+tui-tree-on = Tree view
+tui-tree-off = List view
+tui-subtree-selected = Selected { $count } process(es) under PID { $pid }
+tui-subtree-cleared = Deselected { $count } process(es) under PID { $pid }
+tui-none-selected = No processes selected
+tui-data-loss-refused = Not force killing { $names }: risk of data loss (use the CLI with --i-know-what-im-doing)
+tui-confirm-skipped = { $name } matches "{ $pattern }", killing without confirmation
+tui-killed = Killed { $success }/{ $total } processes
+tui-signaled = Sent { $signal } to { $success }/{ $total } processes
+tui-kill-progress = Waiting for exits ({ $seconds }s left): { $states }
+tui-kill-state = { $pid } { $state }
+tui-escalate-signals = SIGTERM, then SIGKILL
+tui-reclaim = Freed { $actual } MB (RSS estimate: { $estimated } MB, { $percent }%)
+tui-undo = Undid: { $action }
+tui-undo-empty = Nothing to undo
+tui-replay-kill = Replay: { $signal } PIDs { $pids } (not sent)
+tui-replay-queue = Replay: { $total } queued action(s) (not run)
+tui-replay-restart = Replay: restart PIDs { $pids } (not run)
+tui-restarted = Restarted { $success }/{ $total } processes
+tui-queued = Queued: { $count }
+tui-queue-added = Queued { $action } for { $count } process(es)
+tui-queue-empty = No actions queued
+tui-queue-pruned = Dropped { $count } queued action(s) for processes that exited
+tui-queue-done = Ran { $success }/{ $total } queued actions
+tui-replay-done = Replay finished
+tui-designer-format = Format: { $format }
+tui-designer-no-unit = Only memory columns (*_mb) come in other units
+tui-designer-empty = Show at least one column to save
+tui-designer-saved = Saved as [defaults] columns
+tui-panel-saved = Saved view { $name }
+
+## TUI key bindings (footer)
+
+key-quit = Quit
+key-refresh = Refresh
+key-kill = Kill
+key-force-kill = Force Kill
+key-orphans = Orphans
+key-killable = Killable
+key-all = All
+key-groups = Groups
+key-duplicates = Duplicates
+key-filter-cwd = Filter CWD
+key-clear-cwd = Clear CWD
+key-search = Search
+key-clear-search = Clear Search
+key-select = Select
+key-select-all = Select All
+key-clear = Clear
+key-undo = Undo
+key-queue-kill = Queue Kill
+key-queue-force-kill = Queue Force Kill
+key-queue-suspend = Queue Suspend
+key-queue-renice = Queue Renice
+key-review-queue = Review Queue
+key-restart = Restart
+key-sort-mem = Sort:Mem
+key-sort-cpu = Sort:CPU
+key-sort-pid = Sort:PID
+key-sort-name = Sort:Name
+key-sort-cwd = Sort:CWD
+key-sort-then = Then by
+key-reverse = Reverse
+key-layout = Layout
+key-tree = Tree
+key-cores = Cores
+key-fold = Fold
+key-subtree = Subtree
+key-yes = Yes
+key-no = No
+key-cancel = Cancel
+key-via-manager = Via Manager
+key-signal = Signal
+key-escalate = Escalate
+key-inspect = Inspect
+key-close = Close
+key-designer-toggle = Show/Hide
+key-designer-up = Move Up
+key-designer-down = Move Down
+key-designer-width = Width
+key-designer-unit = Unit
+key-designer-format = Format
+key-designer-save = Save
+key-designer-done = Done
+key-filter-panel = Filters
+key-panel-save = Save View
+
+## Confirm dialog
+
+confirm-title = { $action } { $count } process(es)?
+confirm-action-kill = Kill
+confirm-action-force = FORCE KILL
+confirm-action-signal = Send { $signal } to
+confirm-subtitle = Will free ~{ $mb } MB
+confirm-subtitle-signal = Processes keep running unless { $signal } ends them
+confirm-signal = Signal: { $signal } (s to change, e to escalate)
+confirm-signal-escalate = Signal: SIGTERM, then SIGKILL after { $timeout }s (e to turn off)
+confirm-item = { $pid }: { $name } ({ $mb } MB)
+confirm-data-loss = Risk of data loss: may be mid-write
+confirm-data-loss-again = Press y again to kill despite the risk of data loss
+confirm-more = ... and { $count } more
+confirm-yes = Yes (y)
+confirm-no = No (n)
+confirm-manager = Via manager (m)
+
+## Queue review dialog
+
+queue-title = Run { $count } queued action(s)?
+queue-subtitle = { $kills } kill(s), freeing ~{ $mb } MB
+queue-item = { $action } { $pid }: { $name } ({ $mb } MB)
+queue-action-kill = kill
+queue-action-force-kill = force kill
+queue-action-suspend = suspend
+queue-action-renice = renice
+queue-run = Run all (y)
+queue-back = Back (n)
+
+## Restart dialog
+
+restart-title = Restart { $count } process(es)?
+restart-item = { $pid }: { $name }
+restart-run = Restart (y)
+
+## Inspect screen
+
+detail-title = { $pid }: { $name }
+detail-process = Process
+detail-summary = User { $user }, parent { $ppid } ({ $parent }), status { $status }
+detail-started = Started { $time }
+detail-cwd = Working directory: { $cwd }
+detail-command = Command line
+detail-memory = Memory (MB)
+detail-children = Children
+detail-ports = Listening sockets
+detail-files = Open file descriptors
+detail-environment = Environment
+detail-none = (none or not readable)
+detail-more = ... and { $count } more
+detail-hint = Esc, q or Enter to close
+
+## Filter panel
+
+panel-title = Filter processes
+panel-view-name = View name (letters, digits, - and _)
+panel-min-memory = Minimum memory (MB)
+panel-name = Name (substring or glob, e.g. node*)
+panel-user = User (name or UID)
+panel-cwd = Working directory (path prefix or glob)
+panel-orphan = Orphans only
+panel-stale = Stale only
+panel-result = { $count } of { $total } process(es) match
+panel-invalid-memory = Minimum memory must be a number of MB
+panel-no-name = Name the view to save it
+panel-save = Save (Enter)
+panel-cancel = Cancel (Esc)
+
+## Search bar
+
+search-placeholder = Name, command line or cwd (Enter keeps, Esc clears)
@@ -1,6 +1,26 @@
 """TUI interface for procclean."""
 
 from .app import ProcessCleanerApp
-from .screens import ConfirmKillScreen
+from .designer import ListDesignerApp
+from .screens import (
+    ConfirmKillScreen,
+    ConfirmRestartScreen,
+    FilterPanelScreen,
+    ProcessDetailScreen,
+    ReviewQueueScreen,
+)
+from .snapshot import SNAPSHOT_SIZE, export_text, render_to_svg, render_to_text
 
-__all__ = ["ConfirmKillScreen", "ProcessCleanerApp"]
+__all__ = [
+    "SNAPSHOT_SIZE",
+    "ConfirmKillScreen",
+    "ConfirmRestartScreen",
+    "FilterPanelScreen",
+    "ListDesignerApp",
+    "ProcessCleanerApp",
+    "ProcessDetailScreen",
+    "ReviewQueueScreen",
+    "export_text",
+    "render_to_svg",
+    "render_to_text",
+]
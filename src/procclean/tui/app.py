@@ -1,17 +1,25 @@
 """Main TUI application."""
 
-from typing import ClassVar, Literal
-
-from textual import on, work
+import asyncio
+import os
+import signal
+from collections import deque
+from pathlib import Path
+from typing import Any, ClassVar, Literal
+
+from rich.text import Text
+from textual import events, on, work
+from textual.actions import ActionParseResult
 from textual.app import App, ComposeResult
 from textual.binding import Binding
 from textual.containers import Horizontal, Vertical
-from textual.coordinate import Coordinate
+from textual.dom import DOMNode
 from textual.reactive import reactive
 from textual.widgets import (
     DataTable,
     Footer,
     Header,
+    Input,
     Label,
     OptionList,
     Static,
@@ -21,21 +29,210 @@ from textual.widgets.option_list import Option
 
 from procclean.core import (
     CWD_MAX_WIDTH,
-    CWD_TRUNCATE_WIDTH,
-    HIGH_MEMORY_THRESHOLD_MB,
+    DEFAULT_SOURCE,
+    ESCALATE_TIMEOUT_SECONDS,
+    FILTERS,
+    MEMORY_SETTLE_SECONDS,
+    NO_REDACT_ENV,
+    POLKIT_AGENT_ONLY,
+    POLKIT_ENV,
+    RECORD_ENV,
+    REPLAY_ENV,
+    VIA_MANAGER,
+    ConfigPreset,
+    CoreUsage,
+    CpuState,
+    Defaults,
+    KillOutcome,
+    KillResult,
+    OomStatus,
+    OomTracker,
+    ProccleanError,
+    ProcessCollector,
     ProcessInfo,
+    ProcessSource,
+    ReclaimReport,
+    RestartMethod,
+    RestartPlan,
+    RestartResult,
+    ScopedSource,
+    SessionRecorder,
+    SimulatedSource,
+    TreeRow,
+    Trend,
+    TrendTracker,
+    attach_deleted_libs,
+    attach_inhibitors,
+    attach_media,
+    attach_pods,
+    attach_repos,
+    attach_runtime_envs,
+    attach_smaps,
+    attach_vms,
+    audit_origin,
+    cap_processes,
+    capability_warning,
+    core_usage,
+    daemon_process_list,
+    data_loss_targets,
+    describe_oom,
     filter_by_cwd,
+    filter_by_search,
+    find_duplicates,
     find_similar_processes,
+    format_uptime,
     get_memory_summary,
-    get_process_list,
+    get_process_details,
+    is_kill_signal,
+    kill_escalating,
+    kill_order,
     kill_processes,
+    load_config,
+    load_defaults,
+    load_kill_hooks,
+    load_session,
+    measure_reclaim,
+    no_confirm_match,
+    plan_restarts,
+    polkit_enabled,
+    redact_cmdline,
+    renice_process,
+    resolve_supervisors,
+    restart_processes,
+    row_cap,
+    scoped_source_from_env,
+    search_spans,
+    simulated_source_from_env,
+    snapshot_processes,
+    sort_processes,
+    stop_via_managers,
+    subtree_pids,
+    tree_rows,
+)
+from procclean.formatters import (
+    COLUMNS,
+    ClipSide,
+    cell_styles,
+    clip,
+    column_key,
+    default_columns,
+    get_breakpoint,
+    get_layouts,
+    highlight_rules,
+    lib_names,
+)
+from procclean.i18n import get_catalog, t
+
+from .chart import HISTORY_SIZE, create_memory_chart, graphics_enabled
+from .history import ActionHistory, UiState
+from .queue import QUEUE_MARKERS, QUEUE_NICE, ActionQueue, QueuedAction, QueuedKind
+from .screens import (
+    ConfirmKillScreen,
+    ConfirmRestartScreen,
+    FilterPanelScreen,
+    ProcessDetailScreen,
+    ReviewQueueScreen,
 )
-
-from .screens import ConfirmKillScreen
 
 # Type aliases
-ViewType = Literal["all", "orphans", "killable", "groups", "high-mem"]
+# "all", "groups", "duplicates" or the view of a registered filter preset
+ViewType = str
 SortKey = Literal["memory", "cpu", "pid", "name", "cwd"]
+SORT_ORDER: tuple[SortKey, ...] = ("memory", "cpu", "pid", "name", "cwd")
+
+# Table columns as (key, label); selection and PID are never dropped
+TABLE_COLUMNS: tuple[tuple[str, str], ...] = (
+    ("selected", ""),
+    ("pid", "PID"),
+    ("name", "Name"),
+    ("rss_mb", "RAM (MB)"),
+    ("cpu_percent", "CPU%"),
+    ("cwd", "CWD"),
+    ("ppid", "PPID"),
+    ("parent_name", "Parent"),
+    ("status", "Status"),
+)
+
+# Layout columns read from smaps_rollup, on refreshes that show them
+SMAPS_COLUMNS = frozenset({"pss_mb", "uss_mb", "shared_mb", "swap_mb"})
+
+# Sort key for each clickable column header
+COLUMN_SORT_MAP: dict[str, SortKey] = {
+    "pid": "pid",
+    "name": "name",
+    "rss_mb": "memory",
+    "cpu_percent": "cpu",
+    "cwd": "cwd",
+}
+
+# Actions that open dialogs or the search bar; replay applies their recorded
+# outcome instead
+REPLAY_SKIPPED_ACTIONS = frozenset({
+    "quit",
+    "search",
+    "clear_search",
+    "inspect",
+    "kill_selected",
+    "force_kill_selected",
+    "review_queue",
+    "restart_selected",
+    "filter_panel",
+})
+
+# Actions that never change undoable state
+UNTRACKED_ACTIONS = frozenset({
+    "quit",
+    "refresh",
+    "undo",
+    "clear_search",  # Adds the search to the history itself
+    "kill_selected",
+    "force_kill_selected",
+    # Queuing clears the selection, which undo would not unqueue
+    "queue_kill",
+    "queue_force_kill",
+    "queue_suspend",
+    "queue_renice",
+    "review_queue",
+    "restart_selected",
+    "filter_panel",  # Adds the saved view to the history itself
+})
+
+# Columns whose matches of the search are highlighted; only names match fuzzily
+SEARCH_COLUMNS = frozenset({"name", "cmdline", "cwd"})
+SEARCH_HIGHLIGHT = "reverse"
+
+# Below this terminal width the sidebar is hidden to make room for the table
+NARROW_WIDTH = 100
+SIDEBAR_WIDTH = 30
+
+
+class ProcessTable(DataTable):
+    """Process table; Enter inspects the cursor row instead of selecting it.
+
+    Clicking a row still toggles its selection.
+    """
+
+    BINDINGS: ClassVar = [Binding("enter", "app.inspect", t("key-inspect"))]
+
+
+def _arrow(trend: Trend | None) -> str:
+    """Render a trend as a suffix of its cell, e.g. " ↑".
+
+    Returns:
+        str: The arrow after a space, or "" while there is no trend yet.
+    """
+    return f" {trend}" if trend else ""
+
+
+class SearchBar(Input):
+    """Search bar below the process table; the table narrows while typing.
+
+    Enter returns to the table keeping the search, Esc clears it.
+    """
+
+    BINDINGS: ClassVar = [
+        Binding("escape", "app.clear_search", t("key-clear-search")),
+    ]
 
 
 class ProcessCleanerApp(App):
@@ -47,38 +244,148 @@ class ProcessCleanerApp(App):
     current_view = reactive[ViewType]("all")
     sort_key = reactive[SortKey]("memory")
     sort_reverse = reactive(True)
+    sort_secondary = reactive[SortKey | None](None)  # Breaks primary-key ties
     cwd_filter = reactive[str | None](None)
+    name_filter = reactive[str | None](None)
+    tree_mode = reactive(False)  # Parent/child hierarchy instead of a flat list
 
     BINDINGS: ClassVar = [
-        Binding("q", "quit", "Quit"),
-        Binding("r", "refresh", "Refresh"),
-        Binding("k", "kill_selected", "Kill"),
-        Binding("K", "force_kill_selected", "Force Kill"),
-        Binding("o", "show_orphans", "Orphans"),
-        Binding("O", "show_killable", "Killable"),
-        Binding("a", "show_all", "All"),
-        Binding("g", "show_groups", "Groups"),
-        Binding("w", "filter_cwd", "Filter CWD"),
-        Binding("W", "clear_cwd_filter", "Clear CWD"),
-        Binding("space", "toggle_select", "Select"),
-        Binding("s", "select_all_visible", "Select All"),
-        Binding("c", "clear_selection", "Clear"),
+        Binding("q", "quit", t("key-quit")),
+        Binding("r", "refresh", t("key-refresh")),
+        Binding("k", "kill_selected", t("key-kill")),
+        Binding("K", "force_kill_selected", t("key-force-kill")),
+        Binding("o", "show_orphans", t("key-orphans")),
+        Binding("O", "show_killable", t("key-killable")),
+        Binding("a", "show_all", t("key-all")),
+        Binding("g", "show_groups", t("key-groups")),
+        Binding("D", "show_duplicates", t("key-duplicates")),
+        Binding("w", "filter_cwd", t("key-filter-cwd")),
+        Binding("W", "clear_cwd_filter", t("key-clear-cwd")),
+        Binding("f", "filter_panel", t("key-filter-panel")),
+        Binding("slash", "search", t("key-search")),
+        Binding("space", "toggle_select", t("key-select")),
+        Binding("s", "select_all_visible", t("key-select-all")),
+        Binding("c", "clear_selection", t("key-clear")),
+        Binding("u", "undo", t("key-undo")),
+        Binding("x", "queue_kill", t("key-queue-kill")),
+        Binding("X", "queue_force_kill", t("key-queue-force-kill")),
+        Binding("z", "queue_suspend", t("key-queue-suspend")),
+        Binding("n", "queue_renice", t("key-queue-renice")),
+        Binding("p", "review_queue", t("key-review-queue")),
+        Binding("R", "restart_selected", t("key-restart")),
         # Sorting bindings
-        Binding("1", "sort_memory", "Sort:Mem"),
-        Binding("2", "sort_cpu", "Sort:CPU"),
-        Binding("3", "sort_pid", "Sort:PID"),
-        Binding("4", "sort_name", "Sort:Name"),
-        Binding("5", "sort_cwd", "Sort:CWD"),
-        Binding("!", "toggle_sort_order", "Reverse"),
+        Binding("1", "sort_memory", t("key-sort-mem")),
+        Binding("2", "sort_cpu", t("key-sort-cpu")),
+        Binding("3", "sort_pid", t("key-sort-pid")),
+        Binding("4", "sort_name", t("key-sort-name")),
+        Binding("5", "sort_cwd", t("key-sort-cwd")),
+        Binding("6", "cycle_secondary_sort", t("key-sort-then")),
+        Binding("!", "toggle_sort_order", t("key-reverse")),
+        Binding("L", "cycle_layout", t("key-layout")),
+        # Tree view bindings
+        Binding("t", "toggle_tree", t("key-tree")),
+        Binding("e", "toggle_fold", t("key-fold")),
+        Binding("T", "select_subtree", t("key-subtree")),
+        Binding("C", "toggle_cores", t("key-cores")),
+        Binding("enter", "inspect", t("key-inspect")),
+        Binding("escape", "clear_search", t("key-clear-search"), show=False),
     ]
 
-    def __init__(self) -> None:
-        """Initialize the TUI application."""
+    def __init__(
+        self,
+        source: ProcessSource | None = None,
+        recorder: SessionRecorder | None = None,
+        replay: list[dict[str, Any]] | None = None,
+    ) -> None:
+        """Initialize the TUI application.
+
+        Args:
+            source: Process backend; defaults to the live system, scoped by
+                ``PROCCLEAN_SCOPE`` and simulated when ``PROCCLEAN_SIMULATE``
+                is set.
+            recorder: Session recorder; defaults to one writing to
+                ``PROCCLEAN_RECORD`` when set.
+            replay: Recorded events to play back instead of reading live
+                data; defaults to the file named by ``PROCCLEAN_REPLAY``.
+        """
         super().__init__()
+        scoped = scoped_source_from_env()
+        self.source = source or simulated_source_from_env(scoped) or scoped
+        self.redact = NO_REDACT_ENV not in os.environ
+        # Asks the terminal about sixel, which must happen before Textual
+        # starts reading its input
+        self.graphics = graphics_enabled()
+        if polkit_enabled():
+            # pkexec's terminal prompt would draw over the TUI
+            os.environ[POLKIT_ENV] = POLKIT_AGENT_ONLY
+        if recorder is None and (record_path := os.environ.get(RECORD_ENV)):
+            recorder = SessionRecorder(Path(record_path), redact=self.redact)
+        if replay is None and (replay_path := os.environ.get(REPLAY_ENV)):
+            replay = load_session(Path(replay_path))
+        self.recorder = recorder
+        self.replay = replay
         self.processes: list[ProcessInfo] = []
         self.selected_pids: set[int] = set()
+        self.row_cap = row_cap()
+        self.defaults = load_defaults()
+        floor = self.defaults.min_memory
+        self.min_memory = 5.0 if floor is None else floor
+        if self.defaults.sort:
+            # A third sort key and beyond has no TUI equivalent
+            primary, *rest = self.defaults.sort.split(",")
+            self.set_reactive(ProcessCleanerApp.sort_key, primary)
+            self.set_reactive(ProcessCleanerApp.sort_secondary, next(iter(rest), None))
+        try:
+            user = (self.source or DEFAULT_SOURCE).current_user()
+        except OSError:
+            user = None
+        self.highlight_rules = highlight_rules(
+            user, lambda pid: pid in self.selected_pids
+        )
+        self.shown_rows = self.total_rows = 0  # Rows in the table vs. matching
+        self.visible_columns: tuple[str, ...] = ()
+        self.layout_name: str | None = None  # None: responsive default columns
+        self.layout_columns: list[str] | None = default_columns()
+        self.cwd_width = CWD_MAX_WIDTH
+        self.memory_history: deque[float] = deque(maxlen=HISTORY_SIZE)
+        self.history = ActionHistory()
+        self.collapsed: set[int] = set()  # Tree nodes with hidden children
+        self.tree_prefixes: dict[int, str] = {}  # Indent and fold marker per PID
+        self.oom = OomTracker()
+        self.base_sub_title = ""
+        self.search_before: UiState | None = None  # State when the search began
+        self.collector = ProcessCollector(self.source)
+        self.trends = TrendTracker()
+        self.queue = ActionQueue()
 
-    def compose(self) -> ComposeResult:  # noqa: PLR6301
+    @staticmethod
+    def _view_options(defaults: Defaults) -> list[Option]:
+        """Build the sidebar options, one per view.
+
+        All processes come first, process groups and duplicates last and the
+        views of the filter presets in between; presets without a localized
+        label show their description.
+
+        Args:
+            defaults: Configured defaults, for the preset parameters.
+
+        Returns:
+            list[Option]: Options whose ids are ``view-`` plus the view.
+        """
+        options = [Option(t("tui-view-all"), id="view-all")]
+        for spec in FILTERS:
+            if spec.view is None:
+                continue
+            key = f"tui-view-{spec.view}"
+            values = spec.defaults() | defaults.param_values(spec)
+            params = {name: f"{v:g}" for name, v in values.items()}
+            label = t(key, **params) if get_catalog().pattern(key) else spec.description
+            options.append(Option(label, id=f"view-{spec.view}"))
+        options.append(Option(t("tui-view-groups"), id="view-groups"))
+        options.append(Option(t("tui-view-duplicates"), id="view-duplicates"))
+        return options
+
+    def compose(self) -> ComposeResult:
         """Build the TUI layout.
 
         Yields:
@@ -90,37 +397,177 @@ class ProcessCleanerApp(App):
             yield Static("", id="mem-used")
             yield Static("", id="mem-free")
             yield Static("", id="swap")
+            yield Static("", id="load")
+            yield Static("", id="uptime")
+            yield Static("", id="cpu-cores")
+            yield create_memory_chart(self.graphics)
         with Horizontal(id="main-container"):
             with Vertical(id="sidebar"):
-                yield Label("Views", id="sidebar-title")
-                yield OptionList(
-                    Option("All Processes", id="view-all"),
-                    Option("Orphaned", id="view-orphans"),
-                    Option("Killable", id="view-killable"),
-                    Option("Process Groups", id="view-groups"),
-                    Option("High Memory (>500MB)", id="view-high-mem"),
-                    id="view-selector",
-                )
+                yield Label(t("tui-views"), id="sidebar-title")
+                yield OptionList(*self._view_options(self.defaults), id="view-selector")
             with Vertical(id="content"):
-                yield DataTable(id="process-table")
+                yield ProcessTable(id="process-table")
+                yield SearchBar(placeholder=t("search-placeholder"), id="search-bar")
         yield Static("", id="status-bar")
         yield Footer()
 
     def on_mount(self) -> None:
         """Initialize app after mounting."""
-        self.title = "ProcClean"
-        self.sub_title = "Process Cleanup Tool"
+        self.title = t("tui-title")
+        self.sub_title = t("tui-subtitle")
+        inner = self.source
+        if isinstance(inner, SimulatedSource):
+            self.sub_title += f" {t('tui-simulated')}"
+            inner = inner.source
+        if isinstance(inner, ScopedSource):
+            self.sub_title += f" {t('tui-scope', scope=inner.name)}"
+        if self.recorder:
+            self.sub_title += f" {t('tui-recording', path=self.recorder.path)}"
+        self.base_sub_title = self.sub_title
 
         table = self.query_one("#process-table", DataTable)
         table.cursor_type = "row"
-        table.add_columns(
-            "", "PID", "Name", "RAM (MB)", "CPU%", "CWD", "PPID", "Parent", "Status"
-        )
+        self._apply_layout(self.size.width)
 
+        if self.replay is not None:
+            self.sub_title += f" {t('tui-replay')}"
+            self._start_replay(self.replay)
+            return
+        if warning := capability_warning():
+            self.notify(warning, severity="warning", timeout=15)
         self.refresh_data()
         # Auto-refresh every 5 seconds
         self.set_interval(5.0, self.refresh_data)
 
+    def on_unmount(self) -> None:
+        """Close the session recording, if any."""
+        if self.recorder:
+            self.recorder.close()
+
+    async def run_action(
+        self,
+        action: str | ActionParseResult,
+        default_namespace: DOMNode | None = None,
+    ) -> bool:
+        """Run an action, recording and adding app-level actions to history.
+
+        Returns:
+            bool: True if the action was handled.
+        """
+        if not isinstance(action, str) or default_namespace not in {None, self}:
+            return await super().run_action(action, default_namespace)
+        # Widget bindings (cursor keys) are left out; actions carry the
+        # cursor PID instead, which replay moves the cursor back to
+        if self.recorder:
+            pid = self._get_pid_at_cursor()
+            self.recorder.record("action", action=action, pid=pid)
+        before = self._ui_state()
+        handled = await super().run_action(action, default_namespace)
+        if action not in UNTRACKED_ACTIONS and self._ui_state() != before:
+            self.history.push(action, before)
+        return handled
+
+    @work(exclusive=True, group="replay")
+    async def _start_replay(self, events: list[dict[str, Any]]) -> None:
+        """Apply recorded events in order, at their original offsets.
+
+        Args:
+            events: Events loaded from a recording.
+        """
+        elapsed = 0.0
+        for event in events:
+            await asyncio.sleep(max(0.0, event["t"] - elapsed))
+            elapsed = max(elapsed, event["t"])
+            await self._replay_event(event)
+        self.notify(t("tui-replay-done"))
+
+    async def _replay_event(self, event: dict[str, Any]) -> None:
+        """Apply one recorded event to the app.
+
+        Args:
+            event: Event loaded from a recording.
+        """
+        match event["kind"]:
+            case "snapshot":
+                self._update_data(event["memory"], snapshot_processes(event))
+            case "action" if event["action"] not in REPLAY_SKIPPED_ACTIONS:
+                table = self.query_one("#process-table", DataTable)
+                self._restore_cursor(table, event.get("pid"))
+                await self.run_action(event["action"])
+            case "view":
+                self.current_view = event["view"]
+            case "search":
+                self.name_filter = event["query"] or None
+            case "select":
+                self.selected_pids ^= {event["pid"]}
+                self.update_table()
+            case "queue":
+                results = event.get("results", [])
+                success = sum(1 for r in results if r["ok"])
+                self.notify(t("tui-replay-queue", total=len(results)))
+                self.notify(t("tui-queue-done", success=success, total=len(results)))
+                self.queue.clear()
+                self.update_table()
+            case "restart":
+                pids = ", ".join(str(pid) for pid in event["pids"])
+                self.notify(t("tui-replay-restart", pids=pids))
+                results = event.get("results", [])
+                success = sum(1 for r in results if r["ok"])
+                self.notify(t("tui-restarted", success=success, total=len(results)))
+                self.selected_pids.clear()
+                self.update_table()
+            case "kill":
+                pids = ", ".join(str(pid) for pid in event["pids"])
+                default = "SIGKILL" if event["force"] else "SIGTERM"
+                if event.get("escalate"):
+                    default = t("tui-escalate-signals")
+                name = event.get("signal") or default
+                self.notify(t("tui-replay-kill", signal=name, pids=pids))
+                results = event.get("results", [])
+                success = sum(1 for r in results if r["ok"])
+                self.notify(t("tui-killed", success=success, total=len(results)))
+                self.selected_pids.clear()
+                self.update_table()
+
+    def on_resize(self, event: events.Resize) -> None:
+        """Switch layout breakpoints when the terminal is resized."""
+        self._apply_layout(event.size.width)
+
+    def _apply_layout(self, width: int) -> None:
+        """Pick visible columns and cwd width for the terminal width.
+
+        Narrow terminals hide the sidebar first, then drop low-priority
+        columns and shrink cwd according to the shared breakpoints. A named
+        layout keeps all of its columns and only shrinks cwd.
+
+        Args:
+            width: Terminal width in characters.
+        """
+        narrow = width < NARROW_WIDTH
+        self.set_class(narrow, "narrow")
+        table_width = width if narrow else width - SIDEBAR_WIDTH
+        bp = get_breakpoint(table_width)
+        if self.layout_columns is not None:
+            # The table sizes its own cells: widths and units are not applied
+            columns = ("selected", *map(column_key, self.layout_columns))
+        else:
+            columns = tuple(
+                key for key, _ in TABLE_COLUMNS if bp is None or key not in bp.drop
+            )
+        self.cwd_width = bp.cwd_width if bp else CWD_MAX_WIDTH
+        if columns == self.visible_columns:
+            self.update_table()
+            return
+
+        self.visible_columns = columns
+        labels = {key: spec.header for key, spec in COLUMNS.items()}
+        labels.update(TABLE_COLUMNS)
+        table = self.query_one("#process-table", DataTable)
+        table.clear(columns=True)
+        for key in columns:
+            table.add_column(labels[key], key=key)
+        self.update_table()
+
     # Reactive watchers - auto-update table when state changes
     def watch_current_view(self) -> None:
         """Update table when view changes."""
@@ -134,33 +581,125 @@ class ProcessCleanerApp(App):
         """Update table when sort order changes."""
         self.update_table()
 
+    def watch_sort_secondary(self) -> None:
+        """Update table when the secondary sort key changes."""
+        self.update_table()
+
     def watch_cwd_filter(self) -> None:
         """Update table when cwd filter changes."""
         self.update_table()
 
+    def watch_name_filter(self) -> None:
+        """Update table when the search changes, and the search bar to match.
+
+        Undo and replay change the search without the bar.
+        """
+        bar = self.query_one("#search-bar", SearchBar)
+        if bar.value.strip() != (self.name_filter or ""):
+            bar.value = self.name_filter or ""
+        bar.display = bool(self.name_filter) or bar.has_focus
+        self.update_table()
+
+    def watch_tree_mode(self) -> None:
+        """Update table when switching between tree and list view."""
+        self.update_table()
+
     def refresh_data(self) -> None:
-        """Trigger async refresh of process list and memory info."""
-        self._fetch_data()
+        """Trigger async refresh of process list and memory info.
+
+        During replay the data comes from the recording instead.
+        """
+        if self.replay is None:
+            self._fetch_data()
 
     @work(thread=True)
     def _fetch_data(self) -> None:
         """Fetch process data in background thread."""
         mem = get_memory_summary()
-        procs = get_process_list(min_memory_mb=5.0)
+        min_memory = self.min_memory
+        procs = None
+        if self.source is None:
+            procs = daemon_process_list(min_memory_mb=min_memory)
+        if procs is None:
+            procs = self.collector.collect(min_memory_mb=min_memory)
+        attach_runtime_envs(attach_repos(procs), self.source)
+        attach_pods(procs, self.source)
+        attach_vms(procs)
+        attach_media(procs, self.source)
+        attach_inhibitors(procs)
+        attach_deleted_libs(procs, self.source)
+        if SMAPS_COLUMNS.intersection(self.visible_columns):
+            attach_smaps(procs, self.source)  # Walks page tables; only if shown
+        oom = self.oom.check()
+        cores = core_usage()  # Usage since the previous refresh
         self.call_from_thread(self._update_data, mem, procs)
-
-    def _update_data(self, mem: dict[str, float], procs: list[ProcessInfo]) -> None:
+        self.call_from_thread(self._update_oom, oom)
+        self.call_from_thread(self._update_cores, cores)
+
+    def _update_cores(self, usage: CoreUsage) -> None:
+        """Show per-core usage in the header strip, flagging pinned cores."""
+        strip = self.query_one("#cpu-cores", Static)
+        strip.update(
+            t("tui-cores", strip=usage.strip(), average=f"{usage.average:.0f}")
+        )
+        strip.set_class(usage.state == CpuState.PINNED, "pinned")
+        strip.set_class(usage.state == CpuState.BUSY, "busy")
+
+    def _update_oom(self, oom: OomStatus) -> None:
+        """Show the latest kernel OOM kill in the header, alerting on new ones."""
+        self.sub_title = self.base_sub_title
+        if summary := describe_oom(oom):
+            self.sub_title += f" | {summary}"
+        if oom.new:
+            self.notify(t("tui-oom-new", count=oom.new), severity="warning")
+
+    def _update_data(self, mem: dict[str, Any], procs: list[ProcessInfo]) -> None:
         """Update UI with fetched data (called from main thread)."""
-        self.query_one("#mem-total", Static).update(f"Total: {mem['total_gb']:.1f}G")
+        self.query_one("#mem-total", Static).update(
+            t("tui-mem-total", gb=f"{mem['total_gb']:.1f}")
+        )
         self.query_one("#mem-used", Static).update(
-            f"Used: {mem['used_gb']:.1f}G ({mem['percent']:.0f}%)"
+            t(
+                "tui-mem-used",
+                gb=f"{mem['used_gb']:.1f}",
+                percent=f"{mem['percent']:.0f}",
+            )
+        )
+        self.query_one("#mem-free", Static).update(
+            t("tui-mem-free", gb=f"{mem['free_gb']:.1f}")
         )
-        self.query_one("#mem-free", Static).update(f"Free: {mem['free_gb']:.1f}G")
         self.query_one("#swap", Static).update(
-            f"Swap: {mem['swap_used_gb']:.1f}G/{mem['swap_total_gb']:.1f}G"
+            t(
+                "tui-mem-swap",
+                used=f"{mem['swap_used_gb']:.1f}",
+                total=f"{mem['swap_total_gb']:.1f}",
+            )
         )
+        # Recordings made by older versions have no load or uptime
+        if "load_avg" in mem:
+            load = self.query_one("#load", Static)
+            load.update(
+                t(
+                    "tui-load",
+                    load=" ".join(f"{avg:.2f}" for avg in mem["load_avg"]),
+                    cpus=mem["cpu_count"],
+                )
+            )
+            # More runnable tasks than CPUs: everything waits for a core
+            load.set_class(mem["load_avg"][0] > mem["cpu_count"], "saturated")
+            self.query_one("#uptime", Static).update(
+                t("tui-uptime", uptime=format_uptime(mem["uptime_seconds"]))
+            )
+        self.memory_history.append(mem["percent"])
+        chart = self.query_one("#mem-chart")
+        chart.set_history(self.memory_history)  # type: ignore[attr-defined]
         self.processes = procs
+        self.trends.record(procs)
+        if dropped := self.queue.prune(procs):
+            self.notify(t("tui-queue-pruned", count=dropped), severity="warning")
         self.update_table()
+        if self.recorder:
+            self.recorder.snapshot(procs, mem)
 
     def _sort_processes(self, procs: list[ProcessInfo]) -> list[ProcessInfo]:
         """Sort processes by current sort key and order.
@@ -171,15 +710,18 @@ class ProcessCleanerApp(App):
         Returns:
             A new list of processes sorted according to the current sort settings.
         """
-        sort_keys = {
-            "memory": lambda p: p.rss_mb,
-            "cpu": lambda p: p.cpu_percent,
-            "pid": lambda p: p.pid,
-            "name": lambda p: p.name.lower(),
-            "cwd": lambda p: (p.cwd or "").lower(),
-        }
-        key_func = sort_keys.get(self.sort_key, sort_keys["memory"])
-        return sorted(procs, key=key_func, reverse=self.sort_reverse)
+        return sort_processes(procs, self._sort_keys(), reverse=self.sort_reverse)
+
+    def _sort_keys(self) -> list[str]:
+        """Get the active sort keys, most significant first.
+
+        Returns:
+            The primary key, then the secondary key if one is set.
+        """
+        keys: list[str] = [self.sort_key]
+        if self.sort_secondary and self.sort_secondary != self.sort_key:
+            keys.append(self.sort_secondary)
+        return keys
 
     def _filter_by_view(self) -> list[ProcessInfo]:
         """Filter processes based on current view.
@@ -187,15 +729,14 @@ class ProcessCleanerApp(App):
         Returns:
             Filtered list of processes for the current view.
         """
-        if self.current_view == "orphans":
-            return [p for p in self.processes if p.is_orphan]
-        if self.current_view == "killable":
-            return [p for p in self.processes if p.is_orphan_candidate]
-        if self.current_view == "high-mem":
-            return [p for p in self.processes if p.rss_mb > HIGH_MEMORY_THRESHOLD_MB]
         if self.current_view == "groups":
             groups = find_similar_processes(self.processes)
             return [p for group in groups.values() for p in group]
+        if self.current_view == "duplicates":
+            return [p for dupes in find_duplicates(self.processes) for p in dupes]
+        if spec := FILTERS.by_view(self.current_view):
+            params = self.defaults.param_values(spec)
+            return spec.run(list(self.processes), self.source, **params)
         return list(self.processes)
 
     @staticmethod
@@ -225,55 +766,217 @@ class ProcessCleanerApp(App):
         procs = self._filter_by_view()
         if self.cwd_filter:
             procs = filter_by_cwd(procs, self.cwd_filter)
+        if self.name_filter:
+            procs = filter_by_search(procs, self.name_filter)
         procs = self._sort_processes(procs)
+        self.tree_prefixes = {}
+        if self.tree_mode:
+            # Sorting orders siblings; children follow their parent
+            rows = tree_rows(procs, self.collapsed)
+            self.tree_prefixes = {row.proc.pid: self._tree_prefix(row) for row in rows}
+            procs = [row.proc for row in rows]
+        self.total_rows = len(procs)
+        # Selected rows and the cursor row survive the cap
+        procs, _ = cap_processes(
+            procs,
+            self.row_cap,
+            keep=lambda p: p.pid in self.selected_pids or p.pid == cursor_pid,
+        )
+        self.shown_rows = len(procs)
 
         for proc in procs:
-            selected = "[X]" if proc.pid in self.selected_pids else "[ ]"
-            orphan_marker = " [orphan]" if proc.is_orphan else ""
-            tmux_marker = " [tmux]" if proc.in_tmux else ""
-            stale_marker = " [stale]" if proc.exe_deleted else ""
-            status = f"{proc.status}{orphan_marker}{tmux_marker}{stale_marker}"
-
-            cwd = proc.cwd or "?"
-            if len(cwd) > CWD_MAX_WIDTH:
-                cwd = "..." + cwd[-CWD_TRUNCATE_WIDTH:]
-
-            table.add_row(
-                selected,
-                str(proc.pid),
-                proc.name[:20],
-                f"{proc.rss_mb:.1f}",
-                f"{proc.cpu_percent:.1f}",
-                cwd,
-                str(proc.ppid),
-                proc.parent_name[:15],
-                status,
-                key=str(proc.pid),
-            )
+            table.add_row(*self._row_texts(proc), key=str(proc.pid))
 
         self._restore_cursor(table, cursor_pid)
         self.update_status()
 
+    @staticmethod
+    def _tree_prefix(row: TreeRow) -> str:
+        """Render the indent and fold marker before a name in the tree view.
+
+        Returns:
+            Two spaces per level, then a marker for processes with children.
+        """
+        marker = "▸ " if row.collapsed else "▾ " if row.children else "  "
+        return f"{'  ' * row.depth}{marker}"
+
+    def _row_cells(self, proc: ProcessInfo) -> dict[str, str]:
+        """Render every table cell for a process, keyed by column.
+
+        Args:
+            proc: Process to render.
+
+        Returns:
+            Mapping of column key to display value.
+        """
+        orphan_marker = " [orphan]" if proc.is_orphan else ""
+        tmux_marker = " [tmux]" if proc.in_tmux else ""
+        stale_marker = " [stale]" if proc.exe_deleted else ""
+        cwd_marker = " [cwd-deleted]" if proc.cwd_deleted else ""
+        env_marker = " [env-deleted]" if proc.runtime_env_deleted else ""
+        media_marker = " [media]" if proc.media else ""
+        inhibit_marker = " [inhibit]" if proc.inhibitor else ""
+        restart_marker = " [needs-restart]" if proc.deleted_libs else ""
+        rt_marker = " [realtime]" if proc.is_realtime else ""
+        priv_marker = " [priv]" if proc.is_privileged else ""
+        markers = (
+            f"{orphan_marker}{tmux_marker}{stale_marker}{cwd_marker}"
+            f"{env_marker}{media_marker}{inhibit_marker}{restart_marker}"
+            f"{rt_marker}{priv_marker}"
+        )
+        cmdline = redact_cmdline(proc.cmdline) if self.redact else proc.cmdline
+        return {
+            "selected": self._selection_marker(proc),
+            "pid": str(proc.pid),
+            "name": f"{self.tree_prefixes.get(proc.pid, '')}{proc.name[:20]}",
+            "rss_mb": f"{proc.rss_mb:.1f}{_arrow(self.trends.rss(proc))}",
+            "cpu_percent": f"{proc.cpu_percent:.1f}{_arrow(self.trends.cpu(proc))}",
+            "cwd": clip(proc.cwd or "?", self.cwd_width, ClipSide.LEFT),
+            "ppid": str(proc.ppid),
+            "parent_name": proc.parent_name[:15],
+            "status": f"{proc.status}{markers}",
+            "cmdline": cmdline[:60],
+            "username": proc.username,
+            "nice": str(proc.nice),
+            "sched_policy": proc.sched_policy,
+            "rt_priority": str(proc.rt_priority),
+            "privileges": ",".join(proc.privileges) or "-",
+            "confinement": proc.confinement,
+            "package": proc.package or "-",
+            "repo": clip(proc.repo or "-", self.cwd_width, ClipSide.LEFT),
+            "branch": proc.branch or "-",
+            "pod": (proc.pod or "-")[:30],
+            "namespace": proc.namespace or "-",
+            "container": (proc.container or "-")[:25],
+            "vm": (proc.vm or "-")[:25],
+            "hugetlb_mb": f"{proc.hugetlb_mb or 0.0:.1f}",
+            "media": proc.media or "-",
+            "inhibitor": (proc.inhibitor or "-")[:40],
+            "deleted_libs": lib_names(proc.deleted_libs)[:40],
+            "runtime_env": clip(proc.runtime_env or "-", 30, ClipSide.LEFT),
+            **{key: COLUMNS[key].extract(proc) for key in SMAPS_COLUMNS},
+        }
+
+    def _selection_marker(self, proc: ProcessInfo) -> str:
+        """Show whether a process is selected or has an action queued.
+
+        Returns:
+            str: "[X]" if selected, the ``QUEUE_MARKERS`` entry of a queued
+            action otherwise, else "[ ]".
+        """
+        if proc.pid in self.selected_pids:
+            return "[X]"
+        if action := self.queue.get(proc.pid):
+            return QUEUE_MARKERS[action.kind]
+        return "[ ]"
+
+    def _row_texts(self, proc: ProcessInfo) -> list[Text]:
+        """Render the visible cells of a process, highlighted by risk.
+
+        Matches of the search are highlighted on top.
+
+        Args:
+            proc: Process to render.
+
+        Returns:
+            Styled cells in column order.
+        """
+        cells = self._row_cells(proc)
+        styles = cell_styles(proc, self.visible_columns, self.highlight_rules)
+        texts = [Text(cells[key], style=styles[key]) for key in self.visible_columns]
+        if not self.name_filter:
+            return texts
+        for key, text in zip(self.visible_columns, texts, strict=True):
+            if key not in SEARCH_COLUMNS:
+                continue
+            spans = search_spans(self.name_filter, text.plain, fuzzy=key == "name")
+            for start, end in spans or []:
+                text.stylize(SEARCH_HIGHLIGHT, start, end)
+        return texts
+
+    def _update_row(self, table: DataTable, pid: int) -> None:
+        """Re-render a row after its selection changed.
+
+        Selection affects the selected-other-user highlight, so the whole row
+        is redrawn rather than just its selection cell.
+        """
+        proc = next((p for p in self.processes if p.pid == pid), None)
+        if proc is None:
+            return
+        for key, cell in zip(self.visible_columns, self._row_texts(proc), strict=True):
+            table.update_cell(str(pid), key, cell)
+
+    def _ui_state(self) -> UiState:
+        """Capture the undoable view, filter and selection state.
+
+        Returns:
+            The current state.
+        """
+        return UiState(
+            view=self.current_view,
+            sort_key=self.sort_key,
+            sort_reverse=self.sort_reverse,
+            cwd_filter=self.cwd_filter,
+            name_filter=self.name_filter,
+            selected=frozenset(self.selected_pids),
+            sort_secondary=self.sort_secondary,
+        )
+
+    def _restore_state(self, state: UiState) -> None:
+        """Return to a previously captured state.
+
+        Selected PIDs that are no longer listed are dropped.
+
+        Args:
+            state: State captured by ``_ui_state``.
+        """
+        self.current_view = state.view  # type: ignore[assignment]
+        self.sort_key = state.sort_key  # type: ignore[assignment]
+        self.sort_reverse = state.sort_reverse
+        self.sort_secondary = state.sort_secondary  # type: ignore[assignment]
+        self.cwd_filter = state.cwd_filter
+        self.name_filter = state.name_filter
+        listed = {p.pid for p in self.processes}
+        self.selected_pids = set(state.selected & listed)
+        self.update_table()
+
     def update_status(self) -> None:
         """Update status bar with selection info."""
         selected_mb = sum(
             p.rss_mb for p in self.processes if p.pid in self.selected_pids
         )
-        msg = f"Selected: {len(self.selected_pids)} processes ({selected_mb:.1f} MB)"
+        msg = t(
+            "tui-selected", count=len(self.selected_pids), mb=f"{selected_mb:.1f}"
+        )
+        if self.queue:
+            msg += " | " + t("tui-queued", count=len(self.queue))
+        if position := self._row_position():
+            msg += f" | {position}"
+        if self.shown_rows < self.total_rows:
+            shown, total = self.shown_rows, self.total_rows
+            msg += " " + t("tui-truncated", shown=shown, total=total)
         self.query_one("#status-bar", Static).update(msg)
 
+    def _row_position(self) -> str:
+        """Describe where the cursor is within the table.
+
+        Returns:
+            "Row X of N", or an empty string for an empty table.
+        """
+        table = self.query_one("#process-table", DataTable)
+        if not table.row_count:
+            return ""
+        return t("tui-row-position", row=table.cursor_row + 1, total=table.row_count)
+
     @on(OptionList.OptionSelected, "#view-selector")
     def on_view_change(self, event: OptionList.OptionSelected) -> None:
         """Handle view selection changes."""
-        view_map: dict[str, ViewType] = {
-            "view-all": "all",
-            "view-orphans": "orphans",
-            "view-killable": "killable",
-            "view-groups": "groups",
-            "view-high-mem": "high-mem",
-        }
-        if event.option.id and event.option.id in view_map:
-            self.current_view = view_map[event.option.id]
+        view = (event.option.id or "").removeprefix("view-")
+        if view and view != self.current_view:
+            self.history.push("view", self._ui_state())
+            self.current_view = view
+            if self.recorder:
+                self.recorder.record("view", view=self.current_view)
 
     @on(DataTable.RowSelected, "#process-table")
     def on_row_clicked(self, event: DataTable.RowSelected) -> None:
@@ -282,45 +985,50 @@ class ProcessCleanerApp(App):
         # Guard against race: auto-refresh can remove rows mid-flight
         try:
             row_data = event.data_table.get_row(event.row_key)
-            pid = int(row_data[1])
+            pid = int(str(row_data[1]))
         except RowDoesNotExist:
             return
+        if self.recorder:
+            self.recorder.record("select", pid=pid)
+        self.history.push("select", self._ui_state())
 
         # Toggle selection
-        if pid in self.selected_pids:
-            self.selected_pids.remove(pid)
-            new_value = "[ ]"
-        else:
-            self.selected_pids.add(pid)
-            new_value = "[X]"
+        self.selected_pids ^= {pid}
+        self._update_row(event.data_table, pid)
+        self.update_status()
 
-        # Update the clicked row's selection cell using row_key (not cursor_row)
-        selection_column_key = event.data_table.ordered_columns[0].key
-        event.data_table.update_cell(event.row_key, selection_column_key, new_value)
+    @on(DataTable.RowHighlighted, "#process-table")
+    def on_row_highlighted(self) -> None:
+        """Keep the row position in the status bar current."""
         self.update_status()
 
     @on(DataTable.HeaderSelected, "#process-table")
     def on_header_clicked(self, event: DataTable.HeaderSelected) -> None:
         """Sort by column when header is clicked."""
-        # Map column index to sort key.
-        # Sortable: PID(1), Name(2), RAM(3), CPU(4), CWD(5)
-        # Not sortable (no-op): Selection(0), PPID(6), Parent(7), Status(8)
-        # NOTE: Indexes must be updated if column order changes in update_table()
-        column_sort_map: dict[int, SortKey] = {
-            1: "pid",
-            2: "name",
-            3: "memory",
-            4: "cpu",
-            5: "cwd",
-        }
-        col_idx = event.column_index
-        if col_idx in column_sort_map:
-            self._set_sort(column_sort_map[col_idx])
+        # Selection, PPID, Parent and Status headers are not sortable (no-op)
+        key = event.column_key.value
+        if key in COLUMN_SORT_MAP:
+            self._set_sort(COLUMN_SORT_MAP[key])
+
+    def action_inspect(self) -> None:
+        """Show the details of the process at the cursor."""
+        if (proc := self._get_process_at_cursor()) is not None:
+            self._load_details(proc)
+
+    @work(thread=True, exclusive=True, group="inspect")
+    def _load_details(self, proc: ProcessInfo) -> None:
+        """Collect the details of a process off the UI thread, then show them.
+
+        Args:
+            proc: The process to inspect.
+        """
+        details = get_process_details(proc, self.source, redact=self.redact)
+        self.call_from_thread(self.push_screen, ProcessDetailScreen(details))
 
     def action_refresh(self) -> None:
         """Refresh process data."""
         self.refresh_data()
-        self.notify("Refreshed")
+        self.notify(t("tui-refreshed"))
 
     def _get_pid_at_cursor(self) -> int | None:
         """Get the PID of the process at the current cursor position.
@@ -334,7 +1042,7 @@ class ProcessCleanerApp(App):
             return None
         row_data = table.get_row_at(table.cursor_row)
         # row_data is a list of cell values: [selected, pid, name, ...]
-        return int(row_data[1])
+        return int(str(row_data[1]))
 
     def _get_process_at_cursor(self) -> ProcessInfo | None:
         """Get the ProcessInfo at the current cursor position.
@@ -357,24 +1065,55 @@ class ProcessCleanerApp(App):
 
         pid = self._get_pid_at_cursor()
         if pid is not None:
-            # Toggle selection
-            if pid in self.selected_pids:
-                self.selected_pids.remove(pid)
-                new_value = "[ ]"
-            else:
-                self.selected_pids.add(pid)
-                new_value = "[X]"
-
-            # Update just the selection cell, not the entire table
-            table.update_cell_at(Coordinate(table.cursor_row, 0), new_value)
+            # Toggle selection and redraw just this row, not the entire table
+            self.selected_pids ^= {pid}
+            self._update_row(table, pid)
             self.update_status()
 
+    def action_toggle_cores(self) -> None:
+        """Show or hide the per-core CPU strip."""
+        strip = self.query_one("#cpu-cores", Static)
+        strip.toggle_class("visible")
+        shown = strip.has_class("visible")
+        self.notify(t("tui-cores-on" if shown else "tui-cores-off"))
+
+    def action_toggle_tree(self) -> None:
+        """Switch between the flat list and the process tree."""
+        self.tree_mode = not self.tree_mode
+        self.notify(t("tui-tree-on" if self.tree_mode else "tui-tree-off"))
+
+    def action_toggle_fold(self) -> None:
+        """Expand or collapse the children of the current row in the tree."""
+        pid = self._get_pid_at_cursor()
+        if pid is None or not self.tree_mode:
+            return
+        self.collapsed ^= {pid}
+        self.update_table()
+
+    def action_select_subtree(self) -> None:
+        """Select the current row and all of its descendants.
+
+        Descendants hidden by a filter or a collapsed node are included;
+        when the whole subtree is already selected, it is deselected.
+        """
+        pid = self._get_pid_at_cursor()
+        if pid is None:
+            return
+        pids = set(subtree_pids(self.processes, pid))
+        if pids <= self.selected_pids:
+            self.selected_pids -= pids
+            self.notify(t("tui-subtree-cleared", count=len(pids), pid=pid))
+        else:
+            self.selected_pids |= pids
+            self.notify(t("tui-subtree-selected", count=len(pids), pid=pid))
+        self.update_table()
+
     def action_select_all_visible(self) -> None:
         """Select all visible processes."""
         table = self.query_one("#process-table", DataTable)
         for row_idx in range(table.row_count):
             row = table.get_row_at(row_idx)
-            pid = int(row[1])
+            pid = int(str(row[1]))
             self.selected_pids.add(pid)
         self.update_table()
 
@@ -399,6 +1138,10 @@ class ProcessCleanerApp(App):
         """Switch to process groups view."""
         self.current_view = "groups"
 
+    def action_show_duplicates(self) -> None:
+        """Switch to duplicates view: same command line in the same cwd."""
+        self.current_view = "duplicates"
+
     def _set_sort(self, key: SortKey) -> None:
         """Set sort key and update table."""
         if self.sort_key == key:
@@ -408,8 +1151,12 @@ class ProcessCleanerApp(App):
             self.sort_key = key
             # Default order: descending for numeric, ascending for name
             self.sort_reverse = key != "name"
-        order = "desc" if self.sort_reverse else "asc"
-        self.notify(f"Sort: {key} ({order})")
+        self._notify_sort()
+
+    def _notify_sort(self) -> None:
+        """Announce the active sort keys and order."""
+        order = t("tui-sort-desc" if self.sort_reverse else "tui-sort-asc")
+        self.notify(t("tui-sort", key=", ".join(self._sort_keys()), order=order))
 
     def action_sort_memory(self) -> None:
         """Sort the table by resident memory usage."""
@@ -431,49 +1178,457 @@ class ProcessCleanerApp(App):
         """Sort the table by current working directory."""
         self._set_sort("cwd")
 
+    def action_cycle_secondary_sort(self) -> None:
+        """Pick the next secondary sort key, then none again."""
+        choices = [None, *(k for k in SORT_ORDER if k != self.sort_key)]
+        current = self.sort_secondary if self.sort_secondary in choices else None
+        self.sort_secondary = choices[(choices.index(current) + 1) % len(choices)]
+        self._notify_sort()
+
     def action_toggle_sort_order(self) -> None:
         """Toggle the current sort order (ascending/descending)."""
         self.sort_reverse = not self.sort_reverse
-        order = "desc" if self.sort_reverse else "asc"
-        self.notify(f"Sort: {self.sort_key} ({order})")
+        self._notify_sort()
+
+    def action_cycle_layout(self) -> None:
+        """Switch to the next named column layout, then back to the default."""
+        try:
+            layouts = get_layouts()
+            configured = default_columns()
+        except ProccleanError as e:
+            self.notify(e.message, severity="error")
+            return
+        names = [None, *layouts]
+        index = names.index(self.layout_name) if self.layout_name in names else 0
+        self.layout_name = names[(index + 1) % len(names)]
+        self.layout_columns = (
+            layouts[self.layout_name] if self.layout_name else configured
+        )
+        self.notify(t("tui-layout", name=self.layout_name or "default"))
+        self._apply_layout(self.size.width)
 
     def action_filter_cwd(self) -> None:
         """Filter by cwd of currently selected row."""
         proc = self._get_process_at_cursor()
         if proc and proc.cwd and proc.cwd != "?":
             self.cwd_filter = proc.cwd
-            self.notify(f"Filter: cwd={self.cwd_filter}")
+            self.notify(t("tui-filter-cwd", cwd=self.cwd_filter))
         else:
-            self.notify("Cannot filter: unknown cwd", severity="warning")
+            self.notify(t("tui-filter-cwd-unknown"), severity="warning")
 
     def action_clear_cwd_filter(self) -> None:
         """Clear the cwd filter."""
         self.cwd_filter = None
-        self.notify("CWD filter cleared")
+        self.notify(t("tui-filter-cwd-cleared"))
+
+    def action_filter_panel(self) -> None:
+        """Compose filter rules over the table and save them as a view.
+
+        A view saved from the config file opens with its rules, to edit it.
+        """
+        spec = FILTERS.by_view(self.current_view)
+        name, table = "", None
+        if spec is not None and isinstance(spec.apply, ConfigPreset):
+            name = spec.name
+            table = load_config().get("presets", {}).get(name)
+
+        def handle_saved(saved: str | None) -> None:
+            if saved is None:
+                return
+            options = self.query_one("#view-selector", OptionList)
+            options.clear_options()
+            options.add_options(self._view_options(self.defaults))
+            self.notify(t("tui-panel-saved", name=saved))
+            if saved == self.current_view:
+                self.update_table()  # Same view, new rules
+                return
+            self.history.push("view", self._ui_state())
+            self.current_view = saved
+            if self.recorder:
+                self.recorder.record("view", view=self.current_view)
+
+        screen = FilterPanelScreen(list(self.processes), self.source, name, table)
+        self.push_screen(screen, handle_saved)
+
+    def action_search(self) -> None:
+        """Open the search bar; the table narrows as the query is typed."""
+        self.search_before = self._ui_state()
+        bar = self.query_one("#search-bar", SearchBar)
+        bar.display = True
+        bar.focus()
+
+    @on(Input.Changed, "#search-bar")
+    def on_search_changed(self, event: Input.Changed) -> None:
+        """Filter the table live while typing."""
+        self.name_filter = event.value.strip() or None
+
+    @on(Input.Submitted, "#search-bar")
+    def on_search_submitted(self) -> None:
+        """Return to the table, keeping the search."""
+        self._finish_search()
+
+    def action_clear_search(self) -> None:
+        """Clear the search and return to the table.
+
+        Selections made while searching are kept.
+        """
+        bar = self.query_one("#search-bar", SearchBar)
+        if self.name_filter is None and not bar.has_focus:
+            return
+        if self.search_before is None:
+            self.search_before = self._ui_state()
+        self.name_filter = None
+        self._finish_search()
+
+    def _finish_search(self) -> None:
+        """Leave the search bar, recording the search and adding it to undo.
+
+        The bar stays visible below the table while a search is active.
+        """
+        query = self.name_filter or ""
+        if self.recorder:
+            self.recorder.record("search", query=query)
+        before = self.search_before
+        if before is not None and before.name_filter != self.name_filter:
+            self.history.push("search", before)
+        self.search_before = None
+        self.query_one("#search-bar", SearchBar).display = bool(query)
+        self.query_one("#process-table", DataTable).focus()
+        if query:
+            self.notify(t("tui-search", query=query))
+        else:
+            self.notify(t("tui-search-cleared"))
 
     def _do_kill(self, force: bool = False) -> None:
         if not self.selected_pids:
-            self.notify("No processes selected", severity="warning")
+            self.notify(t("tui-none-selected"), severity="warning")
             return
 
         procs = [p for p in self.processes if p.pid in self.selected_pids]
+        try:
+            load_kill_hooks()  # Report malformed hook commands before killing
+            pattern = no_confirm_match(procs[0]) if len(procs) == 1 else None
+            risks = data_loss_targets(procs)
+        except ProccleanError as e:
+            self.notify(e.message, severity="error")
+            return
+        if force and risks:
+            self._refuse_data_loss(risks)
+            return
+        if pattern and not risks:
+            name = procs[0].name
+            self.notify(t("tui-confirm-skipped", name=name, pattern=pattern))
+            self._execute_kill([procs[0].pid], force)
+            return
+        supervisors = resolve_supervisors([p.pid for p in procs], self.source)
+        screen = ConfirmKillScreen(
+            procs, force=force, supervisors=supervisors, data_loss=risks
+        )
 
-        def handle_confirm(confirmed: bool | None) -> None:
+        def handle_confirm(confirmed: bool | str | None) -> None:
             if confirmed:
-                self._execute_kill(list(self.selected_pids), force)
+                via_manager = confirmed == VIA_MANAGER
+                # Managers stop their services; the picked signal is moot
+                sig = None if via_manager else screen.picked_signal
+                escalate = screen.escalate and not via_manager
+                pids = list(self.selected_pids)
+                self._execute_kill(pids, force, via_manager, sig, escalate)
+
+        self.push_screen(screen, handle_confirm)
 
-        self.push_screen(ConfirmKillScreen(procs, force=force), handle_confirm)
+    def _refuse_data_loss(self, risks: list[ProcessInfo]) -> None:
+        """Report that SIGKILL is refused for processes that risk data loss."""
+        names = ", ".join(sorted({p.name for p in risks}))
+        self.notify(t("tui-data-loss-refused", names=names), severity="error")
 
     @work(thread=True)
-    def _execute_kill(self, pids: list[int], force: bool) -> None:
-        """Execute kill in background thread."""
-        results = kill_processes(pids, force=force)
+    def _execute_kill(
+        self,
+        pids: list[int],
+        force: bool,
+        via_manager: bool = False,
+        sig: signal.Signals | None = None,
+        escalate: bool = False,
+    ) -> None:
+        """Execute kill (or stop via supervisors) in background thread.
+
+        Children are signaled before their parents, so a selected subtree
+        goes down leaves first. An escalating kill shows which processes it
+        is still waiting for in the status bar.
+        """
+        pids = kill_order(self.processes, pids)
+
+        def progress(results: list[KillResult], left: float) -> None:
+            self.call_from_thread(self._show_kill_progress, results, left)
+
+        def kill() -> list[tuple[int, bool, str]]:
+            if via_manager:
+                return stop_via_managers(pids, force=force, source=self.source)
+            if escalate:
+                results = kill_escalating(
+                    pids, ESCALATE_TIMEOUT_SECONDS, self.source, progress
+                )
+                for result in results:
+                    if result.outcome is KillOutcome.ALIVE:
+                        self.call_from_thread(
+                            self.notify, result.message, severity="warning"
+                        )
+                return [(r.pid, r.ok, r.message) for r in results]
+            if sig is not None:
+                return kill_processes(pids, force=force, source=self.source, sig=sig)
+            return kill_processes(pids, force=force, source=self.source)
+
+        # Signals other than kills free nothing: the targets keep running
+        rss = (
+            {}
+            if sig is not None and not is_kill_signal(sig)
+            else {p.pid: p.rss_mb for p in self.processes if p.pid in pids}
+        )
+        simulated = isinstance(self.source, SimulatedSource)
+        settle = None if simulated else MEMORY_SETTLE_SECONDS
+        with audit_origin("tui", self._audit_selection()):
+            results, report = measure_reclaim(kill, rss, settle)
         success = sum(1 for _, ok, _ in results if ok)
-        self.call_from_thread(self._on_kill_complete, success, len(results))
+        if self.recorder:
+            self.call_from_thread(
+                self.recorder.record,
+                "kill",
+                pids=pids,
+                force=force,
+                via_manager=via_manager,
+                signal=sig.name if sig else None,
+                escalate=escalate,
+                results=[
+                    {"pid": pid, "ok": ok, "message": msg} for pid, ok, msg in results
+                ],
+            )
+        self.call_from_thread(
+            self._on_kill_complete, success, len(results), report, sig
+        )
 
-    def _on_kill_complete(self, success: int, total: int) -> None:
+    def _audit_selection(self) -> str:
+        """Describe what the processes were picked from, for the audit log.
+
+        Returns:
+            str: E.g. "view=orphans search=node".
+        """
+        parts = [f"view={self.current_view}"]
+        if self.name_filter:
+            parts.append(f"search={self.name_filter}")
+        if self.cwd_filter:
+            parts.append(f"cwd={self.cwd_filter}")
+        return " ".join(parts)
+
+    def _show_kill_progress(self, results: list[KillResult], left: float) -> None:
+        """Show the state of each PID while an escalating kill waits."""
+        states = ", ".join(
+            t("tui-kill-state", pid=r.pid, state=t(f"kill-outcome-{r.outcome}"))
+            for r in results
+        )
+        progress = t("tui-kill-progress", seconds=f"{left:.0f}", states=states)
+        self.query_one("#status-bar", Static).update(progress)
+
+    def _on_kill_complete(
+        self,
+        success: int,
+        total: int,
+        report: ReclaimReport | None = None,
+        sig: signal.Signals | None = None,
+    ) -> None:
         """Handle kill completion (called from main thread)."""
-        self.notify(f"Killed {success}/{total} processes")
+        if sig is not None:
+            name = sig.name
+            self.notify(t("tui-signaled", signal=name, success=success, total=total))
+        else:
+            self.notify(t("tui-killed", success=success, total=total))
+        if report and report.ratio is not None and report.actual_mb is not None:
+            self.notify(
+                t(
+                    "tui-reclaim",
+                    actual=f"{report.actual_mb:.1f}",
+                    estimated=f"{report.estimated_mb:.1f}",
+                    percent=f"{report.ratio * 100:.0f}",
+                )
+            )
+        self.history.push("kill")
+        self.selected_pids.clear()
+        self.refresh_data()
+
+    def action_undo(self) -> None:
+        """Undo the last view, filter, sort or selection change."""
+        entry = self.history.pop_undoable()
+        if entry is None or entry.before is None:
+            self.notify(t("tui-undo-empty"), severity="warning")
+            return
+        self._restore_state(entry.before)
+        self.notify(t("tui-undo", action=entry.label.replace("_", " ")))
+
+    def _queue(self, kind: QueuedKind) -> None:
+        """Queue an action on the selection, or the process at the cursor.
+
+        The selection is cleared, ready for picking the next processes.
+        """
+        procs = [p for p in self.processes if p.pid in self.selected_pids]
+        if not procs and (proc := self._get_process_at_cursor()):
+            procs = [proc]
+        if not procs:
+            self.notify(t("tui-none-selected"), severity="warning")
+            return
+        if kind is QueuedKind.FORCE_KILL:
+            try:
+                risks = data_loss_targets(procs)
+            except ProccleanError as e:
+                self.notify(e.message, severity="error")
+                return
+            if risks:
+                self._refuse_data_loss(risks)
+                return
+        self.queue.add(kind, procs)
+        self.selected_pids.clear()
+        action = t(f"queue-action-{kind}")
+        self.notify(t("tui-queue-added", action=action, count=len(procs)))
+        self.update_table()
+
+    def action_queue_kill(self) -> None:
+        """Queue SIGTERM for the selected processes."""
+        self._queue(QueuedKind.KILL)
+
+    def action_queue_force_kill(self) -> None:
+        """Queue SIGKILL for the selected processes."""
+        self._queue(QueuedKind.FORCE_KILL)
+
+    def action_queue_suspend(self) -> None:
+        """Queue SIGSTOP for the selected processes."""
+        self._queue(QueuedKind.SUSPEND)
+
+    def action_queue_renice(self) -> None:
+        """Queue lowering the priority of the selected processes."""
+        self._queue(QueuedKind.RENICE)
+
+    def action_review_queue(self) -> None:
+        """Review the queued actions and run them all (after confirmation)."""
+        if not self.queue:
+            self.notify(t("tui-queue-empty"), severity="warning")
+            return
+        actions = self.queue.actions
+        kills = {a.pid for a in actions if a.kind.kills}
+        try:
+            risks = data_loss_targets([p for p in self.processes if p.pid in kills])
+        except ProccleanError as e:
+            self.notify(e.message, severity="error")
+            return
+
+        def handle_confirm(confirmed: bool | None) -> None:
+            if confirmed:
+                self._execute_queue(actions)
+
+        screen = ReviewQueueScreen(actions, self.processes, data_loss=risks)
+        self.push_screen(screen, handle_confirm)
+
+    @work(thread=True)
+    def _execute_queue(self, actions: list[QueuedAction]) -> None:
+        """Run reviewed actions in a background thread.
+
+        Renices and suspends go first, then kills, children before parents.
+        """
+        by_kind = {
+            kind: [a.pid for a in actions if a.kind is kind] for kind in QueuedKind
+        }
+        results = [
+            (pid, *renice_process(pid, QUEUE_NICE, self.source))
+            for pid in by_kind[QueuedKind.RENICE]
+        ]
+        with audit_origin("tui", "queue"):
+            if suspend := by_kind[QueuedKind.SUSPEND]:
+                results += kill_processes(
+                    suspend, source=self.source, sig=signal.SIGSTOP
+                )
+            for kind in (QueuedKind.KILL, QueuedKind.FORCE_KILL):
+                if pids := kill_order(self.processes, by_kind[kind]):
+                    force = kind is QueuedKind.FORCE_KILL
+                    results += kill_processes(pids, force=force, source=self.source)
+        success = sum(1 for _, ok, _ in results if ok)
+        if self.recorder:
+            self.call_from_thread(
+                self.recorder.record,
+                "queue",
+                actions=[{"kind": str(a.kind), "pid": a.pid} for a in actions],
+                results=[
+                    {"pid": pid, "ok": ok, "message": msg} for pid, ok, msg in results
+                ],
+            )
+        self.call_from_thread(self._on_queue_complete, success, len(results))
+
+    def _on_queue_complete(self, success: int, total: int) -> None:
+        """Handle completion of a queued batch (called from main thread)."""
+        self.notify(t("tui-queue-done", success=success, total=total))
+        self.history.push("queue")
+        self.queue.clear()
+        self.refresh_data()
+
+    def action_restart_selected(self) -> None:
+        """Restart the selection, or the process at the cursor.
+
+        Supervised processes restart through their manager; others are
+        killed and started again with the same command line, working
+        directory and environment. Respawns that may take SIGKILL are
+        refused for processes that risk data loss.
+        """
+        procs = [p for p in self.processes if p.pid in self.selected_pids]
+        if not procs and (proc := self._get_process_at_cursor()):
+            procs = [proc]
+        if not procs:
+            self.notify(t("tui-none-selected"), severity="warning")
+            return
+        plans = plan_restarts(procs, self.source)
+        killed = [
+            p.proc
+            for p in plans
+            if p.method is not RestartMethod.MANAGER and not p.error
+        ]
+        try:
+            risks = data_loss_targets(killed)
+        except ProccleanError as e:
+            self.notify(e.message, severity="error")
+            return
+        if risks:
+            self._refuse_data_loss(risks)
+            return
+
+        def handle_confirm(confirmed: bool | None) -> None:
+            if confirmed:
+                self._execute_restart(plans)
+
+        self.push_screen(ConfirmRestartScreen(plans, self.redact), handle_confirm)
+
+    @work(thread=True)
+    def _execute_restart(self, plans: list[RestartPlan]) -> None:
+        """Restart processes in a background thread."""
+
+        def progress(results: list[KillResult], left: float) -> None:
+            self.call_from_thread(self._show_kill_progress, results, left)
+
+        with audit_origin("tui", self._audit_selection()):
+            results = restart_processes(
+                plans, self.source, ESCALATE_TIMEOUT_SECONDS, progress
+            )
+        if self.recorder:
+            self.call_from_thread(
+                self.recorder.record,
+                "restart",
+                pids=[p.proc.pid for p in plans],
+                results=[r.to_dict() for r in results],
+            )
+        self.call_from_thread(self._on_restart_complete, results)
+
+    def _on_restart_complete(self, results: list[RestartResult]) -> None:
+        """Handle restart completion (called from main thread)."""
+        for result in results:
+            if not result.ok:
+                self.notify(result.message, severity="warning")
+        success = sum(1 for r in results if r.ok)
+        self.notify(t("tui-restarted", success=success, total=len(results)))
+        self.history.push("restart")
         self.selected_pids.clear()
         self.refresh_data()
 
@@ -0,0 +1,164 @@
+"""Memory history chart widgets."""
+
+import importlib.util
+import os
+import re
+import sys
+from collections.abc import Sequence
+from functools import cache
+
+from textual.widget import Widget
+from textual.widgets import Sparkline
+
+# Opt-in switch for terminal graphics (requires the `graphics` extra)
+GRAPHICS_ENV = "PROCCLEAN_GRAPHICS"
+HISTORY_SIZE = 60  # Samples kept for the chart (5 minutes at 5s refresh)
+
+# Terminals known to speak the kitty graphics protocol or inline images
+GRAPHICS_TERM_PROGRAMS = {"iTerm.app", "WezTerm", "ghostty"}
+# TERM prefixes of terminals that draw sixel graphics out of the box
+SIXEL_TERMS = ("foot", "mlterm", "contour", "yaft")
+DA1_QUERY = b"\x1b[c"  # Primary device attributes request
+DA1_RE = re.compile(r"\x1b\[\?([\d;]*)c")
+DA1_SIXEL = 4  # Device attribute of sixel graphics
+DA1_TIMEOUT = 0.2  # Seconds to wait for the terminal to answer
+
+
+def parse_da1(response: str) -> set[int]:
+    """Parse a primary device attributes answer, e.g. ``ESC[?62;4;22c``.
+
+    Returns:
+        The attributes after the leading device class; empty if
+        ``response`` holds no such answer.
+    """
+    match = DA1_RE.search(response)
+    if match is None:
+        return set()
+    return {int(f) for f in match.group(1).split(";")[1:] if f}
+
+
+@cache
+def query_da1() -> str:
+    """Ask the terminal for its primary device attributes, once.
+
+    The answer arrives as input, so this has to run before the TUI starts
+    reading the terminal. Input left over when it ends, such as a late
+    answer, is discarded rather than read by the TUI as keys.
+
+    Returns:
+        The raw answer, or "" off POSIX, when stdin or stdout is not a
+        terminal, or without an answer in time.
+    """
+    try:
+        import select  # noqa: PLC0415
+        import termios  # noqa: PLC0415 - POSIX only, as is tty
+        import tty  # noqa: PLC0415
+    except ImportError:
+        return ""
+    if not (sys.stdin.isatty() and sys.stdout.isatty()):
+        return ""
+    try:
+        fd = os.open("/dev/tty", os.O_RDWR | os.O_NOCTTY)
+    except OSError:
+        return ""
+    answer = b""
+    try:
+        saved = termios.tcgetattr(fd)
+        tty.setcbreak(fd)  # No echo, no line buffering
+        try:
+            os.write(fd, DA1_QUERY)
+            while not answer.endswith(b"c") and select.select(
+                [fd], [], [], DA1_TIMEOUT
+            )[0]:
+                answer += os.read(fd, 64)
+        finally:
+            termios.tcsetattr(fd, termios.TCSAFLUSH, saved)
+    except termios.error:
+        pass
+    finally:
+        os.close(fd)
+    return answer.decode(errors="replace")
+
+
+def terminal_supports_images() -> bool:
+    """Check whether the terminal advertises kitty or inline image support.
+
+    Returns:
+        True for kitty, iTerm2, WezTerm and Ghostty, otherwise False.
+    """
+    if "KITTY_WINDOW_ID" in os.environ or "kitty" in os.environ.get("TERM", ""):
+        return True
+    return os.environ.get("TERM_PROGRAM", "") in GRAPHICS_TERM_PROGRAMS
+
+
+def terminal_supports_sixel() -> bool:
+    """Check whether the terminal draws sixel graphics.
+
+    Known sixel terminals are recognized by ``TERM``; others, like xterm
+    started with ``-ti vt340``, by attribute 4 in their DA1 answer.
+
+    Returns:
+        True if the terminal speaks sixel, otherwise False.
+    """
+    if os.environ.get("TERM", "").startswith(SIXEL_TERMS):
+        return True
+    return DA1_SIXEL in parse_da1(query_da1())
+
+
+def terminal_supports_graphics() -> bool:
+    """Check whether the terminal advertises graphics protocol support.
+
+    Returns:
+        True for kitty, iTerm2, WezTerm, Ghostty and sixel terminals,
+        otherwise False.
+    """
+    return terminal_supports_images() or terminal_supports_sixel()
+
+
+def graphics_enabled() -> bool:
+    """Check whether the graphics chart should be used.
+
+    Requires the opt-in environment flag, a capable terminal and the
+    ``textual-image`` package.
+
+    Returns:
+        True if the graphics chart can be rendered, otherwise False.
+    """
+    if os.environ.get(GRAPHICS_ENV, "").lower() not in {"1", "true", "yes"}:
+        return False
+    if importlib.util.find_spec("textual_image") is None:
+        return False
+    return terminal_supports_graphics()  # Last: it may query the terminal
+
+
+class MemorySparkline(Sparkline):
+    """Sparkline fallback for the memory history chart."""
+
+    def set_history(self, history: Sequence[float]) -> None:
+        """Replace the plotted memory history.
+
+        Args:
+            history: Memory usage percentages, oldest first.
+        """
+        self.data = list(history)
+
+
+def create_memory_chart(graphics: bool | None = None) -> Widget:
+    """Create the best available memory history chart.
+
+    Args:
+        graphics: Result of ``graphics_enabled``, if already checked.
+
+    Returns:
+        A graphics chart on capable terminals when enabled (sixel on
+        terminals without kitty or inline images), otherwise a sparkline.
+    """
+    if graphics is None:
+        graphics = graphics_enabled()
+    if graphics:
+        from .graphics import MemoryGraphicsChart, MemorySixelChart  # noqa: PLC0415
+
+        if terminal_supports_images():
+            return MemoryGraphicsChart(id="mem-chart")
+        return MemorySixelChart(id="mem-chart")
+    return MemorySparkline([], summary_function=max, id="mem-chart")
@@ -0,0 +1,164 @@
+"""Live preview for designing a process list, ``procclean fmt --interactive``."""
+
+from collections.abc import Callable
+from typing import ClassVar
+
+from rich.text import Text
+from textual import on
+from textual.app import App, ComposeResult
+from textual.binding import Binding
+from textual.containers import Horizontal, ScrollableContainer
+from textual.widgets import Footer, Header, OptionList, Static
+from textual.widgets.option_list import Option
+
+from procclean.core import ProccleanError, ProcessInfo
+from procclean.formatters import ColumnChoice, ListDesign
+from procclean.i18n import t
+
+
+def _label(choice: ColumnChoice) -> Text:
+    """Describe a column in the list: shown or not, key, width and unit.
+
+    Returns:
+        Text: The label, plain text so keys are never taken for markup.
+    """
+    label = Text(f"[{'x' if choice.shown else ' '}] {choice.key}")
+    details = [choice.unit or "", str(choice.width) if choice.width else ""]
+    if extra := " ".join(filter(None, details)):
+        label.append(f"  {extra}", style="dim")
+    return label
+
+
+class ListDesignerApp(App[str | None]):
+    """Toggles columns, widths, units and the format over a live preview.
+
+    Exits with the equivalent ``procclean list`` command, or None when
+    cancelled.
+    """
+
+    CSS_PATH = "app.tcss"
+    TITLE = "procclean fmt"
+
+    BINDINGS: ClassVar = [
+        Binding("space", "toggle", t("key-designer-toggle")),
+        Binding("K", "move(-1)", t("key-designer-up")),
+        Binding("J", "move(1)", t("key-designer-down")),
+        Binding("w", "cycle_width", t("key-designer-width")),
+        Binding("u", "cycle_unit", t("key-designer-unit")),
+        Binding("f", "cycle_format", t("key-designer-format")),
+        Binding("s", "save", t("key-designer-save")),
+        Binding("q", "done", t("key-designer-done")),
+        Binding("escape", "cancel", t("key-cancel")),
+    ]
+
+    def __init__(
+        self,
+        design: ListDesign,
+        procs: list[ProcessInfo],
+        save: Callable[[list[str]], list[ProccleanError]] | None = None,
+    ) -> None:
+        """Initialize the designer.
+
+        Args:
+            design: Design to start from.
+            procs: Sample processes to preview.
+            save: Writes the shown columns to ``[defaults] columns``,
+                returning what is wrong if it could not; None disables saving.
+        """
+        super().__init__()
+        self.design = design
+        self.procs = procs
+        self.save = save
+
+    def compose(self) -> ComposeResult:
+        """Compose the column list, the preview and the command line.
+
+        Yields:
+            ComposeResult: Child widgets.
+        """
+        yield Header()
+        with Horizontal(id="designer-body"):
+            yield OptionList(id="designer-columns")
+            with ScrollableContainer(id="designer-scroll"):
+                yield Static(id="designer-preview")
+        yield Static(id="designer-command")
+        yield Footer()
+
+    def on_mount(self) -> None:
+        """Show the starting design."""
+        self._update(0)
+
+    @property
+    def _cursor(self) -> int:
+        return self.query_one("#designer-columns", OptionList).highlighted or 0
+
+    def _update(self, cursor: int | None = None) -> None:
+        """Redraw the column list, preview and command after a change.
+
+        Args:
+            cursor: Column to highlight; defaults to the highlighted one.
+        """
+        cursor = self._cursor if cursor is None else cursor
+        options = self.query_one("#designer-columns", OptionList)
+        options.clear_options()
+        options.add_options(
+            Option(_label(choice), id=choice.key) for choice in self.design.columns
+        )
+        options.highlighted = cursor
+        preview = Text(self.design.render(self.procs), no_wrap=True)
+        self.query_one("#designer-preview", Static).update(preview)
+        command = Text(self.design.command())
+        self.query_one("#designer-command", Static).update(command)
+
+    @on(OptionList.OptionSelected, "#designer-columns")
+    def _on_selected(self, event: OptionList.OptionSelected) -> None:
+        """Toggle a column picked with Enter."""
+        self.design.toggle(event.option_index)
+        self._update(event.option_index)
+
+    def action_toggle(self) -> None:
+        """Show or hide the highlighted column."""
+        self.design.toggle(self._cursor)
+        self._update()
+
+    def action_move(self, offset: int) -> None:
+        """Move the highlighted column up or down the order."""
+        self._update(self.design.move(self._cursor, offset))
+
+    def action_cycle_width(self) -> None:
+        """Switch the highlighted column to its next width."""
+        self.design.cycle_width(self._cursor)
+        self._update()
+
+    def action_cycle_unit(self) -> None:
+        """Switch the highlighted memory column to its next unit."""
+        if not self.design.cycle_unit(self._cursor):
+            self.notify(t("tui-designer-no-unit"), severity="warning")
+            return
+        self._update()
+
+    def action_cycle_format(self) -> None:
+        """Switch to the next output format."""
+        self.design.cycle_format()
+        self.notify(t("tui-designer-format", format=self.design.fmt))
+        self._update()
+
+    def action_save(self) -> None:
+        """Write the shown columns to the config file."""
+        if self.save is None:
+            return
+        if not self.design.entries:
+            self.notify(t("tui-designer-empty"), severity="warning")
+            return
+        if problems := self.save(self.design.entries):
+            self.notify(problems[0].message, severity="error")
+            return
+        self.notify(t("tui-designer-saved"))
+
+    def action_done(self) -> None:
+        """Quit with the command spelling the design."""
+        self.exit(self.design.command())
+
+    def action_cancel(self) -> None:
+        """Quit without a command."""
+        self.exit(None)
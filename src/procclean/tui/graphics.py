@@ -0,0 +1,71 @@
+"""Terminal graphics (kitty/sixel) memory chart.
+
+Only imported when the ``graphics`` extra is installed and enabled.
+"""
+
+from collections.abc import Sequence
+
+from PIL import Image as PILImage
+from PIL import ImageDraw
+from textual_image.widget import (  # ty: ignore[unresolved-import]
+    Image,
+    SixelImage,
+)
+
+# Chart bitmap size in pixels; the widget scales it to its cell size
+CHART_WIDTH_PX = 480
+CHART_HEIGHT_PX = 48
+LINE_COLOR = (152, 246, 65)
+FILL_COLOR = (152, 246, 65, 64)
+
+
+def render_chart_image(history: Sequence[float]) -> PILImage.Image:
+    """Render memory usage history as a filled line chart.
+
+    Args:
+        history: Memory usage percentages (0-100), oldest first.
+
+    Returns:
+        An RGBA image of the chart.
+    """
+    img = PILImage.new("RGBA", (CHART_WIDTH_PX, CHART_HEIGHT_PX), (0, 0, 0, 0))
+    if len(history) < 2:  # noqa: PLR2004
+        return img
+    step = CHART_WIDTH_PX / (len(history) - 1)
+    points = [
+        (i * step, CHART_HEIGHT_PX - 1 - (v / 100) * (CHART_HEIGHT_PX - 1))
+        for i, v in enumerate(history)
+    ]
+    draw = ImageDraw.Draw(img, "RGBA")
+    draw.polygon(
+        [(0, CHART_HEIGHT_PX), *points, (CHART_WIDTH_PX, CHART_HEIGHT_PX)],
+        fill=FILL_COLOR,
+    )
+    draw.line(points, fill=LINE_COLOR, width=2)
+    return img
+
+
+class MemoryChartMixin:
+    """Plot memory history into the ``image`` of a textual-image widget."""
+
+    image: PILImage.Image | None
+
+    def set_history(self, history: Sequence[float]) -> None:
+        """Replace the plotted memory history.
+
+        Args:
+            history: Memory usage percentages, oldest first.
+        """
+        self.image = render_chart_image(history)
+
+
+class MemoryGraphicsChart(MemoryChartMixin, Image):
+    """Memory history chart rendered with terminal graphics protocols."""
+
+
+class MemorySixelChart(MemoryChartMixin, SixelImage):
+    """Memory history chart always drawn with sixel graphics.
+
+    For terminals found to speak sixel only through their device
+    attributes, which the automatic choice of ``Image`` may miss.
+    """
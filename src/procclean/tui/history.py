@@ -0,0 +1,82 @@
+"""In-session action history for undoing view, filter and selection changes."""
+
+from collections import deque
+from dataclasses import dataclass
+
+# Oldest entries are dropped beyond this many actions
+HISTORY_LIMIT = 100
+
+
+@dataclass(frozen=True)
+class UiState:
+    """The undoable part of the TUI state."""
+
+    view: str
+    sort_key: str
+    sort_reverse: bool
+    cwd_filter: str | None
+    name_filter: str | None
+    selected: frozenset[int]
+    sort_secondary: str | None = None
+
+
+@dataclass(frozen=True)
+class HistoryEntry:
+    """One action, with the state to restore when it is undone.
+
+    Destructive actions (kills) have no ``before`` state: they are listed in
+    the history but cannot be undone.
+    """
+
+    label: str
+    before: UiState | None = None
+
+    @property
+    def undoable(self) -> bool:
+        """Check if undoing this action restores a previous state."""
+        return self.before is not None
+
+
+class ActionHistory:
+    """Bounded log of actions taken in the TUI."""
+
+    def __init__(self, limit: int = HISTORY_LIMIT) -> None:
+        """Initialize an empty history.
+
+        Args:
+            limit: Maximum number of entries kept.
+        """
+        self._entries: deque[HistoryEntry] = deque(maxlen=limit)
+
+    def __len__(self) -> int:
+        """Return the number of entries."""
+        return len(self._entries)
+
+    @property
+    def entries(self) -> list[HistoryEntry]:
+        """Entries from oldest to newest."""
+        return list(self._entries)
+
+    def push(self, label: str, before: UiState | None = None) -> None:
+        """Append an action.
+
+        Args:
+            label: Human-readable action name.
+            before: State prior to the action; None for destructive actions.
+        """
+        self._entries.append(HistoryEntry(label, before))
+
+    def pop_undoable(self) -> HistoryEntry | None:
+        """Remove and return the most recent undoable action.
+
+        Destructive actions after it are dropped along the way, since the
+        state from before them cannot be restored.
+
+        Returns:
+            HistoryEntry | None: The action to undo, or None if there is none.
+        """
+        while self._entries:
+            entry = self._entries.pop()
+            if entry.undoable:
+                return entry
+        return None
@@ -0,0 +1,122 @@
+"""Pending TUI actions, reviewed and run as one batch."""
+
+from dataclasses import dataclass
+from enum import StrEnum
+from typing import Self
+
+from procclean.core import ProcessInfo
+
+QUEUE_NICE = 10  # Nice value queued renices set, as plain `renice` would
+
+
+class QueuedKind(StrEnum):
+    """What to do to a queued process."""
+
+    KILL = "kill"  # SIGTERM
+    FORCE_KILL = "force-kill"  # SIGKILL
+    SUSPEND = "suspend"  # SIGSTOP
+    RENICE = "renice"  # To QUEUE_NICE
+
+    @property
+    def kills(self) -> bool:
+        """Whether the action ends the process, freeing its memory."""
+        return self in {QueuedKind.KILL, QueuedKind.FORCE_KILL}
+
+
+# Selection cell of a queued process, by action
+QUEUE_MARKERS = {
+    QueuedKind.KILL: "[k]",
+    QueuedKind.FORCE_KILL: "[K]",
+    QueuedKind.SUSPEND: "[z]",
+    QueuedKind.RENICE: "[n]",
+}
+
+
+@dataclass(frozen=True)
+class QueuedAction:
+    """One pending action on one process."""
+
+    kind: QueuedKind
+    pid: int
+    name: str
+    rss_mb: float
+    create_time: float  # Tells the process apart from a later one reusing its PID
+
+    @classmethod
+    def of(cls, kind: QueuedKind, proc: ProcessInfo) -> Self:
+        """Queue an action on a process.
+
+        Returns:
+            QueuedAction: The action, remembering the process it was meant for.
+        """
+        return cls(kind, proc.pid, proc.name, proc.rss_mb, proc.create_time)
+
+
+class ActionQueue:
+    """Actions waiting to be reviewed and run, at most one per process.
+
+    Queuing another action for a process replaces the earlier one; the
+    queue keeps the order processes were first queued in.
+    """
+
+    def __init__(self) -> None:
+        """Initialize an empty queue."""
+        self._actions: dict[int, QueuedAction] = {}
+
+    def __len__(self) -> int:
+        """Return the number of queued actions."""
+        return len(self._actions)
+
+    @property
+    def actions(self) -> list[QueuedAction]:
+        """Queued actions, in queuing order."""
+        return list(self._actions.values())
+
+    def get(self, pid: int) -> QueuedAction | None:
+        """Get the action queued for a PID.
+
+        Returns:
+            QueuedAction | None: The action, or None if nothing is queued.
+        """
+        return self._actions.get(pid)
+
+    def add(self, kind: QueuedKind, procs: list[ProcessInfo]) -> None:
+        """Queue an action on processes, replacing what was queued for them.
+
+        Args:
+            kind: Action to queue.
+            procs: Processes to queue it for.
+        """
+        for proc in procs:
+            self._actions[proc.pid] = QueuedAction.of(kind, proc)
+
+    def pids(self, kind: QueuedKind) -> list[int]:
+        """Get the PIDs an action is queued for.
+
+        Returns:
+            list[int]: PIDs in queuing order.
+        """
+        return [a.pid for a in self._actions.values() if a.kind is kind]
+
+    def prune(self, procs: list[ProcessInfo]) -> int:
+        """Drop actions on processes that exited, or whose PID was reused.
+
+        Args:
+            procs: Processes currently listed.
+
+        Returns:
+            int: Number of actions dropped.
+        """
+        alive = {(p.pid, p.create_time) for p in procs}
+        gone = [
+            pid
+            for pid, action in self._actions.items()
+            if (pid, action.create_time) not in alive
+        ]
+        for pid in gone:
+            del self._actions[pid]
+        return len(gone)
+
+    def clear(self) -> None:
+        """Drop every queued action."""
+        self._actions.clear()
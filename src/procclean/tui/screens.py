@@ -1,36 +1,97 @@
 """TUI modal screens."""
 
-from typing import ClassVar
+import shlex
+import signal
+from datetime import datetime
+from typing import Any, ClassVar
 
+from rich.text import Text
 from textual import on
 from textual.app import ComposeResult
 from textual.binding import Binding
-from textual.containers import Container, Horizontal, Vertical
+from textual.containers import Container, Horizontal, Vertical, VerticalScroll
 from textual.screen import ModalScreen
-from textual.widgets import Button, Label
+from textual.widgets import Button, Checkbox, Input, Label, Static
 
-from procclean.core import CONFIRM_PREVIEW_LIMIT, ProcessInfo
+from procclean.core import (
+    CONFIRM_PREVIEW_LIMIT,
+    DETAIL_LIST_LIMIT,
+    ESCALATE_TIMEOUT_SECONDS,
+    VIA_MANAGER,
+    ProccleanError,
+    ProcessDetails,
+    ProcessInfo,
+    ProcessSource,
+    RestartPlan,
+    Supervisor,
+    build_preset,
+    is_kill_signal,
+    priority_note,
+    save_preset,
+    signal_choices,
+)
+from procclean.i18n import t
 
+from .queue import QueuedAction, QueuedKind
 
-class ConfirmKillScreen(ModalScreen[bool]):
-    """Modal screen to confirm killing processes."""
+
+class ConfirmKillScreen(ModalScreen[bool | str]):
+    """Modal screen to confirm killing processes.
+
+    Dismisses with True/False, or ``VIA_MANAGER`` when supervised targets
+    should be stopped through their manager. ``s`` picks another signal to
+    send instead of killing; the choice is left in ``picked_signal``. ``e``
+    toggles ``escalate``: SIGTERM first, then SIGKILL for processes that
+    outlive ``ESCALATE_TIMEOUT_SECONDS``. Killing ``data_loss`` targets
+    takes a second confirmation and never escalates.
+    """
 
     BINDINGS: ClassVar = [
-        Binding("y", "confirm", "Yes"),
-        Binding("n", "cancel", "No"),
-        Binding("escape", "cancel", "Cancel"),
+        Binding("y", "confirm", t("key-yes")),
+        Binding("n", "cancel", t("key-no")),
+        Binding("m", "via_manager", t("key-via-manager")),
+        Binding("s", "cycle_signal", t("key-signal")),
+        Binding("e", "toggle_escalate", t("key-escalate")),
+        Binding("escape", "cancel", t("key-cancel")),
     ]
 
-    def __init__(self, processes: list[ProcessInfo], force: bool = False) -> None:
+    def __init__(
+        self,
+        processes: list[ProcessInfo],
+        force: bool = False,
+        supervisors: dict[int, Supervisor] | None = None,
+        data_loss: list[ProcessInfo] | None = None,
+    ) -> None:
         """Initialize the confirmation screen.
 
         Args:
             processes: Processes that may be killed if confirmed.
             force: Whether the operation is a force kill.
+            supervisors: Managers that would restart the processes, by PID.
+            data_loss: Targets that risk data loss when killed.
         """
         super().__init__()
         self.processes = processes
         self.force = force
+        self.supervisors = supervisors or {}
+        self.data_loss = data_loss or []
+        self.armed = False  # Confirmed once, with data-loss targets
+        # None kills (SIGTERM, or SIGKILL when forced); the rest are offered
+        self.signals: list[signal.Signals | None] = [
+            None,
+            *(sig for sig in signal_choices() if not is_kill_signal(sig)),
+        ]
+        self.picked_signal: signal.Signals | None = None
+        self.escalate = False  # Only applies to a plain SIGTERM kill
+
+    @property
+    def managed(self) -> bool:
+        """Whether any target can be stopped through its manager.
+
+        Returns:
+            bool: True if a supervisor has a stop command.
+        """
+        return any(s.stop_command for s in self.supervisors.values())
 
     def compose(self) -> ComposeResult:
         """Compose child widgets for the confirmation dialog.
@@ -42,38 +103,569 @@ class ConfirmKillScreen(ModalScreen[bool]):
         Yields:
             Child widgets that make up the confirmation dialog.
         """
-        total_mb = sum(p.rss_mb for p in self.processes)
-        action = "FORCE KILL" if self.force else "Kill"
-
         with Container(id="confirm-dialog"):
-            yield Label(
-                f"{action} {len(self.processes)} process(es)?", id="confirm-title"
-            )
-            yield Label(f"Will free ~{total_mb:.1f} MB", id="confirm-subtitle")
+            yield Label(self._title(), id="confirm-title")
+            yield Label(self._subtitle(), id="confirm-subtitle")
+            yield Label(self._signal_hint(), id="confirm-signal")
             with Vertical(id="process-list-container"):
                 for proc in self.processes[:CONFIRM_PREVIEW_LIMIT]:
-                    yield Label(f"  {proc.pid}: {proc.name} ({proc.rss_mb:.1f} MB)")
+                    item = t(
+                        "confirm-item",
+                        pid=proc.pid,
+                        name=proc.name,
+                        mb=f"{proc.rss_mb:.1f}",
+                    )
+                    yield Label(f"  {item}")
+                    if note := priority_note(proc):
+                        yield Label(f"      {note}", classes="priority")
+                    if proc in self.data_loss:
+                        risk = t("confirm-data-loss")
+                        yield Label(f"      {risk}", classes="priority")
+                    if sup := self.supervisors.get(proc.pid):
+                        yield Label(f"      {sup.describe()}", classes="supervisor")
                 if len(self.processes) > CONFIRM_PREVIEW_LIMIT:
                     remaining = len(self.processes) - CONFIRM_PREVIEW_LIMIT
-                    yield Label(f"  ... and {remaining} more")
+                    yield Label(f"  {t('confirm-more', count=remaining)}")
             with Horizontal(id="confirm-buttons"):
-                yield Button("Yes (y)", id="yes", variant="error")
-                yield Button("No (n)", id="no", variant="primary")
+                yield Button(t("confirm-yes"), id="yes", variant="error")
+                yield Button(t("confirm-no"), id="no", variant="primary")
+                if self.managed:
+                    yield Button(t("confirm-manager"), id="manager", variant="warning")
+
+    def _signal_name(self) -> str:
+        if self.picked_signal is not None:
+            return self.picked_signal.name
+        return "SIGKILL" if self.force else "SIGTERM"
+
+    def _title(self) -> str:
+        if self.picked_signal is not None:
+            action = t("confirm-action-signal", signal=self.picked_signal.name)
+        else:
+            action = t("confirm-action-force" if self.force else "confirm-action-kill")
+        return t("confirm-title", action=action, count=len(self.processes))
+
+    def _subtitle(self) -> str:
+        if self.picked_signal is not None:
+            return t("confirm-subtitle-signal", signal=self.picked_signal.name)
+        total_mb = sum(p.rss_mb for p in self.processes)
+        return t("confirm-subtitle", mb=f"{total_mb:.1f}")
+
+    def _signal_hint(self) -> str:
+        if self.armed:
+            return t("confirm-data-loss-again")
+        if self.escalate:
+            timeout = f"{ESCALATE_TIMEOUT_SECONDS:g}"
+            return t("confirm-signal-escalate", timeout=timeout)
+        return t("confirm-signal", signal=self._signal_name())
+
+    def _update_labels(self) -> None:
+        self.query_one("#confirm-title", Label).update(self._title())
+        self.query_one("#confirm-subtitle", Label).update(self._subtitle())
+        self.query_one("#confirm-signal", Label).update(self._signal_hint())
+
+    def action_cycle_signal(self) -> None:
+        """Pick the next signal to send, then the kill again."""
+        index = self.signals.index(self.picked_signal)
+        self.picked_signal = self.signals[(index + 1) % len(self.signals)]
+        self.escalate = False
+        self._update_labels()
+
+    def action_toggle_escalate(self) -> None:
+        """Toggle falling back to SIGKILL for processes ignoring SIGTERM."""
+        if self.force or self.data_loss:
+            return  # Already SIGKILL, or SIGKILL is refused
+        self.escalate = not self.escalate
+        self.picked_signal = None
+        self._update_labels()
 
     def action_confirm(self) -> None:
-        """Confirm killing the selected processes."""
+        """Confirm killing the selected processes.
+
+        With data-loss targets, only a second confirmation kills, unless a
+        signal that does not end them was picked.
+        """
+        ends = self.picked_signal is None or is_kill_signal(self.picked_signal)
+        if self.data_loss and ends and not self.armed:
+            self.armed = True
+            self._update_labels()
+            return
         self.dismiss(True)
 
     def action_cancel(self) -> None:
         """Cancel process killing."""
         self.dismiss(False)
 
+    def action_via_manager(self) -> None:
+        """Stop supervised processes through their manager, if any."""
+        if self.managed:
+            self.dismiss(VIA_MANAGER)
+
     @on(Button.Pressed, "#yes")
     def on_yes(self) -> None:
         """Handle the Yes button being pressed."""
+        self.action_confirm()
+
+    @on(Button.Pressed, "#no")
+    def on_no(self) -> None:
+        """Handle the No button being pressed."""
+        self.dismiss(False)
+
+    @on(Button.Pressed, "#manager")
+    def on_manager(self) -> None:
+        """Handle the Via manager button being pressed."""
+        self.dismiss(VIA_MANAGER)
+
+
+class ReviewQueueScreen(ModalScreen[bool]):
+    """Modal screen to review queued actions and run them as one batch.
+
+    Dismisses with True to run every action, False to keep them queued.
+    Queued kills of ``data_loss`` processes take a second confirmation.
+    """
+
+    BINDINGS: ClassVar = [
+        Binding("y", "confirm", t("key-yes")),
+        Binding("n", "cancel", t("key-no")),
+        Binding("escape", "cancel", t("key-cancel")),
+    ]
+
+    def __init__(
+        self,
+        actions: list[QueuedAction],
+        processes: list[ProcessInfo],
+        data_loss: list[ProcessInfo] | None = None,
+    ) -> None:
+        """Initialize the review screen.
+
+        Args:
+            actions: Queued actions, in queuing order.
+            processes: Listed processes, for priority warnings on targets.
+            data_loss: Processes queued to be killed that risk data loss.
+        """
+        super().__init__()
+        self.actions = actions
+        self.processes = {p.pid: p for p in processes}
+        self.data_loss = {p.pid for p in data_loss or ()}
+        self.armed = False  # Confirmed once, with data-loss targets
+
+    def _lines(self) -> list[tuple[str, str]]:
+        """Describe the actions, grouped by kind.
+
+        Returns:
+            list[tuple[str, str]]: Each line with its CSS class, if any.
+        """
+        lines = []
+        for kind in QueuedKind:
+            for action in (a for a in self.actions if a.kind is kind):
+                item = t(
+                    "queue-item",
+                    action=t(f"queue-action-{kind}"),
+                    pid=action.pid,
+                    name=action.name,
+                    mb=f"{action.rss_mb:.1f}",
+                )
+                lines.append((f"  {item}", ""))
+                proc = self.processes.get(action.pid)
+                if proc and (note := priority_note(proc)):
+                    lines.append((f"      {note}", "priority"))
+                if action.pid in self.data_loss:
+                    lines.append((f"      {t('confirm-data-loss')}", "priority"))
+        return lines
+
+    def compose(self) -> ComposeResult:
+        """Compose the review dialog.
+
+        Yields:
+            Child widgets that make up the review dialog.
+        """
+        kills = [a for a in self.actions if a.kind.kills]
+        freed = sum(a.rss_mb for a in kills)
+        with Container(id="confirm-dialog"):
+            yield Label(t("queue-title", count=len(self.actions)), id="confirm-title")
+            yield Label(
+                t("queue-subtitle", kills=len(kills), mb=f"{freed:.1f}"),
+                id="confirm-subtitle",
+            )
+            with Vertical(id="process-list-container"):
+                lines = self._lines()
+                for line, classes in lines[:CONFIRM_PREVIEW_LIMIT]:
+                    yield Label(line, classes=classes)
+                if len(lines) > CONFIRM_PREVIEW_LIMIT:
+                    remaining = len(lines) - CONFIRM_PREVIEW_LIMIT
+                    yield Label(f"  {t('confirm-more', count=remaining)}")
+            with Horizontal(id="confirm-buttons"):
+                yield Button(t("queue-run"), id="yes", variant="error")
+                yield Button(t("queue-back"), id="no", variant="primary")
+
+    def action_confirm(self) -> None:
+        """Run the queued actions, after a second confirmation if needed."""
+        if self.data_loss and not self.armed:
+            self.armed = True
+            self.query_one("#confirm-subtitle", Label).update(
+                t("confirm-data-loss-again")
+            )
+            return
+        self.dismiss(True)
+
+    def action_cancel(self) -> None:
+        """Go back, keeping the actions queued."""
+        self.dismiss(False)
+
+    @on(Button.Pressed, "#yes")
+    def on_yes(self) -> None:
+        """Handle the Run button being pressed."""
+        self.action_confirm()
+
+    @on(Button.Pressed, "#no")
+    def on_no(self) -> None:
+        """Handle the Back button being pressed."""
+        self.dismiss(False)
+
+
+class ConfirmRestartScreen(ModalScreen[bool]):
+    """Modal screen showing how each process would be restarted.
+
+    Dismisses with True to restart; processes that cannot be restarted are
+    listed with the reason and skipped.
+    """
+
+    BINDINGS: ClassVar = [
+        Binding("y", "confirm", t("key-yes")),
+        Binding("n", "cancel", t("key-no")),
+        Binding("escape", "cancel", t("key-cancel")),
+    ]
+
+    def __init__(self, plans: list[RestartPlan], redact: bool = True) -> None:
+        """Initialize the restart screen.
+
+        Args:
+            plans: How each target would be restarted.
+            redact: Mask secrets in the commands of respawns.
+        """
+        super().__init__()
+        self.plans = plans
+        self.redact = redact
+
+    def compose(self) -> ComposeResult:
+        """Compose the restart dialog.
+
+        Yields:
+            Child widgets that make up the restart dialog.
+        """
+        with Container(id="confirm-dialog"):
+            yield Label(t("restart-title", count=len(self.plans)), id="confirm-title")
+            with Vertical(id="process-list-container"):
+                for plan in self.plans[:CONFIRM_PREVIEW_LIMIT]:
+                    proc = plan.proc
+                    yield Label(f"  {t('restart-item', pid=proc.pid, name=proc.name)}")
+                    classes = "priority" if plan.error else ""
+                    yield Label(f"      {plan.describe(self.redact)}", classes=classes)
+                if len(self.plans) > CONFIRM_PREVIEW_LIMIT:
+                    remaining = len(self.plans) - CONFIRM_PREVIEW_LIMIT
+                    yield Label(f"  {t('confirm-more', count=remaining)}")
+            with Horizontal(id="confirm-buttons"):
+                yield Button(t("restart-run"), id="yes", variant="error")
+                yield Button(t("confirm-no"), id="no", variant="primary")
+
+    def action_confirm(self) -> None:
+        """Restart the processes."""
+        self.dismiss(True)
+
+    def action_cancel(self) -> None:
+        """Cancel the restart."""
+        self.dismiss(False)
+
+    @on(Button.Pressed, "#yes")
+    def on_yes(self) -> None:
+        """Handle the Restart button being pressed."""
         self.dismiss(True)
 
     @on(Button.Pressed, "#no")
     def on_no(self) -> None:
         """Handle the No button being pressed."""
         self.dismiss(False)
+
+
+class ProcessDetailScreen(ModalScreen[None]):
+    """Modal screen with everything about one process.
+
+    Shows the full command line, start time, memory breakdown, children,
+    listening sockets, open file descriptors and environment. Lists longer
+    than ``DETAIL_LIST_LIMIT`` are cut.
+    """
+
+    BINDINGS: ClassVar = [
+        Binding("escape", "close", t("key-close")),
+        Binding("q", "close", t("key-close")),
+        Binding("enter", "close", t("key-close")),
+    ]
+
+    def __init__(self, details: ProcessDetails) -> None:
+        """Initialize the inspect screen.
+
+        Args:
+            details: Details of the process, collected beforehand.
+        """
+        super().__init__()
+        self.details = details
+
+    def sections(self) -> list[tuple[str, list[str]]]:
+        """Build the sections of the screen.
+
+        Returns:
+            list[tuple[str, list[str]]]: Heading and lines of each section.
+        """
+        d = self.details
+        proc = d.proc
+        started = datetime.fromtimestamp(proc.create_time).astimezone()
+        summary = t(
+            "detail-summary",
+            user=proc.username,
+            ppid=proc.ppid,
+            parent=proc.parent_name,
+            status=proc.status,
+        )
+        return [
+            (
+                t("detail-process"),
+                [
+                    summary,
+                    t("detail-started", time=started.strftime("%Y-%m-%d %H:%M:%S")),
+                    t("detail-cwd", cwd=proc.cwd),
+                ],
+            ),
+            (t("detail-command"), [shlex.join(d.argv)]),
+            (t("detail-memory"), [f"{k}: {mb:.1f}" for k, mb in d.memory.items()]),
+            (
+                t("detail-children"),
+                [f"{p.pid}: {p.name} ({p.rss_mb:.1f} MB)" for p in d.children],
+            ),
+            (t("detail-ports"), list(d.ports)),
+            (
+                t("detail-files"),
+                [f"{fd}: {target}" for fd, target in d.open_files.items()],
+            ),
+            (
+                t("detail-environment"),
+                [f"{name}={value}" for name, value in d.environ.items()],
+            ),
+        ]
+
+    def compose(self) -> ComposeResult:
+        """Compose the inspect screen.
+
+        Values are shown as plain text, so brackets in them are not taken
+        for markup.
+
+        Yields:
+            Child widgets that make up the inspect dialog.
+        """
+        proc = self.details.proc
+        with Container(id="detail-dialog"):
+            yield Label(
+                Text(t("detail-title", pid=proc.pid, name=proc.name)),
+                id="detail-title",
+            )
+            with VerticalScroll(id="detail-body"):
+                for heading, lines in self.sections():
+                    yield Label(heading, classes="detail-heading")
+                    for line in lines[:DETAIL_LIST_LIMIT] or [t("detail-none")]:
+                        yield Static(Text(f"  {line}"))
+                    if len(lines) > DETAIL_LIST_LIMIT:
+                        more = t("detail-more", count=len(lines) - DETAIL_LIST_LIMIT)
+                        yield Static(Text(f"  {more}"))
+            yield Label(t("detail-hint"), id="detail-hint")
+
+    def action_close(self) -> None:
+        """Close the inspect screen."""
+        self.dismiss(None)
+
+
+# Inputs of the filter panel, by the preset rule they set
+PANEL_INPUTS = (
+    ("min_memory", "panel-min-memory"),
+    ("name", "panel-pattern"),
+    ("user", "panel-user"),
+    ("cwd", "panel-cwd"),
+)
+# Checkboxes of the filter panel, by the preset rule they set
+PANEL_FLAGS = (("orphan", "panel-orphan"), ("stale", "panel-stale"))
+
+
+def _rule_text(value: object) -> str:
+    """Show a rule of the config file in an input.
+
+    Returns:
+        str: The value, numbers without a trailing ".0"; "" if unset.
+    """
+    if value is None:
+        return ""
+    return f"{value:g}" if isinstance(value, int | float) else str(value)
+
+
+class FilterPanelScreen(ModalScreen[str | None]):
+    """Modal screen composing filter rules into a saved view.
+
+    Rules are those of ``[presets.NAME]``: minimum memory, name pattern,
+    user, cwd and the orphan and stale flags. The processes of the table
+    that pass them are counted and listed as they change. Saving writes the
+    preset to the config file and dismisses with its name; rules the panel
+    does not show, such as ``cmdline``, are kept when editing a preset.
+    """
+
+    BINDINGS: ClassVar = [
+        Binding("ctrl+s", "save", t("key-panel-save")),
+        Binding("escape", "cancel", t("key-cancel")),
+    ]
+
+    def __init__(
+        self,
+        processes: list[ProcessInfo],
+        source: ProcessSource | None = None,
+        name: str = "",
+        table: dict[str, Any] | None = None,
+    ) -> None:
+        """Initialize the filter panel.
+
+        Args:
+            processes: Processes to match, as loaded in the table.
+            source: Process backend, for rules that read more of a process.
+            name: Name of the view to edit; "" for a new one.
+            table: Rules of the preset to edit, as in the config file.
+        """
+        super().__init__()
+        self.processes = processes
+        self.source = source
+        self.view = name
+        self.table = dict(table or {})
+
+    def compose(self) -> ComposeResult:
+        """Compose the filter panel.
+
+        Yields:
+            Child widgets that make up the filter panel.
+        """
+        with Container(id="panel-dialog"):
+            yield Label(t("panel-title"), id="confirm-title")
+            yield Input(self.view, placeholder=t("panel-view-name"), id="panel-name")
+            for rule, widget_id in PANEL_INPUTS:
+                yield Input(
+                    _rule_text(self.table.get(rule)),
+                    placeholder=t(f"panel-{rule.replace('_', '-')}"),
+                    id=widget_id,
+                )
+            with Horizontal(id="panel-flags"):
+                for rule, widget_id in PANEL_FLAGS:
+                    yield Checkbox(
+                        t(f"panel-{rule}"),
+                        self.table.get(rule) is True,
+                        id=widget_id,
+                    )
+            yield Static(id="panel-result")
+            with Horizontal(id="confirm-buttons"):
+                yield Button(t("panel-save"), id="yes", variant="primary")
+                yield Button(t("panel-cancel"), id="no")
+
+    def on_mount(self) -> None:
+        """Show what the starting rules match."""
+        self._update_result()
+
+    def rules(self) -> dict[str, Any]:
+        """Collect the rules as a ``[presets.NAME]`` table.
+
+        Returns:
+            dict[str, Any]: Rules the panel does not show, then the filled in
+            fields and the checked flags.
+
+        Raises:
+            ValueError: If the minimum memory is not a number.
+        """
+        shown = {rule for rule, _ in PANEL_INPUTS + PANEL_FLAGS}
+        table = {k: v for k, v in self.table.items() if k not in shown}
+        for rule, widget_id in PANEL_INPUTS:
+            if value := self.query_one(f"#{widget_id}", Input).value.strip():
+                table[rule] = float(value) if rule == "min_memory" else value
+        for rule, widget_id in PANEL_FLAGS:
+            if self.query_one(f"#{widget_id}", Checkbox).value:
+                table[rule] = True
+        return table
+
+    def _matches(self) -> list[ProcessInfo]:
+        """Apply the rules to the processes.
+
+        Returns:
+            list[ProcessInfo]: The processes passing all of them.
+
+        Raises:
+            ProccleanError: With ``INVALID_CONFIG`` for a malformed rule.
+            ValueError: If the minimum memory is not a number.
+        """
+        name = self.query_one("#panel-name", Input).value.strip()
+        spec = build_preset(name or "panel", self.rules())
+        return spec.run(list(self.processes), self.source)
+
+    def _update_result(self) -> None:
+        """Count and list the processes passing the rules, or what is wrong."""
+        result = self.query_one("#panel-result", Static)
+        try:
+            matches = self._matches()
+        except ValueError:
+            result.update(Text(t("panel-invalid-memory"), style="red"))
+            return
+        except ProccleanError as e:
+            result.update(Text(e.message, style="red"))
+            return
+        lines = [t("panel-result", count=len(matches), total=len(self.processes))]
+        lines += [
+            f"  {t('confirm-item', pid=p.pid, name=p.name, mb=f'{p.rss_mb:.1f}')}"
+            for p in matches[:CONFIRM_PREVIEW_LIMIT]
+        ]
+        if len(matches) > CONFIRM_PREVIEW_LIMIT:
+            more = len(matches) - CONFIRM_PREVIEW_LIMIT
+            lines.append(f"  {t('confirm-more', count=more)}")
+        result.update(Text("\n".join(lines)))
+
+    @on(Input.Changed)
+    @on(Checkbox.Changed)
+    def on_rule_changed(self) -> None:
+        """Match again as rules are typed or toggled."""
+        self._update_result()
+
+    @on(Input.Submitted)
+    def on_submitted(self) -> None:
+        """Save when Enter is pressed in a field."""
+        self.action_save()
+
+    def action_save(self) -> None:
+        """Write the rules as a preset and dismiss with its name."""
+        name = self.query_one("#panel-name", Input).value.strip()
+        if not name:
+            self.notify(t("panel-no-name"), severity="warning")
+            return
+        try:
+            self._matches()
+        except ValueError:
+            self.notify(t("panel-invalid-memory"), severity="error")
+            return
+        except ProccleanError as e:
+            self.notify(e.message, severity="error")
+            return
+        table = {"description": name, **self.rules()}  # Kept if already set
+        if problems := save_preset(name, table):
+            self.notify(problems[0].message, severity="error")
+            return
+        self.dismiss(name)
+
+    def action_cancel(self) -> None:
+        """Close the panel without saving."""
+        self.dismiss(None)
+
+    @on(Button.Pressed, "#yes")
+    def on_save(self) -> None:
+        """Handle the Save button being pressed."""
+        self.action_save()
+
+    @on(Button.Pressed, "#no")
+    def on_cancel(self) -> None:
+        """Handle the Cancel button being pressed."""
+        self.dismiss(None)
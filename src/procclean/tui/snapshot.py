@@ -0,0 +1,103 @@
+"""Headless rendering of the TUI for snapshot tests and screenshots."""
+
+import io
+from collections.abc import Awaitable, Callable, Sequence
+
+from rich.console import Console
+from textual.app import App
+from textual.pilot import Pilot
+
+# Default terminal size (columns, rows) for headless renders
+SNAPSHOT_SIZE: tuple[int, int] = (120, 30)
+
+type SnapshotSetup = Callable[[Pilot], Awaitable[None]]
+
+
+def export_text(app: App) -> str:
+    """Render the active screen of a running app as plain text.
+
+    Mirrors ``App.export_screenshot`` but drops styles, so the result can be
+    compared line by line.
+
+    Args:
+        app: A running app (e.g. inside ``run_test``).
+
+    Returns:
+        The screen contents, one line per terminal row.
+    """
+    width, height = app.size
+    console = Console(
+        width=width,
+        height=height,
+        file=io.StringIO(),
+        force_terminal=True,
+        color_system="truecolor",
+        record=True,
+        legacy_windows=False,
+        safe_box=False,
+    )
+    console.print(app.screen._compositor.render_update(full=True))  # noqa: SLF001
+    return console.export_text(styles=False)
+
+
+async def _render(
+    app: App,
+    size: tuple[int, int],
+    keys: Sequence[str],
+    setup: SnapshotSetup | None,
+    export: Callable[[App], str],
+) -> str:
+    async with app.run_test(size=size) as pilot:
+        await app.workers.wait_for_complete()
+        await pilot.pause()
+        if setup is not None:
+            await setup(pilot)
+        if keys:
+            await pilot.press(*keys)
+        await app.workers.wait_for_complete()
+        await pilot.pause()
+        return export(app)
+
+
+async def render_to_text(
+    app: App,
+    size: tuple[int, int] = SNAPSHOT_SIZE,
+    keys: Sequence[str] = (),
+    setup: SnapshotSetup | None = None,
+) -> str:
+    """Run an app headlessly and capture its screen as plain text.
+
+    Args:
+        app: App instance to run; it must not be running yet.
+        size: Terminal size as (columns, rows).
+        keys: Keys to press once data has loaded.
+        setup: Optional coroutine run before the keys are pressed.
+
+    Returns:
+        The rendered screen as text.
+    """
+    return await _render(app, size, keys, setup, export_text)
+
+
+async def render_to_svg(
+    app: App,
+    size: tuple[int, int] = SNAPSHOT_SIZE,
+    keys: Sequence[str] = (),
+    setup: SnapshotSetup | None = None,
+    title: str | None = None,
+) -> str:
+    """Run an app headlessly and capture its screen as an SVG.
+
+    Args:
+        app: App instance to run; it must not be running yet.
+        size: Terminal size as (columns, rows).
+        keys: Keys to press once data has loaded.
+        setup: Optional coroutine run before the keys are pressed.
+        title: Window title in the SVG; defaults to the app title.
+
+    Returns:
+        The rendered screen as an SVG document.
+    """
+    return await _render(
+        app, size, keys, setup, lambda a: a.export_screenshot(title=title)
+    )
@@ -1,8 +1,26 @@
 """Shared test fixtures."""
 
+import threading
+import time
+
 import pytest
 
-from procclean.core import ProcessInfo
+from procclean.core import (
+    AUDIT_ENV,
+    CACHE_ENV,
+    CONFIG_ENV,
+    FILTERS,
+    NO_CAPABILITY_WARNING_ENV,
+    SOCKET_ENV,
+    ConfigPreset,
+    FakeProcess,
+    FakeProcessSource,
+    ProcessInfo,
+    SnapshotDaemon,
+    SystemInfo,
+    daemon_process_list,
+    load_config,
+)
 
 # Test constants - expected values in assertions
 # PIDs from sample_processes
@@ -33,7 +51,10 @@ CLI_LIMIT_2 = 2
 CLI_MIN_MEMORY = 50.0
 CLI_HIGH_THRESHOLD = 1000.0
 CLI_HIGH_THRESHOLD_200 = 200.0
+CLI_CPU_THRESHOLD = 30.0
 CLI_TOTAL_GB = 16.0
+STATUS_PERCENT = 80
+EXIT_USAGE = 2
 
 # Filter thresholds
 THRESHOLD_100 = 100.0
@@ -49,6 +70,10 @@ MIN_TABLE_LINES = 3
 TEST_PID_42 = 42
 NAME_MAX_WIDTH = 25
 
+# Terminal widths for responsive layout tests
+WIDTH_NARROW = 60
+WIDTH_WIDE = 200
+
 # Kill results count
 KILL_RESULTS_3 = 3
 
@@ -69,10 +94,149 @@ TEST_PATH_BUILD = "/var/build"
 TEST_PATH_SINGLE = "/var/test"
 TEST_PATH_GLOB = "/var/test/?"
 
+# Fake process source
+FAKE_PID_INIT = 1
+FAKE_PID_SHELL = 100
+FAKE_PID_EDITOR = 101
+FAKE_PID_SERVER = 200
+FAKE_PID_DAEMON = 300
+FAKE_PID_OTHER = 400
+FAKE_PID_MISSING = 999
+
+# PIDs and memory from cleanup_source
+CLEAN_PID_ORPHAN = 500
+CLEAN_PID_STALE = 501
+CLEAN_PID_DEV = 502
+CLEAN_TOTAL_MB = 350.0
+FAKE_USER_COUNT = 4
+FAULT_INTERVAL = 3
+GSD_COLOR_UNIT = "org.gnome.SettingsDaemon.Color.service"
+GSD_COLOR_CGROUP = (
+    f"/user.slice/user-1000.slice/user@1000.service/session.slice/{GSD_COLOR_UNIT}"
+)
+POD_UID = "0f4b2c1e-9a8d-4e3f-b2a1-123456789abc"
+POD_CONTAINER_ID = "c0ffee" + "0" * 58
+POD_CGROUP = (
+    "/kubepods.slice/kubepods-burstable.slice/"
+    f"kubepods-burstable-pod{POD_UID.replace('-', '_')}.slice/"
+    f"cri-containerd-{POD_CONTAINER_ID}.scope"
+)
+
 # Default test PID
 TEST_PID_DEFAULT = 1234
 
 
+@pytest.fixture(autouse=True)
+def config_file(tmp_path, monkeypatch):
+    """Point the config loader at a per-test file that does not exist yet.
+
+    Yields:
+        Path: Location tests may write a config.toml to.
+    """
+    path = tmp_path / "config.toml"
+    monkeypatch.setenv(CONFIG_ENV, str(path))
+    load_config.cache_clear()
+    yield path
+    load_config.cache_clear()
+
+
+@pytest.fixture(autouse=True)
+def no_config_presets():
+    """Drop the presets a test registered from its config file."""
+    yield
+    for spec in FILTERS:
+        if isinstance(spec.apply, ConfigPreset):
+            FILTERS.unregister(spec.name)
+
+
+@pytest.fixture(autouse=True)
+def no_oom_events(tmp_path, monkeypatch):
+    """Hide the host's OOM kills so reports and headers are deterministic."""
+    monkeypatch.setattr("procclean.core.oom.VMSTAT_PATH", tmp_path / "vmstat")
+    monkeypatch.setattr("procclean.core.oom._run", lambda _argv: None)
+
+
+@pytest.fixture(autouse=True)
+def no_desktop_state(monkeypatch):
+    """Hide the host's PipeWire streams and inhibitor locks."""
+    monkeypatch.setattr("procclean.core.media.pipewire_streams", dict)
+    monkeypatch.setattr("procclean.core.inhibit._busctl", lambda *_a, **_kw: None)
+
+
+@pytest.fixture(autouse=True)
+def no_daemon(tmp_path, monkeypatch):
+    """Point clients at a socket no daemon listens on, so they scan themselves.
+
+    Returns:
+        Path: Socket path a test may start a daemon on.
+    """
+    path = tmp_path / "daemon.sock"
+    monkeypatch.setenv(SOCKET_ENV, str(path))
+    return path
+
+
+@pytest.fixture(autouse=True)
+def cache_file(tmp_path, monkeypatch):
+    """Keep the snapshot cache of --max-age in the test directory.
+
+    Returns:
+        Path: Location of the cache file.
+    """
+    path = tmp_path / "snapshot.json"
+    monkeypatch.setenv(CACHE_ENV, str(path))
+    return path
+
+
+@pytest.fixture(autouse=True)
+def no_audit_log(monkeypatch):
+    """Keep kills out of the audit log unless a test asks for one."""
+    monkeypatch.setenv(AUDIT_ENV, "")
+
+
+@pytest.fixture(autouse=True)
+def no_capability_warning(monkeypatch):
+    """Keep startup output independent of how the test machine mounts /proc."""
+    monkeypatch.setenv(NO_CAPABILITY_WARNING_ENV, "1")
+
+
+@pytest.fixture
+def audit_file(tmp_path, monkeypatch):
+    """Log kills to a file in the test directory.
+
+    Returns:
+        Path: Location of the audit log.
+    """
+    path = tmp_path / "actions.jsonl"
+    monkeypatch.setenv(AUDIT_ENV, str(path))
+    return path
+
+
+@pytest.fixture
+def daemon(no_daemon, fake_source):
+    """Serve the fake process table on the test socket.
+
+    Yields:
+        SnapshotDaemon: The running daemon, with its first snapshot taken.
+    """
+    daemon = SnapshotDaemon(no_daemon, interval=60, source=fake_source)
+    thread = threading.Thread(target=daemon.serve)
+    thread.start()
+    deadline = time.monotonic() + 5
+    while daemon_process_list(user=fake_source.user) is None:
+        if time.monotonic() > deadline:
+            break
+        time.sleep(0.01)
+    yield daemon
+    daemon.shutdown()
+    thread.join()
+
+
+@pytest.fixture(autouse=True)
+def no_settle_delay(monkeypatch):
+    """Measure memory right after kills instead of waiting for it to settle."""
+    monkeypatch.setattr("procclean.cli.commands.MEMORY_SETTLE_SECONDS", 0.0)
+
+
 @pytest.fixture
 def make_process():
     """Create ProcessInfo objects with configurable defaults.
@@ -96,6 +260,8 @@ def make_process():
         is_orphan: bool = False,
         in_tmux: bool = False,
         status: str = "running",
+        cwd_deleted: bool = False,
+        argv: list[str] | None = None,
     ) -> ProcessInfo:
         return ProcessInfo(
             pid=pid,
@@ -111,6 +277,8 @@ def make_process():
             is_orphan=is_orphan,
             in_tmux=in_tmux,
             status=status,
+            cwd_deleted=cwd_deleted,
+            argv=argv or [],
         )
 
     return _make
@@ -139,3 +307,91 @@ def sample_processes(make_process):
         make_process(pid=4, name="zsh", rss_mb=50.0, cpu_percent=0.5),
         make_process(pid=5, name="app", rss_mb=800.0, cpu_percent=5.0, is_orphan=True),
     ]
+
+
+@pytest.fixture
+def fake_source():
+    """Deterministic process table for end-to-end core tests.
+
+    Layout: init (1) is the parent of a shell, an editor running in tmux, a
+    server, and a protected system daemon; another user owns one process.
+
+    Returns:
+        FakeProcessSource: Source backed by in-memory process records.
+    """
+    return FakeProcessSource([
+        FakeProcess(1, "systemd", ["/sbin/init"], ppid=0, username="root"),
+        FakeProcess(100, "zsh", ["zsh"], rss_mb=10.0, cwd="/home/user"),
+        FakeProcess(
+            101,
+            "nvim",
+            ["nvim", "main.py"],
+            rss_mb=80.0,
+            cwd="/home/user/proj",
+            in_tmux=True,
+        ),
+        FakeProcess(
+            200,
+            "node",
+            ["node", "server.js"],
+            rss_mb=300.0,
+            cpu_percent=10.0,
+            cwd="/home/user/proj",
+            cwd_deleted=True,
+        ),
+        FakeProcess(
+            300,
+            "gsd-color",
+            ["/usr/lib/gsd-color"],
+            rss_mb=20.0,
+            exe="/usr/lib/gsd-color",
+            cgroup=GSD_COLOR_CGROUP,
+            protected=True,
+        ),
+        FakeProcess(400, "postgres", ["postgres"], rss_mb=500.0, username="other"),
+    ])
+
+
+@pytest.fixture
+def cleanup_source():
+    """Source with one process per cleanup category plus protected ones.
+
+    Returns:
+        FakeProcessSource: 500 is an orphan, 501 runs a deleted binary, 502
+        lost its cwd; the system daemon and the tmux orphan must be spared.
+    """
+    return FakeProcessSource([
+        FakeProcess(600, "kitty", ["kitty"], ppid=0),
+        FakeProcess(CLEAN_PID_ORPHAN, "orphan", ["orphan"], rss_mb=100.0),
+        FakeProcess(
+            CLEAN_PID_STALE, "app", ["app"], ppid=600, rss_mb=200.0, exe_deleted=True
+        ),
+        FakeProcess(
+            CLEAN_PID_DEV, "vite", ["vite"], ppid=600, rss_mb=50.0, cwd_deleted=True
+        ),
+        FakeProcess(
+            503,
+            "gsd-xsettings",
+            ["gsd"],
+            ppid=600,
+            exe="/usr/lib/gsd",
+            exe_deleted=True,
+        ),
+        FakeProcess(504, "vim", ["vim"], in_tmux=True, cwd_deleted=True),
+    ])
+
+
+@pytest.fixture
+def system_info():
+    """Fixed system information for report headers.
+
+    Returns:
+        SystemInfo: Host "build-01", up 1d 2h 3m, load 0.50 0.25 0.12.
+    """
+    return SystemInfo(
+        hostname="build-01",
+        kernel="Linux 6.8.0",
+        uptime_seconds=93780.0,
+        load_avg=(0.5, 0.25, 0.125),
+        timestamp="2026-10-16T12:00:00+00:00",
+    )
@@ -1,14 +1,57 @@
 """Tests for TUI app module."""
 
+import signal
+from dataclasses import asdict, replace
 from unittest.mock import patch
 
 import pytest
-from textual.widgets import OptionList, Static
+from textual.widgets import Checkbox, DataTable, Input, Label, OptionList, Static
 
 from procclean import main
-from procclean.tui import ConfirmKillScreen, ProcessCleanerApp
-
-from .conftest import TEST_PATH_SINGLE
+from procclean.core import (
+    VIA_MANAGER,
+    CoreUsage,
+    FilterRegistry,
+    FilterSpec,
+    KillOutcome,
+    KillResult,
+    ProcessDetails,
+    SessionRecorder,
+    Supervisor,
+    load_config,
+    load_presets,
+    load_session,
+    read_history,
+)
+from procclean.formatters import ListDesign
+from procclean.tui import (
+    SNAPSHOT_SIZE,
+    ConfirmKillScreen,
+    ConfirmRestartScreen,
+    FilterPanelScreen,
+    ListDesignerApp,
+    ProcessCleanerApp,
+    ProcessDetailScreen,
+    ReviewQueueScreen,
+    render_to_svg,
+    render_to_text,
+)
+from procclean.tui.chart import (
+    DA1_SIXEL,
+    GRAPHICS_ENV,
+    MemorySparkline,
+    create_memory_chart,
+    graphics_enabled,
+    parse_da1,
+    query_da1,
+    terminal_supports_graphics,
+    terminal_supports_images,
+    terminal_supports_sixel,
+)
+from procclean.tui.history import ActionHistory, UiState
+from procclean.tui.queue import QUEUE_NICE, ActionQueue, QueuedKind
+
+from .conftest import TEST_PATH_SINGLE, WIDTH_NARROW
 
 
 @pytest.fixture
@@ -20,10 +63,11 @@ def mock_process_data(sample_processes):
 
     """
     with (
-        patch("procclean.tui.app.get_process_list") as mock_get_procs,
+        patch("procclean.tui.app.ProcessCollector.collect") as mock_get_procs,
         patch("procclean.tui.app.get_memory_summary") as mock_mem,
         patch("procclean.tui.app.find_similar_processes") as mock_find,
         patch("procclean.tui.app.kill_processes") as mock_kill,
+        patch("procclean.tui.app.resolve_supervisors", return_value={}),
     ):
         mock_get_procs.return_value = sample_processes
         mock_mem.return_value = {
@@ -33,6 +77,9 @@ def mock_process_data(sample_processes):
             "percent": 50.0,
             "swap_used_gb": 1.0,
             "swap_total_gb": 4.0,
+            "load_avg": (0.5, 0.25, 0.125),
+            "cpu_count": 4,
+            "uptime_seconds": 93780.0,
         }
         mock_find.return_value = {"python": sample_processes[:2]}
         mock_kill.return_value = []
@@ -44,6 +91,28 @@ def mock_process_data(sample_processes):
         }
 
 
+@pytest.fixture
+def snapshot_app(fake_source):
+    """App wired to the fake process source with fixed memory stats.
+
+    Yields:
+        ProcessCleanerApp: App ready for headless rendering.
+    """
+    with patch("procclean.tui.app.get_memory_summary") as mock_mem:
+        mock_mem.return_value = {
+            "total_gb": 16.0,
+            "used_gb": 8.0,
+            "free_gb": 8.0,
+            "percent": 50.0,
+            "swap_used_gb": 1.0,
+            "swap_total_gb": 4.0,
+            "load_avg": (0.5, 0.25, 0.125),
+            "cpu_count": 4,
+            "uptime_seconds": 93780.0,
+        }
+        yield ProcessCleanerApp(source=fake_source)
+
+
 @pytest.fixture
 def many_processes(make_process):
     """Create 15 processes for testing >10 display.
@@ -125,6 +194,21 @@ class TestProcessCleanerApp:
             await pilot.press("g")
             assert app.current_view == "groups"
 
+    @pytest.mark.asyncio
+    async def test_show_duplicates_view(self, mock_process_data, sample_processes):
+        """Should list only processes running the same command in one cwd."""
+        first = replace(sample_processes[0], argv=["python", "app.py"])
+        twin = replace(first, pid=9999, create_time=1.0)
+        procs = [first, *sample_processes[1:], twin]
+        mock_process_data["get_procs"].return_value = procs
+        app = ProcessCleanerApp()
+        async with app.run_test() as pilot:
+            await app.workers.wait_for_complete()
+            await pilot.press("D")
+            assert app.current_view == "duplicates"
+            pids = {p.pid for p in app._filter_by_view()}
+            assert pids == {sample_processes[0].pid, 9999}
+
     @pytest.mark.asyncio
     async def test_sort_by_memory(self, mock_process_data):
         """Should sort by memory when '1' pressed."""
@@ -187,6 +271,19 @@ class TestProcessCleanerApp:
             assert app.sort_key == "memory"
             assert app.sort_reverse is False  # Should toggle
 
+    @pytest.mark.asyncio
+    async def test_cycle_secondary_sort(self, mock_process_data):
+        """Should pick a secondary sort key other than the primary with '6'."""
+        app = ProcessCleanerApp()
+        async with app.run_test() as pilot:
+            assert app.sort_secondary is None
+            await pilot.press("6")
+            assert app.sort_secondary == "cpu"
+            assert app._sort_keys() == ["memory", "cpu"]
+            for _ in range(4):
+                await pilot.press("6")
+            assert app.sort_secondary is None
+
     @pytest.mark.asyncio
     async def test_clear_selection(self, mock_process_data):
         """Should clear selection when 'c' pressed."""
@@ -215,6 +312,40 @@ class TestProcessCleanerApp:
             # Static widget uses update() to set content
             assert mem_total is not None
 
+    @pytest.mark.asyncio
+    async def test_memory_bar_load(self, mock_process_data):
+        """Should show load and uptime, flagging a saturated CPU."""
+        app = ProcessCleanerApp()
+        async with app.run_test() as pilot:
+            await app.workers.wait_for_complete()
+            await pilot.pause()
+            load = app.query_one("#load", Static)
+            uptime = app.query_one("#uptime", Static)
+            assert "0.50 0.25 0.12 (4 CPUs)" in str(load.content)
+            assert not load.has_class("saturated")
+            assert "1d 2h 3m" in str(uptime.content)
+            mock_process_data["mem"].return_value["load_avg"] = (6.0, 3.0, 1.0)
+            app.action_refresh()
+            await app.workers.wait_for_complete()
+            await pilot.pause()
+            assert load.has_class("saturated")
+
+    @pytest.mark.asyncio
+    async def test_core_strip_toggle(self, mock_process_data):
+        """Should show the per-core strip on C, flagging pinned cores."""
+        usage = CoreUsage((0.0, 100.0, 0.0, 0.0))
+        with patch("procclean.tui.app.core_usage", return_value=usage):
+            app = ProcessCleanerApp()
+            async with app.run_test() as pilot:
+                await app.workers.wait_for_complete()
+                await pilot.pause()
+                strip = app.query_one("#cpu-cores", Static)
+                assert not strip.has_class("visible")
+                await pilot.press("C")
+                assert strip.has_class("visible")
+                assert strip.has_class("pinned")
+                assert "CPU  █   25%" in str(strip.content)
+
     @pytest.mark.asyncio
     async def test_high_mem_view(self, mock_process_data, make_process):
         """Should filter to high memory processes in high-mem view."""
@@ -233,6 +364,112 @@ class TestProcessCleanerApp:
             # Only processes > 500 MB should be shown
             assert app.current_view == "high-mem"
 
+    @pytest.mark.asyncio
+    async def test_cpu_hogs_view(self, mock_process_data, make_process):
+        """Should filter to CPU-heavy processes in cpu-hogs view."""
+        mock_process_data["get_procs"].return_value = [
+            make_process(pid=1, name="idle", cpu_percent=0.5),
+            make_process(pid=2, name="busy", cpu_percent=95.0),
+        ]
+
+        app = ProcessCleanerApp()
+        async with app.run_test():
+            app.current_view = "cpu-hogs"
+            assert [p.pid for p in app._filter_by_view()] == [2]
+
+    @pytest.mark.asyncio
+    async def test_registered_filter_view(self, mock_process_data, make_process):
+        """Should offer a view for filter presets that register one."""
+        mock_process_data["get_procs"].return_value = [
+            make_process(pid=1, name="idle", status="sleeping"),
+            make_process(pid=2, name="dead", status="zombie"),
+        ]
+        registry = FilterRegistry()
+        registry.register(
+            FilterSpec(
+                "zombies",
+                "Zombies",
+                lambda procs, _source, _params: [
+                    p for p in procs if p.status == "zombie"
+                ],
+                view="zombies",
+            )
+        )
+
+        with patch("procclean.tui.app.FILTERS", registry):
+            app = ProcessCleanerApp()
+            async with app.run_test():
+                options = app.query_one("#view-selector", OptionList)
+                ids = [
+                    options.get_option_at_index(i).id
+                    for i in range(options.option_count)
+                ]
+                assert ids == [
+                    "view-all",
+                    "view-zombies",
+                    "view-groups",
+                    "view-duplicates",
+                ]
+                app.current_view = "zombies"
+                assert [p.pid for p in app._filter_by_view()] == [2]
+
+    @pytest.mark.asyncio
+    async def test_name_search(self, mock_process_data, make_process):
+        """Should filter rows by name, ignoring case, from the search prompt."""
+        mock_process_data["get_procs"].return_value = [
+            make_process(pid=1, name="Node"),
+            make_process(pid=2, name="python"),
+        ]
+
+        app = ProcessCleanerApp()
+        async with app.run_test() as pilot:
+            await pilot.press("slash")
+            await pilot.press(*"node", "enter")
+            table = app.query_one("#process-table", DataTable)
+            assert app.name_filter == "node"
+            assert table.row_count == 1
+
+    @pytest.mark.asyncio
+    async def test_incremental_search(self, mock_process_data, make_process):
+        """Should narrow rows while typing, keep selections and clear on Esc."""
+        mock_process_data["get_procs"].return_value = [
+            make_process(pid=1, name="node"),
+            make_process(pid=2, name="python"),
+            make_process(pid=3, name="bash", cwd="/srv/node-app"),
+        ]
+
+        app = ProcessCleanerApp()
+        async with app.run_test() as pilot:
+            await app.workers.wait_for_complete()
+            await pilot.pause()
+            app.selected_pids = {2}
+            await pilot.press("slash", *"nod")
+            table = app.query_one("#process-table", DataTable)
+            assert app.name_filter == "nod"
+            assert table.row_count == 2
+            await pilot.press("escape")
+            assert app.name_filter is None
+            assert table.row_count == 3
+            assert app.selected_pids == {2}
+            assert app.focused is table
+
+    @pytest.mark.asyncio
+    async def test_search_highlight(self, mock_process_data, make_process):
+        """Should highlight the characters of a name matched fuzzily."""
+        mock_process_data["get_procs"].return_value = [
+            make_process(pid=1, name="node-server"),
+        ]
+
+        app = ProcessCleanerApp()
+        async with app.run_test() as pilot:
+            await app.workers.wait_for_complete()
+            await pilot.pause()
+            await pilot.press("slash", *"ndsv", "enter")
+            texts = app._row_texts(app.processes[0])
+            name = texts[app.visible_columns.index("name")]
+            spans = [(s.start, s.end) for s in name.spans if s.style == "reverse"]
+            assert spans == [(0, 1), (2, 3), (5, 6), (8, 9)]
+
     @pytest.mark.asyncio
     async def test_long_cwd_truncation(self, mock_process_data, make_process):
         """Should truncate long cwd paths."""
@@ -249,6 +486,24 @@ class TestProcessCleanerApp:
             # Table should have been updated with truncated cwd
             assert len(app.processes) == 1
 
+    @pytest.mark.asyncio
+    async def test_narrow_terminal_drops_columns(self, mock_process_data):
+        """Should hide sidebar and low-priority columns on narrow terminals."""
+        app = ProcessCleanerApp()
+        async with app.run_test(size=(80, 24)):
+            assert app.has_class("narrow")
+            assert "ppid" not in app.visible_columns
+            assert "parent_name" not in app.visible_columns
+            assert "pid" in app.visible_columns
+
+    @pytest.mark.asyncio
+    async def test_wide_terminal_keeps_all_columns(self, mock_process_data):
+        """Should show every column and the sidebar on wide terminals."""
+        app = ProcessCleanerApp()
+        async with app.run_test(size=(200, 40)):
+            assert not app.has_class("narrow")
+            assert "parent_name" in app.visible_columns
+
     @pytest.mark.asyncio
     async def test_toggle_select_add(self, mock_process_data):
         """Should add to selection with space."""
@@ -275,6 +530,108 @@ class TestProcessCleanerApp:
             # Should have removed the process
             assert len(app.selected_pids) == initial_count - 1
 
+    @pytest.mark.asyncio
+    async def test_tree_view(self, mock_process_data, make_process):
+        """Should indent children under their parent and fold them with 'e'."""
+        mock_process_data["get_procs"].return_value = [
+            make_process(pid=30, name="child", ppid=10, rss_mb=900.0),
+            make_process(pid=10, name="parent", ppid=1, rss_mb=100.0),
+            make_process(pid=20, name="other", ppid=1, rss_mb=500.0),
+        ]
+        app = ProcessCleanerApp()
+        async with app.run_test() as pilot:
+            await pilot.press("t")
+            table = app.query_one("#process-table", DataTable)
+            rows = [table.get_row_at(i) for i in range(table.row_count)]
+            assert [str(row[2]) for row in rows] == [
+                "  other",
+                "▾ parent",
+                "    child",
+            ]
+            table.move_cursor(row=1)
+            await pilot.press("e")
+            assert table.row_count == 2  # noqa: PLR2004
+            assert str(table.get_row_at(1)[2]) == "▸ parent"
+            await pilot.press("t")
+            assert table.row_count == 3  # noqa: PLR2004
+
+    @pytest.mark.asyncio
+    async def test_kill_subtree(self, mock_process_data, make_process):
+        """Should select a subtree with 'T' and kill children first."""
+        mock_process_data["get_procs"].return_value = [
+            make_process(pid=10, name="parent", ppid=1, rss_mb=900.0),
+            make_process(pid=20, name="child", ppid=10, rss_mb=500.0),
+            make_process(pid=30, name="grandchild", ppid=20, rss_mb=100.0),
+            make_process(pid=40, name="other", ppid=1, rss_mb=50.0),
+        ]
+        app = ProcessCleanerApp()
+        async with app.run_test() as pilot:
+            await pilot.press("T")
+            assert app.selected_pids == {10, 20, 30}
+            await pilot.press("k")
+            await pilot.press("y")
+            await pilot.pause()
+            mock_process_data["kill"].assert_called_with(
+                [30, 20, 10], force=False, source=None
+            )
+
+    @pytest.mark.asyncio
+    async def test_row_cap(self, mock_process_data, many_processes, config_file):
+        """Should cap the table at display.row_cap rows, keeping selected ones."""
+        mock_process_data["get_procs"].return_value = many_processes
+        config_file.write_text("[display]\nrow_cap = 3\n")
+
+        app = ProcessCleanerApp()
+        async with app.run_test():
+            table = app.query_one("#process-table", DataTable)
+            assert table.row_count == app.shown_rows == 3  # noqa: PLR2004
+            assert app.total_rows == len(many_processes)
+            # The smallest process sorts last but stays once selected
+            app.selected_pids = {many_processes[0].pid}
+            app.update_table()
+            assert table.row_count == 3 + 1  # noqa: PLR2004
+
+    @pytest.mark.asyncio
+    async def test_highlight_rules(self, mock_process_data, config_file):
+        """Should style rows with the configured highlights."""
+        config_file.write_text('[highlight]\norphan = "bold yellow"\n')
+
+        app = ProcessCleanerApp()
+        async with app.run_test():
+            table = app.query_one("#process-table", DataTable)
+            # Process 2 is an orphan
+            assert table.get_row("2")[1].style == "bold yellow"
+
+    @pytest.mark.asyncio
+    async def test_config_defaults(self, mock_process_data, make_process, config_file):
+        """Should start with the configured sort, memory floor and thresholds."""
+        mock_process_data["get_procs"].return_value = [
+            make_process(pid=1, name="small", rss_mb=100.0),
+            make_process(pid=2, name="big", rss_mb=600.0),
+        ]
+        config_file.write_text(
+            '[defaults]\nsort = "cpu,name"\nmin_memory = 50\n'
+            "high_memory_threshold = 50\n"
+        )
+
+        app = ProcessCleanerApp()
+        async with app.run_test():
+            assert (app.sort_key, app.sort_secondary) == ("cpu", "name")
+            assert app.min_memory == 50  # noqa: PLR2004
+            app.current_view = "high-mem"
+            assert [p.pid for p in app._filter_by_view()] == [1, 2]
+
+    @pytest.mark.asyncio
+    async def test_row_position(self, mock_process_data, sample_processes):
+        """Should track the cursor row for the status bar."""
+        app = ProcessCleanerApp()
+        async with app.run_test():
+            table = app.query_one("#process-table", DataTable)
+            total = len(sample_processes)
+            assert app._row_position() == f"Row 1 of {total}"
+            table.move_cursor(row=1)
+            assert app._row_position() == f"Row 2 of {total}"
+
     @pytest.mark.asyncio
     async def test_view_change_via_option_list(self, mock_process_data):
         """Should change view when selecting from OptionList."""
@@ -325,7 +682,7 @@ class TestProcessCleanerApp:
             await pilot.press("K")  # Capital K for force kill
             await pilot.press("y")
             await pilot.pause()  # Wait for worker to complete
-            mock_process_data["kill"].assert_called_with([1], force=True)
+            mock_process_data["kill"].assert_called_with([1], force=True, source=None)
 
     @pytest.mark.asyncio
     async def test_filter_cwd(self, mock_process_data, make_process):
@@ -419,6 +776,53 @@ class TestConfirmKillScreen:
             await pilot.press("y")
             assert result is True
 
+    @pytest.mark.asyncio
+    async def test_signal_picker(self, sample_processes, mock_process_data):
+        """Should send the signal picked with 's' instead of killing."""
+        mock_process_data["kill"].return_value = [(1, True, "sent")]
+        app = ProcessCleanerApp()
+        async with app.run_test() as pilot:
+            app.selected_pids.add(1)
+            await pilot.press("k")
+            screen = app.screen
+            assert isinstance(screen, ConfirmKillScreen)
+            await pilot.press("s")
+            assert screen.picked_signal == signal.SIGHUP
+            title = str(screen.query_one("#confirm-title", Label).content)
+            assert title.startswith("Send SIGHUP to")
+            await pilot.press("y")
+            await pilot.pause()
+            mock_process_data["kill"].assert_called_with(
+                [1], force=False, source=None, sig=signal.SIGHUP
+            )
+
+    @pytest.mark.asyncio
+    async def test_escalate_kill(self, sample_processes, mock_process_data):
+        """Should escalate with 'e' and show per-PID progress while waiting."""
+        progress = []
+
+        def escalate(pids, timeout, source, on_progress):
+            on_progress([KillResult(1, KillOutcome.PENDING, "waiting")], 4.0)
+            progress.append(str(app.query_one("#status-bar", Static).content))
+            return [KillResult(1, KillOutcome.ESCALATED, "killed", 5.0)]
+
+        app = ProcessCleanerApp()
+        with patch("procclean.tui.app.kill_escalating", side_effect=escalate):
+            async with app.run_test() as pilot:
+                app.selected_pids.add(1)
+                await pilot.press("k")
+                screen = app.screen
+                assert isinstance(screen, ConfirmKillScreen)
+                await pilot.press("e")
+                assert screen.escalate
+                hint = str(screen.query_one("#confirm-signal", Label).content)
+                assert "then SIGKILL after 5s" in hint
+                await pilot.press("y")
+                await app.workers.wait_for_complete()
+                await pilot.pause()
+        assert progress == ["Waiting for exits (4s left): 1 waiting"]
+        mock_process_data["kill"].assert_not_called()
+
     @pytest.mark.asyncio
     async def test_confirm_yes_button_click(self, sample_processes, mock_process_data):
         """Should return True when Yes button clicked."""
@@ -498,6 +902,102 @@ class TestConfirmKillScreen:
             await pilot.press("escape")
             assert result is False
 
+    @pytest.mark.asyncio
+    async def test_supervisor_note_and_manager_key(
+        self, sample_processes, mock_process_data
+    ):
+        """Should annotate supervised targets and dismiss with VIA_MANAGER on m."""
+        app = ProcessCleanerApp()
+        pid = sample_processes[0].pid
+        supervisors = {pid: Supervisor("systemd", "api.service")}
+        async with app.run_test() as pilot:
+            result = None
+
+            def callback(value):
+                nonlocal result
+                result = value
+
+            app.push_screen(
+                ConfirmKillScreen(sample_processes[:2], supervisors=supervisors),
+                callback,
+            )
+            await pilot.pause()
+            assert len(app.screen.query(".supervisor")) == 1
+            assert app.screen.query("#manager")
+            await pilot.press("m")
+            assert result == VIA_MANAGER
+
+    @pytest.mark.asyncio
+    async def test_manager_key_ignored_without_supervisor(
+        self, sample_processes, mock_process_data
+    ):
+        """Should keep the dialog open on m when nothing is supervised."""
+        app = ProcessCleanerApp()
+        async with app.run_test() as pilot:
+            screen = ConfirmKillScreen(sample_processes[:1])
+            app.push_screen(screen)
+            await pilot.press("m")
+            assert app.screen is screen
+            assert not screen.query("#manager")
+
+    @pytest.mark.asyncio
+    async def test_no_confirm_pattern_skips_dialog(
+        self, sample_processes, mock_process_data, config_file
+    ):
+        """Should kill a single whitelisted process without asking."""
+        target = sample_processes[0]
+        config_file.write_text(f'[kill]\nno_confirm = ["{target.cmdline}"]\n')
+        app = ProcessCleanerApp()
+        async with app.run_test() as pilot:
+            await app.workers.wait_for_complete()
+            await pilot.pause()
+            app.selected_pids = {target.pid}
+            await pilot.press("k")
+            await app.workers.wait_for_complete()
+            assert not isinstance(app.screen, ConfirmKillScreen)
+        mock_process_data["kill"].assert_called_once()
+        assert mock_process_data["kill"].call_args.args[0] == [target.pid]
+
+    @pytest.mark.asyncio
+    async def test_data_loss_needs_second_yes(
+        self, sample_processes, mock_process_data
+    ):
+        """Should only confirm a kill of a database on the second y."""
+        app = ProcessCleanerApp()
+        async with app.run_test() as pilot:
+            result = None
+
+            def callback(value):
+                nonlocal result
+                result = value
+
+            db = replace(sample_processes[0], name="postgres")
+            screen = ConfirmKillScreen([db], data_loss=[db])
+            app.push_screen(screen, callback)
+            await pilot.press("e")
+            assert screen.escalate is False
+            await pilot.press("y")
+            assert result is None
+            assert screen.armed
+            await pilot.press("y")
+            assert result is True
+
+    @pytest.mark.asyncio
+    async def test_force_kill_refused_for_data_loss(
+        self, sample_processes, mock_process_data
+    ):
+        """Should refuse K on a database without opening the dialog."""
+        db = replace(sample_processes[0], name="postgres")
+        mock_process_data["get_procs"].return_value = [db]
+        app = ProcessCleanerApp()
+        async with app.run_test() as pilot:
+            await app.workers.wait_for_complete()
+            await pilot.pause()
+            app.selected_pids = {db.pid}
+            await pilot.press("K")
+            assert not isinstance(app.screen, ConfirmKillScreen)
+        mock_process_data["kill"].assert_not_called()
+
     @pytest.mark.asyncio
     async def test_force_kill_creates_screen(self, sample_processes, mock_process_data):
         """Should create ConfirmKillScreen with force=True."""
@@ -506,11 +1006,449 @@ class TestConfirmKillScreen:
         assert len(screen.processes) == 1
 
 
+class TestProcessDetailScreen:
+    """Tests for the inspect screen."""
+
+    def test_sections(self, sample_processes):
+        """Should list every detail, with a placeholder for empty sections."""
+        proc = sample_processes[0]
+        details = ProcessDetails(
+            proc=proc,
+            argv=["python", "-m", "http.server"],
+            environ={"LANG": "C"},
+            open_files={0: "/dev/null"},
+            ports=("tcp 0.0.0.0:8000",),
+            memory={"rss": 12.5},
+            children=[],
+        )
+        sections = dict(ProcessDetailScreen(details).sections())
+        assert sections["Command line"] == ["python -m http.server"]
+        assert sections["Memory (MB)"] == ["rss: 12.5"]
+        assert sections["Children"] == []
+        assert sections["Listening sockets"] == ["tcp 0.0.0.0:8000"]
+        assert sections["Open file descriptors"] == ["0: /dev/null"]
+        assert sections["Environment"] == ["LANG=C"]
+        assert f"parent {proc.ppid}" in sections["Process"][0]
+
+    @pytest.mark.asyncio
+    async def test_enter_opens_inspect(self, snapshot_app):
+        """Should open the inspect screen on Enter and close it on Escape."""
+        async with snapshot_app.run_test() as pilot:
+            await snapshot_app.workers.wait_for_complete()
+            await pilot.pause()
+            await pilot.press("enter")
+            await snapshot_app.workers.wait_for_complete()
+            await pilot.pause()
+            assert isinstance(snapshot_app.screen, ProcessDetailScreen)
+            assert not snapshot_app.selected_pids
+            await pilot.press("escape")
+            assert not isinstance(snapshot_app.screen, ProcessDetailScreen)
+
+
+class TestUndo:
+    """Tests for the action history and undo."""
+
+    def _state(self, *selected):
+        """Build a state that differs only in selection.
+
+        Returns:
+            UiState: State with the given PIDs selected.
+        """
+        return UiState("all", "memory", True, None, None, frozenset(selected))
+
+    def test_pop_skips_kills(self):
+        """Should step over destructive entries to the last undoable one."""
+        history = ActionHistory()
+        history.push("toggle_select", self._state())
+        history.push("kill")
+        entry = history.pop_undoable()
+        assert entry is not None
+        assert entry.label == "toggle_select"
+        assert history.pop_undoable() is None
+
+    def test_limit(self):
+        """Should drop the oldest entries beyond the limit."""
+        history = ActionHistory(limit=2)
+        for pid in range(3):
+            history.push("toggle_select", self._state(pid))
+        assert [e.before.selected for e in history.entries] == [
+            frozenset({1}),
+            frozenset({2}),
+        ]
+
+    @pytest.mark.asyncio
+    async def test_undo_clear_selection(self, mock_process_data, sample_processes):
+        """Should bring back a selection cleared by accident."""
+        app = ProcessCleanerApp()
+        async with app.run_test() as pilot:
+            await app.workers.wait_for_complete()
+            await pilot.pause()
+            await pilot.press("s")
+            selected = set(app.selected_pids)
+            assert len(selected) == len(sample_processes)
+            await pilot.press("c", "u")
+            assert app.selected_pids == selected
+
+    @pytest.mark.asyncio
+    async def test_undo_view_and_filter(self, mock_process_data):
+        """Should restore the previous view and search."""
+        app = ProcessCleanerApp()
+        async with app.run_test() as pilot:
+            await pilot.press("o", "1")
+            assert app.current_view == "orphans"
+            await pilot.press("u", "u")
+            assert app.current_view == "all"
+            assert app.sort_key == "memory"
+            assert app.sort_reverse is True
+
+    @pytest.mark.asyncio
+    async def test_undo_search(self, mock_process_data):
+        """Should bring back the rows hidden by a search."""
+        app = ProcessCleanerApp()
+        async with app.run_test() as pilot:
+            await pilot.press("slash", *"zzz", "enter")
+            assert app.name_filter == "zzz"
+            await pilot.press("u")
+            assert app.name_filter is None
+            assert app.query_one("#search-bar").value == ""
+
+    @pytest.mark.asyncio
+    async def test_nothing_to_undo(self, mock_process_data):
+        """Should leave state alone when the history is empty."""
+        app = ProcessCleanerApp()
+        async with app.run_test() as pilot:
+            await pilot.press("u")
+            assert app.current_view == "all"
+            assert not app.history
+
+
+class TestActionQueue:
+    """Tests for queuing actions and running them as one batch."""
+
+    def test_replace_and_prune(self, make_process):
+        """Should keep one action per process and drop those of exited ones."""
+        procs = [make_process(pid=1), make_process(pid=2)]
+        queue = ActionQueue()
+        queue.add(QueuedKind.KILL, procs)
+        queue.add(QueuedKind.RENICE, procs[1:])
+        assert queue.pids(QueuedKind.KILL) == [1]
+        assert queue.pids(QueuedKind.RENICE) == [2]
+        reused = make_process(pid=2, create_time=60.0)
+        assert queue.prune([procs[0], reused]) == 1
+        assert [a.pid for a in queue.actions] == [1]
+
+    @pytest.mark.asyncio
+    async def test_queue_and_run(self, mock_process_data):
+        """Should mark queued rows and run every action after one review."""
+        app = ProcessCleanerApp()
+        with patch(
+            "procclean.tui.app.renice_process", return_value=(True, "reniced")
+        ) as renice:
+            async with app.run_test() as pilot:
+                await app.workers.wait_for_complete()
+                await pilot.pause()
+                app.selected_pids.update({1, 2})
+                await pilot.press("x")
+                assert not app.selected_pids
+                assert app._selection_marker(app.processes[0]) == "[k]"
+                app.selected_pids.add(4)
+                await pilot.press("n", "p")
+                screen = app.screen
+                assert isinstance(screen, ReviewQueueScreen)
+                title = str(screen.query_one("#confirm-title", Label).content)
+                assert title == "Run 3 queued action(s)?"
+                await pilot.press("y")
+                await app.workers.wait_for_complete()
+                await pilot.pause()
+                assert not app.queue
+        renice.assert_called_once_with(4, QUEUE_NICE, None)
+        pids = mock_process_data["kill"].call_args.args[0]
+        assert sorted(pids) == [1, 2]
+
+    @pytest.mark.asyncio
+    async def test_back_keeps_queue(self, mock_process_data):
+        """Should keep the actions queued when the review is cancelled."""
+        app = ProcessCleanerApp()
+        async with app.run_test() as pilot:
+            await app.workers.wait_for_complete()
+            await pilot.pause()
+            app.selected_pids.add(1)
+            await pilot.press("z", "p", "n")
+            assert app.queue.pids(QueuedKind.SUSPEND) == [1]
+            mock_process_data["kill"].assert_not_called()
+
+
+class TestRestart:
+    """Tests for restarting processes from the TUI."""
+
+    @pytest.mark.asyncio
+    async def test_restart_via_manager(self, snapshot_app, fake_source):
+        """Should show the plan and restart a unit through systemctl."""
+        async with snapshot_app.run_test() as pilot:
+            await snapshot_app.workers.wait_for_complete()
+            await pilot.pause()
+            snapshot_app.selected_pids = {300}
+            await pilot.press("R")
+            assert isinstance(snapshot_app.screen, ConfirmRestartScreen)
+            await pilot.press("y")
+            await snapshot_app.workers.wait_for_complete()
+            await pilot.pause()
+            assert not snapshot_app.selected_pids
+        (command,) = fake_source.commands
+        assert command[:3] == ["systemctl", "--user", "restart"]
+        assert fake_source.signals == []
+
+    @pytest.mark.asyncio
+    async def test_cancel(self, snapshot_app, fake_source):
+        """Should leave the processes alone when the dialog is cancelled."""
+        async with snapshot_app.run_test() as pilot:
+            await snapshot_app.workers.wait_for_complete()
+            await pilot.pause()
+            snapshot_app.selected_pids = {300}
+            await pilot.press("R", "n")
+            assert not isinstance(snapshot_app.screen, ConfirmRestartScreen)
+        assert fake_source.commands == []
+
+
+class TestAuditLog:
+    """Tests for logging TUI kills to the audit log."""
+
+    @pytest.mark.asyncio
+    async def test_kill_logs_view_and_search(self, snapshot_app, audit_file):
+        """Should attribute kills to the TUI and the view they were made in."""
+        async with snapshot_app.run_test() as pilot:
+            await snapshot_app.workers.wait_for_complete()
+            await pilot.pause()
+            snapshot_app.name_filter = "node"
+            snapshot_app.selected_pids = {200}
+            await pilot.press("k", "y")
+            await snapshot_app.workers.wait_for_complete()
+            await pilot.pause()
+        (record,) = read_history(audit_file)
+        assert (record.pid, record.origin) == (200, "tui")
+        assert record.filter == "view=all search=node"
+
+
+class TestSessionReplay:
+    """Tests for --record and --replay in the TUI."""
+
+    @pytest.mark.asyncio
+    async def test_records_snapshots_and_actions(
+        self, tmp_path, sample_processes, mock_process_data
+    ):
+        """Should log fetched data and key-bound actions with the cursor PID."""
+        path = tmp_path / "session.jsonl"
+        app = ProcessCleanerApp(recorder=SessionRecorder(path))
+        async with app.run_test() as pilot:
+            await app.workers.wait_for_complete()
+            await pilot.pause()
+            cursor_pid = app._get_pid_at_cursor()
+            await pilot.press("space", "o")
+        events = load_session(path)
+        assert events[0]["kind"] == "snapshot"
+        assert len(events[0]["processes"]) == len(sample_processes)
+        actions = [(e["action"], e["pid"]) for e in events if e["kind"] == "action"]
+        assert ("toggle_select", cursor_pid) in actions
+        assert "show_orphans" in {action for action, _ in actions}
+
+    @pytest.mark.asyncio
+    async def test_replays_without_live_data(self, sample_processes, mock_process_data):
+        """Should show recorded data and never read or signal live processes."""
+        target = sample_processes[1].pid
+        events = [
+            {
+                "t": 0,
+                "kind": "snapshot",
+                "processes": [asdict(p) for p in sample_processes],
+                "memory": mock_process_data["mem"].return_value,
+            },
+            {"t": 0, "kind": "action", "action": "toggle_select", "pid": target},
+            {"t": 0, "kind": "view", "view": "orphans"},
+            {"t": 0, "kind": "action", "action": "kill_selected", "pid": target},
+            {
+                "t": 0,
+                "kind": "kill",
+                "pids": [target],
+                "force": False,
+                "via_manager": False,
+                "results": [{"pid": target, "ok": True, "message": "Terminated"}],
+            },
+        ]
+        app = ProcessCleanerApp(replay=events)
+        async with app.run_test() as pilot:
+            await app.workers.wait_for_complete()
+            await pilot.pause()
+            assert [p.pid for p in app.processes] == [p.pid for p in sample_processes]
+            assert app.current_view == "orphans"
+            assert not app.selected_pids
+            assert not isinstance(app.screen, ConfirmKillScreen)
+        mock_process_data["get_procs"].assert_not_called()
+        mock_process_data["kill"].assert_not_called()
+
+
+class TestMemoryChart:
+    """Tests for the memory history chart."""
+
+    @pytest.mark.asyncio
+    async def test_records_memory_history(self, mock_process_data):
+        """Should append a memory sample on every refresh."""
+        app = ProcessCleanerApp()
+        async with app.run_test() as pilot:
+            await pilot.pause()
+            assert list(app.memory_history)[-1] == mock_process_data[
+                "mem"
+            ].return_value["percent"]
+
+    def test_graphics_disabled_by_default(self, monkeypatch):
+        """Graphics chart should be opt-in."""
+        monkeypatch.delenv(GRAPHICS_ENV, raising=False)
+        assert graphics_enabled() is False
+
+    def test_graphics_requires_capable_terminal(self, monkeypatch):
+        """Opting in on an unsupported terminal should fall back."""
+        monkeypatch.setenv(GRAPHICS_ENV, "1")
+        monkeypatch.setenv("TERM", "xterm-256color")
+        monkeypatch.delenv("KITTY_WINDOW_ID", raising=False)
+        monkeypatch.delenv("TERM_PROGRAM", raising=False)
+        with patch("procclean.tui.chart.query_da1", return_value=""):
+            assert graphics_enabled() is False
+
+    def test_parse_da1(self):
+        """Should read the attributes after the device class."""
+        assert parse_da1("\x1b[?62;4;6;22c") == {DA1_SIXEL, 6, 22}
+        assert parse_da1("\x1b[?1;2c") == {2}
+        assert parse_da1("\x1b[?64c") == set()
+        assert parse_da1("") == set()
+
+    def test_da1_needs_a_terminal(self):
+        """Should not query the terminal when stdin or stdout is redirected."""
+        query_da1.cache_clear()
+        try:
+            with (
+                patch("sys.stdin.isatty", return_value=False),
+                patch("procclean.tui.chart.os.open") as mock_open,
+            ):
+                assert query_da1() == ""
+            mock_open.assert_not_called()
+        finally:
+            query_da1.cache_clear()
+
+    def test_sixel_by_term(self, monkeypatch):
+        """Should take known sixel terminals at their TERM."""
+        monkeypatch.setenv("TERM", "foot-extra")
+        monkeypatch.delenv("KITTY_WINDOW_ID", raising=False)
+        monkeypatch.delenv("TERM_PROGRAM", raising=False)
+        assert terminal_supports_images() is False
+        assert terminal_supports_graphics() is True
+
+    def test_sixel_by_da1(self, monkeypatch):
+        """Should detect sixel from attribute 4 of the DA1 answer."""
+        monkeypatch.setenv("TERM", "xterm-256color")
+        with patch("procclean.tui.chart.query_da1", return_value="\x1b[?63;1;4c"):
+            assert terminal_supports_sixel() is True
+        with patch("procclean.tui.chart.query_da1", return_value="\x1b[?63;1;2c"):
+            assert terminal_supports_sixel() is False
+
+    def test_falls_back_to_sparkline(self, monkeypatch):
+        """Should create a sparkline when graphics are unavailable."""
+        monkeypatch.delenv(GRAPHICS_ENV, raising=False)
+        assert isinstance(create_memory_chart(), MemorySparkline)
+
+
+class TestListDesigner:
+    """Tests for the live preview of fmt --interactive."""
+
+    @pytest.mark.asyncio
+    async def test_design_and_done(self, make_process):
+        """Should redraw the preview and command, save, and exit with it."""
+        saved: list[list[str]] = []
+
+        def save(entries: list[str]) -> list:
+            saved.append(entries)
+            return []
+
+        design = ListDesign.from_columns(["pid", "rss_mb"])
+        app = ListDesignerApp(design, [make_process(rss_mb=2.0)], save)
+        async with app.run_test() as pilot:
+            await pilot.press("down", "u")
+            preview = str(app.query_one("#designer-preview", Static).content)
+            assert "RAM (KB)" in preview
+            assert "2048" in preview
+            await pilot.press("K", "s", "f")
+            command = str(app.query_one("#designer-command", Static).content)
+            assert command == "procclean list --columns rss_mb:kb,pid --format json"
+            await pilot.press("q")
+        assert saved == [["rss_mb:kb", "pid"]]
+        assert app.return_value == command
+
+    @pytest.mark.asyncio
+    async def test_cancel(self, make_process):
+        """Should exit without a command on Escape."""
+        app = ListDesignerApp(ListDesign.from_columns(), [make_process()])
+        async with app.run_test() as pilot:
+            await pilot.press("space", "escape")
+        assert app.return_value is None
+
+
+class TestFilterPanel:
+    """Tests for composing filters and saving them as views."""
+
+    @pytest.mark.asyncio
+    async def test_save_view(self, snapshot_app, config_file):
+        """Should match live, save the preset and switch to its view."""
+        async with snapshot_app.run_test() as pilot:
+            await snapshot_app.workers.wait_for_complete()
+            await pilot.pause()
+            await pilot.press("f")
+            panel = snapshot_app.screen
+            assert isinstance(panel, FilterPanelScreen)
+            panel.query_one("#panel-pattern", Input).value = "nvim"
+            panel.query_one("#panel-min-memory", Input).value = "50"
+            await pilot.pause()
+            result = str(panel.query_one("#panel-result", Static).content)
+            assert result.startswith("1 of ")
+            assert "nvim" in result
+            panel.query_one("#panel-name", Input).value = "editors"
+            await pilot.press("ctrl+s")
+            await pilot.pause()
+            assert not isinstance(snapshot_app.screen, FilterPanelScreen)
+            assert snapshot_app.current_view == "editors"
+            options = snapshot_app.query_one("#view-selector", OptionList)
+            assert options.get_option("view-editors")
+            table = snapshot_app.query_one("#process-table", DataTable)
+            assert table.row_count == 1
+        assert load_config()["presets"]["editors"] == {
+            "description": "editors",
+            "min_memory": 50.0,
+            "name": "nvim",
+        }
+
+    @pytest.mark.asyncio
+    async def test_edit_and_cancel(self, snapshot_app, config_file):
+        """Should open a saved view with its rules and leave it on Escape."""
+        text = "[presets.old]\nstale = true\ncmdline = 'x'\n"
+        config_file.write_text(text)
+        load_presets()
+        async with snapshot_app.run_test() as pilot:
+            await snapshot_app.workers.wait_for_complete()
+            await pilot.pause()
+            snapshot_app.current_view = "old"
+            await pilot.press("f")
+            panel = snapshot_app.screen
+            assert isinstance(panel, FilterPanelScreen)
+            assert panel.query_one("#panel-name", Input).value == "old"
+            assert panel.query_one("#panel-stale", Checkbox).value
+            assert panel.rules() == {"cmdline": "x", "stale": True}
+            await pilot.press("escape")
+            assert not isinstance(snapshot_app.screen, FilterPanelScreen)
+        assert config_file.read_text() == text
+
+
 class TestMainFunction:
     """Tests for main entry point."""
 
     @patch("procclean.__main__.run_cli")
-    @patch("procclean.__main__.ProcessCleanerApp")
+    @patch("procclean.tui.ProcessCleanerApp")
     def test_runs_tui_when_no_subcommand(self, mock_app_class, mock_run_cli):
         """Should run TUI when run_cli returns -1."""
         mock_run_cli.return_value = -1
@@ -540,6 +1478,25 @@ class TestDunderMain:
 
         assert callable(dunder_main.main)
 
+    def test_core_import_has_no_ui_dependencies(self):
+        """Importing procclean.core should not load textual, rich or tabulate."""
+        import subprocess  # noqa: PLC0415
+
+        code = (
+            "import sys, procclean.core; "
+            "print(any(m.split('.')[0] in {'textual', 'rich', 'tabulate'} "
+            "for m in sys.modules))"
+        )
+        result = subprocess.run(
+            ["python", "-c", code],
+            capture_output=True,
+            text=True,
+            check=False,
+            timeout=5,
+        )
+        assert result.returncode == 0
+        assert result.stdout.strip() == "False"
+
     def test_run_as_module_version(self):
         """Should be runnable with python -m procclean --version."""
         import subprocess  # noqa: PLC0415
@@ -583,3 +1540,57 @@ class TestDunderMain:
             runpy.run_module("procclean", run_name="__main__", alter_sys=True)
 
         assert exc_info.value.args[0] == 0
+
+
+class TestSnapshots:
+    """Headless rendering of views and dialogs."""
+
+    @pytest.mark.asyncio
+    async def test_main_view(self, snapshot_app):
+        """Should render the header, sidebar, and process rows."""
+        text = await render_to_text(snapshot_app)
+        lines = text.splitlines()
+        assert len(lines) == SNAPSHOT_SIZE[1]
+        assert "ProcClean" in text
+        assert "Views" in text
+        assert "node" in text
+        assert "Total: 16.0G" in text
+
+    @pytest.mark.asyncio
+    async def test_is_deterministic(self, fake_source, snapshot_app):
+        """Should produce identical output for identical state."""
+        first = await render_to_text(snapshot_app)
+        second = await render_to_text(ProcessCleanerApp(source=fake_source))
+        assert first == second
+
+    @pytest.mark.asyncio
+    async def test_narrow_layout(self, snapshot_app):
+        """Should hide the sidebar on narrow terminals."""
+        text = await render_to_text(snapshot_app, size=(WIDTH_NARROW, SNAPSHOT_SIZE[1]))
+        assert "Views" not in text
+        assert all(len(line) <= WIDTH_NARROW for line in text.splitlines())
+
+    @pytest.mark.asyncio
+    async def test_confirm_dialog(self, snapshot_app):
+        """Should render the kill confirmation dialog."""
+        text = await render_to_text(snapshot_app, keys=["space", "k"])
+        assert "Kill 1 process(es)?" in text
+        assert "Yes (y)" in text
+
+    @pytest.mark.asyncio
+    async def test_setup_hook(self, snapshot_app):
+        """Should run the setup coroutine before capturing."""
+
+        async def setup(pilot):
+            pilot.app.cwd_filter = "/home/user/proj"
+
+        text = await render_to_text(snapshot_app, setup=setup)
+        assert "node" in text
+        assert "zsh" not in text
+
+    @pytest.mark.asyncio
+    async def test_svg_export(self, snapshot_app):
+        """Should export an SVG document."""
+        svg = await render_to_svg(snapshot_app, title="procclean")
+        assert svg.lstrip().startswith("<svg")
+        assert "procclean" in svg
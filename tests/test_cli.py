@@ -1,6 +1,12 @@
 """Tests for CLI module."""
 
 import json
+import os
+import shutil
+import signal
+import subprocess
+import time
+from dataclasses import replace
 from pathlib import Path
 from unittest.mock import patch
 
@@ -11,15 +17,66 @@ from procclean.cli import (
     _do_preview,
     _get_kill_targets,
     cmd_groups,
+    cmd_jobs,
     cmd_kill,
+    cmd_leftovers,
     cmd_list,
     cmd_memory,
+    cmd_status,
     create_parser,
     get_filtered_processes,
+    get_source,
     run_cli,
 )
+from procclean.cli.commands import CLEAR_SCREEN
+from procclean.cli.sinks import infer_format
+from procclean.core import (
+    AUDIT_ENV,
+    CPU_SAMPLE_SECONDS,
+    FILTERS,
+    HARDEN_ENV,
+    KTHREADD_PID,
+    LIST_WATCH_SECONDS,
+    NO_CAPABILITY_WARNING_ENV,
+    NO_DAEMON_ENV,
+    NO_REDACT_ENV,
+    POLKIT_ACTION,
+    POLKIT_ENV,
+    QUOTA_NICE,
+    RECORD_ENV,
+    RENICE_NICE,
+    REPLAY_ENV,
+    SCOPE_ENV,
+    SIMULATE_ENV,
+    VIA_MANAGER,
+    Capability,
+    CapabilityReport,
+    CoreUsage,
+    FakeProcess,
+    FakeProcessSource,
+    FieldMask,
+    FilterRegistry,
+    FilterSpec,
+    Inhibitor,
+    Leftover,
+    Pressure,
+    RunResult,
+    ScopedSource,
+    SimulatedSource,
+    capability_report,
+    exe_digest,
+    get_process_list,
+    package_owner,
+)
+from procclean.extras import missing_extra
+from procclean.formatters import COLUMNS, default_columns
 
 from .conftest import (
+    CLEAN_PID_DEV,
+    CLEAN_PID_ORPHAN,
+    CLEAN_PID_STALE,
+    CLEAN_TOTAL_MB,
+    CLI_CPU_THRESHOLD,
     CLI_HIGH_THRESHOLD,
     CLI_HIGH_THRESHOLD_200,
     CLI_LIMIT_2,
@@ -28,9 +85,23 @@ from .conftest import (
     CLI_MIN_MEMORY,
     CLI_TOTAL_GB,
     CWD_MATCH_COUNT,
+    EXIT_USAGE,
+    FAKE_PID_DAEMON,
+    FAKE_PID_EDITOR,
+    FAKE_PID_OTHER,
+    FAKE_PID_SERVER,
+    FAKE_PID_SHELL,
+    FAULT_INTERVAL,
+    GSD_COLOR_UNIT,
+    PID_APP,
     PID_NODE,
     PID_PYTHON,
+    PID_RUST,
+    POD_CGROUP,
+    POD_UID,
+    STATUS_PERCENT,
     TEST_PATH_SINGLE,
+    WIDTH_NARROW,
 )
 
 
@@ -112,6 +183,14 @@ class TestCreateParser:
         args = parser.parse_args(["list", "-s", "cwd"])
         assert args.sort == "cwd"
 
+    def test_list_compound_sort(self):
+        """Should accept comma-separated sort keys and reject unknown ones."""
+        parser = create_parser()
+        args = parser.parse_args(["list", "--sort", "Memory, name,memory"])
+        assert args.sort == "memory,name"
+        with pytest.raises(SystemExit):
+            parser.parse_args(["list", "--sort", "memory,bogus"])
+
     def test_groups_command(self):
         """Should parse groups command."""
         parser = create_parser()
@@ -234,7 +313,105 @@ class TestCmdList:
         assert "formatted output" in captured.out
 
     @patch("procclean.cli.commands.get_process_list")
-    @patch("procclean.cli.commands.filter_orphans")
+    @patch("procclean.cli.commands.format_output")
+    @patch("procclean.cli.commands.sys.stdout")
+    def test_uses_terminal_width_on_tty(self, mock_stdout, mock_format, mock_get):
+        """Should pass terminal width for a responsive table on a TTY."""
+        mock_get.return_value = []
+        mock_stdout.isatty.return_value = True
+        mock_format.return_value = ""
+
+        args = create_parser().parse_args(["list"])
+        with patch("procclean.cli.commands.shutil.get_terminal_size") as mock_size:
+            mock_size.return_value.columns = WIDTH_NARROW
+            cmd_list(args)
+
+        assert mock_format.call_args.kwargs["width"] == WIDTH_NARROW
+
+    @patch("procclean.cli.commands.get_process_list")
+    @patch("procclean.cli.commands.format_output")
+    def test_explicit_columns_skip_responsive_layout(self, mock_format, mock_get):
+        """Should not drop columns the user asked for explicitly."""
+        mock_get.return_value = []
+        mock_format.return_value = ""
+
+        args = create_parser().parse_args(["list", "-c", "pid,ppid"])
+        cmd_list(args)
+
+        assert mock_format.call_args.kwargs["width"] is None
+
+    def test_header(self, fake_source, system_info, capsys):
+        """Should describe the machine above the table and in JSON."""
+        with (
+            patch("procclean.cli.commands.get_source", return_value=fake_source),
+            patch("procclean.cli.commands.get_system_info", return_value=system_info),
+        ):
+            run_cli(["list", "--header", "-c", "pid,name"])
+            out = capsys.readouterr().out
+            assert out.startswith("Host:   build-01\nKernel: Linux 6.8.0\n")
+            run_cli(["list", "--header", "-f", "json"])
+            assert json.loads(capsys.readouterr().out)["header"]["kernel"] == (
+                "Linux 6.8.0"
+            )
+            run_cli(["list", "-f", "json"])
+        assert isinstance(json.loads(capsys.readouterr().out), list)
+
+    def test_kernel_threads(self, capsys):
+        """Should list kernel threads, bracketed, only with --kernel-threads."""
+        source = FakeProcessSource([
+            FakeProcess(30, "kworker/0:1", [], ppid=KTHREADD_PID, rss_mb=0.0),
+        ])
+        argv = ["list", "--min-memory", "0", "-f", "json"]
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            run_cli(argv)
+            assert json.loads(capsys.readouterr().out) == []
+            run_cli([*argv, "--kernel-threads"])
+        procs = json.loads(capsys.readouterr().out)
+        assert [(p["cmdline"], p["is_kernel_thread"]) for p in procs] == [
+            ("[kworker/0:1]", True)
+        ]
+
+    @patch("procclean.cli.commands.get_process_list")
+    @patch("procclean.cli.commands.format_output")
+    def test_layout_from_config(self, mock_format, mock_get, config_file):
+        """Should resolve --layout against [layouts] in the config file."""
+        mock_get.return_value = []
+        mock_format.return_value = ""
+        config_file.write_text('[layouts]\nmine = "pid,cmdline"\n')
+
+        cmd_list(create_parser().parse_args(["list", "--layout", "mine"]))
+
+        assert mock_format.call_args.kwargs["columns"] == ["pid", "cmdline"]
+        assert mock_format.call_args.kwargs["width"] is None
+
+    @patch("procclean.cli.commands.get_process_list")
+    @patch("procclean.cli.commands.format_output")
+    def test_row_cap(
+        self, mock_format, mock_get, config_file, sample_processes, capsys
+    ):
+        """Should cap tables at display.row_cap rows unless --no-cap is given."""
+        mock_get.return_value = sample_processes
+        mock_format.return_value = ""
+        config_file.write_text("[display]\nrow_cap = 1\n")
+
+        cmd_list(create_parser().parse_args(["list", "-f", "csv"]))
+        assert len(mock_format.call_args.args[0]) == len(sample_processes)
+
+        cmd_list(create_parser().parse_args(["list", "-f", "md"]))
+        assert len(mock_format.call_args.args[0]) == 1
+        assert f"Showing 1 of {len(sample_processes)}" in capsys.readouterr().err
+
+        cmd_list(create_parser().parse_args(["list", "--no-cap"]))
+        assert len(mock_format.call_args.args[0]) == len(sample_processes)
+        assert "Showing" not in capsys.readouterr().err
+
+    def test_layout_conflicts_with_columns(self):
+        """Should reject --layout together with --columns."""
+        with pytest.raises(SystemExit):
+            create_parser().parse_args(["list", "-c", "pid", "-L", "debug"])
+
+    @patch("procclean.cli.commands.get_process_list")
+    @patch("procclean.core.registry.filter_orphans")
     @patch("procclean.cli.commands.sort_processes")
     @patch("procclean.cli.commands.format_output")
     def test_filters_orphans(
@@ -253,7 +430,7 @@ class TestCmdList:
         mock_filter.assert_called_once_with(sample_processes)
 
     @patch("procclean.cli.commands.get_process_list")
-    @patch("procclean.cli.commands.filter_killable")
+    @patch("procclean.core.registry.filter_killable")
     @patch("procclean.cli.commands.sort_processes")
     @patch("procclean.cli.commands.format_output")
     def test_filters_killable(
@@ -269,10 +446,10 @@ class TestCmdList:
         args = parser.parse_args(["list", "-k"])
         cmd_list(args)
 
-        mock_filter.assert_called_once_with(sample_processes)
+        mock_filter.assert_called_once_with(sample_processes, source=None)
 
     @patch("procclean.cli.commands.get_process_list")
-    @patch("procclean.cli.commands.filter_high_memory")
+    @patch("procclean.core.registry.filter_high_memory")
     @patch("procclean.cli.commands.sort_processes")
     @patch("procclean.cli.commands.format_output")
     def test_filters_high_memory(
@@ -290,6 +467,68 @@ class TestCmdList:
 
         mock_filter.assert_called_once_with(sample_processes, threshold_mb=400.0)
 
+    @pytest.mark.parametrize(
+        "flags", [["--cwd-deleted"], ["-F", "cwd-deleted"]], ids=["short", "preset"]
+    )
+    @patch("procclean.cli.commands.get_process_list")
+    @patch("procclean.core.registry.filter_cwd_deleted")
+    @patch("procclean.cli.commands.format_output")
+    def test_filters_cwd_deleted(
+        self, mock_format, mock_filter, mock_get_procs, flags, sample_processes
+    ):
+        """Should apply the deleted-cwd filter via flag or preset."""
+        mock_get_procs.return_value = sample_processes
+        mock_filter.return_value = []
+        mock_format.return_value = ""
+
+        args = create_parser().parse_args(["list", *flags])
+        cmd_list(args)
+
+        mock_filter.assert_called_once_with(sample_processes)
+
+    def test_list_filters(self, capsys):
+        """Should describe the filter presets as text or JSON."""
+        assert cmd_list(create_parser().parse_args(["list", "--list-filters"])) == 0
+        out = capsys.readouterr().out
+        assert "needs-restart" in out
+        assert "--high-memory-threshold MB (500 MB)" in out
+        cmd_list(create_parser().parse_args(["list", "--list-filters", "-f", "json"]))
+        data = json.loads(capsys.readouterr().out)
+        assert [spec["name"] for spec in data] == FILTERS.names()
+
+    @patch("procclean.cli.commands.get_process_list")
+    @patch("procclean.cli.commands.format_output")
+    def test_registered_filter(self, mock_format, mock_get_procs, sample_processes):
+        """Should accept presets registered by extensions."""
+        registry = FilterRegistry()
+        for spec in FILTERS:
+            registry.register(spec)
+        registry.register(
+            FilterSpec("first", "First", lambda procs, _source, _params: procs[:1])
+        )
+        mock_get_procs.return_value = sample_processes
+        mock_format.return_value = ""
+
+        with (
+            patch("procclean.cli.parser.FILTERS", registry),
+            patch("procclean.cli.commands.FILTERS", registry),
+        ):
+            cmd_list(create_parser().parse_args(["list", "-F", "first"]))
+
+        assert mock_format.call_args.args[0] == sample_processes[:1]
+
+    @patch("procclean.cli.commands.get_process_list")
+    @patch("procclean.cli.commands.format_output")
+    def test_filter_cmd(self, mock_format, mock_get_procs, make_process):
+        """Should keep processes an external command accepts."""
+        mock_get_procs.return_value = [make_process(pid=1), make_process(pid=2)]
+        mock_format.return_value = ""
+
+        args = ["list", "--filter-cmd", "sh -c 'test {pid} = 2'", "--filter-jobs", "1"]
+        cmd_list(create_parser().parse_args(args))
+
+        assert [p.pid for p in mock_format.call_args.args[0]] == [2]
+
     @patch("procclean.cli.commands.get_process_list")
     @patch("procclean.cli.commands.sort_processes")
     @patch("procclean.cli.commands.format_output")
@@ -426,6 +665,73 @@ class TestCmdGroups:
         assert "python" in captured.out
         assert "processes" in captured.out
 
+    def test_header(self, fake_source, system_info, capsys):
+        """Should put the system information above the groups."""
+        fake_source.processes[101].cmdline = ["zsh", "-i"]
+        with (
+            patch("procclean.cli.commands.get_source", return_value=fake_source),
+            patch("procclean.cli.commands.get_system_info", return_value=system_info),
+        ):
+            run_cli(["groups", "--min-memory", "0", "--header"])
+            assert capsys.readouterr().out.startswith("Host:   build-01\n")
+            run_cli(["groups", "--min-memory", "0", "--header", "-f", "json"])
+        data = json.loads(capsys.readouterr().out)
+        assert data["header"]["hostname"] == "build-01"
+        assert "zsh" in data["groups"]
+
+    @pytest.fixture
+    def dupes(self):
+        """Provide a dev server started twice and once more elsewhere.
+
+        Returns:
+            FakeProcessSource: node 20 (oldest), 21 in /srv and 22 in /tmp.
+        """
+        source = FakeProcessSource([
+            FakeProcess(20, "node", ["node", "server.js"], cwd="/srv"),
+            FakeProcess(21, "node", ["node", "server.js"], cwd="/srv"),
+            FakeProcess(22, "node", ["node", "server.js"], cwd="/tmp"),
+        ])
+        source.processes[21].create_time = 5.0
+        return source
+
+    def test_marks_duplicates(self, dupes, capsys):
+        """Should group by script and flag true duplicates."""
+        with patch("procclean.cli.commands.get_source", return_value=dupes):
+            assert run_cli(["groups"]) == 0
+            assert "node server.js (3 processes" in capsys.readouterr().out
+            run_cli(["groups", "-f", "json"])
+        items = json.loads(capsys.readouterr().out)["node server.js"]
+        assert {i["pid"]: i["duplicate"] for i in items} == {
+            20: True,
+            21: True,
+            22: False,
+        }
+
+    def test_kill_duplicates(self, dupes, capsys):
+        """Should keep the oldest instance and kill the other duplicate."""
+        with patch("procclean.cli.commands.get_source", return_value=dupes):
+            assert run_cli(["groups", "--kill-duplicates", "-y"]) == 0
+        out = capsys.readouterr().out
+        assert "Keeping node (PID 20), killing 1 duplicate(s)" in out
+        assert dupes.signals == [(21, False)]
+
+    def test_kill_duplicates_keep_newest_preview(self, dupes, capsys):
+        """Should preview the older instance without killing it."""
+        with patch("procclean.cli.commands.get_source", return_value=dupes):
+            args = ["groups", "--kill-duplicates", "--keep", "newest", "--preview"]
+            assert run_cli([*args, "-O", "json"]) == 0
+        out = capsys.readouterr().out
+        (victim,) = json.loads(out[out.index("[") : out.rindex("]") + 1])
+        assert victim["pid"] == 20  # noqa: PLR2004
+        assert dupes.signals == []
+
+    def test_no_duplicates(self, fake_source, capsys):
+        """Should say so when every command line runs once."""
+        with patch("procclean.cli.commands.get_source", return_value=fake_source):
+            assert run_cli(["groups", "--kill-duplicates", "-y"]) == 0
+        assert "No duplicate processes found" in capsys.readouterr().out
+        assert fake_source.signals == []
+
 
 class TestCmdKill:
     """Tests for cmd_kill function."""
@@ -442,7 +748,7 @@ class TestCmdKill:
         result = cmd_kill(args)
 
         assert result == 0
-        mock_kill.assert_called_once_with([1], force=False)
+        mock_kill.assert_called_once_with([1], force=False, source=None)
         captured = capsys.readouterr()
         assert "[OK]" in captured.out
 
@@ -457,7 +763,7 @@ class TestCmdKill:
         args = parser.parse_args(["kill", "1", "-f", "-y"])
         cmd_kill(args)
 
-        mock_kill.assert_called_once_with([1], force=True)
+        mock_kill.assert_called_once_with([1], force=True, source=None)
 
     @patch("procclean.cli.commands.get_process_list")
     @patch("procclean.cli.commands.kill_processes")
@@ -558,7 +864,7 @@ class TestCmdKill:
 
         assert result == 0
         mock_filter.assert_called_once_with(sample_processes, "/home/user")
-        mock_kill.assert_called_once_with([1, 2], force=False)
+        mock_kill.assert_called_once_with([1, 2], force=False, source=None)
 
     @patch("procclean.cli.commands.get_process_list")
     @patch("procclean.cli.commands.filter_by_cwd")
@@ -594,6 +900,124 @@ class TestCmdKill:
         captured = capsys.readouterr()
         assert "No processes match" in captured.out
 
+    def test_signal(self, fake_source, capsys):
+        """Should send --signal instead of killing."""
+        with patch("procclean.cli.commands.get_source", return_value=fake_source):
+            result = run_cli(["kill", str(FAKE_PID_SERVER), "--signal", "hup", "-y"])
+        assert result == 0
+        assert fake_source.signals == []
+        assert fake_source.sent == [(FAKE_PID_SERVER, signal.SIGHUP)]
+        out = capsys.readouterr().out
+        assert f"Sent SIGHUP to process {FAKE_PID_SERVER}" in out
+        assert "Freed" not in out
+
+    def test_escalate(self, capsys):
+        """Should SIGKILL processes that outlive --timeout."""
+        source = FakeProcessSource([
+            FakeProcess(10, "polite"),
+            FakeProcess(20, "stubborn", ignores_term=True),
+        ])
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            args = ["kill", "10", "20", "--escalate", "--timeout", "0.05", "-y"]
+            assert run_cli(args) == 0
+        assert source.signals == [(10, False), (20, False), (20, True)]
+        out = capsys.readouterr().out
+        assert "Process 10 exited" in out
+        assert "Process 20 ignored SIGTERM, killed with SIGKILL" in out
+
+    def test_escalate_errors(self, capsys):
+        """Should reject --timeout alone and --escalate with other kill modes."""
+        assert run_cli(["kill", "1", "--timeout", "5s"]) == EXIT_USAGE
+        assert "--timeout requires --escalate" in capsys.readouterr().err
+        assert run_cli(["kill", "1", "--escalate", "--via-manager"]) == EXIT_USAGE
+        assert "cannot be combined with --via-manager" in capsys.readouterr().err
+        with pytest.raises(SystemExit):
+            create_parser().parse_args(["kill", "1", "-f", "--escalate"])
+
+    def test_pgid(self, capsys):
+        """Should signal the whole process group given with --pgid."""
+        source = FakeProcessSource([
+            FakeProcess(50, "sh", pgid=50, sid=10),
+            FakeProcess(51, "grep", pgid=50, sid=10, rss_mb=1.0),
+            FakeProcess(60, "sleep", pgid=60, sid=10),
+        ])
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            assert run_cli(["kill", "--pgid", "50", "-y"]) == 0
+        assert source.group_signals == [(50, signal.SIGTERM)]
+        assert set(source.processes) == {60}
+        out = capsys.readouterr().out
+        assert "Sent SIGTERM to process 51 with process group 50" in out
+
+    def test_sid(self, capsys):
+        """Should signal every group of the --sid session but procclean's own."""
+        source = FakeProcessSource([
+            FakeProcess(50, "sh", pgid=50, sid=10),
+            FakeProcess(60, "sleep", pgid=60, sid=10),
+            FakeProcess(os.getpid(), "procclean", pgid=70, sid=10),
+            FakeProcess(80, "vim", pgid=80, sid=20),
+        ])
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            assert run_cli(["kill", "--sid", "10", "--signal", "HUP", "-y"]) == 0
+        assert source.group_signals == [(50, signal.SIGHUP), (60, signal.SIGHUP)]
+        assert "Skipping process group 70" in capsys.readouterr().out
+
+    def test_kernel_thread_skipped(self, capsys):
+        """Should never signal a kernel thread given by PID."""
+        source = FakeProcessSource([
+            FakeProcess(30, "kworker/0:1", [], ppid=KTHREADD_PID, rss_mb=0.0),
+            FakeProcess(40, "sh", ["sh"]),
+        ])
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            run_cli(["kill", "30", "40", "-y"])
+        assert source.signals == [(40, False)]
+        assert "Skipping kernel thread kworker/0:1" in capsys.readouterr().out
+
+    def test_port(self, capsys):
+        """Should kill whatever listens on the --port given."""
+        source = FakeProcessSource([
+            FakeProcess(40, "node", ports=("tcp 0.0.0.0:3000", "tcp [::]:3000")),
+            FakeProcess(41, "vite", ports=("tcp 127.0.0.1:5173",)),
+            FakeProcess(42, "sh"),
+        ])
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            assert run_cli(["kill", "--port", "3000", "-y"]) == 0
+        assert source.signals == [(40, False)]
+        assert run_cli(["kill", "--port", "70000", "-O", "json"]) == EXIT_USAGE
+        assert "invalid port: '70000'" in capsys.readouterr().out
+
+    def test_group_errors(self, capsys):
+        """Should reject --pgid with PIDs, --escalate or --via-manager."""
+        assert run_cli(["kill", "1", "--pgid", "5"]) == EXIT_USAGE
+        assert "--pgid cannot be combined with PIDs" in capsys.readouterr().err
+        assert run_cli(["kill", "--sid", "5", "--escalate"]) == EXIT_USAGE
+        assert "--sid cannot be combined with --escalate" in capsys.readouterr().err
+        with pytest.raises(SystemExit):
+            create_parser().parse_args(["kill", "--pgid", "1", "--sid", "1"])
+
+    def test_pre_kill_veto(self, config_file, fake_source, capsys):
+        """Should skip kills a failing pre-kill command vetoes."""
+        config_file.write_text('[kill]\npre_kill = "false"\n')
+        with patch("procclean.cli.commands.get_source", return_value=fake_source):
+            assert run_cli(["kill", str(FAKE_PID_SERVER), "-y"]) == 1
+        assert fake_source.signals == []
+        assert "pre_kill command failed" in capsys.readouterr().out
+
+    def test_signal_kill(self, fake_source):
+        """Should treat --signal KILL like --force."""
+        with patch("procclean.cli.commands.get_source", return_value=fake_source):
+            run_cli(["kill", str(FAKE_PID_SERVER), "--signal", "SIGKILL", "-y"])
+        assert fake_source.signals == [(FAKE_PID_SERVER, True)]
+
+    def test_signal_errors(self, capsys):
+        """Should reject unknown signals and conflicting options."""
+        assert run_cli(["kill", "1", "--signal", "NOPE", "-O", "json"]) == EXIT_USAGE
+        assert "unknown signal 'NOPE'" in capsys.readouterr().out
+        with pytest.raises(SystemExit):
+            create_parser().parse_args(["kill", "1", "-f", "--signal", "HUP"])
+        args = ["kill", "1", "--signal", "HUP", "--via-manager"]
+        assert run_cli(args) == EXIT_USAGE
+        assert "cannot be combined with --via-manager" in capsys.readouterr().err
+
 
 class TestCmdMemory:
     """Tests for cmd_memory function."""
@@ -608,6 +1032,9 @@ class TestCmdMemory:
             "percent": 50.0,
             "swap_used_gb": 1.0,
             "swap_total_gb": 4.0,
+            "load_avg": (0.5, 0.25, 0.125),
+            "cpu_count": 4,
+            "uptime_seconds": 93780.0,
         }
 
         parser = create_parser()
@@ -625,10 +1052,14 @@ class TestCmdMemory:
         mock_mem.return_value = {
             "total_gb": 16.0,
             "used_gb": 8.0,
+            "shared_gb": 0.5,
             "free_gb": 8.0,
             "percent": 50.0,
             "swap_used_gb": 1.0,
             "swap_total_gb": 4.0,
+            "load_avg": (0.5, 0.25, 0.125),
+            "cpu_count": 4,
+            "uptime_seconds": 93780.0,
         }
 
         parser = create_parser()
@@ -639,8 +1070,507 @@ class TestCmdMemory:
         captured = capsys.readouterr()
         assert "Total:" in captured.out
         assert "Used:" in captured.out
+        assert "Shared: 0.50 GB" in captured.out
         assert "Free:" in captured.out
         assert "Swap:" in captured.out
+        assert "Load:   0.50 0.25 0.12 (4 CPUs)" in captured.out
+        assert "Uptime: 1d 2h 3m" in captured.out
+        assert "OOM:" not in captured.out
+
+    def test_header(self, system_info, capsys):
+        """Should start the summary with the system information."""
+        with (
+            patch("procclean.cli.commands.get_memory_summary", return_value={}),
+            patch("procclean.cli.commands.get_system_info", return_value=system_info),
+        ):
+            run_cli(["mem", "--header", "-f", "json"])
+        data = json.loads(capsys.readouterr().out)
+        assert next(iter(data)) == "header"
+        assert data["header"]["uptime_seconds"] == int(system_info.uptime_seconds)
+
+    def test_oom_kills(self, tmp_path, capsys):
+        """Should report OOM kills since boot when the log is unreadable."""
+        (tmp_path / "vmstat").write_text("oom_kill 2\n")
+        with patch("procclean.cli.commands.get_memory_summary", return_value={}):
+            run_cli(["mem", "-f", "json"])
+        data = json.loads(capsys.readouterr().out)
+        assert data["oom_kills"] == 2  # noqa: PLR2004
+        assert data["last_oom_kill"] is None
+
+    def test_vms(self, capsys):
+        """Should report VM memory including hugepages."""
+        source = FakeProcessSource([
+            FakeProcess(FAKE_PID_SERVER, "qemu-system-x86", username="libvirt-qemu")
+        ])
+        source.processes[FAKE_PID_SERVER].cmdline = ["qemu-kvm", "-name", "win10"]
+        with (
+            patch("procclean.cli.commands.get_memory_summary", return_value={}),
+            patch("procclean.cli.commands.get_source", return_value=source),
+            patch("procclean.core.vm.get_hugetlb_mb", return_value=1024.0),
+        ):
+            run_cli(["mem", "-f", "json"])
+        vm = json.loads(capsys.readouterr().out)["vms"][0]
+        assert (vm["pid"], vm["name"]) == (FAKE_PID_SERVER, "win10")
+        assert vm["hugetlb_mb"] == 1024.0  # noqa: PLR2004
+
+    @pytest.fixture
+    def forked_source(self):
+        """Workers sharing most of their pages with a master.
+
+        Returns:
+            FakeProcessSource: The processes, with PSS and USS.
+        """
+        shared = {"pss": 60.0, "uss": 20.0, "shared": 180.0, "swap": 1.0}
+        return FakeProcessSource([
+            FakeProcess(10, "gunicorn", rss_mb=200.0, memory=shared),
+            FakeProcess(11, "gunicorn", ppid=10, rss_mb=200.0, memory=shared),
+            FakeProcess(12, "postgres", rss_mb=150.0, memory={"pss": 140.0}),
+        ])
+
+    def test_per_process(self, forked_source, capsys):
+        """Should list the processes with the most PSS."""
+        with (
+            patch("procclean.cli.commands.get_memory_summary", return_value={}),
+            patch("procclean.cli.commands.get_source", return_value=forked_source),
+        ):
+            run_cli(["mem", "--per-process", "-n", "2", "-f", "json"])
+        data = json.loads(capsys.readouterr().out)["processes"]
+        assert [p["pid"] for p in data] == [12, 10]
+        assert data[1] == {
+            "pid": 10,
+            "name": "gunicorn",
+            "rss_mb": 200.0,
+            "pss_mb": 60.0,
+            "uss_mb": 20.0,
+            "shared_mb": 180.0,
+            "swap_mb": 1.0,
+        }
+        assert data[0]["uss_mb"] is None
+
+    def test_mem_detail(self, forked_source, capsys):
+        """Should break down the memory of one process."""
+        with patch("procclean.cli.commands.get_source", return_value=forked_source):
+            assert run_cli(["mem-detail", "11"]) == 0
+            out = capsys.readouterr().out
+            assert "gunicorn (PID 11)" in out
+            assert "PSS:    60.0 MB" in out
+            assert "Shared: 180.0 MB" in out
+            assert run_cli(["mem-detail", "99", "-f", "json"]) == 1
+        assert "process_not_found" in capsys.readouterr().out
+
+
+class TestCmdCpu:
+    """Tests for the cpu command."""
+
+    @pytest.fixture
+    def busy_source(self):
+        """Source with one process spinning on a core.
+
+        Returns:
+            FakeProcessSource: The processes.
+        """
+        return FakeProcessSource([
+            FakeProcess(10, "spin", cpu_percent=99.5),
+            FakeProcess(11, "editor", cpu_percent=2.0),
+            FakeProcess(12, "idle"),
+        ])
+
+    def test_pinned_core(self, busy_source, capsys):
+        """Should point at a pinned core and the process behind it."""
+        usage = CoreUsage((3.0, 100.0, 1.0, 0.0))
+        with (
+            patch("procclean.cli.commands.get_source", return_value=busy_source),
+            patch("procclean.cli.commands.core_usage", return_value=usage),
+        ):
+            assert run_cli(["cpu"]) == 0
+        out = capsys.readouterr().out
+        assert "26.0% average over 4 cores" in out
+        assert "Core(s) 1 pinned" in out
+        assert " 99.5%  spin (PID 10)" in out
+        assert "(PID 12)" not in out
+
+    def test_json(self, busy_source, capsys):
+        """Should report cores, state and the busiest processes as JSON."""
+        usage = CoreUsage((90.0, 80.0))
+        with (
+            patch("procclean.cli.commands.get_source", return_value=busy_source),
+            patch("procclean.cli.commands.core_usage", return_value=usage),
+        ):
+            run_cli(["cpu", "-f", "json", "-n", "1"])
+        data = json.loads(capsys.readouterr().out)
+        assert data["state"] == "busy"
+        assert data["pinned"] == [0]
+        assert data["busiest"] == [{"pid": 10, "name": "spin", "cpu_percent": 99.5}]
+
+
+class TestCmdStatus:
+    """Tests for cmd_status function."""
+
+    @pytest.fixture
+    def status_mocks(self, sample_processes):
+        """Patch memory and killable lookups for status output.
+
+        Yields:
+            list: Killable processes returned by the patched filter.
+        """
+        killable = sample_processes[:2]
+        with (
+            patch("procclean.cli.commands.get_memory_summary") as mock_mem,
+            patch("procclean.cli.commands.get_process_list"),
+            patch("procclean.cli.commands.filter_killable") as mock_filter,
+        ):
+            mock_mem.return_value = {"percent": STATUS_PERCENT}
+            mock_filter.return_value = killable
+            yield killable
+
+    def test_parser_defaults(self):
+        """Status command should default to plain output."""
+        args = create_parser().parse_args(["status"])
+        assert args.command == "status"
+        assert args.format == "plain"
+
+    def test_plain_output(self, status_mocks, capsys):
+        """Should print memory percent and killable summary on one line."""
+        args = create_parser().parse_args(["status"])
+        assert cmd_status(args) == 0
+        out = capsys.readouterr().out.strip()
+        assert out == "MEM 80% | 2 killable (800 MB)"
+
+    def test_waybar_output(self, status_mocks, capsys):
+        """Should emit waybar JSON with a state class."""
+        args = create_parser().parse_args(["status", "-f", "waybar"])
+        cmd_status(args)
+        data = json.loads(capsys.readouterr().out)
+        assert data["class"] == "warning"
+        assert data["percentage"] == STATUS_PERCENT
+        assert "2 killable" in data["tooltip"]
+
+    def test_i3status_output(self, status_mocks, capsys):
+        """Should emit an i3bar block with color for warning state."""
+        args = create_parser().parse_args(["status", "-f", "i3status"])
+        cmd_status(args)
+        data = json.loads(capsys.readouterr().out)
+        assert data["full_text"].startswith("MEM 80%")
+        assert "color" in data
+
+
+class TestCmdJobs:
+    """Tests for cmd_jobs function."""
+
+    @pytest.fixture
+    def session_source(self):
+        """Shell session 100 with two background jobs.
+
+        Yields:
+            FakeProcessSource: Source patched in as the CLI backend.
+        """
+        source = FakeProcessSource([
+            FakeProcess(100, "zsh", ["zsh"], sid=100),
+            FakeProcess(101, "sleep", ["sleep", "600"], ppid=100, sid=100),
+            FakeProcess(102, "vite", ["vite", "dev"], ppid=100, sid=100),
+        ])
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            yield source
+
+    def test_lists_numbered_jobs(self, session_source, capsys):
+        """Should number jobs like the shell does."""
+        args = create_parser().parse_args(["jobs", "--session", "100"])
+        assert cmd_jobs(args) == 0
+        lines = capsys.readouterr().out.splitlines()
+        assert lines[0].startswith("[1] 101")
+        assert "sleep 600" in lines[0]
+        assert lines[1].startswith("[2] 102")
+
+    def test_json(self, session_source, capsys):
+        """Should include job numbers in JSON output."""
+        args = create_parser().parse_args(["jobs", "--session", "100", "-f", "json"])
+        cmd_jobs(args)
+        data = json.loads(capsys.readouterr().out)
+        assert [(d["job"], d["pid"]) for d in data] == [(1, 101), (2, 102)]
+
+    def test_kill_by_job_spec(self, session_source, capsys):
+        """Should kill jobs selected with %N."""
+        args = create_parser().parse_args([
+            "jobs",
+            "--session",
+            "100",
+            "--kill",
+            "%2",
+            "-y",
+        ])
+        assert cmd_jobs(args) == 0
+        assert session_source.signals == [(102, False)]
+        assert "[OK] Process 102 terminated" in capsys.readouterr().out
+
+    def test_kill_all(self, session_source):
+        """Should kill every job when --kill has no numbers."""
+        args = create_parser().parse_args(["jobs", "--session", "100", "-k", "-y"])
+        assert cmd_jobs(args) == 0
+        assert sorted(pid for pid, _ in session_source.signals) == [101, 102]
+
+    def test_unknown_job(self, session_source, capsys):
+        """Should warn about job numbers that do not exist."""
+        args = create_parser().parse_args(["jobs", "--session", "100", "-k", "5"])
+        assert cmd_jobs(args) == 1
+        assert "job %5 not found" in capsys.readouterr().out
+        assert session_source.signals == []
+
+    def test_rejects_bad_job_spec(self):
+        """Should reject job specs that are not N or %N."""
+        with pytest.raises(SystemExit):
+            create_parser().parse_args(["jobs", "-k", "%x"])
+
+
+class TestCmdLeftovers:
+    """Tests for cmd_leftovers function."""
+
+    @pytest.fixture
+    def leftovers(self, tmp_path):
+        """Patch leftover detection with a held socket and a stale lock.
+
+        Yields:
+            list[Leftover]: The leftovers returned by the patched scan.
+        """
+        lock = tmp_path / "dev.lock"
+        lock.write_text("999")
+        socket_path = str(tmp_path / "gone" / "dev.sock")
+        items = [
+            Leftover("socket", socket_path, "gone", pid=FAKE_PID_SERVER),
+            Leftover("lock", str(lock), "owner gone", removable=True),
+        ]
+        with patch("procclean.cli.commands.find_leftovers", return_value=items):
+            yield items
+
+    def test_report_only(self, leftovers, capsys):
+        """Should list leftovers without touching anything."""
+        args = create_parser().parse_args(["leftovers"])
+        with patch("procclean.cli.commands.kill_processes") as mock_kill:
+            assert cmd_leftovers(args) == 0
+        mock_kill.assert_not_called()
+        out = capsys.readouterr().out
+        assert f"PID {FAKE_PID_SERVER}" in out
+        assert "--fix" in out
+        assert Path(leftovers[1].path).exists()
+
+    def test_json_report(self, leftovers, capsys):
+        """Should emit leftovers as JSON objects."""
+        args = create_parser().parse_args(["stale", "-f", "json"])
+        cmd_leftovers(args)
+        data = json.loads(capsys.readouterr().out)
+        assert [d["kind"] for d in data] == ["socket", "lock"]
+        assert data[0]["pid"] == FAKE_PID_SERVER
+
+    def test_fix_kills_holders_and_removes_files(self, leftovers, fake_source, capsys):
+        """Should kill socket holders and delete leftover files."""
+        args = create_parser().parse_args(["leftovers", "--fix", "-y"])
+        with (
+            patch("procclean.cli.commands.get_source", return_value=fake_source),
+            patch("procclean.cli.commands.kill_processes") as mock_kill,
+        ):
+            mock_kill.return_value = [(FAKE_PID_SERVER, True, "terminated")]
+            assert cmd_leftovers(args) == 0
+        mock_kill.assert_called_once_with(
+            [FAKE_PID_SERVER], force=False, source=fake_source
+        )
+        assert not Path(leftovers[1].path).exists()
+        assert "[OK] Removed" in capsys.readouterr().out
+
+    @pytest.mark.parametrize("pid", [FAKE_PID_DAEMON, FAKE_PID_OTHER])
+    def test_fix_spares_protected_holders(self, fake_source, pid, capsys):
+        """Should not kill system services or other users' processes."""
+        items = [Leftover("socket", "/run/gone/bus.sock", "gone", pid=pid)]
+        with (
+            patch("procclean.cli.commands.get_source", return_value=fake_source),
+            patch("procclean.cli.commands.find_leftovers", return_value=items),
+        ):
+            assert run_cli(["leftovers", "--fix", "-y"]) == 0
+        assert f"Not killing PID {pid}" in capsys.readouterr().out
+        assert fake_source.signals == []
+
+    def test_fix_skips_vms(self, capsys):
+        """Should leave a VM holding a stale socket alone without --include-vms."""
+        source = FakeProcessSource([FakeProcess(FAKE_PID_SERVER, "qemu-system-x86")])
+        items = [Leftover("socket", "/tmp/gone/qmp.sock", "gone", pid=FAKE_PID_SERVER)]
+        with (
+            patch("procclean.cli.commands.get_source", return_value=source),
+            patch("procclean.cli.commands.find_leftovers", return_value=items),
+        ):
+            assert run_cli(["leftovers", "--fix", "-y"]) == 0
+            assert "Skipping VM" in capsys.readouterr().out
+            assert source.signals == []
+            assert run_cli(["leftovers", "--fix", "-y", "--include-vms"]) == 0
+        assert source.signals == [(FAKE_PID_SERVER, False)]
+
+    def test_fix_simulated_keeps_files(self, leftovers, fake_source, capsys):
+        """Should neither signal nor delete files under --simulate."""
+        with patch("procclean.cli.commands.DEFAULT_SOURCE", fake_source):
+            result = run_cli(["--simulate", "leftovers", "--fix", "-y"])
+        assert result == 0
+        assert fake_source.signals == []
+        assert Path(leftovers[1].path).exists()
+        assert "no files were removed" in capsys.readouterr().out
+
+    def test_nothing_found(self, capsys):
+        """Should say so when there are no leftovers."""
+        args = create_parser().parse_args(["leftovers", "--dir", "/nonexistent"])
+        with patch("procclean.cli.commands.find_leftovers", return_value=[]) as scan:
+            assert cmd_leftovers(args) == 0
+        assert scan.call_args.kwargs["dirs"] == [Path("/nonexistent")]
+        assert "No stale sockets" in capsys.readouterr().out
+
+
+class TestSimulate:
+    """Tests for --simulate and --faults."""
+
+    def test_live_by_default(self, monkeypatch):
+        """Should use the live system without simulation flags."""
+        monkeypatch.delenv(SIMULATE_ENV, raising=False)
+        args = create_parser().parse_args(["list"])
+        assert get_source(args) is None
+
+    def test_faults_imply_simulate(self):
+        """Should build a simulated source from --faults."""
+        args = create_parser().parse_args(["--faults", "eperm:3", "list"])
+        source = get_source(args)
+        assert isinstance(source, SimulatedSource)
+        assert source.plan.eperm_every == FAULT_INTERVAL
+
+    def test_rejects_bad_faults(self):
+        """Should reject unknown fault names at parse time."""
+        with pytest.raises(SystemExit):
+            create_parser().parse_args(["--faults", "boom:2", "list"])
+
+    def test_kill_reports_injected_failures(self, fake_source, capsys):
+        """Should report simulated failures without touching processes."""
+        with patch("procclean.cli.commands.DEFAULT_SOURCE", fake_source):
+            result = run_cli([
+                "--faults",
+                "eperm:2",
+                "kill",
+                str(FAKE_PID_SERVER),
+                str(FAKE_PID_EDITOR),
+                "-y",
+            ])
+        assert result == 1
+        out = capsys.readouterr().out
+        assert f"[OK] Process {FAKE_PID_SERVER} terminated" in out
+        assert f"[FAILED] Access denied for process {FAKE_PID_EDITOR}" in out
+        assert "no signals were sent" in out
+        assert fake_source.signals == []
+
+    def test_tui_picks_up_simulation(self, monkeypatch):
+        """Should pass the simulation to the TUI via the environment."""
+        monkeypatch.setenv(SIMULATE_ENV, "unset")
+        assert run_cli(["--faults", "esrch:2"]) == -1
+        assert os.environ[SIMULATE_ENV] == "esrch:2"
+
+
+class TestScope:
+    """Tests for --scope."""
+
+    def test_wraps_live_source(self, monkeypatch):
+        """Should scope the live system to the chosen cgroup subtree."""
+        monkeypatch.delenv(SIMULATE_ENV, raising=False)
+        args = create_parser().parse_args(["--scope", "system", "list"])
+        source = get_source(args)
+        assert isinstance(source, ScopedSource)
+        assert source.cgroup_prefix == "/system.slice"
+
+    def test_simulation_wraps_scope(self):
+        """Should simulate on top of the scoped source."""
+        args = create_parser().parse_args(["--scope", "system", "--simulate", "list"])
+        source = get_source(args)
+        assert isinstance(source, SimulatedSource)
+        assert isinstance(source.source, ScopedSource)
+
+    def test_rejects_unknown_scope(self):
+        """Should reject scopes outside the known set at parse time."""
+        with pytest.raises(SystemExit):
+            create_parser().parse_args(["--scope", "galaxy", "list"])
+
+    def test_tui_picks_up_scope(self, monkeypatch):
+        """Should pass the scope to the TUI via the environment."""
+        monkeypatch.setenv(SCOPE_ENV, "unset")
+        assert run_cli(["--scope", "user-slice"]) == -1
+        assert os.environ[SCOPE_ENV] == "user-slice"
+
+
+class TestJsonErrors:
+    """Tests for typed error output."""
+
+    def _error(self, capsys):
+        """Parse the JSON error object from stdout.
+
+        Returns:
+            dict: The ``error`` object.
+        """
+        return json.loads(capsys.readouterr().out)["error"]
+
+    def test_invalid_filter(self, capsys):
+        """Should report an unknown filter preset as invalid_filter."""
+        result = run_cli(["list", "-F", "bogus", "-f", "json"])
+        assert result == EXIT_USAGE
+        error = self._error(capsys)
+        assert error["code"] == "invalid_filter"
+        assert "bogus" in error["message"]
+        assert error["context"]["argument"] == "-F/--filter"
+
+    def test_invalid_filter_cmd(self, capsys):
+        """Should report unknown --filter-cmd placeholders as invalid_filter."""
+        result = run_cli(["list", "--filter-cmd", "check {bogus}", "-f", "json"])
+        assert result == EXIT_USAGE
+        error = self._error(capsys)
+        assert error["code"] == "invalid_filter"
+        assert "{bogus}" in error["message"]
+
+    def test_unknown_argument(self, capsys):
+        """Should report unrecognized options as invalid_argument."""
+        assert run_cli(["list", "--bogus", "--format=json"]) == EXIT_USAGE
+        assert self._error(capsys)["code"] == "invalid_argument"
+
+    @patch("procclean.cli.commands.get_process_list")
+    def test_invalid_column(self, mock_get, sample_processes, capsys):
+        """Should list unknown columns and the valid choices."""
+        mock_get.return_value = sample_processes
+        assert run_cli(["list", "-c", "pid,bogus", "-f", "json"]) == EXIT_USAGE
+        error = self._error(capsys)
+        assert error["code"] == "invalid_column"
+        assert error["context"]["columns"] == ["bogus"]
+        assert "pid" in error["context"]["choices"]
+
+    @patch("procclean.cli.commands.get_process_list")
+    def test_text_mode_uses_stderr(self, mock_get, sample_processes, capsys):
+        """Should print errors as text on stderr without --format json."""
+        mock_get.return_value = sample_processes
+        assert run_cli(["list", "-c", "bogus"]) == EXIT_USAGE
+        captured = capsys.readouterr()
+        assert captured.out == ""
+        assert "procclean: error: Unknown column(s): bogus" in captured.err
+
+    def test_text_mode_keeps_argparse_usage(self):
+        """Should keep argparse's usage errors without --format json."""
+        with pytest.raises(SystemExit):
+            run_cli(["list", "-F", "bogus"])
+
+    def test_no_procfs(self, capsys):
+        """Should report a missing /proc as no_procfs."""
+        with patch("procclean.core.process.PROC_ROOT", Path("/nonexistent/proc")):
+            result = run_cli(["mem", "-f", "json"])
+        assert result == 1
+        error = self._error(capsys)
+        assert error["code"] == "no_procfs"
+        assert error["context"]["path"] == "/nonexistent/proc"
+
+    def test_broken_script(self, config_file, capsys):
+        """Should report a user script that fails to load as invalid_config."""
+        scripts = config_file.parent / "scripts"
+        scripts.mkdir()
+        (scripts / "broken.py").write_text("raise RuntimeError('boom')\n")
+        assert run_cli(["mem", "-f", "json"]) == EXIT_USAGE
+        error = self._error(capsys)
+        assert error["code"] == "invalid_config"
+        assert "boom" in error["message"]
+        assert error["context"]["path"] == str(scripts / "broken.py")
 
 
 class TestGetFilteredProcesses:
@@ -658,7 +1588,7 @@ class TestGetFilteredProcesses:
         assert result == sample_processes
 
     @patch("procclean.cli.commands.get_process_list")
-    @patch("procclean.cli.commands.filter_killable")
+    @patch("procclean.core.registry.filter_killable")
     def test_applies_killable_filter(self, mock_filter, mock_get, sample_processes):
         """Should apply killable filter."""
         mock_get.return_value = sample_processes
@@ -685,6 +1615,1504 @@ class TestGetFilteredProcesses:
         mock_filter.assert_called_once_with(sample_processes, "/home")
         assert result == sample_processes[:1]
 
+    @pytest.mark.parametrize(
+        "flags", [["--cpu-hogs"], ["-F", "cpu-hogs"]], ids=["short", "preset"]
+    )
+    @patch("procclean.cli.commands.get_process_list")
+    def test_applies_cpu_hogs_filter(self, mock_get, flags, sample_processes):
+        """Should keep processes above --cpu-hog-threshold."""
+        mock_get.return_value = sample_processes
+        args = create_parser().parse_args([
+            "list",
+            *flags,
+            "--cpu-hog-threshold",
+            str(CLI_CPU_THRESHOLD),
+        ])
+        assert args.cpu_hog_threshold == CLI_CPU_THRESHOLD
+        assert [p.pid for p in get_filtered_processes(args)] == [PID_RUST]
+
+    @patch("procclean.cli.commands.get_process_list")
+    def test_cpu_threshold(self, mock_get, sample_processes):
+        """Should treat --cpu-threshold as the cpu-hogs preset with it."""
+        mock_get.return_value = sample_processes
+        args = create_parser().parse_args([
+            "kill",
+            "--cpu-threshold",
+            str(CLI_CPU_THRESHOLD),
+            "--cpu-interval",
+            "2s",
+        ])
+        assert args.cpu_hogs
+        assert [p.pid for p in get_filtered_processes(args)] == [PID_RUST]
+        assert mock_get.call_args.kwargs["cpu_interval"] == 2.0  # noqa: PLR2004
+        assert FieldMask.CPU in mock_get.call_args.kwargs["fields"]
+
+    def test_cpu_sampled_only_when_shown(self, fake_source):
+        """Should only wait for CPU usage when it is shown or filtered on."""
+        with (
+            patch("procclean.cli.commands.get_source", return_value=fake_source),
+            patch.object(fake_source, "sample_cpu") as sample,
+        ):
+            run_cli(["list", "-c", "pid,name"])
+            sample.assert_not_called()
+            run_cli(["list", "-c", "pid,name", "--min-cpu", "1"])
+            sample.assert_called_once_with(CPU_SAMPLE_SECONDS)
+
+    @patch("procclean.cli.commands.get_process_list")
+    def test_combines_cpu_bounds_with_preset(self, mock_get, sample_processes):
+        """Should apply --max-cpu on top of the high-memory preset."""
+        mock_get.return_value = sample_processes
+        args = create_parser().parse_args([
+            "kill",
+            "-m",
+            "--high-memory-threshold",
+            "400",
+            "--max-cpu",
+            "10",
+        ])
+        # python=500MB at 25% CPU is busy; app=800MB at 5% is idle
+        assert [p.pid for p in get_filtered_processes(args)] == [PID_APP]
+
+    @pytest.mark.parametrize("command", ["list", "kill"])
+    @patch("procclean.cli.commands.get_process_list")
+    def test_age_bounds(self, mock_get, command, make_process):
+        """Should apply --older-than and --newer-than durations."""
+        now = time.time()
+        mock_get.return_value = [
+            make_process(pid=1, create_time=now - 60),
+            make_process(pid=2, create_time=now - 3 * 3600),
+        ]
+        args = create_parser().parse_args([command, "--older-than", "2h"])
+        assert [p.pid for p in get_filtered_processes(args)] == [2]
+        args = create_parser().parse_args([command, "--newer-than", "10m"])
+        assert [p.pid for p in get_filtered_processes(args)] == [1]
+
+    def test_age_bound_rejects_bad_duration(self):
+        """Should reject durations that do not parse."""
+        with pytest.raises(SystemExit):
+            create_parser().parse_args(["list", "--older-than", "soon"])
+
+
+class TestIncludeEnv:
+    """Tests for list --include-env."""
+
+    def test_json_includes_redacted_env(self, capsys):
+        """Should add allowlisted, redacted variables to JSON output."""
+        source = FakeProcessSource([
+            FakeProcess(
+                FAKE_PID_SERVER,
+                "node",
+                env={"NODE_ENV": "production", "API_TOKEN": "x", "HOME": "/h"},
+            )
+        ])
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            run_cli(["list", "-f", "json", "--include-env", "NODE_ENV,API_*"])
+        data = json.loads(capsys.readouterr().out)
+        assert data[0]["env"] == {"API_TOKEN": "<redacted>", "NODE_ENV": "production"}
+
+    def test_env_is_opt_in(self, capsys):
+        """Should not read environments without --include-env."""
+        source = FakeProcessSource([FakeProcess(FAKE_PID_SERVER, "node")])
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            run_cli(["list", "-f", "json"])
+        assert "env" not in json.loads(capsys.readouterr().out)[0]
+
+
+class TestAttribute:
+    """Tests for list --attribute."""
+
+    @pytest.fixture(autouse=True)
+    def _clear_caches(self):
+        """Forget package lookups between tests."""
+        package_owner.cache_clear()
+        exe_digest.cache_clear()
+
+    @pytest.fixture
+    def source(self):
+        """Source with a packaged daemon and a locally built binary.
+
+        Returns:
+            FakeProcessSource: Two-process source.
+        """
+        return FakeProcessSource([
+            FakeProcess(FAKE_PID_SERVER, "sshd", exe="/usr/sbin/sshd"),
+            FakeProcess(FAKE_PID_OTHER, "myserver", exe="/home/u/bin/myserver"),
+        ])
+
+    @staticmethod
+    def _dpkg(args, **_kwargs):
+        """Fake dpkg-query that only knows sshd.
+
+        Returns:
+            subprocess.CompletedProcess: The query result.
+        """
+        if args[-1] == "/usr/sbin/sshd":
+            return subprocess.CompletedProcess(
+                args, 0, "openssh-server: /usr/sbin/sshd\n", ""
+            )
+        return subprocess.CompletedProcess(args, 1, "", "no path found")
+
+    def test_attributes_and_warns(self, source, capsys):
+        """Should report packages and warn about unpackaged binaries."""
+        with (
+            patch("procclean.cli.commands.get_source", return_value=source),
+            patch(
+                "procclean.core.packages._package_query",
+                return_value=("dpkg-query", "-S"),
+            ),
+            patch("procclean.core.packages.subprocess.run", side_effect=self._dpkg),
+            patch("procclean.core.packages.exe_digest", return_value="ab12"),
+        ):
+            run_cli(["list", "-f", "json", "--attribute"])
+        captured = capsys.readouterr()
+        data = {p["pid"]: p for p in json.loads(captured.out)}
+        assert data[FAKE_PID_SERVER]["package"] == "openssh-server"
+        assert data[FAKE_PID_OTHER]["package"] == ""
+        assert data[FAKE_PID_OTHER]["exe_sha256"] == "ab12"
+        assert f"PID {FAKE_PID_OTHER} (myserver) runs an unpackaged" in captured.err
+        assert "sshd" not in captured.err
+
+    def test_without_package_manager(self, source, capsys):
+        """Should warn once and leave processes unattributed."""
+        with (
+            patch("procclean.cli.commands.get_source", return_value=source),
+            patch("procclean.core.packages._package_query", return_value=None),
+        ):
+            run_cli(["list", "-f", "json", "--attribute"])
+        captured = capsys.readouterr()
+        assert "needs dpkg-query or rpm" in captured.err
+        assert all("package" not in p for p in json.loads(captured.out))
+
+    def test_attribution_is_opt_in(self, source, capsys):
+        """Should not query packages without --attribute."""
+        with (
+            patch("procclean.cli.commands.get_source", return_value=source),
+            patch("procclean.core.packages.subprocess.run") as run,
+        ):
+            run_cli(["list", "-f", "json"])
+        run.assert_not_called()
+        assert "package" not in json.loads(capsys.readouterr().out)[0]
+
+
+class TestOutputFile:
+    """Tests for --output-file and --append."""
+
+    @pytest.fixture
+    def source(self):
+        """Source with a single process.
+
+        Returns:
+            FakeProcessSource: Single-process source.
+        """
+        return FakeProcessSource([FakeProcess(FAKE_PID_SERVER, "node")])
+
+    def _list(self, source, *args):
+        """Run ``list`` against the fake source.
+
+        Returns:
+            int: Exit status.
+        """
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            return run_cli(["list", *args])
+
+    def test_infers_format_from_extension(self, source, tmp_path, capsys):
+        """Should write JSON to a .json file and nothing to stdout."""
+        target = tmp_path / "report.json"
+        assert self._list(source, "--output-file", str(target)) == 0
+        assert json.loads(target.read_text())[0]["pid"] == FAKE_PID_SERVER
+        assert not capsys.readouterr().out
+
+    def test_explicit_format_wins(self, source, tmp_path):
+        """Should keep an explicit non-default format."""
+        target = tmp_path / "report.json"
+        self._list(source, "-f", "csv", "--output-file", str(target))
+        assert target.read_text().startswith("pid,")
+
+    def test_unsupported_extension_format(self):
+        """Should fall back to table for formats the command lacks."""
+        args = create_parser().parse_args(["groups", "--output-file", "g.csv"])
+        assert infer_format(args.output_file, args.output_formats) == "table"
+        assert infer_format("report.MD", ("table", "md")) == "md"
+        assert infer_format("syslog:x.json", ("table", "json")) == "table"
+
+    def test_append(self, source, tmp_path):
+        """Should append instead of truncating with --append."""
+        target = tmp_path / "report.csv"
+        self._list(source, "--output-file", str(target))
+        self._list(source, "--output-file", str(target), "--append")
+        headers = [x for x in target.read_text().splitlines() if x.startswith("pid,")]
+        assert len(headers) == 2  # noqa: PLR2004
+
+    def test_append_requires_output_file(self, capsys):
+        """Should reject --append on its own."""
+        assert run_cli(["list", "--append"]) == EXIT_USAGE
+        assert "--append requires --output-file" in capsys.readouterr().err
+
+    def test_unwritable_target(self, source, tmp_path, capsys):
+        """Should report files that cannot be opened as usage errors."""
+        target = tmp_path / "missing" / "report.txt"
+        assert self._list(source, "--output-file", str(target)) == EXIT_USAGE
+        assert "Cannot write output file" in capsys.readouterr().err
+
+    def test_pipe(self, source, tmp_path):
+        """Should feed the report to a command's stdin."""
+        target = tmp_path / "piped.json"
+        pipe = f"|dd of={target} status=none"
+        self._list(source, "-f", "json", "--output-file", pipe)
+        assert json.loads(target.read_text())[0]["name"] == "node"
+
+    def test_syslog(self, source, capsys):
+        """Should log each non-empty line under the given ident."""
+        with patch("procclean.cli.sinks.syslog") as syslog:
+            self._list(source, "-f", "csv", "--output-file", "syslog:cleanup")
+        syslog.openlog.assert_called_once_with(
+            "cleanup", syslog.LOG_PID, syslog.LOG_USER
+        )
+        lines = [c.args[1] for c in syslog.syslog.call_args_list]
+        assert lines[0].startswith("pid,")
+        assert any(line.startswith(f"{FAKE_PID_SERVER},") for line in lines)
+        assert not capsys.readouterr().out
+
+    def test_kill_prompt_stays_on_terminal(self, fake_source, tmp_path, capsys):
+        """Should prompt on the terminal while results go to the file."""
+        target = tmp_path / "kill.txt"
+        with (
+            patch("procclean.cli.commands.DEFAULT_SOURCE", fake_source),
+            patch("sys.stdin.isatty", return_value=True),
+            patch("builtins.input", return_value="y"),
+        ):
+            run_cli([
+                "--simulate",
+                "kill",
+                str(FAKE_PID_SERVER),
+                "--output-file",
+                str(target),
+            ])
+        assert "About to terminate 1 process(es)" in capsys.readouterr().out
+        report = target.read_text()
+        assert f"[OK] Process {FAKE_PID_SERVER} terminated" in report
+        assert "About to terminate" not in report
+
+
+class TestEvery:
+    """Tests for list --every periodic reports."""
+
+    @pytest.fixture
+    def source(self):
+        """Source with a single process.
+
+        Returns:
+            FakeProcessSource: Single-process source.
+        """
+        return FakeProcessSource([FakeProcess(FAKE_PID_SERVER, "node")])
+
+    def test_appends_json_lines(self, source, tmp_path):
+        """Should append one timestamped record per snapshot to a dated file."""
+        target = tmp_path / "%Y%m%d.jsonl"
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            result = run_cli([
+                "list",
+                "--every",
+                "0.01s",
+                "--count",
+                "2",
+                "--output-file",
+                str(target),
+            ])
+        assert result == 0
+        (log,) = tmp_path.glob("*.jsonl")
+        assert log.name[:8].isdigit()
+        records = [json.loads(line) for line in log.read_text().splitlines()]
+        assert len(records) == 2  # noqa: PLR2004
+        assert records[0]["processes"][0]["pid"] == FAKE_PID_SERVER
+        assert "T" in records[0]["timestamp"]
+
+    def test_table_snapshots_to_stdout(self, source, capsys):
+        """Should print a timestamp line above each table snapshot."""
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            run_cli(["list", "--every", "0.01", "--count", "2", "-c", "pid"])
+        out = capsys.readouterr().out
+        assert out.count("# ") == 2  # noqa: PLR2004
+        assert str(FAKE_PID_SERVER) in out
+
+    def test_stops_on_interrupt(self, source):
+        """Should exit cleanly on Ctrl-C."""
+        with (
+            patch("procclean.cli.commands.get_source", return_value=source),
+            patch("procclean.cli.commands.run_every", side_effect=KeyboardInterrupt),
+        ):
+            assert run_cli(["list", "--every", "1m"]) == 0
+
+    def test_count_requires_every(self, capsys):
+        """Should reject --count without --every."""
+        assert run_cli(["list", "--count", "3"]) == EXIT_USAGE
+        assert "--count requires --every" in capsys.readouterr().err
+
+    def test_rejects_bad_interval(self):
+        """Should reject malformed intervals at parse time."""
+        with pytest.raises(SystemExit):
+            create_parser().parse_args(["list", "--every", "soon"])
+
+
+class TestWatchList:
+    """Tests for list --watch live snapshots."""
+
+    @pytest.fixture
+    def source(self):
+        """Source with two processes.
+
+        Returns:
+            FakeProcessSource: Two-process source.
+        """
+        return FakeProcessSource([
+            FakeProcess(FAKE_PID_SERVER, "node", rss_mb=50.0),
+            FakeProcess(FAKE_PID_EDITOR, "nvim", rss_mb=20.0),
+        ])
+
+    def test_jsonl_records(self, source, capsys):
+        """Should stream one timestamped record per process and snapshot."""
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            argv = ["list", "--watch", "0.01", "--count", "2", "-f", "jsonl"]
+            assert run_cli(argv) == 0
+        records = [json.loads(line) for line in capsys.readouterr().out.splitlines()]
+        assert [r["pid"] for r in records] == [FAKE_PID_SERVER, FAKE_PID_EDITOR] * 2
+        assert all("T" in r["timestamp"] for r in records)
+
+    def test_table_redraws(self, source, capsys):
+        """Should clear a terminal before each table and stamp piped ones."""
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            run_cli(["list", "--watch", "0.01", "--count", "2", "-c", "pid"])
+            assert capsys.readouterr().out.count("# ") == 2  # noqa: PLR2004
+            with patch("sys.stdout.isatty", return_value=True):
+                run_cli(["list", "--watch", "0.01", "--count", "1", "-c", "pid"])
+        out = capsys.readouterr().out
+        assert out.startswith(CLEAR_SCREEN)
+        assert str(FAKE_PID_SERVER) in out
+
+    def test_samples_cpu_once(self, source):
+        """Should wait to measure CPU usage for the first snapshot only."""
+        with (
+            patch("procclean.cli.commands.get_source", return_value=source),
+            patch.object(source, "sample_cpu") as sample,
+        ):
+            run_cli(["list", "--watch", "0.01", "--count", "3", "-s", "cpu"])
+        sample.assert_called_once_with(CPU_SAMPLE_SECONDS)
+
+    def test_default_interval(self):
+        """Should refresh every couple of seconds without a value."""
+        args = create_parser().parse_args(["list", "--watch"])
+        assert args.watch == LIST_WATCH_SECONDS
+
+    def test_rejects_every(self, capsys):
+        """Should refuse --watch together with --every."""
+        assert run_cli(["list", "--watch", "--every", "1m"]) == EXIT_USAGE
+        assert "mutually exclusive" in capsys.readouterr().err
+
+
+class TestBudgets:
+    """Tests for the budgets command."""
+
+    @pytest.fixture
+    def source(self, config_file):
+        """Source with two processes over a 150 MB budget on /src/big.
+
+        Returns:
+            FakeProcessSource: Two-process source.
+        """
+        config_file.write_text(
+            '[[budgets]]\npath = "/src/big"\nlimit = 150\naction = "kill"\n'
+        )
+        return FakeProcessSource([
+            FakeProcess(FAKE_PID_SERVER, "node", cwd="/src/big", rss_mb=100),
+            FakeProcess(FAKE_PID_OTHER, "cargo", cwd="/src/big/x", rss_mb=200),
+        ])
+
+    def test_no_budgets(self, capsys):
+        """Should explain how to configure budgets."""
+        assert run_cli(["budgets"]) == 0
+        assert "No memory budgets configured" in capsys.readouterr().out
+
+    def test_warns(self, source, capsys):
+        """Should report offenders without killing unless enforcing."""
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            result = run_cli(["budgets"])
+        assert result == 1
+        out = capsys.readouterr().out
+        assert "/src/big: 300.0 of 150.0 MB (2 processes), over budget" in out
+        assert f"offender {FAKE_PID_OTHER}: cargo (200.0 MB)" in out
+        assert "--enforce" in out
+        assert source.signals == []
+
+    def test_enforce(self, source, capsys):
+        """Should kill offenders of kill budgets with --enforce."""
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            run_cli(["budgets", "--enforce"])
+        assert source.signals == [(FAKE_PID_OTHER, False)]
+        assert "[OK]" in capsys.readouterr().out
+
+    def test_json_every(self, source, capsys):
+        """Should emit one JSON line per check with --every."""
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            result = run_cli([
+                "budgets",
+                "-f",
+                "json",
+                "--every",
+                "0.01s",
+                "--count",
+                "2",
+            ])
+        assert result == 0
+        lines = capsys.readouterr().out.splitlines()
+        assert len(lines) == 2  # noqa: PLR2004
+        (usage,) = json.loads(lines[0])
+        assert usage["exceeded"] is True
+        assert usage["offenders"] == [FAKE_PID_OTHER]
+        assert usage["killed"] == []
+
+
+class TestEmergency:
+    """Tests for the emergency command."""
+
+    @pytest.fixture
+    def source(self, config_file):
+        """Source with two approved node processes and an editor.
+
+        Returns:
+            FakeProcessSource: Three-process source.
+        """
+        config_file.write_text(
+            '[emergency]\nmin_available = "256M"\nkill = ["node *vite"]\n'
+            "max_kills = 1\n"
+        )
+        return FakeProcessSource([
+            FakeProcess(FAKE_PID_SERVER, "node", ["node", "vite"], rss_mb=900),
+            FakeProcess(FAKE_PID_OTHER, "node", ["node", "vite"], rss_mb=100),
+            FakeProcess(FAKE_PID_EDITOR, "nvim", ["nvim"], rss_mb=2000),
+        ])
+
+    def test_not_configured(self, capsys):
+        """Should explain how to opt in."""
+        assert run_cli(["emergency"]) == 0
+        assert "not configured" in capsys.readouterr().out
+
+    def test_quiet_without_pressure(self, source, capsys):
+        """Should do nothing while memory is fine."""
+        with (
+            patch("procclean.cli.commands.get_source", return_value=source),
+            patch(
+                "procclean.cli.commands.read_pressure",
+                return_value=Pressure(None, 4096),
+            ),
+        ):
+            assert run_cli(["emergency"]) == 0
+        assert capsys.readouterr().out == ""
+        assert source.signals == []
+
+    def test_kills_and_logs(self, source, tmp_path):
+        """Should SIGKILL the largest approved process and log it."""
+        log = tmp_path / "emergency.log"
+        with (
+            patch("procclean.cli.commands.get_source", return_value=source),
+            patch(
+                "procclean.cli.commands.read_pressure",
+                return_value=Pressure(None, 100),
+            ),
+        ):
+            result = run_cli([
+                "emergency",
+                "--every",
+                "0.01s",
+                "--count",
+                "2",
+                "--log",
+                str(log),
+            ])
+        assert result == 0
+        assert source.signals == [(FAKE_PID_SERVER, True)]
+        lines = log.read_text().splitlines()
+        assert "Emergency mode armed: available < 256 MB" in lines[0]
+        assert "EMERGENCY: available 100 MB < 256 MB" in lines[1]
+        assert f"Kill {FAKE_PID_SERVER} (node, 900.0 MB): " in lines[2]
+        # The second check falls in the cooldown
+        assert len(lines) == 3  # noqa: PLR2004
+
+
+class TestWatch:
+    """Tests for the watch command."""
+
+    @pytest.fixture
+    def source(self):
+        """Source with a dev server over the high-memory threshold.
+
+        Returns:
+            FakeProcessSource: Two-process source.
+        """
+        return FakeProcessSource([
+            FakeProcess(FAKE_PID_SERVER, "node", ["node", "vite"], rss_mb=900),
+            FakeProcess(FAKE_PID_EDITOR, "nvim", ["nvim"], rss_mb=100),
+        ])
+
+    def test_requires_filter(self, capsys):
+        """Should refuse to run without a filter preset."""
+        assert run_cli(["watch", "--count", "1"]) != 0
+        assert "watch requires --filter" in capsys.readouterr().err
+
+    def test_kills_and_logs_json(self, source, tmp_path):
+        """Should kill matching processes and log every action as JSON."""
+        log = tmp_path / "watch.log"
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            result = run_cli([
+                "watch",
+                "-F",
+                "high-memory",
+                "--count",
+                "1",
+                "--log",
+                str(log),
+                "--log-format",
+                "json",
+            ])
+        assert result == 0
+        assert source.signals == [(FAKE_PID_SERVER, False)]
+        records = [json.loads(line) for line in log.read_text().splitlines()]
+        assert [r["event"] for r in records] == ["start", "kill", "stop"]
+        assert records[0]["filter"] == "high-memory"
+        assert records[0]["dry_run"] is False
+        assert records[1]["pid"] == FAKE_PID_SERVER
+        assert records[1]["ok"] is True
+
+    def test_dry_run(self, source, capsys):
+        """Should only log what each check would kill."""
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            result = run_cli([
+                "watch",
+                "-F",
+                "high-memory",
+                "--interval",
+                "0.01s",
+                "--count",
+                "2",
+                "--dry-run",
+            ])
+        assert result == 0
+        assert source.signals == []
+        out = capsys.readouterr().out
+        assert "(dry run, nothing is killed)" in out
+        line = f"Would kill {FAKE_PID_SERVER} (node, 900.0 MB)"
+        assert out.count(line) == 2  # noqa: PLR2004
+
+
+class TestQuota:
+    """Tests for the quota command."""
+
+    @pytest.fixture
+    def source(self, config_file):
+        """Source where alice runs two processes against a one-process quota.
+
+        Returns:
+            FakeProcessSource: Three-process source.
+        """
+        config_file.write_text(
+            '[quotas]\nexempt = ["root"]\n'
+            '[[quotas.rules]]\nprocesses = 1\nmemory = "1G"\naction = "renice"\n'
+        )
+        return FakeProcessSource([
+            FakeProcess(FAKE_PID_SERVER, "node", username="alice", create_time=1),
+            FakeProcess(FAKE_PID_OTHER, "make", username="alice", create_time=2),
+            FakeProcess(FAKE_PID_DAEMON, "init", username="root"),
+        ])
+
+    def test_no_quotas(self, capsys):
+        """Should explain how to configure quotas."""
+        assert run_cli(["quota"]) == 0
+        assert "No quotas configured" in capsys.readouterr().out
+
+    def test_shows_usage(self, source, capsys):
+        """Should show usage against limits and the newest offender."""
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            result = run_cli(["quota"])
+        assert result == 1
+        out = capsys.readouterr().out
+        assert "alice: 2/1 processes, 200.0/1024.0 MB, over quota" in out
+        assert f"offender {FAKE_PID_OTHER}: make (100.0 MB)" in out
+        assert "root" not in out
+        assert "--enforce" in out
+        assert source.renices == []
+
+    def test_enforce_renices(self, source, capsys):
+        """Should renice offenders of renice quotas with --enforce."""
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            run_cli(["quota", "--enforce"])
+        assert source.renices == [(FAKE_PID_OTHER, QUOTA_NICE)]
+        assert source.signals == []
+        assert "[OK]" in capsys.readouterr().out
+
+    def test_json(self, source, capsys):
+        """Should report usage per user as JSON."""
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            run_cli(["quota", "-f", "json"])
+        (alice,) = json.loads(capsys.readouterr().out)
+        assert alice["user"] == "alice"
+        assert alice["process_limit"] == 1
+        assert alice["offenders"] == [FAKE_PID_OTHER]
+        assert alice["enforced"] == []
+
+    def test_count_requires_every(self, capsys):
+        """Should reject --count without --every."""
+        assert run_cli(["quota", "--count", "3"]) == EXIT_USAGE
+        assert "--count requires --every" in capsys.readouterr().err
+
+
+class TestRepoFilter:
+    """Tests for git repo columns and --repo."""
+
+    def test_list_repo(self, tmp_path, capsys):
+        """Should show the repo and branch and filter by worktree."""
+        repo = tmp_path / "proj"
+        (repo / ".git").mkdir(parents=True)
+        (repo / ".git" / "HEAD").write_text("ref: refs/heads/feature/x\n")
+        source = FakeProcessSource([
+            FakeProcess(FAKE_PID_SERVER, "vite", cwd=str(repo)),
+            FakeProcess(FAKE_PID_OTHER, "vim", cwd=str(tmp_path)),
+        ])
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            run_cli(["list", "-f", "json", "--repo", str(repo / "src")])
+            data = json.loads(capsys.readouterr().out)
+            run_cli(["list", "-c", "pid,repo,branch"])
+        assert [p["pid"] for p in data] == [FAKE_PID_SERVER]
+        assert data[0]["repo"] == str(repo)
+        assert data[0]["branch"] == "feature/x"
+        assert "feature/x" in capsys.readouterr().out
+
+
+class TestEnvDeleted:
+    """Tests for the runtime_env column and the env-deleted preset."""
+
+    def test_filter_env_deleted(self, tmp_path, capsys):
+        """Should list processes from removed virtualenvs with a marker."""
+        gone = str(tmp_path / "gone")
+        source = FakeProcessSource([
+            FakeProcess(FAKE_PID_SERVER, "python", env={"VIRTUAL_ENV": gone}),
+            FakeProcess(FAKE_PID_OTHER, "python", env={"VIRTUAL_ENV": str(tmp_path)}),
+        ])
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            run_cli(["list", "-f", "json", "-F", "env-deleted"])
+            data = json.loads(capsys.readouterr().out)
+            run_cli(["list", "-c", "pid,runtime_env,status", "-F", "env-deleted"])
+        assert [p["pid"] for p in data] == [FAKE_PID_SERVER]
+        assert data[0]["runtime_env"] == f"venv:{gone}"
+        assert data[0]["runtime_env_deleted"] is True
+        assert "[env-deleted]" in capsys.readouterr().out
+
+
+class TestNeedsRestart:
+    """Tests for the needs-restart preset."""
+
+    def test_filter_needs_restart(self, capsys):
+        """Should list processes running replaced code with a marker."""
+        source = FakeProcessSource([
+            FakeProcess(FAKE_PID_SERVER, "nginx", deleted_libs=("/lib/libssl.so.3",)),
+            FakeProcess(FAKE_PID_OTHER, "vim", exe_deleted=True),
+            FakeProcess(FAKE_PID_DAEMON, "bash"),
+        ])
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            run_cli(["list", "-f", "json", "-F", "needs-restart", "-s", "pid", "-a"])
+            data = json.loads(capsys.readouterr().out)
+            run_cli(["list", "-c", "pid,deleted_libs,status", "-F", "needs-restart"])
+        assert [p["pid"] for p in data] == [FAKE_PID_SERVER, FAKE_PID_OTHER]
+        assert data[0]["deleted_libs"] == ["/lib/libssl.so.3"]
+        assert "deleted_libs" not in data[1]
+        out = capsys.readouterr().out
+        assert "libssl.so.3" in out
+        assert "[needs-restart]" in out
+
+
+class TestPorts:
+    """Tests for the listening port filter and column."""
+
+    def test_list_port(self, capsys):
+        """Should list listeners on --port with their ports."""
+        source = FakeProcessSource([
+            FakeProcess(FAKE_PID_SERVER, "node", ports=("tcp 0.0.0.0:3000",)),
+            FakeProcess(FAKE_PID_OTHER, "dnsmasq", ports=("udp 0.0.0.0:53",)),
+            FakeProcess(FAKE_PID_DAEMON, "bash"),
+        ])
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            run_cli(["list", "-f", "json", "-s", "pid", "-a"])
+            data = json.loads(capsys.readouterr().out)
+            run_cli(["list", "--port", ":53", "-c", "pid,name,ports"])
+        assert [p.get("ports") for p in data] == [
+            ["tcp 0.0.0.0:3000"],
+            None,
+            ["udp 0.0.0.0:53"],
+        ]
+        out = capsys.readouterr().out
+        assert "dnsmasq" in out
+        assert "node" not in out
+
+
+class TestPods:
+    """Tests for the Kubernetes pod columns."""
+
+    def test_list_pod_columns(self, tmp_path, monkeypatch, capsys):
+        """Should show pods by UID when their names are unknown."""
+        monkeypatch.setattr("procclean.core.kube.KUBELET_DIR", tmp_path)
+        monkeypatch.setattr("procclean.core.kube.POD_LOG_DIR", tmp_path / "none")
+        source = FakeProcessSource([
+            FakeProcess(FAKE_PID_SERVER, "nginx", cgroup=POD_CGROUP)
+        ])
+        with (
+            patch("procclean.cli.commands.get_source", return_value=source),
+            patch("procclean.core.kube._from_crictl", return_value={}),
+        ):
+            run_cli(["list", "-f", "json"])
+        data = json.loads(capsys.readouterr().out)
+        assert data[0]["pod"] == POD_UID
+        assert "namespace" not in data[0]
+
+
+class TestIncludeVms:
+    """Tests for keeping VMs out of kill targets."""
+
+    @pytest.fixture
+    def source(self):
+        """An orphaned VM next to an orphaned dev server.
+
+        Returns:
+            FakeProcessSource: Two-process source.
+        """
+        return FakeProcessSource([
+            FakeProcess(FAKE_PID_SERVER, "qemu-system-x86"),
+            FakeProcess(FAKE_PID_OTHER, "vite"),
+        ])
+
+    @pytest.mark.parametrize(
+        "command", [["kill", "-k", "-y"], ["clean", "--auto", "-y"]]
+    )
+    def test_skips_vms(self, source, command, capsys):
+        """Should leave VMs alone without --include-vms."""
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            run_cli(command)
+        assert "Skipping VM ? (qemu-system-x86" in capsys.readouterr().out
+        assert [pid for pid, _ in source.signals] == [FAKE_PID_OTHER]
+
+    def test_include_vms(self, source):
+        """Should kill VMs when explicitly included."""
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            run_cli(["kill", str(FAKE_PID_SERVER), "-y", "--include-vms"])
+        assert [pid for pid, _ in source.signals] == [FAKE_PID_SERVER]
+
+
+class TestMediaBadge:
+    """Tests for the media column and status badge."""
+
+    def test_list_media(self, capsys):
+        """Should badge processes holding media devices open."""
+        source = FakeProcessSource([
+            FakeProcess(FAKE_PID_SERVER, "firefox", media=("audio", "camera")),
+            FakeProcess(FAKE_PID_OTHER, "vite"),
+        ])
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            run_cli(["list", "-c", "pid,media,status"])
+        lines = capsys.readouterr().out.splitlines()
+        row = next(line for line in lines if str(FAKE_PID_SERVER) in line)
+        assert "audio,camera" in row
+        assert "[media]" in row
+        assert "[media]" not in next(
+            line for line in lines if str(FAKE_PID_OTHER) in line
+        )
+
+
+class TestInhibitorBadge:
+    """Tests for the inhibitor column and status badge."""
+
+    def test_list_inhibitor(self, monkeypatch, capsys):
+        """Should show the lock a process holds."""
+        monkeypatch.setattr(
+            "procclean.core.inhibit.list_inhibitors",
+            lambda: [Inhibitor("sleep", "brasero", "Burning disc", FAKE_PID_SERVER)],
+        )
+        source = FakeProcessSource([FakeProcess(FAKE_PID_SERVER, "brasero")])
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            run_cli(["list", "-f", "json"])
+        (data,) = json.loads(capsys.readouterr().out)
+        assert data["inhibitor"] == "sleep: Burning disc"
+
+
+class TestWine:
+    """Tests for the wine command."""
+
+    @pytest.fixture
+    def source(self):
+        """Two processes of a Wine prefix.
+
+        Returns:
+            FakeProcessSource: Two-process source.
+        """
+        env = {"WINEPREFIX": "/home/u/.wine"}
+        return FakeProcessSource([
+            FakeProcess(FAKE_PID_SERVER, "wineserver", env=env),
+            FakeProcess(FAKE_PID_OTHER, "notepad.exe", env=env),
+        ])
+
+    def test_lists_groups(self, source, capsys):
+        """Should print one group per prefix."""
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            run_cli(["wine", "-f", "json"])
+        (group,) = json.loads(capsys.readouterr().out)
+        assert (group["app"], group["prefix"]) == ("notepad.exe", "/home/u/.wine")
+        assert len(group["processes"]) == 2  # noqa: PLR2004
+
+    def test_shutdown(self, source, capsys):
+        """Should shut the prefix down by application name."""
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            assert run_cli(["wine", "--shutdown", "notepad.exe", "-y"]) == 0
+        assert source.commands == [
+            ["env", "WINEPREFIX=/home/u/.wine", "wineserver", "-k"]
+        ]
+        assert "wineserver -k" in capsys.readouterr().out
+
+    def test_shutdown_unknown(self, source, capsys):
+        """Should fail when no prefix matches."""
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            assert run_cli(["wine", "--shutdown", "game.exe", "-y"]) != 0
+        assert "No Wine prefix matches" in capsys.readouterr().err
+        assert source.commands == []
+
+
+class TestPlugins:
+    """Tests for external procclean-* subcommands."""
+
+    @pytest.fixture
+    def plugin_dir(self, tmp_path, monkeypatch):
+        """Put a plugin recording its context on PATH.
+
+        Returns:
+            Path: Directory of the plugin; it writes ``out.txt``.
+        """
+        bin_dir = tmp_path / "bin"
+        bin_dir.mkdir()
+        script = bin_dir / "procclean-hello"
+        script.write_text(
+            "#!/bin/sh\n"
+            f'out="{tmp_path}/out.txt"\n'
+            'echo "$*" > "$out"\n'
+            'echo "$PROCCLEAN_FORMAT" >> "$out"\n'
+            'cat "$PROCCLEAN_SNAPSHOT" >> "$out"\n'
+            "exit 3\n"
+        )
+        script.chmod(0o755)
+        monkeypatch.setenv("PATH", f"{bin_dir}{os.pathsep}{os.environ['PATH']}")
+        return tmp_path
+
+    def test_runs_plugin(self, plugin_dir, fake_source):
+        """Should pass arguments, format and snapshot, and return its status."""
+        with patch("procclean.core.process.DEFAULT_SOURCE", fake_source):
+            assert run_cli(["hello", "a", "-f", "json"]) == 3  # noqa: PLR2004
+        args, fmt, snapshot = (plugin_dir / "out.txt").read_text().splitlines()
+        assert (args, fmt) == ("a -f json", "json")
+        pids = {p["pid"] for p in json.loads(snapshot)["processes"]}
+        assert pids == set(fake_source.processes)
+
+    def test_builtin_wins(self, plugin_dir, tmp_path):
+        """Should not run a plugin named like a built-in command."""
+        script = tmp_path / "bin" / "procclean-list"
+        script.write_text("#!/bin/sh\nexit 3\n")
+        script.chmod(0o755)
+        with patch("procclean.cli.commands.get_process_list", return_value=[]):
+            assert run_cli(["list", "-f", "json"]) == 0
+        assert not (plugin_dir / "out.txt").exists()
+
+    def test_lists_plugins(self, plugin_dir, capsys):
+        """Should list the plugins found on PATH."""
+        assert run_cli(["plugins", "-f", "json"]) == 0
+        plugins = json.loads(capsys.readouterr().out)
+        path = plugin_dir / "bin" / "procclean-hello"
+        assert {"name": "hello", "path": str(path)} in plugins
+
+    def test_unknown_command(self, plugin_dir):
+        """Should still reject commands with no plugin."""
+        with pytest.raises(SystemExit):
+            run_cli(["nosuchcommand"])
+
+
+class TestTiming:
+    """Tests for --timing."""
+
+    def test_text(self, fake_source, capsys):
+        """Should print a phase table on stderr, leaving stdout alone."""
+        with patch(
+            "procclean.cli.commands.scoped_source_from_env", return_value=fake_source
+        ):
+            assert run_cli(["--timing", "list", "-f", "csv"]) == 0
+        captured = capsys.readouterr()
+        assert "timing:" not in captured.out
+        for phase in ("scan", "cwd", "sort", "format", "total"):
+            assert f"  {phase}" in captured.err
+
+    def test_json(self, fake_source, capsys):
+        """Should report the timings as JSON with --format json."""
+        with patch(
+            "procclean.cli.commands.scoped_source_from_env", return_value=fake_source
+        ):
+            run_cli(["--timing", "list", "-f", "json"])
+        captured = capsys.readouterr()
+        assert json.loads(captured.out)
+        timing = json.loads(captured.err)["timing"]
+        assert timing["phases"]["scan"]["calls"] > 1
+        assert "format" in timing["phases"]
+
+    def test_columns_limit_reads(self, fake_source, capsys):
+        """Should skip the cwd and environment reads of unused columns."""
+        argv = ["--timing", "list", "-f", "table", "-c", "pid,name,rss_mb"]
+        with patch(
+            "procclean.cli.commands.scoped_source_from_env", return_value=fake_source
+        ):
+            run_cli(argv)
+            lean = capsys.readouterr().err
+            run_cli([*argv[:-1], "pid,cwd"])
+            with_cwd = capsys.readouterr().err
+        assert "  cwd" not in lean
+        assert "  environ" not in lean
+        assert "  cwd" in with_cwd
+
+
+class TestDaemon:
+    """Tests for listing from the snapshot daemon."""
+
+    def test_list_uses_snapshot(self, daemon, fake_source, capsys):
+        """Should list the daemon's snapshot instead of scanning."""
+        with (
+            patch("procclean.core.process.os.getlogin", return_value="user"),
+            patch("procclean.cli.commands.get_process_list") as mock_get,
+        ):
+            assert run_cli(["list", "-f", "json", "--min-memory", "0"]) == 0
+            mock_get.assert_not_called()
+            pids = [p["pid"] for p in json.loads(capsys.readouterr().out)]
+            expected = get_process_list(min_memory_mb=0, source=fake_source)
+            assert sorted(pids) == sorted(p.pid for p in expected)
+
+            mock_get.return_value = []
+            run_cli(["--no-daemon", "list", "-f", "json"])
+            mock_get.assert_called_once()
+
+    def test_parser(self):
+        """Should parse the socket and scan interval."""
+        args = create_parser().parse_args(["daemon", "--interval", "5s"])
+        assert args.interval == 5  # noqa: PLR2004
+        assert args.socket is None
+
+
+class TestExport:
+    """Tests for the Prometheus exporter command."""
+
+    def test_once(self, fake_source, capsys):
+        """Should print the metrics once with --once."""
+        memory = {
+            "total_gb": 16.0,
+            "used_gb": 4.0,
+            "shared_gb": 0.0,
+            "free_gb": 12.0,
+            "percent": 25.0,
+            "swap_used_gb": 0.0,
+            "swap_total_gb": 0.0,
+        }
+        with (
+            patch("procclean.cli.commands.get_source", return_value=fake_source),
+            patch("procclean.core.exporter.get_memory_summary", return_value=memory),
+        ):
+            assert run_cli(["export", "--once", "--top", "2"]) == 0
+        lines = capsys.readouterr().out.splitlines()
+        assert "procclean_memory_used_ratio 0.25" in lines
+        assert 'procclean_processes{user="other"} 1' in lines
+        cpu = [line for line in lines if line.startswith("procclean_process_cpu")]
+        assert len(cpu) == 2  # noqa: PLR2004
+        assert cpu[0].startswith('procclean_process_cpu_percent{pid="200"')
+
+    def test_parser(self):
+        """Should default to localhost and reject malformed addresses."""
+        args = create_parser().parse_args(["export"])
+        assert args.listen == ("127.0.0.1", 9321)
+        args = create_parser().parse_args(["export", "--listen", ":9100"])
+        assert args.listen == ("", 9100)
+        with pytest.raises(SystemExit):
+            create_parser().parse_args(["export", "--listen", "host:none"])
+
+
+class TestSnapshotCache:
+    """Tests for --max-age."""
+
+    def test_max_age_reuses_scan(self, fake_source, cache_file, capsys):
+        """Should reuse the cached scan of an earlier invocation."""
+        argv = ["--max-age", "5s", "list", "-f", "json", "--min-memory", "0"]
+        with (
+            patch("procclean.core.process.DEFAULT_SOURCE", fake_source),
+            patch("procclean.core.process.os.getlogin", return_value="user"),
+        ):
+            run_cli(argv)
+            first = json.loads(capsys.readouterr().out)
+            fake_source.send_signal(FAKE_PID_SERVER, force=True)
+            run_cli(argv)
+            assert json.loads(capsys.readouterr().out) == first
+            run_cli(argv[2:])
+            fresh = json.loads(capsys.readouterr().out)
+        assert cache_file.exists()
+        assert FAKE_PID_SERVER not in [p["pid"] for p in fresh]
+
+
+class TestColor:
+    """Tests for --color."""
+
+    @pytest.mark.parametrize(("mode", "colored"), [("always", True), ("never", False)])
+    def test_list_table(self, fake_source, mode, colored, capsys):
+        """Should color table cells only when asked to."""
+        argv = ["--color", mode, "list", "-c", "pid,name", "--min-memory", "0"]
+        with (
+            patch("procclean.core.process.DEFAULT_SOURCE", fake_source),
+            patch("procclean.core.filters.DEFAULT_SOURCE", fake_source),
+            patch("procclean.core.process.os.getlogin", return_value="user"),
+        ):
+            assert run_cli(argv) == 0
+        out = capsys.readouterr().out
+        assert ("\x1b[33mnode\x1b[0m" in out) is colored
+
+    def test_auto_and_json_stay_plain(self, fake_source, capsys):
+        """Should not color piped output or non-table formats."""
+        with (
+            patch("procclean.core.process.DEFAULT_SOURCE", fake_source),
+            patch("procclean.core.filters.DEFAULT_SOURCE", fake_source),
+            patch("procclean.core.process.os.getlogin", return_value="user"),
+        ):
+            run_cli(["list", "--min-memory", "0"])
+            run_cli(["--color", "always", "list", "-f", "json", "--min-memory", "0"])
+        assert "\x1b[" not in capsys.readouterr().out
+
+    def test_rejects_unknown_mode(self):
+        """Should only accept always, auto and never."""
+        parser = create_parser()
+        assert parser.parse_args(["list"]).color == "auto"
+        with pytest.raises(SystemExit):
+            parser.parse_args(["--color", "sometimes", "list"])
+
+
+class TestHook:
+    """Tests for the shell hook generator."""
+
+    @pytest.mark.parametrize("shell", ["bash", "zsh", "fish"])
+    def test_kills_quietly_in_left_directory(self, shell, capsys):
+        """Should run a quiet killable cleanup with the chosen action."""
+        assert run_cli(["hook", shell, "--action", "yes"]) == 0
+        out = capsys.readouterr().out
+        assert "procclean kill --cwd" in out
+        assert "--filter killable --quiet --yes" in out
+        assert "__procclean_exit" not in out
+        assert "trap" not in out
+
+    def test_on_exit(self, capsys):
+        """Should also clean the current directory on exit with --on-exit."""
+        run_cli(["hook", "zsh", "--on-exit"])
+        out = capsys.readouterr().out
+        assert "--filter killable --quiet --preview" in out
+        assert "add-zsh-hook zshexit __procclean_exit" in out
+
+    @pytest.mark.skipif(not shutil.which("bash"), reason="bash not installed")
+    def test_bash_cleans_directory_left(self, tmp_path, capsys):
+        """Should clean only when leaving a directory for one outside it."""
+        run_cli(["hook", "bash", "--on-exit"])
+        hook = capsys.readouterr().out
+        (tmp_path / "bin").mkdir()
+        fake = tmp_path / "bin" / "procclean"
+        fake.write_text(f'#!/bin/sh\necho "$*" >> {tmp_path / "calls"}\n')
+        fake.chmod(0o755)
+        project = tmp_path / "project"
+        (project / "src").mkdir(parents=True)
+        script = (
+            f"{hook}\n"
+            f"cd {project}; __procclean_prompt\n"
+            "cd src; __procclean_prompt\n"
+            "cd ..; __procclean_prompt\n"
+            f"cd {tmp_path}; __procclean_prompt\n"
+        )
+        env = {**os.environ, "PATH": f"{fake.parent}:{os.environ['PATH']}"}
+        subprocess.run(["bash", "-c", script], env=env, check=True)
+        calls = (tmp_path / "calls").read_text().splitlines()
+        flags = "--filter killable --quiet --preview"
+        assert calls == [
+            f"kill --cwd {project}/src {flags}",
+            f"kill --cwd {project} {flags}",
+            f"kill --cwd {tmp_path} {flags}",
+        ]
+
+
+class TestRun:
+    """Tests for the run command."""
+
+    @pytest.fixture
+    def survivor(self):
+        """A process left behind by the command.
+
+        Returns:
+            FakeProcessSource: Single-process source.
+        """
+        return FakeProcessSource([FakeProcess(FAKE_PID_SERVER, "node")])
+
+    def test_reports_survivors(self, survivor, capsys):
+        """Should list survivors on stderr and pass the exit status through."""
+        result = RunResult(3, get_process_list(min_memory_mb=0, source=survivor))
+        with (
+            patch("procclean.cli.commands.get_source", return_value=survivor),
+            patch("procclean.cli.commands.run_tracked", return_value=result) as run,
+        ):
+            assert run_cli(["run", "--", "make", "-j4", "test"]) == 3  # noqa: PLR2004
+        assert run.call_args.args[0] == ["make", "-j4", "test"]
+        err = capsys.readouterr().err
+        assert "1 process(es) outlived make -j4 test:" in err
+        assert f"{FAKE_PID_SERVER} node (100.0 MB)" in err
+        assert "--kill" in err
+        assert survivor.signals == []
+
+    def test_kills_survivors(self, survivor):
+        """Should terminate survivors with --kill."""
+        result = RunResult(0, get_process_list(min_memory_mb=0, source=survivor))
+        with (
+            patch("procclean.cli.commands.get_source", return_value=survivor),
+            patch("procclean.cli.commands.run_tracked", return_value=result),
+        ):
+            assert run_cli(["run", "--kill", "-f", "--", "true"]) == 0
+        assert survivor.signals == [(FAKE_PID_SERVER, True)]
+
+    def test_clean_run(self, capsys):
+        """Should stay quiet without survivors and map signals to 128 + N."""
+        with patch("procclean.cli.commands.run_tracked", return_value=RunResult(-9)):
+            assert run_cli(["run", "--", "true"]) == 137  # noqa: PLR2004
+        assert capsys.readouterr().err == ""
+
+
+class TestVerify:
+    """Tests for the verify command."""
+
+    @pytest.fixture
+    def manifest(self, tmp_path):
+        """Manifest requiring node and forbidding xmrig.
+
+        Returns:
+            Path: The manifest file.
+        """
+        path = tmp_path / "expected.toml"
+        path.write_text('required = ["node", "postgres"]\nforbidden = ["xmrig"]\n')
+        return path
+
+    @pytest.fixture
+    def source(self):
+        """Source running node and xmrig but no postgres.
+
+        Returns:
+            FakeProcessSource: Two-process source.
+        """
+        return FakeProcessSource([
+            FakeProcess(FAKE_PID_SERVER, "node"),
+            FakeProcess(FAKE_PID_OTHER, "xmrig"),
+        ])
+
+    def test_reports_diff(self, manifest, source, capsys):
+        """Should list violations and exit non-zero."""
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            result = run_cli(["verify", "--manifest", str(manifest)])
+        assert result == 1
+        out = capsys.readouterr().out
+        assert "- missing: name=postgres (found 0, need 1)" in out
+        assert f"+ forbidden: name=xmrig (PID {FAKE_PID_OTHER})" in out
+        assert "FAILED: 2 of 3 manifest rule(s) violated" in out
+
+    def test_json(self, manifest, source, capsys):
+        """Should report the diff as JSON."""
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            result = run_cli(["verify", "--manifest", str(manifest), "-f", "json"])
+        assert result == 1
+        data = json.loads(capsys.readouterr().out)
+        assert data["ok"] is False
+        assert data["missing"] == [{"rule": "name=postgres", "found": 0, "min": 1}]
+        assert data["forbidden"] == [{"rule": "name=xmrig", "pids": [FAKE_PID_OTHER]}]
+
+    def test_passes(self, tmp_path, source, capsys):
+        """Should exit 0 when the manifest is satisfied."""
+        path = tmp_path / "ok.toml"
+        path.write_text('required = ["node"]\n')
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            assert run_cli(["verify", "--manifest", str(path)]) == 0
+        assert "OK: all 1 manifest rule(s) satisfied" in capsys.readouterr().out
+
+    def test_invalid_manifest(self, tmp_path, capsys):
+        """Should report malformed manifests as usage errors."""
+        path = tmp_path / "bad.toml"
+        path.write_text("required = 3\n")
+        assert run_cli(["verify", "--manifest", str(path)]) == EXIT_USAGE
+        assert "required must be a list" in capsys.readouterr().err
+
+
+class TestRedact:
+    """Tests for cmdline redaction and --no-redact."""
+
+    @pytest.fixture
+    def secret_source(self):
+        """Source with a process that has a token on its command line.
+
+        Returns:
+            FakeProcessSource: Single-process source.
+        """
+        return FakeProcessSource([
+            FakeProcess(FAKE_PID_SERVER, "gh", ["gh", "--token", "ghp_secret"])
+        ])
+
+    def test_list_redacts_by_default(self, secret_source, capsys):
+        """Should mask secrets in list output."""
+        with patch("procclean.cli.commands.get_source", return_value=secret_source):
+            run_cli(["list", "-f", "json"])
+        assert "ghp_secret" not in capsys.readouterr().out
+
+    def test_no_redact(self, secret_source, capsys):
+        """Should show command lines verbatim with --no-redact."""
+        with patch("procclean.cli.commands.get_source", return_value=secret_source):
+            run_cli(["--no-redact", "list", "-f", "json"])
+        data = json.loads(capsys.readouterr().out)
+        assert data[0]["argv"] == ["gh", "--token", "ghp_secret"]
+
+    def test_tui_picks_up_no_redact(self, monkeypatch):
+        """Should pass --no-redact to the TUI via the environment."""
+        monkeypatch.setenv(NO_REDACT_ENV, "")
+        assert run_cli(["--no-redact"]) == -1
+        assert os.environ[NO_REDACT_ENV] == "1"
+
+
+class TestSessionFlags:
+    """Tests for --record and --replay."""
+
+    def test_tui_picks_up_record(self, tmp_path, monkeypatch):
+        """Should pass the recording path to the TUI via the environment."""
+        monkeypatch.setenv(RECORD_ENV, "")
+        path = tmp_path / "session.jsonl"
+        assert run_cli(["--record", str(path)]) == -1
+        assert os.environ[RECORD_ENV] == str(path)
+        assert path.exists()
+
+    def test_tui_picks_up_replay(self, tmp_path, monkeypatch):
+        """Should validate the recording and pass it to the TUI."""
+        monkeypatch.setenv(REPLAY_ENV, "")
+        path = tmp_path / "session.jsonl"
+        path.write_text('{"t": 0, "kind": "view", "view": "orphans"}\n')
+        assert run_cli(["--replay", str(path)]) == -1
+        assert os.environ[REPLAY_ENV] == str(path)
+
+    def test_invalid_replay_fails_before_tui(self, tmp_path, capsys):
+        """Should report a corrupt recording instead of starting the TUI."""
+        path = tmp_path / "session.jsonl"
+        path.write_text("garbage\n")
+        assert run_cli(["--replay", str(path)]) == EXIT_USAGE
+        assert "line 1" in capsys.readouterr().err
+
+    def test_rejects_subcommand(self, tmp_path):
+        """Should refuse to record a non-interactive command."""
+        path = tmp_path / "session.jsonl"
+        assert run_cli(["--record", str(path), "list"]) == EXIT_USAGE
+        assert not path.exists()
+
+    def test_record_and_replay_are_exclusive(self, tmp_path):
+        """Should not allow recording and replaying at once."""
+        path = str(tmp_path / "session.jsonl")
+        with pytest.raises(SystemExit):
+            create_parser().parse_args(["--record", path, "--replay", path])
+
+
+class TestClean:
+    """Tests for the clean command."""
+
+    @pytest.fixture
+    def simulated(self, cleanup_source):
+        """Simulated source over the cleanup fixture, patched into the CLI.
+
+        Yields:
+            SimulatedSource: The source ``clean`` will use.
+        """
+        source = SimulatedSource(cleanup_source)
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            yield source
+
+    def test_preview_without_auto(self, simulated, capsys):
+        """Should list every category with a total but kill nothing."""
+        assert run_cli(["clean"]) == 0
+        out = capsys.readouterr().out
+        for category in ("killable", "stale", "dev-leftovers"):
+            assert category in out
+        assert f"{CLEAN_TOTAL_MB:.1f} MB" in out
+        assert "--auto" in out
+        assert not simulated.killed
+
+    def test_auto_kills_all_categories(self, simulated, capsys):
+        """Should kill the consolidated selection and report memory reclaimed."""
+        assert run_cli(["clean", "--auto", "-y"]) == 0
+        assert simulated.killed == {CLEAN_PID_ORPHAN, CLEAN_PID_STALE, CLEAN_PID_DEV}
+        out = capsys.readouterr().out
+        assert f"about {CLEAN_TOTAL_MB:.1f} MB estimated from RSS" in out
+
+    @patch("sys.stdin")
+    @patch("builtins.input", return_value="n")
+    def test_single_confirmation(self, mock_input, mock_stdin, simulated):
+        """Should ask once for all categories and abort on no."""
+        mock_stdin.isatty.return_value = True
+        assert run_cli(["clean", "--auto"]) == 1
+        mock_input.assert_called_once()
+        assert not simulated.killed
+
+    def test_reports_measured_memory(self, cleanup_source, capsys):
+        """Should compare measured memory freed with the RSS estimate."""
+        samples = [
+            {"used_gb": 4.0, "swap_used_gb": 0.0},
+            {"used_gb": 4.0 - CLEAN_TOTAL_MB / 2 / 1024, "swap_used_gb": 0.0},
+        ]
+        with (
+            patch("procclean.cli.commands.get_source", return_value=cleanup_source),
+            patch("procclean.core.memory.get_memory_summary", side_effect=samples),
+            patch.object(cleanup_source, "send_signal"),
+        ):
+            assert run_cli(["clean", "--auto", "-y"]) == 0
+        out = capsys.readouterr().out
+        assert f"{CLEAN_TOTAL_MB / 2:.1f} MB measured" in out
+        assert f"{CLEAN_TOTAL_MB:.1f} MB estimated from RSS (50%)" in out
+
+    def test_nothing_to_clean(self, capsys):
+        """Should say so when no category has targets."""
+        source = FakeProcessSource([FakeProcess(FAKE_PID_SERVER, "node", ppid=42)])
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            assert run_cli(["clean", "--auto", "-y"]) == 0
+        assert "Nothing to clean up" in capsys.readouterr().out
+
+
+class TestNameFilter:
+    """Tests for --name and its matching options."""
+
+    @pytest.mark.parametrize(
+        ("flags", "expected"),
+        [
+            ([], ["node", "nodemon"]),
+            (["-i"], ["node", "nodemon", "Node Helper"]),
+            (["--exact"], ["node"]),
+            (["--starts-with", "-i"], ["node", "nodemon", "Node Helper"]),
+        ],
+        ids=["contains", "ignore-case", "exact", "starts-with"],
+    )
+    @patch("procclean.cli.commands.get_process_list")
+    def test_list_by_name(self, mock_get, flags, expected, make_process):
+        """Should filter list output by name with the chosen semantics."""
+        names = ["node", "nodemon", "Node Helper", "deno"]
+        mock_get.return_value = [
+            make_process(pid=i, name=n) for i, n in enumerate(names)
+        ]
+        args = create_parser().parse_args(["list", "--name", "node", *flags])
+        assert [p.name for p in get_filtered_processes(args)] == expected
+
+    def test_kill_by_name(self, fake_source, capsys):
+        """Should kill the processes matching --name exactly."""
+        with patch("procclean.cli.commands.get_source", return_value=fake_source):
+            result = run_cli(["kill", "--name", "NODE", "-i", "--exact", "-y"])
+        assert result == 0
+        assert fake_source.signals == [(FAKE_PID_SERVER, False)]
+        assert f"Process {FAKE_PID_SERVER} terminated" in capsys.readouterr().out
+
+    def test_kill_by_glob_and_cwd(self, fake_source, capsys):
+        """Should combine a glob --name with --cwd end to end."""
+        argv = ["kill", "--name", "no*", "--cwd", "/home/user/proj", "-y"]
+        with patch("procclean.cli.commands.get_source", return_value=fake_source):
+            assert run_cli(argv) == 0
+        assert fake_source.signals == [(FAKE_PID_SERVER, False)]
+        assert f"Process {FAKE_PID_SERVER} terminated" in capsys.readouterr().out
+
+    @pytest.mark.parametrize(
+        ("argv", "expected"),
+        [
+            (["--name", "^(node|zsh)$", "--regex"], [FAKE_PID_SHELL, FAKE_PID_SERVER]),
+            (["--cmdline", r"\.(js|py)$"], [FAKE_PID_EDITOR, FAKE_PID_SERVER]),
+            (["--cmdline", "SERVER", "-i"], [FAKE_PID_SERVER]),
+            (["--user", "other"], [FAKE_PID_OTHER]),
+        ],
+        ids=["regex", "cmdline", "cmdline-ignore-case", "user"],
+    )
+    def test_list_patterns(self, fake_source, capsys, argv, expected):
+        """Should filter by regex name, command line and owner."""
+        with patch("procclean.cli.commands.get_source", return_value=fake_source):
+            assert run_cli(["list", "-f", "json", "--min-memory", "0", *argv]) == 0
+        pids = [p["pid"] for p in json.loads(capsys.readouterr().out)]
+        assert sorted(pids) == expected
+
+    @patch("procclean.cli.commands.get_process_list", return_value=[])
+    def test_invalid_regex(self, mock_get, capsys):
+        """Should report an invalid regex as a filter error."""
+        assert run_cli(["list", "-f", "json", "--cmdline", "("]) == 2  # noqa: PLR2004
+        error = json.loads(capsys.readouterr().out)["error"]
+        assert error["code"] == "invalid_filter"
+
+    def test_modes_are_exclusive(self):
+        """Should reject --exact together with --starts-with."""
+        with pytest.raises(SystemExit):
+            create_parser().parse_args([
+                "list",
+                "--name",
+                "x",
+                "--exact",
+                "--starts-with",
+            ])
+
+
+class TestConfigDefaults:
+    """Tests for [defaults] and [presets] in the config file."""
+
+    def test_parser_defaults(self, config_file):
+        """Should use the configured defaults unless an option is given."""
+        config_file.write_text(
+            '[defaults]\nsort = "cpu"\nmin_memory = 20\n'
+            "high_memory_threshold = 1000\n"
+        )
+        args = create_parser().parse_args(["list"])
+        assert (args.sort, args.min_memory, args.high_memory_threshold) == (
+            "cpu",
+            20.0,
+            1000.0,
+        )
+        args = create_parser().parse_args(["kill", "--min-memory", "1"])
+        assert (args.sort, args.min_memory) == ("cpu", 1.0)
+
+    @patch("procclean.cli.commands.get_process_list")
+    @patch("procclean.cli.commands.format_output")
+    def test_default_columns(self, mock_format, mock_get, config_file):
+        """Should use the configured columns without --columns or --layout."""
+        mock_get.return_value = []
+        mock_format.return_value = ""
+        config_file.write_text('[defaults]\ncolumns = ["pid", "cmdline"]\n')
+
+        cmd_list(create_parser().parse_args(["list"]))
+        assert mock_format.call_args.kwargs["columns"] == ["pid", "cmdline"]
+        cmd_list(create_parser().parse_args(["list", "-L", "minimal"]))
+        assert mock_format.call_args.kwargs["columns"] == ["pid", "name", "rss_mb"]
+
+    def test_config_preset(self, config_file, fake_source, capsys):
+        """Should offer [presets] tables as --filter choices."""
+        config_file.write_text(
+            '[presets.proj]\ncwd = "/home/user/proj"\nmin_memory = 100\n'
+        )
+        argv = ["list", "-f", "json", "--min-memory", "0", "--filter", "proj"]
+        with patch("procclean.cli.commands.get_source", return_value=fake_source):
+            assert run_cli(argv) == 0
+        pids = [p["pid"] for p in json.loads(capsys.readouterr().out)]
+        assert pids == [FAKE_PID_SERVER]
+
+    def test_invalid_preset(self, config_file, capsys):
+        """Should report malformed presets as config errors."""
+        config_file.write_text('[presets.proj]\nname = "("\nfilter = "none"\n')
+        assert run_cli(["list", "-f", "json"]) == 2  # noqa: PLR2004
+        error = json.loads(capsys.readouterr().out)["error"]
+        assert error["code"] == "invalid_config"
+        assert "presets.proj.filter" in error["message"]
+
 
 class TestGetKillTargets:
     """Tests for _get_kill_targets function."""
@@ -829,6 +3257,19 @@ class TestConfirmKill:
 
         assert result is False
 
+    @patch("sys.stdin")
+    @patch("builtins.input", return_value="n")
+    def test_flags_realtime_processes(
+        self, mock_input, mock_stdin, make_process, capsys
+    ):
+        """Should warn before killing a realtime process."""
+        mock_stdin.isatty.return_value = True
+        proc = replace(make_process(), sched_policy="fifo", rt_priority=80)
+
+        args = create_parser().parse_args(["kill", "123"])
+        _confirm_kill(args, [proc])
+        assert "realtime (fifo, priority 80)" in capsys.readouterr().out
+
     @patch("sys.stdin")
     @patch("builtins.input", return_value="y")
     def test_shows_process_details(
@@ -846,6 +3287,37 @@ class TestConfirmKill:
         assert "python" in captured.out or sample_processes[0].name in captured.out
         assert "MB" in captured.out
 
+    @patch("sys.stdin")
+    @patch("builtins.input")
+    def test_skips_prompt_for_no_confirm_pattern(
+        self, mock_input, mock_stdin, make_process, config_file, capsys
+    ):
+        """Should not ask before killing a single whitelisted process."""
+        mock_stdin.isatty.return_value = True
+        config_file.write_text('[kill]\nno_confirm = ["target/debug/*"]\n')
+        proc = make_process(name="server", argv=["./target/debug/server"])
+
+        args = create_parser().parse_args(["kill", "123"])
+        assert _confirm_kill(args, [proc]) is True
+        mock_input.assert_not_called()
+        assert "target/debug/*" in capsys.readouterr().out
+
+    @patch("sys.stdin")
+    @patch("builtins.input", return_value="n")
+    def test_batches_are_always_confirmed(
+        self, mock_input, mock_stdin, make_process, config_file
+    ):
+        """Should still prompt when several processes match the whitelist."""
+        mock_stdin.isatty.return_value = True
+        config_file.write_text('[kill]\nno_confirm = ["target/debug/*"]\n')
+        procs = [
+            make_process(pid=pid, argv=["./target/debug/server"]) for pid in (1, 2)
+        ]
+
+        args = create_parser().parse_args(["kill", "123"])
+        assert _confirm_kill(args, procs) is False
+        mock_input.assert_called_once()
+
     @patch("sys.stdin")
     @patch("builtins.input", return_value="y")
     def test_shows_more_indicator(
@@ -865,11 +3337,669 @@ class TestConfirmKill:
         assert "more" in captured.out
 
 
+class TestDataLossKill:
+    """Tests for killing processes that risk data loss."""
+
+    @pytest.fixture
+    def source(self):
+        """Provide a database and a plain process owned by the user.
+
+        Returns:
+            FakeProcessSource: Source with postgres (10) and node (20).
+        """
+        return FakeProcessSource([
+            FakeProcess(10, "postgres", ["postgres", "-D", "/var/lib/pg"]),
+            FakeProcess(20, "node", ["node", "server.js"]),
+        ])
+
+    def test_refuses_sigkill(self, source, capsys):
+        """Should refuse --force, --signal KILL and --escalate on databases."""
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            assert run_cli(["kill", "10", "-f", "-y"]) == 1
+            assert "Refusing to SIGKILL postgres" in capsys.readouterr().err
+            assert run_cli(["kill", "10", "--signal", "KILL", "-y"]) == 1
+            assert run_cli(["kill", "10", "--escalate", "-y"]) == 1
+        assert source.signals == []
+
+    def test_yes_does_not_skip_confirmation(self, source, capsys):
+        """Should refuse to SIGTERM a database unasked, even with -y."""
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            assert run_cli(["kill", "10", "20", "-y"]) == 1
+        assert "without confirmation at a terminal" in capsys.readouterr().err
+        assert source.signals == []
+
+    def test_override(self, source):
+        """Should kill anyway with --i-know-what-im-doing."""
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            args = ["kill", "10", "-f", "-y", "--i-know-what-im-doing"]
+            assert run_cli(args) == 0
+        assert source.signals == [(10, True)]
+
+    @pytest.mark.parametrize(
+        ("typed", "killed"), [("postgres", True), ("y", False)], ids=["name", "y"]
+    )
+    @patch("sys.stdin")
+    def test_typing_the_name(self, mock_stdin, source, typed, killed):
+        """Should SIGTERM a database only once its name is typed."""
+        mock_stdin.isatty.return_value = True
+        with (
+            patch("procclean.cli.commands.get_source", return_value=source),
+            patch("builtins.input", side_effect=["y", typed]),
+        ):
+            assert run_cli(["kill", "10"]) == (0 if killed else 1)
+        assert source.signals == ([(10, False)] if killed else [])
+
+    def test_other_signals_are_no_risk(self, source):
+        """Should send signals that do not end the process as usual."""
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            assert run_cli(["kill", "10", "--signal", "HUP", "-y"]) == 0
+        assert source.sent == [(10, signal.SIGHUP)]
+
+    def test_configured_patterns(self, source, config_file):
+        """Should treat processes matching ``[kill] data_loss`` as databases."""
+        config_file.write_text('[kill]\ndata_loss = ["NODE*"]\n')
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            assert run_cli(["kill", "20", "-f", "-y"]) == 1
+        assert source.signals == []
+
+    def test_jobs(self, capsys):
+        """Should refuse SIGKILL and ask for the name when killing jobs."""
+        source = FakeProcessSource([
+            FakeProcess(100, "zsh", ["zsh"], sid=100),
+            FakeProcess(101, "postgres", ["postgres"], ppid=100, sid=100),
+        ])
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            assert run_cli(["jobs", "--session", "100", "-k", "-y", "--force"]) == 1
+            assert "Refusing to SIGKILL postgres" in capsys.readouterr().err
+            assert run_cli(["jobs", "--session", "100", "-k", "-y"]) == 1
+            assert "without confirmation" in capsys.readouterr().err
+        assert source.signals == []
+
+    def test_run_kill(self, source, capsys):
+        """Should not SIGKILL a database left behind by ``run``."""
+        result = RunResult(0, get_process_list(min_memory_mb=0, source=source))
+        with (
+            patch("procclean.cli.commands.get_source", return_value=source),
+            patch("procclean.cli.commands.run_tracked", return_value=result),
+        ):
+            assert run_cli(["run", "--kill", "-f", "--", "true"]) == 1
+            assert "Refusing to SIGKILL postgres" in capsys.readouterr().err
+            assert source.signals == []
+            args = ["run", "--kill", "-f", "--i-know-what-im-doing", "--", "true"]
+            assert run_cli(args) == 0
+        assert sorted(source.signals) == [(10, True), (20, True)]
+
+    def test_leftovers_fix(self, source, capsys):
+        """Should ask before killing a database that holds a stale socket."""
+        items = [Leftover("socket", "/tmp/gone/.s.PGSQL.5432", "gone", pid=10)]
+        with (
+            patch("procclean.cli.commands.get_source", return_value=source),
+            patch("procclean.cli.commands.find_leftovers", return_value=items),
+        ):
+            assert run_cli(["leftovers", "--fix", "-y"]) == 1
+        assert "without confirmation" in capsys.readouterr().err
+        assert source.signals == []
+
+    def test_budgets_spare(self, config_file, capsys):
+        """Should spare databases when enforcing budgets unattended."""
+        config_file.write_text(
+            '[[budgets]]\npath = "/srv"\nlimit = 50\naction = "kill"\n'
+        )
+        source = FakeProcessSource([
+            FakeProcess(10, "postgres", cwd="/srv", rss_mb=200),
+            FakeProcess(20, "node", cwd="/srv", rss_mb=100),
+        ])
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            run_cli(["budgets", "--enforce", "-f", "json"])
+            (usage,) = json.loads(capsys.readouterr().out)
+            assert usage["spared"] == [10]
+            assert usage["killed"] == [20]
+            run_cli(["budgets", "--enforce", "--i-know-what-im-doing"])
+        assert source.signals == [(20, False), (10, False)]
+
+    def test_watch_spare(self, tmp_path):
+        """Should log a spared database once and never kill it."""
+        source = FakeProcessSource([
+            FakeProcess(10, "postgres", ["postgres"], rss_mb=900),
+        ])
+        log = tmp_path / "watch.log"
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            args = ["watch", "-F", "high-memory", "--interval", "0.01s"]
+            assert run_cli([*args, "--count", "2", "--log", str(log)]) == 0
+        assert log.read_text().count("Spare 10 (postgres): risk of data loss") == 1
+        assert source.signals == []
+
+    def test_emergency_spare(self, config_file, tmp_path):
+        """Should not SIGKILL an approved database under memory pressure."""
+        config_file.write_text(
+            '[emergency]\nmin_available = "256M"\nkill = ["postgres*"]\n'
+        )
+        source = FakeProcessSource([
+            FakeProcess(10, "postgres", ["postgres"], rss_mb=900),
+        ])
+        log = tmp_path / "emergency.log"
+        with (
+            patch("procclean.cli.commands.get_source", return_value=source),
+            patch(
+                "procclean.cli.commands.read_pressure",
+                return_value=Pressure(None, 100),
+            ),
+        ):
+            assert run_cli(["emergency", "--log", str(log)]) == 1
+        assert "Spare 10 (postgres): risk of data loss" in log.read_text()
+        assert source.signals == []
+
+
+class TestRestart:
+    """Tests for the restart command."""
+
+    @pytest.fixture
+    def source(self):
+        """Provide a systemd unit, a plain process and a database.
+
+        Returns:
+            FakeProcessSource: Source with nginx (10), node (20), postgres (30).
+        """
+        env = {"PATH": "/usr/bin"}
+        return FakeProcessSource([
+            FakeProcess(10, "nginx", ["nginx"], cgroup="/system.slice/nginx.service"),
+            FakeProcess(20, "node", ["node", "server.js"], cwd="/srv", env=env),
+            FakeProcess(30, "postgres", ["postgres"], env=env),
+        ])
+
+    def test_unit_and_pid(self, source, capsys):
+        """Should restart a unit via systemctl and respawn a plain PID."""
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            assert run_cli(["restart", "nginx", "20", "-y", "--timeout", "0.05"]) == 0
+        out = capsys.readouterr().out
+        assert "Restarted via `systemctl restart nginx.service`" in out
+        assert "Restarted PID 20 as PID 31" in out
+        assert source.commands == [["systemctl", "restart", "nginx.service"]]
+        env = {"PATH": "/usr/bin"}
+        assert source.spawned == [(["node", "server.js"], "/srv", env, "user")]
+
+    def test_json(self, source, capsys):
+        """Should report results as JSON."""
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            assert run_cli(["restart", "20", "-y", "-f", "json"]) == 0
+        (result,) = json.loads(capsys.readouterr().out)
+        assert result["ok"] is True
+        assert result["new_pid"] == 31  # noqa: PLR2004
+
+    def test_not_found(self, source, capsys):
+        """Should fail for unknown PIDs and units without processes."""
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            assert run_cli(["restart", "99", "-y"]) == 1
+            assert run_cli(["restart", "redis", "-y", "-f", "json"]) == 1
+        out = capsys.readouterr()
+        assert "Process 99 not found" in out.err
+        assert json.loads(out.out)["error"]["code"] == "process_not_found"
+
+    def test_refuses_data_loss(self, source, capsys):
+        """Should not respawn a database, which may take SIGKILL."""
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            assert run_cli(["restart", "30", "-y"]) == 1
+        assert "Refusing to SIGKILL postgres" in capsys.readouterr().err
+        assert source.signals == []
+
+    @patch("sys.stdin")
+    def test_prompt(self, mock_stdin, source, capsys):
+        """Should show the plan and abort unless confirmed."""
+        mock_stdin.isatty.return_value = True
+        with (
+            patch("procclean.cli.commands.get_source", return_value=source),
+            patch("builtins.input", return_value="n"),
+        ):
+            assert run_cli(["restart", "20"]) == 1
+        assert "kill, then run `node server.js` in /srv" in capsys.readouterr().out
+        assert source.signals == []
+
+
+class TestRenice:
+    """Tests for the renice command."""
+
+    def test_default_nice(self, fake_source, capsys):
+        """Should give the lowest CPU priority when no option is given."""
+        with patch("procclean.cli.commands.get_source", return_value=fake_source):
+            assert run_cli(["renice", str(FAKE_PID_SERVER)]) == 0
+        assert fake_source.renices == [(FAKE_PID_SERVER, RENICE_NICE)]
+        assert fake_source.ionices == []
+        assert f"reniced to {RENICE_NICE}" in capsys.readouterr().out
+
+    def test_ionice_only(self, fake_source, capsys):
+        """Should leave the nice value alone when only --ionice is given."""
+        with patch("procclean.cli.commands.get_source", return_value=fake_source):
+            assert run_cli(["renice", str(FAKE_PID_SERVER), "--ionice", "idle"]) == 0
+        assert fake_source.renices == []
+        assert fake_source.ionices == [(FAKE_PID_SERVER, 3, None)]
+        assert "I/O priority set to idle" in capsys.readouterr().out
+
+    def test_json_and_failures(self, fake_source, capsys):
+        """Should report each change, and fail if any was refused."""
+        argv = ["renice", str(FAKE_PID_SERVER), str(FAKE_PID_DAEMON), "-n", "5"]
+        with patch("procclean.cli.commands.get_source", return_value=fake_source):
+            assert run_cli([*argv, "--ionice", "best-effort:7", "-f", "json"]) == 1
+        results = json.loads(capsys.readouterr().out)
+        assert [(r["pid"], r["action"], r["ok"]) for r in results] == [
+            (FAKE_PID_SERVER, "nice", True),
+            (FAKE_PID_SERVER, "ionice", True),
+            (FAKE_PID_DAEMON, "nice", False),
+            (FAKE_PID_DAEMON, "ionice", False),
+        ]
+        assert fake_source.renices == [(FAKE_PID_SERVER, 5)]
+
+    @pytest.mark.parametrize(
+        ("option", "error"),
+        [
+            ("--nice=20", "invalid nice value '20' (expected -20 to 19)"),
+            ("--nice=-21", "invalid nice value '-21'"),
+            ("--nice=low", "invalid nice value 'low'"),
+            ("--ionice=low", "unknown I/O class 'low'"),
+            ("--ionice=idle:3", "invalid I/O priority 'idle:3'"),
+        ],
+    )
+    def test_invalid(self, option, error, capsys):
+        """Should reject nice values and I/O priorities that do not exist."""
+        assert run_cli(["renice", "1", option, "-O", "json"]) == EXIT_USAGE
+        assert error in capsys.readouterr().out
+
+
+class TestHistory:
+    """Tests for the audit log and the history command."""
+
+    @pytest.fixture
+    def source(self):
+        """Provide two plain processes and a protected one.
+
+        Returns:
+            FakeProcessSource: Source with node (20), vim (21) and sshd (22).
+        """
+        return FakeProcessSource([
+            FakeProcess(20, "node", ["node", "server.js"], cwd="/srv"),
+            FakeProcess(21, "vim", ["vim"]),
+            FakeProcess(22, "sshd", ["sshd"], protected=True),
+        ])
+
+    def test_kill_then_history(self, audit_file, source, capsys):
+        """Should list CLI kills with the command line that made them."""
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            assert run_cli(["kill", "20", "-y"]) == 0
+        capsys.readouterr()
+        assert run_cli(["history", "-f", "json"]) == 0
+        (record,) = json.loads(capsys.readouterr().out)
+        assert (record["pid"], record["name"], record["cwd"]) == (20, "node", "/srv")
+        assert (record["origin"], record["filter"]) == ("cli", "kill 20 -y")
+        assert record["signal"] == "SIGTERM"
+        assert audit_file.exists()
+
+    def test_text_and_filters(self, audit_file, source, capsys):
+        """Should print records and narrow them down."""
+        with patch("procclean.cli.commands.get_source", return_value=source):
+            run_cli(["kill", "20", "21", "22", "-y"])
+        capsys.readouterr()
+        assert run_cli(["history"]) == 0
+        out = capsys.readouterr().out
+        assert "[OK]  SIGTERM  20 node (100.0 MB)" in out
+        assert "from cli: kill 20 21 22 -y" in out
+        assert run_cli(["history", "--failed"]) == 0
+        out = capsys.readouterr().out
+        assert "sshd" in out
+        assert "node" not in out
+        run_cli(["history", "-n", "1", "-f", "json"])
+        assert [r["pid"] for r in json.loads(capsys.readouterr().out)] == [22]
+        run_cli(["history", "--name", "vim", "--since", "1h", "-f", "json"])
+        assert [r["pid"] for r in json.loads(capsys.readouterr().out)] == [21]
+
+    def test_empty(self, audit_file, capsys):
+        """Should say where nothing was recorded, or that logging is off."""
+        assert run_cli(["history"]) == 0
+        assert f"No actions recorded in {audit_file}" in capsys.readouterr().out
+        with patch.dict(os.environ, {AUDIT_ENV: ""}):
+            assert run_cli(["history"]) == 0
+        assert "audit log is turned off" in capsys.readouterr().out
+
+
+class TestDoctor:
+    """Tests for the capability report and the startup warning."""
+
+    @pytest.fixture
+    def hidden(self):
+        """Provide a report from a /proc mounted with hidepid=2.
+
+        Returns:
+            CapabilityReport: procfs available, other users hidden.
+        """
+        return CapabilityReport(
+            "linux",
+            1000,
+            "user",
+            (
+                Capability("procfs", True, "/proc is mounted"),
+                Capability("hidepid", False, "hidden (hidepid=2)", restricted=True),
+                Capability("others", False, "unreadable", ("cwd",)),
+            ),
+        )
+
+    def test_table(self, hidden, capsys):
+        """Should list every check and fail for a restricted environment."""
+        with patch("procclean.cli.commands.capability_report", return_value=hidden):
+            assert run_cli(["doctor"]) == 1
+        out = capsys.readouterr().out
+        assert "running as user (UID 1000)" in out
+        assert "[OK]      procfs   /proc is mounted" in out
+        assert "[MISSING] hidepid  hidden (hidepid=2)" in out
+        assert "Columns affected: cwd" in out
+        assert "Environment:" in out
+        assert "Python " in out
+
+    def test_fix_and_config(self, hidden, config_file, capsys):
+        """Should print fixes, and check the config even when it is broken."""
+        config_file.write_text("[defaults]\nsort = 'bogus'\n")
+        fixed = replace(hidden.capabilities[1], fix="Run as root")
+        report = replace(hidden, capabilities=(hidden.capabilities[0], fixed))
+        with patch("procclean.cli.commands.capability_report", return_value=report):
+            assert run_cli(["doctor"]) == 1
+        out = capsys.readouterr().out
+        assert "Fix: Run as root" in out
+        assert "[MISSING] config" in out
+        assert "defaults.sort" in out
+        assert f"move {config_file} aside" in out
+
+    def test_valid_config_passes(self, capsys):
+        """Should not fail for an unrestricted system without a config file."""
+        report = CapabilityReport(
+            "linux", 0, "root", (Capability("procfs", True, "mounted"),)
+        )
+        with patch("procclean.cli.commands.capability_report", return_value=report):
+            assert run_cli(["doctor", "-f", "json"]) == 0
+        data = json.loads(capsys.readouterr().out)
+        config = data["environment"][-1]
+        assert (config["key"], config["ok"]) == ("config", True)
+        assert data["python"]
+
+    def test_json(self, hidden, capsys):
+        """Should describe the report as JSON."""
+        with patch("procclean.cli.commands.capability_report", return_value=hidden):
+            run_cli(["doctor", "-f", "json"])
+        data = json.loads(capsys.readouterr().out)
+        assert [c["key"] for c in data["capabilities"]] == [
+            "procfs",
+            "hidepid",
+            "others",
+        ]
+        assert data["missing_columns"] == ["cwd"]
+
+    def test_runs_without_procfs(self, capsys):
+        """Should report a missing /proc instead of failing on it."""
+        missing = Path("/nonexistent/proc")
+        with (
+            patch("procclean.core.process.PROC_ROOT", missing),
+            patch("procclean.cli.commands.capability_report") as report,
+        ):
+            report.return_value = capability_report(missing)
+            assert run_cli(["doctor"]) == 1
+        assert "/nonexistent/proc is not mounted" in capsys.readouterr().out
+
+    def test_startup_warning(self, hidden, monkeypatch, capsys):
+        """Should warn on stderr before running a command in a restricted /proc."""
+        monkeypatch.delenv(NO_CAPABILITY_WARNING_ENV)
+        with patch(
+            "procclean.core.capabilities.capability_report", return_value=hidden
+        ):
+            run_cli(["history"])
+        err = capsys.readouterr().err
+        assert "Warning: hidden (hidepid=2)" in err
+        assert "procclean doctor" in err
+
+
+class TestConfigCommand:
+    """Tests for config show, validate and edit."""
+
+    def test_show(self, config_file, capsys):
+        """Should list effective settings with their sources."""
+        config_file.write_text("[display]\nrow_cap = 100\n")
+        assert run_cli(["config", "show"]) == 0
+        out = capsys.readouterr().out
+        assert f"Config file: {config_file}" in out
+        assert f"display.row_cap      = 100  # {config_file}" in out
+        assert "display.natural_sort = true  # default" in out
+
+    def test_show_json(self, config_file, capsys):
+        """Should describe the settings as JSON."""
+        run_cli(["config", "show", "-f", "json"])
+        data = json.loads(capsys.readouterr().out)
+        assert data["path"] == str(config_file)
+        assert not data["exists"]
+        assert {"key": "kill.protected", "value": [], "source": "default"} in (
+            data["settings"]
+        )
+
+    def test_validate(self, config_file, capsys):
+        """Should accept a missing or valid file."""
+        assert run_cli(["config", "validate"]) == 0
+        assert "defaults apply" in capsys.readouterr().out
+        config_file.write_text("[display]\nnatural_sort = false\n")
+        assert run_cli(["config", "validate"]) == 0
+        assert f"{config_file} is valid." in capsys.readouterr().out
+
+    def test_validate_problems(self, config_file, capsys):
+        """Should list every problem, even ones that stop other commands."""
+        config_file.write_text(
+            "[defaults]\nsort = 'bogus'\n[dispaly]\n[layouts]\nwide = 'nope'\n"
+        )
+        assert run_cli(["config", "validate"]) == 1
+        out = capsys.readouterr().out
+        assert "has 3 problem(s)" in out
+        assert "did you mean display?" in out
+        assert "defaults.sort" in out
+        run_cli(["config", "validate", "-f", "json"])
+        data = json.loads(capsys.readouterr().out)
+        assert not data["valid"]
+        assert data["problems"][0]["context"] == {"key": "dispaly"}
+
+    @staticmethod
+    def _editor(*contents):
+        """Fake an editor that writes each content in turn.
+
+        Returns:
+            Callable: Replacement for subprocess.run.
+        """
+        queue = list(contents)
+
+        def run(argv, check):
+            Path(argv[-1]).write_text(queue.pop(0))
+            return subprocess.CompletedProcess(argv, 0)
+
+        return run
+
+    def test_edit_saves_valid(self, config_file, monkeypatch, capsys):
+        """Should open $EDITOR on a copy and save it once valid."""
+        monkeypatch.setenv("EDITOR", "nano -w")
+        monkeypatch.delenv("VISUAL", raising=False)
+        editor = self._editor("[display]\nrow_cap = 10\n")
+        with patch("procclean.cli.commands.subprocess.run", side_effect=editor) as run:
+            assert run_cli(["config", "edit"]) == 0
+        assert run.call_args.args[0][:2] == ["nano", "-w"]
+        assert config_file.read_text() == "[display]\nrow_cap = 10\n"
+        assert f"Saved {config_file}" in capsys.readouterr().out
+        assert list(config_file.parent.glob(".config-*")) == []
+
+    @patch("sys.stdin")
+    def test_edit_discards_invalid(self, mock_stdin, config_file, capsys):
+        """Should keep the old file when the edit is invalid and not retried."""
+        mock_stdin.isatty.return_value = False
+        config_file.write_text("[display]\nrow_cap = 10\n")
+        editor = self._editor("[display]\nrow_cap = -1\n")
+        with patch("procclean.cli.commands.subprocess.run", side_effect=editor):
+            assert run_cli(["config", "edit"]) == 1
+        assert config_file.read_text() == "[display]\nrow_cap = 10\n"
+        out = capsys.readouterr().out
+        assert "Invalid display.row_cap" in out
+        assert "Changes discarded" in out
+
+    @patch("sys.stdin")
+    @patch("builtins.input", return_value="")
+    def test_edit_again(self, mock_input, mock_stdin, config_file):
+        """Should reopen the editor until the config is valid."""
+        mock_stdin.isatty.return_value = True
+        editor = self._editor("[kill\n", "[kill]\nprotected = ['db']\n")
+        with patch("procclean.cli.commands.subprocess.run", side_effect=editor):
+            assert run_cli(["config", "edit"]) == 0
+        mock_input.assert_called_once()
+        assert config_file.read_text() == "[kill]\nprotected = ['db']\n"
+
+
+class TestColumnsCommand:
+    """Tests for the columns command."""
+
+    def test_table(self, capsys):
+        """Should list every column, marking defaults and slow ones."""
+        assert run_cli(["columns"]) == 0
+        lines = capsys.readouterr().out.splitlines()
+        assert lines[0].split() == ["Key", "Header", "Type", "Slow", "Description"]
+        keys = [line.split()[0] for line in lines[1 : len(COLUMNS) + 1]]
+        assert keys[0] == "pid*"
+        assert {k.rstrip("*") for k in keys} == set(COLUMNS)
+        ports = next(line for line in lines if line.startswith("ports "))
+        assert ports.split()[2:4] == ["list", "yes"]
+
+    def test_json(self, capsys):
+        """Should describe the columns as JSON."""
+        assert run_cli(["columns", "-f", "json"]) == 0
+        data = json.loads(capsys.readouterr().out)
+        assert [c["key"] for c in data] == list(COLUMNS)
+        assert data[0] == {
+            "key": "pid",
+            "header": "PID",
+            "type": "int",
+            "description": "Process ID",
+            "slow": False,
+            "default": True,
+        }
+
+
+class TestFmtCommand:
+    """Tests for designing list columns with fmt."""
+
+    def test_print(self, config_file, capsys):
+        """Should spell the columns and format as list options."""
+        config_file.write_text('[defaults]\ncolumns = "pid,rss_mb:gb"\n')
+        assert run_cli(["fmt"]) == 0
+        assert capsys.readouterr().out == "procclean list --columns pid,rss_mb:gb\n"
+        assert run_cli(["fmt", "-c", "pid, cwd:40", "-f", "md"]) == 0
+        out = capsys.readouterr().out
+        assert out == "procclean list --columns pid,cwd:40 --format md\n"
+
+    def test_save(self, config_file, capsys):
+        """Should write [defaults] columns, unless the file would be invalid."""
+        config_file.write_text("# Mine\n[display]\nrow_cap = 10\n")
+        assert run_cli(["fmt", "-c", "pid,rss_mb:gb", "--save"]) == 0
+        assert f"Saved {config_file}." in capsys.readouterr().out
+        assert config_file.read_text() == (
+            "# Mine\n[display]\nrow_cap = 10\n\n"
+            '[defaults]\ncolumns = "pid,rss_mb:gb"\n'
+        )
+        assert default_columns() == ["pid", "rss_mb:gb"]
+        config_file.write_text("[kill]\nprotected = 1\n")
+        assert run_cli(["fmt", "-c", "pid", "--save"]) == 1
+        assert "kill.protected" in capsys.readouterr().out
+        assert config_file.read_text() == "[kill]\nprotected = 1\n"
+
+    def test_interactive_needs_terminal(self, capsys):
+        """Should refuse --interactive without a terminal."""
+        assert run_cli(["fmt", "--interactive"]) != 0
+        assert "needs a terminal" in capsys.readouterr().err
+
+
+class TestHarden:
+    """Tests for scanning in a sandboxed child with --harden."""
+
+    def test_flag(self, monkeypatch, capsys):
+        """Should sandbox scans and keep them away from the daemon."""
+        monkeypatch.setenv(HARDEN_ENV, "0")
+        monkeypatch.setenv(NO_DAEMON_ENV, "")
+        assert run_cli(["fmt"]) == 0
+        assert os.environ[HARDEN_ENV] == "0"
+        assert run_cli(["--harden", "fmt"]) == 0
+        assert os.environ[HARDEN_ENV] == os.environ[NO_DAEMON_ENV] == "1"
+        capsys.readouterr()
+
+    def test_unsupported(self, monkeypatch, capsys):
+        """Should refuse to run unconfined when hardening was asked for."""
+        monkeypatch.setenv(HARDEN_ENV, "1")
+        with patch("procclean.core.sandbox.platform.machine", return_value="mips"):
+            assert run_cli(["fmt"]) != 0
+        assert "--harden needs Linux on x86_64 or aarch64" in capsys.readouterr().err
+
+
+class TestPolkit:
+    """Tests for the --polkit flag and the polkit command."""
+
+    def test_policy(self, monkeypatch, capsys):
+        """Should enable the helper and print its polkit action."""
+        monkeypatch.setenv(POLKIT_ENV, "")
+        helper = "/opt/procclean/bin/procclean-helper"
+        assert run_cli(["--polkit", "polkit", "--helper", helper]) == 0
+        assert os.environ[POLKIT_ENV] == "1"
+        out = capsys.readouterr().out
+        assert f'<action id="{POLKIT_ACTION}">' in out
+        assert f">{helper}</annotate>" in out
+
+    @pytest.mark.parametrize(
+        ("argv", "error"),
+        [
+            (["--helper", "bin/helper"], "--helper must be an absolute path"),
+            ([], "procclean-helper is not installed"),
+        ],
+    )
+    def test_no_helper(self, capsys, argv, error):
+        """Should refuse a relative helper, or none found on $PATH."""
+        with patch("procclean.core.polkit.shutil.which", return_value=None):
+            assert run_cli(["polkit", *argv]) != 0
+        assert error in capsys.readouterr().err
+
+
+class TestSupervisedKill:
+    """Tests for stopping supervised processes via their manager."""
+
+    @patch("sys.stdin")
+    @patch("builtins.input", return_value="m")
+    def test_prompt_offers_manager(self, mock_input, mock_stdin, fake_source, capsys):
+        """Should show the respawn note and accept m as stop-via-manager."""
+        mock_stdin.isatty.return_value = True
+        args = create_parser().parse_args(["kill", str(FAKE_PID_DAEMON)])
+        procs = _get_kill_targets(args, fake_source)
+        assert _confirm_kill(args, procs, fake_source) == VIA_MANAGER
+        out = capsys.readouterr().out
+        assert f"will likely respawn via systemd ({GSD_COLOR_UNIT})" in out
+        assert "m = stop via manager" in mock_input.call_args.args[0]
+
+    @patch("sys.stdin")
+    @patch("builtins.input", return_value="m")
+    def test_m_ignored_without_manager(self, mock_input, mock_stdin, fake_source):
+        """Should treat m as no when no target is supervised."""
+        mock_stdin.isatty.return_value = True
+        args = create_parser().parse_args(["kill", str(FAKE_PID_SERVER)])
+        procs = _get_kill_targets(args, fake_source)
+        assert _confirm_kill(args, procs, fake_source) is False
+
+    def test_via_manager_flag(self, fake_source, capsys):
+        """Should run the manager's stop command instead of signaling."""
+        with patch("procclean.cli.commands.get_source", return_value=fake_source):
+            result = run_cli(["kill", str(FAKE_PID_DAEMON), "--via-manager", "-y"])
+        assert result == 0
+        assert fake_source.signals == []
+        assert fake_source.commands == [
+            ["systemctl", "--user", "stop", GSD_COLOR_UNIT]
+        ]
+        assert "[OK] Stopped via `systemctl --user stop" in capsys.readouterr().out
+
+
 class TestCmdKillPreview:
     """Tests for cmd_kill preview mode."""
 
     @patch("procclean.cli.commands.get_process_list")
-    @patch("procclean.cli.commands.filter_killable")
+    @patch("procclean.core.registry.filter_killable")
     @patch("procclean.cli.commands.format_output")
     def test_preview_does_not_kill(
         self, mock_format, mock_filter, mock_get, sample_processes, capsys
@@ -889,7 +4019,7 @@ class TestCmdKillPreview:
         assert "would be killed" in captured.out
 
     @patch("procclean.cli.commands.get_process_list")
-    @patch("procclean.cli.commands.filter_killable")
+    @patch("procclean.core.registry.filter_killable")
     @patch("procclean.cli.commands.format_output")
     def test_preview_json_format(
         self, mock_format, mock_filter, mock_get, sample_processes
@@ -935,11 +4065,33 @@ class TestRunCli:
         mock_mem.return_value = {
             "total_gb": 16.0,
             "used_gb": 8.0,
+            "shared_gb": 0.5,
             "free_gb": 8.0,
             "percent": 50.0,
             "swap_used_gb": 1.0,
             "swap_total_gb": 4.0,
+            "load_avg": (0.5, 0.25, 0.125),
+            "cpu_count": 4,
+            "uptime_seconds": 93780.0,
         }
 
         result = run_cli(["mem"])
         assert result == 0
+
+
+class TestMissingExtra:
+    """Tests for install hints of missing optional dependencies."""
+
+    @pytest.mark.parametrize(
+        ("module", "extra"), [("rich.console", "cli"), ("textual", "tui")]
+    )
+    def test_names_the_extra(self, module, extra):
+        """Should tell which extra installs a missing module."""
+        error = missing_extra(ModuleNotFoundError(name=module))
+        assert f"pip install 'procclean[{extra}]'" in str(error.code)
+
+    def test_reraises_unknown_modules(self):
+        """Should not hide failed imports of anything else."""
+        error = ModuleNotFoundError(name="procclean.nope")
+        with pytest.raises(ModuleNotFoundError):
+            missing_extra(error)
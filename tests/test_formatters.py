@@ -3,20 +3,43 @@
 import csv
 import io
 import json
+from dataclasses import replace
+from unittest.mock import patch
 
+import pytest
+
+from procclean.core import ErrorCode, FieldMask, ProccleanError
 from procclean.formatters import (
+    BREAKPOINTS,
+    BUILTIN_LAYOUTS,
     COLUMNS,
     DEFAULT_COLUMNS,
+    UNIT_STEPS,
+    WIDTH_STEPS,
     ClipSide,
     ColumnSpec,
+    ListDesign,
+    cell_styles,
     clip,
+    column_spec,
+    columns_fields,
+    fit_columns,
+    format_age,
     format_csv,
+    format_header,
     format_json,
+    format_jsonl,
     format_markdown,
     format_output,
     format_table,
     get_available_columns,
+    get_breakpoint,
+    get_layouts,
     get_rows,
+    highlight_rules,
+    highlight_styles,
+    layout_columns,
+    parse_columns,
 )
 
 from .conftest import (
@@ -24,10 +47,13 @@ from .conftest import (
     CLIP_WIDTH_10,
     CLIP_WIDTH_15,
     COL_COUNT_3,
+    FAKE_PID_MISSING,
     MIN_TABLE_LINES,
     NAME_MAX_WIDTH,
     TEST_PATH_SINGLE,
     TEST_PID_42,
+    WIDTH_NARROW,
+    WIDTH_WIDE,
 )
 
 
@@ -109,6 +135,36 @@ class TestColumnSpec:
         assert "[orphan]" in result
         assert "[tmux]" in result
 
+    def test_status_column_marks_deleted_cwd(self, make_process):
+        """Status column should flag processes whose cwd was removed."""
+        assert "[cwd-deleted]" in COLUMNS["status"].extract(
+            make_process(cwd_deleted=True)
+        )
+        assert "[cwd-deleted]" not in COLUMNS["status"].extract(make_process())
+
+    def test_scheduling_columns(self, make_process):
+        """Should show nice, policy and realtime priority, flagging realtime."""
+        proc = replace(make_process(), nice=-5, sched_policy="fifo", rt_priority=50)
+        assert COLUMNS["nice"].extract(proc) == "-5"
+        assert COLUMNS["sched_policy"].extract(proc) == "fifo"
+        assert COLUMNS["rt_priority"].extract(proc) == "50"
+        assert "[realtime]" in COLUMNS["status"].extract(proc)
+        assert "[realtime]" not in COLUMNS["status"].extract(make_process())
+
+    def test_privilege_column_and_badge(self, make_process):
+        """Should list privilege reasons and add a [priv] status badge."""
+        proc = replace(make_process(), privileges=["root", "setuid"])
+        assert COLUMNS["privileges"].extract(proc) == "root,setuid"
+        assert "[priv]" in COLUMNS["status"].extract(proc)
+        assert COLUMNS["privileges"].extract(make_process()) == "-"
+        assert "[priv]" not in COLUMNS["status"].extract(make_process())
+
+    def test_kernel_thread_badge(self, make_process):
+        """Should add a [kthread] status badge to kernel threads."""
+        proc = replace(make_process(), is_kernel_thread=True)
+        assert COLUMNS["status"].extract(proc).endswith("[kthread]")
+        assert "[kthread]" not in COLUMNS["status"].extract(make_process())
+
 
 class TestGetRows:
     """Tests for get_rows function."""
@@ -149,6 +205,71 @@ class TestFormatTable:
         assert "Name" in result
         # simple_outline format uses box chars
         assert "─" in result or "-" in result
+        assert "\x1b[" not in result
+
+    def test_color(self, make_process):
+        """Should wrap styled cells in ANSI escapes."""
+        system = make_process(pid=FAKE_PID_MISSING, name="zsh")
+        procs = [make_process(rss_mb=900.0), system]
+        result = format_table(procs, columns=["pid", "rss_mb"], color=True)
+        assert "\x1b[31m900.0\x1b[0m" in result
+        assert f"\x1b[2m{FAKE_PID_MISSING}\x1b[0m" in result
+        assert "\x1b[31m1234" not in result
+
+
+class TestCellStyles:
+    """Tests for the value-based color rules."""
+
+    def test_plain_process(self, make_process):
+        """Should leave an unremarkable process unstyled."""
+        styles = cell_styles(make_process(), ["pid", "rss_mb"])
+        assert styles == {"pid": "", "rss_mb": ""}
+
+    def test_row_and_cell_rules(self, make_process):
+        """Should color orphan rows and stack the memory color on its cell."""
+        proc = make_process(rss_mb=900.0, is_orphan=True)
+        styles = cell_styles(proc, ["name", "rss_mb"])
+        assert styles == {"name": "yellow", "rss_mb": "yellow red"}
+
+    def test_system_service(self, make_process):
+        """Should dim system services."""
+        styles = cell_styles(make_process(name="pipewire"), ["name"])
+        assert styles == {"name": "dim"}
+
+
+class TestHighlights:
+    """Tests for the TUI row highlights."""
+
+    def test_config_overrides(self, config_file):
+        """Should merge [highlight] over the defaults; empty disables."""
+        config_file.write_text('[highlight]\norphan = "bold yellow"\nstale = ""\n')
+        styles = highlight_styles()
+        assert styles["orphan"] == "bold yellow"
+        assert not styles["stale"]
+        assert styles["protected"] == "dim"
+        names = [rule.name for rule in highlight_rules("user", lambda _pid: False)]
+        assert "stale" not in names
+        assert names[-1] == "high-memory"
+
+    @pytest.mark.parametrize(
+        "table", ['bogus = "red"', "orphan = 3", 'orphan = "bold nonsense"']
+    )
+    def test_invalid_config(self, config_file, table):
+        """Should reject unknown highlights and invalid styles."""
+        config_file.write_text(f"[highlight]\n{table}\n")
+        with pytest.raises(ProccleanError) as exc:
+            highlight_styles()
+        assert exc.value.code == ErrorCode.INVALID_CONFIG
+
+    def test_selected_other_user(self, make_process):
+        """Should flag only selected processes of other users."""
+        mine = make_process()
+        theirs = make_process(pid=FAKE_PID_MISSING, username="root")
+        rules = highlight_rules("user", lambda _pid: True)
+        assert not cell_styles(mine, ["pid"], rules)["pid"]
+        assert cell_styles(theirs, ["pid"], rules)["pid"] == "bold red"
+        rules = highlight_rules("user", lambda _pid: False)
+        assert not cell_styles(theirs, ["pid"], rules)["pid"]
 
 
 class TestFormatMarkdown:
@@ -200,6 +321,53 @@ class TestFormatJson:
         assert "cpu_percent" in data
         assert "status" in data
 
+    def test_argv_keeps_argument_boundaries(self, make_process):
+        """Should emit argv as a list, preserving whitespace inside arguments."""
+        argv = ["grep", "-e", "two  spaces", ""]
+        proc = make_process(cmdline=" ".join(argv), argv=argv)
+        data = json.loads(format_json([proc]))[0]
+        assert data["argv"] == argv
+
+    def test_output_redacts_by_default(self, make_process):
+        """Should mask cmdline secrets in every format unless disabled."""
+        argv = ["mysql", "--password=hunter2"]
+        proc = make_process(cmdline=" ".join(argv), argv=argv)
+        for fmt in ("table", "json", "jsonl", "csv", "md"):
+            out = format_output([proc], fmt, columns=["pid", "cmdline"])
+            assert "hunter2" not in out, fmt
+        raw = json.loads(format_output([proc], "json", redact=False))[0]
+        assert raw["argv"] == argv
+        assert proc.cmdline == "mysql --password=hunter2"  # Input untouched
+
+    def test_env_only_when_captured(self, make_process):
+        """Should omit env unless --include-env captured it."""
+        proc = make_process()
+        assert "env" not in json.loads(format_json([proc]))[0]
+        proc.env = {"PORT": "3000"}
+        assert json.loads(format_json([proc]))[0]["env"] == {"PORT": "3000"}
+
+
+class TestFormatJsonl:
+    """Tests for format_jsonl function."""
+
+    def test_one_record_per_process(self, sample_processes):
+        """Should write each process as a compact record on its own line."""
+        lines = format_output(sample_processes, "jsonl").splitlines()
+        assert len(lines) == len(sample_processes)
+        assert [json.loads(line)["pid"] for line in lines] == [
+            p.pid for p in sample_processes
+        ]
+
+    def test_timestamp_and_header(self, make_process, system_info):
+        """Should stamp every record and put the header record first."""
+        stamp = "2026-01-01T00:00:00"
+        out = format_jsonl([make_process(pid=7)], stamp, header=system_info)
+        header, record = (json.loads(line) for line in out.splitlines())
+        assert header["header"]["hostname"] == "build-01"
+        assert next(iter(record)) == "timestamp"
+        assert record["pid"] == 7  # noqa: PLR2004
+        assert not format_jsonl([])
+
 
 class TestFormatCsv:
     """Tests for format_csv function."""
@@ -264,6 +432,39 @@ class TestFormatOutput:
         assert "PID" in result
 
 
+class TestHeader:
+    """Tests for the --header system information."""
+
+    def test_table(self, system_info):
+        """Should align the fields and render the uptime and load."""
+        assert format_header(system_info, "table").splitlines() == [
+            "Host:   build-01",
+            "Kernel: Linux 6.8.0",
+            "Uptime: 1d 2h 3m",
+            "Load:   0.50 0.25 0.12",
+            "Time:   2026-10-16T12:00:00+00:00",
+        ]
+
+    def test_csv_comments(self, sample_processes, system_info):
+        """Should put comment lines right above the CSV header row."""
+        lines = format_output(sample_processes, "csv", header=system_info).splitlines()
+        assert lines[0] == "# Host: build-01"
+        assert lines[5].startswith("pid,")
+
+    def test_markdown(self, sample_processes, system_info):
+        """Should list the fields above the Markdown table."""
+        report = format_output(sample_processes, "md", header=system_info)
+        assert report.startswith("- **Host:** build-01\n")
+        assert report.endswith("\n\n" + format_output(sample_processes, "md"))
+
+    def test_json(self, sample_processes, system_info):
+        """Should wrap the processes in an object with the header."""
+        data = json.loads(format_output(sample_processes, "json", header=system_info))
+        assert data["header"]["hostname"] == "build-01"
+        assert data["header"]["load_avg"] == [0.5, 0.25, 0.12]
+        assert len(data["processes"]) == len(sample_processes)
+
+
 class TestGetAvailableColumns:
     """Tests for get_available_columns function."""
 
@@ -299,3 +500,206 @@ class TestColumnsDefinitions:
     def test_name_column_has_max_width(self):
         """Name column should have max_width configured."""
         assert COLUMNS["name"].max_width == NAME_MAX_WIDTH
+
+    @pytest.mark.parametrize(
+        ("seconds", "expected"),
+        [
+            (45, "45s"),
+            (12 * 60 + 5, "12m"),
+            (4 * 3600 + 12 * 60, "4h 12m"),
+            (3 * 86400 + 4 * 3600 + 59, "3d 4h"),
+        ],
+    )
+    def test_format_age(self, seconds, expected):
+        """Should render ages in their two largest units."""
+        assert format_age(seconds) == expected
+
+    def test_age_column(self, make_process):
+        """Should show the age from create_time, or "-" when unknown."""
+        with patch("procclean.formatters.columns.time.time", return_value=7200.0):
+            assert COLUMNS["age"].extract(make_process(create_time=3600.0)) == "1h 0m"
+        assert COLUMNS["age"].extract(make_process(create_time=0.0)) == "-"
+        assert columns_fields(["age"]) == FieldMask.NONE
+
+    def test_columns_fields(self):
+        """Should collect only the optional fields the columns display."""
+        assert columns_fields(["pid", "name", "rss_mb"]) == FieldMask.NONE
+        assert columns_fields(["cwd", "nice", "bogus"]) == (
+            FieldMask.CWD | FieldMask.SCHED
+        )
+        assert FieldMask.CWD in columns_fields(["repo"])
+        assert columns_fields(["cpu_percent"]) == FieldMask.CPU
+
+    def test_descriptions(self):
+        """Should describe every column, flagging the slow ones."""
+        assert all(spec.description for spec in COLUMNS.values())
+        assert COLUMNS["cpu_percent"].slow
+        assert COLUMNS["ports"].slow
+        assert not COLUMNS["pid"].slow
+        assert COLUMNS["age"].to_dict() == {
+            "key": "age",
+            "header": "Age",
+            "type": "duration",
+            "description": "Time since the process started",
+            "slow": False,
+            "default": False,
+        }
+
+
+class TestLayouts:
+    """Tests for named column layouts."""
+
+    def test_builtin_layouts_use_known_columns(self):
+        """Every built-in layout should only reference existing columns."""
+        for name, columns in BUILTIN_LAYOUTS.items():
+            assert set(columns) <= set(COLUMNS), name
+
+    def test_config_adds_and_overrides(self, config_file):
+        """Should merge [layouts] from the config over the built-ins."""
+        config_file.write_text(
+            '[layouts]\nminimal = "pid,name"\nwide = ["pid", "cmdline"]\n'
+        )
+        layouts = get_layouts()
+        assert layouts["minimal"] == ["pid", "name"]
+        assert layouts["wide"] == ["pid", "cmdline"]
+        assert layouts["debug"] == list(BUILTIN_LAYOUTS["debug"])
+
+    def test_config_rejects_unknown_columns(self, config_file):
+        """Should report unknown columns in a configured layout."""
+        config_file.write_text('[layouts]\nbad = "pid,bogus"\n')
+        with pytest.raises(ProccleanError) as exc:
+            get_layouts()
+        assert exc.value.code == ErrorCode.INVALID_CONFIG
+        assert exc.value.context["layout"] == "bad"
+
+    def test_layout_columns(self):
+        """Should resolve names and reject unknown layouts."""
+        assert layout_columns(None) is None
+        assert layout_columns("minimal") == list(BUILTIN_LAYOUTS["minimal"])
+        with pytest.raises(ProccleanError) as exc:
+            layout_columns("nope")
+        assert exc.value.code == ErrorCode.INVALID_ARGUMENT
+        assert "debug" in exc.value.context["choices"]
+
+
+class TestColumnOptions:
+    """Tests for widths and units in --columns entries."""
+
+    def test_parse(self):
+        """Should keep entries whole and check their widths and units."""
+        assert parse_columns("pid, cwd:40,rss_mb:gb:8") == [
+            "pid",
+            "cwd:40",
+            "rss_mb:gb:8",
+        ]
+        for value in ("cwd:3", "cwd:gb", "pid:wide", "rss_mb:tb"):
+            with pytest.raises(ProccleanError) as exc:
+                parse_columns(value)
+            assert exc.value.code == ErrorCode.INVALID_COLUMN
+            assert exc.value.message.startswith(f"Invalid column '{value}'")
+        with pytest.raises(ProccleanError, match="Unknown column"):
+            parse_columns("bogus:40")
+
+    def test_width_and_unit(self, make_process):
+        """Should clip to the width and convert memory from MB."""
+        proc = make_process(rss_mb=1536.0, cwd="/home/user/projects/app")
+        cwd = column_spec("cwd:12")
+        assert (cwd.max_width, cwd.clip_side) == (12, ClipSide.LEFT)
+        assert cwd.extract(proc) == "...jects/app"
+        rss = column_spec("rss_mb:gb")
+        assert (rss.header, rss.extract(proc)) == ("RAM (GB)", "1.50")
+        assert column_spec("rss_mb:kb").extract(proc) == "1572864"
+        pss = column_spec("pss_mb:gb")
+        assert pss.extract(replace(proc, pss_mb=None)) == "-"
+        headers, rows = get_rows([proc], ["pid", "rss_mb:gb:6"])
+        assert headers == ["PID", "RAM (GB)"]
+        assert rows[0][1] == "1.50"
+        assert columns_fields(["pss_mb:gb"]) == FieldMask.SMAPS
+        assert [s.key for s in fit_columns(["cwd:12", "bogus:1"], None)] == ["cwd"]
+
+
+class TestListDesign:
+    """Tests for designing a process list interactively."""
+
+    def test_from_columns(self):
+        """Should show the given columns first, then list every other one."""
+        design = ListDesign.from_columns(["cwd:40", "rss_mb:gb"], "md")
+        assert design.entries == ["cwd:40", "rss_mb:gb"]
+        assert [c.key for c in design.columns[:3]] == ["cwd", "rss_mb", "pid"]
+        assert len(design.columns) == len(COLUMNS)
+        assert ListDesign.from_columns().entries == list(DEFAULT_COLUMNS)
+        with pytest.raises(ProccleanError):
+            ListDesign.from_columns(["cwd:1"])
+
+    def test_toggle_and_cycle(self):
+        """Should toggle, move and cycle columns, spelling them as options."""
+        design = ListDesign.from_columns(["pid", "rss_mb"])
+        assert design.command() == "procclean list --columns pid,rss_mb"
+        design.toggle(2)  # The first hidden column
+        assert design.move(2, -5) == 0
+        design.cycle_width(0)
+        assert design.cycle_unit(2)
+        assert not design.cycle_unit(1)
+        design.cycle_format()
+        assert design.entries == [f"name:{WIDTH_STEPS[1]}", "pid", "rss_mb:kb"]
+        assert design.arguments()[-2:] == ["--format", "json"]
+        for _ in UNIT_STEPS[1:]:
+            design.cycle_unit(2)
+        assert design.columns[2].entry == "rss_mb"
+        for choice in design.columns:
+            choice.shown = False
+        assert design.command() == "procclean list --format json"
+
+    def test_render(self, sample_processes):
+        """Should preview the output list prints with the same options."""
+        design = ListDesign.from_columns(["pid", "rss_mb:gb"], "md")
+        assert design.render(sample_processes) == format_output(
+            sample_processes, "md", ["pid", "rss_mb:gb"]
+        )
+        assert "RAM (GB)" in design.render(sample_processes)
+
+
+class TestBreakpoints:
+    """Tests for responsive column layouts."""
+
+    def test_no_breakpoint_without_width(self):
+        """Unconstrained output should not match any breakpoint."""
+        assert get_breakpoint(None) is None
+
+    def test_no_breakpoint_on_wide_terminal(self):
+        """Wide terminals should keep the full layout."""
+        assert get_breakpoint(WIDTH_WIDE) is None
+
+    def test_narrowest_breakpoint_wins(self):
+        """Very narrow widths should match the first (narrowest) breakpoint."""
+        assert get_breakpoint(WIDTH_NARROW) is BREAKPOINTS[0]
+
+    def test_breakpoints_ordered_narrowest_first(self):
+        """Breakpoints should be sorted by max_width ascending."""
+        widths = [bp.max_width for bp in BREAKPOINTS]
+        assert widths == sorted(widths)
+
+    def test_fit_columns_keeps_all_when_wide(self):
+        """All requested columns should be kept on wide terminals."""
+        specs = fit_columns(DEFAULT_COLUMNS, WIDTH_WIDE)
+        assert [s.key for s in specs] == list(DEFAULT_COLUMNS)
+
+    def test_fit_columns_drops_low_priority(self):
+        """Narrow terminals should drop ppid and status first."""
+        keys = [s.key for s in fit_columns(DEFAULT_COLUMNS, WIDTH_NARROW)]
+        assert "ppid" not in keys
+        assert "status" not in keys
+        assert "pid" in keys
+        assert "cwd" in keys
+
+    def test_fit_columns_shrinks_cwd(self):
+        """The cwd column should be narrowed but keep its clip side."""
+        cwd = next(s for s in fit_columns(["cwd"], WIDTH_NARROW) if s.key == "cwd")
+        assert cwd.max_width == BREAKPOINTS[0].cwd_width
+        assert cwd.clip_side == ClipSide.LEFT
+
+    def test_format_table_with_width(self, sample_processes):
+        """format_table should omit dropped column headers when narrow."""
+        result = format_table(sample_processes, width=WIDTH_NARROW)
+        assert "PPID" not in result
+        assert "PID" in result
@@ -0,0 +1,132 @@
+"""Tests for i18n module."""
+
+import re
+from pathlib import Path
+
+import pytest
+
+from procclean import i18n
+from procclean.i18n import (
+    DEFAULT_LOCALE,
+    Catalog,
+    available_locales,
+    detect_locale,
+    get_catalog,
+    parse_ftl,
+    t,
+)
+
+SRC_DIR = Path(i18n.__file__).parent
+# Message ids passed to t(), including both branches of `t("a" if x else "b")`
+MESSAGE_ID_RE = re.compile(r"(?:\bt\(|\belse)\s*[\"']([a-z][a-z0-9-]*)[\"']")
+
+
+@pytest.fixture
+def locales_dir(tmp_path, monkeypatch):
+    """Point the catalog loader at a temp dir with en and de catalogs.
+
+    Yields:
+        Path: The temporary locales directory.
+    """
+    (tmp_path / "en.ftl").write_text("hello = Hello, { $name }!\nbye = Bye\n")
+    (tmp_path / "de.ftl").write_text("hello = Hallo, { $name }!\n")
+    monkeypatch.setattr(i18n, "LOCALES_DIR", tmp_path)
+    get_catalog.cache_clear()
+    yield tmp_path
+    get_catalog.cache_clear()
+
+
+class TestParseFtl:
+    """Tests for parse_ftl function."""
+
+    def test_simple_messages(self):
+        """Should parse id = value pairs and skip comments."""
+        text = "# comment\n## Section\n\nfoo = Foo\nbar-baz = Bar { $x }\n"
+        assert parse_ftl(text) == {"foo": "Foo", "bar-baz": "Bar { $x }"}
+
+    def test_multiline_message(self):
+        """Should join indented continuation lines with newlines."""
+        text = "help =\n    Line one\n    Line two\nnext = N\n"
+        assert parse_ftl(text) == {"help": "Line one\nLine two", "next": "N"}
+
+    def test_skips_attributes(self):
+        """Should ignore message attributes."""
+        text = "button = Yes\n    .title = Confirm\n"
+        assert parse_ftl(text) == {"button": "Yes"}
+
+
+class TestCatalog:
+    """Tests for Catalog formatting."""
+
+    def test_substitutes_variables(self):
+        """Should replace placeables with argument values."""
+        catalog = Catalog("en", {"msg": "Killed { $ok }/{ $total }"})
+        assert catalog.format("msg", ok=1, total=2) == "Killed 1/2"
+
+    def test_string_literal_placeable(self):
+        """Should render string literal placeables verbatim."""
+        catalog = Catalog("en", {"msg": 'Use { "{" } braces'})
+        assert catalog.format("msg") == "Use { braces"
+
+    def test_missing_variable(self):
+        """Should leave unknown variables visible."""
+        catalog = Catalog("en", {"msg": "PID { $pid }"})
+        assert catalog.format("msg") == "PID {$pid}"
+
+    def test_fallback_and_missing_key(self):
+        """Should consult the fallback, then return the id."""
+        english = Catalog("en", {"a": "A", "b": "B"})
+        german = Catalog("de", {"a": "Ä"}, english)
+        assert german.format("a") == "Ä"
+        assert german.format("b") == "B"
+        assert german.format("nope") == "nope"
+
+
+class TestLocaleDetection:
+    """Tests for detect_locale and get_catalog."""
+
+    def test_default_is_english(self, locales_dir):
+        """Should use English for C/POSIX and unknown languages."""
+        assert detect_locale({}) == DEFAULT_LOCALE
+        assert detect_locale({"LANG": "C.UTF-8"}) == DEFAULT_LOCALE
+        assert detect_locale({"LANG": "fr_FR.UTF-8"}) == DEFAULT_LOCALE
+
+    def test_strips_region_and_encoding(self, locales_dir):
+        """Should match de_DE.UTF-8 to the de catalog."""
+        assert detect_locale({"LANG": "de_DE.UTF-8"}) == "de"
+
+    def test_precedence(self, locales_dir):
+        """Should prefer PROCCLEAN_LANG over LC_ALL and LANG."""
+        env = {"PROCCLEAN_LANG": "de", "LC_ALL": "en_US", "LANG": "en_US"}
+        assert detect_locale(env) == "de"
+        assert detect_locale({"LC_ALL": "de_AT", "LANG": "en_US"}) == "de"
+
+    def test_translation_with_fallback(self, locales_dir, monkeypatch):
+        """Should translate via the detected catalog with English fallback."""
+        monkeypatch.setenv("PROCCLEAN_LANG", "de")
+        assert t("hello", name="Welt") == "Hallo, Welt!"
+        assert t("bye") == "Bye"
+        assert available_locales() == ["de", "en"]
+
+
+class TestEnglishCatalog:
+    """Tests for the shipped English catalog."""
+
+    def test_defines_every_used_message(self):
+        """Every message id referenced in the source should exist in en.ftl."""
+        catalog = get_catalog(DEFAULT_LOCALE)
+        used = {
+            key
+            for path in SRC_DIR.rglob("*.py")
+            for key in MESSAGE_ID_RE.findall(path.read_text(encoding="utf-8"))
+        }
+        assert used
+        assert sorted(used - catalog.messages.keys()) == []
+
+    def test_kill_messages(self):
+        """Should keep the historical English kill messages."""
+        catalog = get_catalog(DEFAULT_LOCALE)
+        assert catalog.format("kill-terminated", pid=1) == "Process 1 terminated"
+        assert catalog.format("confirm-title", action="Kill", count=2) == (
+            "Kill 2 process(es)?"
+        )